@@ -18,6 +18,17 @@ pub enum ReachabilityType {
     Tests,
 }
 
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum BackendOption {
+    /// Generate goto programs for CBMC.
+    #[default]
+    #[strum(serialize = "cprover")]
+    CProver,
+    /// Generate WhyML programs for Why3.
+    Why3,
+}
+
 /// Command line arguments that this instance of the compiler run was called
 /// with. Usually stored in and accessible via [`crate::kani_queries::QueryDb`].
 #[derive(Debug, Default, Clone, clap::Parser)]
@@ -28,9 +39,16 @@ pub struct Arguments {
     /// Option name used to enable coverage checks.
     #[clap(long = "coverage-checks")]
     pub check_coverage: bool,
+    /// Option name used to report the value of every nondeterministic value to the verifier.
+    /// Kani uses it to block previous counterexamples when searching for distinct ones.
+    #[clap(long = "record-nondet")]
+    pub record_nondet: bool,
     /// Option name used to dump function pointer restrictions.
     #[clap(long = "restrict-vtable-fn-ptrs")]
     pub emit_vtable_restrictions: bool,
+    /// Option name used to dump the restrictions of plain function pointer calls.
+    #[clap(long = "restrict-fn-ptrs")]
+    pub emit_fn_ptr_restrictions: bool,
     /// Option name used to use json pretty-print for output files.
     #[clap(long = "pretty-json-files")]
     pub output_pretty_json: bool,
@@ -74,6 +92,84 @@ pub struct Arguments {
     /// Enable specific checks.
     #[clap(long)]
     pub ub_check: Vec<ExtraChecks>,
+    /// Option name used to select how `asm!` blocks without a model are handled.
+    #[clap(long, default_value = "unsupported")]
+    pub inline_asm: InlineAsm,
+    /// Option name used to select how the intrinsics that Kani doesn't support are handled.
+    #[clap(long, default_value = "unreachable")]
+    pub unknown_intrinsics: UnknownIntrinsics,
+    /// Option name used to select the backend that generates the verification model.
+    #[clap(long, default_value = "cprover")]
+    pub backend: BackendOption,
+    /// Option name used to select whether panics abort or unwind the stack.
+    #[clap(long, default_value = "abort")]
+    pub panic_strategy: PanicStrategy,
+    /// Option name used to generate one model per codegen unit, shared by its harnesses.
+    #[clap(long)]
+    pub shared_model: bool,
+    /// Option name used to write the items included in each model, and the items they use.
+    #[clap(long)]
+    pub reachability_report: bool,
+    /// Option name used to write the unsafe operations reachable from each harness.
+    #[clap(long)]
+    pub unsafe_report: bool,
+    /// Option name used to record the public functions of the crate, and whether a harness
+    /// reaches them.
+    #[clap(long)]
+    pub public_functions: bool,
+    /// Option name used to select how transcendental float intrinsics are encoded.
+    #[clap(long, default_value = "library")]
+    pub float_math: FloatMath,
+    /// Option name used to select which MIR optimizations run before codegen. `0` disables them.
+    #[clap(long, default_value = "0")]
+    pub mir_opt_level: u8,
+    /// Option name used to replace a function of a dependency by its contract, given the path
+    /// that Kani recorded when it verified the contract, e.g., `my_crate::ops::div`.
+    #[clap(long)]
+    pub assume_contract: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum InlineAsm {
+    /// Fail verification if an `asm!` block is reachable.
+    #[default]
+    Unsupported,
+    /// Assign nondeterministic values to the outputs of an `asm!` block and continue.
+    Havoc,
+}
+
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum PanicStrategy {
+    /// A panic stops the execution, as with `-C panic=abort`.
+    #[default]
+    Abort,
+    /// A panic unwinds the stack and runs the destructors, and may be caught with
+    /// `std::panic::catch_unwind`, as with `-C panic=unwind`.
+    Unwind,
+}
+
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum UnknownIntrinsics {
+    /// Abort compilation if an unsupported intrinsic is reachable.
+    Error,
+    /// Fail verification if an unsupported intrinsic is reachable.
+    #[default]
+    Unreachable,
+    /// Assign a nondeterministic value to the result of an unsupported intrinsic and continue.
+    Havoc,
+}
+
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum FloatMath {
+    /// Call the functions of the C math library, e.g., `sinf`.
+    #[default]
+    Library,
+    /// Use a nondeterministic value constrained by the range and monotonicity of the function.
+    Model,
 }
 
 #[derive(Debug, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
@@ -84,4 +180,14 @@ pub enum ExtraChecks {
     Validity,
     /// Check for using uninitialized memory.
     Uninit,
+    /// Check for reads of a union field with an invalid value because another field was written.
+    Union,
+    /// Check that pointers follow the strict provenance rules.
+    Provenance,
+    /// Check that values marked as secret don't flow into public sinks.
+    Taint,
+    /// Check that references aren't used after an aliasing access invalidated them.
+    Aliasing,
+    /// Check that branches and memory indices don't depend on values marked as secret.
+    ConstantTime,
 }
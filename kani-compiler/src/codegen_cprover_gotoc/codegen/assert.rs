@@ -59,6 +59,18 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
     CodeCoverage,
+    /// Checks added with `--check-constant-time` that a branch or a memory index doesn't depend
+    /// on a value marked with `kani::secret`.
+    ///
+    /// SPECIAL BEHAVIOR: None, the class tells their failures apart from the ones of the
+    /// assertions and safety checks, since they don't mean that the code misbehaves.
+    ConstantTime,
+    /// Checks added after each `kani::assume` with `-Z assumption-checks`, to determine whether
+    /// the assumptions up to that point can be satisfied.
+    ///
+    /// SPECIAL BEHAVIOR: Like `Cover`, "errors" for this type of assertion just mean "reachable".
+    /// These checks are not reported as properties: the driver warns about the unreachable ones.
+    AssumptionCheck,
     /// Ordinary (Rust) assertions and panics.
     ///
     /// SPECIAL BEHAVIOR: These assertion failures should be observable during normal execution of Rust code.
@@ -222,14 +234,22 @@ pub fn codegen_fatal_error(
     }
 
     /// Kani hooks function calls to `panic` and calls this intead.
-    pub fn codegen_panic(&self, span: SpanStable, fargs: Vec<Expr>) -> Stmt {
+    ///
+    /// With `--panic-strategy=unwind`, the panic starts unwinding the stack instead of ending the
+    /// execution.
+    pub fn codegen_panic(&mut self, span: SpanStable, fargs: Vec<Expr>) -> Stmt {
         // CBMC requires that the argument to the assertion must be a string constant.
         // If there is one in the MIR, use it; otherwise, explain that we can't.
         assert!(!fargs.is_empty(), "Panic requires a string message");
         let msg = self.extract_const_message(&fargs[0]).unwrap_or(String::from(
             "This is a placeholder message; Kani doesn't support message formatted at runtime",
         ));
-        self.codegen_fatal_error(PropertyClass::Assertion, &msg, span)
+        if self.is_unwind_enabled() {
+            let loc = self.codegen_caller_span_stable(span);
+            self.codegen_start_unwinding(&msg, loc)
+        } else {
+            self.codegen_fatal_error(PropertyClass::Assertion, &msg, span)
+        }
     }
 
     /// Kani does not currently support all MIR constructs.
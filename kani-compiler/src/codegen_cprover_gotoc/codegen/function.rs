@@ -5,6 +5,7 @@
 
 use crate::codegen_cprover_gotoc::codegen::block::reverse_postorder;
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::attributes::KaniAttributes;
 use cbmc::goto_program::{Expr, Stmt, Symbol};
 use cbmc::InternString;
 use stable_mir::mir::mono::Instance;
@@ -65,6 +66,12 @@ pub fn codegen_function(&mut self, instance: Instance) {
             self.print_instance(instance, &body);
             self.codegen_function_prelude(&body);
             self.codegen_declare_variables(&body);
+            if KaniAttributes::for_instance(self.tcx, instance).havoc_statics() {
+                let loc = self.codegen_span_stable(instance.def.span());
+                for stmt in self.codegen_havoc_statics(loc) {
+                    self.current_fn_mut().push_onto_block(stmt);
+                }
+            }
 
             // Get the order from internal body for now.
             reverse_postorder(&body).for_each(|bb| self.codegen_block(bb, &body.blocks[bb]));
@@ -220,7 +227,8 @@ pub fn declare_function(&mut self, instance: Instance) {
 pub mod rustc_smir {
     use crate::stable_mir::CrateDef;
     use rustc_middle::mir::coverage::CovTerm;
-    use rustc_middle::mir::coverage::MappingKind::Code;
+    use rustc_middle::mir::coverage::Mapping;
+    use rustc_middle::mir::coverage::MappingKind::{Branch, Code};
     use rustc_middle::mir::coverage::SourceRegion;
     use rustc_middle::ty::TyCtxt;
     use stable_mir::mir::mono::Instance;
@@ -239,6 +247,32 @@ pub fn region_from_coverage_opaque(
         region_from_coverage(tcx, cov_term, instance)
     }
 
+    /// Retrieves the branch regions whose true or false arm is associated with
+    /// the data in a `CoverageOpaque` object. Each region is paired with the
+    /// branch direction (`true` or `false`) that the term counts.
+    ///
+    /// Branch mappings are only generated when the crate is compiled with
+    /// `-Z coverage-options=branch`.
+    pub fn branch_regions_from_coverage_opaque(
+        tcx: TyCtxt,
+        coverage_opaque: &CoverageOpaque,
+        instance: Instance,
+    ) -> Vec<(bool, SourceRegion)> {
+        let cov_term = parse_coverage_opaque(coverage_opaque);
+        coverage_mappings(tcx, instance)
+            .into_iter()
+            .filter_map(|mapping| match mapping.kind {
+                Branch { true_term, .. } if true_term == cov_term => {
+                    Some((true, mapping.source_region))
+                }
+                Branch { false_term, .. } if false_term == cov_term => {
+                    Some((false, mapping.source_region))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Retrieves the `SourceRegion` associated with a `CovTerm` object.
     ///
     /// Note: This function could be in the internal `rustc` impl for `Coverage`.
@@ -247,22 +281,26 @@ pub fn region_from_coverage(
         coverage: CovTerm,
         instance: Instance,
     ) -> Option<SourceRegion> {
+        // Iterate over the coverage mappings and match with the coverage term.
+        // Other mappings (e.g., branches) are handled separately.
+        coverage_mappings(tcx, instance).into_iter().find_map(|mapping| match mapping.kind {
+            Code(term) if term == coverage => Some(mapping.source_region),
+            _ => None,
+        })
+    }
+
+    /// Retrieves the coverage mappings of an instance.
+    fn coverage_mappings(tcx: TyCtxt<'_>, instance: Instance) -> Vec<Mapping> {
         // We need to pull the coverage info from the internal MIR instance.
         let instance_def = rustc_smir::rustc_internal::internal(tcx, instance.def.def_id());
         let body = tcx.instance_mir(rustc_middle::ty::InstanceKind::Item(instance_def));
 
         // Some functions, like `std` ones, may not have coverage info attached
         // to them because they have been compiled without coverage flags.
-        if let Some(cov_info) = &body.function_coverage_info {
-            // Iterate over the coverage mappings and match with the coverage term.
-            for mapping in &cov_info.mappings {
-                let Code(term) = mapping.kind else { unreachable!() };
-                if term == coverage {
-                    return Some(mapping.source_region.clone());
-                }
-            }
-        }
-        None
+        body.function_coverage_info
+            .as_ref()
+            .map(|cov_info| cov_info.mappings.clone())
+            .unwrap_or_default()
     }
 
     /// Parse a `CoverageOpaque` item and return the corresponding `CovTerm`:
@@ -3,6 +3,7 @@
 //! this module handles intrinsics
 use super::typ;
 use super::{bb_label, PropertyClass};
+use crate::args::{FloatMath, UnknownIntrinsics};
 use crate::codegen_cprover_gotoc::codegen::ty_stable::pointee_type_stable;
 use crate::codegen_cprover_gotoc::{utils, GotocCtx};
 use crate::intrinsics::Intrinsic;
@@ -155,6 +156,19 @@ macro_rules! codegen_simple_intrinsic {
             }};
         }
 
+        // Transcendental float intrinsics, which are replaced by an over-approximating model with
+        // `--float-math model`.
+        macro_rules! codegen_float_math {
+            ($f:ident) => {{
+                if self.queries.args().float_math == FloatMath::Model {
+                    let intrinsic = Intrinsic::from_instance(&instance);
+                    self.codegen_float_math_model(intrinsic, fargs, place, loc)
+                } else {
+                    codegen_simple_intrinsic!($f)
+                }
+            }};
+        }
+
         // Intrinsics which encode a division operation with overflow check
         macro_rules! codegen_op_with_div_overflow_check {
             ($f:ident) => {{
@@ -348,6 +362,16 @@ macro_rules! unstable_codegen {
             Intrinsic::Bswap => {
                 self.codegen_expr_to_place_stable(place, fargs.remove(0).bswap(), loc)
             }
+            Intrinsic::CatchUnwind if self.is_unwind_enabled() => {
+                self.codegen_catch_unwind(fargs, place, loc)
+            }
+            // Without unwinding, every panic aborts, so `catch_unwind` can't be modeled.
+            Intrinsic::CatchUnwind => self.codegen_unknown_intrinsic(
+                intrinsic_str,
+                place,
+                span,
+                "https://github.com/model-checking/kani/issues/267",
+            ),
             Intrinsic::CeilF32 => codegen_simple_intrinsic!(Ceilf),
             Intrinsic::CeilF64 => codegen_simple_intrinsic!(Ceil),
             Intrinsic::CompareBytes => self.codegen_compare_bytes(fargs, place, loc),
@@ -356,8 +380,8 @@ macro_rules! unstable_codegen {
             }
             Intrinsic::CopySignF32 => codegen_simple_intrinsic!(Copysignf),
             Intrinsic::CopySignF64 => codegen_simple_intrinsic!(Copysign),
-            Intrinsic::CosF32 => codegen_simple_intrinsic!(Cosf),
-            Intrinsic::CosF64 => codegen_simple_intrinsic!(Cos),
+            Intrinsic::CosF32 => codegen_float_math!(Cosf),
+            Intrinsic::CosF64 => codegen_float_math!(Cos),
             Intrinsic::Ctlz => codegen_count_intrinsic!(ctlz, true),
             Intrinsic::CtlzNonZero => codegen_count_intrinsic!(ctlz, false),
             Intrinsic::Ctpop => self.codegen_ctpop(place, span, fargs.remove(0), farg_types[0]),
@@ -370,10 +394,10 @@ macro_rules! unstable_codegen {
                 self.codegen_expr_to_place_stable(place, e, loc)
             }
             Intrinsic::ExactDiv => self.codegen_exact_div(fargs, place, loc),
-            Intrinsic::Exp2F32 => codegen_simple_intrinsic!(Exp2f),
-            Intrinsic::Exp2F64 => codegen_simple_intrinsic!(Exp2),
-            Intrinsic::ExpF32 => codegen_simple_intrinsic!(Expf),
-            Intrinsic::ExpF64 => codegen_simple_intrinsic!(Exp),
+            Intrinsic::Exp2F32 => codegen_float_math!(Exp2f),
+            Intrinsic::Exp2F64 => codegen_float_math!(Exp2),
+            Intrinsic::ExpF32 => codegen_float_math!(Expf),
+            Intrinsic::ExpF64 => codegen_float_math!(Exp),
             Intrinsic::FabsF32 => codegen_simple_intrinsic!(Fabsf),
             Intrinsic::FabsF64 => codegen_simple_intrinsic!(Fabs),
             Intrinsic::FaddFast => {
@@ -407,12 +431,12 @@ macro_rules! unstable_codegen {
                 self.codegen_expr_to_place_stable(place, Expr::c_false(), loc)
             }
             Intrinsic::Likely => self.codegen_expr_to_place_stable(place, fargs.remove(0), loc),
-            Intrinsic::Log10F32 => codegen_simple_intrinsic!(Log10f),
-            Intrinsic::Log10F64 => codegen_simple_intrinsic!(Log10),
-            Intrinsic::Log2F32 => codegen_simple_intrinsic!(Log2f),
-            Intrinsic::Log2F64 => codegen_simple_intrinsic!(Log2),
-            Intrinsic::LogF32 => codegen_simple_intrinsic!(Logf),
-            Intrinsic::LogF64 => codegen_simple_intrinsic!(Log),
+            Intrinsic::Log10F32 => codegen_float_math!(Log10f),
+            Intrinsic::Log10F64 => codegen_float_math!(Log10),
+            Intrinsic::Log2F32 => codegen_float_math!(Log2f),
+            Intrinsic::Log2F64 => codegen_float_math!(Log2),
+            Intrinsic::LogF32 => codegen_float_math!(Logf),
+            Intrinsic::LogF64 => codegen_float_math!(Log),
             Intrinsic::MaxNumF32 => codegen_simple_intrinsic!(Fmaxf),
             Intrinsic::MaxNumF64 => codegen_simple_intrinsic!(Fmax),
             Intrinsic::MinAlignOf => codegen_intrinsic_const!(),
@@ -425,10 +449,10 @@ macro_rules! unstable_codegen {
             Intrinsic::NearbyIntF32 => codegen_simple_intrinsic!(Nearbyintf),
             Intrinsic::NearbyIntF64 => codegen_simple_intrinsic!(Nearbyint),
             Intrinsic::NeedsDrop => codegen_intrinsic_const!(),
-            Intrinsic::PowF32 => codegen_simple_intrinsic!(Powf),
-            Intrinsic::PowF64 => codegen_simple_intrinsic!(Pow),
-            Intrinsic::PowIF32 => codegen_simple_intrinsic!(Powif),
-            Intrinsic::PowIF64 => codegen_simple_intrinsic!(Powi),
+            Intrinsic::PowF32 => codegen_float_math!(Powf),
+            Intrinsic::PowF64 => codegen_float_math!(Pow),
+            Intrinsic::PowIF32 => codegen_float_math!(Powif),
+            Intrinsic::PowIF64 => codegen_float_math!(Powi),
             Intrinsic::PrefAlignOf => codegen_intrinsic_const!(),
             Intrinsic::PtrGuaranteedCmp => self.codegen_ptr_guaranteed_cmp(fargs, place, loc),
             Intrinsic::PtrOffsetFrom => self.codegen_ptr_offset_from(fargs, place, loc),
@@ -445,8 +469,8 @@ macro_rules! unstable_codegen {
             Intrinsic::RoundF64 => codegen_simple_intrinsic!(Round),
             Intrinsic::SaturatingAdd => codegen_intrinsic_binop_with_mm!(saturating_add),
             Intrinsic::SaturatingSub => codegen_intrinsic_binop_with_mm!(saturating_sub),
-            Intrinsic::SinF32 => codegen_simple_intrinsic!(Sinf),
-            Intrinsic::SinF64 => codegen_simple_intrinsic!(Sin),
+            Intrinsic::SinF32 => codegen_float_math!(Sinf),
+            Intrinsic::SinF64 => codegen_float_math!(Sin),
             Intrinsic::SimdAdd => self.codegen_simd_op_with_overflow(
                 Expr::plus,
                 Expr::add_overflow_p,
@@ -467,6 +491,7 @@ macro_rules! unstable_codegen {
             Intrinsic::SimdExtract => {
                 self.codegen_intrinsic_simd_extract(fargs, place, farg_types, ret_ty, span)
             }
+            Intrinsic::SimdGather => self.codegen_simd_gather(fargs, place, loc),
             Intrinsic::SimdGe => {
                 self.codegen_simd_cmp(Expr::vector_ge, fargs, place, span, farg_types, ret_ty)
             }
@@ -482,6 +507,8 @@ macro_rules! unstable_codegen {
             Intrinsic::SimdLt => {
                 self.codegen_simd_cmp(Expr::vector_lt, fargs, place, span, farg_types, ret_ty)
             }
+            Intrinsic::SimdMaskedLoad => self.codegen_simd_masked_load(fargs, place, loc),
+            Intrinsic::SimdMaskedStore => self.codegen_simd_masked_store(fargs, loc),
             Intrinsic::SimdMul => self.codegen_simd_op_with_overflow(
                 Expr::mul,
                 Expr::mul_overflow_p,
@@ -494,6 +521,29 @@ macro_rules! unstable_codegen {
                 self.codegen_simd_cmp(Expr::vector_neq, fargs, place, span, farg_types, ret_ty)
             }
             Intrinsic::SimdOr => codegen_intrinsic_binop!(bitor),
+            Intrinsic::SimdReduceAddOrdered | Intrinsic::SimdReduceAddUnordered => {
+                self.codegen_simd_reduce(Expr::plus, fargs, place, loc)
+            }
+            Intrinsic::SimdReduceAll => self.codegen_simd_reduce_mask(Expr::and, fargs, place, loc),
+            Intrinsic::SimdReduceAnd => self.codegen_simd_reduce(Expr::bitand, fargs, place, loc),
+            Intrinsic::SimdReduceAny => self.codegen_simd_reduce_mask(Expr::or, fargs, place, loc),
+            Intrinsic::SimdReduceMax => self.codegen_simd_reduce_min_max(true, fargs, place, loc),
+            Intrinsic::SimdReduceMin => self.codegen_simd_reduce_min_max(false, fargs, place, loc),
+            Intrinsic::SimdReduceMulOrdered | Intrinsic::SimdReduceMulUnordered => {
+                self.codegen_simd_reduce(Expr::mul, fargs, place, loc)
+            }
+            Intrinsic::SimdReduceOr => self.codegen_simd_reduce(Expr::bitor, fargs, place, loc),
+            Intrinsic::SimdReduceXor => self.codegen_simd_reduce(Expr::bitxor, fargs, place, loc),
+            Intrinsic::SimdSaturatingAdd => {
+                let mm = self.symbol_table.machine_model().clone();
+                self.codegen_simd_lanewise(|a, b| a.saturating_add(b, &mm), fargs, place, loc)
+            }
+            Intrinsic::SimdSaturatingSub => {
+                let mm = self.symbol_table.machine_model().clone();
+                self.codegen_simd_lanewise(|a, b| a.saturating_sub(b, &mm), fargs, place, loc)
+            }
+            Intrinsic::SimdScatter => self.codegen_simd_scatter(fargs, loc),
+            Intrinsic::SimdSelect => self.codegen_simd_select(fargs, place, loc),
             Intrinsic::SimdShl | Intrinsic::SimdShr => {
                 self.codegen_simd_shift_with_distance_check(fargs, intrinsic_str, place, loc)
             }
@@ -555,7 +605,51 @@ macro_rules! unstable_codegen {
             }
             // Unimplemented
             Intrinsic::Unimplemented { name, issue_link } => {
-                self.codegen_unimplemented_stmt(&name, loc, &issue_link)
+                self.codegen_unknown_intrinsic(&name, place, span, &issue_link)
+            }
+        }
+    }
+
+    /// Generate code for an intrinsic that Kani doesn't support, according to
+    /// `--unknown-intrinsics`: either abort compilation, fail verification if the intrinsic is
+    /// reachable, or assign a nondeterministic value to its result and ignore its other effects.
+    fn codegen_unknown_intrinsic(
+        &mut self,
+        name: &str,
+        place: &Place,
+        span: Span,
+        issue_link: &str,
+    ) -> Stmt {
+        let loc = self.codegen_span_stable(span);
+        match self.queries.args().unknown_intrinsics {
+            UnknownIntrinsics::Error => {
+                self.tcx.dcx().span_err(
+                    rustc_internal::internal(self.tcx, span),
+                    format!(
+                        "Kani doesn't support the intrinsic `{name}`. Model it with \
+                        `#[kani::builtin_model(\"{name}\")]`, or use \
+                        `--unknown-intrinsics unreachable` to verify the code that doesn't reach it"
+                    ),
+                );
+                self.codegen_unimplemented_stmt(name, loc, issue_link)
+            }
+            UnknownIntrinsics::Unreachable => {
+                self.codegen_unimplemented_stmt(name, loc, issue_link)
+            }
+            UnknownIntrinsics::Havoc => {
+                // Save this occurrence so we can emit a warning in the compilation report.
+                let key = format!("'{name}' intrinsic (havocked)");
+                self.unsupported_constructs.entry(key.into()).or_default().push(loc);
+                if self.place_ty_stable(place).kind().is_unit() {
+                    return Stmt::skip(loc);
+                }
+                let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
+                    self,
+                    self.codegen_place_stable(place, loc)
+                )
+                .goto_expr;
+                let typ = place_expr.typ().clone();
+                place_expr.assign(Expr::nondet(typ), loc)
             }
         }
     }
@@ -701,6 +795,104 @@ fn codegen_exact_div(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Location)
         )
     }
 
+    /// Codegen a model of a transcendental float intrinsic, which `--float-math model` uses
+    /// instead of calling the function of the C library.
+    ///
+    /// The result is a nondeterministic value constrained by the range of the function, by its
+    /// value at a few points, and by its monotonicity around them, e.g., `sin(x)` is in `[-1, 1]`
+    /// and `exp(x) >= 1` for any `x >= 0`. These hold for the exact function, and rounding
+    /// preserves them, so the model over-approximates any reasonable implementation.
+    fn codegen_float_math_model(
+        &mut self,
+        intrinsic: Intrinsic,
+        fargs: Vec<Expr>,
+        p: &Place,
+        loc: Location,
+    ) -> Stmt {
+        let x = fargs[0].clone();
+        let typ = x.typ().clone();
+        let constant = |c: f64| {
+            if typ.is_float() { Expr::float_constant(c as f32) } else { Expr::double_constant(c) }
+        };
+        let (result, decl) =
+            self.decl_temp_variable(typ.clone(), Some(Expr::nondet(typ.clone())), loc);
+        let max = if typ.is_float() { f32::MAX as f64 } else { f64::MAX };
+        let is_nan = |e: &Expr| e.clone().feq(e.clone()).not();
+        let is_finite = |e: &Expr| e.clone().ge(constant(-max)).and(e.clone().le(constant(max)));
+        let eq = |e: &Expr, c: f64| e.clone().feq(constant(c));
+        let in_range = |e: &Expr, min: f64, max: f64| {
+            e.clone().ge(constant(min)).and(e.clone().le(constant(max)))
+        };
+        let r = &result;
+        let axioms = match intrinsic {
+            Intrinsic::SinF32 | Intrinsic::SinF64 => vec![
+                is_finite(&x).not().implies(is_nan(r)),
+                is_finite(&x).implies(in_range(r, -1.0, 1.0)),
+                // `|sin(x)| <= |x|`, so `sin(x)` also has the sign of `x`.
+                is_finite(&x)
+                    .and(x.clone().ge(constant(0.0)))
+                    .implies(r.clone().ge(constant(0.0)).and(r.clone().le(x.clone()))),
+                is_finite(&x)
+                    .and(x.clone().le(constant(0.0)))
+                    .implies(r.clone().le(constant(0.0)).and(r.clone().ge(x.clone()))),
+            ],
+            Intrinsic::CosF32 | Intrinsic::CosF64 => vec![
+                is_finite(&x).not().implies(is_nan(r)),
+                is_finite(&x).implies(in_range(r, -1.0, 1.0)),
+                eq(&x, 0.0).implies(eq(r, 1.0)),
+            ],
+            Intrinsic::ExpF32 | Intrinsic::ExpF64 | Intrinsic::Exp2F32 | Intrinsic::Exp2F64 => {
+                vec![
+                    is_nan(&x).implies(is_nan(r)),
+                    eq(&x, 0.0).implies(eq(r, 1.0)),
+                    eq(&x, f64::NEG_INFINITY).implies(eq(r, 0.0)),
+                    eq(&x, f64::INFINITY).implies(eq(r, f64::INFINITY)),
+                    x.clone().ge(constant(0.0)).implies(r.clone().ge(constant(1.0))),
+                    x.clone().le(constant(0.0)).implies(in_range(r, 0.0, 1.0)),
+                ]
+            }
+            Intrinsic::LogF32
+            | Intrinsic::LogF64
+            | Intrinsic::Log2F32
+            | Intrinsic::Log2F64
+            | Intrinsic::Log10F32
+            | Intrinsic::Log10F64 => vec![
+                is_nan(&x).or(x.clone().lt(constant(0.0))).implies(is_nan(r)),
+                eq(&x, 0.0).implies(eq(r, f64::NEG_INFINITY)),
+                eq(&x, 1.0).implies(eq(r, 0.0)),
+                eq(&x, f64::INFINITY).implies(eq(r, f64::INFINITY)),
+                x.clone().ge(constant(1.0)).implies(r.clone().ge(constant(0.0))),
+                x.clone().gt(constant(0.0)).and(x.clone().le(constant(1.0))).implies(
+                    r.clone().le(constant(0.0)).and(r.clone().gt(constant(f64::NEG_INFINITY))),
+                ),
+            ],
+            Intrinsic::PowF32 | Intrinsic::PowF64 => {
+                let y = fargs[1].clone();
+                vec![
+                    eq(&y, 0.0).or(eq(&x, 1.0)).implies(eq(r, 1.0)),
+                    // A positive base gives a non-negative result for any exponent but NaN.
+                    x.clone()
+                        .gt(constant(0.0))
+                        .and(is_nan(&y).not())
+                        .implies(r.clone().ge(constant(0.0))),
+                ]
+            }
+            Intrinsic::PowIF32 | Intrinsic::PowIF64 => {
+                let y = fargs[1].clone();
+                vec![
+                    y.is_zero().or(eq(&x, 1.0)).implies(eq(r, 1.0)),
+                    x.clone().gt(constant(0.0)).implies(r.clone().ge(constant(0.0))),
+                ]
+            }
+            _ => unreachable!("no float math model for `{intrinsic:?}`"),
+        };
+        let assumptions = axioms.into_iter().map(|axiom| Stmt::assume(axiom, loc));
+        let mut stmts = vec![decl];
+        stmts.extend(assumptions);
+        stmts.push(self.codegen_expr_to_place_stable(p, result, loc));
+        Stmt::block(stmts, loc)
+    }
+
     /// Generates either a panic or no-op for `assert_*` intrinsics.
     /// These are intrinsics that statically compile to panics if the type
     /// layout is invalid so we get a message that mentions the offending type.
@@ -1403,7 +1595,7 @@ fn codegen_intrinsic_simd_extract(
         let vec = fargs.remove(0);
         let index = fargs.remove(0);
 
-        let (_, vector_base_type) = self.simd_size_and_type(rust_arg_types[0]);
+        let (len, vector_base_type) = self.simd_size_and_type(rust_arg_types[0]);
         if rust_ret_type != vector_base_type {
             let err_msg = format!(
                 "expected return type `{}` (element of input `{}`), found `{}`",
@@ -1416,7 +1608,9 @@ fn codegen_intrinsic_simd_extract(
         self.tcx.dcx().abort_if_errors();
 
         let loc = self.codegen_span_stable(span);
-        self.codegen_expr_to_place_stable(p, vec.index_array(index), loc)
+        let bounds_check = self.codegen_simd_lane_check(index.clone(), len, "simd_extract", loc);
+        let assign = self.codegen_expr_to_place_stable(p, vec.index_array(index), loc);
+        Stmt::block(vec![bounds_check, assign], loc)
     }
 
     /// Insert is a generic update of a single value in a SIMD vector.
@@ -1444,7 +1638,7 @@ fn codegen_intrinsic_simd_insert(
         let index = fargs.remove(0);
         let newval = fargs.remove(0);
 
-        let (_, vector_base_type) = self.simd_size_and_type(rust_arg_types[0]);
+        let (len, vector_base_type) = self.simd_size_and_type(rust_arg_types[0]);
         if vector_base_type != rust_arg_types[2] {
             let err_msg = format!(
                 "expected inserted type `{}` (element of input `{}`), found `{}`",
@@ -1458,9 +1652,11 @@ fn codegen_intrinsic_simd_insert(
 
         // Type checker should have ensured it's a vector type
         let elem_ty = cbmc_ret_ty.base_type().unwrap().clone();
+        let bounds_check = self.codegen_simd_lane_check(index.clone(), len, "simd_insert", loc);
         let (tmp, decl) = self.decl_temp_variable(cbmc_ret_ty, Some(vec), loc);
         Stmt::block(
             vec![
+                bounds_check,
                 decl,
                 tmp.clone().index_array(index).assign(newval.cast_to(elem_ty), loc),
                 self.codegen_expr_to_place_stable(p, tmp, loc),
@@ -1693,9 +1889,8 @@ fn codegen_simd_shift_with_distance_check(
     /// In the case of some SIMD intrinsics, the backend is responsible for
     /// performing this and similar checks, and erroring out if it proceeds.
     ///
-    /// TODO: Check that `indexes` contains constant values which are within the
-    /// expected bounds. See
-    /// <https://github.com/model-checking/kani/issues/1960> for more details.
+    /// The indexes don't need to be constants, so we also check that each of them
+    /// is within the bounds of the two input vectors.
     ///
     /// This code mimics CBMC's `shuffle_vector_exprt::lower()` here:
     /// <https://github.com/diffblue/cbmc/blob/develop/src/ansi-c/c_expr.cpp>
@@ -1744,22 +1939,208 @@ fn codegen_intrinsic_simd_shuffle(
         let st_rep = Type::ssize_t();
         let n_rep = Expr::int_constant(in_type_len, st_rep.clone());
 
+        let bound = Expr::int_constant(2 * in_type_len, st_rep.clone());
+
         // P = indexes.expanded_map(v -> if v < N then vec1[v] else vec2[v-N])
-        let elems = (0..n)
-            .map(|i| {
-                let idx = Expr::int_constant(i, st_rep.clone());
-                // Must not use `indexes.index(i)` directly, because codegen wraps arrays in struct
-                let v = self.codegen_idx_array(indexes.clone(), idx).cast_to(st_rep.clone());
-                let cond = v.clone().lt(n_rep.clone());
-                let t = vec1.clone().index(v.clone());
-                let e = vec2.clone().index(v.sub(n_rep.clone()));
-                cond.ternary(t, e)
-            })
-            .collect();
+        let mut in_bounds = Expr::bool_true();
+        let mut elems = vec![];
+        for i in 0..n {
+            let idx = Expr::int_constant(i, st_rep.clone());
+            // Must not use `indexes.index(i)` directly, because codegen wraps arrays in struct
+            let v = self.codegen_idx_array(indexes.clone(), idx).cast_to(st_rep.clone());
+            in_bounds = in_bounds.and(v.clone().lt(bound.clone()));
+            let cond = v.clone().lt(n_rep.clone());
+            let t = vec1.clone().index(v.clone());
+            let e = vec2.clone().index(v.sub(n_rep.clone()));
+            elems.push(cond.ternary(t, e));
+        }
         self.tcx.dcx().abort_if_errors();
         let cbmc_ret_ty = self.codegen_ty_stable(rust_ret_type);
         let loc = self.codegen_span_stable(span);
-        self.codegen_expr_to_place_stable(p, Expr::vector_expr(cbmc_ret_ty, elems), loc)
+        let bounds_check = self.codegen_assert_assume(
+            in_bounds,
+            PropertyClass::SafetyCheck,
+            "`simd_shuffle` index out of bounds",
+            loc,
+        );
+        let assign =
+            self.codegen_expr_to_place_stable(p, Expr::vector_expr(cbmc_ret_ty, elems), loc);
+        Stmt::block(vec![bounds_check, assign], loc)
+    }
+
+    /// Check that `index` is a valid lane of a SIMD vector with `len` lanes.
+    fn codegen_simd_lane_check(
+        &self,
+        index: Expr,
+        len: u64,
+        intrinsic: &str,
+        loc: Location,
+    ) -> Stmt {
+        let len = Expr::int_constant(len, index.typ().clone());
+        self.codegen_assert_assume(
+            index.lt(len),
+            PropertyClass::SafetyCheck,
+            format!("`{intrinsic}` index out of bounds").as_str(),
+            loc,
+        )
+    }
+
+    /// Intrinsics which apply `op` to each pair of lanes of two SIMD vectors, e.g.,
+    /// `simd_saturating_add`.
+    ///
+    /// We expand the operation lane by lane because CBMC operations like the ones used for
+    /// saturation don't accept vectors as arguments.
+    fn codegen_simd_lanewise<F: Fn(Expr, Expr) -> Expr>(
+        &mut self,
+        op: F,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        loc: Location,
+    ) -> Stmt {
+        let a = fargs.remove(0);
+        let b = fargs.remove(0);
+        let lanes = simd_lanes(&a).into_iter().zip(simd_lanes(&b)).map(|(a, b)| op(a, b));
+        let res = Expr::vector_expr(a.typ().clone(), lanes.collect());
+        self.codegen_expr_to_place_stable(p, res, loc)
+    }
+
+    /// `simd_select(mask, a, b)` picks the lanes of `a` where the corresponding lane of
+    /// `mask` is set, and the lanes of `b` otherwise.
+    fn codegen_simd_select(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Location) -> Stmt {
+        let mask = fargs.remove(0);
+        let a = fargs.remove(0);
+        let b = fargs.remove(0);
+        let lanes = simd_lanes(&mask)
+            .into_iter()
+            .zip(simd_lanes(&a).into_iter().zip(simd_lanes(&b)))
+            .map(|(m, (a, b))| lane_is_set(m).ternary(a, b));
+        let res = Expr::vector_expr(a.typ().clone(), lanes.collect());
+        self.codegen_expr_to_place_stable(p, res, loc)
+    }
+
+    /// `simd_reduce_*` folds the lanes of a vector with `op`, from the first lane to the last.
+    /// The ordered variants start from the accumulator given as second argument.
+    ///
+    /// Integer additions and multiplications wrap around, like their scalar counterparts in
+    /// `std::simd`.
+    fn codegen_simd_reduce<F: Fn(Expr, Expr) -> Expr>(
+        &mut self,
+        op: F,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        loc: Location,
+    ) -> Stmt {
+        let vec = fargs.remove(0);
+        let mut lanes = simd_lanes(&vec).into_iter();
+        let init = if fargs.is_empty() { lanes.next().unwrap() } else { fargs.remove(0) };
+        let res = lanes.fold(init, op);
+        self.codegen_expr_to_place_stable(p, res, loc)
+    }
+
+    /// `simd_reduce_all` and `simd_reduce_any` check whether all or any lanes of a mask are set.
+    fn codegen_simd_reduce_mask<F: Fn(Expr, Expr) -> Expr>(
+        &mut self,
+        op: F,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        loc: Location,
+    ) -> Stmt {
+        let mask = fargs.remove(0);
+        let res = simd_lanes(&mask).into_iter().map(lane_is_set).reduce(op).unwrap();
+        self.codegen_expr_to_place_stable(p, res.cast_to(Type::c_bool()), loc)
+    }
+
+    /// `simd_reduce_max` and `simd_reduce_min` return the largest and smallest lane of a vector.
+    /// Floating point lanes follow the semantics of `fmax` and `fmin`, i.e., `NaN` lanes are
+    /// ignored unless all lanes are `NaN`.
+    fn codegen_simd_reduce_min_max(
+        &mut self,
+        is_max: bool,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        loc: Location,
+    ) -> Stmt {
+        let vec = fargs.remove(0);
+        let elem_ty = vec.typ().base_type().unwrap().clone();
+        let res = simd_lanes(&vec)
+            .into_iter()
+            .reduce(|a, b| {
+                let builtin = match (elem_ty.is_float(), is_max) {
+                    (true, true) => Some(BuiltinFn::Fmaxf),
+                    (true, false) => Some(BuiltinFn::Fminf),
+                    _ if elem_ty.is_double() && is_max => Some(BuiltinFn::Fmax),
+                    _ if elem_ty.is_double() => Some(BuiltinFn::Fmin),
+                    _ => None,
+                };
+                match builtin {
+                    Some(builtin) => builtin.call(vec![a, b], loc),
+                    None if is_max => a.clone().ge(b.clone()).ternary(a, b),
+                    None => a.clone().le(b.clone()).ternary(a, b),
+                }
+            })
+            .unwrap();
+        self.codegen_expr_to_place_stable(p, res, loc)
+    }
+
+    /// `simd_masked_load(mask, ptr, values)` reads `*ptr.add(i)` for each lane `i` that is set
+    /// in `mask`, and takes the lane of `values` otherwise. Only the lanes that are set must be
+    /// valid for reads.
+    fn codegen_simd_masked_load(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Location) -> Stmt {
+        let mask = fargs.remove(0);
+        let ptr = fargs.remove(0);
+        let values = fargs.remove(0);
+        let lanes = simd_lanes(&mask).into_iter().zip(simd_lanes(&values)).enumerate().map(
+            |(i, (m, value))| {
+                let elem = ptr.clone().index(Expr::int_constant(i, Type::size_t()));
+                lane_is_set(m).ternary(elem, value)
+            },
+        );
+        let res = Expr::vector_expr(values.typ().clone(), lanes.collect());
+        self.codegen_expr_to_place_stable(p, res, loc)
+    }
+
+    /// `simd_masked_store(mask, ptr, values)` writes the lane `i` of `values` to `*ptr.add(i)`
+    /// for each lane `i` that is set in `mask`.
+    fn codegen_simd_masked_store(&mut self, mut fargs: Vec<Expr>, loc: Location) -> Stmt {
+        let mask = fargs.remove(0);
+        let ptr = fargs.remove(0);
+        let values = fargs.remove(0);
+        let stores = simd_lanes(&mask).into_iter().zip(simd_lanes(&values)).enumerate().map(
+            |(i, (m, value))| {
+                let elem = ptr.clone().index(Expr::int_constant(i, Type::size_t()));
+                Stmt::if_then_else(lane_is_set(m), elem.assign(value, loc), None, loc)
+            },
+        );
+        Stmt::block(stores.collect(), loc)
+    }
+
+    /// `simd_gather(values, pointers, mask)` reads `*pointers[i]` for each lane `i` that is set
+    /// in `mask`, and takes the lane of `values` otherwise.
+    fn codegen_simd_gather(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Location) -> Stmt {
+        let values = fargs.remove(0);
+        let pointers = fargs.remove(0);
+        let mask = fargs.remove(0);
+        let lanes = simd_lanes(&mask)
+            .into_iter()
+            .zip(simd_lanes(&pointers).into_iter().zip(simd_lanes(&values)))
+            .map(|(m, (ptr, value))| lane_is_set(m).ternary(ptr.dereference(), value));
+        let res = Expr::vector_expr(values.typ().clone(), lanes.collect());
+        self.codegen_expr_to_place_stable(p, res, loc)
+    }
+
+    /// `simd_scatter(values, pointers, mask)` writes the lane `i` of `values` to `*pointers[i]`
+    /// for each lane `i` that is set in `mask`, from the first lane to the last.
+    fn codegen_simd_scatter(&mut self, mut fargs: Vec<Expr>, loc: Location) -> Stmt {
+        let values = fargs.remove(0);
+        let pointers = fargs.remove(0);
+        let mask = fargs.remove(0);
+        let stores = simd_lanes(&mask)
+            .into_iter()
+            .zip(simd_lanes(&pointers).into_iter().zip(simd_lanes(&values)))
+            .map(|(m, (ptr, value))| {
+                Stmt::if_then_else(lane_is_set(m), ptr.dereference().assign(value, loc), None, loc)
+            });
+        Stmt::block(stores.collect(), loc)
     }
 
     /// A volatile load of a memory location:
@@ -1964,6 +2345,19 @@ pub fn codegen_swap(&mut self, mut fargs: Vec<Expr>, farg_types: &[Ty], loc: Loc
     }
 }
 
+/// The lanes of the SIMD vector `vec`.
+fn simd_lanes(vec: &Expr) -> Vec<Expr> {
+    (0..vec.typ().len().unwrap())
+        .map(|i| vec.clone().index_array(Expr::int_constant(i, Type::ssize_t())))
+        .collect()
+}
+
+/// Whether a lane of a SIMD mask is set. Mask lanes are either all zeros or all ones.
+fn lane_is_set(lane: Expr) -> Expr {
+    let zero = lane.typ().zero();
+    lane.neq(zero)
+}
+
 fn instance_args(instance: &Instance) -> GenericArgs {
     let TyKind::RigidTy(RigidTy::FnDef(_, args)) = instance.ty().kind() else {
         unreachable!(
@@ -15,6 +15,7 @@
 mod span;
 mod statement;
 mod static_var;
+mod unwind;
 
 // Visible for all codegen module.
 pub mod contract;
@@ -369,6 +369,7 @@ fn codegen_alloc_pointer(
         debug!(?res_t, ?alloc_id, "codegen_alloc_pointer");
         let base_addr = match GlobalAlloc::from(alloc_id) {
             GlobalAlloc::Function(instance) => {
+                self.record_address_taken(instance);
                 // We want to return the function pointer (not to be confused with function item)
                 self.codegen_func_expr(instance, loc).address_of()
             }
@@ -1,6 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::args::ExtraChecks;
 use crate::codegen_cprover_gotoc::codegen::place::ProjectedPlace;
 use crate::codegen_cprover_gotoc::codegen::ty_stable::pointee_type_stable;
 use crate::codegen_cprover_gotoc::codegen::PropertyClass;
@@ -27,7 +28,7 @@
 };
 use stable_mir::ty::{ClosureKind, IntTy, RigidTy, Size, Ty, TyConst, TyKind, UintTy, VariantIdx};
 use std::collections::BTreeMap;
-use tracing::{debug, trace, warn};
+use tracing::{debug, trace};
 
 impl<'tcx> GotocCtx<'tcx> {
     fn codegen_comparison(&mut self, op: &BinOp, e1: &Operand, e2: &Operand) -> Expr {
@@ -420,7 +421,8 @@ fn codegen_rvalue_binary_op(
                 // https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
                 // These checks may allow a wrapping-around behavior in CBMC:
                 // https://github.com/model-checking/kani/issues/1150
-                let overflow_res = ce1.clone().cast_to(Type::ssize_t()).add_overflow(offset_bytes);
+                let overflow_res =
+                    ce1.clone().cast_to(Type::ssize_t()).add_overflow(offset_bytes.clone());
                 let overflow_check = self.codegen_assert_assume(
                     overflow_res.overflowed.not(),
                     PropertyClass::ArithmeticOverflow,
@@ -428,11 +430,24 @@ fn codegen_rvalue_binary_op(
                     loc,
                 );
                 let res = ce1.clone().plus(ce2);
-                Expr::statement_expression(
-                    vec![bytes_overflow_check, overflow_check, res.as_stmt(loc)],
-                    ce1.typ().clone(),
-                    loc,
-                )
+                let mut stmts = vec![bytes_overflow_check, overflow_check];
+                if self.queries.args().ub_check.contains(&ExtraChecks::Provenance) {
+                    // The result may be one byte past the end of the allocation.
+                    let in_bounds = res.clone().same_object(ce1.clone()).and(
+                        res.clone()
+                            .pointer_offset()
+                            .cast_to(Type::size_t())
+                            .le(ce1.clone().object_size()),
+                    );
+                    stmts.push(self.codegen_assert_assume(
+                        offset_bytes.is_zero().or(in_bounds),
+                        PropertyClass::SafetyCheck,
+                        "Strict provenance: pointer offset leaves the bounds of its allocation",
+                        loc,
+                    ));
+                }
+                stmts.push(res.as_stmt(loc));
+                Expr::statement_expression(stmts, ce1.typ().clone(), loc)
             }
         }
     }
@@ -748,8 +763,13 @@ pub fn codegen_rvalue_stable(&mut self, rv: &Rvalue, loc: Location) -> Expr {
                 }
             }
             Rvalue::Len(p) => self.codegen_rvalue_len(p, loc),
-            // Rust has begun distinguishing "ptr -> num" and "num -> ptr" (providence-relevant casts) but we do not yet:
-            // Should we? Tracking ticket: https://github.com/model-checking/kani/issues/1274
+            // Rust has begun distinguishing "ptr -> num" and "num -> ptr" (providence-relevant casts) but we only do
+            // with `--ub-check=provenance`. Tracking ticket: https://github.com/model-checking/kani/issues/1274
+            Rvalue::Cast(CastKind::PointerWithExposedProvenance, e, t)
+                if self.queries.args().ub_check.contains(&ExtraChecks::Provenance) =>
+            {
+                self.codegen_exposed_provenance_cast(e, *t, loc)
+            }
             Rvalue::Cast(
                 CastKind::IntToInt
                 | CastKind::FloatToFloat
@@ -1055,6 +1075,27 @@ fn codegen_fat_ptr_to_thin_ptr_cast(&mut self, src: &Operand, dst_t: Ty) -> Expr
 
     /// This handles all kinds of casts, except a limited subset that are instead
     /// handled by [`Self::codegen_pointer_cast`].
+    /// Cast an integer into a pointer under `--ub-check=provenance`. The strict provenance rules
+    /// forbid such casts, since the pointer has to pick its provenance among the exposed ones, so
+    /// we flag every cast of a non-null address. Note that `ptr::without_provenance` is a
+    /// transmute, which is not flagged.
+    fn codegen_exposed_provenance_cast(
+        &mut self,
+        src: &Operand,
+        dst_ty: Ty,
+        loc: Location,
+    ) -> Expr {
+        let addr = self.codegen_operand_stable(src);
+        let check = self.codegen_assert(
+            addr.is_zero(),
+            PropertyClass::SafetyCheck,
+            "Strict provenance: integer-to-pointer cast creates a pointer with exposed provenance",
+            loc,
+        );
+        let cast = self.codegen_misc_cast(src, dst_ty);
+        Expr::statement_expression(vec![check, cast.clone().as_stmt(loc)], cast.typ().clone(), loc)
+    }
+
     fn codegen_misc_cast(&mut self, src: &Operand, dst_ty: Ty) -> Expr {
         let src_ty = self.operand_ty_stable(src);
         debug!(
@@ -1156,6 +1197,7 @@ fn codegen_pointer_cast(
             PointerCoercion::ReifyFnPointer => match self.operand_ty_stable(operand).kind() {
                 TyKind::RigidTy(RigidTy::FnDef(def, args)) => {
                     let instance = Instance::resolve(def, &args).unwrap();
+                    self.record_address_taken(instance);
                     // We need to handle this case in a special way because `codegen_operand_stable` compiles FnDefs to dummy structs.
                     // (cf. the function documentation)
                     self.codegen_func_expr(instance, loc).address_of()
@@ -1169,6 +1211,7 @@ fn codegen_pointer_cast(
                 {
                     let instance = Instance::resolve_closure(def, &args, ClosureKind::FnOnce)
                         .expect("failed to normalize and resolve closure during codegen");
+                    self.record_address_taken(instance);
                     self.codegen_func_expr(instance, loc).address_of()
                 } else {
                     unreachable!("{:?} cannot be cast to a fn ptr", operand)
@@ -1214,7 +1257,7 @@ fn codegen_pointer_cast(
     /// -> All the cases above where the pointer refers to a parametrized struct where the type
     /// parameter is the target of the unsize casting.
     ///     - E.g.: `RcBox<String>` to `RcBox<dyn Any>`
-    fn codegen_unsized_cast(
+    pub(super) fn codegen_unsized_cast(
         &mut self,
         src_goto_expr: Expr,
         src_mir_type: Ty,
@@ -1340,7 +1383,8 @@ fn codegen_vtable_method_field(&mut self, instance: Instance, ty: Ty, idx: usize
                 .address_of()
                 .cast_to(field_type)
         } else {
-            warn!(
+            // The method is never invoked via dynamic dispatch, so it wasn't collected.
+            debug!(
                 "Unable to find vtable symbol for virtual function {}, attempted lookup for symbol name: {}",
                 instance.name(),
                 fn_name,
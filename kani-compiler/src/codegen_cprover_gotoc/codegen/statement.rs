@@ -3,7 +3,10 @@
 use super::typ::TypeExt;
 use super::typ::FN_RETURN_VOID_VAR_NAME;
 use super::{bb_label, PropertyClass};
-use crate::codegen_cprover_gotoc::codegen::function::rustc_smir::region_from_coverage_opaque;
+use crate::args::InlineAsm;
+use crate::codegen_cprover_gotoc::codegen::function::rustc_smir::{
+    branch_regions_from_coverage_opaque, region_from_coverage_opaque,
+};
 use crate::codegen_cprover_gotoc::{GotocCtx, VtableCtx};
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
@@ -14,8 +17,9 @@
 use stable_mir::abi::{ArgAbi, FnAbi, PassMode};
 use stable_mir::mir::mono::{Instance, InstanceKind};
 use stable_mir::mir::{
-    AssertMessage, BasicBlockIdx, CopyNonOverlapping, NonDivergingIntrinsic, Operand, Place,
-    Statement, StatementKind, SwitchTargets, Terminator, TerminatorKind, RETURN_LOCAL,
+    AssertMessage, BasicBlockIdx, CopyNonOverlapping, InlineAsmOperand, NonDivergingIntrinsic,
+    Operand, Place, Statement, StatementKind, SwitchTargets, Terminator, TerminatorKind,
+    UnwindAction, RETURN_LOCAL,
 };
 use stable_mir::ty::{Abi, RigidTy, Span, Ty, TyKind, VariantIdx};
 use tracing::{debug, debug_span, trace};
@@ -165,15 +169,29 @@ pub fn codegen_statement(&mut self, stmt: &Statement) -> Stmt {
                 let counter_data = format!("{coverage_opaque:?} ${function_name}$");
                 let maybe_source_region =
                     region_from_coverage_opaque(self.tcx, &coverage_opaque, instance);
-                if let Some(source_region) = maybe_source_region {
-                    let coverage_stmt =
-                        self.codegen_coverage(&counter_data, stmt.span, source_region);
+                let mut coverage_stmts: Vec<_> = maybe_source_region
+                    .map(|source_region| {
+                        self.codegen_coverage(&counter_data, stmt.span, source_region)
+                    })
+                    .into_iter()
+                    .collect();
+                for (direction, source_region) in
+                    branch_regions_from_coverage_opaque(self.tcx, &coverage_opaque, instance)
+                {
+                    let branch_data = format!("{counter_data} branch({direction})");
+                    coverage_stmts.push(self.codegen_coverage(
+                        &branch_data,
+                        stmt.span,
+                        source_region,
+                    ));
+                }
+                if coverage_stmts.is_empty() {
+                    Stmt::skip(location)
+                } else {
                     // TODO: Avoid single-statement blocks when conversion of
                     // standalone statements to the irep format is fixed.
                     // More details in <https://github.com/model-checking/kani/issues/3012>
-                    Stmt::block(vec![coverage_stmt], location)
-                } else {
-                    Stmt::skip(location)
+                    Stmt::block(coverage_stmts, location)
                 }
             }
             StatementKind::PlaceMention(_) => todo!(),
@@ -201,6 +219,14 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
             TerminatorKind::SwitchInt { discr, targets } => {
                 self.codegen_switch_int(discr, targets, loc)
             }
+            // With `--panic-strategy=unwind`, the caller continues unwinding once the cleanup
+            // blocks of this function have run.
+            TerminatorKind::Resume if self.is_unwind_enabled() => self.codegen_return(loc),
+            TerminatorKind::Abort if self.is_unwind_enabled() => self.codegen_assert_assume_false(
+                PropertyClass::Assertion,
+                "panic in a function that cannot unwind",
+                loc,
+            ),
             // The following two use `codegen_mimic_unimplemented`
             // because we don't want to raise the warning during compilation.
             // These operations will normally be codegen'd but normally be unreachable
@@ -215,37 +241,19 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                 loc,
                 "https://github.com/model-checking/kani/issues/692",
             ),
-            TerminatorKind::Return => {
-                let rty = self.current_fn().instance_stable().fn_abi().unwrap().ret.ty;
-                if rty.kind().is_unit() {
-                    self.codegen_ret_unit(loc)
-                } else {
-                    let place = Place::from(RETURN_LOCAL);
-                    let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
-                        self,
-                        self.codegen_place_stable(&place, loc)
-                    )
-                    .goto_expr;
-                    assert_eq!(rty, self.place_ty_stable(&place), "Unexpected return type");
-                    if rty.kind().is_bool() {
-                        place_expr.cast_to(Type::c_bool()).ret(loc)
-                    } else {
-                        place_expr.ret(loc)
-                    }
-                }
-            }
+            TerminatorKind::Return => self.codegen_return(loc),
             TerminatorKind::Unreachable => self.codegen_assert_assume_false(
                 PropertyClass::Unreachable,
                 "unreachable code",
                 loc,
             ),
-            TerminatorKind::Drop { place, target, unwind: _ } => {
-                self.codegen_drop(place, target, loc)
+            TerminatorKind::Drop { place, target, unwind } => {
+                self.codegen_drop(place, target, unwind, loc)
             }
-            TerminatorKind::Call { func, args, destination, target, .. } => {
-                self.codegen_funcall(func, args, destination, target, term.span)
+            TerminatorKind::Call { func, args, destination, target, unwind } => {
+                self.codegen_funcall(func, args, destination, target, unwind, term.span)
             }
-            TerminatorKind::Assert { cond, expected, msg, target, .. } => {
+            TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
                 let cond = {
                     let r = self.codegen_operand_stable(cond);
                     if *expected { r } else { Expr::not(r) }
@@ -269,28 +277,66 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                 let (msg_str, reach_stmt) =
                     self.codegen_reachability_check(msg.to_owned(), term.span);
 
-                Stmt::block(
-                    vec![
-                        reach_stmt,
-                        self.codegen_assert_assume(
-                            cond.cast_to(Type::bool()),
-                            PropertyClass::Assertion,
-                            &msg_str,
-                            loc,
-                        ),
-                        Stmt::goto(bb_label(*target), loc),
-                    ],
-                    loc,
-                )
+                let check = if self.is_unwind_enabled() {
+                    let panic = Stmt::block(
+                        vec![
+                            self.codegen_start_unwinding(&msg_str, loc),
+                            self.codegen_unwind(unwind, loc),
+                        ],
+                        loc,
+                    );
+                    Expr::not(cond.cast_to(Type::bool())).if_then_else(panic, None, loc)
+                } else {
+                    self.codegen_assert_assume(
+                        cond.cast_to(Type::bool()),
+                        PropertyClass::Assertion,
+                        &msg_str,
+                        loc,
+                    )
+                };
+                Stmt::block(vec![reach_stmt, check, Stmt::goto(bb_label(*target), loc)], loc)
+            }
+            TerminatorKind::InlineAsm { operands, destination, .. } => {
+                if self.queries.args().inline_asm == InlineAsm::Havoc {
+                    self.codegen_inline_asm_havoc(operands, *destination, loc)
+                } else {
+                    self.codegen_unimplemented_stmt(
+                        "TerminatorKind::InlineAsm",
+                        loc,
+                        "https://github.com/model-checking/kani/issues/2",
+                    )
+                }
             }
-            TerminatorKind::InlineAsm { .. } => self.codegen_unimplemented_stmt(
-                "TerminatorKind::InlineAsm",
-                loc,
-                "https://github.com/model-checking/kani/issues/2",
-            ),
         }
     }
 
+    /// Generate code for an `asm!` block with `--inline-asm=havoc`: every output of the block
+    /// gets a nondeterministic value, and any other effect of the block is ignored.
+    ///
+    /// Blocks that never return, i.e., with the `noreturn` option, block the execution.
+    fn codegen_inline_asm_havoc(
+        &mut self,
+        operands: &[InlineAsmOperand],
+        destination: Option<BasicBlockIdx>,
+        loc: Location,
+    ) -> Stmt {
+        let mut stmts = vec![];
+        for out_place in operands.iter().filter_map(|operand| operand.out_place.as_ref()) {
+            let place = unwrap_or_return_codegen_unimplemented_stmt!(
+                self,
+                self.codegen_place_stable(out_place, loc)
+            )
+            .goto_expr;
+            let typ = place.typ().clone();
+            stmts.push(place.assign(Expr::nondet(typ), loc));
+        }
+        match destination {
+            Some(target) => stmts.push(Stmt::goto(bb_label(target), loc)),
+            None => stmts.push(Stmt::assume(Expr::bool_false(), loc)),
+        }
+        Stmt::block(stmts, loc)
+    }
+
     /// Create a statement that sets the variable discriminant to the value that corresponds to the
     /// variant index.
     pub fn codegen_set_discriminant(
@@ -380,6 +426,27 @@ fn codegen_deinit(&mut self, place: &Place, loc: Location) -> Stmt {
         }
     }
 
+    /// Generate the return of the current function, which returns the value of the return local.
+    pub(super) fn codegen_return(&mut self, loc: Location) -> Stmt {
+        let rty = self.current_fn().instance_stable().fn_abi().unwrap().ret.ty;
+        if rty.kind().is_unit() {
+            self.codegen_ret_unit(loc)
+        } else {
+            let place = Place::from(RETURN_LOCAL);
+            let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
+                self,
+                self.codegen_place_stable(&place, loc)
+            )
+            .goto_expr;
+            assert_eq!(rty, self.place_ty_stable(&place), "Unexpected return type");
+            if rty.kind().is_bool() {
+                place_expr.cast_to(Type::c_bool()).ret(loc)
+            } else {
+                place_expr.ret(loc)
+            }
+        }
+    }
+
     /// A special case handler to codegen `return ();`
     fn codegen_ret_unit(&mut self, loc: Location) -> Stmt {
         let is_file_local = false;
@@ -395,7 +462,13 @@ fn codegen_ret_unit(&mut self, loc: Location) -> Stmt {
     ///
     /// TODO: this function doesn't handle unwinding which begins if the destructor panics
     /// <https://github.com/model-checking/kani/issues/221>
-    fn codegen_drop(&mut self, place: &Place, target: &BasicBlockIdx, loc: Location) -> Stmt {
+    fn codegen_drop(
+        &mut self,
+        place: &Place,
+        target: &BasicBlockIdx,
+        unwind: &UnwindAction,
+        loc: Location,
+    ) -> Stmt {
         let place_ty = self.place_ty_stable(place);
         let drop_instance = Instance::resolve_drop_in_place(place_ty);
         debug!(?place_ty, ?drop_instance, "codegen_drop");
@@ -453,7 +526,11 @@ fn codegen_drop(&mut self, place: &Place, target: &BasicBlockIdx, loc: Location)
             ),
         };
         let goto_target = Stmt::goto(bb_label(*target), loc);
-        let block = vec![drop_implementation, goto_target];
+        let block = if self.is_unwind_enabled() {
+            vec![drop_implementation, self.codegen_unwind_check(unwind, loc), goto_target]
+        } else {
+            vec![drop_implementation, goto_target]
+        };
         Stmt::block(block, loc)
     }
 
@@ -581,6 +658,7 @@ fn codegen_funcall(
         args: &[Operand],
         destination: &Place,
         target: &Option<BasicBlockIdx>,
+        unwind: &UnwindAction,
         span: Span,
     ) -> Stmt {
         debug!(?func, ?args, ?destination, ?span, "codegen_funcall");
@@ -625,7 +703,13 @@ fn codegen_funcall(
                 };
 
                 if let Some(hk) = self.hooks.hook_applies(self.tcx, instance) {
-                    return hk.handle(self, instance, fargs, destination, *target, span);
+                    let stmt = hk.handle(self, instance, fargs, destination, *target, span);
+                    // A hooked panic starts unwinding instead of ending the execution.
+                    return if self.is_unwind_enabled() {
+                        Stmt::block(vec![stmt, self.codegen_unwind_check(unwind, loc)], loc)
+                    } else {
+                        stmt
+                    };
                 }
 
                 let mut stmts: Vec<Stmt> = match instance.kind {
@@ -654,6 +738,9 @@ fn codegen_funcall(
                         }
                     }
                 };
+                if self.is_unwind_enabled() {
+                    stmts.push(self.codegen_unwind_check(unwind, loc));
+                }
                 stmts.push(self.codegen_end_call(*target, loc));
                 Stmt::block(stmts, loc)
             }
@@ -669,14 +756,20 @@ fn codegen_funcall(
                 );
                 let fargs = self.codegen_funcall_args(&fn_ptr_abi, &args);
                 let func_expr = self.codegen_operand_stable(func).dereference();
+                let call_stmt =
+                    self.codegen_expr_to_place_stable(destination, func_expr.call(fargs), loc);
+                let call_stmt = if self.vtable_ctx.emit_fn_ptr_restrictions {
+                    self.fn_ptr_call_with_restricted_fn_ptr(fn_sig_internal, call_stmt)
+                } else {
+                    call_stmt
+                };
                 // Actually generate the function call and return.
-                Stmt::block(
-                    vec![
-                        self.codegen_expr_to_place_stable(destination, func_expr.call(fargs), loc),
-                        Stmt::goto(bb_label(target.unwrap()), loc),
-                    ],
-                    loc,
-                )
+                let mut stmts = vec![call_stmt];
+                if self.is_unwind_enabled() {
+                    stmts.push(self.codegen_unwind_check(unwind, loc));
+                }
+                stmts.push(self.codegen_end_call(*target, loc));
+                Stmt::block(stmts, loc)
             }
             x => unreachable!("Function call where the function was of unexpected type: {:?}", x),
         }
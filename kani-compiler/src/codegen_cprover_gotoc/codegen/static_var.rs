@@ -5,8 +5,14 @@
 
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::kani_middle::is_interior_mut;
+use cbmc::goto_program::{Expr, Location, Stmt};
+use rustc_hir::Mutability;
+use rustc_middle::ty::ParamEnv;
+use rustc_smir::rustc_internal;
 use stable_mir::mir::mono::{Instance, StaticDef};
+use stable_mir::ty::{RigidTy, Ty, TyKind};
 use stable_mir::CrateDef;
+use std::collections::HashSet;
 use tracing::debug;
 
 impl<'tcx> GotocCtx<'tcx> {
@@ -45,5 +51,67 @@ pub fn declare_static(&mut self, def: StaticDef) {
         self.ensure_global_var(symbol_name, false, typ, location)
             .set_is_hidden(false) // Static items are always user defined.
             .set_pretty_name(pretty_name);
+        if self.is_havoc_candidate(def) {
+            self.havoc_statics.push(def);
+        }
+    }
+
+    /// Generate the statements that assign an arbitrary value to the mutable statics, which start
+    /// the harnesses annotated with `#[kani::havoc_statics]`.
+    pub fn codegen_havoc_statics(&mut self, loc: Location) -> Vec<Stmt> {
+        self.havoc_statics
+            .clone()
+            .into_iter()
+            .map(|def| {
+                let instance = Instance::from(def);
+                let typ = self.codegen_ty_stable(instance.ty());
+                let var = self.symbol_table.lookup(instance.mangled_name()).unwrap().to_expr();
+                var.assign(Expr::nondet(typ), loc)
+            })
+            .collect()
+    }
+
+    /// Whether this is a `static mut` or a static with interior mutability of a crate under
+    /// verification, which `#[kani::havoc_statics]` havocs.
+    ///
+    /// The statics of the standard library and of Kani keep their initial value, as well as the
+    /// statics whose initialization is tracked by a `std::sync::Once`, e.g., a `OnceLock`. An
+    /// arbitrary `Once` may be in the middle of an initialization by another thread, so these
+    /// statics start uninitialized instead.
+    fn is_havoc_candidate(&self, def: StaticDef) -> bool {
+        let krate = def.krate().name;
+        if matches!(krate.as_str(), "core" | "alloc" | "std" | "kani" | "kani_core")
+            || !def.attrs_by_path(&["kanitool".into(), "recursion_tracker".into()]).is_empty()
+        {
+            return false;
+        }
+        let def_id = rustc_internal::internal(self.tcx, def.def_id());
+        let ty = rustc_internal::internal(self.tcx, def.ty());
+        let is_mutable = self.tcx.static_mutability(def_id) == Some(Mutability::Mut)
+            || !ty.is_freeze(self.tcx, ParamEnv::reveal_all());
+        is_mutable && !contains_once(def.ty(), &mut HashSet::new())
+    }
+}
+
+/// Whether the type stores a `std::sync::Once`, such as the `OnceLock` and `LazyLock` types, and
+/// the statics declared with `lazy_static!`.
+fn contains_once(ty: Ty, visited: &mut HashSet<Ty>) -> bool {
+    if !visited.insert(ty) {
+        return false;
+    }
+    match ty.kind() {
+        TyKind::RigidTy(RigidTy::Adt(def, args)) => {
+            let name = def.name();
+            (name.starts_with("std::") && name.ends_with("::Once"))
+                || def
+                    .variants_iter()
+                    .flat_map(|variant| variant.fields())
+                    .any(|field| contains_once(field.ty_with_args(&args), visited))
+        }
+        TyKind::RigidTy(RigidTy::Array(elem, _)) => contains_once(elem, visited),
+        TyKind::RigidTy(RigidTy::Tuple(fields)) => {
+            fields.into_iter().any(|field| contains_once(field, visited))
+        }
+        _ => false,
     }
 }
@@ -0,0 +1,140 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the model of the unwinding of panics, which is used with
+//! `--panic-strategy=unwind`.
+//!
+//! A panic sets a global flag, and jumps to the unwind action of the call that panicked: either a
+//! cleanup block, which runs the destructors of the current frame, or the return of the current
+//! function. Every call checks the flag once it returns, so the unwinding continues in the
+//! callers. The `catch_unwind` intrinsic clears the flag if the function it calls unwinds.
+//!
+//! A panic is reported as a failure unless a `catch_unwind` is being executed, which is tracked by
+//! a global counter.
+//!
+//! The standard library is compiled with `-C panic=abort`, so it has no cleanup blocks: its frames
+//! are unwound without running the destructors of their locals.
+
+use crate::args::PanicStrategy;
+use crate::codegen_cprover_gotoc::codegen::{bb_label, PropertyClass};
+use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::unwrap_or_return_codegen_unimplemented_stmt;
+use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use stable_mir::mir::{Mutability, Place, UnwindAction};
+use stable_mir::ty::{RigidTy, Ty, TyKind};
+
+/// The global flag that is set while a panic unwinds the stack.
+const UNWINDING_FLAG: &str = "__kani_unwinding";
+
+/// The global counter of the `catch_unwind` calls that are being executed.
+const CATCH_DEPTH: &str = "__kani_catch_depth";
+
+impl<'tcx> GotocCtx<'tcx> {
+    /// Whether panics unwind the stack, i.e., with `--panic-strategy=unwind`.
+    pub fn is_unwind_enabled(&self) -> bool {
+        self.queries.args().panic_strategy == PanicStrategy::Unwind
+    }
+
+    fn unwinding_flag(&mut self, loc: Location) -> Expr {
+        self.ensure_global_var(UNWINDING_FLAG, false, Type::bool(), loc).to_expr()
+    }
+
+    fn catch_depth(&mut self, loc: Location) -> Expr {
+        self.ensure_global_var(CATCH_DEPTH, false, Type::size_t(), loc).to_expr()
+    }
+
+    /// Start unwinding the stack because of a panic with the given message.
+    ///
+    /// The panic fails verification unless it happens inside a `catch_unwind`. This must be
+    /// followed by a jump to the unwind action of the panicking call, e.g., with
+    /// [Self::codegen_unwind_check].
+    pub fn codegen_start_unwinding(&mut self, msg: &str, loc: Location) -> Stmt {
+        let caught = self.catch_depth(loc).is_zero().not();
+        let flag = self.unwinding_flag(loc);
+        Stmt::block(
+            vec![
+                self.codegen_assert(caught, PropertyClass::Assertion, msg, loc),
+                flag.assign(Expr::bool_true(), loc),
+            ],
+            loc,
+        )
+    }
+
+    /// Continue unwinding with the given action if the call that just returned is unwinding.
+    pub fn codegen_unwind_check(&mut self, unwind: &UnwindAction, loc: Location) -> Stmt {
+        let flag = self.unwinding_flag(loc);
+        let unwind = self.codegen_unwind(unwind, loc);
+        flag.if_then_else(unwind, None, loc)
+    }
+
+    /// Jump to the given unwind action.
+    pub fn codegen_unwind(&mut self, unwind: &UnwindAction, loc: Location) -> Stmt {
+        match unwind {
+            UnwindAction::Cleanup(bb) => Stmt::goto(bb_label(*bb), loc),
+            // Calls of the functions compiled with `-C panic=abort` cannot unwind, so they have
+            // no cleanup. Unwind the current frame anyway, so a `catch_unwind` in a caller can
+            // still catch the panic.
+            UnwindAction::Continue | UnwindAction::Unreachable => self.codegen_return(loc),
+            UnwindAction::Terminate => self.codegen_assert_assume_false(
+                PropertyClass::Assertion,
+                "panic in a function that cannot unwind",
+                loc,
+            ),
+        }
+    }
+
+    /// Generate code for `catch_unwind(try_fn, data, catch_fn)`, which calls `try_fn(data)`. If it
+    /// unwinds, this stops the unwinding, calls `catch_fn(data, payload)`, and returns 1.
+    /// Otherwise, this returns 0.
+    ///
+    /// Kani doesn't track the payload of panics, so `payload` is a null pointer, and the panic
+    /// payload is built by the `PanicCleanup` hook instead.
+    pub fn codegen_catch_unwind(&mut self, fargs: Vec<Expr>, place: &Place, loc: Location) -> Stmt {
+        let [try_fn, data, catch_fn] = fargs.try_into().unwrap();
+        let flag = self.unwinding_flag(loc);
+        let depth = self.catch_depth(loc);
+        let one = Type::size_t().one();
+        let payload = data.typ().null();
+        let caught = Stmt::block(
+            vec![
+                flag.clone().assign(Expr::bool_false(), loc),
+                catch_fn.dereference().call(vec![data.clone(), payload]).as_stmt(loc),
+                self.codegen_expr_to_place_stable(place, Type::signed_int(32).one(), loc),
+            ],
+            loc,
+        );
+        let returned = self.codegen_expr_to_place_stable(place, Type::signed_int(32).zero(), loc);
+        Stmt::block(
+            vec![
+                depth.clone().assign(depth.clone().plus(one.clone()), loc),
+                try_fn.dereference().call(vec![data]).as_stmt(loc),
+                depth.clone().assign(depth.sub(one), loc),
+                flag.if_then_else(caught, Some(returned), loc),
+            ],
+            loc,
+        )
+    }
+
+    /// Generate the `Box<dyn Any + Send>` that `catch_unwind` returns as the payload of a panic.
+    ///
+    /// Kani doesn't track the value that a panic carries, so the payload is a `Box<()>`.
+    pub fn codegen_panic_payload(&mut self, place: &Place, loc: Location) -> Stmt {
+        let box_ty = self.place_ty_stable(place);
+        let TyKind::RigidTy(RigidTy::Adt(_, args)) = box_ty.kind() else {
+            unreachable!("Expected a `Box<dyn Any + Send>` payload, but found `{box_ty}`")
+        };
+        let dyn_ty = *args.0[0].expect_ty();
+        let unit_ptr_ty = Ty::new_ptr(Ty::new_tuple(&[]), Mutability::Mut);
+        // A `Box` of a zero-sized type holds a dangling pointer.
+        let unit_ptr = Type::size_t().one().cast_to(self.codegen_ty_stable(unit_ptr_ty));
+        let dyn_ptr_ty = Ty::new_ptr(dyn_ty, Mutability::Mut);
+        let dyn_ptr = self.codegen_unsized_cast(unit_ptr, unit_ptr_ty, dyn_ptr_ty, loc);
+        let payload = dyn_ptr.transmute_to(self.codegen_ty_stable(box_ty), &self.symbol_table);
+        let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
+            self,
+            self.codegen_place_stable(place, loc)
+        )
+        .goto_expr;
+        place_expr.assign(payload, loc)
+    }
+}
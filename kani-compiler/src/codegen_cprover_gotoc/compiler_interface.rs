@@ -3,26 +3,29 @@
 
 //! This file contains the code necessary to interface with the compiler backend
 
-use crate::args::ReachabilityType;
+use crate::args::{self, ReachabilityType};
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::kani_middle::analysis;
-use crate::kani_middle::attributes::{is_test_harness_description, KaniAttributes};
+use crate::kani_middle::attributes::{
+    is_proof_harness, is_test_harness_description, KaniAttributes,
+};
 use crate::kani_middle::check_reachable_items;
 use crate::kani_middle::codegen_units::{CodegenUnit, CodegenUnits};
-use crate::kani_middle::metadata::gen_test_metadata;
+use crate::kani_middle::metadata::{gen_public_functions_metadata, gen_test_metadata};
 use crate::kani_middle::provide;
 use crate::kani_middle::reachability::{
-    collect_reachable_items, filter_const_crate_items, filter_crate_items,
+    collect_reachable_items, filter_const_crate_items, filter_crate_items, reachability_report,
 };
 use crate::kani_middle::transform::{BodyTransformation, GlobalPasses};
+use crate::kani_middle::unsafe_audit::unsafe_operations_report;
 use crate::kani_queries::QueryDb;
-use cbmc::goto_program::Location;
+use cbmc::goto_program::{Location, SymbolTable};
 use cbmc::irep::goto_binary_serde::write_goto_binary_file;
 use cbmc::RoundingMode;
 use cbmc::{InternedString, MachineModel};
 use kani_metadata::artifact::convert_type;
 use kani_metadata::UnsupportedFeature;
-use kani_metadata::{ArtifactType, HarnessMetadata, KaniMetadata};
+use kani_metadata::{ArtifactType, FunctionMetadata, HarnessMetadata, KaniMetadata};
 use kani_metadata::{AssignsContract, CompilerArtifactStub};
 use rustc_codegen_ssa::back::archive::{ArArchiveBuilder, ArchiveBuilder, DEFAULT_OBJECT_READER};
 use rustc_codegen_ssa::back::metadata::create_wrapper_file;
@@ -43,11 +46,11 @@
 use rustc_session::Session;
 use rustc_smir::rustc_internal;
 use rustc_target::abi::Endian;
-use rustc_target::spec::PanicStrategy;
+use rustc_target::spec::{PanicStrategy, TargetTriple};
 use stable_mir::mir::mono::{Instance, MonoItem};
 use stable_mir::{CrateDef, DefId};
 use std::any::Any;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsString;
 use std::fmt::Write;
 use std::fs::File;
@@ -55,6 +58,7 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
 use std::time::Instant;
 use tempfile::Builder as TempFileBuilder;
 use tracing::{debug, error, info};
@@ -78,6 +82,7 @@ pub fn new(queries: Arc<Mutex<QueryDb>>) -> Self {
     /// Generate code that is reachable from the given starting points.
     ///
     /// Invariant: iff `check_contract.is_some()` then `return.2.is_some()`
+    #[allow(clippy::too_many_arguments)]
     fn codegen_items<'tcx>(
         &self,
         tcx: TyCtxt<'tcx>,
@@ -86,6 +91,7 @@ fn codegen_items<'tcx>(
         machine_model: &MachineModel,
         check_contract: Option<InternalDefId>,
         mut transformer: BodyTransformation,
+        writer: &mut ModelWriter,
     ) -> (GotocCtx<'tcx>, Vec<MonoItem>, Option<AssignsContract>) {
         // This runs reachability analysis before global passes are applied.
         //
@@ -124,10 +130,18 @@ fn codegen_items<'tcx>(
 
         // Re-collect reachable items after global transformations were applied. This is necessary
         // since global pass could add extra calls to instrumentation.
-        let (items, _) = with_timer(
+        let (items, call_graph) = with_timer(
             || collect_reachable_items(tcx, &mut transformer, starting_items),
             "codegen reachability analysis (second pass)",
         );
+        let (write_reachability, write_unsafe) = {
+            let queries = self.queries.lock().unwrap();
+            (queries.args().reachability_report, queries.args().unsafe_report)
+        };
+        let reachability = write_reachability
+            .then(|| reachability_report(starting_items, &items, &call_graph, &transformer));
+        let unsafe_operations = write_unsafe
+            .then(|| unsafe_operations_report(starting_items, &items, &call_graph, &transformer));
 
         // Follow rustc naming convention (cx is abbrev for context).
         // https://rustc-dev-guide.rust-lang.org/conventions.html#naming-conventions
@@ -197,11 +211,12 @@ fn codegen_items<'tcx>(
             BTreeMap::from_iter(gcx.type_map.iter().map(|(k, v)| (*k, v.to_string().into())));
 
         // Get the vtable function pointer restrictions if requested
-        let vtable_restrictions = if gcx.vtable_ctx.emit_vtable_restrictions {
-            Some(gcx.vtable_ctx.get_virtual_function_restrictions())
-        } else {
-            None
-        };
+        let vtable_restrictions =
+            if gcx.vtable_ctx.emit_vtable_restrictions || gcx.vtable_ctx.emit_fn_ptr_restrictions {
+                Some(gcx.vtable_ctx.get_virtual_function_restrictions())
+            } else {
+                None
+            };
 
         // No output should be generated if user selected no_codegen.
         if !tcx.sess.opts.unstable_opts.no_codegen && tcx.sess.opts.output_types.should_codegen() {
@@ -211,13 +226,24 @@ fn codegen_items<'tcx>(
                 write_file(&symtab_goto, ArtifactType::SymTab, &gcx.symbol_table, pretty);
                 symbol_table_to_gotoc(&tcx, &symtab_goto);
             } else {
-                write_goto_binary_file(symtab_goto, &gcx.symbol_table);
+                // The symbol table isn't used after this point, so we move it to the writer.
+                let symbol_table = std::mem::replace(
+                    &mut gcx.symbol_table,
+                    SymbolTable::new(machine_model.clone()),
+                );
+                writer.write(symtab_goto.to_path_buf(), symbol_table);
             }
             write_file(&symtab_goto, ArtifactType::TypeMap, &type_map, pretty);
             // If they exist, write out vtable virtual call function pointer restrictions
             if let Some(restrictions) = vtable_restrictions {
                 write_file(&symtab_goto, ArtifactType::VTableRestriction, &restrictions, pretty);
             }
+            if let Some(report) = reachability {
+                write_file(&symtab_goto, ArtifactType::Reachability, &report, pretty);
+            }
+            if let Some(report) = unsafe_operations {
+                write_file(&symtab_goto, ArtifactType::UnsafeOperations, &report, pretty);
+            }
         }
 
         (gcx, items, contract_info)
@@ -254,7 +280,7 @@ fn codegen_crate(
             // Queries shouldn't change today once codegen starts.
             let queries = self.queries.lock().unwrap().clone();
             check_target(tcx.sess);
-            check_options(tcx.sess);
+            check_options(tcx.sess, &queries);
 
             // Codegen all items that need to be processed according to the selected reachability mode:
             //
@@ -266,15 +292,53 @@ fn codegen_crate(
             let base_filename = base_filepath.as_path();
             let reachability = queries.args().reachability_analysis;
             let mut results = GotoCodegenResults::new(tcx, reachability);
+            let mut writer = ModelWriter::new();
             match reachability {
                 ReachabilityType::Harnesses => {
                     let mut units = CodegenUnits::new(&queries, tcx);
                     let mut modifies_instances = vec![];
+                    let mut assumptions = vec![];
+                    let mut model_paths = vec![];
                     // Cross-crate collecting of all items that are reachable from the crate harnesses.
-                    for unit in units.iter() {
+                    for (unit_idx, unit) in units.iter().enumerate() {
+                        // With `--shared-model`, the harnesses of a unit share one model, except
+                        // the ones that check a contract, since their model is instrumented for it.
+                        let (shared, separate): (Vec<&Instance>, Vec<&Instance>) =
+                            unit.harnesses.iter().partition(|harness| {
+                                queries.args().shared_model
+                                    && contract_metadata_for_harness(tcx, harness.def.def_id())
+                                        .is_none()
+                            });
+                        if !shared.is_empty() {
+                            let transformer = BodyTransformation::new(&queries, tcx, &unit);
+                            let model_path = units.shared_model_path(tcx, unit_idx);
+                            let roots: Vec<_> =
+                                shared.iter().map(|harness| MonoItem::Fn(**harness)).collect();
+                            let (gcx, items, _) = self.codegen_items(
+                                tcx,
+                                &roots,
+                                &model_path,
+                                &results.machine_model,
+                                None,
+                                transformer,
+                                &mut writer,
+                            );
+                            // We can't tell which harness reaches each assumption, so every
+                            // harness gets the assumptions of the whole model.
+                            let locations: BTreeSet<String> =
+                                gcx.assumptions.iter().map(|loc| loc.short_string()).collect();
+                            for harness in &shared {
+                                assumptions.push((**harness, locations.iter().cloned().collect()));
+                                model_paths.push((**harness, model_path.clone()));
+                            }
+                            // Likewise, every harness reaches the unsupported constructs of the
+                            // whole model.
+                            results.record_reaching_harnesses(&gcx, &shared);
+                            results.extend(gcx, items, None);
+                        }
                         // We reset the body cache for now because each codegen unit has different
                         // configurations that affect how we transform the instance body.
-                        for harness in &unit.harnesses {
+                        for harness in separate {
                             let transformer = BodyTransformation::new(&queries, tcx, &unit);
                             let model_path = units.harness_model_path(*harness).unwrap();
                             let contract_metadata =
@@ -286,7 +350,12 @@ fn codegen_crate(
                                 &results.machine_model,
                                 contract_metadata,
                                 transformer,
+                                &mut writer,
                             );
+                            let locations: BTreeSet<String> =
+                                gcx.assumptions.iter().map(|loc| loc.short_string()).collect();
+                            assumptions.push((*harness, locations.into_iter().collect()));
+                            results.record_reaching_harnesses(&gcx, &[harness]);
                             results.extend(gcx, items, None);
                             if let Some(assigns_contract) = contract_info {
                                 modifies_instances.push((*harness, assigns_contract));
@@ -294,6 +363,14 @@ fn codegen_crate(
                         }
                     }
                     units.store_modifies(&modifies_instances);
+                    units.store_assumptions(assumptions);
+                    units.store_model_paths(model_paths);
+                    if queries.args().public_functions {
+                        units.store_public_functions(gen_public_functions_metadata(
+                            tcx,
+                            &results.items,
+                        ));
+                    }
                     units.write_metadata(&queries, tcx);
                 }
                 ReachabilityType::Tests => {
@@ -323,10 +400,28 @@ fn codegen_crate(
                         &results.machine_model,
                         Default::default(),
                         transformer,
+                        &mut writer,
                     );
                     results.extend(gcx, items, None);
 
                     assert!(contract_info.is_none());
+                    // The model is copied for each test below.
+                    writer.wait();
+
+                    // Tests are not the only harnesses of the crate. Include the items reachable
+                    // from the proof harnesses when reporting which functions are covered.
+                    if queries.args().public_functions {
+                        let unit = CodegenUnit::default();
+                        let mut transformer = BodyTransformation::new(&queries, tcx, &unit);
+                        let proofs = filter_crate_items(tcx, |_, item| is_proof_harness(tcx, item))
+                            .into_iter()
+                            .map(MonoItem::Fn)
+                            .collect::<Vec<_>>();
+                        let (mut covered, _) =
+                            collect_reachable_items(tcx, &mut transformer, &proofs);
+                        covered.extend(results.items.iter().cloned());
+                        results.public_functions = gen_public_functions_metadata(tcx, &covered);
+                    }
 
                     for (test_fn, test_desc) in harnesses.iter().zip(descriptions.iter()) {
                         let instance =
@@ -363,6 +458,7 @@ fn codegen_crate(
                         &results.machine_model,
                         Default::default(),
                         transformer,
+                        &mut writer,
                     );
                     assert!(contract_info.is_none());
                     let _ = results.extend(gcx, items, None);
@@ -386,6 +482,7 @@ fn codegen_crate(
                     );
                 }
             }
+            writer.wait();
             codegen_results(tcx, rustc_metadata, &results.machine_model)
         });
         ret_val.unwrap()
@@ -478,16 +575,24 @@ fn check_target(session: &Session) {
     let is_x86_64_darwin_target = session.target.llvm_target.starts_with("x86_64-apple-");
     // looking for `arm64-apple-*`
     let is_arm64_darwin_target = session.target.llvm_target.starts_with("arm64-apple-");
+    // 32-bit ARM targets, e.g., `armv7-unknown-linux-gnueabihf` or `thumbv7em-none-eabihf`,
+    // which can be verified with `--target`.
+    let is_arm32_target = session.target.arch == "arm";
+    // Custom targets given with a target spec JSON, e.g., for firmware without an upstream
+    // triple, whose machine model is built from the spec.
+    let is_custom_target = matches!(session.opts.target_triple, TargetTriple::TargetJson { .. });
 
     if !is_x86_64_linux_target
         && !is_arm64_linux_target
         && !is_x86_64_darwin_target
         && !is_arm64_darwin_target
+        && !is_arm32_target
+        && !is_custom_target
     {
         let err_msg = format!(
             "Kani requires the target platform to be `x86_64-unknown-linux-gnu`, \
-            `aarch64-unknown-linux-gnu`, `x86_64-apple-*` or `arm64-apple-*`, but \
-            it is {}",
+            `aarch64-unknown-linux-gnu`, `x86_64-apple-*`, `arm64-apple-*`, a 32-bit ARM \
+            target or a custom target spec, but it is {}",
             &session.target.llvm_target
         );
         session.dcx().err(err_msg);
@@ -496,8 +601,8 @@ fn check_target(session: &Session) {
     session.dcx().abort_if_errors();
 }
 
-fn check_options(session: &Session) {
-    // The requirements for `min_global_align` and `endian` are needed to build
+fn check_options(session: &Session, queries: &QueryDb) {
+    // The requirement for `min_global_align` is needed to build
     // a valid CBMC machine model in function `machine_model_from_session` from
     // src/kani-compiler/src/codegen_cprover_gotoc/context/goto_ctx.rs
     match session.target.options.min_global_align {
@@ -511,19 +616,21 @@ fn check_options(session: &Session) {
         _ => (),
     }
 
-    if session.target.options.endian != Endian::Little {
-        session.dcx().err("Kani requires the target architecture option `endian` to be `little`.");
-    }
-
     if !session.overflow_checks() {
         session.dcx().err("Kani requires overflow checks in order to provide a sound analysis.");
     }
 
-    if session.panic_strategy() != PanicStrategy::Abort {
-        session.dcx().err(
-            "Kani can only handle abort panic strategy (-C panic=abort). See for more details \
-        https://github.com/model-checking/kani/issues/692",
-        );
+    let expected_strategy = match queries.args().panic_strategy {
+        args::PanicStrategy::Abort => PanicStrategy::Abort,
+        args::PanicStrategy::Unwind => PanicStrategy::Unwind,
+    };
+    if session.panic_strategy() != expected_strategy {
+        session.dcx().err(format!(
+            "Kani was configured with the `{}` panic strategy, but the crate uses \
+            `-C panic={}`. See for more details https://github.com/model-checking/kani/issues/692",
+            expected_strategy.desc(),
+            session.panic_strategy().desc(),
+        ));
     }
 
     session.dcx().abort_if_errors();
@@ -582,6 +689,57 @@ fn symbol_table_to_gotoc(tcx: &TyCtxt, base_path: &Path) -> PathBuf {
     output_filename
 }
 
+/// The maximum number of models written in the background. Each of them holds a whole symbol
+/// table, so more threads would use a lot of memory for large crates on machines with many cores.
+const MAX_WRITER_THREADS: usize = 2;
+
+/// Writes the goto binaries of the models in background threads, so the codegen of the next
+/// harness runs while the previous models are serialized.
+///
+/// The codegen itself can't run in parallel, since the compiler context isn't thread-safe, but the
+/// conversion of the symbol table into ireps and its serialization only use the symbol table.
+struct ModelWriter {
+    /// The threads writing a model, in the order they were started.
+    pending: Vec<JoinHandle<()>>,
+    /// The maximum number of models written at the same time. Each of them keeps its symbol table
+    /// in memory until it's written.
+    max_threads: usize,
+}
+
+impl ModelWriter {
+    fn new() -> Self {
+        let max_threads = thread::available_parallelism()
+            .map_or(1, |threads| threads.get())
+            .min(MAX_WRITER_THREADS);
+        ModelWriter { pending: vec![], max_threads }
+    }
+
+    /// Write the symbol table to `path` in the background, after waiting for the oldest model if
+    /// too many are being written.
+    fn write(&mut self, path: PathBuf, symbol_table: SymbolTable) {
+        if self.pending.len() >= self.max_threads {
+            join(self.pending.remove(0));
+        }
+        let handle = thread::Builder::new()
+            .name("kani-write-goto".into())
+            .spawn(move || write_goto_binary_file(&path, &symbol_table))
+            .unwrap();
+        self.pending.push(handle);
+    }
+
+    /// Wait until all models are written.
+    fn wait(&mut self) {
+        self.pending.drain(..).for_each(join);
+    }
+}
+
+/// Join the thread, and propagate its panic, if any.
+fn join(handle: JoinHandle<()>) {
+    if let Err(panic) = handle.join() {
+        std::panic::resume_unwind(panic);
+    }
+}
+
 pub fn write_file<T>(base_path: &Path, file_type: ArtifactType, source: &T, pretty: bool)
 where
     T: serde::Serialize,
@@ -600,7 +758,10 @@ pub fn write_file<T>(base_path: &Path, file_type: ArtifactType, source: &T, pret
 struct GotoCodegenResults {
     reachability: ReachabilityType,
     harnesses: Vec<HarnessMetadata>,
+    public_functions: Vec<FunctionMetadata>,
     unsupported_constructs: UnsupportedConstructs,
+    /// The harnesses that reach each unsupported construct.
+    reaching_harnesses: FxHashMap<InternedString, BTreeSet<String>>,
     concurrent_constructs: UnsupportedConstructs,
     items: Vec<MonoItem>,
     crate_name: InternedString,
@@ -612,7 +773,9 @@ pub fn new(tcx: TyCtxt, reachability: ReachabilityType) -> Self {
         GotoCodegenResults {
             reachability,
             harnesses: vec![],
+            public_functions: vec![],
             unsupported_constructs: UnsupportedConstructs::default(),
+            reaching_harnesses: FxHashMap::default(),
             concurrent_constructs: UnsupportedConstructs::default(),
             items: vec![],
             crate_name: tcx.crate_name(LOCAL_CRATE).as_str().into(),
@@ -640,6 +803,7 @@ pub fn generate_metadata(&self) -> KaniMetadata {
                         }
                     })
                     .collect(),
+                harnesses: self.reaching_harnesses.get(construct).cloned().unwrap_or_default(),
             })
             .collect();
         let (proofs, tests) = if self.reachability == ReachabilityType::Harnesses {
@@ -652,6 +816,7 @@ pub fn generate_metadata(&self) -> KaniMetadata {
             proof_harnesses: proofs,
             unsupported_features,
             test_harnesses: tests,
+            public_functions: self.public_functions.clone(),
         }
     }
 
@@ -664,11 +829,30 @@ fn extend(
         let mut items = items;
         self.harnesses.extend(metadata);
         self.concurrent_constructs.extend(gcx.concurrent_constructs);
-        self.unsupported_constructs.extend(gcx.unsupported_constructs);
+        // Each model may contain the same construct, so we merge their locations.
+        for (construct, locations) in gcx.unsupported_constructs {
+            let entry = self.unsupported_constructs.entry(construct).or_default();
+            for location in locations {
+                let key = location.short_string();
+                if !entry.iter().any(|known| known.short_string() == key) {
+                    entry.push(location);
+                }
+            }
+        }
         self.items.append(&mut items);
         gcx.transformer
     }
 
+    /// Record that the given harnesses reach every unsupported construct of the model in `gcx`.
+    fn record_reaching_harnesses(&mut self, gcx: &GotocCtx, harnesses: &[&Instance]) {
+        for construct in gcx.unsupported_constructs.keys() {
+            self.reaching_harnesses
+                .entry(*construct)
+                .or_default()
+                .extend(harnesses.iter().map(|harness| harness.name()));
+        }
+    }
+
     /// Prints a report at the end of the compilation.
     fn print_report(&self, tcx: TyCtxt) {
         // Print all unsupported constructs.
@@ -684,6 +868,10 @@ fn print_report(&self, tcx: TyCtxt) {
                 writeln!(&mut msg, "    - {construct} ({})", locations.len()).unwrap();
             });
             msg += "\nVerification will fail if one or more of these constructs is reachable.";
+            if unsupported.keys().any(|construct| construct.ends_with("(havocked)")) {
+                msg += "\nThe havocked intrinsics don't fail verification: their result is \
+                nondeterministic and their other effects are ignored, which may cause false proofs.";
+            }
             msg += "\nSee https://model-checking.github.io/kani/rust-feature-support.html for more \
             details.";
             tcx.dcx().warn(msg);
@@ -709,9 +897,10 @@ fn print_report(&self, tcx: TyCtxt) {
 
 /// Builds a machine model which is required by CBMC
 fn new_machine_model(sess: &Session) -> MachineModel {
-    // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`
-    // or `aarch64-apple-darwin` platform. We check the target platform in function
-    // `check_target` from src/kani-compiler/src/codegen_cprover_gotoc/compiler_interface.rs
+    // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`,
+    // `aarch64-apple-darwin` or 32-bit ARM platform, or a custom target spec. We check the target
+    // platform in function `check_target` from
+    // src/kani-compiler/src/codegen_cprover_gotoc/compiler_interface.rs
     // and error if it is not any of the ones we expect.
     let architecture = &sess.target.arch;
     let os = &sess.target.os;
@@ -719,11 +908,14 @@ fn new_machine_model(sess: &Session) -> MachineModel {
 
     // The model assumes the following values for session options:
     //   * `min_global_align`: 1
-    //   * `endian`: `Endian::Little`
     //
     // We check these options in function `check_options` from
     // src/kani-compiler/src/codegen_cprover_gotoc/compiler_interface.rs
     // and error if their values are not the ones we expect.
+    //
+    // Both endiannesses are supported: CBMC reads the endianness from the machine model when it
+    // interprets the byte-level operations of the goto program, e.g., transmutes and pointer
+    // casts, which become `byte_extract_big_endian` or `byte_extract_little_endian`.
     let alignment = sess.target.options.min_global_align.unwrap_or(1);
     let is_big_endian = match sess.target.options.endian {
         Endian::Little => false,
@@ -821,8 +1013,76 @@ fn new_machine_model(sess: &Session) -> MachineModel {
                 word_size: int_width,
             }
         }
+        "arm" => {
+            // The 32-bit ARM procedure call standard (AAPCS), also used by `arm-*-linux-*`.
+            let bool_width = 8;
+            let char_is_unsigned = true;
+            let char_width = 8;
+            let double_width = 64;
+            let float_width = 32;
+            let int_width = 32;
+            let long_double_width = 64;
+            let long_int_width = 32;
+            let long_long_int_width = 64;
+            let short_int_width = 16;
+            let single_width = 32;
+            let wchar_t_is_unsigned = true;
+            let wchar_t_width = 32;
+
+            MachineModel {
+                architecture: "arm".to_string(),
+                alignment,
+                bool_width,
+                char_is_unsigned,
+                char_width,
+                double_width,
+                float_width,
+                int_width,
+                is_big_endian,
+                long_double_width,
+                long_int_width,
+                long_long_int_width,
+                memory_operand_size: int_width / 8,
+                null_is_zero: true,
+                pointer_width,
+                rounding_mode: RoundingMode::ToNearest,
+                short_int_width,
+                single_width,
+                wchar_t_is_unsigned,
+                wchar_t_width,
+                word_size: int_width,
+            }
+        }
         _ => {
-            panic!("Unsupported architecture: {architecture}");
+            // Other architectures are only supported with a custom target spec. The width of
+            // `int` comes from the spec, and the other C types follow the data model that
+            // matches the pointer width, i.e., `long` is 32 bits wide unless pointers are 64 bits.
+            let int_width = sess.target.c_int_width.parse().unwrap();
+            let long_int_width = if pointer_width == 64 { 64 } else { 32 };
+
+            MachineModel {
+                architecture: architecture.to_string(),
+                alignment,
+                bool_width: 8,
+                char_is_unsigned: false,
+                char_width: 8,
+                double_width: 64,
+                float_width: 32,
+                int_width,
+                is_big_endian,
+                long_double_width: 64,
+                long_int_width,
+                long_long_int_width: 64,
+                memory_operand_size: int_width / 8,
+                null_is_zero: true,
+                pointer_width,
+                rounding_mode: RoundingMode::ToNearest,
+                short_int_width: 16,
+                single_width: 32,
+                wchar_t_is_unsigned: false,
+                wchar_t_width: 32,
+                word_size: int_width,
+            }
         }
     }
 }
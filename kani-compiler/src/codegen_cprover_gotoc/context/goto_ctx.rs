@@ -37,7 +37,7 @@
 use rustc_span::Span;
 use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{HasDataLayout, TargetDataLayout};
-use stable_mir::mir::mono::Instance;
+use stable_mir::mir::mono::{Instance, StaticDef};
 use stable_mir::mir::Body;
 use stable_mir::ty::Allocation;
 use std::fmt::Debug;
@@ -74,6 +74,12 @@ pub struct GotocCtx<'tcx> {
     pub concurrent_constructs: UnsupportedConstructs,
     /// The body transformation agent.
     pub transformer: BodyTransformation,
+    /// The locations of the `kani::assume` calls that were codegened, which are reported when
+    /// none of the assertions of a harness is reachable.
+    pub assumptions: Vec<Location>,
+    /// The mutable statics that are havocked at the beginning of the harnesses annotated with
+    /// `#[kani::havoc_statics]`.
+    pub havoc_statics: Vec<StaticDef>,
 }
 
 /// Constructor
@@ -84,9 +90,10 @@ pub fn new(
         machine_model: &MachineModel,
         transformer: BodyTransformation,
     ) -> GotocCtx<'tcx> {
-        let fhks = fn_hooks();
+        let fhks = fn_hooks(tcx);
         let symbol_table = SymbolTable::new(machine_model.clone());
         let emit_vtable_restrictions = queries.args().emit_vtable_restrictions;
+        let emit_fn_ptr_restrictions = queries.args().emit_fn_ptr_restrictions;
         GotocCtx {
             tcx,
             queries,
@@ -95,7 +102,7 @@ pub fn new(
             full_crate_name: full_crate_name(tcx),
             global_var_count: 0,
             alloc_map: FxHashMap::default(),
-            vtable_ctx: VtableCtx::new(emit_vtable_restrictions),
+            vtable_ctx: VtableCtx::new(emit_vtable_restrictions, emit_fn_ptr_restrictions),
             current_fn: None,
             type_map: FxHashMap::default(),
             str_literals: FxHashMap::default(),
@@ -103,6 +110,8 @@ pub fn new(
             unsupported_constructs: FxHashMap::default(),
             concurrent_constructs: FxHashMap::default(),
             transformer,
+            assumptions: vec![],
+            havoc_statics: vec![],
         }
     }
 }
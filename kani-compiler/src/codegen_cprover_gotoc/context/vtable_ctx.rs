@@ -13,14 +13,26 @@
 /// performance compared to heuristics that consider a wider set of possible
 /// function pointer targets.
 ///
+/// With `--restrict-fn-ptrs`, calls through plain function pointers are restricted likewise to
+/// the functions whose address is taken with the same signature, e.g., by a `ReifyFnPointer` cast.
+/// A call to any other function (e.g., a pointer obtained by a transmute) fails the assertion that
+/// CBMC adds to the restricted call.
+///
 /// For the current CBMC implementation of function restrictions, see:
 ///     http://cprover.diffblue.com/md__home_travis_build_diffblue_cbmc_doc_architectural_restrict-function-pointer.html
 use crate::codegen_cprover_gotoc::codegen::typ::pointee_type;
 use crate::codegen_cprover_gotoc::GotocCtx;
 use cbmc::goto_program::{Stmt, Type};
 use cbmc::InternedString;
-use kani_metadata::{CallSite, PossibleMethodEntry, TraitDefinedMethod, VtableCtxResults};
+use kani_metadata::{
+    AddressTakenEntry, CallSite, FnPtrCallSite, PossibleMethodEntry, TraitDefinedMethod,
+    VtableCtxResults,
+};
 use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::Safety;
+use rustc_middle::ty::{self, ParamEnv, PolyFnSig, Ty};
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
 use tracing::debug;
 
 /// This structure represents data about the vtable that we construct
@@ -30,24 +42,37 @@ pub struct VtableCtx {
     // Option to actually enable restrictions
     pub emit_vtable_restrictions: bool,
 
+    // Option to enable the restrictions of plain function pointer calls
+    pub emit_fn_ptr_restrictions: bool,
+
     // Map: (normalized trait name, method index) -> possible implementations
     possible_methods: FxHashMap<TraitDefinedMethod, Vec<InternedString>>,
 
     // All sites where a virtual call takes place
     call_sites: Vec<CallSite>,
 
+    // Map: normalized function pointer type -> functions whose address is taken with that type
+    address_taken: FxHashMap<InternedString, Vec<InternedString>>,
+
+    // All sites where a function pointer call takes place
+    fn_ptr_call_sites: Vec<FnPtrCallSite>,
+
     // Internal tracing of index needed for call site wrappers
     call_site_global_idx: usize,
 }
 
 /// Constructor
 impl VtableCtx {
-    pub fn new(emit_vtable_restrictions: bool) -> Self {
+    pub fn new(emit_vtable_restrictions: bool, emit_fn_ptr_restrictions: bool) -> Self {
         debug!("Restricting vtable function pointers? {:?}", emit_vtable_restrictions);
+        debug!("Restricting plain function pointers? {:?}", emit_fn_ptr_restrictions);
         Self {
             emit_vtable_restrictions,
+            emit_fn_ptr_restrictions,
             possible_methods: FxHashMap::default(),
             call_sites: Vec::new(),
+            address_taken: FxHashMap::default(),
+            fn_ptr_call_sites: Vec::new(),
             call_site_global_idx: 0,
         }
     }
@@ -72,6 +97,15 @@ pub fn add_possible_method(
         }
     }
 
+    /// Add a function whose address is taken as a function pointer of the given signature.
+    pub fn add_address_taken(&mut self, signature: InternedString, function: InternedString) {
+        assert!(self.emit_fn_ptr_restrictions);
+        let functions = self.address_taken.entry(signature).or_default();
+        if !functions.contains(&function) {
+            functions.push(function);
+        }
+    }
+
     /// The vtable index for drop
     pub fn drop_index() -> usize {
         rustc_middle::ty::COMMON_VTABLE_ENTRIES_DROPINPLACE
@@ -81,7 +115,7 @@ pub fn drop_index() -> usize {
 /// Internal tracking helpers
 impl VtableCtx {
     fn get_call_site_global_idx(&mut self) -> usize {
-        assert!(self.emit_vtable_restrictions);
+        assert!(self.emit_vtable_restrictions || self.emit_fn_ptr_restrictions);
         self.call_site_global_idx += 1;
         self.call_site_global_idx
     }
@@ -132,14 +166,61 @@ pub fn virtual_call_with_restricted_fn_ptr(
         );
         body.with_label(label)
     }
+
+    /// Create a label to the function pointer call site, whose pointer has the given signature.
+    pub fn fn_ptr_call_with_restricted_fn_ptr(
+        &mut self,
+        fn_sig: PolyFnSig<'tcx>,
+        body: Stmt,
+    ) -> Stmt {
+        assert!(self.vtable_ctx.emit_fn_ptr_restrictions);
+
+        let label: InternedString =
+            format!("restricted_call_label_{}", self.vtable_ctx.get_call_site_global_idx()).into();
+        let site = FnPtrCallSite {
+            signature: self.fn_ptr_signature(fn_sig),
+            function_name: self.current_fn().name().into(),
+            label,
+        };
+        self.vtable_ctx.fn_ptr_call_sites.push(site);
+        body.with_label(label)
+    }
+
+    /// Record that the address of `instance` is taken, so it's a possible target of the function
+    /// pointer calls with the same signature.
+    pub fn record_address_taken(&mut self, instance: Instance) {
+        if !self.vtable_ctx.emit_fn_ptr_restrictions {
+            return;
+        }
+        let ty = rustc_internal::internal(self.tcx, instance.ty());
+        let fn_sig = match ty.kind() {
+            // A closure is cast to a function pointer without its (empty) environment.
+            ty::Closure(_, args) => {
+                self.tcx.signature_unclosure(args.as_closure().sig(), Safety::Safe)
+            }
+            _ => ty.fn_sig(self.tcx),
+        };
+        let signature = self.fn_ptr_signature(fn_sig);
+        self.vtable_ctx.add_address_taken(signature, instance.mangled_name().into());
+    }
+
+    /// The canonical name of the function pointer type with the given signature. Lifetimes and
+    /// safety are ignored, since they don't change which functions can be called, and a safe
+    /// function pointer can be cast to an unsafe one.
+    fn fn_ptr_signature(&self, fn_sig: PolyFnSig<'tcx>) -> InternedString {
+        let fn_sig = self.tcx.normalize_erasing_late_bound_regions(ParamEnv::reveal_all(), fn_sig);
+        let fn_sig = ty::FnSig { safety: Safety::Unsafe, ..fn_sig };
+        self.ty_mangled_name(Ty::new_fn_ptr(self.tcx, ty::Binder::dummy(fn_sig)))
+    }
 }
 
 /// Write out information per crate. We need to later aggregate the information
 /// for the final combined executable (virtual calls can be across dependencies).
 impl VtableCtx {
-    /// Write out (1) all call sites and (2) possible concrete methods to JSON.
+    /// Write out (1) all call sites, (2) possible concrete methods, (3) all function pointer call
+    /// sites and (4) the functions whose address is taken to JSON.
     pub fn get_virtual_function_restrictions(&mut self) -> VtableCtxResults {
-        assert!(self.emit_vtable_restrictions);
+        assert!(self.emit_vtable_restrictions || self.emit_fn_ptr_restrictions);
 
         VtableCtxResults {
             call_sites: self.call_sites.clone(),
@@ -148,6 +229,12 @@ pub fn get_virtual_function_restrictions(&mut self) -> VtableCtxResults {
                 .drain()
                 .map(|(k, v)| PossibleMethodEntry { trait_method: k, possibilities: v })
                 .collect(),
+            fn_ptr_call_sites: self.fn_ptr_call_sites.clone(),
+            address_taken: self
+                .address_taken
+                .drain()
+                .map(|(signature, functions)| AddressTakenEntry { signature, functions })
+                .collect(),
         }
     }
 }
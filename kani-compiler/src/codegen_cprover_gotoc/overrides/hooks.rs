@@ -11,8 +11,9 @@
 use crate::codegen_cprover_gotoc::codegen::{bb_label, PropertyClass};
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::kani_middle::attributes::matches_diagnostic as matches_function;
+use crate::kani_middle::transform::has_builtin_model;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
-use cbmc::goto_program::{BuiltinFn, Expr, Stmt, Type};
+use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Symbol, Type};
 use rustc_middle::ty::TyCtxt;
 use rustc_smir::rustc_internal;
 use stable_mir::mir::mono::Instance;
@@ -98,8 +99,20 @@ fn handle(
         let cond = fargs.remove(0).cast_to(Type::bool());
         let target = target.unwrap();
         let loc = gcx.codegen_span_stable(span);
-
-        Stmt::block(vec![gcx.codegen_assume(cond, loc), Stmt::goto(bb_label(target), loc)], loc)
+        gcx.assumptions.push(loc);
+
+        let mut stmts = vec![gcx.codegen_assume(cond, loc)];
+        if gcx.queries.args().unstable_features.contains(&"assumption-checks".to_string()) {
+            // This check fails if any execution satisfies the assumptions up to this point.
+            stmts.push(gcx.codegen_assert(
+                Expr::bool_false(),
+                PropertyClass::AssumptionCheck,
+                "assumption is satisfiable",
+                loc,
+            ));
+        }
+        stmts.push(Stmt::goto(bb_label(target), loc));
+        Stmt::block(stmts, loc)
     }
 }
 
@@ -178,6 +191,42 @@ fn handle(
     }
 }
 
+/// The checks added by `--check-constant-time`, which are reported with their own property class.
+struct ConstantTimeCheck;
+impl GotocHook for ConstantTimeCheck {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance.def, "KaniConstantTimeCheck")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        mut fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let cond = fargs.remove(0).cast_to(Type::bool());
+        let msg = fargs.remove(0);
+        let msg = gcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = gcx.codegen_caller_span_stable(span);
+
+        let (msg, reach_stmt) = gcx.codegen_reachability_check(msg, span);
+
+        Stmt::block(
+            vec![
+                reach_stmt,
+                gcx.codegen_assert(cond, PropertyClass::ConstantTime, &msg, caller_loc),
+                Stmt::goto(bb_label(target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
 struct Nondet;
 
 impl GotocHook for Nondet {
@@ -206,17 +255,36 @@ fn handle(
                 gcx.codegen_place_stable(assign_to, loc)
             )
             .goto_expr;
-            Stmt::block(
-                vec![
-                    pe.assign(gcx.codegen_ty_stable(pt).nondet(), loc),
-                    Stmt::goto(bb_label(target), loc),
-                ],
-                loc,
-            )
+            let typ = gcx.codegen_ty_stable(pt);
+            let mut stmts = vec![pe.clone().assign(typ.nondet(), loc)];
+            if gcx.queries.args().record_nondet {
+                stmts.push(record_nondet(gcx, pe, &typ, loc));
+            }
+            stmts.push(Stmt::goto(bb_label(target), loc));
+            Stmt::block(stmts, loc)
         }
     }
 }
 
+/// The function called with every nondeterministic value when `--record-nondet` is enabled.
+/// Its definition is provided by the driver, which links it with the goto binary.
+const RECORD_NONDET_FN: &str = "__kani_record_nondet";
+
+/// Generate a call `__kani_record_nondet(&value, sizeof(value))`.
+fn record_nondet(gcx: &mut GotocCtx, value: Expr, typ: &Type, loc: Location) -> Stmt {
+    let size = typ.sizeof_expr(&gcx.symbol_table);
+    let func = gcx
+        .ensure(RECORD_NONDET_FN, |_, name| {
+            Symbol::builtin_function(
+                name,
+                vec![Type::void_pointer(), Type::size_t()],
+                Type::empty(),
+            )
+        })
+        .to_expr();
+    func.call(vec![value.address_of().cast_to(Type::void_pointer()), size]).as_stmt(loc)
+}
+
 struct Panic;
 
 impl GotocHook for Panic {
@@ -242,6 +310,38 @@ fn handle(
     }
 }
 
+/// The function of the standard library that retrieves the payload of a panic caught by
+/// `catch_unwind`. Kani doesn't track the payload, so we return a placeholder instead, see
+/// [GotocCtx::codegen_panic_payload].
+///
+/// The original function also decrements the panic count, which Kani's panic hook never increments.
+struct PanicCleanup;
+
+impl GotocHook for PanicCleanup {
+    fn hook_applies(&self, _tcx: TyCtxt, instance: Instance) -> bool {
+        instance.name() == "std::panicking::r#try::cleanup"
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        _fargs: Vec<Expr>,
+        assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        let loc = gcx.codegen_span_stable(span);
+        Stmt::block(
+            vec![
+                gcx.codegen_panic_payload(assign_to, loc),
+                Stmt::goto(bb_label(target.unwrap()), loc),
+            ],
+            loc,
+        )
+    }
+}
+
 /// Encodes __CPROVER_r_ok(ptr, size)
 struct IsAllocated;
 impl GotocHook for IsAllocated {
@@ -454,6 +554,58 @@ fn handle(
     }
 }
 
+/// The helpers of the standard library that implement 128-bit arithmetic in software, using
+/// 64-bit halves, e.g., to format a `u128`. CBMC supports wider bit-vectors natively, so we encode
+/// these helpers directly, instead of unrolling their software implementation.
+///
+/// - `u128_mulhi(x, y)` returns the high 128 bits of the 256-bit product `x * y`.
+/// - `udiv_1e19(n)` returns the quotient and the remainder of `n / 10^19`.
+struct Wide128Helpers;
+
+impl GotocHook for Wide128Helpers {
+    fn hook_applies(&self, _tcx: TyCtxt, instance: Instance) -> bool {
+        let name = instance.name();
+        name == "core::fmt::num::u128_mulhi" || name == "core::fmt::num::udiv_1e19"
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        instance: Instance,
+        fargs: Vec<Expr>,
+        assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        let loc = gcx.codegen_span_stable(span);
+        let place_expr = unwrap_or_return_codegen_unimplemented_stmt!(
+            gcx,
+            gcx.codegen_place_stable(assign_to, loc)
+        )
+        .goto_expr;
+        let code = if instance.name().ends_with("u128_mulhi") {
+            let [x, y] = fargs.try_into().unwrap();
+            let wide = Type::unsigned_int(256);
+            let product = x.cast_to(wide.clone()).mul(y.cast_to(wide.clone()));
+            let high = product.lshr(Expr::int_constant(128, wide));
+            vec![place_expr.assign(high.cast_to(Type::unsigned_int(128)), loc)]
+        } else {
+            let [n] = fargs.try_into().unwrap();
+            let (n_var, n_decl) = gcx.decl_temp_variable(n.typ().clone(), Some(n), loc);
+            let div = Expr::int_constant(10_000_000_000_000_000_000u64, n_var.typ().clone());
+            let quot = place_expr.clone().member(GotocCtx::tuple_fld_name(0), &gcx.symbol_table);
+            let rem = place_expr.member(GotocCtx::tuple_fld_name(1), &gcx.symbol_table);
+            let rem_typ = rem.typ().clone();
+            vec![
+                n_decl,
+                quot.assign(n_var.clone().div(div.clone()), loc),
+                rem.assign(n_var.rem(div).cast_to(rem_typ), loc),
+            ]
+        };
+        Stmt::block([code, vec![Stmt::goto(bb_label(target.unwrap()), loc)]].concat(), loc)
+    }
+}
+
 /// A builtin that is essentially a C-style dereference operation, creating an
 /// unsafe shallow copy. Importantly either this copy or the original needs to
 /// be `mem::forget`en or a double-free will occur.
@@ -539,24 +691,29 @@ fn handle(
     }
 }
 
-pub fn fn_hooks() -> GotocHooks {
-    GotocHooks {
-        hooks: vec![
-            Rc::new(Panic),
-            Rc::new(Assume),
-            Rc::new(Assert),
-            Rc::new(Check),
-            Rc::new(Cover),
-            Rc::new(Nondet),
-            Rc::new(IsAllocated),
-            Rc::new(PointerObject),
-            Rc::new(PointerOffset),
-            Rc::new(RustAlloc),
-            Rc::new(MemCmp),
-            Rc::new(UntrackedDeref),
-            Rc::new(InitContracts),
-        ],
+pub fn fn_hooks(tcx: TyCtxt) -> GotocHooks {
+    let mut hooks: Vec<Rc<dyn GotocHook>> = vec![
+        Rc::new(Panic),
+        Rc::new(PanicCleanup),
+        Rc::new(Assume),
+        Rc::new(Assert),
+        Rc::new(Check),
+        Rc::new(ConstantTimeCheck),
+        Rc::new(Cover),
+        Rc::new(Nondet),
+        Rc::new(IsAllocated),
+        Rc::new(PointerObject),
+        Rc::new(PointerOffset),
+        Rc::new(MemCmp),
+        Rc::new(Wide128Helpers),
+        Rc::new(UntrackedDeref),
+        Rc::new(InitContracts),
+    ];
+    // With an allocator model, `Box` allocations must call the model instead of `malloc`.
+    if !has_builtin_model(tcx, "__rust_alloc") {
+        hooks.push(Rc::new(RustAlloc));
     }
+    GotocHooks { hooks }
 }
 
 pub struct GotocHooks {
@@ -0,0 +1,202 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file contains the interface between the compiler and the Why3 backend, which generates one
+//! WhyML file per crate with one module per harness.
+
+use crate::args::ReachabilityType;
+use crate::codegen_why3::Why3Ctx;
+use crate::kani_middle::codegen_units::CodegenUnits;
+use crate::kani_middle::provide;
+use crate::kani_middle::transform::BodyTransformation;
+use crate::kani_queries::QueryDb;
+use kani_metadata::{ArtifactType, CompilerArtifactStub};
+use rustc_codegen_ssa::back::archive::{ArArchiveBuilder, ArchiveBuilder, DEFAULT_OBJECT_READER};
+use rustc_codegen_ssa::back::metadata::create_wrapper_file;
+use rustc_codegen_ssa::traits::CodegenBackend;
+use rustc_codegen_ssa::{CodegenResults, CrateInfo};
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::temp_dir::MaybeTempDir;
+use rustc_errors::{ErrorGuaranteed, DEFAULT_LOCALE_RESOURCE};
+use rustc_metadata::creader::MetadataLoaderDyn;
+use rustc_metadata::fs::{emit_wrapper_file, METADATA_FILENAME};
+use rustc_metadata::EncodedMetadata;
+use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
+use rustc_middle::ty::TyCtxt;
+use rustc_middle::util::Providers;
+use rustc_session::config::{CrateType, OutputFilenames, OutputType};
+use rustc_session::output::out_filename;
+use rustc_session::Session;
+use rustc_smir::rustc_internal;
+use std::any::Any;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempfile::Builder as TempFileBuilder;
+use tracing::debug;
+use why3_ast::why3_program::Program;
+
+#[derive(Clone)]
+pub struct Why3CodegenBackend {
+    /// The query is shared with `KaniCompiler` and it is initialized as part of `rustc`
+    /// initialization, which may happen after this object is created.
+    /// Since we don't have any guarantees on when the compiler creates the Backend object, neither
+    /// in which thread it will be used, we prefer to explicitly synchronize any query access.
+    queries: Arc<Mutex<QueryDb>>,
+}
+
+impl Why3CodegenBackend {
+    pub fn new(queries: Arc<Mutex<QueryDb>>) -> Self {
+        Why3CodegenBackend { queries }
+    }
+
+    /// Generate a WhyML program with one module per harness of the crate.
+    fn codegen_harnesses(&self, tcx: TyCtxt, queries: &QueryDb, base_filename: &Path) {
+        let units = CodegenUnits::new(queries, tcx);
+        let mut program = Program::new();
+        for unit in units.iter() {
+            let mut transformer = BodyTransformation::new(queries, tcx, unit);
+            let mut ctx = Why3Ctx::new(tcx, &mut transformer);
+            for harness in &unit.harnesses {
+                program.add_module(ctx.codegen_harness(*harness));
+            }
+        }
+        if !program.modules.is_empty()
+            && !tcx.sess.opts.unstable_opts.no_codegen
+            && tcx.sess.opts.output_types.should_codegen()
+        {
+            let path = base_filename.with_extension("mlw");
+            let mut writer = BufWriter::new(File::create(&path).unwrap());
+            program.write_to(&mut writer).unwrap();
+            tcx.dcx().note(format!(
+                "Generated WhyML program `{}`. Use `why3 prove` to verify it.",
+                path.display()
+            ));
+        }
+        units.write_metadata(queries, tcx);
+    }
+}
+
+impl CodegenBackend for Why3CodegenBackend {
+    fn metadata_loader(&self) -> Box<MetadataLoaderDyn> {
+        Box::new(rustc_codegen_ssa::back::metadata::DefaultMetadataLoader)
+    }
+
+    fn provide(&self, providers: &mut Providers) {
+        provide::provide(providers, &self.queries.lock().unwrap());
+    }
+
+    fn print_version(&self) {
+        println!("Kani-why3 version: {}", env!("CARGO_PKG_VERSION"));
+    }
+
+    fn locale_resource(&self) -> &'static str {
+        // We don't currently support multiple languages.
+        DEFAULT_LOCALE_RESOURCE
+    }
+
+    fn codegen_crate(
+        &self,
+        tcx: TyCtxt,
+        rustc_metadata: EncodedMetadata,
+        _need_metadata_module: bool,
+    ) -> Box<dyn Any> {
+        let ret_val = rustc_internal::run(tcx, || {
+            // Queries shouldn't change today once codegen starts.
+            let queries = self.queries.lock().unwrap().clone();
+
+            // The Why3 backend only generates code for proof harnesses. Dependencies are compiled
+            // with reachability `None`, so they don't generate anything.
+            let base_filepath = tcx.output_filenames(()).path(OutputType::Object);
+            let base_filename = base_filepath.as_path();
+            match queries.args().reachability_analysis {
+                ReachabilityType::Harnesses => self.codegen_harnesses(tcx, &queries, base_filename),
+                ReachabilityType::None => {}
+                reachability => {
+                    tcx.dcx().err(format!(
+                        "The Why3 backend only supports proof harnesses, but the reachability \
+                        mode is `{}`.",
+                        reachability.as_ref()
+                    ));
+                }
+            }
+            codegen_results(tcx, rustc_metadata)
+        });
+        ret_val.unwrap()
+    }
+
+    fn join_codegen(
+        &self,
+        ongoing_codegen: Box<dyn Any>,
+        _sess: &Session,
+        _filenames: &OutputFilenames,
+    ) -> (CodegenResults, FxIndexMap<WorkProductId, WorkProduct>) {
+        match ongoing_codegen.downcast::<(CodegenResults, FxIndexMap<WorkProductId, WorkProduct>)>()
+        {
+            Ok(val) => *val,
+            Err(val) => panic!("unexpected error: {:?}", (*val).type_id()),
+        }
+    }
+
+    /// Emit output files during the link stage if it was requested.
+    ///
+    /// Like the goto backend, we build `rlib` files that only contain the `rmeta` file, and we
+    /// stub any other requested file with the path of the `kani-metadata.json` file.
+    fn link(
+        &self,
+        sess: &Session,
+        codegen_results: CodegenResults,
+        outputs: &OutputFilenames,
+    ) -> Result<(), ErrorGuaranteed> {
+        let requested_crate_types = &codegen_results.crate_info.crate_types;
+        for crate_type in requested_crate_types {
+            let out_fname = out_filename(
+                sess,
+                *crate_type,
+                outputs,
+                codegen_results.crate_info.local_crate_name,
+            );
+            let out_path = out_fname.as_path();
+            debug!(?crate_type, ?out_path, "link");
+            if *crate_type == CrateType::Rlib {
+                // Emit the `rlib` that contains just one file: `<crate>.rmeta`
+                let mut builder = Box::new(ArArchiveBuilder::new(sess, &DEFAULT_OBJECT_READER));
+                let tmp_dir = TempFileBuilder::new().prefix("kani").tempdir().unwrap();
+                let path = MaybeTempDir::new(tmp_dir, sess.opts.cg.save_temps);
+                let (metadata, _metadata_position) = create_wrapper_file(
+                    sess,
+                    ".rmeta".to_string(),
+                    codegen_results.metadata.raw_data(),
+                );
+                let metadata = emit_wrapper_file(sess, &metadata, &path, METADATA_FILENAME);
+                builder.add_file(&metadata);
+                builder.build(&out_path);
+            } else {
+                // Write the location of the kani metadata file in the requested compiler output file.
+                let base_filepath = outputs.path(OutputType::Object);
+                let base_filename = base_filepath.as_path();
+                let content_stub = CompilerArtifactStub {
+                    metadata_path: base_filename.with_extension(ArtifactType::Metadata),
+                };
+                let out_file = File::create(out_path).unwrap();
+                serde_json::to_writer(out_file, &content_stub).unwrap();
+            }
+        }
+        Ok(())
+    }
+}
+
+fn codegen_results(tcx: TyCtxt, rustc_metadata: EncodedMetadata) -> Box<dyn Any> {
+    let work_products = FxIndexMap::<WorkProductId, WorkProduct>::default();
+    Box::new((
+        CodegenResults {
+            modules: vec![],
+            allocator_module: None,
+            metadata_module: None,
+            metadata: rustc_metadata,
+            crate_info: CrateInfo::new(tcx, tcx.sess.target.arch.to_string()),
+        },
+        work_products,
+    ))
+}
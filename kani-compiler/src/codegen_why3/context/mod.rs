@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module carries the context used by Kani to convert MIR into WhyML.
+
+mod why3_ctx;
+
+pub use why3_ctx::Why3Ctx;
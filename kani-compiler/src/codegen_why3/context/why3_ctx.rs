@@ -0,0 +1,605 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This file translates the MIR of a harness into a WhyML function.
+//!
+//! WhyML has no `goto`, so the control-flow graph of a body is unfolded into a tree of
+//! expressions: each basic block is followed by the code of its successors, and the code of a
+//! basic block that has several predecessors is duplicated. Calls are inlined, so a harness becomes
+//! a single WhyML function whose verification conditions are as precise as the MIR.
+//!
+//! Rust integers are mathematical integers, and the result of every checked arithmetic operation
+//! is compared with the range of its type, which is how the assertions that MIR emits for overflows
+//! are discharged. The result of an unchecked operation is asserted to be in range, since its
+//! overflow is undefined behavior.
+//!
+//! The backend only supports a subset of Rust: booleans, integers and tuples of them, without
+//! references, loops or recursion. An unsupported construct is replaced by `absurd`, so
+//! verification fails if it is reachable, and reported as a warning.
+
+use crate::kani_middle::attributes::matches_diagnostic as matches_function;
+use crate::kani_middle::transform::BodyTransformation;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    AggregateKind, AssertMessage, BasicBlockIdx, BinOp, Body, ConstOperand, Local, Operand, Place,
+    ProjectionElem, Rvalue, Statement, StatementKind, SwitchTargets, Terminator, TerminatorKind,
+    UnOp,
+};
+use stable_mir::ty::{ConstantKind, RigidTy, Span, Ty, TyKind};
+use stable_mir::CrateDef;
+use std::collections::HashSet;
+use tracing::debug;
+use why3_ast::why3_program::{BinaryOp, Expr, Function, Module, Type, UnaryOp};
+
+/// The modules of the Why3 standard library that the generated code uses.
+const IMPORTS: [&str; 4] = ["int.Int", "int.ComputerDivision", "bool.Bool", "ref.Ref"];
+
+/// A construct that the Why3 backend doesn't support, with a description for the user.
+struct Unsupported(String);
+
+type Result<T> = std::result::Result<T, Unsupported>;
+
+/// A body being translated: the harness, or a function inlined in it.
+struct Frame {
+    body: Body,
+    /// The prefix of the names of the locals of this frame, so each inlined call has its own.
+    prefix: String,
+}
+
+pub struct Why3Ctx<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    transformer: &'a mut BodyTransformation,
+    /// The functions being inlined, to detect recursion.
+    call_stack: Vec<Instance>,
+    /// The number of inlined calls so far, used to name the locals of each frame.
+    frames: usize,
+    /// The unsupported constructs that were reported, since the code of a basic block may be
+    /// generated several times.
+    reported: HashSet<(String, rustc_span::Span)>,
+}
+
+impl<'a, 'tcx> Why3Ctx<'a, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, transformer: &'a mut BodyTransformation) -> Self {
+        Why3Ctx { tcx, transformer, call_stack: vec![], frames: 0, reported: HashSet::new() }
+    }
+
+    /// Generate a module with a function that contains the code of the given harness.
+    pub fn codegen_harness(&mut self, harness: Instance) -> Module {
+        debug!(harness=?harness.name(), "codegen_harness");
+        let body = self.transformer.body(self.tcx, harness);
+        let frame = Frame { body, prefix: String::new() };
+        self.call_stack.push(harness);
+        let code = self.codegen_body(&frame, vec![]);
+        self.call_stack.pop();
+
+        let mut module = Module::new(module_name(&harness.name()));
+        for import in IMPORTS {
+            module.add_import(import);
+        }
+        module.add_function(Function::new("harness", vec![], Type::Unit, code));
+        module
+    }
+
+    /// Generate the code of a body, which declares its locals, whose arguments are initialized
+    /// with the given values.
+    fn codegen_body(&mut self, frame: &Frame, mut args: Vec<Option<Expr>>) -> Expr {
+        let mut code = self.codegen_block(frame, 0, &mut vec![]);
+        let arg_count = frame.body.arg_locals().len();
+        for (local, decl) in frame.body.locals().iter().enumerate().rev() {
+            let arg = if (1..=arg_count).contains(&local) { args.pop().flatten() } else { None };
+            // Locals whose type isn't supported aren't declared, since any use is unsupported.
+            let Some(vars) = local_vars(decl.ty) else { continue };
+            for (field, typ) in vars.into_iter().rev() {
+                let value = arg.clone().unwrap_or(Expr::any(typ, None));
+                code = Expr::let_ref(self.local_name(frame, local, field), typ, value, code);
+            }
+        }
+        code
+    }
+
+    /// Generate the code of a basic block, followed by the code of its successors.
+    ///
+    /// `path` contains the basic blocks from the entry of the body to this one, to detect loops.
+    fn codegen_block(
+        &mut self,
+        frame: &Frame,
+        bb: BasicBlockIdx,
+        path: &mut Vec<BasicBlockIdx>,
+    ) -> Expr {
+        let block = &frame.body.blocks[bb];
+        if path.contains(&bb) {
+            return self.codegen_unsupported("loops".to_string(), block.terminator.span);
+        }
+        path.push(bb);
+        let mut exprs = vec![];
+        for stmt in &block.statements {
+            match self.codegen_statement(frame, stmt) {
+                Ok(Expr::Literal(_)) => {}
+                Ok(expr) => exprs.push(expr),
+                Err(Unsupported(construct)) => {
+                    // The rest of the block is unreachable.
+                    exprs.push(self.codegen_unsupported(construct, stmt.span));
+                    path.pop();
+                    return Expr::sequence(exprs);
+                }
+            }
+        }
+        let terminator = match self.codegen_terminator(frame, &block.terminator, path) {
+            Ok(expr) => expr,
+            Err(Unsupported(construct)) => {
+                self.codegen_unsupported(construct, block.terminator.span)
+            }
+        };
+        exprs.push(terminator);
+        path.pop();
+        Expr::sequence(exprs)
+    }
+
+    /// Report an unsupported construct, and generate code that fails verification if it's
+    /// reachable.
+    fn codegen_unsupported(&mut self, construct: String, span: Span) -> Expr {
+        let span = rustc_internal::internal(self.tcx, span);
+        if self.reported.insert((construct.clone(), span)) {
+            self.tcx.dcx().span_warn(
+                span,
+                format!(
+                    "The Why3 backend doesn't support {construct}. Verification fails if this \
+                    code is reachable."
+                ),
+            );
+        }
+        Expr::Absurd
+    }
+
+    fn codegen_statement(&mut self, frame: &Frame, stmt: &Statement) -> Result<Expr> {
+        match &stmt.kind {
+            StatementKind::Assign(place, rvalue) => self.codegen_assign(frame, place, rvalue),
+            StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::FakeRead(..)
+            | StatementKind::Retag(..)
+            | StatementKind::PlaceMention(_)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Coverage(_)
+            | StatementKind::ConstEvalCounter
+            | StatementKind::Nop => Ok(Expr::unit()),
+            StatementKind::SetDiscriminant { .. }
+            | StatementKind::Deinit(_)
+            | StatementKind::Intrinsic(_) => Err(Unsupported("this statement".to_string())),
+        }
+    }
+
+    fn codegen_terminator(
+        &mut self,
+        frame: &Frame,
+        term: &Terminator,
+        path: &mut Vec<BasicBlockIdx>,
+    ) -> Result<Expr> {
+        match &term.kind {
+            TerminatorKind::Goto { target } => Ok(self.codegen_block(frame, *target, path)),
+            TerminatorKind::SwitchInt { discr, targets } => {
+                self.codegen_switch_int(frame, discr, targets, path)
+            }
+            TerminatorKind::Return => {
+                let ret = Place::from(0);
+                self.codegen_place_read(frame, &ret)
+            }
+            TerminatorKind::Unreachable => Ok(Expr::Absurd),
+            TerminatorKind::Assert { cond, expected, msg, target, .. } => {
+                let cond = self.codegen_operand(frame, cond)?;
+                let cond = if *expected { cond } else { !cond };
+                let check = Expr::assert(cond, Some(assert_message(msg)));
+                Ok(Expr::sequence(vec![check, self.codegen_block(frame, *target, path)]))
+            }
+            TerminatorKind::Drop { place, target, .. } => {
+                let ty = place.ty(frame.body.locals()).unwrap();
+                if Instance::resolve_drop_in_place(ty).is_empty_shim() {
+                    Ok(self.codegen_block(frame, *target, path))
+                } else {
+                    Err(Unsupported(format!("the drop of `{ty}`")))
+                }
+            }
+            TerminatorKind::Call { func, args, destination, target, .. } => {
+                let call = self.codegen_call(frame, func, args, destination)?;
+                let next = match target {
+                    Some(target) => self.codegen_block(frame, *target, path),
+                    None => Expr::Absurd,
+                };
+                Ok(Expr::sequence(vec![call, next]))
+            }
+            TerminatorKind::Resume | TerminatorKind::Abort | TerminatorKind::InlineAsm { .. } => {
+                Err(Unsupported("this terminator".to_string()))
+            }
+        }
+    }
+
+    fn codegen_switch_int(
+        &mut self,
+        frame: &Frame,
+        discr: &Operand,
+        targets: &SwitchTargets,
+        path: &mut Vec<BasicBlockIdx>,
+    ) -> Result<Expr> {
+        let discr_ty = discr.ty(frame.body.locals()).unwrap();
+        let discr = self.codegen_operand(frame, discr)?;
+        let mut code = self.codegen_block(frame, targets.otherwise(), path);
+        for (value, target) in targets.branches().collect::<Vec<_>>().into_iter().rev() {
+            let value = switch_value(value, discr_ty)?;
+            let cond = Expr::binary(BinaryOp::Eq, discr.clone(), value);
+            code = Expr::if_then_else(cond, self.codegen_block(frame, target, path), code);
+        }
+        Ok(code)
+    }
+
+    /// Generate a call, which is either a Kani function that we model, or inlined.
+    fn codegen_call(
+        &mut self,
+        frame: &Frame,
+        func: &Operand,
+        args: &[Operand],
+        destination: &Place,
+    ) -> Result<Expr> {
+        let TyKind::RigidTy(RigidTy::FnDef(def, generic_args)) =
+            func.ty(frame.body.locals()).unwrap().kind()
+        else {
+            return Err(Unsupported("calls through function pointers".to_string()));
+        };
+        let instance = Instance::resolve(def, &generic_args).unwrap();
+        if matches_function(self.tcx, instance.def, "KaniAssert")
+            || matches_function(self.tcx, instance.def, "KaniCheck")
+        {
+            let cond = self.codegen_operand(frame, &args[0])?;
+            Ok(Expr::assert(cond, Some(const_str(&args[1]).unwrap_or("assertion".into()))))
+        } else if matches_function(self.tcx, instance.def, "KaniAssume") {
+            Ok(Expr::assume(self.codegen_operand(frame, &args[0])?))
+        } else if matches_function(self.tcx, instance.def, "KaniAny") {
+            let ty = destination.ty(frame.body.locals()).unwrap();
+            let typ = scalar_type(ty).ok_or_else(|| unsupported_type(ty))?;
+            let post = in_range(Expr::variable("result"), ty).ok();
+            self.codegen_place_write(frame, destination, Expr::any(typ, post))
+        } else if self.is_panic(instance) {
+            let msg = args.first().and_then(const_str).unwrap_or("panic".into());
+            Ok(Expr::assert(Expr::bool(false), Some(msg)))
+        } else if instance.has_body() {
+            self.codegen_inlined_call(frame, instance, args, destination)
+        } else {
+            Err(Unsupported(format!("the call to `{}`", instance.name())))
+        }
+    }
+
+    /// Whether the function panics, like the functions that the cprover backend hooks.
+    fn is_panic(&self, instance: Instance) -> bool {
+        let def_id = rustc_internal::internal(self.tcx, instance.def.def_id());
+        Some(def_id) == self.tcx.lang_items().panic_fn()
+            || self.tcx.has_attr(def_id, rustc_span::sym::rustc_const_panic_str)
+            || Some(def_id) == self.tcx.lang_items().panic_fmt()
+            || Some(def_id) == self.tcx.lang_items().begin_panic_fn()
+            || matches_function(self.tcx, instance.def, "KaniPanic")
+    }
+
+    /// Inline the body of the function, whose locals are declared in a new frame.
+    fn codegen_inlined_call(
+        &mut self,
+        frame: &Frame,
+        instance: Instance,
+        args: &[Operand],
+        destination: &Place,
+    ) -> Result<Expr> {
+        if self.call_stack.contains(&instance) {
+            return Err(Unsupported("recursive calls".to_string()));
+        }
+        let args = args
+            .iter()
+            .map(|arg| {
+                let ty = arg.ty(frame.body.locals()).unwrap();
+                if ty.kind().is_unit() {
+                    Ok(None)
+                } else {
+                    self.codegen_operand(frame, arg).map(Some)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let body = self.transformer.body(self.tcx, instance);
+        self.frames += 1;
+        let callee = Frame { body, prefix: format!("f{}_", self.frames) };
+        self.call_stack.push(instance);
+        let code = self.codegen_body(&callee, args);
+        self.call_stack.pop();
+        self.codegen_place_write(frame, destination, code)
+    }
+
+    fn codegen_assign(&mut self, frame: &Frame, place: &Place, rvalue: &Rvalue) -> Result<Expr> {
+        let ty = place.ty(frame.body.locals()).unwrap();
+        if let Some(fields) = tuple_fields(ty) {
+            if !place.projection.is_empty() {
+                return Err(Unsupported("nested tuples".to_string()));
+            }
+            let values = self.codegen_rvalue_fields(frame, rvalue)?;
+            let assignments = fields
+                .into_iter()
+                .zip(values)
+                .filter(|((_, typ), _)| *typ != Type::Unit)
+                .map(|((field, _), value)| {
+                    Expr::assign(self.local_name(frame, place.local, Some(field)), value)
+                })
+                .collect();
+            Ok(Expr::sequence(assignments))
+        } else {
+            let value = self.codegen_rvalue(frame, rvalue)?;
+            self.codegen_place_write(frame, place, value)
+        }
+    }
+
+    /// Generate the value of an rvalue of a tuple type, one expression per field.
+    fn codegen_rvalue_fields(&mut self, frame: &Frame, rvalue: &Rvalue) -> Result<Vec<Expr>> {
+        match rvalue {
+            Rvalue::CheckedBinaryOp(op, left, right) => {
+                let ty = left.ty(frame.body.locals()).unwrap();
+                let result = self.codegen_binary_op(frame, op, left, right)?;
+                let in_range = in_range(result.clone(), ty)?;
+                Ok(vec![result, !in_range])
+            }
+            Rvalue::Aggregate(AggregateKind::Tuple, operands) => {
+                operands.iter().map(|operand| self.codegen_operand(frame, operand)).collect()
+            }
+            Rvalue::Use(Operand::Copy(place) | Operand::Move(place))
+                if place.projection.is_empty() =>
+            {
+                let ty = place.ty(frame.body.locals()).unwrap();
+                let fields = tuple_fields(ty).ok_or_else(|| unsupported_type(ty))?;
+                Ok(fields
+                    .into_iter()
+                    .map(|(field, typ)| {
+                        if typ == Type::Unit {
+                            Expr::unit()
+                        } else {
+                            Expr::variable(self.local_name(frame, place.local, Some(field)))
+                        }
+                    })
+                    .collect())
+            }
+            _ => Err(Unsupported("this expression".to_string())),
+        }
+    }
+
+    fn codegen_rvalue(&mut self, frame: &Frame, rvalue: &Rvalue) -> Result<Expr> {
+        match rvalue {
+            Rvalue::Use(operand) => self.codegen_operand(frame, operand),
+            Rvalue::BinaryOp(op, left, right) => self.codegen_binary_op(frame, op, left, right),
+            Rvalue::UnaryOp(UnOp::Not, operand)
+                if operand.ty(frame.body.locals()).unwrap().kind().is_bool() =>
+            {
+                Ok(!self.codegen_operand(frame, operand)?)
+            }
+            Rvalue::UnaryOp(UnOp::Neg, operand) => {
+                Ok(Expr::unary(UnaryOp::Neg, self.codegen_operand(frame, operand)?))
+            }
+            _ => Err(Unsupported("this expression".to_string())),
+        }
+    }
+
+    fn codegen_binary_op(
+        &mut self,
+        frame: &Frame,
+        op: &BinOp,
+        left: &Operand,
+        right: &Operand,
+    ) -> Result<Expr> {
+        let ty = left.ty(frame.body.locals()).unwrap();
+        let is_bool = ty.kind().is_bool();
+        let why3_op = match op {
+            BinOp::Add | BinOp::AddUnchecked => BinaryOp::Add,
+            BinOp::Sub | BinOp::SubUnchecked => BinaryOp::Sub,
+            BinOp::Mul | BinOp::MulUnchecked => BinaryOp::Mul,
+            BinOp::Div => BinaryOp::Div,
+            BinOp::Rem => BinaryOp::Mod,
+            BinOp::Eq => BinaryOp::Eq,
+            BinOp::Ne => BinaryOp::Neq,
+            BinOp::Lt => BinaryOp::Lt,
+            BinOp::Le => BinaryOp::Lte,
+            BinOp::Gt => BinaryOp::Gt,
+            BinOp::Ge => BinaryOp::Gte,
+            BinOp::BitAnd if is_bool => BinaryOp::And,
+            BinOp::BitOr if is_bool => BinaryOp::Or,
+            BinOp::BitXor if is_bool => BinaryOp::Neq,
+            op => return Err(Unsupported(format!("the operation `{op:?}`"))),
+        };
+        let left = self.codegen_operand(frame, left)?;
+        let right = self.codegen_operand(frame, right)?;
+        let result = Expr::binary(why3_op, left, right);
+        let unchecked = match op {
+            BinOp::AddUnchecked => "unchecked_add",
+            BinOp::SubUnchecked => "unchecked_sub",
+            BinOp::MulUnchecked => "unchecked_mul",
+            _ => return Ok(result),
+        };
+        // An unchecked operation that overflows is undefined behavior, and MIR doesn't check it.
+        let check = Expr::assert(
+            in_range(result.clone(), ty)?,
+            Some(format!("attempt to compute `{unchecked}` which would overflow")),
+        );
+        Ok(Expr::sequence(vec![check, result]))
+    }
+
+    fn codegen_operand(&mut self, frame: &Frame, operand: &Operand) -> Result<Expr> {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.codegen_place_read(frame, place),
+            Operand::Constant(constant) => codegen_constant(constant),
+        }
+    }
+
+    fn codegen_place_read(&mut self, frame: &Frame, place: &Place) -> Result<Expr> {
+        let ty = place.ty(frame.body.locals()).unwrap();
+        if ty.kind().is_unit() {
+            return Ok(Expr::unit());
+        }
+        scalar_type(ty).ok_or_else(|| unsupported_type(ty))?;
+        Ok(Expr::variable(self.place_name(frame, place)?))
+    }
+
+    /// Assign the value to the place. Values of type `()` are evaluated for their effects only.
+    fn codegen_place_write(&mut self, frame: &Frame, place: &Place, value: Expr) -> Result<Expr> {
+        let ty = place.ty(frame.body.locals()).unwrap();
+        if ty.kind().is_unit() || matches!(ty.kind(), TyKind::RigidTy(RigidTy::Never)) {
+            return Ok(value);
+        }
+        scalar_type(ty).ok_or_else(|| unsupported_type(ty))?;
+        Ok(Expr::assign(self.place_name(frame, place)?, value))
+    }
+
+    /// The name of the variable that stores the place, which is either a local or a field of a
+    /// tuple local.
+    fn place_name(&self, frame: &Frame, place: &Place) -> Result<String> {
+        match place.projection.as_slice() {
+            [] => Ok(self.local_name(frame, place.local, None)),
+            [ProjectionElem::Field(field, _)]
+                if tuple_fields(frame.body.locals()[place.local].ty).is_some() =>
+            {
+                Ok(self.local_name(frame, place.local, Some(*field)))
+            }
+            _ => Err(Unsupported("dereferences and projections other than tuple fields".into())),
+        }
+    }
+
+    fn local_name(&self, frame: &Frame, local: Local, field: Option<usize>) -> String {
+        let name = format!("{}l{local}", frame.prefix);
+        match field {
+            Some(field) => field_var(&name, Some(field)),
+            None => name,
+        }
+    }
+}
+
+/// The variables that store a local of the given type, i.e., one variable for a scalar, one per
+/// field for a tuple, and none for `()`, or `None` if the type isn't supported.
+fn local_vars(ty: Ty) -> Option<Vec<(Option<usize>, Type)>> {
+    if ty.kind().is_unit() || matches!(ty.kind(), TyKind::RigidTy(RigidTy::Never)) {
+        Some(vec![])
+    } else if let Some(typ) = scalar_type(ty) {
+        Some(vec![(None, typ)])
+    } else {
+        let fields = tuple_fields(ty)?;
+        Some(
+            fields
+                .into_iter()
+                .filter(|(_, typ)| *typ != Type::Unit)
+                .map(|(field, typ)| (Some(field), typ))
+                .collect(),
+        )
+    }
+}
+
+fn field_var(name: &str, field: Option<usize>) -> String {
+    match field {
+        Some(field) => format!("{name}_{field}"),
+        None => name.to_string(),
+    }
+}
+
+/// The WhyML type of a boolean or an integer.
+fn scalar_type(ty: Ty) -> Option<Type> {
+    match ty.kind() {
+        TyKind::RigidTy(RigidTy::Bool) => Some(Type::Bool),
+        TyKind::RigidTy(RigidTy::Int(_) | RigidTy::Uint(_)) => Some(Type::Int),
+        _ => None,
+    }
+}
+
+/// The fields of a tuple whose fields are scalars or `()`.
+fn tuple_fields(ty: Ty) -> Option<Vec<(usize, Type)>> {
+    let TyKind::RigidTy(RigidTy::Tuple(fields)) = ty.kind() else { return None };
+    if fields.is_empty() {
+        return None;
+    }
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            if field.kind().is_unit() {
+                Some((idx, Type::Unit))
+            } else {
+                Some((idx, scalar_type(*field)?))
+            }
+        })
+        .collect()
+}
+
+/// The bounds of an integer type.
+fn int_range(ty: Ty) -> Option<(Expr, Expr)> {
+    match ty.kind() {
+        TyKind::RigidTy(RigidTy::Int(int_ty)) => {
+            let bits = int_ty.num_bytes() * 8;
+            let max = i128::MAX >> (128 - bits);
+            Some((Expr::int(-max - 1), Expr::int(max)))
+        }
+        TyKind::RigidTy(RigidTy::Uint(uint_ty)) => {
+            let bits = uint_ty.num_bytes() * 8;
+            Some((Expr::int(0), Expr::int(u128::MAX >> (128 - bits))))
+        }
+        _ => None,
+    }
+}
+
+/// Whether the value is in the range of the integer type.
+fn in_range(value: Expr, ty: Ty) -> Result<Expr> {
+    let (min, max) = int_range(ty).ok_or_else(|| unsupported_type(ty))?;
+    Ok(Expr::binary(BinaryOp::Lte, min, value.clone()).and(Expr::binary(BinaryOp::Lte, value, max)))
+}
+
+/// The value of a `SwitchInt` target, which MIR stores as the bits of the value.
+fn switch_value(bits: u128, ty: Ty) -> Result<Expr> {
+    match ty.kind() {
+        TyKind::RigidTy(RigidTy::Bool) => Ok(Expr::bool(bits != 0)),
+        TyKind::RigidTy(RigidTy::Uint(_)) => Ok(Expr::int(bits)),
+        TyKind::RigidTy(RigidTy::Int(int_ty)) => {
+            // Sign-extend the value to 128 bits.
+            let shift = 128 - int_ty.num_bytes() * 8;
+            Ok(Expr::int(((bits << shift) as i128) >> shift))
+        }
+        _ => Err(unsupported_type(ty)),
+    }
+}
+
+fn codegen_constant(constant: &ConstOperand) -> Result<Expr> {
+    let ty = constant.const_.ty();
+    if ty.kind().is_unit() {
+        return Ok(Expr::unit());
+    }
+    let ConstantKind::Allocated(alloc) = constant.const_.kind() else {
+        return Err(Unsupported("this constant".to_string()));
+    };
+    let value = match ty.kind() {
+        TyKind::RigidTy(RigidTy::Bool) => alloc.read_bool().map(Expr::bool),
+        TyKind::RigidTy(RigidTy::Int(_)) => alloc.read_int().map(Expr::int),
+        TyKind::RigidTy(RigidTy::Uint(_)) => alloc.read_uint().map(Expr::int),
+        _ => return Err(unsupported_type(ty)),
+    };
+    value.map_err(|_| Unsupported("this constant".to_string()))
+}
+
+/// The value of a constant `&str`, e.g., the message of an assertion.
+fn const_str(operand: &Operand) -> Option<String> {
+    let Operand::Constant(constant) = operand else { return None };
+    let ConstantKind::Allocated(alloc) = constant.const_.kind() else { return None };
+    let (_, prov) = alloc.provenance.ptrs.first()?;
+    let GlobalAlloc::Memory(data) = GlobalAlloc::from(prov.0) else { return None };
+    String::from_utf8(data.raw_bytes().ok()?).ok()
+}
+
+fn assert_message(msg: &AssertMessage) -> String {
+    msg.description().unwrap_or("assertion failed").to_string()
+}
+
+fn unsupported_type(ty: Ty) -> Unsupported {
+    Unsupported(format!("the type `{ty}`"))
+}
+
+/// The name of the module of a harness, which must be a valid WhyML module name.
+fn module_name(harness: &str) -> String {
+    let name: String =
+        harness.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("Harness_{name}")
+}
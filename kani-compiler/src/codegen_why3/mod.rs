@@ -0,0 +1,7 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+mod compiler_interface;
+mod context;
+
+pub use compiler_interface::Why3CodegenBackend;
+pub use context::Why3Ctx;
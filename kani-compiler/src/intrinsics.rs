@@ -40,6 +40,7 @@ pub enum Intrinsic {
     BlackBox,
     Breakpoint,
     Bswap,
+    CatchUnwind,
     CeilF32,
     CeilF64,
     CompareBytes,
@@ -114,14 +115,32 @@ pub enum Intrinsic {
     SimdRem,
     SimdEq,
     SimdExtract,
+    SimdGather,
     SimdGe,
     SimdGt,
     SimdInsert,
     SimdLe,
     SimdLt,
+    SimdMaskedLoad,
+    SimdMaskedStore,
     SimdMul,
     SimdNe,
     SimdOr,
+    SimdReduceAddOrdered,
+    SimdReduceAddUnordered,
+    SimdReduceAll,
+    SimdReduceAnd,
+    SimdReduceAny,
+    SimdReduceMax,
+    SimdReduceMin,
+    SimdReduceMulOrdered,
+    SimdReduceMulUnordered,
+    SimdReduceOr,
+    SimdReduceXor,
+    SimdSaturatingAdd,
+    SimdSaturatingSub,
+    SimdScatter,
+    SimdSelect,
     SimdShl,
     SimdShr,
     SimdShuffle(String),
@@ -231,10 +250,7 @@ pub fn from_instance(intrinsic_instance: &Instance) -> Self {
             }
             "catch_unwind" => {
                 assert_sig_matches!(sig, RigidTy::FnPtr(_), RigidTy::RawPtr(_, Mutability::Mut), RigidTy::FnPtr(_) => RigidTy::Int(IntTy::I32));
-                Self::Unimplemented {
-                    name: intrinsic_str,
-                    issue_link: "https://github.com/model-checking/kani/issues/267".into(),
-                }
+                Self::CatchUnwind
             }
             "compare_bytes" => {
                 assert_sig_matches!(sig, RigidTy::RawPtr(_, Mutability::Not), RigidTy::RawPtr(_, Mutability::Not), RigidTy::Uint(UintTy::Usize) => RigidTy::Int(IntTy::I32));
@@ -550,6 +566,10 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
             assert_sig_matches!(sig, _, RigidTy::Uint(UintTy::U32) => _);
             Some(Intrinsic::SimdExtract)
         }
+        "simd_gather" => {
+            assert_sig_matches!(sig, _, _, _ => _);
+            Some(Intrinsic::SimdGather)
+        }
         "simd_ge" => {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdGe)
@@ -570,6 +590,14 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdLt)
         }
+        "simd_masked_load" => {
+            assert_sig_matches!(sig, _, _, _ => _);
+            Some(Intrinsic::SimdMaskedLoad)
+        }
+        "simd_masked_store" => {
+            assert_sig_matches!(sig, _, _, _ => RigidTy::Tuple(_));
+            Some(Intrinsic::SimdMaskedStore)
+        }
         "simd_mul" => {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdMul)
@@ -582,6 +610,66 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdOr)
         }
+        "simd_reduce_add_ordered" => {
+            assert_sig_matches!(sig, _, _ => _);
+            Some(Intrinsic::SimdReduceAddOrdered)
+        }
+        "simd_reduce_add_unordered" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceAddUnordered)
+        }
+        "simd_reduce_all" => {
+            assert_sig_matches!(sig, _ => RigidTy::Bool);
+            Some(Intrinsic::SimdReduceAll)
+        }
+        "simd_reduce_and" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceAnd)
+        }
+        "simd_reduce_any" => {
+            assert_sig_matches!(sig, _ => RigidTy::Bool);
+            Some(Intrinsic::SimdReduceAny)
+        }
+        "simd_reduce_max" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceMax)
+        }
+        "simd_reduce_min" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceMin)
+        }
+        "simd_reduce_mul_ordered" => {
+            assert_sig_matches!(sig, _, _ => _);
+            Some(Intrinsic::SimdReduceMulOrdered)
+        }
+        "simd_reduce_mul_unordered" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceMulUnordered)
+        }
+        "simd_reduce_or" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceOr)
+        }
+        "simd_reduce_xor" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdReduceXor)
+        }
+        "simd_saturating_add" => {
+            assert_sig_matches!(sig, _, _ => _);
+            Some(Intrinsic::SimdSaturatingAdd)
+        }
+        "simd_saturating_sub" => {
+            assert_sig_matches!(sig, _, _ => _);
+            Some(Intrinsic::SimdSaturatingSub)
+        }
+        "simd_scatter" => {
+            assert_sig_matches!(sig, _, _, _ => RigidTy::Tuple(_));
+            Some(Intrinsic::SimdScatter)
+        }
+        "simd_select" => {
+            assert_sig_matches!(sig, _, _, _ => _);
+            Some(Intrinsic::SimdSelect)
+        }
         "simd_shl" => {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdShl)
@@ -15,9 +15,11 @@
 //! in order to apply the stubs. For the subsequent runs, we add the stub configuration to
 //! `-C llvm-args`.
 
-use crate::args::Arguments;
+use crate::args::{Arguments, BackendOption};
 #[cfg(feature = "cprover")]
 use crate::codegen_cprover_gotoc::GotocCodegenBackend;
+#[cfg(feature = "why3")]
+use crate::codegen_why3::Why3CodegenBackend;
 use crate::kani_middle::check_crate_items;
 use crate::kani_queries::QueryDb;
 use crate::session::init_session;
@@ -42,16 +44,30 @@ pub fn run(args: Vec<String>) -> ExitCode {
     }
 }
 
-/// Configure the cprover backend that generate goto-programs.
-#[cfg(feature = "cprover")]
+/// Configure the backend selected with `--backend`: either the cprover backend that generates
+/// goto-programs, or the Why3 backend that generates WhyML programs.
+///
+/// The compiler creates the backend after [KaniCompiler::config] has parsed the arguments.
+#[cfg(any(feature = "cprover", feature = "why3"))]
 fn backend(queries: Arc<Mutex<QueryDb>>) -> Box<dyn CodegenBackend> {
-    Box::new(GotocCodegenBackend::new(queries))
+    let backend = queries.lock().unwrap().args().backend;
+    match backend {
+        #[cfg(feature = "cprover")]
+        BackendOption::CProver => Box::new(GotocCodegenBackend::new(queries)),
+        #[cfg(feature = "why3")]
+        BackendOption::Why3 => Box::new(Why3CodegenBackend::new(queries)),
+        #[allow(unreachable_patterns)]
+        _ => {
+            eprintln!("Kani was built without the `{}` backend.", backend.as_ref());
+            std::process::exit(1)
+        }
+    }
 }
 
 /// Fallback backend. It will trigger an error if no backend has been enabled.
-#[cfg(not(feature = "cprover"))]
+#[cfg(not(any(feature = "cprover", feature = "why3")))]
 fn backend(queries: Arc<Mutex<QueryDb>>) -> Box<CodegenBackend> {
-    compile_error!("No backend is available. Only supported value today is `cprover`");
+    compile_error!("No backend is available. Supported values are `cprover` and `why3`");
 }
 
 /// This object controls the compiler behavior.
@@ -4,12 +4,15 @@
 
 use std::collections::BTreeMap;
 
-use kani_metadata::{CbmcSolver, HarnessAttributes, HarnessKind, Stub};
+use kani_metadata::{
+    CbmcSolver, HarnessAttributes, HarnessChecks, HarnessKind, HarnessSlicing, Stub,
+};
 use quote::ToTokens;
 use rustc_ast::{
     attr, AttrArgs, AttrArgsEq, AttrKind, Attribute, ExprKind, LitKind, MetaItem, MetaItemKind,
 };
 use rustc_errors::ErrorGuaranteed;
+use rustc_hir::def_id::LocalDefId;
 use rustc_hir::{def::DefKind, def_id::DefId};
 use rustc_middle::ty::{Instance, TyCtxt, TyKind};
 use rustc_session::Session;
@@ -19,9 +22,9 @@
 use stable_mir::{CrateDef, DefId as StableDefId};
 use std::str::FromStr;
 use strum_macros::{AsRefStr, EnumString};
-use syn::parse::Parser;
+use syn::parse::{ParseStream, Parser};
 use syn::punctuated::Punctuated;
-use syn::{PathSegment, TypePath};
+use syn::{Ident, LitInt, LitStr, PathSegment, TypePath};
 
 use tracing::{debug, trace};
 
@@ -30,10 +33,34 @@
 #[derive(Debug, Clone, Copy, AsRefStr, EnumString, PartialEq, Eq, PartialOrd, Ord)]
 #[strum(serialize_all = "snake_case")]
 enum KaniAttributeKind {
+    /// A function that models the `asm!` blocks with the given template, e.g.,
+    /// `#[kani::asm_model("pause")]`, or the block at the given location, e.g.,
+    /// `#[kani::asm_model(location = "src/lib.rs:10")]`.
+    AsmModel,
+    /// A function that models the foreign function or intrinsic with the given symbol, e.g.,
+    /// `#[kani::builtin_model("__rust_probestack")]`.
+    BuiltinModel,
+    CbmcArgs,
+    /// Check that all the heap allocations of a harness are freed when it returns.
+    CheckLeaks,
+    /// Turn automatic check classes on or off for a harness, e.g., `#[kani::checks(overflow = off)]`.
+    Checks,
+    /// Start a harness from an arbitrary state of the mutable statics, instead of their initial
+    /// value.
+    HavocStatics,
+    MemoryLimit,
+    /// Turn the arithmetic overflow checks of a function on or off, e.g.,
+    /// `#[kani::overflow_checks(off)]`.
+    OverflowChecks,
     Proof,
     ShouldPanic,
+    /// Turn slicing passes on or off for a harness, e.g., `#[kani::slicing(full = on)]`.
+    Slicing,
     Solver,
     Stub,
+    /// Free-form labels attached to a harness, e.g., to group harnesses in external tools.
+    Tag,
+    Timeout,
     /// Attribute used to mark unstable APIs.
     Unstable,
     Unwind,
@@ -81,14 +108,25 @@ impl KaniAttributeKind {
     /// Returns whether an item is only relevant for harnesses.
     pub fn is_harness_only(self) -> bool {
         match self {
-            KaniAttributeKind::Proof
+            KaniAttributeKind::CbmcArgs
+            | KaniAttributeKind::CheckLeaks
+            | KaniAttributeKind::Checks
+            | KaniAttributeKind::HavocStatics
+            | KaniAttributeKind::MemoryLimit
+            | KaniAttributeKind::Proof
             | KaniAttributeKind::ShouldPanic
+            | KaniAttributeKind::Slicing
             | KaniAttributeKind::Solver
             | KaniAttributeKind::Stub
             | KaniAttributeKind::ProofForContract
             | KaniAttributeKind::StubVerified
+            | KaniAttributeKind::Tag
+            | KaniAttributeKind::Timeout
             | KaniAttributeKind::Unwind => true,
-            KaniAttributeKind::Unstable
+            KaniAttributeKind::AsmModel
+            | KaniAttributeKind::BuiltinModel
+            | KaniAttributeKind::OverflowChecks
+            | KaniAttributeKind::Unstable
             | KaniAttributeKind::FnMarker
             | KaniAttributeKind::Recursion
             | KaniAttributeKind::RecursionTracker
@@ -140,6 +178,15 @@ pub struct ContractAttributes {
     pub modifies_wrapper: Symbol,
 }
 
+/// The `asm!` blocks modeled by a function annotated with `#[kani::asm_model]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsmPattern {
+    /// The blocks with this template, modulo whitespace.
+    Template(String),
+    /// The block that starts at this line of a file whose path ends with `file`.
+    Location { file: String, line: usize },
+}
+
 impl<'tcx> std::fmt::Debug for KaniAttributes<'tcx> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("KaniAttributes")
@@ -289,6 +336,24 @@ pub fn contract_attributes(&self) -> Option<ContractAttributes> {
         })
     }
 
+    /// Return the template of the `asm!` blocks modeled by this function, if any.
+    pub fn asm_model(&self) -> Option<AsmPattern> {
+        self.expect_maybe_one(KaniAttributeKind::AsmModel)
+            .and_then(|attr| parse_asm_model(self.tcx, attr))
+    }
+
+    /// Return the symbol of the builtin modeled by this function, if any.
+    pub fn builtin_model(&self) -> Option<String> {
+        self.expect_maybe_one(KaniAttributeKind::BuiltinModel)
+            .and_then(|attr| parse_builtin_model(self.tcx, attr))
+    }
+
+    /// Return whether the overflow checks of this function were turned on or off, if any.
+    pub fn overflow_checks(&self) -> Option<bool> {
+        self.expect_maybe_one(KaniAttributeKind::OverflowChecks)
+            .and_then(|attr| parse_overflow_checks(self.tcx, attr))
+    }
+
     /// Return a function marker if any.
     pub fn fn_marker(&self) -> Option<Symbol> {
         self.attribute_value(KaniAttributeKind::FnMarker)
@@ -324,7 +389,27 @@ pub(super) fn check_attributes(&self) {
                 ));
             }
             match kind {
-                KaniAttributeKind::ShouldPanic => {
+                KaniAttributeKind::AsmModel => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_asm_model(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::BuiltinModel => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_builtin_model(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::OverflowChecks => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_overflow_checks(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::ShouldPanic
+                | KaniAttributeKind::CheckLeaks
+                | KaniAttributeKind::HavocStatics => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
                         expect_no_args(self.tcx, kind, attr);
@@ -348,7 +433,37 @@ pub(super) fn check_attributes(&self) {
                 KaniAttributeKind::Unwind => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
-                        parse_unwind(self.tcx, attr);
+                        parse_unwind(self.tcx, self.item, attr);
+                    })
+                }
+                KaniAttributeKind::Timeout => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_timeout(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::MemoryLimit => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_memory_limit(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::CbmcArgs => attrs.iter().for_each(|attr| {
+                    parse_cbmc_args(self.tcx, attr);
+                }),
+                KaniAttributeKind::Tag => attrs.iter().for_each(|attr| {
+                    parse_tags(self.tcx, attr);
+                }),
+                KaniAttributeKind::Checks => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_checks(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::Slicing => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_slicing(self.tcx, attr);
                     })
                 }
                 KaniAttributeKind::Proof => {
@@ -466,9 +581,14 @@ fn report_unstable_forbidden(&self, unstable_attr: &UnstableAttribute) -> ErrorG
             .emit()
     }
 
+    /// Whether this harness starts from an arbitrary state of the mutable statics.
+    pub fn havoc_statics(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::HavocStatics)
+    }
+
     /// Is this item a harness? (either `proof` or `proof_for_contract`
     /// attribute are present)
-    fn is_proof_harness(&self) -> bool {
+    pub fn is_proof_harness(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::Proof)
             || self.map.contains_key(&KaniAttributeKind::ProofForContract)
     }
@@ -492,6 +612,8 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         self.map.iter().fold(harness_attrs, |mut harness, (kind, attributes)| {
             match kind {
                 KaniAttributeKind::ShouldPanic => harness.should_panic = true,
+                KaniAttributeKind::CheckLeaks => harness.check_leaks = true,
+                KaniAttributeKind::HavocStatics => harness.havoc_statics = true,
                 KaniAttributeKind::Recursion => {
                     self.tcx.dcx().span_err(self.tcx.def_span(self.item), "The attribute `kani::recursion` should only be used in combination with function contracts.");
                 }
@@ -502,7 +624,30 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                     harness.stubs.extend_from_slice(&parse_stubs(self.tcx, self.item, attributes));
                 }
                 KaniAttributeKind::Unwind => {
-                    harness.unwind_value = parse_unwind(self.tcx, attributes[0])
+                    (harness.unwind_value, harness.loop_unwinds) =
+                        parse_unwind(self.tcx, self.item, attributes[0]);
+                }
+                KaniAttributeKind::Timeout => {
+                    harness.timeout = parse_timeout(self.tcx, attributes[0])
+                }
+                KaniAttributeKind::MemoryLimit => {
+                    harness.memory_limit = parse_memory_limit(self.tcx, attributes[0])
+                }
+                KaniAttributeKind::CbmcArgs => {
+                    harness.cbmc_args = attributes
+                        .iter()
+                        .flat_map(|attr| parse_cbmc_args(self.tcx, attr))
+                        .collect()
+                }
+                KaniAttributeKind::Tag => {
+                    harness.tags =
+                        attributes.iter().flat_map(|attr| parse_tags(self.tcx, attr)).collect()
+                }
+                KaniAttributeKind::Checks => {
+                    harness.checks = parse_checks(self.tcx, attributes[0]);
+                }
+                KaniAttributeKind::Slicing => {
+                    harness.slicing = parse_slicing(self.tcx, attributes[0]);
                 }
                 KaniAttributeKind::Proof => { /* no-op */ }
                 KaniAttributeKind::ProofForContract => self.handle_proof_for_contract(&mut harness),
@@ -523,7 +668,10 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                     // Internal attribute which shouldn't exist here.
                     unreachable!()
                 }
-                KaniAttributeKind::FnMarker => {
+                KaniAttributeKind::AsmModel
+                | KaniAttributeKind::BuiltinModel
+                | KaniAttributeKind::FnMarker
+                | KaniAttributeKind::OverflowChecks => {
                     /* no-op */
                 }
             };
@@ -540,6 +688,7 @@ fn handle_proof_for_contract(&self, harness: &mut HarnessAttributes) {
         assert!(matches!(
                 &harness.kind, HarnessKind::ProofForContract { target_fn }
                 if *target_fn == name.to_string()));
+        harness.contract_path = Some(contract_path(self.tcx, id));
         if KaniAttributes::for_item(self.tcx, id).contract_attributes().is_none() {
             dcx.struct_span_err(
                 span,
@@ -636,6 +785,14 @@ pub fn test_harness_name(tcx: TyCtxt, def: &impl CrateDef) -> String {
     parse_str_value(&marker).unwrap()
 }
 
+/// The crate-qualified definition path of a function with a contract, e.g., `my_crate::ops::div`.
+///
+/// Unlike `def_path_str`, the path is the same whether the function is local or comes from a
+/// dependency, so it can name the contracts verified in another crate.
+pub fn contract_path(tcx: TyCtxt, def_id: DefId) -> String {
+    format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate_verbose())
+}
+
 /// Expect the contents of this attribute to be of the format #[attribute =
 /// "value"] and return the `"value"`.
 fn expect_key_string_value(
@@ -765,8 +922,80 @@ fn expect_no_args(tcx: TyCtxt, kind: KaniAttributeKind, attr: &Attribute) {
     }
 }
 
+/// Return the default unwind value and the unwind bounds of individual loops from the given
+/// attribute, which is either `unwind(8)` or `unwind(loops = {"path::to::fn::0": 16, "*": 2})`.
+/// The loops are keyed by their CBMC id, and `*` sets the bound of all the other loops.
+fn parse_unwind(
+    tcx: TyCtxt,
+    harness: DefId,
+    attr: &Attribute,
+) -> (Option<u32>, BTreeMap<String, u32>) {
+    let Ok(bounds) = syn_attr(attr).parse_args_with(parse_loop_bounds) else {
+        return (parse_unwind_value(tcx, attr), BTreeMap::new());
+    };
+    let current_module = tcx.parent_module_from_def_id(harness.expect_local()).to_local_def_id();
+    let mut default = None;
+    let mut loops = BTreeMap::new();
+    for (id, bound) in bounds {
+        let id = id.value();
+        let Ok(bound) = bound.base10_parse::<u32>() else {
+            tcx.dcx().span_err(
+                attr.span,
+                format!("invalid bound `{bound}` for loop `{id}`, expected a `u32` value"),
+            );
+            continue;
+        };
+        if id == "*" {
+            default = Some(bound);
+            continue;
+        }
+        match loop_id(tcx, current_module, &id) {
+            Ok(loop_id) => {
+                loops.insert(loop_id, bound);
+            }
+            Err(msg) => {
+                tcx.dcx().span_err(attr.span, format!("invalid loop `{id}` in `unwind`: {msg}"));
+            }
+        }
+    }
+    (default, loops)
+}
+
+/// Parse the `loops = {"<id>": <bound>, ...}` argument of the `unwind` attribute.
+fn parse_loop_bounds(input: ParseStream) -> syn::Result<Vec<(LitStr, LitInt)>> {
+    let name: Ident = input.parse()?;
+    if name != "loops" {
+        return Err(syn::Error::new(name.span(), "expected `loops`"));
+    }
+    input.parse::<syn::Token![=]>()?;
+    let content;
+    syn::braced!(content in input);
+    let entries = Punctuated::<_, syn::Token![,]>::parse_terminated_with(&content, |entry| {
+        let id: LitStr = entry.parse()?;
+        entry.parse::<syn::Token![:]>()?;
+        let bound: LitInt = entry.parse()?;
+        Ok((id, bound))
+    })?;
+    Ok(entries.into_iter().collect())
+}
+
+/// The id of the loop `<path>::<index>` in CBMC, i.e., the mangled name of the function followed
+/// by the index of the loop in the function, as reported by `--show-loops`.
+fn loop_id(tcx: TyCtxt, current_module: LocalDefId, id: &str) -> Result<String, String> {
+    let (path, index) =
+        id.rsplit_once("::").ok_or("expected `<path to function>::<loop index>`")?;
+    let index: u32 =
+        index.parse().map_err(|_| format!("expected a loop index, found `{index}`"))?;
+    let def_id = resolve_fn(tcx, current_module, path).map_err(|err| err.to_string())?;
+    if tcx.generics_of(def_id).requires_monomorphization(tcx) {
+        return Err(format!("`{path}` is generic, so its loops have no single id"));
+    }
+    let instance = Instance::mono(tcx, def_id);
+    Ok(format!("{}.{index}", tcx.symbol_name(instance).name))
+}
+
 /// Return the unwind value from the given attribute.
-fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
+fn parse_unwind_value(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
     // Get Attribute value and if it's not none, assign it to the metadata
     match parse_integer(attr) {
         None => {
@@ -788,6 +1017,288 @@ fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
     }
 }
 
+/// Return the timeout value (in seconds) from the given attribute.
+fn parse_timeout(tcx: TyCtxt, attr: &Attribute) -> Option<u64> {
+    match parse_integer(attr) {
+        None => {
+            tcx.dcx().span_err(
+                attr.span,
+                "invalid argument for `timeout` attribute, expected an integer",
+            );
+            None
+        }
+        Some(0) => {
+            tcx.dcx().span_err(attr.span, "the `timeout` value must be greater than zero");
+            None
+        }
+        Some(timeout_value) => {
+            if let Ok(val) = timeout_value.try_into() {
+                Some(val)
+            } else {
+                tcx.dcx().span_err(attr.span, "value above maximum permitted value - u64::MAX");
+                None
+            }
+        }
+    }
+}
+
+/// Return the memory limit value (in MiB) from the given attribute.
+fn parse_memory_limit(tcx: TyCtxt, attr: &Attribute) -> Option<u64> {
+    match parse_integer(attr) {
+        None => {
+            tcx.dcx().span_err(
+                attr.span,
+                "invalid argument for `memory_limit` attribute, expected an integer",
+            );
+            None
+        }
+        Some(0) => {
+            tcx.dcx().span_err(attr.span, "the `memory_limit` value must be greater than zero");
+            None
+        }
+        Some(limit) => {
+            if let Ok(val) = limit.try_into() {
+                Some(val)
+            } else {
+                tcx.dcx().span_err(attr.span, "value above maximum permitted value - u64::MAX");
+                None
+            }
+        }
+    }
+}
+
+/// Return the CBMC arguments from the given attribute, e.g., `["--refine-arrays"]` for
+/// `#[kani::cbmc_args("--refine-arrays")]`.
+fn parse_cbmc_args(tcx: TyCtxt, attr: &Attribute) -> Vec<String> {
+    const ATTRIBUTE: &str = "#[kani::cbmc_args]";
+    let Some(attr_args) = attr.meta_item_list().filter(|args| !args.is_empty()) else {
+        tcx.dcx().span_err(
+            attr.span,
+            format!("the `{ATTRIBUTE}` attribute expects at least one string argument"),
+        );
+        return vec![];
+    };
+    let mut args = vec![];
+    for attr_arg in attr_args.iter() {
+        match attr_arg.lit() {
+            Some(lit) if lit.kind.is_str() => {
+                let arg = lit.symbol.to_string();
+                if arg == "--function" {
+                    tcx.dcx().span_err(
+                        attr_arg.span(),
+                        format!("`--function` is not supported by the `{ATTRIBUTE}` attribute"),
+                    );
+                } else {
+                    args.push(arg);
+                }
+            }
+            _ => {
+                tcx.dcx().span_err(
+                    attr_arg.span(),
+                    format!(
+                        "invalid argument for `{ATTRIBUTE}` attribute, expected a string literal"
+                    ),
+                );
+            }
+        }
+    }
+    args
+}
+
+/// Return the blocks modeled according to the given attribute, e.g., the template `"pause"` for
+/// `#[kani::asm_model("pause")]`, or the location of the block for
+/// `#[kani::asm_model(location = "src/lib.rs:10")]`.
+fn parse_asm_model(tcx: TyCtxt, attr: &Attribute) -> Option<AsmPattern> {
+    const ATTRIBUTE: &str = "#[kani::asm_model]";
+    let pattern = attr.meta_item_list().and_then(|args| match args.as_slice() {
+        [arg] if arg.has_name(Symbol::intern("location")) => {
+            let location = arg.value_str()?.to_string();
+            let (file, line) = location.rsplit_once(':')?;
+            Some(AsmPattern::Location { file: file.to_string(), line: line.parse().ok()? })
+        }
+        [arg] => arg.lit().and_then(|lit| match lit.kind {
+            LitKind::Str(template, _) => Some(AsmPattern::Template(template.to_string())),
+            _ => None,
+        }),
+        _ => None,
+    });
+    if pattern.is_none() {
+        tcx.dcx().span_err(
+            attr.span,
+            format!(
+                "the `{ATTRIBUTE}` attribute expects either a template, e.g., `\"pause\"`, or a \
+                location, e.g., `location = \"src/lib.rs:10\"`"
+            ),
+        );
+    }
+    pattern
+}
+
+/// Return the symbol from the given attribute, e.g., `"__rust_probestack"` for
+/// `#[kani::builtin_model("__rust_probestack")]`.
+fn parse_builtin_model(tcx: TyCtxt, attr: &Attribute) -> Option<String> {
+    const ATTRIBUTE: &str = "#[kani::builtin_model]";
+    let symbol = attr.meta_item_list().and_then(|args| match args.as_slice() {
+        [arg] => arg.lit().and_then(|lit| match lit.kind {
+            LitKind::Str(symbol, _) => Some(symbol.to_string()),
+            _ => None,
+        }),
+        _ => None,
+    });
+    if symbol.is_none() {
+        tcx.dcx().span_err(
+            attr.span,
+            format!("the `{ATTRIBUTE}` attribute expects a single string literal argument"),
+        );
+    }
+    symbol
+}
+
+/// Return whether the given attribute turns the checks on, e.g., `false` for
+/// `#[kani::overflow_checks(off)]`.
+fn parse_overflow_checks(tcx: TyCtxt, attr: &Attribute) -> Option<bool> {
+    const ATTRIBUTE: &str = "#[kani::overflow_checks]";
+    let value = attr.meta_item_list().and_then(|args| match args.as_slice() {
+        [arg] if arg.is_word() => match arg.name_or_empty().as_str() {
+            "on" => Some(true),
+            "off" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    });
+    if value.is_none() {
+        tcx.dcx().span_err(attr.span, format!("the `{ATTRIBUTE}` attribute expects `on` or `off`"));
+    }
+    value
+}
+
+/// Return the tags from the given attribute, e.g., `["slow", "nightly"]` for
+/// `#[kani::tag("slow", "nightly")]`.
+fn parse_tags(tcx: TyCtxt, attr: &Attribute) -> Vec<String> {
+    const ATTRIBUTE: &str = "#[kani::tag]";
+    let Some(attr_args) = attr.meta_item_list().filter(|args| !args.is_empty()) else {
+        tcx.dcx().span_err(
+            attr.span,
+            format!("the `{ATTRIBUTE}` attribute expects at least one string argument"),
+        );
+        return vec![];
+    };
+    let mut tags = vec![];
+    for attr_arg in attr_args.iter() {
+        match attr_arg.lit() {
+            Some(lit) if lit.kind.is_str() && !lit.symbol.is_empty() => {
+                tags.push(lit.symbol.to_string());
+            }
+            _ => {
+                tcx.dcx().span_err(
+                    attr_arg.span(),
+                    format!(
+                        "invalid argument for `{ATTRIBUTE}` attribute, expected a non-empty string literal"
+                    ),
+                );
+            }
+        }
+    }
+    tags
+}
+
+/// Return the check classes turned on or off by the given attribute, e.g.,
+/// `#[kani::checks(overflow = off, unwinding = on)]`.
+fn parse_checks(tcx: TyCtxt, attr: &Attribute) -> HarnessChecks {
+    let mut checks = HarnessChecks::default();
+    for (check, enabled) in parse_switches(tcx, attr, "#[kani::checks]", "check") {
+        let class = match check.as_str() {
+            "memory_safety" => &mut checks.memory_safety,
+            "overflow" => &mut checks.overflow,
+            "unwinding" => &mut checks.unwinding,
+            _ => {
+                tcx.dcx().span_err(
+                    attr.span,
+                    format!(
+                        "unknown check `{check}` for `#[kani::checks]` attribute, expected one of \
+                        `memory_safety`, `overflow` or `unwinding`"
+                    ),
+                );
+                continue;
+            }
+        };
+        if class.replace(enabled).is_some() {
+            tcx.dcx().span_err(attr.span, format!("check `{check}` was specified more than once"));
+        }
+    }
+    checks
+}
+
+/// Return the slicing passes turned on or off by the given attribute, e.g.,
+/// `#[kani::slicing(formula = off, full = on)]`.
+fn parse_slicing(tcx: TyCtxt, attr: &Attribute) -> HarnessSlicing {
+    let mut slicing = HarnessSlicing::default();
+    for (pass, enabled) in parse_switches(tcx, attr, "#[kani::slicing]", "pass") {
+        let setting = match pass.as_str() {
+            "formula" => &mut slicing.formula,
+            "constant_propagation" => &mut slicing.constant_propagation,
+            "full" => &mut slicing.full,
+            _ => {
+                tcx.dcx().span_err(
+                    attr.span,
+                    format!(
+                        "unknown pass `{pass}` for `#[kani::slicing]` attribute, expected one of \
+                        `formula`, `constant_propagation` or `full`"
+                    ),
+                );
+                continue;
+            }
+        };
+        if setting.replace(enabled).is_some() {
+            tcx.dcx().span_err(attr.span, format!("pass `{pass}` was specified more than once"));
+        }
+    }
+    slicing
+}
+
+/// Parse the `<name> = on|off` arguments of the given attribute, and return each name with
+/// whether it was turned on. Invalid arguments are reported and skipped.
+fn parse_switches(
+    tcx: TyCtxt,
+    attr: &Attribute,
+    attribute: &str,
+    what: &str,
+) -> Vec<(String, bool)> {
+    let parser = Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated;
+    let settings = match syn_attr(attr).parse_args_with(parser) {
+        Ok(settings) if !settings.is_empty() => settings,
+        _ => {
+            tcx.dcx().span_err(
+                attr.span,
+                format!(
+                    "the `{attribute}` attribute expects a list of `<{what}> = on|off` arguments"
+                ),
+            );
+            return vec![];
+        }
+    };
+    let mut switches = vec![];
+    for setting in settings {
+        let name = setting.path.to_token_stream().to_string();
+        let value = match &setting.value {
+            syn::Expr::Path(path) => path.to_token_stream().to_string(),
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) => lit.value(),
+            other => other.to_token_stream().to_string(),
+        };
+        match value.as_str() {
+            "on" => switches.push((name, true)),
+            "off" => switches.push((name, false)),
+            _ => {
+                tcx.dcx().span_err(
+                    attr.span,
+                    format!("invalid value `{value}` for `{name}`, expected `on` or `off`"),
+                );
+            }
+        }
+    }
+    switches
+}
+
 fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<Stub> {
     let current_module = tcx.parent_module_from_def_id(harness.expect_local());
     let check_resolve = |attr: &Attribute, path: &TypePath| {
@@ -14,7 +14,9 @@
 use crate::kani_middle::resolve::expect_resolve_fn;
 use crate::kani_middle::stubbing::{check_compatibility, harness_stub_map};
 use crate::kani_queries::QueryDb;
-use kani_metadata::{ArtifactType, AssignsContract, HarnessKind, HarnessMetadata, KaniMetadata};
+use kani_metadata::{
+    ArtifactType, AssignsContract, FunctionMetadata, HarnessKind, HarnessMetadata, KaniMetadata,
+};
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::OutputType;
@@ -45,6 +47,7 @@ struct CrateInfo {
 pub struct CodegenUnits {
     units: Vec<CodegenUnit>,
     harness_info: HashMap<Harness, HarnessMetadata>,
+    public_functions: Vec<FunctionMetadata>,
     crate_info: CrateInfo,
 }
 
@@ -73,10 +76,20 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
             let units = group_by_stubs(tcx, &all_harnesses);
             validate_units(tcx, &units);
             debug!(?units, "CodegenUnits::new");
-            CodegenUnits { units, harness_info: all_harnesses, crate_info }
+            CodegenUnits {
+                units,
+                harness_info: all_harnesses,
+                public_functions: vec![],
+                crate_info,
+            }
         } else {
             // Leave other reachability type handling as is for now.
-            CodegenUnits { units: vec![], harness_info: HashMap::default(), crate_info }
+            CodegenUnits {
+                units: vec![],
+                harness_info: HashMap::default(),
+                public_functions: vec![],
+                crate_info,
+            }
         }
     }
 
@@ -91,6 +104,18 @@ pub fn store_modifies(&mut self, harness_modifies: &[(Harness, AssignsContract)]
         }
     }
 
+    /// We store the locations of the assumptions reachable from each harness.
+    pub fn store_assumptions(&mut self, harness_assumptions: Vec<(Harness, Vec<String>)>) {
+        for (harness, assumptions) in harness_assumptions {
+            self.harness_info.get_mut(&harness).unwrap().assumptions = assumptions;
+        }
+    }
+
+    /// Store the public functions of the crate, and whether they are reachable from a harness.
+    pub fn store_public_functions(&mut self, public_functions: Vec<FunctionMetadata>) {
+        self.public_functions = public_functions;
+    }
+
     /// Write compilation metadata into a file.
     pub fn write_metadata(&self, queries: &QueryDb, tcx: TyCtxt) {
         let metadata = self.generate_metadata();
@@ -102,6 +127,22 @@ pub fn harness_model_path(&self, harness: Harness) -> Option<&PathBuf> {
         self.harness_info[&harness].goto_file.as_ref()
     }
 
+    /// The path of the model shared by the harnesses of the unit with the given index.
+    pub fn shared_model_path(&self, tcx: TyCtxt, unit_idx: usize) -> PathBuf {
+        let base_filepath = tcx.output_filenames(()).path(OutputType::Object);
+        let base_filename = base_filepath.as_path();
+        let file_stem =
+            format!("{}_unit{unit_idx}", base_filename.file_stem().unwrap().to_str().unwrap());
+        base_filename.with_file_name(file_stem).with_extension(ArtifactType::SymTabGoto)
+    }
+
+    /// We store the model shared by each harness.
+    pub fn store_model_paths(&mut self, harness_models: Vec<(Harness, PathBuf)>) {
+        for (harness, model) in harness_models {
+            self.harness_info.get_mut(&harness).unwrap().goto_file = Some(model);
+        }
+    }
+
     /// Generate [KaniMetadata] for the target crate.
     fn generate_metadata(&self) -> KaniMetadata {
         let (proof_harnesses, test_harnesses) =
@@ -111,6 +152,7 @@ fn generate_metadata(&self) -> KaniMetadata {
             proof_harnesses,
             unsupported_features: vec![],
             test_harnesses,
+            public_functions: self.public_functions.clone(),
         }
     }
 }
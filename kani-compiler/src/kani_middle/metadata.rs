@@ -3,12 +3,17 @@
 //! This module handles Kani metadata generation. For example, generating HarnessMetadata for a
 //! given function.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use crate::kani_middle::attributes::test_harness_name;
-use kani_metadata::{ArtifactType, HarnessAttributes, HarnessKind, HarnessMetadata};
+use kani_metadata::{
+    ArtifactType, FunctionMetadata, HarnessAttributes, HarnessKind, HarnessMetadata,
+};
+use rustc_hir::def::DefKind;
 use rustc_middle::ty::TyCtxt;
-use stable_mir::mir::mono::Instance;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, MonoItem};
 use stable_mir::CrateDef;
 
 use super::{attributes::KaniAttributes, SourceLocation};
@@ -37,6 +42,7 @@ pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) ->
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
         contract: Default::default(),
+        assumptions: vec![],
     }
 }
 
@@ -65,5 +71,45 @@ pub fn gen_test_metadata(
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
         contract: Default::default(),
+        assumptions: vec![],
     }
 }
+
+/// Create the metadata for the public functions of the local crate.
+///
+/// A function is considered reachable if any instance of it is in `reachable_items`.
+pub fn gen_public_functions_metadata(
+    tcx: TyCtxt,
+    reachable_items: &[MonoItem],
+) -> Vec<FunctionMetadata> {
+    let reachable: HashSet<_> = reachable_items
+        .iter()
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) => Some(rustc_internal::internal(tcx, instance.def.def_id())),
+            MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+        })
+        .collect();
+    let visibilities = tcx.effective_visibilities(());
+    stable_mir::all_local_items()
+        .iter()
+        .filter_map(|item| {
+            let def_id = rustc_internal::internal(tcx, item.def_id());
+            if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                || !visibilities.is_exported(def_id.expect_local())
+            {
+                return None;
+            }
+            let attributes = KaniAttributes::for_item(tcx, def_id);
+            if attributes.is_proof_harness() {
+                return None;
+            }
+            let module = tcx.parent_module_from_def_id(def_id.expect_local()).to_def_id();
+            Some(FunctionMetadata {
+                name: tcx.def_path_str(def_id),
+                module: tcx.def_path_str(module),
+                has_contract: attributes.has_contract(),
+                is_reachable: reachable.contains(&def_id),
+            })
+        })
+        .collect()
+}
@@ -38,6 +38,7 @@
 pub mod resolve;
 pub mod stubbing;
 pub mod transform;
+pub mod unsafe_audit;
 
 /// Check that all crate items are supported and there's no misconfiguration.
 /// This method will exhaustively print any error / warning and it will abort at the end if any
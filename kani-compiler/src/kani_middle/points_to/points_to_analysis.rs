@@ -298,6 +298,9 @@ fn apply_terminator_effect<'mir>(
                                 self.successors_for_operand(state, args[1].node.clone());
                             state.extend(&lvalue_set, &rvalue_set);
                         }
+                        // `catch_unwind` calls function pointers, which this analysis can't resolve,
+                        // so its effects are ignored.
+                        Intrinsic::CatchUnwind => {}
                         Intrinsic::Unimplemented { .. } => {
                             // This will be taken care of at the codegen level.
                         }
@@ -716,6 +719,20 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::SimdMul
         | Intrinsic::SimdNe
         | Intrinsic::SimdOr
+        | Intrinsic::SimdReduceAddOrdered
+        | Intrinsic::SimdReduceAddUnordered
+        | Intrinsic::SimdReduceAll
+        | Intrinsic::SimdReduceAnd
+        | Intrinsic::SimdReduceAny
+        | Intrinsic::SimdReduceMax
+        | Intrinsic::SimdReduceMin
+        | Intrinsic::SimdReduceMulOrdered
+        | Intrinsic::SimdReduceMulUnordered
+        | Intrinsic::SimdReduceOr
+        | Intrinsic::SimdReduceXor
+        | Intrinsic::SimdSaturatingAdd
+        | Intrinsic::SimdSaturatingSub
+        | Intrinsic::SimdSelect
         | Intrinsic::SimdShl
         | Intrinsic::SimdShr
         | Intrinsic::SimdShuffle(_)
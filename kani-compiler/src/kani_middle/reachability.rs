@@ -9,7 +9,10 @@
 //!   - For every function, traverse its body and collect the following:
 //!     - Constants / Static objects.
 //!     - Functions that are called or have their address taken.
-//!     - VTable methods for types that are coerced as unsized types.
+//!     - VTable methods for types that are coerced as unsized types. A method is only collected
+//!       once its trait method is invoked via dynamic dispatch in some reachable function, since
+//!       the other entries of a vtable can never be called. Codegen fills the entries of the
+//!       methods that weren't collected with null pointers.
 //!   - For every static, collect initializer and drop functions.
 //!
 //! We have kept this module agnostic of any Kani code in case we can contribute this back to rustc.
@@ -18,9 +21,11 @@
 //! kept with internal APIs.
 use tracing::{debug, debug_span, trace};
 
+use kani_metadata::{ReachabilityReport, ReachableItem};
 use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_hir::def_id::DefId;
 use rustc_middle::ty::{TyCtxt, VtblEntry};
 use rustc_session::config::OutputType;
 use rustc_smir::rustc_internal;
@@ -35,7 +40,7 @@
 use stable_mir::{CrateDef, ItemKind};
 use std::fmt::{Display, Formatter};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     fs::File,
     io::{BufWriter, Write},
 };
@@ -72,6 +77,43 @@ pub fn collect_reachable_items(
     (sorted_items, collector.call_graph)
 }
 
+/// Build the report of the items of a model, written with `--reachability-report`.
+pub fn reachability_report(
+    roots: &[MonoItem],
+    items: &[MonoItem],
+    call_graph: &CallGraph,
+    transformer: &BodyTransformation,
+) -> ReachabilityReport {
+    let mut items: Vec<ReachableItem> = items
+        .iter()
+        .filter_map(|item| {
+            let (krate, stubbed) = match item {
+                MonoItem::Fn(instance) => (instance.def.krate(), transformer.is_stubbed(*instance)),
+                MonoItem::Static(def) => (def.krate(), false),
+                MonoItem::GlobalAsm(_) => return None,
+            };
+            let node = Node(item.clone());
+            let uses: BTreeSet<String> = call_graph
+                .edges
+                .get(&node)
+                .into_iter()
+                .flatten()
+                .filter(|succ| !matches!(succ.0.item, MonoItem::GlobalAsm(_)))
+                .map(|succ| succ.to_string())
+                .collect();
+            Some(ReachableItem {
+                name: node.to_string(),
+                krate: krate.name,
+                stubbed,
+                uses: uses.into_iter().collect(),
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    let harnesses = roots.iter().map(|root| Node(root.clone()).to_string()).collect();
+    ReachabilityReport { harnesses, items }
+}
+
 /// Collect all (top-level) items in the crate that matches the given predicate.
 /// An item can only be a root if they are a non-generic function.
 pub fn filter_crate_items<F>(tcx: TyCtxt, predicate: F) -> Vec<Instance>
@@ -118,10 +160,13 @@ pub fn filter_const_crate_items<F>(
         if let Ok(instance) = Instance::try_from(item) {
             if predicate(tcx, instance) {
                 let body = transformer.body(tcx, instance);
-                let mut collector =
-                    MonoItemsFnCollector { tcx, body: &body, collected: FxHashSet::default() };
+                let mut collector = MonoItemsFnCollector::new(tcx, &body);
                 collector.visit_body(&body);
                 roots.extend(collector.collected.into_iter());
+                roots.extend(collector.vtable_methods.into_iter().map(|(_, item)| CollectedItem {
+                    item,
+                    reason: CollectionReason::VTableMethod,
+                }));
             }
         }
     }
@@ -156,6 +201,11 @@ struct MonoItemsCollector<'tcx, 'a> {
     queue: Vec<MonoItem>,
     /// Call graph used for dataflow analysis.
     call_graph: CallGraph,
+    /// Trait methods invoked via dynamic dispatch in the items visited so far.
+    virtual_calls: FxHashSet<DefId>,
+    /// Vtable methods of trait methods that haven't been invoked via dynamic dispatch yet, with
+    /// the item where the vtable was created.
+    pending_vtable_methods: HashMap<DefId, Vec<(MonoItem, MonoItem)>>,
 }
 
 impl<'tcx, 'a> MonoItemsCollector<'tcx, 'a> {
@@ -165,6 +215,8 @@ pub fn new(tcx: TyCtxt<'tcx>, transformer: &'a mut BodyTransformation) -> Self {
             collected: FxHashSet::default(),
             queue: vec![],
             call_graph: CallGraph::default(),
+            virtual_calls: FxHashSet::default(),
+            pending_vtable_methods: HashMap::default(),
             transformer,
         }
     }
@@ -203,10 +255,34 @@ fn reachable_items(&mut self) {
     fn visit_fn(&mut self, instance: Instance) -> Vec<CollectedItem> {
         let _guard = debug_span!("visit_fn", function=?instance).entered();
         let body = self.transformer.body(self.tcx, instance);
-        let mut collector =
-            MonoItemsFnCollector { tcx: self.tcx, collected: FxHashSet::default(), body: &body };
+        let mut collector = MonoItemsFnCollector::new(self.tcx, &body);
         collector.visit_body(&body);
-        collector.collected.into_iter().collect()
+        let mut next_items: Vec<_> = collector.collected.into_iter().collect();
+
+        // Release the vtable methods that became reachable.
+        for trait_method in collector.virtual_calls {
+            if self.virtual_calls.insert(trait_method) {
+                let pending = self.pending_vtable_methods.remove(&trait_method).unwrap_or_default();
+                for (from, item) in pending {
+                    trace!(?from, ?item, "visit_fn release vtable method");
+                    self.call_graph.add_edge(from, item.clone(), CollectionReason::VTableMethod);
+                    if !self.collected.contains(&item) {
+                        self.queue.push(item);
+                    }
+                }
+            }
+        }
+        for (trait_method, item) in collector.vtable_methods {
+            if self.virtual_calls.contains(&trait_method) {
+                next_items.push(CollectedItem { item, reason: CollectionReason::VTableMethod });
+            } else {
+                self.pending_vtable_methods
+                    .entry(trait_method)
+                    .or_default()
+                    .push((MonoItem::Fn(instance), item));
+            }
+        }
+        next_items
     }
 
     /// Visit a static object and collect drop / initialization functions.
@@ -242,10 +318,25 @@ fn visit_asm(&mut self, item: &MonoItem) {
 struct MonoItemsFnCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     collected: FxHashSet<CollectedItem>,
+    /// The vtable methods of the vtables created in this function, with the trait method they
+    /// implement. They are only reachable if the trait method is invoked via dynamic dispatch.
+    vtable_methods: Vec<(DefId, MonoItem)>,
+    /// The trait methods invoked via dynamic dispatch in this function.
+    virtual_calls: FxHashSet<DefId>,
     body: &'a Body,
 }
 
 impl<'a, 'tcx> MonoItemsFnCollector<'a, 'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, body: &'a Body) -> Self {
+        MonoItemsFnCollector {
+            tcx,
+            collected: FxHashSet::default(),
+            vtable_methods: vec![],
+            virtual_calls: FxHashSet::default(),
+            body,
+        }
+    }
+
     /// Collect the implementation of all trait methods and its supertrait methods for the given
     /// concrete type.
     fn collect_vtable_methods(&mut self, concrete_ty: Ty, trait_ty: Ty) {
@@ -274,14 +365,24 @@ fn collect_vtable_methods(&mut self, concrete_ty: Ty, trait_ty: Ty) {
                     None
                 }
                 VtblEntry::Method(instance) => {
+                    let trait_method = trait_method_of(self.tcx, instance.def_id());
                     let instance = rustc_internal::stable(instance);
-                    should_codegen_locally(&instance).then_some(MonoItem::Fn(instance))
+                    should_codegen_locally(&instance)
+                        .then_some((trait_method, MonoItem::Fn(instance)))
                 }
             });
             trace!(methods=?methods.clone().collect::<Vec<_>>(), "collect_vtable_methods");
-            self.collected.extend(
-                methods.map(|item| CollectedItem { item, reason: CollectionReason::VTableMethod }),
-            );
+            for (trait_method, item) in methods {
+                match trait_method {
+                    Some(trait_method) => self.vtable_methods.push((trait_method, item)),
+                    // We don't know which trait method this entry implements, e.g., for closures,
+                    // so we collect it right away.
+                    None => {
+                        self.collected
+                            .insert(CollectedItem { item, reason: CollectionReason::VTableMethod });
+                    }
+                }
+            }
         }
 
         // Add the destructor for the concrete type.
@@ -293,8 +394,11 @@ fn collect_vtable_methods(&mut self, concrete_ty: Ty, trait_ty: Ty) {
     fn collect_instance(&mut self, instance: Instance, is_direct_call: bool) {
         let should_collect = match instance.kind {
             InstanceKind::Virtual { .. } => {
-                // Instance definition has no body.
+                // Instance definition has no body, but the vtable methods that implement it are
+                // now reachable.
                 assert!(is_direct_call, "Expected direct call {instance:?}");
+                let def_id = rustc_internal::internal(self.tcx, instance.def.def_id());
+                self.virtual_calls.insert(trait_method_of(self.tcx, def_id).unwrap_or(def_id));
                 false
             }
             InstanceKind::Intrinsic => {
@@ -484,6 +588,13 @@ fn extract_unsize_coercion(tcx: TyCtxt, orig_ty: Ty, dst_trait: Ty) -> (Ty, Ty)
     (rustc_internal::stable(src_ty), rustc_internal::stable(dst_ty))
 }
 
+/// Return the trait method that the given method implements, or the method itself if it is a
+/// trait method. Return `None` if the item isn't an associated function, e.g., for closures.
+fn trait_method_of(tcx: TyCtxt, def_id: DefId) -> Option<DefId> {
+    let item = tcx.opt_associated_item(def_id)?;
+    Some(item.trait_item_def_id.unwrap_or(def_id))
+}
+
 /// Convert a `MonoItem` into a stable `Fingerprint` which can be used as a stable hash across
 /// compilation sessions. This allow us to provide a stable deterministic order to codegen.
 fn to_fingerprint(tcx: TyCtxt, item: &MonoItem) -> Fingerprint {
@@ -577,6 +688,20 @@ fn add_edges(&mut self, from: MonoItem, to: &[CollectedItem]) {
         }
     }
 
+    /// The items reachable from the given item, including itself.
+    pub fn reachable_from(&self, root: &MonoItem) -> HashSet<MonoItem> {
+        let mut reachable = HashSet::from([root.clone()]);
+        let mut queue = vec![Node(root.clone())];
+        while let Some(node) = queue.pop() {
+            for succ in self.edges.get(&node).into_iter().flatten() {
+                if reachable.insert(succ.0.item.clone()) {
+                    queue.push(Node::from(succ.clone()));
+                }
+            }
+        }
+        reachable
+    }
+
     /// Print the graph in DOT format to a file.
     /// See <https://graphviz.org/doc/info/lang.html> for more information.
     fn dump_dot(&self, tcx: TyCtxt) -> std::io::Result<()> {
@@ -541,6 +541,10 @@ fn visit_rvalue(&mut self, rvalue: &mut Rvalue) {
 
     fn visit_operand(&mut self, _operand: &mut Operand) {}
 
+    fn visit_place(&mut self, _place: &mut Place) {}
+
+    fn visit_local(&mut self, _local: &mut Local) {}
+
     fn super_body(&mut self, body: &mut MutableBody) {
         for bb in body.blocks.iter_mut() {
             self.visit_basic_block(bb);
@@ -556,7 +560,8 @@ fn super_basic_block(&mut self, bb: &mut BasicBlock) {
 
     fn super_statement(&mut self, stmt: &mut Statement) {
         match &mut stmt.kind {
-            StatementKind::Assign(_, rvalue) => {
+            StatementKind::Assign(place, rvalue) => {
+                self.visit_place(place);
                 self.visit_rvalue(rvalue);
             }
             StatementKind::Intrinsic(intrisic) => match intrisic {
@@ -573,17 +578,18 @@ fn super_statement(&mut self, stmt: &mut Statement) {
                     self.visit_operand(count);
                 }
             },
-            StatementKind::FakeRead(_, _)
-            | StatementKind::SetDiscriminant { .. }
-            | StatementKind::Deinit(_)
-            | StatementKind::StorageLive(_)
-            | StatementKind::StorageDead(_)
-            | StatementKind::Retag(_, _)
-            | StatementKind::PlaceMention(_)
-            | StatementKind::AscribeUserType { .. }
-            | StatementKind::Coverage(_)
-            | StatementKind::ConstEvalCounter
-            | StatementKind::Nop => {}
+            StatementKind::FakeRead(_, place)
+            | StatementKind::SetDiscriminant { place, .. }
+            | StatementKind::Deinit(place)
+            | StatementKind::Retag(_, place)
+            | StatementKind::PlaceMention(place)
+            | StatementKind::AscribeUserType { place, .. } => {
+                self.visit_place(place);
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                self.visit_local(local);
+            }
+            StatementKind::Coverage(_) | StatementKind::ConstEvalCounter | StatementKind::Nop => {}
         }
     }
 
@@ -593,11 +599,12 @@ fn super_terminator(&mut self, term: &mut Terminator) {
             TerminatorKind::Assert { cond, .. } => {
                 self.visit_operand(cond);
             }
-            TerminatorKind::Call { func, args, .. } => {
+            TerminatorKind::Call { func, args, destination, .. } => {
                 self.visit_operand(func);
                 for arg in args {
                     self.visit_operand(arg);
                 }
+                self.visit_place(destination);
             }
             TerminatorKind::SwitchInt { discr, .. } => {
                 self.visit_operand(discr);
@@ -605,11 +612,13 @@ fn super_terminator(&mut self, term: &mut Terminator) {
             TerminatorKind::InlineAsm { .. } => {
                 // we don't support inline assembly.
             }
+            TerminatorKind::Drop { place, .. } => {
+                self.visit_place(place);
+            }
             TerminatorKind::Return
             | TerminatorKind::Goto { .. }
             | TerminatorKind::Resume
             | TerminatorKind::Abort
-            | TerminatorKind::Drop { .. }
             | TerminatorKind::Unreachable => {}
         }
     }
@@ -635,9 +644,13 @@ fn super_rvalue(&mut self, rvalue: &mut Rvalue) {
             Rvalue::UnaryOp(_, op) | Rvalue::Use(op) => {
                 self.visit_operand(op);
             }
-            Rvalue::AddressOf(..) => {}
-            Rvalue::CopyForDeref(_) | Rvalue::Discriminant(_) | Rvalue::Len(_) => {}
-            Rvalue::Ref(..) => {}
+            Rvalue::AddressOf(_, place)
+            | Rvalue::CopyForDeref(place)
+            | Rvalue::Discriminant(place)
+            | Rvalue::Len(place)
+            | Rvalue::Ref(_, _, place) => {
+                self.visit_place(place);
+            }
             Rvalue::ThreadLocalRef(_) => {}
             Rvalue::NullaryOp(..) => {}
         }
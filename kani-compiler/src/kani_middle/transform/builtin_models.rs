@@ -0,0 +1,142 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module contains the pass that replaces the calls to builtins by calls to their models.
+//!
+//! A builtin is a function without a body that Kani can't verify, i.e., a foreign function such as
+//! a compiler builtin (e.g., `__rust_probestack`), or a Rust intrinsic. A model is a function
+//! annotated with `#[kani::builtin_model("<symbol>")]`, which replaces every call to the foreign
+//! function with the given symbol, or to the intrinsic with the given name, e.g.:
+//!
+//! ```ignore
+//! #[kani::builtin_model("__udivti3")]
+//! fn udivti3_model(a: u128, b: u128) -> u128 {
+//!     a / b
+//! }
+//! ```
+//!
+//! The model must have the same signature as the builtin.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::reachability::filter_crate_items;
+use crate::kani_middle::transform::body::{MutMirVisitor, MutableBody};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, ConstOperand, LocalDecl, Operand, Terminator, TerminatorKind};
+use stable_mir::ty::{MirConst, RigidTy, Span, TyKind};
+use std::collections::HashMap;
+use tracing::{debug, trace};
+
+/// Replace the calls to builtins that have a model by calls to the model.
+#[derive(Debug)]
+pub struct BuiltinModelPass {
+    /// The models of the local crate, keyed by the symbol of the builtin.
+    models: HashMap<String, Instance>,
+}
+
+impl TransformPass for BuiltinModelPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        !self.models.is_empty()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let mut new_body = MutableBody::from(body);
+        let locals = new_body.locals().to_vec();
+        let mut visitor =
+            BuiltinModelVisitor { tcx, changed: false, locals, models: &self.models, instance };
+        visitor.visit_body(&mut new_body);
+        (visitor.changed, new_body.into())
+    }
+}
+
+struct BuiltinModelVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    changed: bool,
+    locals: Vec<LocalDecl>,
+    models: &'a HashMap<String, Instance>,
+    /// The function being transformed.
+    instance: Instance,
+}
+
+impl<'a, 'tcx> MutMirVisitor for BuiltinModelVisitor<'a, 'tcx> {
+    fn visit_terminator(&mut self, term: &mut Terminator) {
+        let span = term.span;
+        if let TerminatorKind::Call { func, .. } = &mut term.kind
+            && let TyKind::RigidTy(RigidTy::FnDef(def, args)) =
+                func.ty(&self.locals).unwrap().kind()
+            && let Ok(callee) = Instance::resolve(def, &args)
+            && !callee.has_body()
+        {
+            let symbol = callee.intrinsic_name().unwrap_or_else(|| callee.mangled_name());
+            if let Some(model) = self.models.get(&symbol)
+                && check_model_signature(self.tcx, *model, callee, span)
+            {
+                debug!(function=?self.instance.name(), ?symbol, "replace builtin");
+                let const_ = MirConst::try_new_zero_sized(model.ty()).unwrap();
+                *func = Operand::Constant(ConstOperand { span, user_ty: None, const_ });
+                self.changed = true;
+            }
+        }
+        self.super_terminator(term);
+    }
+}
+
+impl BuiltinModelPass {
+    /// Build the pass with the models of the local crate.
+    pub fn new(tcx: TyCtxt) -> BuiltinModelPass {
+        let models = filter_crate_items(tcx, |tcx, instance| {
+            KaniAttributes::for_instance(tcx, instance).builtin_model().is_some()
+        })
+        .into_iter()
+        .map(|model| (KaniAttributes::for_instance(tcx, model).builtin_model().unwrap(), model))
+        .collect();
+        BuiltinModelPass { models }
+    }
+}
+
+/// Whether the local crate has a model for the builtin with the given symbol, e.g., a model of
+/// `__rust_alloc` declared with `#[kani::allocator_model]`.
+pub fn has_builtin_model(tcx: TyCtxt, symbol: &str) -> bool {
+    !filter_crate_items(tcx, |tcx, instance| {
+        KaniAttributes::for_instance(tcx, instance).builtin_model().is_some_and(|s| s == symbol)
+    })
+    .is_empty()
+}
+
+/// Check that the signature of `model` matches the one of the builtin it replaces, and emit an
+/// error otherwise.
+fn check_model_signature(tcx: TyCtxt, model: Instance, builtin: Instance, span: Span) -> bool {
+    let (Ok(model_abi), Ok(builtin_abi)) = (model.fn_abi(), builtin.fn_abi()) else {
+        return false;
+    };
+    let model_args = model_abi.args.iter().map(|arg| arg.ty);
+    let builtin_args: Vec<_> = builtin_abi.args.iter().map(|arg| arg.ty).collect();
+    if model_args.eq(builtin_args.iter().copied()) && model_abi.ret.ty == builtin_abi.ret.ty {
+        return true;
+    }
+    let builtin_args: Vec<String> = builtin_args.iter().map(|ty| ty.to_string()).collect();
+    tcx.dcx().span_err(
+        rustc_internal::internal(tcx, span),
+        format!(
+            "the model `{}` of `{}` must have the signature `fn({}) -> {}`",
+            model.name(),
+            builtin.name(),
+            builtin_args.join(", "),
+            builtin_abi.ret.ty
+        ),
+    );
+    false
+}
@@ -0,0 +1,546 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A transformation pass that instruments the code to detect accesses through a reference that
+//! was invalidated by an aliasing access, following a simplified version of Stacked Borrows.
+//!
+//! Every reference and raw pointer local gets a shadow local with its tag, and the Kani library
+//! tracks the borrow stack of a non-deterministically chosen byte, i.e., the tags that may access
+//! it. Creating a mutable reference pushes a fresh tag on the stack, and creating a raw pointer
+//! pushes the tag shared by all raw pointers. An access through a tag removes the mutable
+//! references above it, and a write also removes the raw pointers above it. Every access through
+//! a pointer, and every access to a local whose address is taken, is instrumented with:
+//!
+//! ```ignore
+//! assert!(aliasing_access(&raw const place, size, tag, is_write));
+//! ```
+//!
+//! The tags of the pointer arguments and of the returned pointer cross function boundaries through
+//! global variables of the Kani library. Shared references use the tag of the pointer they are
+//! derived from, so they are never invalidated. The tags of the pointers stored in memory, of the
+//! pointers to unsized types, and of the pointers created by the standard library are unknown, and
+//! the accesses through them aren't checked.
+
+use crate::args::ExtraChecks;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::check_uninit::{get_mem_init_fn_def, is_harness};
+use crate::kani_middle::transform::check_union::{address_of, insert_call};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    Body, BorrowKind, ConstOperand, Local, MutBorrowKind, Mutability, NonDivergingIntrinsic,
+    Operand, Place, ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind,
+    RETURN_LOCAL,
+};
+use stable_mir::ty::{FnDef, GenericArgKind, GenericArgs, MirConst, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::CrateDef;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::trace;
+
+/// The crates that are not instrumented, so the pointers they create have an unknown tag.
+const LIBRARY_CRATES: &[&str] = &["alloc", "core", "kani", "kani_core", "std"];
+
+/// The tag of the pointers whose origin isn't known. Must match the Kani library.
+const UNKNOWN_TAG: u128 = 0;
+/// The tag of the accesses to a local through its name. Must match the Kani library.
+const OWNER_TAG: u128 = 1;
+
+const KANI_INITIALIZE_BORROW_STACK_DIAGNOSTIC: &str = "KaniInitializeBorrowStack";
+const KANI_ALIASING_ACCESS_DIAGNOSTIC: &str = "KaniAliasingAccess";
+const KANI_ALIASING_RETAG_DIAGNOSTIC: &str = "KaniAliasingRetag";
+const KANI_SET_ARGUMENT_TAG_DIAGNOSTIC: &str = "KaniSetArgumentTag";
+const KANI_ARGUMENT_TAG_DIAGNOSTIC: &str = "KaniArgumentTag";
+const KANI_RESET_ARGUMENT_TAGS_DIAGNOSTIC: &str = "KaniResetArgumentTags";
+const KANI_SET_RETURN_TAG_DIAGNOSTIC: &str = "KaniSetReturnTag";
+const KANI_RETURN_TAG_DIAGNOSTIC: &str = "KaniReturnTag";
+
+/// Instrument the code with checks for accesses through invalidated references.
+#[derive(Debug)]
+pub struct AliasingPass {
+    pub check_type: CheckType,
+    /// Used to cache FnDef lookups of the injected borrow tracking functions.
+    pub mem_init_fn_cache: HashMap<&'static str, FnDef>,
+}
+
+impl TransformPass for AliasingPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        let args = query_db.args();
+        args.ub_check.contains(&ExtraChecks::Aliasing)
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !is_instrumented(instance) {
+            return (false, body);
+        }
+        let mut instrumenter = AliasingInstrumenter::new(tcx, self, body);
+        instrumenter.instrument();
+        // Choose the tracked byte before anything else runs.
+        if is_harness(instance, tcx) {
+            let mut source = instrumenter.entry_source();
+            let instance = instrumenter.resolve(KANI_INITIALIZE_BORROW_STACK_DIAGNOSTIC, None);
+            insert_call(
+                &mut instrumenter.body,
+                instance,
+                vec![],
+                &mut source,
+                InsertPosition::Before,
+            );
+        }
+        (true, instrumenter.body.into())
+    }
+}
+
+/// Whether the body of `instance` is instrumented, so it exchanges the tags of its pointer
+/// arguments and of its returned pointer with its callers.
+fn is_instrumented(instance: Instance) -> bool {
+    instance.has_body() && !LIBRARY_CRATES.contains(&instance.def.krate().name.as_str())
+}
+
+/// The pointer whose tag is used to access memory.
+#[derive(Clone, Copy, Debug)]
+enum Tag {
+    /// The local itself.
+    Owner,
+    /// The pointer stored in a local with a shadow tag.
+    Shadow(Local),
+}
+
+/// An access to the memory of `place` through the pointer `tag`.
+#[derive(Clone, Debug)]
+struct Access {
+    place: Place,
+    tag: Tag,
+    write: bool,
+}
+
+struct AliasingInstrumenter<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    pass: &'a mut AliasingPass,
+    body: MutableBody,
+    /// The shadow local with the tag of each local of a pointer type.
+    shadow: BTreeMap<Local, Local>,
+    /// The locals whose address is taken, so they may be accessed through a pointer.
+    address_taken: HashSet<Local>,
+}
+
+impl<'a, 'tcx> AliasingInstrumenter<'a, 'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, pass: &'a mut AliasingPass, body: Body) -> Self {
+        let address_taken = body
+            .blocks
+            .iter()
+            .flat_map(|block| &block.statements)
+            .filter_map(|stmt| match &stmt.kind {
+                StatementKind::Assign(
+                    _,
+                    Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place),
+                ) if !place.projection.iter().any(|elem| matches!(elem, ProjectionElem::Deref)) => {
+                    Some(place.local)
+                }
+                _ => None,
+            })
+            .collect();
+        let mut body = MutableBody::from(body);
+        let pointers: Vec<Local> =
+            (0..body.locals().len()).filter(|local| is_pointer(body.locals()[*local].ty)).collect();
+        let shadow = pointers
+            .into_iter()
+            .map(|local| {
+                let span = body.locals()[local].span;
+                (local, body.new_local(Ty::unsigned_ty(UintTy::U64), span, Mutability::Mut))
+            })
+            .collect();
+        AliasingInstrumenter { tcx, pass, body, shadow, address_taken }
+    }
+
+    fn instrument(&mut self) {
+        // Instrument the instructions in reverse order, so the sources of the previous ones are
+        // still valid.
+        for bb in (0..self.body.blocks().len()).rev() {
+            let block = self.body.blocks()[bb].clone();
+            self.instrument_terminator(bb, block.terminator);
+            for (idx, stmt) in block.statements.into_iter().enumerate().rev() {
+                self.instrument_statement(SourceInstruction::Statement { idx, bb }, stmt);
+            }
+        }
+        self.initialize_shadow();
+    }
+
+    fn entry_source(&self) -> SourceInstruction {
+        if self.body.blocks()[0].statements.is_empty() {
+            SourceInstruction::Terminator { bb: 0 }
+        } else {
+            SourceInstruction::Statement { idx: 0, bb: 0 }
+        }
+    }
+
+    /// Initialize the tags of the pointer arguments from the caller, and clear the tags of the
+    /// other pointers.
+    fn initialize_shadow(&mut self) {
+        let mut source = self.entry_source();
+        let position = InsertPosition::Before;
+        let shadow: Vec<(Local, Local)> = self.shadow.iter().map(|(l, s)| (*l, *s)).collect();
+        for (local, shadow) in shadow {
+            if local > 0 && local <= self.body.arg_count() {
+                let span = source.span(self.body.blocks());
+                let idx = self.body.new_uint_operand((local - 1) as _, UintTy::Usize, span);
+                let instance = self.resolve(KANI_ARGUMENT_TAG_DIAGNOSTIC, None);
+                self.body.insert_call(&instance, &mut source, position, vec![idx], shadow.into());
+            } else {
+                let unknown = self.tag_operand(UNKNOWN_TAG, &source);
+                self.body.assign_to(shadow.into(), Rvalue::Use(unknown), &mut source, position);
+            }
+        }
+        let instance = self.resolve(KANI_RESET_ARGUMENT_TAGS_DIAGNOSTIC, None);
+        insert_call(&mut self.body, instance, vec![], &mut source, position);
+    }
+
+    fn instrument_statement(&mut self, mut source: SourceInstruction, stmt: Statement) {
+        let position = InsertPosition::Before;
+        match stmt.kind {
+            StatementKind::Assign(place, rvalue) => {
+                let tag = self.rvalue_tag(&rvalue, &mut source);
+                self.check_place(&place, true, &mut source, position);
+                if let Some(shadow) = self.shadow_of(&place) {
+                    let tag = tag.unwrap_or_else(|| self.tag_operand(UNKNOWN_TAG, &source));
+                    self.body.assign_to(shadow.into(), Rvalue::Use(tag), &mut source, position);
+                }
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => {
+                if let (Some(src), Some(dst)) = (deref_place(&copy.src), deref_place(&copy.dst)) {
+                    self.check_place(&src, false, &mut source, position);
+                    self.check_place(&dst, true, &mut source, position);
+                }
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(_))
+            | StatementKind::FakeRead(..)
+            | StatementKind::SetDiscriminant { .. }
+            | StatementKind::Deinit(_)
+            | StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::Retag(..)
+            | StatementKind::PlaceMention(_)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Coverage(_)
+            | StatementKind::ConstEvalCounter
+            | StatementKind::Nop => {}
+        }
+    }
+
+    fn instrument_terminator(&mut self, bb: usize, terminator: Terminator) {
+        let mut source = SourceInstruction::Terminator { bb };
+        match terminator.kind {
+            TerminatorKind::Call { func, args, destination, target, .. } => {
+                self.instrument_call(&func, &args, &destination, target.is_some(), source);
+            }
+            TerminatorKind::Return => {
+                if let Some(shadow) = self.shadow.get(&RETURN_LOCAL).copied() {
+                    let instance = self.resolve(KANI_SET_RETURN_TAG_DIAGNOSTIC, None);
+                    let args = vec![Operand::Copy(Place::from(shadow))];
+                    insert_call(
+                        &mut self.body,
+                        instance,
+                        args,
+                        &mut source,
+                        InsertPosition::Before,
+                    );
+                }
+            }
+            TerminatorKind::SwitchInt { discr, .. } => {
+                if let Some(place) = operand_place(&discr) {
+                    self.check_place(&place, false, &mut source, InsertPosition::Before);
+                }
+            }
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::Drop { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Unreachable
+            | TerminatorKind::Assert { .. }
+            | TerminatorKind::InlineAsm { .. } => {}
+        }
+    }
+
+    fn instrument_call(
+        &mut self,
+        func: &Operand,
+        args: &[Operand],
+        destination: &Place,
+        returns: bool,
+        mut source: SourceInstruction,
+    ) {
+        let (before, after) = (InsertPosition::Before, InsertPosition::After);
+        for arg in args.iter().filter_map(operand_place) {
+            self.check_place(&arg, false, &mut source, before);
+        }
+        // Only the callees that are instrumented read the tags, and calls through function
+        // pointers may not pass the arguments in the same order, e.g., to closures.
+        let callee = match func.ty(self.body.locals()).unwrap().kind() {
+            TyKind::RigidTy(RigidTy::FnDef(def, fn_args)) => Instance::resolve(def, &fn_args).ok(),
+            _ => None,
+        };
+        let instrumented = callee.is_some_and(is_instrumented);
+        if instrumented {
+            for (idx, arg) in args.iter().enumerate() {
+                if is_pointer(arg.ty(self.body.locals()).unwrap()) {
+                    let tag = self.operand_tag(arg, &source);
+                    let span = source.span(self.body.blocks());
+                    let idx = self.body.new_uint_operand(idx as _, UintTy::Usize, span);
+                    let instance = self.resolve(KANI_SET_ARGUMENT_TAG_DIAGNOSTIC, None);
+                    insert_call(&mut self.body, instance, vec![idx, tag], &mut source, before);
+                }
+            }
+        }
+        if returns {
+            if let Some(shadow) = self.shadow_of(destination) {
+                if instrumented {
+                    let instance = self.resolve(KANI_RETURN_TAG_DIAGNOSTIC, None);
+                    self.body.insert_call(&instance, &mut source, after, vec![], shadow.into());
+                } else {
+                    let unknown = self.tag_operand(UNKNOWN_TAG, &source);
+                    self.body.assign_to(shadow.into(), Rvalue::Use(unknown), &mut source, after);
+                }
+            }
+            self.check_place(destination, true, &mut source, after);
+        }
+    }
+
+    /// Check the accesses performed by `rvalue`, and return the tag of the pointer that it
+    /// creates, if it's known.
+    fn rvalue_tag(&mut self, rvalue: &Rvalue, source: &mut SourceInstruction) -> Option<Operand> {
+        let position = InsertPosition::Before;
+        match rvalue {
+            Rvalue::Ref(_, kind, place) => {
+                // Two-phase borrows may be used after a read of the place they borrow, so they are
+                // treated like raw pointers.
+                let unique = matches!(
+                    kind,
+                    BorrowKind::Mut {
+                        kind: MutBorrowKind::Default | MutBorrowKind::ClosureCapture
+                    }
+                );
+                let parent = self.check_place(place, unique, source, position);
+                match kind {
+                    BorrowKind::Mut { .. } => Some(self.retag(place, parent, unique, source)),
+                    BorrowKind::Shared | BorrowKind::Fake(_) => parent,
+                }
+            }
+            Rvalue::AddressOf(_, place) => {
+                let parent = self.check_place(place, false, source, position);
+                Some(self.retag(place, parent, false, source))
+            }
+            Rvalue::Use(operand) | Rvalue::Cast(_, operand, _) => {
+                if let Some(place) = operand_place(operand) {
+                    self.check_place(&place, false, source, position);
+                }
+                Some(self.operand_tag(operand, source))
+            }
+            Rvalue::CopyForDeref(place) => {
+                self.check_place(place, false, source, position);
+                self.shadow_of(place).map(|shadow| Operand::Copy(shadow.into()))
+            }
+            Rvalue::Discriminant(place) | Rvalue::Len(place) => {
+                self.check_place(place, false, source, position);
+                None
+            }
+            Rvalue::Aggregate(_, operands) => {
+                for place in operands.iter().filter_map(operand_place) {
+                    self.check_place(&place, false, source, position);
+                }
+                None
+            }
+            Rvalue::BinaryOp(_, lhs, rhs) | Rvalue::CheckedBinaryOp(_, lhs, rhs) => {
+                for place in [lhs, rhs].into_iter().filter_map(operand_place) {
+                    self.check_place(&place, false, source, position);
+                }
+                None
+            }
+            Rvalue::Repeat(operand, _)
+            | Rvalue::ShallowInitBox(operand, _)
+            | Rvalue::UnaryOp(_, operand) => {
+                if let Some(place) = operand_place(operand) {
+                    self.check_place(&place, false, source, position);
+                }
+                None
+            }
+            Rvalue::NullaryOp(..) | Rvalue::ThreadLocalRef(_) => None,
+        }
+    }
+
+    /// Check the accesses to memory performed by using `place`, and return the tag through which
+    /// `place` itself is accessed, if it's known.
+    fn check_place(
+        &mut self,
+        place: &Place,
+        write: bool,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) -> Option<Operand> {
+        let mut tag = None;
+        for access in self.accesses(place, write) {
+            let tag_operand = match access.tag {
+                Tag::Owner => self.tag_operand(OWNER_TAG, source),
+                Tag::Shadow(shadow) => Operand::Copy(shadow.into()),
+            };
+            self.check_access(&access, tag_operand.clone(), source, position);
+            if access.place == *place {
+                tag = Some(tag_operand);
+            }
+        }
+        tag
+    }
+
+    /// The accesses to memory performed by using `place` whose tag is known: the access to a local
+    /// whose address is taken, and the access through the pointer stored in a local.
+    fn accesses(&self, place: &Place, write: bool) -> Vec<Access> {
+        let derefs: Vec<usize> = place
+            .projection
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, elem)| matches!(elem, ProjectionElem::Deref).then_some(idx))
+            .collect();
+        let len = place.projection.len();
+        let prefix =
+            |end: usize| Place { local: place.local, projection: place.projection[..end].to_vec() };
+        let mut accesses = vec![];
+        // The pointers used by the following dereferences are read.
+        let first = derefs.first().copied().unwrap_or(len);
+        if self.address_taken.contains(&place.local) {
+            accesses.push(Access {
+                place: prefix(first),
+                tag: Tag::Owner,
+                write: write && first == len,
+            });
+        }
+        if let (0, Some(shadow)) = (first, self.shadow.get(&place.local)) {
+            let second = derefs.get(1).copied().unwrap_or(len);
+            let tag = Tag::Shadow(*shadow);
+            accesses.push(Access { place: prefix(second), tag, write: write && second == len });
+        }
+        accesses
+    }
+
+    fn check_access(
+        &mut self,
+        access: &Access,
+        tag: Operand,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) {
+        let ty = access.place.ty(self.body.locals()).unwrap();
+        let Some(size) = sized_size(ty) else { return };
+        let span = source.span(self.body.blocks());
+        let size = self.body.new_uint_operand(size as _, UintTy::Usize, span);
+        let write = self.bool_operand(access.write, source);
+        let ptr = address_of(&mut self.body, access.place.clone(), source, position);
+        let instance = self.resolve(KANI_ALIASING_ACCESS_DIAGNOSTIC, Some(ty));
+        let allowed =
+            insert_call(&mut self.body, instance, vec![ptr, size, tag, write], source, position);
+        self.body.insert_check(
+            self.tcx,
+            &self.pass.check_type,
+            source,
+            position,
+            allowed,
+            "Undefined Behavior: Access through a reference that was invalidated by an aliasing access",
+        );
+    }
+
+    /// Return the tag of a new pointer to `place` derived from a pointer with the tag `parent`.
+    fn retag(
+        &mut self,
+        place: &Place,
+        parent: Option<Operand>,
+        unique: bool,
+        source: &mut SourceInstruction,
+    ) -> Operand {
+        let ty = place.ty(self.body.locals()).unwrap();
+        let (Some(parent), Some(size)) = (parent, sized_size(ty)) else {
+            return self.tag_operand(UNKNOWN_TAG, source);
+        };
+        let position = InsertPosition::Before;
+        let span = source.span(self.body.blocks());
+        let size = self.body.new_uint_operand(size as _, UintTy::Usize, span);
+        let unique = self.bool_operand(unique, source);
+        let ptr = address_of(&mut self.body, place.clone(), source, position);
+        let instance = self.resolve(KANI_ALIASING_RETAG_DIAGNOSTIC, Some(ty));
+        let tag = insert_call(
+            &mut self.body,
+            instance,
+            vec![ptr, size, parent, unique],
+            source,
+            position,
+        );
+        Operand::Copy(tag.into())
+    }
+
+    /// The tag of a pointer operand, which is only known if it's a local.
+    fn operand_tag(&self, operand: &Operand, source: &SourceInstruction) -> Operand {
+        match operand_place(operand).and_then(|place| self.shadow_of(&place)) {
+            Some(shadow) => Operand::Copy(shadow.into()),
+            None => self.tag_operand(UNKNOWN_TAG, source),
+        }
+    }
+
+    /// The shadow local with the tag of `place`, if it's a local of a pointer type.
+    fn shadow_of(&self, place: &Place) -> Option<Local> {
+        self.shadow.get(&place.local).copied().filter(|_| place.projection.is_empty())
+    }
+
+    fn tag_operand(&self, tag: u128, source: &SourceInstruction) -> Operand {
+        let span = source.span(self.body.blocks());
+        let const_ = MirConst::try_from_uint(tag, UintTy::U64).unwrap();
+        Operand::Constant(ConstOperand { span, user_ty: None, const_ })
+    }
+
+    fn bool_operand(&self, value: bool, source: &SourceInstruction) -> Operand {
+        let span = source.span(self.body.blocks());
+        Operand::Constant(ConstOperand { span, user_ty: None, const_: MirConst::from_bool(value) })
+    }
+
+    /// Resolve one of the borrow tracking functions for the given type, if it's generic.
+    fn resolve(&mut self, diagnostic: &'static str, ty: Option<Ty>) -> Instance {
+        let fn_def = get_mem_init_fn_def(self.tcx, diagnostic, &mut self.pass.mem_init_fn_cache);
+        let generic_args = GenericArgs(ty.into_iter().map(GenericArgKind::Type).collect());
+        Instance::resolve(fn_def, &generic_args).unwrap()
+    }
+}
+
+fn is_pointer(ty: Ty) -> bool {
+    ty.kind().is_ref() || ty.kind().is_raw_ptr()
+}
+
+/// The size of `ty` in bytes, if it's sized.
+fn sized_size(ty: Ty) -> Option<usize> {
+    let shape = ty.layout().ok()?.shape();
+    shape.is_sized().then(|| shape.size.bytes())
+}
+
+/// The place read by an operand, if it isn't a constant.
+fn operand_place(operand: &Operand) -> Option<Place> {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => Some(place.clone()),
+        Operand::Constant(_) => None,
+    }
+}
+
+/// The place that an operand of a pointer type points to, if the operand isn't a constant.
+fn deref_place(operand: &Operand) -> Option<Place> {
+    let mut place = operand_place(operand)?;
+    place.projection.push(ProjectionElem::Deref);
+    Some(place)
+}
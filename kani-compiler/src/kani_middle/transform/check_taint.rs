@@ -0,0 +1,534 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A transformation pass that instruments the code to check that the values marked with
+//! `kani::secret` never flow into a `kani::public_sink`.
+//!
+//! Every local of a function gets a boolean shadow local with its taint, and the Kani library
+//! records which objects were written a tainted value. The taint of an assignment is the union of
+//! the taint of the places that it reads, where reading through a pointer also reads the taint of
+//! the object that it points to. The taint of the arguments and of the returned value crosses
+//! function boundaries through global variables of the Kani library.
+//!
+//! Every call to `kani::public_sink(value)` is instrumented with:
+//!
+//! ```ignore
+//! assert!(!(taint(value) || is_object_tainted(value)));
+//! ```
+//!
+//! With `--check-constant-time`, the branch conditions and the memory indices are checked to be
+//! untainted as well, with checks of the `constant_time` property class.
+//!
+//! Only explicit flows are tracked, i.e., branching on a secret doesn't taint the values assigned
+//! in the branches. The taint of an object is never cleared, and the functions of the Kani library
+//! are not instrumented.
+
+use crate::args::ExtraChecks;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::check_uninit::get_mem_init_fn_def;
+use crate::kani_middle::transform::check_union::{address_of, insert_call};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::mir::{
+    BinOp, Body, ConstOperand, Local, Mutability, NonDivergingIntrinsic, Operand, Place,
+    ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind, UnOp,
+    RETURN_LOCAL,
+};
+use stable_mir::ty::{FnDef, GenericArgKind, GenericArgs, MirConst, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::CrateDef;
+use std::collections::{HashMap, HashSet};
+use tracing::trace;
+
+const INDEX_MSG: &str = "Memory index depends on secret data";
+
+const KANI_SECRET_DIAGNOSTIC: &str = "KaniSecret";
+const KANI_PUBLIC_SINK_DIAGNOSTIC: &str = "KaniPublicSink";
+const KANI_TAINT_OBJECT_DIAGNOSTIC: &str = "KaniTaintObject";
+const KANI_IS_OBJECT_TAINTED_DIAGNOSTIC: &str = "KaniIsObjectTainted";
+const KANI_SET_ARGUMENT_TAINT_DIAGNOSTIC: &str = "KaniSetArgumentTaint";
+const KANI_ARGUMENT_TAINT_DIAGNOSTIC: &str = "KaniArgumentTaint";
+const KANI_SET_RETURN_TAINT_DIAGNOSTIC: &str = "KaniSetReturnTaint";
+const KANI_RETURN_TAINT_DIAGNOSTIC: &str = "KaniReturnTaint";
+const KANI_CONSTANT_TIME_CHECK_DIAGNOSTIC: &str = "KaniConstantTimeCheck";
+
+/// Instrument the code to check that secret values don't flow into public sinks.
+#[derive(Debug)]
+pub struct TaintPass {
+    pub check_type: CheckType,
+    /// Used to cache FnDef lookups of the injected taint tracking functions.
+    pub mem_init_fn_cache: HashMap<&'static str, FnDef>,
+    /// The check used for the branches and the memory indices, if `--check-constant-time` is on.
+    pub constant_time_check: Option<CheckType>,
+}
+
+impl TaintPass {
+    pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
+        let constant_time = queries.args().ub_check.contains(&ExtraChecks::ConstantTime);
+        let constant_time_check = constant_time
+            .then(|| find_fn_def(tcx, KANI_CONSTANT_TIME_CHECK_DIAGNOSTIC))
+            .flatten()
+            .map(|def| CheckType::Assert(Instance::resolve(def, &GenericArgs(vec![])).unwrap()));
+        TaintPass {
+            check_type: CheckType::new_assert(tcx),
+            mem_init_fn_cache: HashMap::new(),
+            constant_time_check,
+        }
+    }
+}
+
+impl TransformPass for TaintPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        let args = query_db.args();
+        args.ub_check.contains(&ExtraChecks::Taint)
+            || args.ub_check.contains(&ExtraChecks::ConstantTime)
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        // The taint tracking functions are part of the Kani library, so instrumenting it would
+        // cause an infinite recursion.
+        if instance.def.krate().name == "kani" {
+            return (false, body);
+        }
+        let mut instrumenter = TaintInstrumenter::new(tcx, self, body);
+        instrumenter.instrument();
+        (true, instrumenter.body.into())
+    }
+}
+
+struct TaintInstrumenter<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    pass: &'a mut TaintPass,
+    body: MutableBody,
+    /// The shadow local with the taint of each local of the original body.
+    shadow: Vec<Local>,
+    /// The locals whose address is taken, so they may be read or written through a pointer.
+    address_taken: HashSet<Local>,
+}
+
+impl<'a, 'tcx> TaintInstrumenter<'a, 'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, pass: &'a mut TaintPass, body: Body) -> Self {
+        let address_taken = body
+            .blocks
+            .iter()
+            .flat_map(|block| &block.statements)
+            .filter_map(|stmt| match &stmt.kind {
+                StatementKind::Assign(
+                    _,
+                    Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place),
+                ) if !has_deref(place) => Some(place.local),
+                _ => None,
+            })
+            .collect();
+        let mut body = MutableBody::from(body);
+        let shadow = (0..body.locals().len())
+            .map(|local| {
+                let span = body.locals()[local].span;
+                body.new_local(Ty::bool_ty(), span, Mutability::Mut)
+            })
+            .collect();
+        TaintInstrumenter { tcx, pass, body, shadow, address_taken }
+    }
+
+    fn instrument(&mut self) {
+        // Instrument the instructions in reverse order, so the sources of the previous ones are
+        // still valid.
+        for bb in (0..self.body.blocks().len()).rev() {
+            let block = self.body.blocks()[bb].clone();
+            self.instrument_terminator(bb, block.terminator);
+            for (idx, stmt) in block.statements.into_iter().enumerate().rev() {
+                self.instrument_statement(SourceInstruction::Statement { idx, bb }, stmt);
+            }
+        }
+        self.initialize_shadow();
+    }
+
+    /// Initialize the taint of the arguments from the caller, and clear the taint of the other
+    /// locals.
+    fn initialize_shadow(&mut self) {
+        let mut source = if self.body.blocks()[0].statements.is_empty() {
+            SourceInstruction::Terminator { bb: 0 }
+        } else {
+            SourceInstruction::Statement { idx: 0, bb: 0 }
+        };
+        let position = InsertPosition::Before;
+        for local in 0..self.shadow.len() {
+            let shadow = Place::from(self.shadow[local]);
+            if local > 0 && local <= self.body.arg_count() {
+                let span = source.span(self.body.blocks());
+                let idx = self.body.new_uint_operand((local - 1) as _, UintTy::Usize, span);
+                let instance = self.resolve(KANI_ARGUMENT_TAINT_DIAGNOSTIC, None);
+                self.body.insert_call(&instance, &mut source, position, vec![idx], shadow);
+            } else {
+                let value = self.bool_operand(false, &source);
+                self.body.assign_to(shadow, Rvalue::Use(value), &mut source, position);
+            }
+        }
+    }
+
+    fn instrument_statement(&mut self, mut source: SourceInstruction, stmt: Statement) {
+        let position = InsertPosition::Before;
+        match stmt.kind {
+            StatementKind::Assign(place, rvalue) => {
+                // The count of a pointer offset is an index into the memory.
+                if let Rvalue::BinaryOp(BinOp::Offset, _, count) = &rvalue {
+                    self.check_operand_constant_time(count, INDEX_MSG, &mut source, position);
+                }
+                let taint = self.rvalue_taint(&rvalue, &mut source, position);
+                self.write(&place, taint, &mut source, position);
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => {
+                if let (Some(src), Some(dst)) = (deref_place(&copy.src), deref_place(&copy.dst)) {
+                    let taint = self.read(&src, &mut source, position);
+                    self.write(&dst, taint, &mut source, position);
+                }
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(_))
+            | StatementKind::FakeRead(..)
+            | StatementKind::SetDiscriminant { .. }
+            | StatementKind::Deinit(_)
+            | StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::Retag(..)
+            | StatementKind::PlaceMention(_)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Coverage(_)
+            | StatementKind::ConstEvalCounter
+            | StatementKind::Nop => {}
+        }
+    }
+
+    fn instrument_terminator(&mut self, bb: usize, terminator: Terminator) {
+        let mut source = SourceInstruction::Terminator { bb };
+        let position = InsertPosition::Before;
+        match terminator.kind {
+            TerminatorKind::Call { func, args, destination, target, .. } => {
+                self.instrument_call(&func, &args, &destination, target.is_some(), source);
+            }
+            TerminatorKind::Drop { place, .. } => {
+                // The drop glue receives a pointer to the value.
+                let taint = self.read(&place, &mut source, position);
+                self.set_argument_taint(0, taint, &mut source);
+            }
+            TerminatorKind::Return => {
+                let taint = self.read(&Place::from(RETURN_LOCAL), &mut source, position);
+                let instance = self.resolve(KANI_SET_RETURN_TAINT_DIAGNOSTIC, None);
+                let args = vec![Operand::Copy(Place::from(taint))];
+                insert_call(&mut self.body, instance, args, &mut source, position);
+            }
+            TerminatorKind::SwitchInt { discr, .. } => {
+                let msg = "Branch condition depends on secret data";
+                self.check_operand_constant_time(&discr, msg, &mut source, position);
+            }
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Unreachable
+            | TerminatorKind::Assert { .. }
+            | TerminatorKind::InlineAsm { .. } => {}
+        }
+    }
+
+    fn instrument_call(
+        &mut self,
+        func: &Operand,
+        args: &[Operand],
+        destination: &Place,
+        returns: bool,
+        mut source: SourceInstruction,
+    ) {
+        let (before, after) = (InsertPosition::Before, InsertPosition::After);
+        let callee = match func.ty(self.body.locals()).unwrap().kind() {
+            TyKind::RigidTy(RigidTy::FnDef(def, fn_args)) => Some((def, fn_args)),
+            _ => None,
+        };
+        match callee.as_ref().and_then(|(def, _)| self.diagnostic_name(*def)).as_deref() {
+            Some(KANI_SECRET_DIAGNOSTIC) => {
+                if returns {
+                    let secret = self.bool_operand(true, &source);
+                    let taint =
+                        self.body.insert_assignment(Rvalue::Use(secret), &mut source, after);
+                    self.write(destination, taint, &mut source, after);
+                }
+            }
+            Some(KANI_PUBLIC_SINK_DIAGNOSTIC) => {
+                // A constant reference can't point to a secret.
+                let Some(value) = deref_place(&args[0]) else { return };
+                let taint = self.read(&value, &mut source, before);
+                let public = self.body.insert_assignment(
+                    Rvalue::UnaryOp(UnOp::Not, Operand::Copy(Place::from(taint))),
+                    &mut source,
+                    before,
+                );
+                self.body.insert_check(
+                    self.tcx,
+                    &self.pass.check_type,
+                    &mut source,
+                    before,
+                    public,
+                    "Secret data flows into a public sink",
+                );
+            }
+            _ => {
+                let arg_taints: Vec<Local> = args
+                    .iter()
+                    .map(|arg| match operand_place(arg) {
+                        Some(arg) => self.read(&arg, &mut source, before),
+                        None => self.union(&[], &mut source, before),
+                    })
+                    .collect();
+                for (idx, taint) in arg_taints.iter().enumerate() {
+                    self.set_argument_taint(idx, *taint, &mut source);
+                }
+                let mut taint = self.union(&arg_taints, &mut source, before);
+                // Functions without a body, e.g., intrinsics, may read and write the memory that
+                // their arguments point to.
+                let instance = callee.and_then(|(def, args)| Instance::resolve(def, &args).ok());
+                let opaque = instance.is_some_and(|instance| {
+                    !instance.has_body() && !matches!(instance.kind, InstanceKind::Virtual { .. })
+                });
+                let pointees: Vec<Place> = if opaque {
+                    args.iter()
+                        .filter(|arg| {
+                            let ty = arg.ty(self.body.locals()).unwrap();
+                            ty.kind().is_raw_ptr() || ty.kind().is_ref()
+                        })
+                        .filter_map(deref_place)
+                        .collect()
+                } else {
+                    vec![]
+                };
+                for pointee in &pointees {
+                    let pointee_taint = self.read(pointee, &mut source, before);
+                    taint = self.union(&[taint, pointee_taint], &mut source, before);
+                }
+                for pointee in &pointees {
+                    self.write(pointee, taint, &mut source, before);
+                }
+                // The callee overwrites the taint of the value returned if it is instrumented.
+                let instance = self.resolve(KANI_SET_RETURN_TAINT_DIAGNOSTIC, None);
+                let args = vec![Operand::Copy(Place::from(taint))];
+                insert_call(&mut self.body, instance, args, &mut source, before);
+                if returns {
+                    let instance = self.resolve(KANI_RETURN_TAINT_DIAGNOSTIC, None);
+                    let taint = insert_call(&mut self.body, instance, vec![], &mut source, after);
+                    self.write(destination, taint, &mut source, after);
+                }
+            }
+        }
+    }
+
+    /// Return a local with the taint of the values read by `rvalue`.
+    fn rvalue_taint(
+        &mut self,
+        rvalue: &Rvalue,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) -> Local {
+        let places: Vec<Place> = match rvalue {
+            Rvalue::AddressOf(_, place)
+            | Rvalue::CopyForDeref(place)
+            | Rvalue::Discriminant(place)
+            | Rvalue::Len(place)
+            | Rvalue::Ref(_, _, place) => vec![place.clone()],
+            Rvalue::Aggregate(_, operands) => operands.iter().filter_map(operand_place).collect(),
+            Rvalue::BinaryOp(_, lhs, rhs) | Rvalue::CheckedBinaryOp(_, lhs, rhs) => {
+                [lhs, rhs].into_iter().filter_map(operand_place).collect()
+            }
+            Rvalue::Cast(_, operand, _)
+            | Rvalue::Repeat(operand, _)
+            | Rvalue::ShallowInitBox(operand, _)
+            | Rvalue::UnaryOp(_, operand)
+            | Rvalue::Use(operand) => operand_place(operand).into_iter().collect(),
+            Rvalue::NullaryOp(..) | Rvalue::ThreadLocalRef(_) => vec![],
+        };
+        let taints: Vec<Local> =
+            places.iter().map(|place| self.read(place, source, position)).collect();
+        self.union(&taints, source, position)
+    }
+
+    /// Return a local with the taint of `place`.
+    fn read(
+        &mut self,
+        place: &Place,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) -> Local {
+        self.check_indices(place, source, position);
+        let local_taint = self.shadow[place.local];
+        if !self.in_memory(place) {
+            return local_taint;
+        }
+        let ty = place.ty(self.body.locals()).unwrap();
+        let ptr = address_of(&mut self.body, place.clone(), source, position);
+        let instance = self.resolve(KANI_IS_OBJECT_TAINTED_DIAGNOSTIC, Some(ty));
+        let object_taint = insert_call(&mut self.body, instance, vec![ptr], source, position);
+        self.union(&[local_taint, object_taint], source, position)
+    }
+
+    /// Record that a value with the given taint was written to `place`.
+    fn write(
+        &mut self,
+        place: &Place,
+        taint: Local,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) {
+        self.check_indices(place, source, position);
+        // Writing through a pointer doesn't change the taint of the pointer.
+        if !has_deref(place) {
+            let shadow = Place::from(self.shadow[place.local]);
+            let taint = Operand::Copy(Place::from(taint));
+            // Writing a part of a local keeps the taint of the other parts.
+            let rvalue = if place.projection.is_empty() {
+                Rvalue::Use(taint)
+            } else {
+                Rvalue::BinaryOp(BinOp::BitOr, Operand::Copy(shadow.clone()), taint)
+            };
+            self.body.assign_to(shadow, rvalue, source, position);
+        }
+        if self.in_memory(place) {
+            let ty = place.ty(self.body.locals()).unwrap();
+            let ptr = address_of(&mut self.body, place.clone(), source, position);
+            let instance = self.resolve(KANI_TAINT_OBJECT_DIAGNOSTIC, Some(ty));
+            let args = vec![ptr, Operand::Copy(Place::from(taint))];
+            insert_call(&mut self.body, instance, args, source, position);
+        }
+    }
+
+    /// Return a local with the union of the given taints.
+    fn union(
+        &mut self,
+        taints: &[Local],
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) -> Local {
+        let Some((first, rest)) = taints.split_first() else {
+            let untainted = self.bool_operand(false, source);
+            return self.body.insert_assignment(Rvalue::Use(untainted), source, position);
+        };
+        rest.iter().fold(*first, |union, taint| {
+            self.body.insert_binary_op(
+                BinOp::BitOr,
+                Operand::Copy(Place::from(union)),
+                Operand::Copy(Place::from(*taint)),
+                source,
+                position,
+            )
+        })
+    }
+
+    /// With `--check-constant-time`, check that the indices used by `place` are untainted.
+    fn check_indices(
+        &mut self,
+        place: &Place,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) {
+        for elem in &place.projection {
+            if let ProjectionElem::Index(index) = elem {
+                let taint = self.shadow[*index];
+                self.check_constant_time(taint, INDEX_MSG, source, position);
+            }
+        }
+    }
+
+    /// With `--check-constant-time`, check that `operand` is untainted.
+    fn check_operand_constant_time(
+        &mut self,
+        operand: &Operand,
+        msg: &str,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) {
+        if self.pass.constant_time_check.is_none() {
+            return;
+        }
+        if let Some(place) = operand_place(operand) {
+            let taint = self.read(&place, source, position);
+            self.check_constant_time(taint, msg, source, position);
+        }
+    }
+
+    /// With `--check-constant-time`, check that `taint` isn't set.
+    fn check_constant_time(
+        &mut self,
+        taint: Local,
+        msg: &str,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) {
+        let Some(check_type) = &self.pass.constant_time_check else { return };
+        let public = self.body.insert_assignment(
+            Rvalue::UnaryOp(UnOp::Not, Operand::Copy(Place::from(taint))),
+            source,
+            position,
+        );
+        self.body.insert_check(self.tcx, check_type, source, position, public, msg);
+    }
+
+    fn set_argument_taint(&mut self, idx: usize, taint: Local, source: &mut SourceInstruction) {
+        let span = source.span(self.body.blocks());
+        let idx = self.body.new_uint_operand(idx as _, UintTy::Usize, span);
+        let instance = self.resolve(KANI_SET_ARGUMENT_TAINT_DIAGNOSTIC, None);
+        let args = vec![idx, Operand::Copy(Place::from(taint))];
+        insert_call(&mut self.body, instance, args, source, InsertPosition::Before);
+    }
+
+    /// Whether `place` is in an object whose taint is tracked by the Kani library.
+    fn in_memory(&self, place: &Place) -> bool {
+        has_deref(place) || self.address_taken.contains(&place.local)
+    }
+
+    fn bool_operand(&self, value: bool, source: &SourceInstruction) -> Operand {
+        let span = source.span(self.body.blocks());
+        Operand::Constant(ConstOperand { span, user_ty: None, const_: MirConst::from_bool(value) })
+    }
+
+    fn diagnostic_name(&self, def: FnDef) -> Option<String> {
+        let def_id = rustc_internal::internal(self.tcx, def.def_id());
+        self.tcx.get_diagnostic_name(def_id).map(|name| name.to_ident_string())
+    }
+
+    /// Resolve one of the taint tracking functions for the given type, if it's generic.
+    fn resolve(&mut self, diagnostic: &'static str, ty: Option<Ty>) -> Instance {
+        let fn_def = get_mem_init_fn_def(self.tcx, diagnostic, &mut self.pass.mem_init_fn_cache);
+        let generic_args = GenericArgs(ty.into_iter().map(GenericArgKind::Type).collect());
+        Instance::resolve(fn_def, &generic_args).unwrap()
+    }
+}
+
+fn has_deref(place: &Place) -> bool {
+    place.projection.iter().any(|elem| matches!(elem, ProjectionElem::Deref))
+}
+
+/// The place read by an operand, if it isn't a constant.
+fn operand_place(operand: &Operand) -> Option<Place> {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => Some(place.clone()),
+        Operand::Constant(_) => None,
+    }
+}
+
+/// The place that an operand of a pointer type points to, if the operand isn't a constant.
+fn deref_place(operand: &Operand) -> Option<Place> {
+    let mut place = operand_place(operand)?;
+    place.projection.push(ProjectionElem::Deref);
+    Some(place)
+}
@@ -22,7 +22,7 @@
 use std::collections::HashMap;
 
 pub use delayed_ub::DelayedUbPass;
-pub use ptr_uninit::UninitPass;
+pub use ptr_uninit::{is_harness, UninitPass};
 pub use ty_layout::{PointeeInfo, PointeeLayout};
 
 mod delayed_ub;
@@ -295,11 +295,9 @@ fn build_get_and_check(
                 return;
             }
             PointeeLayout::Union { .. } => {
-                // Here we are reading from a pointer to a union.
-                // TODO: we perhaps need to check that the union at least contains an intersection
-                // of all layouts initialized.
-                let reason = "Interaction between raw pointers and unions is not yet supported.";
-                self.inject_assert_false(self.tcx, body, source, operation.position(), reason);
+                // Here we are reading from a pointer to a union, which is fine even if the union
+                // is uninitialized. Its initialization state is copied along with it, and checked
+                // when one of its fields is read.
                 return;
             }
         };
@@ -450,10 +448,8 @@ fn build_set(
                 // performing some pointer operations with it. If we are creating a union from
                 // scratch, an operation will contain a union field.
 
-                // TODO: If we don't have a union field, we are either creating a pointer to a union
-                // or assigning to one. In the former case, it is safe to return from this function,
-                // since the union must be already tracked (on creation and update). In the latter
-                // case, we should have been using union assignment instead. Nevertheless, this is
+                // If we don't have a union field, the union is written through a pointer in a way
+                // that is not tracked by the union operations, e.g., by dropping it. This is
                 // currently mitigated by injecting `assert!(false)`.
                 let union_field = match operation.union_field() {
                     Some(field) => field,
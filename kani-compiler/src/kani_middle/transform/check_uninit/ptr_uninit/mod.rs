@@ -76,7 +76,7 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
 }
 
 /// Checks if the instance is a harness -- an entry point of Kani analysis.
-fn is_harness(instance: Instance, tcx: TyCtxt) -> bool {
+pub fn is_harness(instance: Instance, tcx: TyCtxt) -> bool {
     let harness_identifiers = [
         vec![
             rustc_span::symbol::Symbol::intern("kanitool"),
@@ -21,7 +21,7 @@
         visit::{Location, PlaceContext},
         AggregateKind, CastKind, LocalDecl, MirVisitor, NonDivergingIntrinsic, Operand, Place,
         PointerCoercion, ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
-        TerminatorKind,
+        TerminatorKind, RETURN_LOCAL,
     },
     ty::{AdtKind, ConstantKind, RigidTy, TyKind},
 };
@@ -66,6 +66,7 @@ fn find_all(mut self, body: &MutableBody) -> Vec<InitRelevantInstruction> {
                         self.push_target(MemoryInitOp::LoadArgument {
                             operand: Operand::Copy(Place { local: idx, projection: vec![] }),
                             argument_no: idx,
+                            position: InsertPosition::Before,
                         })
                     }
                 }
@@ -147,7 +148,10 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                         }
                         place_to_add_projections.projection.push(projection_elem.clone());
                     }
-                    if place_without_deref.ty(&&self.locals).unwrap().kind().is_raw_ptr() {
+                    // Writes of unions are tracked by the union operations below.
+                    if place_without_deref.ty(&&self.locals).unwrap().kind().is_raw_ptr()
+                        && !place.ty(&self.locals).unwrap().kind().is_union()
+                    {
                         self.push_target(MemoryInitOp::Set {
                             operand: Operand::Copy(place_without_deref),
                             value: true,
@@ -156,14 +160,17 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                     }
                 }
                 // Check whether Rvalue creates a new initialized pointer previously not captured inside shadow memory.
-                if place.ty(&self.locals).unwrap().kind().is_raw_ptr() {
-                    if let Rvalue::AddressOf(..) = rvalue {
-                        self.push_target(MemoryInitOp::Set {
-                            operand: Operand::Copy(place.clone()),
-                            value: true,
-                            position: InsertPosition::After,
-                        });
-                    }
+                // Pointers to unions are not blessed, since the initialization state of unions is
+                // already tracked when they are created and updated.
+                if place.ty(&self.locals).unwrap().kind().is_raw_ptr()
+                    && let Rvalue::AddressOf(_, pointee) = rvalue
+                    && !pointee.ty(&self.locals).unwrap().kind().is_union()
+                {
+                    self.push_target(MemoryInitOp::Set {
+                        operand: Operand::Copy(place.clone()),
+                        value: true,
+                        position: InsertPosition::After,
+                    });
                 }
 
                 // TODO: add support for ADTs which could have unions as subfields. Currently,
@@ -181,7 +188,7 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                         }
                         place_to_add_projections.projection.push(projection_elem.clone());
                     }
-                    contains_union
+                    contains_union || place.ty(&self.locals).unwrap().kind().is_union()
                 };
 
                 // Need to copy some information about union initialization, since lvalue is
@@ -268,7 +275,7 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
         }
         // Leave it as an exhaustive match to be notified when a new kind is added.
         match &term.kind {
-            TerminatorKind::Call { func, args, destination, .. } => {
+            TerminatorKind::Call { func, args, destination, target, .. } => {
                 self.super_terminator(term, location);
                 let instance = match try_resolve_instance(&self.locals, func) {
                     Ok(instance) => instance,
@@ -404,6 +411,14 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                     }
                     _ => {}
                 }
+                if target.is_some() && destination.ty(&self.locals).unwrap().kind().is_union() {
+                    // Load the initialization state of the returned union, see `Return` below.
+                    self.push_target(MemoryInitOp::LoadArgument {
+                        operand: Operand::Copy(destination.clone()),
+                        argument_no: 0,
+                        position: InsertPosition::After,
+                    });
+                }
             }
             TerminatorKind::Drop { place, .. } => {
                 self.super_terminator(term, location);
@@ -437,11 +452,22 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                     });
                 }
             }
+            TerminatorKind::Return => {
+                self.super_terminator(term, location);
+                // Returned unions are passed to the caller through the argument buffer, using
+                // the number 0, since arguments are 1-indexed.
+                let ret_place = Place { local: RETURN_LOCAL, projection: vec![] };
+                if ret_place.ty(&self.locals).unwrap().kind().is_union() {
+                    self.push_target(MemoryInitOp::StoreArgument {
+                        operand: Operand::Copy(ret_place),
+                        argument_no: 0,
+                    });
+                }
+            }
             TerminatorKind::Goto { .. }
             | TerminatorKind::SwitchInt { .. }
             | TerminatorKind::Resume
             | TerminatorKind::Abort
-            | TerminatorKind::Return
             | TerminatorKind::Unreachable
             | TerminatorKind::Assert { .. }
             | TerminatorKind::InlineAsm { .. } => self.super_terminator(term, location),
@@ -683,6 +709,20 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::SimdMul
         | Intrinsic::SimdNe
         | Intrinsic::SimdOr
+        | Intrinsic::SimdReduceAddOrdered
+        | Intrinsic::SimdReduceAddUnordered
+        | Intrinsic::SimdReduceAll
+        | Intrinsic::SimdReduceAnd
+        | Intrinsic::SimdReduceAny
+        | Intrinsic::SimdReduceMax
+        | Intrinsic::SimdReduceMin
+        | Intrinsic::SimdReduceMulOrdered
+        | Intrinsic::SimdReduceMulUnordered
+        | Intrinsic::SimdReduceOr
+        | Intrinsic::SimdReduceXor
+        | Intrinsic::SimdSaturatingAdd
+        | Intrinsic::SimdSaturatingSub
+        | Intrinsic::SimdSelect
         | Intrinsic::SimdShl
         | Intrinsic::SimdShr
         | Intrinsic::SimdShuffle(_)
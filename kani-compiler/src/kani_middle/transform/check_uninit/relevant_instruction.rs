@@ -43,8 +43,8 @@ pub enum MemoryInitOp {
     /// Create a union from scratch with a given field index and store it in the provided operand.
     CreateUnion { operand: Operand, field: FieldIdx },
     /// Load argument containing a union from the argument buffer together if the argument number
-    /// provided matches.
-    LoadArgument { operand: Operand, argument_no: usize },
+    /// provided matches. The argument number 0 is used for the return value.
+    LoadArgument { operand: Operand, argument_no: usize, position: InsertPosition },
     /// Store argument containing a union into the argument buffer together with the argument number
     /// provided.
     StoreArgument { operand: Operand, argument_no: usize },
@@ -211,14 +211,14 @@ pub fn position(&self) -> InsertPosition {
         match self {
             MemoryInitOp::Set { position, .. }
             | MemoryInitOp::SetSliceChunk { position, .. }
-            | MemoryInitOp::SetRef { position, .. } => *position,
+            | MemoryInitOp::SetRef { position, .. }
+            | MemoryInitOp::LoadArgument { position, .. } => *position,
             MemoryInitOp::Check { .. }
             | MemoryInitOp::CheckSliceChunk { .. }
             | MemoryInitOp::CheckRef { .. }
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. }
-            | MemoryInitOp::StoreArgument { .. }
-            | MemoryInitOp::LoadArgument { .. } => InsertPosition::Before,
+            | MemoryInitOp::StoreArgument { .. } => InsertPosition::Before,
             MemoryInitOp::Copy { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::CreateUnion { .. } => InsertPosition::After,
@@ -0,0 +1,367 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A transformation pass that instruments the code to detect reads of a union field whose value
+//! is invalid for its type because another field was written last.
+//!
+//! Like the memory initialization state of `-Z uninit-checks`, the field last written of a
+//! non-deterministically chosen union is tracked by the Kani library. Every read of a union field
+//! whose type has invalid bit patterns, e.g., `bool`, `char` or a reference, is instrumented with:
+//!
+//! ```ignore
+//! assert!(is_union_field_active(&raw const u, field) || is_valid(&raw const u.field));
+//! ```
+//!
+//! The active field is unknown after writes that the pass can't see, e.g., through a raw pointer
+//! to the bytes of the union, in which case the read isn't checked.
+
+use crate::args::ExtraChecks;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::check_uninit::{get_mem_init_fn_def, is_harness};
+use crate::kani_middle::transform::check_values::{
+    build_limits, ty_validity_per_offset, ValidValueReq,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::visit::{Location, PlaceContext};
+use stable_mir::mir::{
+    AggregateKind, BinOp, Body, FieldIdx, Local, LocalDecl, MirVisitor, Mutability, Operand, Place,
+    ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
+};
+use stable_mir::target::MachineInfo;
+use stable_mir::ty::{FnDef, GenericArgKind, GenericArgs, Ty, UintTy};
+use std::collections::HashMap;
+use tracing::{debug, trace};
+
+const KANI_INITIALIZE_UNION_FIELD_STATE_DIAGNOSTIC: &str = "KaniInitializeUnionFieldState";
+const KANI_SET_UNION_FIELD_DIAGNOSTIC: &str = "KaniSetUnionField";
+const KANI_IS_UNION_FIELD_ACTIVE_DIAGNOSTIC: &str = "KaniIsUnionFieldActive";
+const KANI_COPY_UNION_FIELD_DIAGNOSTIC: &str = "KaniCopyUnionField";
+
+/// Instrument the code with checks for reads of union fields that aren't the one last written.
+#[derive(Debug)]
+pub struct UnionFieldPass {
+    pub check_type: CheckType,
+    /// Used to cache FnDef lookups of the injected union tracking functions.
+    pub mem_init_fn_cache: HashMap<&'static str, FnDef>,
+}
+
+impl TransformPass for UnionFieldPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        let args = query_db.args();
+        args.ub_check.contains(&ExtraChecks::Union)
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let mut new_body = MutableBody::from(body);
+        let targets = UnionFieldVisitor::find_all(&new_body);
+        let inject_setup = is_harness(instance, tcx);
+        let changed = inject_setup || !targets.is_empty();
+        // Instrument the instructions in reverse order, so the sources of the previous ones are
+        // still valid.
+        for target in targets.into_iter().rev() {
+            let mut source = target.source;
+            for operation in target.before_instruction {
+                self.build_operation(tcx, &mut new_body, &mut source, operation);
+            }
+            for operation in target.after_instruction {
+                self.build_operation(tcx, &mut new_body, &mut source, operation);
+            }
+        }
+        // Choose the tracked union before anything else runs.
+        if inject_setup {
+            let mut source = if new_body.blocks()[0].statements.is_empty() {
+                SourceInstruction::Terminator { bb: 0 }
+            } else {
+                SourceInstruction::Statement { idx: 0, bb: 0 }
+            };
+            insert_call(
+                &mut new_body,
+                self.resolve(tcx, KANI_INITIALIZE_UNION_FIELD_STATE_DIAGNOSTIC, None),
+                vec![],
+                &mut source,
+                InsertPosition::Before,
+            );
+        }
+        (changed, new_body.into())
+    }
+}
+
+impl UnionFieldPass {
+    fn build_operation(
+        &mut self,
+        tcx: TyCtxt,
+        body: &mut MutableBody,
+        source: &mut SourceInstruction,
+        operation: UnionFieldOp,
+    ) {
+        debug!(?operation, "build_operation");
+        let position = operation.position();
+        let span = source.span(body.blocks());
+        match operation {
+            UnionFieldOp::SetField { union_place, field } => {
+                let union_ty = union_place.ty(body.locals()).unwrap();
+                let union_ptr = address_of(body, union_place, source, position);
+                let field = body.new_uint_operand(field as _, UintTy::Usize, span);
+                insert_call(
+                    body,
+                    self.resolve(tcx, KANI_SET_UNION_FIELD_DIAGNOSTIC, Some(union_ty)),
+                    vec![union_ptr, field],
+                    source,
+                    position,
+                );
+            }
+            UnionFieldOp::Copy { from, to } => {
+                let union_ty = to.ty(body.locals()).unwrap();
+                let from_ptr = address_of(body, from, source, position);
+                let to_ptr = address_of(body, to, source, position);
+                insert_call(
+                    body,
+                    self.resolve(tcx, KANI_COPY_UNION_FIELD_DIAGNOSTIC, Some(union_ty)),
+                    vec![from_ptr, to_ptr],
+                    source,
+                    position,
+                );
+            }
+            UnionFieldOp::CheckRead { union_place, field, read_place, ranges } => {
+                let union_ty = union_place.ty(body.locals()).unwrap();
+                let read_ty = read_place.ty(body.locals()).unwrap();
+                let union_ptr = address_of(body, union_place, source, position);
+                let field = body.new_uint_operand(field as _, UintTy::Usize, span);
+                let active = insert_call(
+                    body,
+                    self.resolve(tcx, KANI_IS_UNION_FIELD_ACTIVE_DIAGNOSTIC, Some(union_ty)),
+                    vec![union_ptr, field],
+                    source,
+                    position,
+                );
+                let read_ptr = Rvalue::AddressOf(Mutability::Not, read_place);
+                let mut valid = build_limits(body, &ranges[0], read_ptr.clone(), source);
+                for range in &ranges[1..] {
+                    let valid_range = build_limits(body, range, read_ptr.clone(), source);
+                    valid = body.insert_binary_op(
+                        BinOp::BitAnd,
+                        Operand::Move(Place::from(valid)),
+                        Operand::Move(Place::from(valid_range)),
+                        source,
+                        position,
+                    );
+                }
+                let result = body.insert_binary_op(
+                    BinOp::BitOr,
+                    Operand::Move(Place::from(active)),
+                    Operand::Move(Place::from(valid)),
+                    source,
+                    position,
+                );
+                let msg = format!(
+                    "Undefined Behavior: Invalid value of type `{read_ty}` read from a field of \
+                    `{union_ty}` other than the one last written"
+                );
+                body.insert_check(tcx, &self.check_type, source, position, result, &msg);
+            }
+        }
+    }
+
+    /// Resolve one of the union tracking functions for the given union type, if it's generic.
+    fn resolve(&mut self, tcx: TyCtxt, diagnostic: &'static str, union_ty: Option<Ty>) -> Instance {
+        let fn_def = get_mem_init_fn_def(tcx, diagnostic, &mut self.mem_init_fn_cache);
+        let generic_args = GenericArgs(union_ty.into_iter().map(GenericArgKind::Type).collect());
+        Instance::resolve(fn_def, &generic_args).unwrap()
+    }
+}
+
+/// Insert a call to `instance`, and return the local that stores its result.
+pub(super) fn insert_call(
+    body: &mut MutableBody,
+    instance: Instance,
+    args: Vec<Operand>,
+    source: &mut SourceInstruction,
+    position: InsertPosition,
+) -> Local {
+    let ret_ty = instance.fn_abi().unwrap().ret.ty;
+    let ret = body.new_local(ret_ty, source.span(body.blocks()), Mutability::Mut);
+    body.insert_call(&instance, source, position, args, Place::from(ret));
+    ret
+}
+
+pub(super) fn address_of(
+    body: &mut MutableBody,
+    place: Place,
+    source: &mut SourceInstruction,
+    position: InsertPosition,
+) -> Operand {
+    let rvalue = Rvalue::AddressOf(Mutability::Not, place);
+    Operand::Move(Place::from(body.insert_assignment(rvalue, source, position)))
+}
+
+/// The union tracking operations of an instruction.
+#[derive(Clone, Debug)]
+enum UnionFieldOp {
+    /// Record that `field` is the active field of the union at `union_place`.
+    SetField { union_place: Place, field: FieldIdx },
+    /// Copy the active field of the union `from` to the union `to`.
+    Copy { from: Place, to: Place },
+    /// Check that `read_place`, which is inside the field `field` of the union at `union_place`,
+    /// satisfies `ranges` unless `field` is the active field.
+    CheckRead { union_place: Place, field: FieldIdx, read_place: Place, ranges: Vec<ValidValueReq> },
+}
+
+impl UnionFieldOp {
+    /// Writes are recorded after the instruction, and reads are checked before it.
+    fn position(&self) -> InsertPosition {
+        match self {
+            UnionFieldOp::SetField { .. } | UnionFieldOp::Copy { .. } => InsertPosition::After,
+            UnionFieldOp::CheckRead { .. } => InsertPosition::Before,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct UnionFieldInstruction {
+    source: SourceInstruction,
+    before_instruction: Vec<UnionFieldOp>,
+    after_instruction: Vec<UnionFieldOp>,
+}
+
+/// Collect the reads and writes of union fields of a body.
+struct UnionFieldVisitor<'a> {
+    locals: &'a [LocalDecl],
+    machine: MachineInfo,
+    /// The instruction being visited.
+    current: SourceInstruction,
+    targets: Vec<UnionFieldInstruction>,
+}
+
+impl<'a> UnionFieldVisitor<'a> {
+    fn find_all(body: &'a MutableBody) -> Vec<UnionFieldInstruction> {
+        let mut visitor = UnionFieldVisitor {
+            locals: body.locals(),
+            machine: MachineInfo::target(),
+            current: SourceInstruction::Statement { idx: 0, bb: 0 },
+            targets: vec![],
+        };
+        for (bb, block) in body.blocks().iter().enumerate() {
+            visitor.current = SourceInstruction::Statement { idx: 0, bb };
+            visitor.visit_basic_block(block);
+        }
+        visitor.targets
+    }
+
+    fn push_target(&mut self, operation: UnionFieldOp) {
+        if self.targets.last().map_or(true, |target| target.source != self.current) {
+            self.targets.push(UnionFieldInstruction {
+                source: self.current,
+                before_instruction: vec![],
+                after_instruction: vec![],
+            });
+        }
+        let target = self.targets.last_mut().unwrap();
+        match operation.position() {
+            InsertPosition::Before => target.before_instruction.push(operation),
+            InsertPosition::After => target.after_instruction.push(operation),
+        }
+    }
+
+    /// Record the writes of an assignment to `place`.
+    fn visit_write(&mut self, place: &Place, rvalue: &Rvalue) {
+        if let Some((union_place, field)) = innermost_union_field(self.locals, place) {
+            self.push_target(UnionFieldOp::SetField { union_place, field });
+        }
+        if place.ty(self.locals).unwrap().kind().is_union() {
+            match rvalue {
+                Rvalue::Aggregate(AggregateKind::Adt(.., Some(field)), _) => {
+                    self.push_target(UnionFieldOp::SetField {
+                        union_place: place.clone(),
+                        field: *field,
+                    });
+                }
+                Rvalue::Use(Operand::Copy(from) | Operand::Move(from)) => {
+                    self.push_target(UnionFieldOp::Copy { from: from.clone(), to: place.clone() });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> MirVisitor for UnionFieldVisitor<'a> {
+    fn visit_statement(&mut self, stmt: &Statement, location: Location) {
+        // Only check the reads in the rvalue, the destination is a write.
+        if let StatementKind::Assign(place, rvalue) = &stmt.kind {
+            self.visit_rvalue(rvalue, location);
+            self.visit_write(place, rvalue);
+        } else {
+            self.super_statement(stmt, location);
+        }
+        let SourceInstruction::Statement { idx, bb } = self.current else { unreachable!() };
+        self.current = SourceInstruction::Statement { idx: idx + 1, bb };
+    }
+
+    fn visit_terminator(&mut self, term: &Terminator, location: Location) {
+        let SourceInstruction::Statement { bb, .. } = self.current else { unreachable!() };
+        self.current = SourceInstruction::Terminator { bb };
+        self.super_terminator(term, location);
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        // Taking the address of a field doesn't read it.
+        if !matches!(rvalue, Rvalue::AddressOf(..)) {
+            self.super_rvalue(rvalue, location);
+        }
+    }
+
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        if !ptx.is_mutating() {
+            if let Some((union_place, field)) = innermost_union_field(self.locals, place) {
+                // Only the bytes of the union are read, so stop at the first dereference.
+                let deref = place.projection[union_place.projection.len()..]
+                    .iter()
+                    .position(|elem| matches!(elem, ProjectionElem::Deref))
+                    .map_or(place.projection.len(), |pos| union_place.projection.len() + pos);
+                let read_place =
+                    Place { local: place.local, projection: place.projection[..deref].to_vec() };
+                let read_ty = read_place.ty(self.locals).unwrap();
+                match ty_validity_per_offset(&self.machine, read_ty, 0) {
+                    Ok(ranges) if !ranges.is_empty() => {
+                        self.push_target(UnionFieldOp::CheckRead {
+                            union_place,
+                            field,
+                            read_place,
+                            ranges,
+                        });
+                    }
+                    // Types without invalid values can't be read with an invalid value, and the
+                    // types that aren't supported are already reported by `-Z valid-value-checks`.
+                    _ => {}
+                }
+            }
+        }
+        self.super_place(place, ptx, location)
+    }
+}
+
+/// Find the last field projection of `place` on a union, and return the union and the field.
+fn innermost_union_field(locals: &[LocalDecl], place: &Place) -> Option<(Place, FieldIdx)> {
+    place.projection.iter().enumerate().rev().find_map(|(idx, elem)| {
+        let ProjectionElem::Field(field, _) = elem else { return None };
+        let union_place =
+            Place { local: place.local, projection: place.projection[..idx].to_vec() };
+        union_place.ty(locals).unwrap().kind().is_union().then_some((union_place, *field))
+    })
+}
@@ -1,7 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This module contains code related to the MIR-to-MIR pass to enable contracts.
-use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::attributes::{contract_path, KaniAttributes};
 use crate::kani_middle::codegen_units::CodegenUnit;
 use crate::kani_middle::find_fn_def;
 use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
@@ -266,6 +266,9 @@ pub struct FunctionWithContractPass {
     check_fn: Option<InternalDefId>,
     /// Functions that should be stubbed by their contract.
     replace_fns: HashSet<InternalDefId>,
+    /// Paths of the functions from dependencies whose contracts were verified when the
+    /// dependency was verified. They are stubbed by their contract as well.
+    assumed_contracts: HashSet<String>,
     /// Functions annotated with contract attributes will contain contract closures even if they
     /// are not to be used in this harness.
     /// In order to avoid bringing unnecessary logic, we clear their body.
@@ -330,7 +333,7 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
 impl FunctionWithContractPass {
     /// Build the pass by collecting which functions we are stubbing and which ones we are
     /// verifying.
-    pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> FunctionWithContractPass {
+    pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> FunctionWithContractPass {
         if let Some(harness) = unit.harnesses.first() {
             let attrs = KaniAttributes::for_instance(tcx, *harness);
             let check_fn = attrs.interpret_for_contract_attribute().map(|(_, def_id, _)| def_id);
@@ -341,9 +344,11 @@ pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> FunctionWithContractPass {
                 .collect();
             let run_contract_fn = find_fn_def(tcx, "KaniRunContract");
             assert!(run_contract_fn.is_some(), "Failed to find Kani run contract function");
+            let assumed_contracts = queries.args().assume_contract.iter().cloned().collect();
             FunctionWithContractPass {
                 check_fn,
                 replace_fns,
+                assumed_contracts,
                 unused_closures: Default::default(),
                 run_contract_fn,
             }
@@ -439,7 +444,10 @@ fn contract_mode(&self, tcx: TyCtxt, fn_def: FnDef) -> Option<ContractMode> {
                 } else {
                     ContractMode::SimpleCheck
                 }
-            } else if self.replace_fns.contains(&fn_def_id) {
+            } else if self.replace_fns.contains(&fn_def_id)
+                || (!fn_def_id.is_local()
+                    && self.assumed_contracts.contains(&contract_path(tcx, fn_def_id)))
+            {
                 ContractMode::Replace
             } else {
                 ContractMode::Original
@@ -0,0 +1,248 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module contains the pass that replaces `asm!` blocks by calls to their models.
+//!
+//! A model is a function annotated with `#[kani::asm_model("<template>")]`, which models every
+//! `asm!` block with the same template, modulo whitespace. The model takes the inputs of the
+//! block, in order, followed by a mutable reference to each of its outputs, e.g.:
+//!
+//! ```ignore
+//! #[kani::asm_model("lzcnt {0}, {1}")]
+//! fn lzcnt_model(input: u64, output: &mut u64) {
+//!     *output = input.leading_zeros() as u64;
+//! }
+//! ```
+//!
+//! A block can also be modeled individually with `#[kani::asm_model(location = "<file>:<line>")]`,
+//! which takes precedence over the templates.
+//!
+//! Blocks without a model are handled during codegen, according to `--inline-asm`.
+use crate::kani_middle::attributes::{AsmPattern, KaniAttributes};
+use crate::kani_middle::reachability::filter_crate_items;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    Body, BorrowKind, InlineAsmOperand, LocalDecl, MutBorrowKind, Mutability, Operand, Place,
+    Rvalue, Terminator, TerminatorKind,
+};
+use stable_mir::ty::{Region, RegionKind, Span, Ty};
+use std::collections::HashMap;
+use tracing::{debug, trace};
+
+/// Replace the `asm!` blocks that have a model by a call to the model.
+#[derive(Debug)]
+pub struct InlineAsmPass {
+    /// The models of the local crate, keyed by their normalized template.
+    models: HashMap<String, Instance>,
+    /// The models of individual blocks, with the location of the block.
+    located_models: Vec<(String, usize, Instance)>,
+}
+
+impl TransformPass for InlineAsmPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        !self.models.is_empty() || !self.located_models.is_empty()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let mut new_body = MutableBody::from(body);
+        let mut changed = false;
+        for bb in 0..new_body.blocks().len() {
+            let terminator = &new_body.blocks()[bb].terminator;
+            let TerminatorKind::InlineAsm { template, operands, destination, unwind, .. } =
+                &terminator.kind
+            else {
+                continue;
+            };
+            let Some(model) = self
+                .located_model(terminator.span)
+                .or_else(|| self.models.get(&normalize(&template_from_debug(template))))
+            else {
+                continue;
+            };
+            let (span, operands, destination, unwind) =
+                (terminator.span, operands.clone(), *destination, *unwind);
+            if !check_model_signature(tcx, *model, &operands, new_body.locals(), span) {
+                continue;
+            }
+            debug!(function=?instance.name(), model=?model.name(), "replace asm block");
+            let mut source = SourceInstruction::Terminator { bb };
+            let mut args: Vec<Operand> =
+                operands.iter().filter_map(|operand| operand.in_value.clone()).collect();
+            for out_place in operands.iter().filter_map(|operand| operand.out_place.clone()) {
+                let borrow = Rvalue::Ref(
+                    Region { kind: RegionKind::ReErased },
+                    BorrowKind::Mut { kind: MutBorrowKind::Default },
+                    out_place,
+                );
+                let local = new_body.insert_assignment(borrow, &mut source, InsertPosition::Before);
+                args.push(Operand::Move(Place::from(local)));
+            }
+            let func =
+                Operand::Copy(Place::from(new_body.new_local(model.ty(), span, Mutability::Not)));
+            let ret_ty = model.fn_abi().unwrap().ret.ty;
+            let ret = new_body.new_local(ret_ty, span, Mutability::Mut);
+            let kind = TerminatorKind::Call {
+                func,
+                args,
+                destination: Place::from(ret),
+                target: destination,
+                unwind,
+            };
+            new_body.replace_terminator(&source, Terminator { kind, span });
+            changed = true;
+        }
+        (changed, new_body.into())
+    }
+}
+
+impl InlineAsmPass {
+    /// Build the pass with the models of the local crate.
+    pub fn new(tcx: TyCtxt) -> InlineAsmPass {
+        let mut models = HashMap::new();
+        let mut located_models = vec![];
+        for model in filter_crate_items(tcx, |tcx, instance| {
+            KaniAttributes::for_instance(tcx, instance).asm_model().is_some()
+        }) {
+            match KaniAttributes::for_instance(tcx, model).asm_model().unwrap() {
+                AsmPattern::Template(template) => {
+                    models.insert(normalize(&template), model);
+                }
+                AsmPattern::Location { file, line } => located_models.push((file, line, model)),
+            }
+        }
+        InlineAsmPass { models, located_models }
+    }
+
+    /// The model of the block at the given location, if any.
+    fn located_model(&self, span: Span) -> Option<&Instance> {
+        let filename = span.get_filename();
+        let line = span.get_lines().start_line;
+        self.located_models
+            .iter()
+            .find(|(file, model_line, _)| *model_line == line && filename.ends_with(file.as_str()))
+            .map(|(_, _, model)| model)
+    }
+}
+
+/// Check that the signature of `model` matches the operands of the block it replaces, and emit an
+/// error otherwise.
+fn check_model_signature(
+    tcx: TyCtxt,
+    model: Instance,
+    operands: &[InlineAsmOperand],
+    locals: &[LocalDecl],
+    span: Span,
+) -> bool {
+    let expected: Vec<Ty> = operands
+        .iter()
+        .filter_map(|operand| operand.in_value.as_ref().map(|value| value.ty(locals).unwrap()))
+        .chain(operands.iter().filter_map(|operand| {
+            let out_ty = operand.out_place.as_ref()?.ty(locals).unwrap();
+            Some(Ty::new_ref(Region { kind: RegionKind::ReErased }, out_ty, Mutability::Mut))
+        }))
+        .collect();
+    let actual: Vec<Ty> = model.fn_abi().unwrap().args.iter().map(|arg| arg.ty).collect();
+    if actual == expected {
+        return true;
+    }
+    let expected: Vec<String> = expected.iter().map(|ty| ty.to_string()).collect();
+    tcx.dcx().span_err(
+        rustc_internal::internal(tcx, span),
+        format!(
+            "the model `{}` of this `asm!` block must take the arguments `({})`",
+            model.name(),
+            expected.join(", ")
+        ),
+    );
+    false
+}
+
+/// Collapse every run of whitespace of a template into a single space.
+fn normalize(template: &str) -> String {
+    template.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Rebuild the template of an `asm!` block from its StableMIR representation, which is the debug
+/// representation of its pieces, e.g.,
+/// `[String("mov "), Placeholder { operand_idx: 0, modifier: Some('e'), span: .. }]`.
+fn template_from_debug(debug: &str) -> String {
+    const STRING: &str = "String(\"";
+    const PLACEHOLDER: &str = "Placeholder { operand_idx: ";
+    let mut template = String::new();
+    let mut rest = debug;
+    loop {
+        let placeholder = rest.find(PLACEHOLDER);
+        let string = rest.find(STRING).filter(|start| placeholder.map_or(true, |p| *start < p));
+        match (string, placeholder) {
+            (Some(start), _) => {
+                let mut chars = rest[start + STRING.len()..].chars();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => template.push('\n'),
+                            Some('t') => template.push('\t'),
+                            Some('r') => template.push('\r'),
+                            Some('0') => template.push('\0'),
+                            Some(other) => template.push(other),
+                            None => break,
+                        },
+                        // Braces in the pieces are literal, so we escape them like in the source.
+                        '{' => template.push_str("{{"),
+                        '}' => template.push_str("}}"),
+                        c => template.push(c),
+                    }
+                }
+                rest = chars.as_str();
+            }
+            (None, Some(start)) => {
+                rest = &rest[start + PLACEHOLDER.len()..];
+                let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                let index = &rest[..digits];
+                let modifier = rest
+                    .find("modifier: ")
+                    .and_then(|pos| rest[pos..].strip_prefix("modifier: Some('")?.chars().next());
+                match modifier {
+                    Some(modifier) => template.push_str(&format!("{{{index}:{modifier}}}")),
+                    None => template.push_str(&format!("{{{index}}}")),
+                }
+                rest = &rest[digits..];
+            }
+            (None, None) => break,
+        }
+    }
+    template
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_template_from_debug() {
+        assert_eq!(template_from_debug(r#"[String("pause")]"#), "pause");
+        assert_eq!(
+            template_from_debug(
+                r#"[String("lzcnt "), Placeholder { operand_idx: 0, modifier: None, span: src/lib.rs:3:5 }, String(", "), Placeholder { operand_idx: 1, modifier: Some('e'), span: src/lib.rs:3:9 }, String("\n\"{x}\"")]"#
+            ),
+            "lzcnt {0}, {1:e}\n\"{{x}}\""
+        );
+        assert_eq!(normalize("  lzcnt {0},\n\t{1} "), "lzcnt {0}, {1}");
+    }
+}
@@ -16,31 +16,49 @@
 //!
 //! For all instrumentation passes, always use exhaustive matches to ensure soundness in case a new
 //! case is added.
-use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::codegen_units::{CodegenUnit, Stubs};
 use crate::kani_middle::reachability::CallGraph;
 use crate::kani_middle::transform::body::CheckType;
+use crate::kani_middle::transform::builtin_models::BuiltinModelPass;
+use crate::kani_middle::transform::check_aliasing::AliasingPass;
+use crate::kani_middle::transform::check_taint::TaintPass;
 use crate::kani_middle::transform::check_uninit::{DelayedUbPass, UninitPass};
+use crate::kani_middle::transform::check_union::UnionFieldPass;
 use crate::kani_middle::transform::check_values::ValidValuePass;
 use crate::kani_middle::transform::contracts::{AnyModifiesPass, FunctionWithContractPass};
+use crate::kani_middle::transform::inline_asm::InlineAsmPass;
 use crate::kani_middle::transform::kani_intrinsics::IntrinsicGeneratorPass;
+use crate::kani_middle::transform::optimize::MirOptPass;
+use crate::kani_middle::transform::overflow_checks::OverflowChecksPass;
+use crate::kani_middle::transform::slicing::SlicingPass;
 use crate::kani_middle::transform::stubs::{ExternFnStubPass, FnStubPass};
 use crate::kani_queries::QueryDb;
 use dump_mir_pass::DumpMirPass;
 use rustc_middle::ty::TyCtxt;
 use stable_mir::mir::mono::{Instance, MonoItem};
 use stable_mir::mir::Body;
+use stable_mir::ty::{RigidTy, TyKind};
 use std::collections::HashMap;
 use std::fmt::Debug;
 
+pub use builtin_models::has_builtin_model;
 pub use internal_mir::RustcInternalMir;
 
 pub(crate) mod body;
+mod builtin_models;
+mod check_aliasing;
+mod check_taint;
 mod check_uninit;
+mod check_union;
 mod check_values;
 mod contracts;
 mod dump_mir_pass;
+mod inline_asm;
 mod internal_mir;
 mod kani_intrinsics;
+mod optimize;
+mod overflow_checks;
+mod slicing;
 mod stubs;
 
 /// Object used to retrieve a transformed instance body.
@@ -53,27 +71,46 @@ pub struct BodyTransformation {
     /// The passes that may change the function body according to harness configuration.
     /// The stubbing passes should be applied before so user stubs take precedence.
     stub_passes: Vec<Box<dyn TransformPass>>,
+    /// The passes that simplify the function body without changing its behavior.
+    /// They run after stubbing, so stubs are inlined and optimized as well.
+    opt_passes: Vec<Box<dyn TransformPass>>,
     /// The passes that may add safety checks to the function body.
     inst_passes: Vec<Box<dyn TransformPass>>,
     /// Cache transformation results.
     cache: HashMap<Instance, TransformationResult>,
+    /// The functions whose body is replaced by a stub.
+    stubs: Stubs,
 }
 
 impl BodyTransformation {
     pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
         let mut transformer = BodyTransformation {
             stub_passes: vec![],
+            opt_passes: vec![],
             inst_passes: vec![],
             cache: Default::default(),
+            stubs: unit.stubs.clone(),
         };
         let check_type = CheckType::new_assert_assume(tcx);
         transformer.add_pass(queries, FnStubPass::new(&unit.stubs));
         transformer.add_pass(queries, ExternFnStubPass::new(&unit.stubs));
-        transformer.add_pass(queries, FunctionWithContractPass::new(tcx, &unit));
+        transformer.add_pass(queries, MirOptPass::new(queries, &unit.stubs));
+        transformer.add_pass(queries, InlineAsmPass::new(tcx));
+        transformer.add_pass(queries, BuiltinModelPass::new(tcx));
+        transformer.add_pass(queries, OverflowChecksPass);
+        transformer.add_pass(queries, FunctionWithContractPass::new(queries, tcx, &unit));
         // This has to come after the contract pass since we want this to only replace the closure
         // body that is relevant for this harness.
         transformer.add_pass(queries, AnyModifiesPass::new(tcx, &unit));
         transformer.add_pass(queries, ValidValuePass { check_type: check_type.clone() });
+        transformer.add_pass(
+            queries,
+            UnionFieldPass {
+                // Like `UninitPass`, this uses demonic non-determinism to choose the tracked union.
+                check_type: CheckType::new_assert(tcx),
+                mem_init_fn_cache: HashMap::new(),
+            },
+        );
         // Putting `UninitPass` after `ValidValuePass` makes sure that the code generated by
         // `UninitPass` does not get unnecessarily instrumented by valid value checks. However, it
         // would also make sense to check that the values are initialized before checking their
@@ -87,6 +124,14 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
                 mem_init_fn_cache: HashMap::new(),
             },
         );
+        transformer.add_pass(
+            queries,
+            AliasingPass {
+                // Like `UninitPass`, this uses demonic non-determinism to choose the tracked byte.
+                check_type: CheckType::new_assert(tcx),
+                mem_init_fn_cache: HashMap::new(),
+            },
+        );
         transformer.add_pass(
             queries,
             IntrinsicGeneratorPass {
@@ -95,6 +140,10 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
                 arguments: queries.args().clone(),
             },
         );
+        transformer.add_pass(queries, TaintPass::new(queries, tcx));
+        // This has to be the last pass, so the locals read by the checks of the other passes are
+        // kept.
+        transformer.add_pass(queries, SlicingPass);
         transformer
     }
 
@@ -110,7 +159,8 @@ pub fn body(&mut self, tcx: TyCtxt, instance: Instance) -> Body {
             None => {
                 let mut body = instance.body().unwrap();
                 let mut modified = false;
-                for pass in self.stub_passes.iter_mut().chain(self.inst_passes.iter_mut()) {
+                let passes = self.stub_passes.iter_mut().chain(self.opt_passes.iter_mut());
+                for pass in passes.chain(self.inst_passes.iter_mut()) {
                     let result = pass.transform(tcx, body, instance);
                     modified |= result.0;
                     body = result.1;
@@ -127,11 +177,20 @@ pub fn body(&mut self, tcx: TyCtxt, instance: Instance) -> Body {
         }
     }
 
+    /// Whether the body of the function is replaced by a stub.
+    pub fn is_stubbed(&self, instance: Instance) -> bool {
+        match instance.ty().kind() {
+            TyKind::RigidTy(RigidTy::FnDef(def, _)) => self.stubs.contains_key(&def),
+            _ => false,
+        }
+    }
+
     fn add_pass<P: TransformPass + 'static>(&mut self, query_db: &QueryDb, pass: P) {
         if pass.is_enabled(&query_db) {
             match P::transformation_type() {
                 TransformationType::Instrumentation => self.inst_passes.push(Box::new(pass)),
                 TransformationType::Stubbing => self.stub_passes.push(Box::new(pass)),
+                TransformationType::Optimization => self.opt_passes.push(Box::new(pass)),
             }
         }
     }
@@ -144,6 +203,8 @@ pub(crate) enum TransformationType {
     Instrumentation,
     /// Apply some sort of stubbing.
     Stubbing,
+    /// Simplify the body without changing its behavior.
+    Optimization,
 }
 
 /// A trait to represent transformation passes that can be used to modify the body of a function.
@@ -0,0 +1,380 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module contains the pass that implements `--mir-opt-level`, which simplifies the function
+//! bodies before they are instrumented and translated, so CBMC receives smaller formulas.
+//!
+//! - Level 1 replaces the uses of the locals that are only ever assigned one scalar constant by the
+//!   constant, turns the `SwitchInt` terminators whose discriminant is a constant into a `Goto`,
+//!   and retargets the jumps to blocks that do nothing but jump to another block.
+//! - Level 2 also inlines the calls to tiny functions, i.e., functions whose body is a single
+//!   block without any call, before running the optimizations of level 1.
+//!
+//! No block is removed, so the checks of the blocks that became unreachable are still reported as
+//! unreachable. However, the inlined functions no longer show up in the traces, which is why the
+//! optimizations are disabled by default.
+use crate::kani_middle::codegen_units::Stubs;
+use crate::kani_middle::transform::body::MutMirVisitor;
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::mir::visit::{statement_location, terminator_location, Location, PlaceContext};
+use stable_mir::mir::{
+    BasicBlock, BasicBlockIdx, Body, ConstOperand, Local, LocalDecl, MirVisitor, Operand, Place,
+    ProjectionElem, Rvalue, Statement, StatementKind, SwitchTargets, TerminatorKind, RETURN_LOCAL,
+};
+use stable_mir::ty::{ConstantKind, MirConst, RigidTy, TyKind};
+use stable_mir::CrateDef;
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, trace};
+
+/// The maximum number of statements of a function that is inlined.
+const MAX_INLINED_STATEMENTS: usize = 8;
+
+/// The crates whose functions are intercepted or generated by Kani, so they must keep their calls.
+const KANI_CRATES: &[&str] = &["kani", "kani_core"];
+
+/// Simplify the function bodies according to `--mir-opt-level`.
+#[derive(Debug)]
+pub struct MirOptPass {
+    level: u8,
+    /// The functions that are stubbed must not be inlined, since their stub replaces their body.
+    stubs: Stubs,
+}
+
+impl TransformPass for MirOptPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Optimization
+    }
+
+    /// The coverage statements must stay in the function and the blocks they were generated for.
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.level > 0 && !query_db.args().check_coverage
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, mut body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let mut changed = false;
+        if self.level >= 2 {
+            let inlined;
+            (inlined, body) = inline_tiny_calls(tcx, &self.stubs, body);
+            changed |= inlined;
+        }
+        while propagate_constants(&mut body) {
+            changed = true;
+        }
+        changed |= fold_constant_switches(&mut body);
+        changed |= skip_empty_blocks(&mut body);
+        (changed, body)
+    }
+}
+
+impl MirOptPass {
+    pub fn new(queries: &QueryDb, stubs: &Stubs) -> MirOptPass {
+        MirOptPass { level: queries.args().mir_opt_level, stubs: stubs.clone() }
+    }
+}
+
+/// Replace the calls to tiny functions by their body.
+///
+/// The arguments are assigned to fresh locals, followed by the statements of the callee, whose
+/// locals are renumbered, and by the assignment of its return value to the destination.
+fn inline_tiny_calls(tcx: TyCtxt, stubs: &Stubs, body: Body) -> (bool, Body) {
+    let mut locals = body.locals().to_vec();
+    let arg_count = body.arg_locals().len();
+    let spread_arg = body.spread_arg();
+    let mut blocks = body.blocks;
+    let mut changed = false;
+    for bb in blocks.iter_mut() {
+        let TerminatorKind::Call { func, args, destination, target: Some(target), .. } =
+            &bb.terminator.kind
+        else {
+            continue;
+        };
+        let Some(callee) = tiny_callee(tcx, stubs, func, &locals) else { continue };
+        debug!(?func, "inline");
+        let (args, destination, target) = (args.clone(), destination.clone(), *target);
+        let offset = locals.len();
+        locals.extend(callee.locals().iter().cloned());
+        let span = bb.terminator.span;
+        for (idx, arg) in args.iter().enumerate() {
+            let kind =
+                StatementKind::Assign(Place::from(offset + 1 + idx), Rvalue::Use(arg.clone()));
+            bb.statements.push(Statement { kind, span });
+        }
+        let mut renumber = RenumberLocals { offset };
+        let [callee_bb] = callee.blocks.as_slice() else { unreachable!() };
+        for stmt in &callee_bb.statements {
+            let mut stmt = stmt.clone();
+            renumber.visit_statement(&mut stmt);
+            bb.statements.push(stmt);
+        }
+        let ret = Operand::Move(Place::from(offset + RETURN_LOCAL));
+        let kind = StatementKind::Assign(destination, Rvalue::Use(ret));
+        bb.statements.push(Statement { kind, span });
+        bb.terminator.kind = TerminatorKind::Goto { target };
+        changed = true;
+    }
+    (changed, Body::new(blocks, locals, arg_count, body.var_debug_info, spread_arg, body.span))
+}
+
+/// The body of the function called by `func`, if it can be inlined.
+fn tiny_callee(tcx: TyCtxt, stubs: &Stubs, func: &Operand, locals: &[LocalDecl]) -> Option<Body> {
+    let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(locals).ok()?.kind() else {
+        return None;
+    };
+    if stubs.contains_key(&def) {
+        return None;
+    }
+    let instance = Instance::resolve(def, &args).ok()?;
+    if instance.kind != InstanceKind::Item
+        || !instance.has_body()
+        || KANI_CRATES.contains(&instance.def.krate().name.as_str())
+        || has_special_attributes(tcx, instance)
+    {
+        return None;
+    }
+    let body = instance.body()?;
+    is_tiny(&body).then_some(body)
+}
+
+/// Whether Kani handles the function specially, e.g., with a hook or by generating its body, or
+/// looks for its calls, in which case it must not be inlined.
+fn has_special_attributes(tcx: TyCtxt, instance: Instance) -> bool {
+    let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+    tcx.get_attrs_unchecked(def_id).iter().any(|attr| {
+        attr.has_name(rustc_span::symbol::sym::rustc_diagnostic_item)
+            || attr.path().first().is_some_and(|segment| segment.as_str() == "kanitool")
+    })
+}
+
+/// Whether the body is a single block that returns after a few statements.
+///
+/// The body must not refer to any function either, since the stubbing passes already ran on the
+/// caller.
+fn is_tiny(body: &Body) -> bool {
+    let [bb] = body.blocks.as_slice() else { return false };
+    if !matches!(bb.terminator.kind, TerminatorKind::Return)
+        || bb.statements.len() > MAX_INLINED_STATEMENTS
+        || body.spread_arg().is_some()
+    {
+        return false;
+    }
+    let mut visitor = FunctionRefs { found: false };
+    visitor.visit_body(body);
+    !visitor.found
+}
+
+struct FunctionRefs {
+    found: bool,
+}
+
+impl MirVisitor for FunctionRefs {
+    fn visit_mir_const(&mut self, constant: &MirConst, _location: Location) {
+        self.found |= matches!(constant.ty().kind(), TyKind::RigidTy(RigidTy::FnDef(..)));
+    }
+}
+
+/// Shift every local of an inlined statement by the index of the first local of the callee.
+struct RenumberLocals {
+    offset: usize,
+}
+
+impl MutMirVisitor for RenumberLocals {
+    fn visit_operand(&mut self, operand: &mut Operand) {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.visit_place(place),
+            Operand::Constant(_) => {}
+        }
+    }
+
+    fn visit_place(&mut self, place: &mut Place) {
+        self.visit_local(&mut place.local);
+        for elem in place.projection.iter_mut() {
+            if let ProjectionElem::Index(local) = elem {
+                self.visit_local(local);
+            }
+        }
+    }
+
+    fn visit_local(&mut self, local: &mut Local) {
+        *local += self.offset;
+    }
+}
+
+/// Replace the uses of the locals that are assigned a single scalar constant by the constant.
+///
+/// The locals that are written anywhere else, or whose address is taken, are skipped. The
+/// assignments are kept, so the locals still show up in the traces. Return whether any use was
+/// replaced, in which case other locals may now be assigned a constant.
+fn propagate_constants(body: &mut Body) -> bool {
+    let arg_count = body.arg_locals().len();
+    let mut constants: HashMap<Local, ConstOperand> = HashMap::new();
+    let mut excluded = HashSet::new();
+    let mut writes = WrittenLocals { written: HashSet::new() };
+    for (idx, bb) in body.blocks.iter().enumerate() {
+        for (stmt_idx, stmt) in bb.statements.iter().enumerate() {
+            match &stmt.kind {
+                StatementKind::Assign(place, Rvalue::Use(Operand::Constant(constant)))
+                    if place.projection.is_empty() && is_scalar(constant) =>
+                {
+                    if constants.insert(place.local, constant.clone()).is_some() {
+                        excluded.insert(place.local);
+                    }
+                }
+                _ => writes.visit_statement(stmt, statement_location(body, &idx, stmt_idx)),
+            }
+        }
+        writes.visit_terminator(&bb.terminator, terminator_location(body, &idx));
+    }
+    constants.retain(|local, _| {
+        *local > arg_count && !excluded.contains(local) && !writes.written.contains(local)
+    });
+    if constants.is_empty() {
+        return false;
+    }
+
+    let mut visitor = ReplaceLocals { constants, changed: false };
+    for bb in body.blocks.iter_mut() {
+        visitor.visit_basic_block(bb);
+    }
+    visitor.changed
+}
+
+/// Only scalars are propagated, so every use of the local still refers to the same allocation
+/// otherwise.
+fn is_scalar(constant: &ConstOperand) -> bool {
+    matches!(constant.const_.kind(), ConstantKind::Allocated(_))
+        && matches!(
+            constant.const_.ty().kind(),
+            TyKind::RigidTy(
+                RigidTy::Bool
+                    | RigidTy::Char
+                    | RigidTy::Int(_)
+                    | RigidTy::Uint(_)
+                    | RigidTy::Float(_)
+            )
+        )
+}
+
+/// Collect the locals that are written or whose address is taken.
+struct WrittenLocals {
+    written: HashSet<Local>,
+}
+
+impl MirVisitor for WrittenLocals {
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        if ptx.is_mutating() {
+            self.written.insert(place.local);
+        }
+        self.super_place(place, ptx, location)
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        if let Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) = rvalue {
+            self.written.insert(place.local);
+        }
+        self.super_rvalue(rvalue, location)
+    }
+}
+
+struct ReplaceLocals {
+    constants: HashMap<Local, ConstOperand>,
+    changed: bool,
+}
+
+impl MutMirVisitor for ReplaceLocals {
+    fn visit_operand(&mut self, operand: &mut Operand) {
+        if let Operand::Copy(place) | Operand::Move(place) = operand
+            && place.projection.is_empty()
+            && let Some(constant) = self.constants.get(&place.local)
+        {
+            *operand = Operand::Constant(constant.clone());
+            self.changed = true;
+        }
+    }
+}
+
+/// Replace the `SwitchInt` terminators whose discriminant is a constant by a jump to the target
+/// of its value.
+fn fold_constant_switches(body: &mut Body) -> bool {
+    let mut changed = false;
+    for bb in body.blocks.iter_mut() {
+        if let TerminatorKind::SwitchInt { discr: Operand::Constant(constant), targets } =
+            &bb.terminator.kind
+            && let ConstantKind::Allocated(alloc) = constant.const_.kind()
+            && let Ok(value) = alloc.read_uint()
+        {
+            let target = targets
+                .branches()
+                .find_map(|(branch, target)| (branch == value).then_some(target))
+                .unwrap_or(targets.otherwise());
+            bb.terminator.kind = TerminatorKind::Goto { target };
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Retarget the jumps to blocks that only jump to another block.
+///
+/// Only the blocks without any statement are skipped, since even the storage markers are used by
+/// the checks of dangling pointers. The blocks themselves are kept.
+fn skip_empty_blocks(body: &mut Body) -> bool {
+    let targets: Vec<BasicBlockIdx> =
+        (0..body.blocks.len()).map(|idx| final_target(&body.blocks, idx)).collect();
+    let mut changed = false;
+    for bb in body.blocks.iter_mut() {
+        let mut retarget = |target: &mut BasicBlockIdx| {
+            changed |= targets[*target] != *target;
+            *target = targets[*target];
+        };
+        match &mut bb.terminator.kind {
+            TerminatorKind::Goto { target }
+            | TerminatorKind::Drop { target, .. }
+            | TerminatorKind::Assert { target, .. }
+            | TerminatorKind::Call { target: Some(target), .. }
+            | TerminatorKind::InlineAsm { destination: Some(target), .. } => retarget(target),
+            TerminatorKind::SwitchInt { targets: switch_targets, .. } => {
+                let mut otherwise = switch_targets.otherwise();
+                retarget(&mut otherwise);
+                let branches = switch_targets
+                    .branches()
+                    .map(|(value, mut target)| {
+                        retarget(&mut target);
+                        (value, target)
+                    })
+                    .collect();
+                *switch_targets = SwitchTargets::new(branches, otherwise);
+            }
+            TerminatorKind::Call { target: None, .. }
+            | TerminatorKind::InlineAsm { destination: None, .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable => {}
+        }
+    }
+    changed
+}
+
+/// The first block reached from `start` that does something other than jumping.
+fn final_target(blocks: &[BasicBlock], start: BasicBlockIdx) -> BasicBlockIdx {
+    let mut visited = HashSet::from([start]);
+    let mut current = start;
+    while let TerminatorKind::Goto { target } = blocks[current].terminator.kind
+        && blocks[current].statements.iter().all(|stmt| matches!(stmt.kind, StatementKind::Nop))
+        && visited.insert(target)
+    {
+        current = target;
+    }
+    current
+}
@@ -0,0 +1,72 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module contains the pass that removes the arithmetic overflow checks of the functions
+//! annotated with `#[kani::overflow_checks(off)]`.
+//!
+//! The overflow checks are the `Assert` terminators that rustc adds after the checked arithmetic
+//! operations. We replace them by a `Goto` to their target, so the operation wraps around as it
+//! would without `-C overflow-checks`. The checks of the division, remainder and shift operations
+//! are kept, since they panic regardless of that option.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::transform::body::{MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{AssertMessage, BinOp, Body, Terminator, TerminatorKind};
+use tracing::{debug, trace};
+
+/// Remove the overflow checks of the functions where they were turned off.
+#[derive(Debug)]
+pub struct OverflowChecksPass;
+
+impl TransformPass for OverflowChecksPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if KaniAttributes::for_instance(tcx, instance).overflow_checks() != Some(false) {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        let mut changed = false;
+        for bb in 0..new_body.blocks().len() {
+            let terminator = &new_body.blocks()[bb].terminator;
+            let TerminatorKind::Assert { msg, target, .. } = &terminator.kind else {
+                continue;
+            };
+            if !is_overflow_check(msg) {
+                continue;
+            }
+            debug!(function=?instance.name(), ?msg, "remove overflow check");
+            let kind = TerminatorKind::Goto { target: *target };
+            let terminator = Terminator { kind, span: terminator.span };
+            new_body.replace_terminator(&SourceInstruction::Terminator { bb }, terminator);
+            changed = true;
+        }
+        (changed, new_body.into())
+    }
+}
+
+/// Whether the assertion checks that an addition, subtraction, multiplication or negation
+/// doesn't overflow.
+fn is_overflow_check(msg: &AssertMessage) -> bool {
+    matches!(
+        msg,
+        AssertMessage::Overflow(BinOp::Add | BinOp::Sub | BinOp::Mul, ..)
+            | AssertMessage::OverflowNeg(_)
+    )
+}
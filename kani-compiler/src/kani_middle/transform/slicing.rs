@@ -0,0 +1,180 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module contains the pass that implements `-Z mir-slicing`, which removes the assignments
+//! that cannot affect any check of the harness before codegen.
+//!
+//! An assignment is removed if it writes a whole local that is never read, borrowed, or used by a
+//! terminator, and if its value can be computed without any check, e.g., arithmetic without
+//! overflow checks or the construction of an aggregate. The pass runs until no assignment can be
+//! removed, since removing one may make the locals it reads unused.
+//!
+//! Since this runs after the other passes, the locals read by the checks they add are kept.
+//! Floating-point operations are also kept, since CBMC checks them for overflow and NaN.
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::visit::{statement_location, terminator_location, Location, PlaceContext};
+use stable_mir::mir::{
+    BinOp, Body, CastKind, Local, LocalDecl, MirVisitor, Operand, Place, ProjectionElem, Rvalue,
+    StatementKind, UnOp, RETURN_LOCAL,
+};
+use std::collections::HashSet;
+use tracing::{debug, trace};
+
+/// Remove the assignments that cannot affect any check.
+#[derive(Debug)]
+pub struct SlicingPass;
+
+impl TransformPass for SlicingPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().unstable_features.contains(&"mir-slicing".to_string())
+    }
+
+    fn transform(&mut self, _tcx: TyCtxt, mut body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let arg_count = body.arg_locals().len();
+        let locals = body.locals().to_vec();
+        let mut changed = false;
+        loop {
+            let used = used_locals(&body);
+            let mut removed = 0;
+            for bb in body.blocks.iter_mut() {
+                for stmt in bb.statements.iter_mut() {
+                    if let StatementKind::Assign(place, rvalue) = &stmt.kind
+                        && place.projection.is_empty()
+                        && place.local > arg_count
+                        && !used.contains(&place.local)
+                        && is_removable(rvalue, &locals)
+                    {
+                        debug!(function=?instance.name(), ?place, ?rvalue, "remove assignment");
+                        stmt.kind = StatementKind::Nop;
+                        removed += 1;
+                    }
+                }
+            }
+            if removed == 0 {
+                break;
+            }
+            changed = true;
+        }
+        (changed, body)
+    }
+}
+
+/// Collect the locals that are read anywhere in the body, i.e., everywhere but as the destination
+/// of an assignment to the whole local and in the storage markers.
+fn used_locals(body: &Body) -> HashSet<Local> {
+    let mut visitor = UsedLocals { used: HashSet::from([RETURN_LOCAL]) };
+    for (idx, bb) in body.blocks.iter().enumerate() {
+        for (stmt_idx, stmt) in bb.statements.iter().enumerate() {
+            let location = statement_location(body, &idx, stmt_idx);
+            match &stmt.kind {
+                StatementKind::Assign(place, rvalue) if place.projection.is_empty() => {
+                    visitor.visit_rvalue(rvalue, location)
+                }
+                StatementKind::StorageLive(_) | StatementKind::StorageDead(_) => {}
+                _ => visitor.visit_statement(stmt, location),
+            }
+        }
+        visitor.visit_terminator(&bb.terminator, terminator_location(body, &idx));
+    }
+    visitor.used
+}
+
+struct UsedLocals {
+    used: HashSet<Local>,
+}
+
+impl MirVisitor for UsedLocals {
+    fn visit_local(&mut self, local: &Local, _ptx: PlaceContext, _location: Location) {
+        self.used.insert(*local);
+    }
+}
+
+/// Whether the value of the rvalue can be computed without any check, so it can be removed if
+/// it's never used.
+fn is_removable(rvalue: &Rvalue, locals: &[LocalDecl]) -> bool {
+    match rvalue {
+        Rvalue::Use(operand) => is_removable_operand(operand),
+        Rvalue::Aggregate(_, operands) => operands.iter().all(is_removable_operand),
+        Rvalue::BinaryOp(op, lhs, rhs) | Rvalue::CheckedBinaryOp(op, lhs, rhs) => {
+            is_removable_op(*op)
+                && [lhs, rhs].iter().all(|operand| {
+                    is_removable_operand(operand) && !is_float_operand(operand, locals)
+                })
+        }
+        Rvalue::UnaryOp(UnOp::Not | UnOp::Neg, operand) => is_removable_operand(operand),
+        Rvalue::Cast(CastKind::IntToInt, operand, _) => is_removable_operand(operand),
+        Rvalue::AddressOf(..)
+        | Rvalue::Cast(..)
+        | Rvalue::CopyForDeref(_)
+        | Rvalue::Discriminant(_)
+        | Rvalue::Len(_)
+        | Rvalue::Ref(..)
+        | Rvalue::Repeat(..)
+        | Rvalue::ShallowInitBox(..)
+        | Rvalue::ThreadLocalRef(_)
+        | Rvalue::NullaryOp(..)
+        | Rvalue::UnaryOp(..) => false,
+    }
+}
+
+/// Division, remainder, shifts and the unchecked operations are excluded since codegen adds
+/// checks to them.
+fn is_removable_op(op: BinOp) -> bool {
+    match op {
+        BinOp::Add
+        | BinOp::Sub
+        | BinOp::Mul
+        | BinOp::BitXor
+        | BinOp::BitAnd
+        | BinOp::BitOr
+        | BinOp::Eq
+        | BinOp::Lt
+        | BinOp::Le
+        | BinOp::Ne
+        | BinOp::Ge
+        | BinOp::Gt
+        | BinOp::Cmp => true,
+        BinOp::AddUnchecked
+        | BinOp::SubUnchecked
+        | BinOp::MulUnchecked
+        | BinOp::Div
+        | BinOp::Rem
+        | BinOp::Shl
+        | BinOp::ShlUnchecked
+        | BinOp::Shr
+        | BinOp::ShrUnchecked
+        | BinOp::Offset => false,
+    }
+}
+
+/// CBMC checks floating-point arithmetic for overflow and NaN. We don't know the type of an
+/// operand if it fails to resolve, so it's treated as a float.
+fn is_float_operand(operand: &Operand, locals: &[LocalDecl]) -> bool {
+    operand.ty(locals).map_or(true, |ty| ty.kind().is_float())
+}
+
+/// Operands that dereference a pointer may fail memory safety checks, so they are kept.
+fn is_removable_operand(operand: &Operand) -> bool {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => is_removable_place(place),
+        Operand::Constant(_) => true,
+    }
+}
+
+fn is_removable_place(place: &Place) -> bool {
+    place.projection.iter().all(|elem| !matches!(elem, ProjectionElem::Deref))
+}
@@ -0,0 +1,144 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This module implements `--unsafe-report`, which lists the unsafe operations reachable from
+//! each harness: raw pointer dereferences, union field accesses, transmutes, and calls to unsafe
+//! functions.
+//!
+//! The operations are collected from the bodies of the functions as written, before any
+//! transformation, so the functions replaced by a stub are not audited. The operations of the
+//! standard library and of the Kani library are not listed.
+use crate::kani_middle::reachability::CallGraph;
+use crate::kani_middle::transform::BodyTransformation;
+use kani_metadata::{UnsafeOperation, UnsafeOperationKind};
+use stable_mir::mir::mono::{Instance, InstanceKind, MonoItem};
+use stable_mir::mir::visit::{Location, PlaceContext};
+use stable_mir::mir::{
+    Body, CastKind, MirVisitor, Place, ProjectionElem, Rvalue, Safety, Terminator, TerminatorKind,
+};
+use stable_mir::ty::{AdtKind, RigidTy, Span, TyKind};
+use stable_mir::CrateDef;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The crates whose operations are not listed.
+const LIBRARY_CRATES: &[&str] = &["alloc", "core", "kani", "kani_core", "std"];
+
+/// Build the report of the unsafe operations reachable from each root of a model.
+pub fn unsafe_operations_report(
+    roots: &[MonoItem],
+    items: &[MonoItem],
+    call_graph: &CallGraph,
+    transformer: &BodyTransformation,
+) -> BTreeMap<String, Vec<UnsafeOperation>> {
+    let operations: HashMap<&MonoItem, Vec<UnsafeOperation>> = items
+        .iter()
+        .filter_map(|item| {
+            let MonoItem::Fn(instance) = item else { return None };
+            let audited = instance.has_body()
+                && !transformer.is_stubbed(*instance)
+                && !LIBRARY_CRATES.contains(&instance.def.krate().name.as_str());
+            audited.then(|| (item, unsafe_operations(*instance)))
+        })
+        .collect();
+    roots
+        .iter()
+        .filter_map(|root| {
+            let MonoItem::Fn(harness) = root else { return None };
+            let reachable = call_graph.reachable_from(root);
+            let harness_operations: BTreeSet<UnsafeOperation> = reachable
+                .iter()
+                .filter_map(|item| operations.get(item))
+                .flatten()
+                .cloned()
+                .collect();
+            Some((harness.name(), harness_operations.into_iter().collect()))
+        })
+        .collect()
+}
+
+/// Collect the unsafe operations in the body of the function.
+fn unsafe_operations(instance: Instance) -> Vec<UnsafeOperation> {
+    let body = instance.body().unwrap();
+    let mut visitor = UnsafeVisitor { body: &body, function: instance.name(), operations: vec![] };
+    visitor.visit_body(&body);
+    visitor.operations
+}
+
+struct UnsafeVisitor<'a> {
+    body: &'a Body,
+    function: String,
+    operations: Vec<UnsafeOperation>,
+}
+
+impl UnsafeVisitor<'_> {
+    fn record(&mut self, kind: UnsafeOperationKind, span: Span, detail: String) {
+        let file = span.get_filename().to_string();
+        // Operations inlined from the standard library have a remapped path.
+        if file.starts_with("/rustc/") {
+            return;
+        }
+        let line = span.get_lines().start_line;
+        let function = self.function.clone();
+        self.operations.push(UnsafeOperation { file, line, kind, function, detail });
+    }
+}
+
+impl MirVisitor for UnsafeVisitor<'_> {
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        let mut ty = self.body.locals()[place.local].ty;
+        for elem in &place.projection {
+            match (elem, ty.kind()) {
+                (ProjectionElem::Deref, TyKind::RigidTy(RigidTy::RawPtr(..))) => {
+                    self.record(
+                        UnsafeOperationKind::RawPointerDeref,
+                        location.span(),
+                        ty.to_string(),
+                    );
+                }
+                (ProjectionElem::Field(..), TyKind::RigidTy(RigidTy::Adt(def, _)))
+                    if def.kind() == AdtKind::Union =>
+                {
+                    self.record(UnsafeOperationKind::UnionAccess, location.span(), ty.to_string());
+                }
+                _ => {}
+            }
+            ty = elem.ty(ty).unwrap();
+        }
+        self.super_place(place, ptx, location)
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        if let Rvalue::Cast(CastKind::Transmute, operand, to) = rvalue {
+            let from = operand.ty(self.body.locals()).unwrap();
+            self.record(UnsafeOperationKind::Transmute, location.span(), format!("{from} to {to}"));
+        }
+        self.super_rvalue(rvalue, location)
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator, location: Location) {
+        if let TerminatorKind::Call { func, args, destination, .. } = &terminator.kind {
+            let ty = func.ty(self.body.locals()).unwrap();
+            if let TyKind::RigidTy(RigidTy::FnDef(def, fn_args)) = ty.kind()
+                && ty.kind().fn_sig().unwrap().value.safety == Safety::Unsafe
+            {
+                let instance = Instance::resolve(def, &fn_args).ok();
+                let is_transmute = instance.is_some_and(|instance| {
+                    instance.kind == InstanceKind::Intrinsic
+                        && instance.intrinsic_name().unwrap().starts_with("transmute")
+                });
+                if is_transmute {
+                    let from = args[0].ty(self.body.locals()).unwrap();
+                    let to = destination.ty(self.body.locals()).unwrap();
+                    self.record(
+                        UnsafeOperationKind::Transmute,
+                        location.span(),
+                        format!("{from} to {to}"),
+                    );
+                } else {
+                    let name = instance.map_or_else(|| def.name(), |instance| instance.name());
+                    self.record(UnsafeOperationKind::UnsafeCall, location.span(), name);
+                }
+            }
+        }
+        self.super_terminator(terminator, location)
+    }
+}
@@ -40,6 +40,8 @@
 mod args;
 #[cfg(feature = "cprover")]
 mod codegen_cprover_gotoc;
+#[cfg(feature = "why3")]
+mod codegen_why3;
 mod intrinsics;
 mod kani_compiler;
 mod kani_middle;
@@ -0,0 +1,249 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The API to embed the driver in other tools, e.g., test runners or CI services, which verifies a
+//! project and returns the results instead of printing them.
+//!
+//! ```no_run
+//! use kani_driver::{Input, Options, Session};
+//!
+//! let options = Options::new().args(["--quiet", "--harness", "check_parser"]);
+//! let session = Session::new(&options)?;
+//! let results = session.verify(&Input::Cargo)?;
+//! for harness in &results.harnesses {
+//!     println!("{}: {:?}", harness.name, harness.status);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The result types only expose what Kani reports to users, so they don't change with the
+//! internals of the driver. They are `#[non_exhaustive]` so fields can be added.
+
+use anyhow::Result;
+use clap::Parser;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::args::{CargoKaniArgs, ValidateArgs};
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::CheckStatus;
+use crate::harness_runner::{self, HarnessRunner};
+use crate::project;
+use crate::resume::CompletedHarness;
+use crate::session::KaniSession;
+
+/// The code to verify.
+#[derive(Clone, Debug)]
+pub enum Input {
+    /// The Cargo package in the current directory, or the packages selected by the options, e.g.,
+    /// with `--manifest-path` or `--workspace`.
+    Cargo,
+    /// A single Rust file, which is compiled as a crate named after the file by default.
+    File { path: PathBuf, crate_name: Option<String> },
+    /// Crates that another build system, e.g., Buck2 or Bazel, compiled with `kani-compiler`,
+    /// given by the metadata files that the compiler wrote. Their models are copied to the target
+    /// directory, and linked and verified there.
+    Prebuilt { metadata: Vec<PathBuf> },
+}
+
+/// The options of a verification run, which are the command line options of `cargo kani`, e.g.,
+/// `--harness` or `--default-unwind`.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    args: Vec<OsString>,
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Options::default()
+    }
+
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, T>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// A verification session, which locates the Kani installation and holds the options.
+pub struct Session {
+    session: KaniSession,
+}
+
+impl Session {
+    /// Parse and validate the options. Unlike the `kani` command, invalid options are returned as
+    /// an error rather than exiting the process.
+    pub fn new(options: &Options) -> Result<Session> {
+        let args = std::iter::once(OsString::from("cargo-kani")).chain(options.args.clone());
+        let args = CargoKaniArgs::try_parse_from(args)?;
+        args.validate()?;
+        Ok(Session { session: KaniSession::new(args.verify_opts)? })
+    }
+
+    /// Build the input and verify its harnesses. A harness that fails verification is reported
+    /// in the results, so an error means that the harnesses couldn't be verified, e.g., because
+    /// the build failed. With `--only-codegen`, no harness is verified. With `--resume`, the
+    /// harnesses that the interrupted run already verified are reported as
+    /// [HarnessStatus::Resumed].
+    pub fn verify(&self, input: &Input) -> Result<Results> {
+        let project = match input {
+            Input::Cargo => project::cargo_project(&self.session, false)?,
+            Input::File { path, crate_name } => {
+                project::standalone_project(path, crate_name.clone(), &self.session)?
+            }
+            Input::Prebuilt { metadata } => project::prebuilt_project(metadata, &self.session)?,
+        };
+        if self.session.args.only_codegen {
+            return Ok(Results { harnesses: vec![] });
+        }
+        let harnesses = self.session.determine_targets(&project.get_all_harnesses())?;
+        let runner = HarnessRunner { sess: &self.session, project: &project };
+        let results = runner.check_all_harnesses(&harnesses)?;
+        self.session.write_reports(&project, &results)?;
+        let resumed = self.session.resumed_harnesses.lock().unwrap();
+        let harnesses = resumed
+            .iter()
+            .map(HarnessResult::from)
+            .chain(results.iter().map(HarnessResult::from))
+            .collect();
+        Ok(Results { harnesses })
+    }
+}
+
+/// The results of a verification run.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Results {
+    pub harnesses: Vec<HarnessResult>,
+}
+
+impl Results {
+    /// Whether every harness was verified, including the ones skipped with `--resume`.
+    pub fn is_success(&self) -> bool {
+        self.harnesses.iter().all(|harness| {
+            matches!(
+                harness.status,
+                HarnessStatus::Success | HarnessStatus::Resumed { failed: false }
+            )
+        })
+    }
+}
+
+/// The result of a harness.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct HarnessResult {
+    /// The path of the harness, e.g., `parser::check_parser`.
+    pub name: String,
+    pub status: HarnessStatus,
+    /// The checks that failed, if the status is [HarnessStatus::Failure]. It may be empty if
+    /// verification didn't produce results, e.g., because CBMC crashed, and it is empty for
+    /// resumed harnesses.
+    pub failed_checks: Vec<FailedCheck>,
+    /// The verification time, which is the one of the interrupted run for resumed harnesses.
+    pub runtime: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HarnessStatus {
+    Success,
+    Failure,
+    Timeout,
+    OutOfMemory,
+    /// The harness was skipped with `--resume`, since the interrupted run already verified it.
+    /// `failed` tells whether that run reported a failure.
+    Resumed {
+        failed: bool,
+    },
+}
+
+/// A check that failed.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct FailedCheck {
+    /// The class of the check, e.g., `assertion` or `arithmetic_overflow`.
+    pub class: String,
+    pub description: String,
+    /// The location of the check, e.g., `src/lib.rs:10:5 in function check_parser`.
+    pub location: Option<String>,
+}
+
+impl From<&harness_runner::HarnessResult<'_>> for HarnessResult {
+    fn from(result: &harness_runner::HarnessResult<'_>) -> Self {
+        let verification = &result.result;
+        let status = match verification.status {
+            VerificationStatus::Success => HarnessStatus::Success,
+            VerificationStatus::Failure => HarnessStatus::Failure,
+            VerificationStatus::Timeout => HarnessStatus::Timeout,
+            VerificationStatus::OutOfMemory => HarnessStatus::OutOfMemory,
+        };
+        let failed_checks = match &verification.results {
+            Ok(properties) => properties
+                .iter()
+                .filter(|prop| prop.status == CheckStatus::Failure)
+                .map(|prop| FailedCheck {
+                    class: prop.property_class(),
+                    description: prop.description.clone(),
+                    location: (!prop.source_location.is_missing())
+                        .then(|| prop.source_location.to_string()),
+                })
+                .collect(),
+            Err(_) => vec![],
+        };
+        HarnessResult {
+            name: result.harness.pretty_name.clone(),
+            status,
+            failed_checks,
+            runtime: verification.runtime,
+        }
+    }
+}
+
+impl From<&CompletedHarness> for HarnessResult {
+    fn from(harness: &CompletedHarness) -> Self {
+        HarnessResult {
+            name: harness.harness.clone(),
+            status: HarnessStatus::Resumed { failed: harness.failed },
+            failed_checks: vec![],
+            runtime: Duration::from_secs_f64(harness.runtime),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::baseline::BaselineStatus;
+
+    #[test]
+    fn check_resumed_results() {
+        let resumed = |name: &str, status, failed| {
+            HarnessResult::from(&CompletedHarness {
+                harness: name.to_string(),
+                crate_name: "krate".to_string(),
+                fingerprints: vec![],
+                status,
+                failed,
+                runtime: 1.5,
+            })
+        };
+        let success = resumed("check_success", BaselineStatus::Success, false);
+        assert_eq!(success.status, HarnessStatus::Resumed { failed: false });
+        assert_eq!(success.runtime, Duration::from_millis(1500));
+        let results = Results { harnesses: vec![success.clone()] };
+        assert!(results.is_success());
+        let failure = resumed("check_failure", BaselineStatus::Failure, true);
+        let results = Results { harnesses: vec![success, failure] };
+        assert!(!results.is_success());
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements the `bazel` subcommand handling.
+
+use crate::args::{ValidateArgs, VerificationArgs};
+use clap::error::ErrorKind;
+use clap::{Error, Parser};
+use kani_metadata::UnstableFeature;
+use std::path::PathBuf;
+
+/// Verify crates that Bazel rules, or another build system, compiled with `kani-compiler`,
+/// instead of building them with cargo.
+///
+/// This is an **unstable option**.
+#[derive(Debug, Parser)]
+pub struct BazelArgs {
+    /// The crate information file written by the build rules, which lists the metadata file
+    /// that `kani-compiler` wrote for each crate to verify.
+    #[arg(long, value_name = "FILE", required_unless_present = "print_compiler_args")]
+    pub crate_info: Option<PathBuf>,
+
+    /// Print, as JSON, the compiler and the arguments the build rules must compile the crates
+    /// with, for the given verification options.
+    #[arg(long, conflicts_with = "crate_info")]
+    pub print_compiler_args: bool,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+impl ValidateArgs for BazelArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+
+        if !self
+            .verify_opts
+            .common_args
+            .unstable_features
+            .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `bazel` subcommand is unstable and requires -Z unstable-options",
+            ));
+        }
+
+        match &self.crate_info {
+            Some(crate_info) if !crate_info.is_file() => Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: `--crate-info` argument `{}` is not a file",
+                    crate_info.display()
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
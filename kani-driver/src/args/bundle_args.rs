@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the bundle and verify-bundle subcommands
+
+use crate::args::ValidateArgs;
+use clap::error::ErrorKind;
+use clap::{Error, Parser};
+use std::path::PathBuf;
+
+/// Verify a package and archive the evidence of what was proven: the toolchain, the Kani and CBMC
+/// versions, the options, the harness metadata, the goto binaries and the results.
+#[derive(Debug, Parser)]
+pub struct CargoBundleArgs {
+    /// The path of the archive to create.
+    #[arg(long, short, value_name = "FILE", default_value = "kani-bundle.tar.gz")]
+    pub output: PathBuf,
+}
+
+impl ValidateArgs for CargoBundleArgs {
+    fn validate(&self) -> Result<(), Error> {
+        let parent = self.output.parent();
+        if let Some(parent) = parent.filter(|dir| !dir.as_os_str().is_empty() && !dir.is_dir()) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid argument: `{}` is not a directory.", parent.display()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Verify the goto binaries of an archive created by `cargo kani bundle` again, with the options
+/// it records, and check that the results are the same.
+#[derive(Debug, Parser)]
+pub struct CargoVerifyBundleArgs {
+    /// The archive to verify.
+    #[arg(value_name = "FILE")]
+    pub bundle: PathBuf,
+}
+
+impl ValidateArgs for CargoVerifyBundleArgs {
+    fn validate(&self) -> Result<(), Error> {
+        if !self.bundle.is_file() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid argument: `{}` is not a regular file.", self.bundle.display()),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -33,11 +33,12 @@ pub struct CargoCommonArgs {
     #[arg(long)]
     pub workspace: bool,
 
-    /// Run Kani on the specified packages.
+    /// Run Kani on the specified packages. Like in cargo, each package can be given by its name,
+    /// a glob pattern that matches workspace members (e.g. `'parser-*'`), or `name@version`.
     #[arg(long, short, conflicts_with("workspace"), num_args(1..))]
     pub package: Vec<String>,
 
-    /// Exclude the specified packages
+    /// Exclude the specified packages. Accepts the same package selections as `--package`.
     #[arg(long, short, requires("workspace"), conflicts_with("package"), num_args(1..))]
     pub exclude: Vec<String>,
 }
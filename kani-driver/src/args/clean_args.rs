@@ -0,0 +1,71 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the clean subcommand
+
+use crate::args::ValidateArgs;
+use clap::{Error, Parser};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Remove the artifacts generated by Kani in the target directory, without touching the ones
+/// generated by regular cargo builds.
+#[derive(Debug, Parser)]
+pub struct CargoCleanArgs {
+    /// Only remove the artifacts that are older than the given age, e.g., `12h`, `7d` or `2w`.
+    #[arg(long, value_name = "AGE")]
+    pub older_than: Option<Age>,
+
+    /// Keep the artifacts of the given number of most recent builds of each crate, and of the
+    /// most recent coverage runs, and remove the older ones.
+    #[arg(long, value_name = "N")]
+    pub keep_builds: Option<usize>,
+
+    /// Print what would be removed, without removing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// An age given as a number followed by a unit: `s`, `m`, `h`, `d` or `w`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Age(pub Duration);
+
+impl FromStr for Age {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid age `{value}`, expected e.g. `30m`, `12h` or `7d`");
+        let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let (number, unit) = value.split_at(split);
+        let number: u64 = number.parse().map_err(|_| invalid())?;
+        let unit_secs = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            "w" => 7 * 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        Ok(Age(Duration::from_secs(number * unit_secs)))
+    }
+}
+
+impl ValidateArgs for CargoCleanArgs {
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_age_parsing() {
+        assert_eq!("90s".parse(), Ok(Age(Duration::from_secs(90))));
+        assert_eq!("12h".parse(), Ok(Age(Duration::from_secs(12 * 3600))));
+        assert_eq!("2w".parse(), Ok(Age(Duration::from_secs(14 * 86400))));
+        assert!("7".parse::<Age>().is_err());
+        assert!("d".parse::<Age>().is_err());
+        assert!("7y".parse::<Age>().is_err());
+    }
+}
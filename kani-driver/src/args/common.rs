@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! Define arguments that should be common to all subcommands in Kani.
 use crate::args::ValidateArgs;
-use clap::{error::Error, error::ErrorKind};
+use clap::error::Error;
 pub use kani_metadata::{EnabledUnstableFeatures, UnstableFeature};
 
 /// Common Kani arguments that we expect to be included in most subcommands.
@@ -21,11 +21,6 @@ pub struct CommonArgs {
     #[arg(long, hide_short_help = true)]
     pub enable_unstable: bool,
 
-    /// We no longer support dry-run. Use `--verbose` to see the commands being printed during
-    /// Kani execution.
-    #[arg(long, hide = true)]
-    pub dry_run: bool,
-
     /// Enable an unstable feature.
     #[clap(flatten)]
     pub unstable_features: EnabledUnstableFeatures,
@@ -33,12 +28,6 @@ pub struct CommonArgs {
 
 impl ValidateArgs for CommonArgs {
     fn validate(&self) -> Result<(), Error> {
-        if self.dry_run {
-            return Err(Error::raw(
-                ErrorKind::ValueValidation,
-                "The `--dry-run` option is obsolete. Use --verbose instead.",
-            ));
-        }
         Ok(())
     }
 }
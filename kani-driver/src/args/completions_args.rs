@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the completions subcommand
+
+use crate::args::ValidateArgs;
+use clap::{Error, Parser};
+use clap_complete::Shell;
+
+/// Print a script that completes the arguments of `cargo-kani` in the given shell.
+/// In bash and fish, the values of `--harness` are completed with the harnesses found by the
+/// latest build of the package.
+#[derive(Debug, Parser)]
+pub struct CargoCompletionsArgs {
+    /// The shell to generate the completion script for.
+    #[arg(required_unless_present = "harness_names")]
+    pub shell: Option<Shell>,
+
+    /// Print the names of the harnesses found by the latest build of the package, one per line.
+    /// This is used by the completion scripts.
+    #[arg(long, hide = true, conflicts_with = "shell")]
+    pub harness_names: bool,
+}
+
+impl ValidateArgs for CargoCompletionsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
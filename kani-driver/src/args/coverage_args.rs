@@ -0,0 +1,71 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the coverage subcommand
+
+use crate::args::ValidateArgs;
+use clap::error::ErrorKind;
+use clap::{Error, Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// Process the coverage results saved by previous verification runs.
+#[derive(Debug, Parser)]
+pub struct CargoCoverageArgs {
+    /// Merge the coverage results of all harnesses into a single report that shows which lines
+    /// are covered by any harness.
+    #[arg(long)]
+    pub merge: bool,
+
+    /// The coverage results to merge. Each path can be the folder with the results of a
+    /// verification run (`kanicov_<date>`), or the raw coverage results of a harness.
+    /// Defaults to the results of the latest verification run of the package.
+    pub paths: Vec<PathBuf>,
+
+    /// The format of the merged report.
+    #[arg(long, value_enum, default_value_t = CoverageReportFormat::Summary)]
+    pub format: CoverageReportFormat,
+
+    /// Write the merged report to the given file instead of printing it.
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Save a snapshot of the lines covered by any harness to the given file.
+    #[arg(long, value_name = "FILE")]
+    pub save_snapshot: Option<PathBuf>,
+
+    /// Compare the merged coverage against a snapshot saved with `--save-snapshot`, and report
+    /// the lines that lost proof coverage.
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Return an error if any line lost proof coverage compared to the baseline.
+    #[arg(long, requires("baseline"))]
+    pub fail_on_regression: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CoverageReportFormat {
+    /// A summary of the line coverage of each file, including the uncovered lines.
+    Summary,
+    /// An LCOV tracefile with the line, function and branch coverage of each file.
+    Lcov,
+    /// A Cobertura XML report with the line and branch coverage of each file.
+    Cobertura,
+}
+
+impl ValidateArgs for CargoCoverageArgs {
+    fn validate(&self) -> Result<(), Error> {
+        if !self.merge {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `coverage` subcommand currently requires the `--merge` argument.",
+            ));
+        }
+        if let Some(path) = self.paths.iter().find(|path| !path.exists()) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid argument: `{}` does not exist.", path.display()),
+            ));
+        }
+        Ok(())
+    }
+}
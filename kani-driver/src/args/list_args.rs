@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the list subcommand
+
+use crate::args::ValidateArgs;
+use clap::error::ErrorKind;
+use clap::{Error, Parser, ValueEnum};
+use std::path::PathBuf;
+
+/// List the harnesses and contracts of a package, with the attributes that control their
+/// verification.
+#[derive(Debug, Parser)]
+pub struct CargoListArgs {
+    /// The format of the inventory.
+    #[arg(long, value_enum, default_value_t = ListFormat::Pretty)]
+    pub format: ListFormat,
+
+    /// Write the inventory to the given file instead of printing it.
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    /// A human-readable summary of the harnesses and contracts of each crate.
+    Pretty,
+    /// A JSON document with every harness and function of each crate, meant for external tools.
+    Json,
+}
+
+impl ValidateArgs for CargoListArgs {
+    fn validate(&self) -> Result<(), Error> {
+        let parent = self.output.as_ref().and_then(|output| output.parent());
+        if let Some(parent) = parent.filter(|dir| !dir.as_os_str().is_empty() && !dir.is_dir()) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid argument: `{}` is not a directory.", parent.display()),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -3,22 +3,31 @@
 //! Module that define Kani's command line interface. This includes all subcommands.
 
 pub mod assess_args;
+pub mod bazel_args;
+pub mod bundle_args;
 pub mod cargo;
+pub mod clean_args;
 pub mod common;
+pub mod completions_args;
+pub mod coverage_args;
+pub mod list_args;
+pub mod mutate_args;
 pub mod playback_args;
 pub mod std_args;
+pub mod why_reachable_args;
 
 pub use assess_args::*;
 
 use self::common::*;
 use crate::args::cargo::CargoTargetArgs;
+use crate::metadata::HarnessFilter;
 use crate::util::warning;
 use cargo::CargoCommonArgs;
 use clap::builder::{PossibleValue, TypedValueParser};
 use clap::{error::ContextKind, error::ContextValue, error::Error, error::ErrorKind, ValueEnum};
-use kani_metadata::CbmcSolver;
+use kani_metadata::{CbmcSolver, HarnessChecks};
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use strum::VariantNames;
 
@@ -93,6 +102,9 @@ pub enum StandaloneSubcommand {
     Playback(Box<playback_args::KaniPlaybackArgs>),
     /// Verify the rust standard library.
     VerifyStd(Box<std_args::VerifyStdArgs>),
+    /// Verify crates built by Bazel rules, or another build system, instead of cargo.
+    #[command(visible_alias = "prebuilt")]
+    Bazel(Box<bazel_args::BazelArgs>),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -106,6 +118,16 @@ pub struct CargoKaniArgs {
     #[command(subcommand)]
     pub command: Option<CargoKaniSubcommand>,
 
+    /// Watch the workspace for changes, and re-verify the harnesses affected by each change.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub watch: bool,
+
+    /// Verify the documentation examples tagged `kani`, and the ones that contain `kani::proof`.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub doc: bool,
+
     #[command(flatten)]
     pub verify_opts: VerificationArgs,
 }
@@ -118,6 +140,39 @@ pub enum CargoKaniSubcommand {
 
     /// Execute concrete playback testcases of a local package.
     Playback(Box<playback_args::CargoPlaybackArgs>),
+
+    /// Merge the coverage results of previous verification runs.
+    /// This subcommand is unstable and requires `-Z source-coverage` to be used.
+    #[command(hide = true)]
+    Coverage(Box<coverage_args::CargoCoverageArgs>),
+
+    /// List the harnesses and contracts of a package, with the attributes that control their
+    /// verification. This subcommand is unstable and requires `-Z unstable-options` to be used.
+    List(Box<list_args::CargoListArgs>),
+
+    /// Print a script that completes the arguments of `cargo-kani` in the given shell.
+    Completions(Box<completions_args::CargoCompletionsArgs>),
+
+    /// Verify a package and archive the toolchain, versions, options, goto binaries and results.
+    /// This subcommand is unstable and requires `-Z unstable-options` to be used.
+    Bundle(Box<bundle_args::CargoBundleArgs>),
+
+    /// Check that the results recorded by `cargo kani bundle` can be reproduced.
+    /// This subcommand is unstable and requires `-Z unstable-options` to be used.
+    VerifyBundle(Box<bundle_args::CargoVerifyBundleArgs>),
+
+    /// Remove the goto binaries, symbol tables, metadata and cached results generated by Kani,
+    /// by age or by build. This subcommand is unstable and requires `-Z unstable-options` to be
+    /// used.
+    Clean(Box<clean_args::CargoCleanArgs>),
+
+    /// Print one path through which a harness reaches a function.
+    /// This subcommand is unstable and requires `-Z unstable-options` to be used.
+    WhyReachable(Box<why_reachable_args::CargoWhyReachableArgs>),
+
+    /// Check that the harnesses reject small mutations of the code, e.g., `<` replaced by `<=`.
+    /// This subcommand is unstable and requires `-Z unstable-options` to be used.
+    Mutate(Box<mutate_args::CargoMutateArgs>),
 }
 
 // Common arguments for invoking Kani for verification purpose. This gets put into KaniContext,
@@ -143,11 +198,82 @@ pub struct VerificationArgs {
         value_enum
     )]
     pub concrete_playback: Option<ConcretePlaybackMode>,
+    /// The format of the generated concrete playback tests. `proptest` also generates a proptest
+    /// regression entry for each counterexample, which is added to the `proptest-regressions`
+    /// folder of the crate with `--concrete-playback=inplace`.
+    #[arg(
+        long,
+        hide_short_help = true,
+        value_enum,
+        default_value_t = ConcretePlaybackFormat::UnitTest,
+        requires("concrete_playback")
+    )]
+    pub concrete_playback_format: ConcretePlaybackFormat,
     /// Keep temporary files generated throughout Kani process. This is already the default
     /// behavior for `cargo-kani`.
     #[arg(long, hide_short_help = true)]
     pub keep_temps: bool,
 
+    /// Copy the artifacts of each verified harness to `kani/<crate>/<harness>/` in the target
+    /// directory, together with a `manifest.json` that describes them. `failed` only keeps the
+    /// artifacts of the harnesses that failed, and `none` removes the ones of previous runs.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, value_name = "HARNESSES")]
+    pub keep_artifacts: Option<KeepArtifacts>,
+
+    /// Generate one goto binary per crate (and set of stubs), shared by all its harnesses, instead
+    /// of one per harness. The entry point is selected for each harness before instrumentation.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub shared_model: bool,
+
+    /// Write the functions included in the model of each harness to `kani-reachability.json` in
+    /// the output directory, with their crate and whether they were stubbed.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub reachability_report: bool,
+
+    /// Write the unsafe operations reachable from each harness to `kani-unsafe-operations.json`
+    /// in the output directory, with their location and whether Kani checks them.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub unsafe_report: bool,
+
+    /// Check that the branches and the memory indices of the harnesses don't depend on the values
+    /// marked with `kani::secret`, so the code runs in constant time with respect to them.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub check_constant_time: bool,
+
+    /// Cache the linked and instrumented goto binaries of each harness, keyed by the hash of
+    /// their inputs, so that unchanged harnesses skip linking and instrumentation.
+    #[arg(long)]
+    pub cache_goto: bool,
+
+    /// The directory of the cache used by `--cache-goto`. Defaults to `kani-cache` in the output
+    /// directory. The directory can be shared by several machines, e.g., on a network file system.
+    #[arg(long, requires("cache_goto"), value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Write the contracts verified in each library package to `kani-contracts.json`, next to its
+    /// manifest, signed with the key given to `--contracts-key`.
+    /// This option is unstable and requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true, requires("contracts_key"))]
+    pub export_contracts: bool,
+
+    /// Replace the functions whose contract was verified in a dependency, according to the
+    /// `kani-contracts.json` of the dependency, by their contract, as with
+    /// `#[kani::stub_verified]`. Only the summaries signed with the key given to
+    /// `--contracts-key` are used.
+    /// This option is unstable and requires `-Z function-contracts` to be used.
+    #[arg(long, hide_short_help = true, requires("contracts_key"))]
+    pub assume_contracts: bool,
+
+    /// The file that contains the secret key used to sign and check the summaries of verified
+    /// contracts.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub contracts_key: Option<PathBuf>,
+
     /// Generate C file equivalent to inputted program.
     /// This feature is unstable and it requires `--enable-unstable` to be used
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
@@ -157,6 +283,24 @@ pub struct VerificationArgs {
     #[arg(long)]
     pub target_dir: Option<PathBuf>,
 
+    /// Read the project configuration from the given file instead of the `kani.toml` next to
+    /// `Cargo.toml`, if any.
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Verify the code as compiled for the given target triple, or for the custom target described
+    /// by the given rustc target spec JSON file, instead of the host.
+    /// The Kani libraries for the target must have been built first.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long = "target", value_name = "TRIPLE|SPEC")]
+    pub target_triple: Option<String>,
+
+    /// Verify `no_std` crates against `core` and `alloc` only, which is required for targets
+    /// without an operating system, e.g. `thumbv7em-none-eabihf`.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long)]
+    pub no_std: bool,
+
     /// Force Kani to rebuild all packages before the verification.
     #[arg(long)]
     pub force_build: bool,
@@ -165,11 +309,26 @@ pub struct VerificationArgs {
     #[arg(long, default_value = "regular", ignore_case = true, value_enum)]
     pub output_format: OutputFormat,
 
+    /// With `--quiet`, show a single line with the progress of the verification, and print the
+    /// results of the harnesses that fail.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, requires("quiet"))]
+    pub progress: bool,
+
+    /// Stream the events of the run as JSON objects on stdout, one per line, instead of the human
+    /// readable output: the start of the build, the start of each harness, the result and trace
+    /// of each property, the end of each harness, and the end of the run.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, default_value = "human", value_enum, value_name = "FMT")]
+    pub message_format: MessageFormat,
+
     #[command(flatten)]
     pub checks: CheckArgs,
 
     /// If specified, only run harnesses that match this filter. This option can be provided
     /// multiple times, which will run all tests matching any of the filters.
+    /// A filter can be a substring of the harness name, a glob pattern (e.g. `parser::*`), or a
+    /// regular expression delimited by `/` (e.g. `/_roundtrip$/`).
     /// If used with --exact, the harness filter will only match the exact fully qualified name of a harness.
     #[arg(long = "harness", num_args(1), value_name = "HARNESS_FILTER")]
     pub harnesses: Vec<String>,
@@ -178,6 +337,23 @@ pub struct VerificationArgs {
     #[arg(long, requires("harnesses"))]
     pub exact: bool,
 
+    /// Skip the harnesses with the given `#[kani::tag]`. This option can be provided multiple
+    /// times.
+    #[arg(long = "skip-tag", value_name = "TAG")]
+    pub skip_tags: Vec<String>,
+
+    /// Only verify the harnesses of shard `K` out of `N`, e.g., `--partition 2/4`. The harnesses
+    /// are split deterministically, so CI jobs can verify the shards in parallel.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "K/N")]
+    pub partition: Option<Partition>,
+
+    /// Balance the shards of `--partition` using the runtimes of the harnesses in a baseline
+    /// saved with `--save-baseline`, instead of their number.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, requires("partition"), value_name = "FILE")]
+    pub partition_runtimes: Option<PathBuf>,
+
     /// Link external C files referenced by Rust code.
     /// This is an experimental feature and requires `-Z c-ffi` to be used
     #[arg(long, hide = true, num_args(1..))]
@@ -188,6 +364,11 @@ pub struct VerificationArgs {
     /// Kani will only compile the crate. No verification will be performed
     #[arg(long, hide_short_help = true)]
     pub only_codegen: bool,
+    /// Print the harnesses that would be verified and their settings, without linking or
+    /// verifying them. The crates are still compiled, since that's how harnesses are found.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, conflicts_with("only_codegen"))]
+    pub dry_run: bool,
 
     /// Deprecated flag. This is a no-op since we no longer support the legacy linker and
     /// it will be removed in a future Kani release.
@@ -203,8 +384,60 @@ pub struct VerificationArgs {
     /// Specify the value used for loop unwinding for the specified harness in CBMC
     #[arg(long, requires("harnesses"))]
     pub unwind: Option<u32>,
+    /// When a harness fails only because of unwinding assertions, verify it again with
+    /// exponentially increasing unwind bounds, and report the bound that sufficed.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true, conflicts_with("unwind"))]
+    pub infer_unwind: bool,
+    /// The maximum unwind bound tried by `--infer-unwind`.
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("infer_unwind"),
+        default_value_t = 128,
+        value_name = "N"
+    )]
+    pub infer_unwind_limit: u32,
+    /// When `--infer-unwind` retries a harness where a single loop failed its unwinding assertion,
+    /// unwind that loop incrementally up to `--infer-unwind-limit` in one CBMC run, reusing the
+    /// solver state between bounds instead of verifying the harness again for each bound.
+    #[arg(long, hide_short_help = true, requires("infer_unwind"))]
+    pub incremental_unwind: bool,
+    /// Search for up to N distinct counterexamples for each failed property, by blocking the
+    /// previous ones, and list them all.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(
+        long,
+        hide_short_help = true,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub counterexamples: Option<u32>,
+    /// Execute the counterexamples of the failed harnesses concretely with their concrete
+    /// playback tests, and report the failures that none of them reproduces as needing review.
+    /// Combine with `--counterexamples N` to execute up to N counterexamples per failed property.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true)]
+    pub concolic: bool,
+    /// Specify the maximum time in seconds to verify each harness. CBMC is stopped once this
+    /// limit is reached. The `#[kani::timeout]` attribute takes precedence over this value.
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub default_harness_timeout: Option<u64>,
+    /// Specify the maximum memory in MiB that CBMC and the solver it runs may use to verify each
+    /// harness. CBMC is stopped once this limit is exceeded, and the harness is reported as out
+    /// of memory. The `#[kani::memory_limit]` attribute takes precedence over this value.
+    /// Memory limits don't apply to remote workers.
+    #[arg(
+        long,
+        value_name = "MIB",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "remote_workers"
+    )]
+    pub memory_limit: Option<u64>,
     /// Specify the CBMC solver to use. Overrides the harness `solver` attribute.
     /// If no solver is specified (with --solver or harness attribute), Kani will use CaDiCaL.
+    /// Use `portfolio:<SOLVER>,<SOLVER>,...` to run several solvers in parallel on each harness,
+    /// and use the result of the first one to finish.
     #[arg(long, value_parser = CbmcSolverValueParser::new(CbmcSolver::VARIANTS))]
     pub solver: Option<CbmcSolver>,
     /// Pass through directly to CBMC; must be the last flag.
@@ -218,10 +451,77 @@ pub struct VerificationArgs {
     // consumes everything
     pub cbmc_args: Vec<OsString>,
 
+    /// The engine that verifies the goto binary of each harness. `esbmc` runs ESBMC, which must
+    /// be in the `PATH`, instead of CBMC, e.g., to use its k-induction mode with
+    /// `--esbmc-args --k-induction`.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, default_value = "cbmc", hide_short_help = true)]
+    pub engine: Engine,
+
+    /// Pass through directly to ESBMC with `--engine esbmc`; must be the last flag.
+    #[arg(long, allow_hyphen_values = true, num_args(0..), hide_short_help = true)]
+    // consumes everything
+    pub esbmc_args: Vec<OsString>,
+
     /// Number of parallel jobs, defaults to 1
     #[arg(short, long, hide = true, requires("enable_unstable"))]
     pub jobs: Option<Option<usize>>,
 
+    /// Stop verifying harnesses once one of them fails. Harnesses that are already being verified
+    /// in parallel are still completed.
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Resume a verification run that was interrupted, e.g., by a crash or Ctrl-C. The harnesses
+    /// that the interrupted run already verified are skipped, and their results are included in
+    /// the final summary. This assumes the code under verification didn't change since.
+    #[arg(long)]
+    pub resume: bool,
+
+    /// The categories of harness results that make Kani exit with an error, as a comma separated
+    /// list. By default, all categories but `cover-miss` are errors.
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "CATEGORIES",
+        default_values_t = DEFAULT_FAIL_ON
+    )]
+    pub fail_on: Vec<FailureCategory>,
+
+    /// Report the resources used to verify each harness: wall-clock time, peak memory, number of
+    /// VCCs, size of the SAT problem and solver time. They are printed in the final summary and
+    /// written to `kani-stats.json` in the output directory.
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Push the metrics of the run and of each harness (duration, result, VCCs and peak memory)
+    /// to a StatsD server, e.g., `statsd://localhost:8125`, or to an OpenTelemetry collector
+    /// over OTLP/HTTP, e.g., `http://localhost:4318/v1/metrics`.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true, value_name = "URL")]
+    pub metrics: Option<MetricsEndpoint>,
+
+    /// Report the time spent in each phase: the build and codegen, and for each harness, linking,
+    /// the goto-instrument passes, CBMC symbolic execution and SAT solving.
+    #[arg(long)]
+    pub profile_phases: bool,
+
+    /// Verify the harnesses on a remote worker, which is reached with `ssh` and must have CBMC in
+    /// its path. This option can be repeated to distribute the harnesses across several workers,
+    /// and a host can be repeated to verify several harnesses on it at once.
+    #[arg(long = "remote-worker", value_name = "HOST")]
+    pub remote_workers: Vec<String>,
+
+    /// Directory where the goto binaries are copied on the remote workers.
+    #[arg(
+        long,
+        value_name = "DIR",
+        default_value = "/tmp/kani-remote",
+        requires = "remote_workers"
+    )]
+    pub remote_dir: String,
+
     /// Enable extra pointer checks such as invalid pointers in relation operations and pointer
     /// arithmetic overflow.
     /// This feature is unstable and it may yield false counter examples. It requires
@@ -236,6 +536,11 @@ pub struct VerificationArgs {
     /// Disable restricting the targets of virtual table function pointer calls
     #[arg(long, hide_short_help = true)]
     pub no_restrict_vtable: bool,
+    /// Restrict the targets of plain function pointer calls to the functions whose address is
+    /// taken with the same signature.
+    /// This feature is unstable and it requires `--enable-unstable` to be used
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub restrict_fn_ptrs: bool,
     /// Turn off assertion reachability checks
     #[arg(long)]
     pub no_assertion_reach_checks: bool,
@@ -245,6 +550,70 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
     pub ignore_global_asm: bool,
 
+    /// How to handle reachable `asm!` blocks that don't have a model registered with
+    /// `#[kani::asm_model]`. `havoc` assigns nondeterministic values to their outputs and
+    /// ignores any other effect, which may cause false proofs.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, value_name = "MODE", default_value = "unsupported")]
+    pub inline_asm: InlineAsm,
+
+    /// How to encode transcendental float functions, such as `sin`, `exp`, `log` and `powf`.
+    /// `model` replaces them by a nondeterministic value constrained by the range of the function
+    /// and by its monotonicity, which is sound but may cause spurious failures.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, value_name = "MODE", default_value = "library")]
+    pub float_math: FloatMath,
+
+    /// Optimize the MIR of every function before it's translated, which shrinks the formulas
+    /// CBMC has to solve. `1` propagates constants and simplifies the control flow, and `2` also
+    /// inlines tiny functions. Use `0` to disable the optimizations when debugging a trace, since
+    /// the inlined functions don't appear in it.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(
+        long,
+        hide_short_help = true,
+        value_name = "LEVEL",
+        default_value_t = 0,
+        value_parser = clap::value_parser!(u8).range(0..=2),
+        conflicts_with("coverage")
+    )]
+    pub mir_opt_level: u8,
+
+    /// Verify the code as compiled for a variant of the target with the given byte order, so
+    /// transmutes, `to_ne_bytes` and pointer casts follow it. `both` verifies the harnesses once
+    /// with each byte order. The Kani libraries for the variant must have been built first.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, default_value = "native")]
+    pub endianness: Endianness,
+
+    /// How to handle reachable intrinsics that Kani doesn't support. `error` aborts compilation,
+    /// `unreachable` fails verification if they are reached, and `havoc` assigns a
+    /// nondeterministic value to their result and ignores any other effect, which may cause false
+    /// proofs.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, value_name = "MODE", default_value = "unreachable")]
+    pub unknown_intrinsics: UnknownIntrinsics,
+
+    /// The backend that generates the verification model. `why3` generates a WhyML program per
+    /// crate instead of a goto program, whose harnesses can be proved with `why3 prove`. Kani
+    /// doesn't run Why3, so this requires `--only-codegen`. `smt2` writes a self-contained
+    /// SMT-LIB2 script per harness, which is satisfiable if and only if a property of the harness
+    /// can fail.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, default_value = "cprover", hide_short_help = true)]
+    pub backend: Backend,
+
+    /// The SMT solver that checks the scripts of `--backend smt2`. Without it, Kani only writes
+    /// the scripts.
+    #[arg(long, value_enum, value_name = "SOLVER", hide_short_help = true)]
+    pub smt_solver: Option<SmtSolver>,
+
+    /// Whether panics abort the execution or unwind the stack. With `unwind`, `catch_unwind` can
+    /// stop a panic, and the destructors of the crate under verification run while unwinding.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, value_enum, value_name = "STRATEGY", default_value = "abort")]
+    pub panic_strategy: PanicStrategy,
+
     /// Write the GotoC symbol table to a file in JSON format instead of goto binary format.
     #[arg(long, hide_short_help = true)]
     pub write_json_symtab: bool,
@@ -257,6 +626,16 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
     pub no_slice_formula: bool,
 
+    /// Propagate constants through the goto program with goto-instrument before verification,
+    /// which removes the code that they make unreachable.
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub constant_propagation: bool,
+
+    /// Remove the instructions that cannot affect any property with goto-instrument's
+    /// `--full-slice` before verification.
+    #[arg(long, hide_short_help = true, requires("enable_unstable"))]
+    pub full_slice: bool,
+
     /// Synthesize loop contracts for all loops.
     #[arg(
         long,
@@ -278,6 +657,55 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub coverage: bool,
 
+    /// The format used to save the coverage results. `lcov` and `cobertura` write a single report
+    /// with the coverage of all harnesses, which can be consumed by other tools.
+    #[arg(
+        long,
+        hide_short_help = true,
+        value_enum,
+        default_value_t = CoverageFormat::Raw,
+        requires("coverage")
+    )]
+    pub coverage_format: CoverageFormat,
+
+    /// Also report which directions of each branch were covered, in addition to the covered
+    /// code regions.
+    #[arg(long, hide_short_help = true, requires("coverage"))]
+    pub branch_coverage: bool,
+
+    /// Write a SARIF 2.1.0 report with the verification failures to the given file.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub sarif: Option<PathBuf>,
+
+    /// Write a self-contained HTML report with the verification results to the given file.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub html_report: Option<PathBuf>,
+
+    /// Compare the results against a baseline saved with `--save-baseline`. Only harnesses that
+    /// newly fail or got slower than the baseline cause Kani to return an error.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Save the status and runtime of each harness to the given file, to be used with
+    /// `--baseline` in later runs.
+    /// This option is unstable and requires `-Z unstable-options` to be used.
+    #[arg(long, hide_short_help = true, value_name = "FILE")]
+    pub save_baseline: Option<PathBuf>,
+
+    /// The runtime increase (in percent) over the baseline for a harness to be reported as a
+    /// regression.
+    #[arg(
+        long,
+        hide_short_help = true,
+        requires("baseline"),
+        default_value_t = 50,
+        value_name = "PERCENT"
+    )]
+    pub baseline_slowdown_threshold: u32,
+
     /// Arguments to pass down to Cargo
     #[command(flatten)]
     pub cargo: CargoCommonArgs,
@@ -296,9 +724,10 @@ pub fn restrict_vtable(&self) -> bool {
         // if we flip the default, this will become: !self.no_restrict_vtable
     }
 
-    /// Assertion reachability checks should be disabled when running with --visualize
+    /// Assertion reachability checks should be disabled when running with --visualize, and with
+    /// `--backend smt2`, whose scripts don't distinguish them from the other properties.
     pub fn assertion_reach_checks(&self) -> bool {
-        !self.no_assertion_reach_checks && !self.visualize
+        !self.no_assertion_reach_checks && !self.visualize && self.backend != Backend::Smt2
     }
 
     /// Suppress our default value, if the user has supplied it explicitly in --cbmc-args
@@ -313,7 +742,8 @@ pub fn cbmc_object_bits(&self) -> Option<u32> {
     /// Computes how many threads should be used to verify harnesses.
     pub fn jobs(&self) -> Option<usize> {
         match self.jobs {
-            None => Some(1),          // no argument, default 1
+            // no argument, default 1, or one job per remote worker
+            None => Some(self.remote_workers.len().max(1)),
             Some(None) => None,       // -j
             Some(Some(x)) => Some(x), // -j=x
         }
@@ -329,6 +759,34 @@ pub fn is_stubbing_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::Stubbing)
             || self.is_function_contracts_enabled()
     }
+
+    /// Whether CBMC should produce a trace for failed properties.
+    /// Concrete playback needs the trace values, and reports use the trace to locate failures.
+    pub fn requires_trace(&self) -> bool {
+        self.concrete_playback.is_some()
+            || self.sarif.is_some()
+            || self.html_report.is_some()
+            || self.message_format == MessageFormat::Json
+            || self.searches_counterexamples()
+    }
+
+    /// Whether Kani should search for more than one counterexample per failed property.
+    pub fn searches_counterexamples(&self) -> bool {
+        self.counterexamples.is_some_and(|n| n > 1)
+    }
+
+    /// Return an error if an unstable option was used without `-Z unstable-options`.
+    fn check_unstable_option(&self, is_set: bool, option: &str) -> Result<(), Error> {
+        if is_set && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!("The `{option}` argument is unstable and requires `-Z unstable-options`"),
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -339,11 +797,200 @@ pub enum ConcretePlaybackMode {
     InPlace,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ConcretePlaybackFormat {
+    /// A unit test that replays the raw bytes of the counterexample.
+    UnitTest,
+    /// A unit test, plus a proptest regression entry with the counterexample.
+    Proptest,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CoverageFormat {
+    /// The raw coverage results of each harness, in JSON.
+    Raw,
+    /// An LCOV tracefile with the line and function coverage of all harnesses.
+    Lcov,
+    /// A Cobertura XML report with the line and branch coverage of all harnesses.
+    Cobertura,
+}
+
+/// The categories of harness results that make Kani exit with an error by default.
+const DEFAULT_FAIL_ON: [FailureCategory; 4] = [
+    FailureCategory::Failure,
+    FailureCategory::Timeout,
+    FailureCategory::OutOfMemory,
+    FailureCategory::Unsupported,
+];
+
+/// The categories of harness results that can make Kani exit with an error.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FailureCategory {
+    /// A property failed.
+    Failure,
+    /// The harness exceeded its timeout.
+    Timeout,
+    /// The harness exceeded its memory limit.
+    OutOfMemory,
+    /// The only failures are reachable constructs that Kani does not support.
+    Unsupported,
+    /// The harness succeeded, but some `kani::cover` property is not satisfiable.
+    CoverMiss,
+}
+
+/// A shard of the harnesses selected with `--partition K/N`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    /// The index of the shard, starting at 1.
+    pub shard: usize,
+    pub total: usize,
+}
+
+impl FromStr for Partition {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (shard, total) = value.split_once('/').ok_or("expected `K/N`, e.g., `1/4`")?;
+        let shard: usize = shard.parse().map_err(|_| format!("invalid shard `{shard}`"))?;
+        let total: usize =
+            total.parse().map_err(|_| format!("invalid number of shards `{total}`"))?;
+        if shard == 0 || shard > total {
+            return Err(format!("the shard must be between 1 and {total}"));
+        }
+        Ok(Partition { shard, total })
+    }
+}
+
+/// Where `--metrics` pushes the metrics of a run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MetricsEndpoint {
+    /// The `host:port` address of a StatsD server.
+    Statsd(String),
+    /// The URL of the metrics endpoint of an OpenTelemetry collector.
+    Otlp(String),
+}
+
+impl FromStr for MetricsEndpoint {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(address) = value.strip_prefix("statsd://") {
+            Ok(MetricsEndpoint::Statsd(address.trim_end_matches('/').to_string()))
+        } else if value.starts_with("http://") || value.starts_with("https://") {
+            Ok(MetricsEndpoint::Otlp(value.to_string()))
+        } else {
+            Err("expected a `statsd://` or an `http(s)://` URL".to_string())
+        }
+    }
+}
+
+/// The format of the messages printed on stdout.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    Human,
+    /// JSON objects, one per line.
+    Json,
+}
+
+/// How `--inline-asm` handles the `asm!` blocks without a model.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum InlineAsm {
+    /// Fail verification if an `asm!` block is reachable.
+    Unsupported,
+    /// Assign nondeterministic values to the outputs of `asm!` blocks and continue.
+    Havoc,
+}
+
+/// How `--float-math` encodes transcendental float functions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FloatMath {
+    /// Call the functions of the C math library, which CBMC may not define.
+    Library,
+    /// Use an over-approximating model of each function.
+    Model,
+}
+
+/// The byte order selected with `--endianness`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Endianness {
+    /// The byte order of the target.
+    Native,
+    Little,
+    Big,
+    /// Verify with each byte order in turn.
+    Both,
+}
+
+/// How `--unknown-intrinsics` handles the intrinsics that Kani doesn't support.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum UnknownIntrinsics {
+    /// Abort compilation if an unsupported intrinsic is reachable.
+    Error,
+    /// Fail verification if an unsupported intrinsic is reached.
+    Unreachable,
+    /// Assign a nondeterministic value to the result of unsupported intrinsics and continue.
+    Havoc,
+}
+
+/// The backend selected with `--backend`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// Generate goto programs, which are verified with CBMC.
+    Cprover,
+    /// Generate WhyML programs, which can be verified with Why3.
+    Why3,
+    /// Generate SMT-LIB2 scripts in the bitvector and array theories, which can be checked with
+    /// any SMT solver.
+    Smt2,
+}
+
+/// The engine selected with `--engine`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Engine {
+    Cbmc,
+    Esbmc,
+}
+
+/// The SMT solver selected with `--smt-solver`, which must be in the `PATH`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SmtSolver {
+    Z3,
+    Cvc5,
+    Bitwuzla,
+}
+
+/// The panic strategy selected with `--panic-strategy`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PanicStrategy {
+    /// A panic fails verification.
+    Abort,
+    /// A panic unwinds the stack, and fails verification unless it is caught by `catch_unwind`.
+    Unwind,
+}
+
+/// The harnesses whose artifacts are kept by `--keep-artifacts`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum KeepArtifacts {
+    /// Keep no artifacts.
+    None,
+    /// Keep the artifacts of the harnesses that failed.
+    Failed,
+    /// Keep the artifacts of every harness.
+    All,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Regular,
     Terse,
     Old,
+    /// Terse output, plus a JUnit XML report where each harness is a test case. Unstable, it
+    /// requires `-Z unstable-options`.
+    Junit,
+    /// Terse output, plus GitHub Actions annotations for the failed properties and the
+    /// unreachable cover properties.
+    Github,
 }
 
 #[derive(Debug, clap::Args)]
@@ -400,6 +1047,24 @@ pub fn undefined_function_on(&self) -> bool {
     pub fn unwinding_on(&self) -> bool {
         !self.no_default_checks && !self.no_unwinding_checks || self.unwinding_checks
     }
+
+    /// The checks enabled for a harness. The classes turned on or off by the harness with
+    /// `#[kani::checks]` take precedence over the command line options.
+    pub fn for_harness(&self, harness: &HarnessChecks) -> EnabledChecks {
+        EnabledChecks {
+            memory_safety: harness.memory_safety.unwrap_or_else(|| self.memory_safety_on()),
+            overflow: harness.overflow.unwrap_or_else(|| self.overflow_on()),
+            unwinding: harness.unwinding.unwrap_or_else(|| self.unwinding_on()),
+        }
+    }
+}
+
+/// The classes of automatic checks enabled for a harness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnabledChecks {
+    pub memory_safety: bool,
+    pub overflow: bool,
+    pub unwinding: bool,
 }
 
 /// Utility function to error out on arguments that are invalid Cargo specific.
@@ -424,6 +1089,7 @@ fn validate(&self) -> Result<(), Error> {
 
         match &self.command {
             Some(StandaloneSubcommand::VerifyStd(args)) => args.validate()?,
+            Some(StandaloneSubcommand::Bazel(args)) => args.validate()?,
             // TODO: Invoke PlaybackArgs::validate()
             None | Some(StandaloneSubcommand::Playback(..)) => {}
         };
@@ -440,6 +1106,8 @@ fn validate(&self) -> Result<(), Error> {
         check_no_cargo_opt(!self.verify_opts.cargo.exclude.is_empty(), "--exclude")?;
         check_no_cargo_opt(self.verify_opts.cargo.workspace, "--workspace")?;
         check_no_cargo_opt(self.verify_opts.cargo.manifest_path.is_some(), "--manifest-path")?;
+        check_no_cargo_opt(self.verify_opts.config.is_some(), "--config")?;
+        check_no_cargo_opt(self.verify_opts.concolic, "--concolic")?;
         if let Some(input) = &self.input {
             if !input.is_file() {
                 return Err(Error::raw(
@@ -470,6 +1138,14 @@ fn validate(&self) -> Result<(), Error> {
             // Assess doesn't implement validation yet.
             CargoKaniSubcommand::Assess(_) => Ok(()),
             CargoKaniSubcommand::Playback(playback) => playback.validate(),
+            CargoKaniSubcommand::Coverage(coverage) => coverage.validate(),
+            CargoKaniSubcommand::List(list) => list.validate(),
+            CargoKaniSubcommand::Completions(completions) => completions.validate(),
+            CargoKaniSubcommand::Bundle(bundle) => bundle.validate(),
+            CargoKaniSubcommand::VerifyBundle(bundle) => bundle.validate(),
+            CargoKaniSubcommand::Clean(clean) => clean.validate(),
+            CargoKaniSubcommand::WhyReachable(why_reachable) => why_reachable.validate(),
+            CargoKaniSubcommand::Mutate(mutate) => mutate.validate(),
         }
     }
 }
@@ -487,6 +1163,115 @@ fn validate(&self) -> Result<(), Error> {
                 "Assess is unstable and requires 'cargo kani --enable-unstable assess'",
             ));
         }
+        if matches!(self.command, Some(CargoKaniSubcommand::Coverage(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::SourceCoverage)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `coverage` subcommand is unstable and requires `-Z source-coverage` to be used.",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::List(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `list` subcommand is unstable and requires `-Z unstable-options` to be used.",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::Bundle(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `bundle` subcommand is unstable and requires `-Z unstable-options` to be used.",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::VerifyBundle(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `verify-bundle` subcommand is unstable and requires `-Z unstable-options` to be used.",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::Clean(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `clean` subcommand is unstable and requires `-Z unstable-options` to be used.",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::WhyReachable(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `why-reachable` subcommand is unstable and requires `-Z unstable-options` to be used.",
+            ));
+        }
+        if matches!(self.command, Some(CargoKaniSubcommand::Mutate(_)))
+            && !self
+                .verify_opts
+                .common_args
+                .unstable_features
+                .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `mutate` subcommand is unstable and requires `-Z unstable-options` to be used.",
+            ));
+        }
+        if self.watch {
+            self.verify_opts.check_unstable_option(true, "--watch")?;
+            if self.command.is_some() || self.verify_opts.assess {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "The `--watch` argument cannot be used with a subcommand or `--assess`.",
+                ));
+            }
+            if self.verify_opts.concrete_playback == Some(ConcretePlaybackMode::InPlace) {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "The `--watch` argument cannot be used with `--concrete-playback=inplace`, \
+                    since the generated tests would trigger a new verification.",
+                ));
+            }
+        }
+        if self.doc {
+            self.verify_opts.check_unstable_option(true, "--doc")?;
+            if self.command.is_some() || self.verify_opts.assess || self.watch {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "The `--doc` argument cannot be used with a subcommand, `--assess` or `--watch`.",
+                ));
+            }
+        }
         Ok(())
     }
 }
@@ -566,12 +1351,56 @@ fn validate(&self) -> Result<(), Error> {
                 "Conflicting options: --concrete-playback isn't compatible with --jobs.",
             ));
         }
-        if self.jobs.is_some() && self.output_format != OutputFormat::Terse {
+        if self.jobs.is_some()
+            && !matches!(
+                self.output_format,
+                OutputFormat::Terse | OutputFormat::Junit | OutputFormat::Github
+            )
+        {
             // More verbose output formats make it hard to interpret output right now when run in parallel.
             // This can be removed when we change up how results are printed.
             return Err(Error::raw(
                 ErrorKind::ArgumentConflict,
-                "Conflicting options: --jobs requires `--output-format=terse`",
+                "Conflicting options: --jobs requires `--output-format=terse`, `--output-format=junit` or `--output-format=github`",
+            ));
+        }
+        if self.backend == Backend::Why3 && !self.only_codegen {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "`--backend why3` only generates WhyML programs and requires `--only-codegen`. \
+                Use `why3 prove` to verify them.",
+            ));
+        }
+        if !self.esbmc_args.is_empty() && self.engine != Engine::Esbmc {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "`--esbmc-args` requires `--engine esbmc`.",
+            ));
+        }
+        if self.engine == Engine::Esbmc
+            && (self.visualize
+                || self.concrete_playback.is_some()
+                || self.infer_unwind
+                || self.searches_counterexamples()
+                || self.backend != Backend::Cprover)
+        {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "`--engine esbmc` cannot be used with `--visualize`, `--concrete-playback`, \
+                `--infer-unwind`, `--counterexamples` or `--backend`.",
+            ));
+        }
+        if self.smt_solver.is_some() && self.backend != Backend::Smt2 {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "`--smt-solver` requires `--backend smt2`.",
+            ));
+        }
+        if self.backend == Backend::Smt2 && (self.visualize || self.concrete_playback.is_some()) {
+            return Err(Error::raw(
+                ErrorKind::ArgumentConflict,
+                "`--backend smt2` doesn't produce traces, so it cannot be used with \
+                `--visualize` or `--concrete-playback`.",
             ));
         }
         if let Some(out_dir) = &self.target_dir {
@@ -585,6 +1414,14 @@ fn validate(&self) -> Result<(), Error> {
                 ));
             }
         }
+        if let Some(spec) = self.target_triple.as_ref().filter(|target| target.ends_with(".json")) {
+            if !Path::new(spec).is_file() {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!("Invalid argument: target spec `{spec}` is not a file"),
+                ));
+            }
+        }
 
         if self.concrete_playback.is_some()
             && !self.common_args.unstable_features.contains(UnstableFeature::ConcretePlayback)
@@ -624,6 +1461,83 @@ fn validate(&self) -> Result<(), Error> {
             ));
         }
 
+        if self.export_contracts && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--export-contracts` argument is unstable and requires `-Z \
+            function-contracts` to be used.",
+            ));
+        }
+
+        if self.assume_contracts && !self.is_function_contracts_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--assume-contracts` argument is unstable and requires `-Z \
+            function-contracts` to be used.",
+            ));
+        }
+
+        for filter in &self.harnesses {
+            if let Err(err) = HarnessFilter::parse(filter, self.exact) {
+                return Err(Error::raw(ErrorKind::InvalidValue, format!("{err:#}")));
+            }
+        }
+
+        self.check_unstable_option(self.sarif.is_some(), "--sarif")?;
+        self.check_unstable_option(self.html_report.is_some(), "--html-report")?;
+        self.check_unstable_option(
+            self.output_format == OutputFormat::Junit,
+            "--output-format=junit",
+        )?;
+        self.check_unstable_option(self.baseline.is_some(), "--baseline")?;
+        self.check_unstable_option(self.infer_unwind, "--infer-unwind")?;
+        self.check_unstable_option(self.counterexamples.is_some(), "--counterexamples")?;
+        self.check_unstable_option(self.concolic, "--concolic")?;
+        if self.concolic && self.concrete_playback != Some(ConcretePlaybackMode::InPlace) {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                "The `--concolic` argument requires `--concrete-playback=inplace`, since the \
+                counterexamples are executed with the generated tests.",
+            ));
+        }
+        self.check_unstable_option(self.save_baseline.is_some(), "--save-baseline")?;
+        self.check_unstable_option(!self.remote_workers.is_empty(), "--remote-worker")?;
+        self.check_unstable_option(self.fail_fast, "--fail-fast")?;
+        self.check_unstable_option(self.resume, "--resume")?;
+        self.check_unstable_option(self.cache_goto, "--cache-goto")?;
+        self.check_unstable_option(self.shared_model, "--shared-model")?;
+        self.check_unstable_option(self.reachability_report, "--reachability-report")?;
+        self.check_unstable_option(self.unsafe_report, "--unsafe-report")?;
+        self.check_unstable_option(self.check_constant_time, "--check-constant-time")?;
+        self.check_unstable_option(self.target_triple.is_some(), "--target")?;
+        self.check_unstable_option(self.no_std, "--no-std")?;
+        self.check_unstable_option(self.dry_run, "--dry-run")?;
+        self.check_unstable_option(self.progress, "--progress")?;
+        self.check_unstable_option(self.partition.is_some(), "--partition")?;
+        self.check_unstable_option(
+            self.message_format != MessageFormat::Human,
+            "--message-format",
+        )?;
+        self.check_unstable_option(self.keep_artifacts.is_some(), "--keep-artifacts")?;
+        self.check_unstable_option(self.inline_asm != InlineAsm::Unsupported, "--inline-asm")?;
+        self.check_unstable_option(
+            self.unknown_intrinsics != UnknownIntrinsics::Unreachable,
+            "--unknown-intrinsics",
+        )?;
+        self.check_unstable_option(self.backend != Backend::Cprover, "--backend")?;
+        self.check_unstable_option(self.engine != Engine::Cbmc, "--engine")?;
+        self.check_unstable_option(
+            self.panic_strategy != PanicStrategy::Abort,
+            "--panic-strategy",
+        )?;
+        self.check_unstable_option(self.float_math != FloatMath::Library, "--float-math")?;
+        self.check_unstable_option(self.mir_opt_level > 0, "--mir-opt-level")?;
+        self.check_unstable_option(self.endianness != Endianness::Native, "--endianness")?;
+        self.check_unstable_option(self.fail_on != DEFAULT_FAIL_ON, "--fail-on")?;
+        self.check_unstable_option(self.stats, "--stats")?;
+        self.check_unstable_option(self.metrics.is_some(), "--metrics")?;
+        self.check_unstable_option(self.profile_phases, "--profile-phases")?;
+
         Ok(())
     }
 }
@@ -648,23 +1562,17 @@ fn parse_ref(
         value: &std::ffi::OsStr,
     ) -> Result<Self::Value, clap::error::Error> {
         let value = value.to_str().unwrap();
-        // `value` is one of the possible `CbmcSolver` values or `bin=<binary>`
-        let segments: Vec<&str> = value.split('=').collect();
 
         let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
         err.insert(ContextKind::InvalidArg, ContextValue::String(arg.unwrap().to_string()));
         err.insert(ContextKind::InvalidValue, ContextValue::String(value.to_string()));
 
-        if segments.len() == 2 {
-            if segments[0] != "bin" {
-                return Err(err);
-            }
-            return Ok(CbmcSolver::Binary(segments[1].into()));
-        } else if segments.len() == 1 {
-            let solver = CbmcSolver::from_str(value);
-            return solver.map_err(|_| err);
+        // `value` is either `portfolio:<solver>,<solver>,...` or a single solver.
+        if let Some(solvers) = value.strip_prefix("portfolio:") {
+            let solvers: Option<Vec<_>> = solvers.split(',').map(parse_single_solver).collect();
+            return solvers.map(CbmcSolver::Portfolio).ok_or(err);
         }
-        Err(err)
+        parse_single_solver(value).ok_or(err)
     }
 
     /// Used for the help message
@@ -673,6 +1581,15 @@ fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>
     }
 }
 
+/// Parse one of the possible `CbmcSolver` values or `bin=<binary>`.
+pub(crate) fn parse_single_solver(value: &str) -> Option<CbmcSolver> {
+    match value.split_once('=') {
+        Some(("bin", binary)) if !binary.contains('=') => Some(CbmcSolver::Binary(binary.into())),
+        Some(_) => None,
+        None => CbmcSolver::from_str(value).ok(),
+    }
+}
+
 impl<I, T> From<I> for CbmcSolverValueParser
 where
     I: IntoIterator<Item = T>,
@@ -721,6 +1638,13 @@ fn check_multiple_harnesses() {
         assert_eq!(args.verify_opts.harnesses, vec!["a".to_owned(), "b".to_owned()]);
     }
 
+    #[test]
+    fn check_invalid_harness_pattern() {
+        let args =
+            StandaloneArgs::try_parse_from("kani input.rs --harness /(/".split(" ")).unwrap();
+        assert_eq!(args.verify_opts.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+
     #[test]
     fn check_multiple_harnesses_without_flag_fail() {
         let result = StandaloneArgs::try_parse_from(
@@ -772,12 +1696,16 @@ macro_rules! check_opt {
     }
 
     #[test]
-    fn check_dry_run_fails() {
-        // We don't support --dry-run anymore but we print a friendly reminder for now.
+    fn check_dry_run_unstable() {
+        // `--dry-run` used to be an obsolete option, it now prints the verification plan.
         let args = vec!["kani", "file.rs", "--dry-run"];
         let err =
             StandaloneArgs::try_parse_from(&args).unwrap().verify_opts.validate().unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        let args = vec!["kani", "file.rs", "--dry-run", "-Z", "unstable-options"];
+        let res = StandaloneArgs::try_parse_from(&args).unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(res.verify_opts.dry_run);
     }
 
     /// Kani should fail if the argument given is not a file.
@@ -811,6 +1739,11 @@ fn check_restrict_vtable_unstable() {
         check_unstable_flag!("--restrict-vtable", restrict_vtable);
     }
 
+    #[test]
+    fn check_restrict_fn_ptrs_unstable() {
+        check_unstable_flag!("--restrict-fn-ptrs", restrict_fn_ptrs);
+    }
+
     #[test]
     fn check_restrict_cbmc_args() {
         check_opt!("--cbmc-args --json-ui", true, cbmc_args, vec!["--json-ui"]);
@@ -819,6 +1752,8 @@ fn check_restrict_cbmc_args() {
     #[test]
     fn check_disable_slicing_unstable() {
         check_unstable_flag!("--no-slice-formula", no_slice_formula);
+        check_unstable_flag!("--constant-propagation", constant_propagation);
+        check_unstable_flag!("--full-slice", full_slice);
     }
 
     #[test]
@@ -854,6 +1789,19 @@ fn check_concrete_playback_conflicts() {
         );
     }
 
+    #[test]
+    fn check_concrete_playback_format() {
+        let res =
+            parse_unstable_disabled("--concrete-playback=print -Z concrete-playback").unwrap();
+        assert_eq!(res.verify_opts.concrete_playback_format, ConcretePlaybackFormat::UnitTest);
+        let res = parse_unstable_disabled(
+            "--concrete-playback=print --concrete-playback-format=proptest -Z concrete-playback",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.concrete_playback_format, ConcretePlaybackFormat::Proptest);
+        assert!(parse_unstable_disabled("--concrete-playback-format=proptest").is_err());
+    }
+
     #[test]
     fn check_enable_stubbing() {
         let res = parse_unstable_disabled("--harness foo").unwrap();
@@ -871,6 +1819,612 @@ fn check_enable_stubbing() {
         assert!(matches!(res.verify_opts.validate(), Ok(())));
     }
 
+    #[test]
+    fn check_jobs_output_format() {
+        let res = parse_unstable_enabled("-j --output-format junit -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        let res = parse_unstable_enabled("-j --output-format junit").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_enabled("-j --output-format github").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        expect_validation_error(
+            "kani --enable-unstable -j --output-format regular test.rs",
+            ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn check_watch_validation() {
+        fn parse(args: &str) -> CargoKaniArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap()
+        }
+        let res = parse("cargo-kani --watch");
+        assert!(res.watch);
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        assert!(parse("cargo-kani --watch -Z unstable-options").validate().is_ok());
+        assert_eq!(
+            parse("cargo-kani --watch -Z unstable-options -Z concrete-playback --concrete-playback inplace")
+                .validate()
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ArgumentConflict
+        );
+    }
+
+    #[test]
+    fn check_bazel_subcommand_validation() {
+        fn parse(args: &str) -> Result<StandaloneArgs, Error> {
+            StandaloneArgs::try_parse_from(args.split_whitespace())
+        }
+        let res = parse("kani bazel --print-compiler-args").unwrap();
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let res = parse("kani bazel --print-compiler-args -Z unstable-options").unwrap();
+        assert!(res.validate().is_ok());
+        let Some(StandaloneSubcommand::Bazel(args)) = res.command else { unreachable!() };
+        assert!(args.print_compiler_args);
+        assert!(parse("kani bazel -Z unstable-options").is_err());
+        let res = parse("kani prebuilt --crate-info info.json -Z unstable-options").unwrap();
+        assert!(matches!(res.command, Some(StandaloneSubcommand::Bazel(_))));
+        assert!(
+            parse("kani bazel --print-compiler-args --crate-info info.json -Z unstable-options")
+                .is_err()
+        );
+        let res = parse("kani bazel --crate-info missing.json -Z unstable-options").unwrap();
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_mutate_subcommand_validation() {
+        fn parse(args: &str) -> CargoKaniArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap()
+        }
+        let res = parse("cargo-kani mutate --list");
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let res = parse("cargo-kani -Z unstable-options mutate --file src/lib.rs --file src/a.rs");
+        assert!(res.validate().is_ok());
+        let Some(CargoKaniSubcommand::Mutate(args)) = res.command else { unreachable!() };
+        assert!(!args.list);
+        assert_eq!(args.file, [PathBuf::from("src/lib.rs"), PathBuf::from("src/a.rs")]);
+    }
+
+    #[test]
+    fn check_doc_validation() {
+        fn parse(args: &str) -> CargoKaniArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap()
+        }
+        let res = parse("cargo-kani --doc");
+        assert!(res.doc);
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        assert!(parse("cargo-kani --doc -Z unstable-options").validate().is_ok());
+        assert_eq!(
+            parse("cargo-kani --doc --watch -Z unstable-options").validate().unwrap_err().kind(),
+            ErrorKind::ArgumentConflict
+        );
+    }
+
+    #[test]
+    fn check_coverage_subcommand_validation() {
+        fn parse(args: &str) -> CargoKaniArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap()
+        }
+        let res = parse("cargo-kani coverage --merge");
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let res = parse("cargo-kani -Z source-coverage coverage");
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let res = parse("cargo-kani -Z source-coverage coverage --merge --format lcov");
+        assert!(res.validate().is_ok());
+        let Some(CargoKaniSubcommand::Coverage(args)) = res.command else { unreachable!() };
+        assert_eq!(args.format, coverage_args::CoverageReportFormat::Lcov);
+        assert!(args.paths.is_empty());
+        let res = parse("cargo-kani -Z source-coverage coverage --merge does_not_exist");
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+        assert!(
+            CargoKaniArgs::try_parse_from(
+                "cargo-kani coverage --merge --fail-on-regression".split_whitespace()
+            )
+            .is_err()
+        );
+        let res = parse("cargo-kani coverage --merge --baseline cov.json --fail-on-regression");
+        let Some(CargoKaniSubcommand::Coverage(args)) = res.command else { unreachable!() };
+        assert_eq!(args.baseline, Some(PathBuf::from("cov.json")));
+        assert!(args.fail_on_regression);
+    }
+
+    #[test]
+    fn check_clean_subcommand_validation() {
+        fn parse(args: &str) -> CargoKaniArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap()
+        }
+        let res = parse("cargo-kani clean --older-than 7d");
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let res = parse("cargo-kani -Z unstable-options clean --older-than 7d --keep-builds 2");
+        assert!(res.validate().is_ok());
+        let Some(CargoKaniSubcommand::Clean(args)) = res.command else { unreachable!() };
+        assert_eq!(
+            args.older_than,
+            Some(clean_args::Age(std::time::Duration::from_secs(7 * 86400)))
+        );
+        assert_eq!(args.keep_builds, Some(2));
+        assert!(
+            CargoKaniArgs::try_parse_from("cargo-kani clean --older-than soon".split_whitespace())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn check_why_reachable_subcommand_validation() {
+        fn parse(args: &str) -> CargoKaniArgs {
+            CargoKaniArgs::try_parse_from(args.split_whitespace()).unwrap()
+        }
+        let res = parse("cargo-kani why-reachable parse --harness check_parse");
+        assert_eq!(res.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+        let res = parse("cargo-kani -Z unstable-options why-reachable parse --harness check_parse");
+        assert!(res.validate().is_ok());
+        let Some(CargoKaniSubcommand::WhyReachable(args)) = res.command else { unreachable!() };
+        assert_eq!(args.function, "parse");
+        assert_eq!(args.harness, "check_parse");
+        assert!(
+            CargoKaniArgs::try_parse_from(
+                "cargo-kani -Z unstable-options why-reachable parse".split_whitespace()
+            )
+            .is_err()
+        );
+        let res = parse_unstable_disabled("--reachability-report").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+
+    #[test]
+    fn check_unsafe_report_args() {
+        let res = parse_unstable_disabled("--unsafe-report").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--unsafe-report -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(res.verify_opts.unsafe_report);
+    }
+
+    #[test]
+    fn check_constant_time_args() {
+        let res = parse_unstable_disabled("--check-constant-time").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--check-constant-time -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(res.verify_opts.check_constant_time);
+    }
+
+    #[test]
+    fn check_baseline_args() {
+        let res = parse_unstable_disabled("--baseline base.json").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+
+        let res = parse_unstable_disabled("--baseline base.json -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert_eq!(res.verify_opts.baseline_slowdown_threshold, 50);
+
+        let res = parse_unstable_disabled(
+            "--baseline base.json --baseline-slowdown-threshold 10 -Z unstable-options",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.baseline_slowdown_threshold, 10);
+        assert!(parse_unstable_disabled("--baseline-slowdown-threshold 10").is_err());
+    }
+
+    #[test]
+    fn check_infer_unwind_args() {
+        let res = parse_unstable_disabled("--infer-unwind").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--infer-unwind -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert_eq!(res.verify_opts.infer_unwind_limit, 128);
+        assert!(parse_unstable_disabled("--infer-unwind-limit 10").is_err());
+        assert!(parse_unstable_disabled("--infer-unwind --unwind 2 --harness foo").is_err());
+        assert!(parse_unstable_disabled("--incremental-unwind").is_err());
+        let res =
+            parse_unstable_disabled("--infer-unwind --incremental-unwind -Z unstable-options")
+                .unwrap();
+        assert!(res.verify_opts.incremental_unwind);
+    }
+
+    #[test]
+    fn check_target_spec_args() {
+        let res = parse_unstable_disabled("--target missing.json -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.validate().unwrap_err().kind(), ErrorKind::InvalidValue);
+        let res =
+            parse_unstable_disabled("--target thumbv7em-none-eabihf -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+    }
+
+    #[test]
+    fn check_endianness_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.endianness, Endianness::Native);
+        let res = parse_unstable_disabled("--endianness big").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--endianness both -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.endianness, Endianness::Both);
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(parse_unstable_disabled("--endianness middle").is_err());
+    }
+
+    #[test]
+    fn check_fail_on_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.fail_on, DEFAULT_FAIL_ON);
+        assert!(!res.verify_opts.fail_fast);
+        let res = parse_unstable_disabled("--fail-on failure,cover-miss").unwrap();
+        assert_eq!(res.verify_opts.fail_on, [FailureCategory::Failure, FailureCategory::CoverMiss]);
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res =
+            parse_unstable_disabled("--fail-on failure --fail-fast -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(res.verify_opts.fail_fast);
+        assert!(parse_unstable_disabled("--fail-on panics").is_err());
+    }
+
+    #[test]
+    fn check_partition_args() {
+        let res = parse_unstable_disabled("--partition 2/3 -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.partition, Some(Partition { shard: 2, total: 3 }));
+        assert!(res.verify_opts.validate().is_ok());
+        let res = parse_unstable_disabled("--partition 1/2").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        assert!(parse_unstable_disabled("--partition 0/2 -Z unstable-options").is_err());
+        assert!(parse_unstable_disabled("--partition 3/2 -Z unstable-options").is_err());
+        assert!(parse_unstable_disabled("--partition 2 -Z unstable-options").is_err());
+        assert!(
+            parse_unstable_disabled("--partition-runtimes base.json -Z unstable-options").is_err()
+        );
+    }
+
+    #[test]
+    fn check_metrics_args() {
+        let res = parse_unstable_enabled("--metrics statsd://localhost:8125").unwrap();
+        assert_eq!(
+            res.verify_opts.metrics,
+            Some(MetricsEndpoint::Statsd("localhost:8125".to_string()))
+        );
+        let res = parse_unstable_enabled("--metrics http://localhost:4318/v1/metrics").unwrap();
+        assert_eq!(
+            res.verify_opts.metrics,
+            Some(MetricsEndpoint::Otlp("http://localhost:4318/v1/metrics".to_string()))
+        );
+        assert!(parse_unstable_enabled("--metrics localhost:8125").is_err());
+        let res = parse_unstable_disabled("--metrics statsd://localhost:8125").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+
+    #[test]
+    fn check_message_format_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.message_format, MessageFormat::Human);
+        let res = parse_unstable_disabled("--message-format json").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--message-format json -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert_eq!(res.verify_opts.message_format, MessageFormat::Json);
+    }
+
+    #[test]
+    fn check_float_math_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.float_math, FloatMath::Library);
+        let res = parse_unstable_disabled("--float-math model").unwrap();
+        assert_eq!(res.verify_opts.float_math, FloatMath::Model);
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--float-math model -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+    }
+
+    #[test]
+    fn check_mir_opt_level_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.mir_opt_level, 0);
+        let res = parse_unstable_disabled("--mir-opt-level 2").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--mir-opt-level 2 -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.mir_opt_level, 2);
+        assert!(res.verify_opts.validate().is_ok());
+        let res = parse_unstable_disabled("--mir-opt-level 3 -Z unstable-options");
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::ValueValidation);
+        let res = parse_unstable_disabled(
+            "--mir-opt-level 1 --coverage -Z source-coverage -Z unstable-options",
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_inline_asm_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.inline_asm, InlineAsm::Unsupported);
+        let res = parse_unstable_disabled("--inline-asm havoc").unwrap();
+        assert_eq!(res.verify_opts.inline_asm, InlineAsm::Havoc);
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--inline-asm havoc -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+    }
+
+    #[test]
+    fn check_unknown_intrinsics_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.unknown_intrinsics, UnknownIntrinsics::Unreachable);
+        let res = parse_unstable_disabled("--unknown-intrinsics havoc").unwrap();
+        assert_eq!(res.verify_opts.unknown_intrinsics, UnknownIntrinsics::Havoc);
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res =
+            parse_unstable_disabled("--unknown-intrinsics error -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+    }
+
+    #[test]
+    fn check_backend_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.backend, Backend::Cprover);
+        let res = parse_unstable_disabled("--backend why3 --only-codegen").unwrap();
+        assert_eq!(res.verify_opts.backend, Backend::Why3);
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--backend why3 -Z unstable-options").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res =
+            parse_unstable_disabled("--backend why3 --only-codegen -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+    }
+
+    #[test]
+    fn check_engine_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.engine, Engine::Cbmc);
+        let res = parse_unstable_disabled("--engine esbmc").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled(
+            "-Z unstable-options --engine esbmc --esbmc-args --k-induction",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.esbmc_args, vec!["--k-induction"]);
+        assert!(res.verify_opts.validate().is_ok());
+        let res =
+            parse_unstable_disabled("-Z unstable-options --esbmc-args --k-induction").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_enabled("--engine esbmc --visualize -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.validate().unwrap_err().kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_smt_solver_args() {
+        let res = parse_unstable_disabled("--backend smt2 -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.smt_solver, None);
+        assert!(!res.verify_opts.assertion_reach_checks());
+        assert!(res.verify_opts.validate().is_ok());
+        let res =
+            parse_unstable_disabled("--backend smt2 --smt-solver z3 -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.smt_solver, Some(SmtSolver::Z3));
+        assert!(res.verify_opts.validate().is_ok());
+        let res = parse_unstable_disabled("--smt-solver cvc5 -Z unstable-options").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_enabled("--backend smt2 --visualize -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.validate().unwrap_err().kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_panic_strategy_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.panic_strategy, PanicStrategy::Abort);
+        let res = parse_unstable_disabled("--panic-strategy unwind").unwrap();
+        assert_eq!(res.verify_opts.panic_strategy, PanicStrategy::Unwind);
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--panic-strategy unwind -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+    }
+
+    #[test]
+    fn check_keep_artifacts_args() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.keep_artifacts, None);
+        let res = parse_unstable_disabled("--keep-artifacts failed").unwrap();
+        assert_eq!(res.verify_opts.keep_artifacts, Some(KeepArtifacts::Failed));
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--keep-artifacts all -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(parse_unstable_disabled("--keep-artifacts some").is_err());
+    }
+
+    #[test]
+    fn check_remote_worker_args() {
+        let res = parse_unstable_disabled("--remote-worker a").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled(
+            "--remote-worker a --remote-worker b --remote-worker a -Z unstable-options",
+        )
+        .unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert_eq!(res.verify_opts.remote_workers, ["a", "b", "a"]);
+        assert_eq!(res.verify_opts.remote_dir, "/tmp/kani-remote");
+        assert_eq!(res.verify_opts.jobs(), Some(3));
+        let res = parse_unstable_enabled("--remote-worker a -j 8").unwrap();
+        assert_eq!(res.verify_opts.jobs(), Some(8));
+        let err = parse_unstable_disabled("--remote-dir /tmp/foo").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+        let err = parse_unstable_enabled("--remote-worker a --memory-limit 1024").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_solver_args() {
+        let res = parse_unstable_disabled("--solver kissat").unwrap();
+        assert_eq!(res.verify_opts.solver, Some(CbmcSolver::Kissat));
+        let res = parse_unstable_disabled("--solver bin=my_solver").unwrap();
+        assert_eq!(res.verify_opts.solver, Some(CbmcSolver::Binary("my_solver".into())));
+        let res = parse_unstable_disabled("--solver portfolio:minisat,cadical,bin=foo").unwrap();
+        assert_eq!(
+            res.verify_opts.solver,
+            Some(CbmcSolver::Portfolio(vec![
+                CbmcSolver::Minisat,
+                CbmcSolver::Cadical,
+                CbmcSolver::Binary("foo".into())
+            ]))
+        );
+        assert!(parse_unstable_disabled("--solver portfolio:minisat,z3").is_err());
+        assert!(parse_unstable_disabled("--solver portfolio:").is_err());
+        assert!(parse_unstable_disabled("--solver foo=bar").is_err());
+    }
+
+    #[test]
+    fn check_counterexamples_args() {
+        let res = parse_unstable_disabled("--counterexamples 3").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res = parse_unstable_disabled("--counterexamples 3 -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(res.verify_opts.searches_counterexamples());
+        assert!(res.verify_opts.requires_trace());
+        let res = parse_unstable_disabled("--counterexamples 1 -Z unstable-options").unwrap();
+        assert!(!res.verify_opts.searches_counterexamples());
+        assert!(parse_unstable_disabled("--counterexamples 0").is_err());
+    }
+
+    #[test]
+    fn check_concolic_args() {
+        let res = parse_unstable_disabled(
+            "--concolic --concrete-playback=inplace -Z concrete-playback -Z unstable-options",
+        )
+        .unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        let res = parse_unstable_disabled("--concolic -Z unstable-options").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        let res =
+            parse_unstable_disabled("--concolic --concrete-playback=inplace -Z concrete-playback")
+                .unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+
+    #[test]
+    fn check_coverage_format() {
+        let res = parse_unstable_disabled("--coverage -Z source-coverage").unwrap();
+        assert_eq!(res.verify_opts.coverage_format, CoverageFormat::Raw);
+        let res = parse_unstable_disabled("--coverage --coverage-format lcov -Z source-coverage")
+            .unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert_eq!(res.verify_opts.coverage_format, CoverageFormat::Lcov);
+        let res =
+            parse_unstable_disabled("--coverage --coverage-format cobertura -Z source-coverage")
+                .unwrap();
+        assert_eq!(res.verify_opts.coverage_format, CoverageFormat::Cobertura);
+        assert!(parse_unstable_disabled("--coverage-format lcov").is_err());
+        assert!(parse_unstable_disabled("--branch-coverage").is_err());
+        let res =
+            parse_unstable_disabled("--coverage --branch-coverage -Z source-coverage").unwrap();
+        assert!(res.verify_opts.branch_coverage);
+    }
+
+    #[test]
+    fn check_export_contracts_args() {
+        assert!(parse_unstable_disabled("--export-contracts -Z function-contracts").is_err());
+        assert!(parse_unstable_disabled("--assume-contracts -Z function-contracts").is_err());
+        for flag in ["--export-contracts", "--assume-contracts"] {
+            let res = parse_unstable_disabled(&format!("{flag} --contracts-key key")).unwrap();
+            assert_eq!(
+                res.verify_opts.validate().unwrap_err().kind(),
+                ErrorKind::MissingRequiredArgument
+            );
+        }
+        let res = parse_unstable_disabled(
+            "--export-contracts --assume-contracts --contracts-key key -Z function-contracts",
+        )
+        .unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert!(res.verify_opts.export_contracts);
+        assert!(res.verify_opts.assume_contracts);
+        assert_eq!(res.verify_opts.contracts_key, Some(PathBuf::from("key")));
+    }
+
+    #[test]
+    fn check_sarif_unstable() {
+        let res = parse_unstable_disabled("--sarif report.sarif").unwrap();
+        assert_eq!(
+            res.verify_opts.validate().unwrap_err().kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+
+        let res = parse_unstable_disabled("--sarif report.sarif -Z unstable-options").unwrap();
+        assert!(res.verify_opts.validate().is_ok());
+        assert_eq!(res.verify_opts.sarif, Some(PathBuf::from("report.sarif")));
+    }
+
     #[test]
     fn check_features_parsing() {
         fn parse(args: &[&str]) -> Vec<String> {
@@ -913,5 +2467,6 @@ fn check_invalid_args<'a, I>(args: I)
         check_invalid_args("kani input.rs --workspace".split_whitespace());
         check_invalid_args("kani input.rs --package foo".split_whitespace());
         check_invalid_args("kani input.rs --exclude bar --workspace".split_whitespace());
+        check_invalid_args("kani input.rs --config kani.toml".split_whitespace());
     }
 }
@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the mutate subcommand
+
+use crate::args::ValidateArgs;
+use clap::{Error, Parser};
+use std::path::PathBuf;
+
+/// Apply small mutations to the sources of a package, e.g., replacing `<` with `<=`, and report
+/// the mutants that every harness still accepts.
+#[derive(Debug, Parser)]
+pub struct CargoMutateArgs {
+    /// Only mutate the given source files.
+    #[arg(long, value_name = "PATH")]
+    pub file: Vec<PathBuf>,
+
+    /// Print the mutants instead of verifying them.
+    #[arg(long)]
+    pub list: bool,
+}
+
+impl ValidateArgs for CargoMutateArgs {
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
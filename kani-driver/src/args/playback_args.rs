@@ -15,6 +15,15 @@ pub struct CargoPlaybackArgs {
     #[command(flatten)]
     pub playback: PlaybackArgs,
 
+    /// Run all the concrete playback tests of the workspace, and report which ones still
+    /// reproduce their counterexample, which ones now pass (stale), and which ones no longer
+    /// compile.
+    #[arg(
+        long,
+        conflicts_with_all(["only_codegen", "test_args", "debug_harness", "minimize_harness"])
+    )]
+    pub batch: bool,
+
     /// Arguments to pass down to Cargo that are specific to tests.
     #[command(flatten)]
     pub cargo: CargoTestArgs,
@@ -46,6 +55,20 @@ pub struct PlaybackArgs {
     #[arg(long, default_value = "human")]
     pub message_format: MessageFormat,
 
+    /// Build the concrete playback tests of the given harness with debug information, and
+    /// generate `rust-gdb` and `rust-lldb` scripts to debug them instead of running them.
+    #[arg(long, value_name = "HARNESS", conflicts_with_all(["only_codegen", "test_args"]))]
+    pub debug_harness: Option<String>,
+
+    /// Minimize the counterexample reproduced by the concrete playback test of the given
+    /// harness, and print the smallest concrete values that still fail the same check.
+    #[arg(
+        long,
+        value_name = "HARNESS",
+        conflicts_with_all(["only_codegen", "test_args", "debug_harness"])
+    )]
+    pub minimize_harness: Option<String>,
+
     /// Arguments to be passed to the test binary.
     #[arg(num_args(0..), last = true)]
     pub test_args: Vec<String>,
@@ -61,6 +84,13 @@ pub enum MessageFormat {
     Json,
 }
 
+impl PlaybackArgs {
+    /// Whether the tests are built to be inspected by Kani instead of being run directly.
+    pub fn inspect_tests(&self) -> bool {
+        self.debug_harness.is_some() || self.minimize_harness.is_some()
+    }
+}
+
 impl ValidateArgs for CargoPlaybackArgs {
     fn validate(&self) -> Result<(), Error> {
         self.playback.validate()?;
@@ -128,6 +158,43 @@ fn check_parse_format_works() {
         assert_eq!(args.playback.message_format, MessageFormat::Json)
     }
 
+    #[test]
+    fn check_parse_debug_works() {
+        let input = "playback -Z concrete-playback --debug-harness check_foo".split_whitespace();
+        let args = CargoPlaybackArgs::try_parse_from(input).unwrap();
+        args.validate().unwrap();
+        assert_eq!(args.playback.debug_harness.as_deref(), Some("check_foo"));
+
+        let input =
+            "playback -Z concrete-playback --debug-harness check_foo -- TEST".split_whitespace();
+        assert!(CargoPlaybackArgs::try_parse_from(input).is_err());
+    }
+
+    #[test]
+    fn check_parse_minimize_works() {
+        let input = "playback -Z concrete-playback --minimize-harness check_foo".split_whitespace();
+        let args = CargoPlaybackArgs::try_parse_from(input).unwrap();
+        args.validate().unwrap();
+        assert_eq!(args.playback.minimize_harness.as_deref(), Some("check_foo"));
+        assert!(args.playback.inspect_tests());
+
+        let input = "playback -Z concrete-playback --minimize-harness check_foo --debug-harness \
+            check_foo"
+            .split_whitespace();
+        assert!(CargoPlaybackArgs::try_parse_from(input).is_err());
+    }
+
+    #[test]
+    fn check_parse_batch_works() {
+        let input = "playback -Z concrete-playback --batch --workspace".split_whitespace();
+        let args = CargoPlaybackArgs::try_parse_from(input).unwrap();
+        args.validate().unwrap();
+        assert!(args.batch);
+
+        let input = "playback -Z concrete-playback --batch -- TEST".split_whitespace();
+        assert!(CargoPlaybackArgs::try_parse_from(input).is_err());
+    }
+
     #[test]
     fn check_kani_parse_test_works() {
         let input = "playback -Z concrete-playback input.rs -- TEST_NAME".split_whitespace();
@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the why-reachable subcommand
+
+use crate::args::ValidateArgs;
+use clap::{Error, Parser};
+
+/// Print one path through which a harness reaches a function, i.e., why the function is included
+/// in the model of the harness.
+#[derive(Debug, Parser)]
+pub struct CargoWhyReachableArgs {
+    /// The function to look for. Either its full path, e.g. `my_crate::parser::parse`, or a
+    /// suffix of it made of whole path segments, e.g. `parser::parse`.
+    pub function: String,
+
+    /// The harness to start from. Either its full path or its name.
+    #[arg(long)]
+    pub harness: String,
+}
+
+impl ValidateArgs for CargoWhyReachableArgs {
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -1,26 +1,62 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::{bail, Result};
+use crate::args::parse_single_solver;
+use crate::session::KaniSession;
+use crate::util::warning;
+use anyhow::{bail, Context, Result};
 use clap::Parser;
+use kani_metadata::{HarnessChecks, HarnessSlicing, KaniMetadata};
+use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use toml::value::Table;
 use toml::Value;
 
+/// The name of the project configuration file, which is looked up next to the `Cargo.toml`.
+const CONFIG_FILE: &str = "kani.toml";
+
 /// Produce the list of arguments to pass to ourself (cargo-kani).
 ///
-/// The arguments passed via command line have precedence over the ones from the Cargo.toml.
+/// The arguments passed via command line have precedence over the ones from the `kani.toml`,
+/// which have precedence over the ones from the Cargo.toml.
 pub fn join_args(input_args: Vec<OsString>) -> Result<Vec<OsString>> {
-    let toml_path = cargo_locate_project(&input_args);
-    if toml_path.is_err() {
+    // Try parsing our command line arguments as they presently look, to see if a "manifest-path"
+    // or a "config" has been given.
+    let current_args = crate::args::CargoKaniArgs::parse_from(&input_args);
+    let toml_path = cargo_locate_project(current_args.verify_opts.cargo.manifest_path);
+    let Ok(toml_path) = toml_path else {
         // We're not inside a Cargo project. Don't error... yet.
         return Ok(input_args);
+    };
+    let file = std::fs::read_to_string(&toml_path)?;
+    let (mut kani_args, mut cbmc_args) = toml_to_args(&file)?;
+
+    let config_path = match current_args.verify_opts.config {
+        Some(path) => Some(path),
+        None => {
+            let path = toml_path.with_file_name(CONFIG_FILE);
+            // Let the session find the per-harness configuration as well.
+            path.exists().then(|| {
+                kani_args.extend(["--config".into(), path.clone().into()]);
+                path
+            })
+        }
+    };
+    if let Some(path) = config_path {
+        let file = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read `{}`", path.display()))?;
+        let (config_kani_args, config_cbmc_args) = config_to_args(&file)?;
+        kani_args.extend(config_kani_args);
+        if cbmc_args.is_empty() {
+            cbmc_args = config_cbmc_args;
+        } else {
+            // Skip the `--cbmc-args` flag, which must only be included once.
+            cbmc_args.extend(config_cbmc_args.into_iter().skip(1));
+        }
     }
-    let file = std::fs::read_to_string(toml_path?)?;
-    let (kani_args, cbmc_args) = toml_to_args(&file)?;
     merge_args(input_args, kani_args, cbmc_args)
 }
 
@@ -66,11 +102,8 @@ fn merge_args(
 }
 
 /// `locate-project` produces a response like: `/full/path/to/src/cargo-kani/Cargo.toml`
-fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
-    // Try parsing our command line arguments as they presently look, to see if a "manifest-path" has been given.
-    let current_args = crate::args::CargoKaniArgs::parse_from(input_args);
-
-    if let Some(path) = current_args.verify_opts.cargo.manifest_path {
+fn cargo_locate_project(manifest_path: Option<PathBuf>) -> Result<PathBuf> {
+    if let Some(path) = manifest_path {
         Ok(path)
     } else {
         let cmd =
@@ -97,29 +130,41 @@ fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
 /// - "kani"
 fn toml_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
     let config = tomldata.parse::<Value>()?;
+    let tables = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
+    tables_to_args(tables.iter().filter_map(|table| get_table(&config, table)))
+}
+
+/// Parse a `kani.toml` string and extract the cargo-kani arguments we should try injecting.
+/// The `flags` and `unstable` entries are the same as in the Cargo.toml, but at the top level.
+fn config_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
+    let config = tomldata.parse::<Value>()?;
+    tables_to_args(config.as_table().into_iter())
+}
+
+/// Extract the arguments from the `flags` and `unstable` entries of the given tables.
+fn tables_to_args<'a>(
+    tables: impl Iterator<Item = &'a Table>,
+) -> Result<(Vec<OsString>, Vec<OsString>)> {
     // To make testing easier, our function contract is to produce a stable ordering of flags for a given input.
     // Consequently, we use BTreeMap instead of HashMap here.
     let mut map: BTreeMap<String, Value> = BTreeMap::new();
-    let tables = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
     let mut args = Vec::new();
 
     for table in tables {
-        if let Some(table) = get_table(&config, table) {
-            if let Some(entry) = table.get("flags") {
-                if let Some(val) = entry.as_table() {
-                    map.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
-                }
+        if let Some(entry) = table.get("flags") {
+            if let Some(val) = entry.as_table() {
+                map.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
             }
+        }
 
-            if let Some(entry) = table.get("unstable") {
-                if let Some(val) = entry.as_table() {
-                    args.append(
-                        &mut val
-                            .iter()
-                            .filter_map(|(k, v)| unstable_entry(k, v).transpose())
-                            .collect::<Result<Vec<_>>>()?,
-                    );
-                }
+        if let Some(entry) = table.get("unstable") {
+            if let Some(val) = entry.as_table() {
+                args.append(
+                    &mut val
+                        .iter()
+                        .filter_map(|(k, v)| unstable_entry(k, v).transpose())
+                        .collect::<Result<Vec<_>>>()?,
+                );
             }
         }
     }
@@ -218,6 +263,121 @@ fn get_table<'a>(start: &'a Value, table: &str) -> Option<&'a Table> {
     current.as_table()
 }
 
+/// The settings of one harness in the `[harness."<name>"]` tables of a `kani.toml`, which take
+/// precedence over the attributes of the harness.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HarnessConfig {
+    pub unwind: Option<u32>,
+    pub solver: Option<String>,
+    pub timeout: Option<u64>,
+    pub memory_limit: Option<u64>,
+    /// Appended to the arguments of `#[kani::cbmc_args]`.
+    #[serde(default)]
+    pub cbmc_args: Vec<String>,
+    /// Appended to the tags of `#[kani::tag]`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub checks: HarnessChecks,
+    #[serde(default)]
+    pub slicing: HarnessSlicing,
+}
+
+/// The severity of a kind of failure, set in the `[severity]` table of a `kani.toml`. Only
+/// errors make Kani exit with an error.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// The versions of the verification tools pinned in the `[tools]` table of a `kani.toml`, with
+/// `cargo kani setup --cbmc-version <VERSION> --kissat-version <VERSION>` and the pins of their
+/// sources.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolVersions {
+    pub cbmc: Option<String>,
+    pub kissat: Option<String>,
+}
+
+/// The parts of a `kani.toml` that aren't command line arguments.
+#[derive(Debug, Default, Deserialize)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub harness: BTreeMap<String, HarnessConfig>,
+    /// The severity of the failures of each property class, e.g., `cover`, and of `timeout`,
+    /// `out_of_memory` and `coverage_regression`.
+    #[serde(default)]
+    pub severity: BTreeMap<String, Severity>,
+    #[serde(default)]
+    pub tools: ToolVersions,
+}
+
+/// Read the per-harness configuration and the severities of a `kani.toml`. The harnesses are
+/// indexed by their fully qualified name.
+pub fn read_project_config(path: &Path) -> Result<ProjectConfig> {
+    let file = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read `{}`", path.display()))?;
+    parse_project_config(&file).with_context(|| format!("Invalid `{}`", path.display()))
+}
+
+fn parse_project_config(tomldata: &str) -> Result<ProjectConfig> {
+    let config: ProjectConfig = toml::from_str(tomldata)?;
+    for (name, harness) in &config.harness {
+        if let Some(solver) = &harness.solver {
+            if parse_single_solver(solver).is_none() {
+                bail!("invalid solver `{solver}` for harness `{name}`");
+            }
+        }
+    }
+    Ok(config)
+}
+
+impl KaniSession {
+    /// Apply the per-harness configuration of the `--config` file to the attributes of the
+    /// harnesses found in `metadata`.
+    pub fn apply_harness_configs(&self, metadata: &mut [KaniMetadata]) {
+        if self.harness_configs.is_empty() {
+            return;
+        }
+        let mut unused: Vec<&String> = self.harness_configs.keys().collect();
+        for harness in metadata.iter_mut().flat_map(|krate| {
+            krate.proof_harnesses.iter_mut().chain(krate.test_harnesses.iter_mut())
+        }) {
+            if let Some(config) = self.harness_configs.get(&harness.pretty_name) {
+                unused.retain(|name| **name != harness.pretty_name);
+                let attributes = &mut harness.attributes;
+                attributes.unwind_value = config.unwind.or(attributes.unwind_value);
+                if let Some(solver) = config.solver.as_deref().and_then(parse_single_solver) {
+                    attributes.solver = Some(solver);
+                }
+                attributes.timeout = config.timeout.or(attributes.timeout);
+                attributes.memory_limit = config.memory_limit.or(attributes.memory_limit);
+                attributes.cbmc_args.extend(config.cbmc_args.iter().cloned());
+                attributes.tags.extend(config.tags.iter().cloned());
+                let checks = &mut attributes.checks;
+                checks.memory_safety = config.checks.memory_safety.or(checks.memory_safety);
+                checks.overflow = config.checks.overflow.or(checks.overflow);
+                checks.unwinding = config.checks.unwinding.or(checks.unwinding);
+                let slicing = &mut attributes.slicing;
+                slicing.formula = config.slicing.formula.or(slicing.formula);
+                slicing.constant_propagation =
+                    config.slicing.constant_propagation.or(slicing.constant_propagation);
+                slicing.full = config.slicing.full.or(slicing.full);
+            }
+        }
+        if !self.args.common_args.quiet {
+            for name in unused {
+                warning(&format!("no harness named `{name}`, but it has a configuration"));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -235,6 +395,68 @@ fn check_toml_parsing() {
         assert_eq!(b.1, vec!["--cbmc-args", "--fake"]);
     }
 
+    #[test]
+    fn check_config_parsing() {
+        let data = "[flags]
+                         default-unwind = \"4\"
+                         skip-tag = [\"slow\"]
+                         cbmc-args = [\"--fake\"]
+                         [unstable]
+                         enabled-feature = true
+                         [harness.\"check_foo\"]
+                         unwind = 10";
+        let (kani_args, cbmc_args) = config_to_args(data).unwrap();
+        assert_eq!(
+            kani_args,
+            vec!["-Zenabled-feature", "--default-unwind", "4", "--skip-tag", "slow"]
+        );
+        assert_eq!(cbmc_args, vec!["--cbmc-args", "--fake"]);
+    }
+
+    #[test]
+    fn check_harness_config_parsing() {
+        let data = "[harness.\"parser::check_parse\"]
+                         unwind = 10
+                         solver = \"kissat\"
+                         tags = [\"slow\"]
+                         checks = { overflow = false }
+                         slicing = { full = true }";
+        let configs = parse_project_config(data).unwrap().harness;
+        let expected = HarnessConfig {
+            unwind: Some(10),
+            solver: Some("kissat".into()),
+            tags: vec!["slow".into()],
+            checks: HarnessChecks { overflow: Some(false), ..Default::default() },
+            slicing: HarnessSlicing { full: Some(true), ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs["parser::check_parse"], expected);
+        let config = parse_project_config("[flags]\ndefault-unwind = \"2\"").unwrap();
+        assert!(config.harness.is_empty());
+        assert!(parse_project_config("[harness.check]\nsolver = \"z4\"").is_err());
+        assert!(parse_project_config("[harness.check]\nunknown = 1").is_err());
+    }
+
+    #[test]
+    fn check_severity_parsing() {
+        let data = "[severity]
+                         cover = \"warning\"
+                         unsupported_construct = \"info\"";
+        let severity = parse_project_config(data).unwrap().severity;
+        assert_eq!(severity["cover"], Severity::Warning);
+        assert_eq!(severity["unsupported_construct"], Severity::Info);
+        assert!(Severity::Info < Severity::Warning && Severity::Warning < Severity::Error);
+        assert!(parse_project_config("[severity]\ncover = \"fatal\"").is_err());
+    }
+
+    #[test]
+    fn check_tool_versions_parsing() {
+        let tools = parse_project_config("[tools]\ncbmc = \"6.1.1\"").unwrap().tools;
+        assert_eq!(tools, ToolVersions { cbmc: Some("6.1.1".into()), kissat: None });
+        assert!(parse_project_config("[tools]\nz3 = \"4.12\"").is_err());
+    }
+
     #[test]
     fn check_merge_args_with_only_command_line_args() {
         let cmd_args: Vec<OsString> =
@@ -0,0 +1,160 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A content-addressed cache of the goto binaries produced by the driver, enabled with
+//! `--cache-goto`.
+//!
+//! Linking a goto binary and instrumenting it with `goto-instrument` are deterministic, so their
+//! outputs can be reused as long as their inputs don't change. Each cache entry is keyed by a hash
+//! of the contents of the input files and of the options that affect the output, as well as the
+//! Kani version, since it determines the version of the CBMC tools. The cache directory may be
+//! shared by several machines, e.g., on a network file system.
+
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+use anyhow::{Context, Result};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// The name of the default cache directory, in the output directory of the project.
+const DEFAULT_CACHE_DIR: &str = "kani-cache";
+
+/// The steps whose outputs are cached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum CachedStep {
+    /// Linking the symbol table of a harness into a goto binary.
+    Link,
+    /// The `goto-instrument` passes run on the goto binary of a harness.
+    Instrument,
+}
+
+/// The inputs of a cached step, which are hashed to compute the key of its output.
+#[derive(Debug, Default)]
+pub struct CacheKey {
+    hasher: [DefaultHasher; 2],
+}
+
+impl CacheKey {
+    pub fn new(step: CachedStep) -> CacheKey {
+        let mut key = CacheKey::default();
+        // Use two differently seeded hashers for a 128-bit key.
+        key.hasher[1].write_u8(1);
+        key.add(KANI_VERSION).add(step.as_ref());
+        key
+    }
+
+    /// Add a value that affects the output of the step, e.g., an option.
+    pub fn add(&mut self, value: impl Hash) -> &mut CacheKey {
+        self.hasher.iter_mut().for_each(|hasher| value.hash(hasher));
+        self
+    }
+
+    /// Add the contents of an input file of the step.
+    pub fn add_file(&mut self, path: &Path) -> Result<&mut CacheKey> {
+        let contents =
+            std::fs::read(path).with_context(|| format!("Failed to read `{}`", path.display()))?;
+        Ok(self.add(contents))
+    }
+
+    fn finish(&self) -> String {
+        format!("{:016x}{:016x}", self.hasher[0].finish(), self.hasher[1].finish())
+    }
+}
+
+/// A cache directory, with one file per entry.
+pub struct ArtifactCache {
+    dir: PathBuf,
+}
+
+impl ArtifactCache {
+    pub fn new(dir: PathBuf) -> Result<ArtifactCache> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create cache directory `{}`", dir.display()))?;
+        Ok(ArtifactCache { dir })
+    }
+
+    fn entry(&self, step: CachedStep, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{}-{}.goto", step.as_ref(), key.finish()))
+    }
+
+    /// Copy the cached output of `step` for `key` to `output`. Return whether the cache had it.
+    pub fn restore(&self, step: CachedStep, key: &CacheKey, output: &Path) -> Result<bool> {
+        let entry = self.entry(step, key);
+        if !entry.exists() {
+            return Ok(false);
+        }
+        std::fs::copy(&entry, output)
+            .with_context(|| format!("Failed to restore cached `{}`", entry.display()))?;
+        Ok(true)
+    }
+
+    /// Store `output` as the output of `step` for `key`.
+    pub fn store(&self, step: CachedStep, key: &CacheKey, output: &Path) -> Result<()> {
+        let entry = self.entry(step, key);
+        // Copy to a temporary file first, so that concurrent readers, e.g., on other machines
+        // sharing the cache, never see a partial entry.
+        let tmp = tempfile::NamedTempFile::new_in(&self.dir)?;
+        std::fs::copy(output, tmp.path())?;
+        tmp.persist(&entry)
+            .with_context(|| format!("Failed to store cache entry `{}`", entry.display()))?;
+        Ok(())
+    }
+}
+
+impl KaniSession {
+    /// Produce `output` with `produce`, unless the output for the same `key` is in the cache.
+    /// Does nothing special if `--cache-goto` isn't enabled.
+    pub fn cached_step(
+        &self,
+        outdir: &Path,
+        step: CachedStep,
+        key: impl FnOnce() -> Result<CacheKey>,
+        output: &Path,
+        produce: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        if !self.args.cache_goto {
+            return produce();
+        }
+        let dir = self.args.cache_dir.clone().unwrap_or_else(|| outdir.join(DEFAULT_CACHE_DIR));
+        let cache = ArtifactCache::new(dir)?;
+        let key = key()?;
+        if cache.restore(step, &key, output)? {
+            debug!(?step, ?output, "cache hit");
+            return Ok(());
+        }
+        produce()?;
+        cache.store(step, &key, output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ArtifactCache::new(dir.path().join("cache")).unwrap();
+        let output = dir.path().join("harness.goto");
+        std::fs::write(&output, "linked").unwrap();
+
+        let mut key = CacheKey::new(CachedStep::Link);
+        key.add("--some-option");
+        assert!(!cache.restore(CachedStep::Link, &key, &output).unwrap());
+        cache.store(CachedStep::Link, &key, &output).unwrap();
+
+        std::fs::write(&output, "stale").unwrap();
+        assert!(cache.restore(CachedStep::Link, &key, &output).unwrap());
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "linked");
+
+        // The same inputs for a different step, or different inputs, don't hit.
+        let mut key = CacheKey::new(CachedStep::Instrument);
+        key.add("--some-option");
+        assert!(!cache.restore(CachedStep::Instrument, &key, &output).unwrap());
+        let mut key = CacheKey::new(CachedStep::Link);
+        key.add("--other-option");
+        assert!(!cache.restore(CachedStep::Link, &key, &output).unwrap());
+    }
+}
@@ -18,6 +18,8 @@
 use super::table_builder::TableBuilder;
 use super::table_failure_reasons::FailureReasonsTableRow;
 use super::table_promising_tests::PromisingTestsTableRow;
+use super::table_spec_coverage::SpecCoverageTableRow;
+use super::table_uncovered_modules::UncoveredModulesTableRow;
 use super::table_unsupported_features::UnsupportedFeaturesTableRow;
 use super::AssessArgs;
 
@@ -38,6 +40,10 @@ pub struct AssessMetadata {
     pub failure_reasons: TableBuilder<FailureReasonsTableRow>,
     /// Report on the tests that Kani can successfully analyze
     pub promising_tests: TableBuilder<PromisingTestsTableRow>,
+    /// Report on the public functions with contracts or reachable from harnesses, per package
+    pub spec_coverage: TableBuilder<SpecCoverageTableRow>,
+    /// Report on the modules where no public function is reachable from a harness
+    pub uncovered_modules: TableBuilder<UncoveredModulesTableRow>,
 }
 
 impl AssessMetadata {
@@ -45,6 +51,8 @@ pub fn new(
         unsupported_features: TableBuilder<UnsupportedFeaturesTableRow>,
         failure_reasons: TableBuilder<FailureReasonsTableRow>,
         promising_tests: TableBuilder<PromisingTestsTableRow>,
+        spec_coverage: TableBuilder<SpecCoverageTableRow>,
+        uncovered_modules: TableBuilder<UncoveredModulesTableRow>,
     ) -> AssessMetadata {
         AssessMetadata {
             version: env!("CARGO_PKG_VERSION").to_string(),
@@ -52,6 +60,8 @@ pub fn new(
             unsupported_features,
             failure_reasons,
             promising_tests,
+            spec_coverage,
+            uncovered_modules,
         }
     }
 
@@ -66,6 +76,8 @@ pub fn from_error(err: &dyn std::error::Error) -> AssessMetadata {
             unsupported_features: TableBuilder::new(),
             failure_reasons: TableBuilder::new(),
             promising_tests: TableBuilder::new(),
+            spec_coverage: TableBuilder::new(),
+            uncovered_modules: TableBuilder::new(),
         }
     }
     pub fn empty() -> AssessMetadata {
@@ -75,6 +87,8 @@ pub fn empty() -> AssessMetadata {
             unsupported_features: TableBuilder::new(),
             failure_reasons: TableBuilder::new(),
             promising_tests: TableBuilder::new(),
+            spec_coverage: TableBuilder::new(),
+            uncovered_modules: TableBuilder::new(),
         }
     }
 }
@@ -118,6 +132,12 @@ pub(crate) fn aggregate_metadata(metas: Vec<AssessMetadata>) -> AssessMetadata {
         for item in meta.promising_tests.build() {
             result.promising_tests.add(item.clone());
         }
+        for item in meta.spec_coverage.build() {
+            result.spec_coverage.add(item.clone());
+        }
+        for item in meta.uncovered_modules.build() {
+            result.uncovered_modules.add(item.clone());
+        }
     }
     result
 }
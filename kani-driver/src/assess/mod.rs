@@ -17,6 +17,8 @@
 mod table_builder;
 mod table_failure_reasons;
 mod table_promising_tests;
+mod table_spec_coverage;
+mod table_uncovered_modules;
 mod table_unsupported_features;
 
 /// `cargo kani assess` main entry point.
@@ -47,6 +49,7 @@ fn assess_project(mut session: KaniSession) -> Result<AssessMetadata> {
     session.args.tests = true;
     session.args.output_format = crate::args::OutputFormat::Terse;
     session.codegen_tests = true;
+    session.record_public_functions = true;
     if session.args.jobs.is_none() {
         // assess will default to fully parallel instead of single-threaded.
         // can be overridden with e.g. `cargo kani --enable-unstable -j 8 assess`
@@ -83,11 +86,21 @@ fn assess_project(mut session: KaniSession) -> Result<AssessMetadata> {
         println!("No crates contained Rust features unsupported by Kani");
     }
 
+    // How much of the public API is specified with contracts and covered by harnesses.
+    let spec_coverage = table_spec_coverage::build(&packages_metadata);
+    println!("{}", spec_coverage.render());
+    let uncovered_modules = table_uncovered_modules::build(&packages_metadata);
+    if !uncovered_modules.build().is_empty() {
+        println!("{}", uncovered_modules.render());
+    }
+
     if session.args.only_codegen {
         return Ok(AssessMetadata::new(
             unsupported_features,
             TableBuilder::new(),
             TableBuilder::new(),
+            spec_coverage,
+            uncovered_modules,
         ));
     }
 
@@ -113,7 +126,13 @@ fn assess_project(mut session: KaniSession) -> Result<AssessMetadata> {
     let promising_tests = table_promising_tests::build(&results);
     println!("{}", promising_tests.render());
 
-    Ok(AssessMetadata::new(unsupported_features, failure_reasons, promising_tests))
+    Ok(AssessMetadata::new(
+        unsupported_features,
+        failure_reasons,
+        promising_tests,
+        spec_coverage,
+        uncovered_modules,
+    ))
 }
 
 /// Merges a collection of Kani metadata by figuring out which package each belongs to, from cargo metadata.
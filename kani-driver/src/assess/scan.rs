@@ -142,6 +142,10 @@ pub(crate) fn assess_scan_main(session: KaniSession, args: &ScanArgs) -> Result<
     let results = aggregate_metadata(success_metas);
     print_failures(failed_packages);
     println!("{}", results.unsupported_features.render());
+    println!("{}", results.spec_coverage.render());
+    if !results.uncovered_modules.build().is_empty() {
+        println!("{}", results.uncovered_modules.render());
+    }
 
     if !session.args.only_codegen {
         println!("{}", results.failure_reasons.render());
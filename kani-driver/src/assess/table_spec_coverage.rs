@@ -0,0 +1,143 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::cmp::Ordering;
+
+use kani_metadata::KaniMetadata;
+use serde::{Deserialize, Serialize};
+
+use super::table_builder::{ColumnType, RenderableTableRow, TableBuilder, TableRow};
+
+/// Reports, for each crate, how many of its public functions have a contract, and how many are
+/// reachable from at least one harness.
+///
+/// For example:
+///
+/// ```text
+/// ==================================================================
+///  Crate    |    Public |      With | Reachable from | Uncovered
+///           | functions | contracts |      harnesses |   modules
+/// ----------+-----------+-----------+----------------+-----------
+///  my_crate |        42 |         3 |             17 |         2
+/// ==================================================================
+/// ```
+pub(crate) fn build(metadata: &[KaniMetadata]) -> TableBuilder<SpecCoverageTableRow> {
+    let mut builder = TableBuilder::new();
+
+    for package_metadata in metadata {
+        let functions = &package_metadata.public_functions;
+        builder.add(SpecCoverageTableRow {
+            crate_name: package_metadata.crate_name.clone(),
+            public_functions: functions.len(),
+            with_contracts: functions.iter().filter(|f| f.has_contract).count(),
+            reachable: functions.iter().filter(|f| f.is_reachable).count(),
+            uncovered_modules: super::table_uncovered_modules::uncovered_modules(functions).len(),
+        })
+    }
+
+    builder
+}
+
+/// Reports the specification and proof coverage of a crate.
+///
+/// See [`build`]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SpecCoverageTableRow {
+    /// The name of the crate.
+    pub crate_name: String,
+    /// The number of public functions in the crate.
+    pub public_functions: usize,
+    /// The number of public functions that have a contract.
+    pub with_contracts: usize,
+    /// The number of public functions that are reachable from at least one harness.
+    pub reachable: usize,
+    /// The number of modules that have public functions, but none reachable from a harness.
+    pub uncovered_modules: usize,
+}
+
+impl TableRow for SpecCoverageTableRow {
+    type Key = String;
+
+    fn key(&self) -> Self::Key {
+        self.crate_name.clone()
+    }
+
+    fn merge(&mut self, new: Self) {
+        self.public_functions += new.public_functions;
+        self.with_contracts += new.with_contracts;
+        self.reachable += new.reachable;
+        self.uncovered_modules += new.uncovered_modules;
+    }
+
+    fn compare(&self, right: &Self) -> Ordering {
+        self.crate_name.cmp(&right.crate_name)
+    }
+}
+
+impl RenderableTableRow for SpecCoverageTableRow {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "Crate",
+            "Public\nfunctions",
+            "With\ncontracts",
+            "Reachable from\nharnesses",
+            "Uncovered\nmodules",
+        ]
+    }
+
+    fn columns() -> Vec<ColumnType> {
+        use ColumnType::*;
+        vec![Text, Number, Number, Number, Number]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.crate_name.clone(),
+            self.public_functions.to_string(),
+            self.with_contracts.to_string(),
+            self.reachable.to_string(),
+            self.uncovered_modules.to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kani_metadata::FunctionMetadata;
+
+    #[test]
+    fn check_row_lengths() {
+        use SpecCoverageTableRow as Row;
+        assert_eq!(Row::columns().len(), Row::headers().len());
+        assert_eq!(Row::columns().len(), Row::row(&Default::default()).len());
+    }
+
+    #[test]
+    fn check_spec_coverage() {
+        let function = |name: &str, module: &str, has_contract, is_reachable| FunctionMetadata {
+            name: name.to_string(),
+            module: module.to_string(),
+            has_contract,
+            is_reachable,
+        };
+        let metadata = KaniMetadata {
+            crate_name: "krate".to_string(),
+            proof_harnesses: vec![],
+            unsupported_features: vec![],
+            test_harnesses: vec![],
+            public_functions: vec![
+                function("a::f", "a", true, true),
+                function("a::g", "a", false, false),
+                function("b::h", "b", true, false),
+            ],
+        };
+        let table = build(&[metadata]);
+        let rows = table.build();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].public_functions, 3);
+        assert_eq!(rows[0].with_contracts, 2);
+        assert_eq!(rows[0].reachable, 1);
+        assert_eq!(rows[0].uncovered_modules, 1);
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use kani_metadata::{FunctionMetadata, KaniMetadata};
+use serde::{Deserialize, Serialize};
+
+use super::table_builder::{ColumnType, RenderableTableRow, TableBuilder, TableRow};
+
+/// Reports the modules with public functions where none of them is reachable from a harness.
+///
+/// For example:
+///
+/// ```text
+/// ===================================================
+///  Module without proof coverage   | Public functions
+/// ---------------------------------+------------------
+///  my_crate::parser                |                12
+///  my_crate::util                  |                 3
+/// ===================================================
+/// ```
+pub(crate) fn build(metadata: &[KaniMetadata]) -> TableBuilder<UncoveredModulesTableRow> {
+    let mut builder = TableBuilder::new();
+
+    for package_metadata in metadata {
+        for (module, public_functions) in uncovered_modules(&package_metadata.public_functions) {
+            let module = if module.is_empty() {
+                package_metadata.crate_name.clone()
+            } else {
+                format!("{}::{module}", package_metadata.crate_name)
+            };
+            builder.add(UncoveredModulesTableRow { module, public_functions })
+        }
+    }
+
+    builder
+}
+
+/// Return the modules that have public functions, but none reachable from a harness, together
+/// with their number of public functions.
+pub(crate) fn uncovered_modules(functions: &[FunctionMetadata]) -> BTreeMap<&str, usize> {
+    let mut modules: BTreeMap<&str, (usize, bool)> = BTreeMap::new();
+    for function in functions {
+        let (count, covered) = modules.entry(function.module.as_str()).or_default();
+        *count += 1;
+        *covered |= function.is_reachable;
+    }
+    modules
+        .into_iter()
+        .filter_map(|(module, (count, covered))| (!covered).then_some((module, count)))
+        .collect()
+}
+
+/// Reports a module without any proof coverage.
+///
+/// See [`build`]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct UncoveredModulesTableRow {
+    /// The path of the module, including the crate name.
+    pub module: String,
+    /// The number of public functions in the module.
+    pub public_functions: usize,
+}
+
+impl TableRow for UncoveredModulesTableRow {
+    type Key = String;
+
+    fn key(&self) -> Self::Key {
+        self.module.clone()
+    }
+
+    fn merge(&mut self, new: Self) {
+        self.public_functions += new.public_functions;
+    }
+
+    fn compare(&self, right: &Self) -> Ordering {
+        self.public_functions
+            .cmp(&right.public_functions)
+            .reverse()
+            .then_with(|| self.module.cmp(&right.module))
+    }
+}
+
+impl RenderableTableRow for UncoveredModulesTableRow {
+    fn headers() -> Vec<&'static str> {
+        vec!["Module without proof coverage", "Public functions"]
+    }
+
+    fn columns() -> Vec<ColumnType> {
+        use ColumnType::*;
+        vec![Text, Number]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.module.clone(), self.public_functions.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_row_lengths() {
+        use UncoveredModulesTableRow as Row;
+        assert_eq!(Row::columns().len(), Row::headers().len());
+        assert_eq!(Row::columns().len(), Row::row(&Default::default()).len());
+    }
+}
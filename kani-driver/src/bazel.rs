@@ -0,0 +1,75 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `kani bazel`, which verifies crates that Bazel rules built, instead of building
+//! them with cargo.
+//!
+//! The rules compile each crate with `kani-compiler` and the arguments printed by
+//! `kani bazel --print-compiler-args`. They then list the metadata files written by the compiler
+//! for the crates to verify in a crate information file, e.g.:
+//!
+//! ```json
+//! {
+//!   "crates": [
+//!     { "metadata": "bazel-out/k8-fastbuild/bin/parser/parser.kani-metadata.json" }
+//!   ]
+//! }
+//! ```
+//!
+//! Relative paths are resolved from the current directory.
+
+use crate::call_single_file::to_rustc_arg;
+use crate::metadata::from_json;
+use crate::session::{lib_folder_for, KaniSession};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The crate information file written by the build rules.
+#[derive(Debug, Deserialize)]
+struct CrateInfo {
+    crates: Vec<CrateEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateEntry {
+    /// The metadata file written by `kani-compiler` for the crate.
+    metadata: PathBuf,
+}
+
+/// The compiler and the arguments the build rules must compile the crates with.
+#[derive(Debug, Serialize)]
+struct CompilerArgs {
+    /// The compiler, which replaces `rustc`.
+    compiler: PathBuf,
+    /// The arguments of every crate, including the dependencies of the crates to verify.
+    args: Vec<String>,
+    /// The additional arguments of the crates to verify, which select the harnesses to compile.
+    target_args: Vec<String>,
+}
+
+/// Print the compiler and its arguments for the options of the session, as JSON.
+pub(crate) fn print_compiler_args(session: &KaniSession) -> Result<()> {
+    let lib_path = lib_folder_for(session.target_name())?;
+    let mut args = session.kani_rustc_flags(session.lib_config(lib_path)?);
+    if let Some(target) = &session.args.target_triple {
+        args.push("--target".into());
+        args.push(target.into());
+    }
+    args.push(to_rustc_arg(session.kani_compiler_flags()).into());
+    let compiler_args = CompilerArgs {
+        compiler: session.kani_compiler.clone(),
+        args: args.iter().map(|arg| arg.to_string_lossy().into_owned()).collect(),
+        target_args: vec![session.reachability_arg()],
+    };
+    println!("{}", serde_json::to_string_pretty(&compiler_args)?);
+    Ok(())
+}
+
+/// Read the metadata files listed in the crate information file.
+pub(crate) fn read_crate_info(crate_info: &Path) -> Result<Vec<PathBuf>> {
+    let info: CrateInfo = from_json(crate_info).with_context(|| {
+        format!("Failed to read the crate information `{}`", crate_info.display())
+    })?;
+    Ok(info.crates.into_iter().map(|entry| entry.metadata).collect())
+}
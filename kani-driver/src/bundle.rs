@@ -0,0 +1,192 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani bundle` and `cargo kani verify-bundle`.
+//!
+//! A bundle is a `.tar.gz` archive with the evidence of a verification run, so it can be archived
+//! and checked again later:
+//! - `manifest.json`: the toolchain, the Kani and CBMC versions, the options of the run, and the
+//!   result of each harness.
+//! - `metadata.json`: the metadata of the crates, with the attributes each harness was verified
+//!   with.
+//! - `goto/`: the instrumented goto binary of each harness, as given to CBMC.
+//!
+//! Verifying a bundle runs CBMC on its goto binaries with the recorded options, without building
+//! the package, and fails if any harness has a different result.
+
+use crate::args::bundle_args::{CargoBundleArgs, CargoVerifyBundleArgs};
+use crate::args::{CargoKaniArgs, VerificationArgs};
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::metadata::from_json;
+use crate::project;
+use crate::report::baseline::{Baseline, BaselineStatus};
+use crate::session::KaniSession;
+use crate::util::warning;
+use crate::version::KANI_VERSION;
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use kani_metadata::{ArtifactType, KaniMetadata};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const METADATA_FILE: &str = "metadata.json";
+const GOTO_DIR: &str = "goto";
+
+/// The description of a verification run stored in a bundle.
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleManifest {
+    /// The Rust toolchain Kani was built with.
+    toolchain: String,
+    /// The target triple the package was compiled for.
+    target: String,
+    /// The output of `cbmc --version`.
+    cbmc_version: String,
+    /// The arguments of `cargo-kani`, including the ones read from the configuration files.
+    args: Vec<String>,
+    /// The result of each harness, which also records the Kani version.
+    results: Baseline,
+}
+
+/// `cargo kani bundle` main entry point.
+pub(crate) fn bundle_cargo(
+    session: KaniSession,
+    args: CargoBundleArgs,
+    input_args: &[OsString],
+) -> Result<()> {
+    let project = project::cargo_project(&session, false)?;
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let runner = HarnessRunner { sess: &session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+
+    let staging = tempfile::tempdir()?;
+    let manifest = BundleManifest {
+        toolchain: env!("RUSTUP_TOOLCHAIN").to_string(),
+        target: session.build_target().to_string(),
+        cbmc_version: cbmc_version()?,
+        args: input_args.iter().map(|arg| arg.to_string_lossy().into_owned()).collect(),
+        results: Baseline::new(&results),
+    };
+    write_json(&staging.path().join(MANIFEST_FILE), &manifest)?;
+    write_json(&staging.path().join(METADATA_FILE), &project.metadata)?;
+    let goto_dir = staging.path().join(GOTO_DIR);
+    std::fs::create_dir(&goto_dir)?;
+    for HarnessResult { harness, .. } in &results {
+        let model = project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
+        let goto_file = session.harness_binary(model, harness);
+        std::fs::copy(goto_file, goto_dir.join(goto_file_name(&harness.pretty_name)))?;
+    }
+
+    let mut cmd = Command::new("tar");
+    cmd.arg("czf").arg(&args.output).arg("-C").arg(staging.path()).arg(".");
+    session.run_suppress(cmd).context("Failed to create the bundle")?;
+    if !session.args.common_args.quiet {
+        println!("[info] Bundle written to {}", args.output.display());
+    }
+
+    session.print_final_summary(&results)
+}
+
+/// `cargo kani verify-bundle` main entry point.
+pub(crate) fn verify_bundle_cargo(
+    mut session: KaniSession,
+    args: CargoVerifyBundleArgs,
+) -> Result<()> {
+    let staging = tempfile::tempdir()?;
+    let mut cmd = Command::new("tar");
+    cmd.arg("xzf").arg(&args.bundle).arg("-C").arg(staging.path());
+    session.run_suppress(cmd).context("Failed to extract the bundle")?;
+    let manifest: BundleManifest = from_json(&staging.path().join(MANIFEST_FILE))?;
+    let metadata: Vec<KaniMetadata> = from_json(&staging.path().join(METADATA_FILE))?;
+
+    if manifest.results.kani_version != KANI_VERSION {
+        warning(&format!(
+            "the bundle was created by Kani {}, but this is Kani {KANI_VERSION}",
+            manifest.results.kani_version
+        ));
+    }
+    let cbmc_version = cbmc_version()?;
+    if manifest.cbmc_version != cbmc_version {
+        warning(&format!(
+            "the bundle was created with CBMC {}, but this is CBMC {cbmc_version}",
+            manifest.cbmc_version
+        ));
+    }
+
+    session.args = recorded_verify_opts(&manifest.args)?;
+
+    let harnesses: Vec<_> = metadata
+        .iter()
+        .flat_map(|krate| krate.proof_harnesses.iter().chain(krate.test_harnesses.iter()))
+        .collect();
+    let mut mismatches = vec![];
+    for expected in &manifest.results.harnesses {
+        let Some(harness) = harnesses.iter().find(|harness| {
+            harness.pretty_name == expected.harness && harness.crate_name == expected.crate_name
+        }) else {
+            bail!("No metadata for harness `{}` in the bundle", expected.harness);
+        };
+        let goto_file = staging.path().join(GOTO_DIR).join(goto_file_name(&harness.pretty_name));
+        let result = session.run_cbmc(&goto_file, harness)?;
+        let status = BaselineStatus::from(result.status);
+        if status == expected.status {
+            println!("Harness {}: {status:?}, as recorded", harness.pretty_name);
+        } else {
+            println!(
+                "Harness {}: {status:?}, but {:?} was recorded",
+                harness.pretty_name, expected.status
+            );
+            mismatches.push(harness.pretty_name.as_str());
+        }
+    }
+    if !mismatches.is_empty() {
+        bail!("Failed to reproduce the results of: `{}`", mismatches.join("`, `"));
+    }
+    println!("Reproduced the results of {} harnesses.", manifest.results.harnesses.len());
+    Ok(())
+}
+
+/// The options to verify the goto binaries of a bundle with, parsed from the recorded arguments.
+fn recorded_verify_opts(args: &[String]) -> Result<VerificationArgs> {
+    let mut verify_opts = CargoKaniArgs::try_parse_from(args)
+        .context("Failed to parse the arguments recorded in the bundle")?
+        .verify_opts;
+    // The per-harness configuration was already applied to the recorded metadata, and the goto
+    // binaries are verified once, with the unwind bounds they were instrumented with.
+    verify_opts.config = None;
+    verify_opts.infer_unwind = false;
+    Ok(verify_opts)
+}
+
+fn goto_file_name(harness: &str) -> String {
+    format!("{}.out", harness.replace("::", "-"))
+}
+
+fn cbmc_version() -> Result<String> {
+    let output = Command::new("cbmc").arg("--version").output().context("Failed to run CBMC")?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    let contents = serde_json::to_string_pretty(value)?;
+    std::fs::write(path, contents).with_context(|| format!("Failed to write `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_recorded_verify_opts() {
+        let args: Vec<String> = "cargo-kani --config kani.toml --default-unwind 3 -Z unstable-options bundle -o out.tar.gz"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        let verify_opts = recorded_verify_opts(&args).unwrap();
+        assert_eq!(verify_opts.config, None);
+        assert_eq!(verify_opts.default_unwind, Some(3));
+        assert_eq!(goto_file_name("parser::check_parse"), "parser-check_parse.out");
+    }
+}
@@ -4,7 +4,7 @@
 use crate::args::VerificationArgs;
 use crate::call_single_file::{to_rustc_arg, LibConfig};
 use crate::project::Artifact;
-use crate::session::{lib_folder, lib_no_core_folder, setup_cargo_command, KaniSession};
+use crate::session::{lib_folder_for, lib_no_core_folder, setup_cargo_command, KaniSession};
 use crate::util;
 use anyhow::{bail, Context, Result};
 use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
@@ -61,7 +61,7 @@ pub fn cargo_build_std(&self, std_path: &Path, krate_path: &Path) -> Result<Vec<
         rustc_args.push(to_rustc_arg(vec!["--ignore-global-asm".to_string()]).into());
 
         let mut cargo_args: Vec<OsString> = vec!["build".into()];
-        cargo_args.append(&mut cargo_config_args());
+        cargo_args.append(&mut cargo_config_args(self.build_target()));
 
         // Configuration needed to parse cargo compilation status.
         cargo_args.push("--message-format".into());
@@ -106,7 +106,7 @@ pub fn cargo_build_std(&self, std_path: &Path, krate_path: &Path) -> Result<Vec<
 
     /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir`
     pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
-        let build_target = env!("TARGET"); // see build.rs
+        let build_target = self.build_target();
         let metadata = self.cargo_metadata(build_target)?;
         let target_dir = self
             .args
@@ -115,15 +115,21 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
             .unwrap_or(&metadata.target_directory.clone().into())
             .clone()
             .join("kani");
-        let outdir = target_dir.join(build_target).join("debug/deps");
+        let outdir = target_dir.join(self.target_name()).join("debug/deps");
 
         if self.args.force_build && target_dir.exists() {
             fs::remove_dir_all(&target_dir)?;
         }
 
-        let lib_path = lib_folder().unwrap();
-        let mut rustc_args = self.kani_rustc_flags(LibConfig::new(lib_path));
-        rustc_args.push(to_rustc_arg(self.kani_compiler_flags()).into());
+        let lib_path = lib_folder_for(self.target_name())?;
+        let mut rustc_args = self.kani_rustc_flags(self.lib_config(lib_path)?);
+        let mut compiler_flags = self.kani_compiler_flags();
+        compiler_flags.extend(
+            self.assumed_contracts(&metadata)?
+                .into_iter()
+                .map(|path| format!("--assume-contract={path}")),
+        );
+        rustc_args.push(to_rustc_arg(compiler_flags).into());
 
         let mut cargo_args: Vec<OsString> = vec!["rustc".into()];
         if let Some(path) = &self.args.cargo.manifest_path {
@@ -141,7 +147,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
             cargo_args.push(format!("--features={}", features.join(",")).into());
         }
 
-        cargo_args.append(&mut cargo_config_args());
+        cargo_args.append(&mut cargo_config_args(build_target));
 
         cargo_args.push("--target-dir".into());
         cargo_args.push(target_dir.into());
@@ -338,10 +344,10 @@ fn same_target(t1: &Target, t2: &Target) -> bool {
     }
 }
 
-pub fn cargo_config_args() -> Vec<OsString> {
+pub fn cargo_config_args(target: &str) -> Vec<OsString> {
     [
         "--target",
-        env!("TARGET"),
+        target,
         // Propagate `--cfg=kani_host` to build scripts.
         "-Zhost-config",
         "-Ztarget-applies-to-host",
@@ -361,60 +367,100 @@ fn print_msg(diagnostic: &Diagnostic, use_rendered: bool) -> Result<()> {
     Ok(())
 }
 
-/// Check that all package names are present in the workspace, otherwise return which aren't.
-fn validate_package_names(package_names: &[String], packages: &[Package]) -> Result<()> {
-    let package_list: Vec<String> = packages.iter().map(|pkg| pkg.name.clone()).collect();
-    let unknown_packages: Vec<&String> =
-        package_names.iter().filter(|pkg_name| !package_list.contains(pkg_name)).collect();
+/// A package selection given to `--package` or `--exclude`, following cargo's syntax: a package
+/// name, a glob pattern of package names (e.g. `parser-*`), or a name and version (`name@1.2.0`).
+#[derive(Debug)]
+struct PackageSpec {
+    spec: String,
+    name: glob::Pattern,
+    version: Option<String>,
+}
 
-    // Some packages aren't in the workspace. Return an error which includes their names.
-    if !unknown_packages.is_empty() {
-        let fmt_packages: Vec<String> =
-            unknown_packages.iter().map(|pkg| format!("`{pkg}`")).collect();
-        let error_msg = if unknown_packages.len() == 1 {
-            format!("couldn't find package {}", fmt_packages[0])
+impl PackageSpec {
+    fn new(spec: &str) -> Result<Self> {
+        let (name, version) = match spec.split_once('@') {
+            Some((name, version)) => (name, Some(version.to_string())),
+            None => (spec, None),
+        };
+        let name = glob::Pattern::new(name)
+            .with_context(|| format!("invalid glob pattern in package selection `{spec}`"))?;
+        Ok(PackageSpec { spec: spec.to_string(), name, version })
+    }
+
+    /// Whether this is a glob pattern, which cargo only matches against workspace members.
+    fn is_pattern(&self) -> bool {
+        self.name.as_str().contains(['*', '?', '['])
+    }
+
+    fn matches(&self, name: &str, version: &str) -> bool {
+        self.name.matches(name) && self.version.as_ref().map_or(true, |v| v == version)
+    }
+
+    fn matches_package(&self, package: &Package) -> bool {
+        self.matches(&package.name, &package.version.to_string())
+    }
+}
+
+/// Select the packages matched by any of the given specs. Plain names may select any package of
+/// the metadata (e.g., a dependency), while patterns only select workspace members.
+/// Return an error with the specs that didn't match any package.
+fn select_packages<'b>(specs: &[String], metadata: &'b Metadata) -> Result<Vec<&'b Package>> {
+    let specs = specs.iter().map(|spec| PackageSpec::new(spec)).collect::<Result<Vec<_>>>()?;
+    let workspace_packages = metadata.workspace_packages();
+    let mut selected: Vec<&Package> = vec![];
+    let mut unknown_packages = vec![];
+    for spec in &specs {
+        let matched: Vec<&Package> = if spec.is_pattern() {
+            workspace_packages.iter().copied().filter(|pkg| spec.matches_package(pkg)).collect()
         } else {
-            format!("couldn't find packages {}", fmt_packages.join(", "))
+            metadata.packages.iter().filter(|pkg| spec.matches_package(pkg)).collect()
         };
-        bail!(error_msg);
-    };
-    Ok(())
+        if matched.is_empty() {
+            unknown_packages.push(format!("`{}`", spec.spec));
+        }
+        for pkg in matched {
+            if !selected.iter().any(|selected| selected.id == pkg.id) {
+                selected.push(pkg);
+            }
+        }
+    }
+
+    // Some packages aren't in the workspace. Return an error which includes their names.
+    match unknown_packages.as_slice() {
+        [] => Ok(selected),
+        [package] => bail!("couldn't find package {package}"),
+        packages => bail!("couldn't find packages {}", packages.join(", ")),
+    }
 }
 
 /// Extract the packages that should be verified.
 ///
 /// The result is build following these rules:
-/// - If `--package <pkg>` is given, return the list of packages selected.
-/// - If `--exclude <pkg>` is given, return the list of packages not excluded.
+/// - If `--package <spec>` is given, return the list of packages selected.
+/// - If `--exclude <spec>` is given, return the list of workspace members not excluded.
 /// - If `--workspace` is given, return the list of workspace members.
 /// - If no argument provided, return the root package if there's one or all members.
 ///   - I.e.: Do whatever cargo does when there's no `default_members`.
 ///   - This is because `default_members` is not available in cargo metadata.
 ///     See <https://github.com/rust-lang/cargo/issues/8033>.
 ///
-/// In addition, if either `--package <pkg>` or `--exclude <pkg>` is given,
-/// validate that `<pkg>` is a package name in the workspace, or return an error
-/// otherwise.
-fn packages_to_verify<'b>(
+/// Like in cargo, a `<spec>` can be a package name, a glob pattern or `name@version`. If a spec
+/// doesn't match any package, return an error.
+pub(crate) fn packages_to_verify<'b>(
     args: &VerificationArgs,
     metadata: &'b Metadata,
 ) -> Result<Vec<&'b Package>> {
     debug!(package_selection=?args.cargo.package, package_exclusion=?args.cargo.exclude, workspace=args.cargo.workspace, "packages_to_verify args");
     let packages = if !args.cargo.package.is_empty() {
-        validate_package_names(&args.cargo.package, &metadata.packages)?;
-        args.cargo
-            .package
-            .iter()
-            .map(|pkg_name| metadata.packages.iter().find(|pkg| pkg.name == *pkg_name).unwrap())
-            .collect()
+        select_packages(&args.cargo.package, metadata)?
     } else if !args.cargo.exclude.is_empty() {
         // should be ensured by argument validation
         assert!(args.cargo.workspace);
-        validate_package_names(&args.cargo.exclude, &metadata.packages)?;
+        let excluded = select_packages(&args.cargo.exclude, metadata)?;
         metadata
             .workspace_packages()
             .into_iter()
-            .filter(|pkg| !args.cargo.exclude.contains(&pkg.name))
+            .filter(|pkg| !excluded.iter().any(|excluded| excluded.id == pkg.id))
             .collect()
     } else {
         match (args.cargo.workspace, metadata.root_package()) {
@@ -422,6 +468,9 @@ fn packages_to_verify<'b>(
             (_, Some(root_pkg)) => vec![root_pkg],
         }
     };
+    if packages.is_empty() {
+        bail!("no packages left to verify after applying `--exclude`");
+    }
     trace!(?packages, "packages_to_verify result");
     Ok(packages)
 }
@@ -584,3 +633,27 @@ fn package_targets(args: &VerificationArgs, package: &Package) -> Vec<Verificati
     }
     verification_targets
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_package_spec() {
+        let spec = PackageSpec::new("parser").unwrap();
+        assert!(!spec.is_pattern());
+        assert!(spec.matches("parser", "0.1.0"));
+        assert!(!spec.matches("parser-core", "0.1.0"));
+
+        let spec = PackageSpec::new("parser-*").unwrap();
+        assert!(spec.is_pattern());
+        assert!(spec.matches("parser-core", "0.1.0"));
+        assert!(!spec.matches("parser", "0.1.0"));
+
+        let spec = PackageSpec::new("parser@0.2.0").unwrap();
+        assert!(spec.matches("parser", "0.2.0"));
+        assert!(!spec.matches("parser", "0.1.0"));
+
+        assert!(PackageSpec::new("parser-[").is_err());
+    }
+}
@@ -2,26 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{bail, Result};
-use kani_metadata::{CbmcSolver, HarnessMetadata};
+use kani_metadata::{CbmcSolver, HarnessChecks, HarnessMetadata};
 use regex::Regex;
 use rustc_demangle::demangle;
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsString;
 use std::fmt::Write;
+use std::os::unix::process::CommandExt;
 use std::path::Path;
 use std::process::Command;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::thread;
 use std::time::{Duration, Instant};
 
-use crate::args::{OutputFormat, VerificationArgs};
+use crate::args::{FailureCategory, OutputFormat, VerificationArgs};
+use crate::args_toml::Severity;
 use crate::cbmc_output_parser::{
-    extract_results, process_cbmc_output, CheckStatus, Property, VerificationOutput,
+    extract_results, process_cbmc_output, CheckStatus, ParserItem, ProcessLimits, Property,
+    VerificationOutput,
+};
+use crate::cbmc_property_renderer::{
+    format_coverage, format_result, has_no_reachable_assertions, kani_cbmc_output_filter,
 };
-use crate::cbmc_property_renderer::{format_coverage, format_result, kani_cbmc_output_filter};
 use crate::coverage::cov_results::{CoverageCheck, CoverageResults};
 use crate::coverage::cov_results::{CoverageRegion, CoverageTerm};
 use crate::session::KaniSession;
+use crate::stats::HarnessStats;
 
 /// We will use Cadical by default since it performed better than MiniSAT in our analysis.
 /// Note: Kissat was marginally better, but it is an external solver which could be more unstable.
@@ -31,6 +39,10 @@
 pub enum VerificationStatus {
     Success,
     Failure,
+    /// CBMC was stopped because the harness exceeded its timeout.
+    Timeout,
+    /// CBMC was stopped because the harness exceeded its memory limit.
+    OutOfMemory,
 }
 
 /// Represents failed properties in three different categories.
@@ -61,22 +73,45 @@ pub struct VerificationResult {
     pub runtime: Duration,
     /// Whether concrete playback generated a test
     pub generated_concrete_test: bool,
+    /// The unwind bound found by `--infer-unwind`, if verification only succeeded with it.
+    pub inferred_unwind: Option<u32>,
     /// The coverage results
     pub coverage_results: Option<CoverageResults>,
+    /// The resource statistics of this CBMC invocation.
+    pub stats: HarnessStats,
+    /// The locations of the assumptions that no execution satisfies, found with
+    /// `-Z assumption-checks`.
+    pub unsatisfiable_assumptions: Vec<String>,
 }
 
 impl KaniSession {
     /// Verify a goto binary that's been prepared with goto-instrument
     pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
-        let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
+        self.run_cbmc_with_args(file, harness, &[])
+    }
 
-        // TODO get cbmc path from self
-        let mut cmd = Command::new("cbmc");
-        cmd.args(args);
+    /// Verify a goto binary with additional CBMC arguments, e.g., to restrict the properties
+    /// that are checked.
+    pub fn run_cbmc_with_args(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        extra_args: &[OsString],
+    ) -> Result<VerificationResult> {
+        if let CbmcSolver::Portfolio(solvers) = self.resolve_solver(&harness.attributes.solver) {
+            // The old output format prints CBMC's output directly, so we cannot run several
+            // solvers at once. Fall back to the first solver of the portfolio instead.
+            if self.args.output_format != OutputFormat::Old {
+                return self.run_cbmc_portfolio(file, harness, extra_args, solvers);
+            }
+        }
+        let mut args: Vec<OsString> = self.cbmc_flags(file, harness)?;
+        args.extend_from_slice(extra_args);
 
         let start_time = Instant::now();
 
         let verification_results = if self.args.output_format == crate::args::OutputFormat::Old {
+            let (cmd, _remote_job) = self.cbmc_command(file, args, None)?;
             if self.run_terminal(cmd).is_err() {
                 VerificationResult::mock_failure()
             } else {
@@ -85,12 +120,26 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
         } else {
             // Add extra argument to receive the output in JSON format.
             // Done here because `--visualize` uses the XML format instead.
-            cmd.arg("--json-ui");
+            args.push("--json-ui".into());
+            let timeout = resolve_timeout_value(&self.args, harness).map(Duration::from_secs);
+            let (mut cmd, _remote_job) = self.cbmc_command(file, args, timeout)?;
+            if timeout.is_some() {
+                // Run CBMC in its own process group, so the timeout also stops the solver it
+                // spawned. Otherwise, CBMC stays in our group so that Ctrl-C reaches it.
+                cmd.process_group(0);
+            }
 
             // Spawn the CBMC process and process its output below
             let cbmc_process_opt = self.run_piped(cmd)?;
             let cbmc_process = cbmc_process_opt.ok_or(anyhow::Error::msg("Failed to run cbmc"))?;
-            let output = process_cbmc_output(cbmc_process, |i| {
+            let peak_memory = AtomicU64::new(0);
+            let memory_check = self.memory_check(harness, &peak_memory);
+            let limits = ProcessLimits {
+                timeout,
+                memory_check: memory_check.as_ref().map(|check| check as _),
+                cancel: None,
+            };
+            let output = process_cbmc_output(cbmc_process, limits, |i| {
                 kani_cbmc_output_filter(
                     i,
                     self.args.extra_pointer_checks,
@@ -99,12 +148,120 @@ pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<Verific
                 )
             })?;
 
-            VerificationResult::from(output, harness.attributes.should_panic, start_time)
+            let mut result =
+                VerificationResult::from(output, harness.attributes.should_panic, start_time);
+            if self.remote_workers.is_none() {
+                result.record_peak_memory(&peak_memory);
+            }
+            result
         };
 
         Ok(verification_results)
     }
 
+    /// Run one CBMC process per solver of the portfolio in parallel, and use the result of the
+    /// first solver that produces a conclusive result. The other processes are killed.
+    fn run_cbmc_portfolio(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        extra_args: &[OsString],
+        solvers: &[CbmcSolver],
+    ) -> Result<VerificationResult> {
+        let start_time = Instant::now();
+        let timeout = resolve_timeout_value(&self.args, harness).map(Duration::from_secs);
+        let peak_memory = AtomicU64::new(0);
+        let memory_check = self.memory_check(harness, &peak_memory);
+        let cancel = AtomicBool::new(false);
+
+        let mut processes = Vec::with_capacity(solvers.len());
+        for solver in solvers {
+            let mut args = self.cbmc_flags_with_solver(file, harness, solver)?;
+            args.extend_from_slice(extra_args);
+            args.push("--json-ui".into());
+            let (mut cmd, remote_job) = self.cbmc_command(file, args, timeout)?;
+            // The processes that lose are killed, along with the solvers they spawned.
+            cmd.process_group(0);
+            let process = self.run_piped(cmd)?.ok_or(anyhow::Error::msg("Failed to run cbmc"))?;
+            processes.push((solver, process, remote_job));
+        }
+
+        let (winner, output) = thread::scope(|scope| -> Result<_> {
+            let (tx, rx) = mpsc::channel();
+            for (solver, process, remote_job) in processes {
+                let limits = ProcessLimits {
+                    timeout,
+                    memory_check: memory_check.as_ref().map(|check| check as _),
+                    cancel: Some(&cancel),
+                };
+                let tx = tx.clone();
+                scope.spawn(move || {
+                    let output = process_cbmc_output(process, limits, |i| {
+                        // Don't print the output of each solver as it is processed, since the
+                        // processes are interleaved.
+                        kani_cbmc_output_filter(
+                            i,
+                            self.args.extra_pointer_checks,
+                            true,
+                            &self.args.output_format,
+                        )
+                    });
+                    drop(remote_job);
+                    let _ = tx.send((solver, output));
+                });
+            }
+            drop(tx);
+
+            let mut last = None;
+            for (solver, output) in rx.iter() {
+                let output = output?;
+                if !cancel.load(Ordering::Relaxed) && is_conclusive(&output) {
+                    cancel.store(true, Ordering::Relaxed);
+                    last = Some((Some(solver), output));
+                } else if last.is_none() {
+                    last = Some((None, output));
+                }
+            }
+            Ok(last.unwrap())
+        })?;
+
+        if !self.args.common_args.quiet {
+            match winner {
+                Some(solver) => println!(
+                    "Solver portfolio: {} finished first in {:.2}s",
+                    solver_name(solver),
+                    start_time.elapsed().as_secs_f32()
+                ),
+                None => println!("Solver portfolio: no solver produced a conclusive result"),
+            }
+        }
+        let mut result =
+            VerificationResult::from(output, harness.attributes.should_panic, start_time);
+        if self.remote_workers.is_none() {
+            result.record_peak_memory(&peak_memory);
+        }
+        Ok(result)
+    }
+
+    /// The memory check of the CBMC processes of `harness`, if it has a memory limit or if
+    /// `--stats` or `--metrics` is set. The peak memory usage of the processes is recorded in `peak_memory`.
+    /// With remote workers, only the local `ssh` process can be sampled, so its usage is not
+    /// recorded.
+    fn memory_check<'a>(
+        &self,
+        harness: &HarnessMetadata,
+        peak_memory: &'a AtomicU64,
+    ) -> Option<impl Fn(u32) -> bool + Sync + 'a> {
+        // We can only measure the local `ssh` process of remote workers, so no limit applies.
+        if self.remote_workers.is_some() {
+            return None;
+        }
+        let limit = resolve_memory_limit(&self.args, harness);
+        let record_usage = self.args.stats || self.args.metrics.is_some();
+        (limit.is_some() || record_usage)
+            .then(|| memory_check(limit.unwrap_or(u64::MAX), peak_memory))
+    }
+
     /// used by call_cbmc_viewer, invokes different variants of CBMC.
     // TODO: this could use some cleanup and refactoring.
     pub fn call_cbmc(&self, args: Vec<OsString>, output: &Path) -> Result<()> {
@@ -128,9 +285,24 @@ pub fn cbmc_flags(
         file: &Path,
         harness_metadata: &HarnessMetadata,
     ) -> Result<Vec<OsString>> {
-        let mut args = self.cbmc_check_flags();
+        let solver = self.resolve_solver(&harness_metadata.attributes.solver);
+        self.cbmc_flags_with_solver(file, harness_metadata, solver)
+    }
 
-        if let Some(object_bits) = self.args.cbmc_object_bits() {
+    /// The flags of [Self::cbmc_flags] for the given solver.
+    pub(crate) fn cbmc_flags_with_solver(
+        &self,
+        file: &Path,
+        harness_metadata: &HarnessMetadata,
+        solver: &CbmcSolver,
+    ) -> Result<Vec<OsString>> {
+        let mut args = self.cbmc_check_flags(&harness_metadata.attributes.checks);
+
+        if harness_metadata.attributes.check_leaks {
+            args.push("--memory-leak-check".into());
+        }
+
+        if let Some(object_bits) = self.cbmc_object_bits(harness_metadata) {
             args.push("--object-bits".into());
             args.push(object_bits.to_string().into());
         }
@@ -139,25 +311,41 @@ pub fn cbmc_flags(
             args.push("--unwind".into());
             args.push(unwind_value.to_string().into());
         }
+        if !harness_metadata.attributes.loop_unwinds.is_empty() {
+            let unwindset: Vec<_> = harness_metadata
+                .attributes
+                .loop_unwinds
+                .iter()
+                .map(|(loop_id, bound)| format!("{loop_id}:{bound}"))
+                .collect();
+            args.push("--unwindset".into());
+            args.push(unwindset.join(",").into());
+        }
 
-        self.handle_solver_args(&harness_metadata.attributes.solver, &mut args)?;
+        push_solver_args(solver, &mut args)?;
 
         if self.args.run_sanity_checks {
             args.push("--validate-goto-model".into());
             args.push("--validate-ssa-equation".into());
         }
 
+        let slice_formula =
+            harness_metadata.attributes.slicing.formula.unwrap_or(!self.args.no_slice_formula);
         if !self.args.visualize
             && self.args.concrete_playback.is_none()
-            && !self.args.no_slice_formula
+            && !self.args.searches_counterexamples()
+            && slice_formula
         {
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
+        if self.args.requires_trace() {
             args.push("--trace".into());
         }
 
+        // Arguments from the command line come last, so they take precedence over the ones
+        // given to the harness via `#[kani::cbmc_args]`.
+        args.extend(harness_metadata.attributes.cbmc_args.iter().map(OsString::from));
         args.extend(self.args.cbmc_args.iter().cloned());
 
         args.push(file.to_owned().into_os_string());
@@ -171,7 +359,10 @@ pub fn cbmc_flags(
     }
 
     /// Just the flags to CBMC that enable property checking of any sort.
-    pub fn cbmc_check_flags(&self) -> Vec<OsString> {
+    /// The check classes turned on or off by the harness with `#[kani::checks]` take precedence
+    /// over the command line options.
+    pub fn cbmc_check_flags(&self, harness_checks: &HarnessChecks) -> Vec<OsString> {
+        let checks = self.args.checks.for_harness(harness_checks);
         let mut args = Vec::new();
 
         // We assume that malloc cannot fail, see https://github.com/model-checking/kani/issues/891
@@ -186,11 +377,11 @@ pub fn cbmc_check_flags(&self) -> Vec<OsString> {
         // So these options are deliberately skipped to avoid erroneously re-checking operations.
         args.push("--no-signed-overflow-check".into());
 
-        if !self.args.checks.memory_safety_on() {
+        if !checks.memory_safety {
             args.push("--no-bounds-check".into());
             args.push("--no-pointer-check".into());
         }
-        if self.args.checks.overflow_on() {
+        if checks.overflow {
             args.push("--float-overflow-check".into());
             args.push("--nan-check".into());
 
@@ -204,7 +395,7 @@ pub fn cbmc_check_flags(&self) -> Vec<OsString> {
             args.push("--no-div-by-zero-check".into());
         }
 
-        if !self.args.checks.unwinding_on() {
+        if !checks.unwinding {
             args.push("--no-unwinding-assertions".into());
         } else {
             args.push("--no-self-loops-to-assumptions".into());
@@ -227,39 +418,71 @@ pub fn handle_solver_args(
         harness_solver: &Option<CbmcSolver>,
         args: &mut Vec<OsString>,
     ) -> Result<()> {
-        let solver = if let Some(solver) = &self.args.solver {
-            // `--solver` option takes precedence over attributes
-            solver
-        } else if let Some(solver) = harness_solver {
-            solver
+        push_solver_args(self.resolve_solver(harness_solver), args)
+    }
+
+    /// The `--object-bits` value, unless the user has supplied it explicitly in `--cbmc-args` or
+    /// in the `#[kani::cbmc_args]` attribute of the harness.
+    pub fn cbmc_object_bits(&self, harness: &HarnessMetadata) -> Option<u32> {
+        if harness.attributes.cbmc_args.iter().any(|arg| arg == "--object-bits") {
+            None
         } else {
-            &DEFAULT_SOLVER
-        };
+            self.args.cbmc_object_bits()
+        }
+    }
 
-        match solver {
-            CbmcSolver::Cadical => {
-                args.push("--sat-solver".into());
-                args.push("cadical".into());
-            }
-            CbmcSolver::Kissat => {
-                args.push("--external-sat-solver".into());
-                args.push("kissat".into());
-            }
-            CbmcSolver::Minisat => {
-                // Minisat is currently CBMC's default solver, so no need to
-                // pass any arguments
-            }
-            CbmcSolver::Binary(solver_binary) => {
-                // Check if the specified binary exists in path
-                if which::which(solver_binary).is_err() {
-                    bail!("the specified solver \"{solver_binary}\" was not found in path")
-                }
-                args.push("--external-sat-solver".into());
-                args.push(solver_binary.into());
+    /// The solver used for a harness.
+    fn resolve_solver<'a>(&'a self, harness_solver: &'a Option<CbmcSolver>) -> &'a CbmcSolver {
+        resolve_solver_value(&self.args, harness_solver)
+    }
+}
+
+fn push_solver_args(solver: &CbmcSolver, args: &mut Vec<OsString>) -> Result<()> {
+    match solver {
+        CbmcSolver::Cadical => {
+            args.push("--sat-solver".into());
+            args.push("cadical".into());
+        }
+        CbmcSolver::Kissat => {
+            args.push("--external-sat-solver".into());
+            args.push("kissat".into());
+        }
+        CbmcSolver::Minisat => {
+            // Minisat is currently CBMC's default solver, so no need to
+            // pass any arguments
+        }
+        CbmcSolver::Binary(solver_binary) => {
+            // Check if the specified binary exists in path
+            if which::which(solver_binary).is_err() {
+                bail!("the specified solver \"{solver_binary}\" was not found in path")
             }
+            args.push("--external-sat-solver".into());
+            args.push(solver_binary.into());
+        }
+        CbmcSolver::Portfolio(solvers) => {
+            // Tools that invoke CBMC once use the first solver of the portfolio.
+            push_solver_args(&solvers[0], args)?;
         }
-        Ok(())
     }
+    Ok(())
+}
+
+/// A human readable name of a solver.
+pub(crate) fn solver_name(solver: &CbmcSolver) -> String {
+    match solver {
+        CbmcSolver::Binary(binary) => binary.clone(),
+        CbmcSolver::Portfolio(solvers) => {
+            solvers.iter().map(solver_name).collect::<Vec<_>>().join(",")
+        }
+        solver => solver.as_ref().to_string(),
+    }
+}
+
+/// Whether a CBMC run produced verification results, rather than being killed or failing.
+fn is_conclusive(output: &VerificationOutput) -> bool {
+    !output.timed_out
+        && !output.out_of_memory
+        && output.processed_items.iter().any(|item| matches!(item, ParserItem::Result { .. }))
 }
 
 impl VerificationResult {
@@ -277,9 +500,46 @@ fn from(
         start_time: Instant,
     ) -> VerificationResult {
         let runtime = start_time.elapsed();
-        let (_, results) = extract_results(output.processed_items);
-
-        if let Some(results) = results {
+        let (messages, results) = extract_results(output.processed_items);
+        let stats = HarnessStats::from_messages(messages.iter().filter_map(|item| match item {
+            ParserItem::Message { message_text, .. } => Some(message_text.as_str()),
+            _ => None,
+        }));
+
+        if output.timed_out {
+            // Any result we got before CBMC was stopped is incomplete, so we don't report it.
+            VerificationResult {
+                status: VerificationStatus::Timeout,
+                failed_properties: FailedProperties::None,
+                results: Err(output.process_status),
+                runtime,
+                generated_concrete_test: false,
+                inferred_unwind: None,
+                coverage_results: None,
+                stats,
+                unsatisfiable_assumptions: vec![],
+            }
+        } else if output.out_of_memory {
+            VerificationResult {
+                status: VerificationStatus::OutOfMemory,
+                failed_properties: FailedProperties::None,
+                results: Err(output.process_status),
+                runtime,
+                generated_concrete_test: false,
+                inferred_unwind: None,
+                coverage_results: None,
+                stats,
+                unsatisfiable_assumptions: vec![],
+            }
+        } else if let Some(results) = results {
+            let (assumption_checks, results): (Vec<_>, Vec<_>) =
+                results.into_iter().partition(|prop| prop.is_assumption_check_property());
+            // The check after an assumption only succeeds if it's unreachable.
+            let unsatisfiable_assumptions = assumption_checks
+                .iter()
+                .filter(|prop| prop.status == CheckStatus::Success)
+                .map(|prop| prop.source_location.to_string())
+                .collect();
             let (status, failed_properties) =
                 verification_outcome_from_properties(&results, should_panic);
             let coverage_results = coverage_results_from_properties(&results);
@@ -289,7 +549,10 @@ fn from(
                 results: Ok(results),
                 runtime,
                 generated_concrete_test: false,
+                inferred_unwind: None,
                 coverage_results,
+                stats,
+                unsatisfiable_assumptions,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -299,11 +562,26 @@ fn from(
                 results: Err(output.process_status),
                 runtime,
                 generated_concrete_test: false,
+                inferred_unwind: None,
                 coverage_results: None,
+                stats,
+                unsatisfiable_assumptions: vec![],
             }
         }
     }
 
+    /// Record the peak memory sampled while CBMC ran, if any.
+    fn record_peak_memory(&mut self, peak_memory: &AtomicU64) {
+        let peak_memory = peak_memory.load(Ordering::Relaxed);
+        self.stats.peak_memory = (peak_memory > 0).then_some(peak_memory);
+    }
+
+    /// Whether verification succeeded only because none of the assertions was reachable.
+    pub fn is_vacuous(&self) -> bool {
+        self.status == VerificationStatus::Success
+            && self.results.as_ref().is_ok_and(|properties| has_no_reachable_assertions(properties))
+    }
+
     pub fn mock_success() -> VerificationResult {
         VerificationResult {
             status: VerificationStatus::Success,
@@ -311,7 +589,10 @@ pub fn mock_success() -> VerificationResult {
             results: Ok(vec![]),
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
+            inferred_unwind: None,
             coverage_results: None,
+            stats: HarnessStats::default(),
+            unsatisfiable_assumptions: vec![],
         }
     }
 
@@ -325,7 +606,10 @@ fn mock_failure() -> VerificationResult {
             results: Err(42),
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
+            inferred_unwind: None,
             coverage_results: None,
+            stats: HarnessStats::default(),
+            unsatisfiable_assumptions: vec![],
         }
     }
 
@@ -351,6 +635,26 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
                 writeln!(result, "Verification Time: {}s", self.runtime.as_secs_f32()).unwrap();
                 result
             }
+            Err(_) if self.status == VerificationStatus::Timeout => {
+                let verification_result = console::style("TIMEOUT").yellow();
+                format!(
+                    "\nCBMC was stopped after {}s because the harness timed out\n\
+                    VERIFICATION:- {verification_result}\n\
+                    You may want to increase the timeout with `#[kani::timeout]` or \
+                    `--default-harness-timeout`, or reduce the size of the problem.\n",
+                    self.runtime.as_secs()
+                )
+            }
+            Err(_) if self.status == VerificationStatus::OutOfMemory => {
+                let verification_result = console::style("OUT-OF-MEMORY").yellow();
+                format!(
+                    "\nCBMC was stopped after {}s because the harness exceeded its memory limit\n\
+                    VERIFICATION:- {verification_result}\n\
+                    You may want to increase the limit with `#[kani::memory_limit]` or \
+                    `--memory-limit`, or use stubbing to reduce the size of the problem.\n",
+                    self.runtime.as_secs()
+                )
+            }
             Err(exit_status) => {
                 let verification_result = console::style("FAILED").red();
                 let explanation = if *exit_status == 137 {
@@ -370,6 +674,55 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
     }
 
     /// Find the failed properties from this verification run
+    /// The category of this result that may make Kani exit with an error, if any.
+    pub fn failure_category(&self) -> Option<FailureCategory> {
+        match (self.status, &self.results) {
+            (VerificationStatus::Timeout, _) => Some(FailureCategory::Timeout),
+            (VerificationStatus::OutOfMemory, _) => Some(FailureCategory::OutOfMemory),
+            (VerificationStatus::Failure, Ok(properties)) => {
+                let mut failed =
+                    properties.iter().filter(|prop| prop.status == CheckStatus::Failure).peekable();
+                let unsupported = failed.peek().is_some()
+                    && failed.all(|prop| prop.property_class() == "unsupported_construct");
+                Some(if unsupported {
+                    FailureCategory::Unsupported
+                } else {
+                    FailureCategory::Failure
+                })
+            }
+            (VerificationStatus::Failure, Err(_)) => Some(FailureCategory::Failure),
+            (VerificationStatus::Success, Ok(properties)) => {
+                let cover_miss = properties.iter().any(|prop| {
+                    prop.is_cover_property()
+                        && matches!(
+                            prop.status,
+                            CheckStatus::Unsatisfiable | CheckStatus::Unreachable
+                        )
+                });
+                cover_miss.then_some(FailureCategory::CoverMiss)
+            }
+            (VerificationStatus::Success, Err(_)) => None,
+        }
+    }
+
+    /// The highest severity of the failures of this result, if any, given the severities of the
+    /// property classes set in the configuration. Failures without a severity are errors.
+    pub fn failure_severity(&self, severities: &BTreeMap<String, Severity>) -> Option<Severity> {
+        let severity = |class: &str| severities.get(class).copied().unwrap_or(Severity::Error);
+        match (self.status, &self.results) {
+            (VerificationStatus::Timeout, _) => Some(severity("timeout")),
+            (VerificationStatus::OutOfMemory, _) => Some(severity("out_of_memory")),
+            (VerificationStatus::Failure, Ok(properties)) => properties
+                .iter()
+                .filter(|prop| prop.status == CheckStatus::Failure)
+                .map(|prop| severity(&prop.property_class()))
+                .max()
+                .or(Some(Severity::Error)),
+            (VerificationStatus::Failure, Err(_)) => Some(Severity::Error),
+            (VerificationStatus::Success, _) => self.failure_category().map(|_| severity("cover")),
+        }
+    }
+
     pub fn failed_properties(&self) -> Vec<&Property> {
         if let Ok(properties) = &self.results {
             properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect()
@@ -401,7 +754,7 @@ fn verification_outcome_from_properties(
 }
 
 /// Determines the `FailedProperties` variant that corresponds to an array of properties
-fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
+pub(crate) fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
     let failed_properties: Vec<&Property> =
         properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
     // Return `FAILURE` if there isn't at least one failed property
@@ -437,6 +790,13 @@ fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageR
     // ExpressionUsed(0) $test_cov$ - src/main.rs:6:19 - 6:28
     // ```
     //
+    // With branch coverage, the description of the checks for each branch
+    // direction also includes the direction:
+    //
+    // ```
+    // CounterIncrement(1) $test_cov$ branch(true) - src/main.rs:6:8 - 6:16
+    // ```
+    //
     // The span is further processed to extract the code region attributes.
     // Ideally, we should have coverage mappings (i.e., the relation between
     // counters and code regions) available in the coverage metadata:
@@ -446,7 +806,7 @@ fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageR
         static COUNTER_RE: OnceLock<Regex> = OnceLock::new();
         COUNTER_RE.get_or_init(|| {
             Regex::new(
-                r#"^(?<kind>CounterIncrement|ExpressionUsed)\((?<counter_num>[0-9]+)\) \$(?<func_name>[^\$]+)\$ - (?<span>.+)"#,
+                r#"^(?<kind>CounterIncrement|ExpressionUsed)\((?<counter_num>[0-9]+)\) \$(?<func_name>[^\$]+)\$(?: branch\((?<branch>true|false)\))? - (?<span>.+)"#,
             )
             .unwrap()
         })
@@ -471,8 +831,9 @@ fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageR
                 _ => unreachable!("counter kind could not be recognized: {:?}", kind),
             };
             let region = CoverageRegion::from_str(span);
+            let branch = captures.name("branch").map(|direction| direction.as_str() == "true");
 
-            let cov_check = CoverageCheck::new(function, term, region, status);
+            let cov_check = CoverageCheck::new(function, term, region, branch, status);
             let file = cov_check.region.file.clone();
 
             if let Entry::Vacant(e) = coverage_results.entry(file.clone()) {
@@ -498,10 +859,131 @@ pub fn resolve_unwind_value(
     args.unwind.or(harness_metadata.attributes.unwind_value).or(args.default_unwind)
 }
 
+/// Solve the solver used for a harness. The `--solver` option takes precedence over the
+/// `#[kani::solver]` attribute.
+pub fn resolve_solver_value<'a>(
+    args: &'a VerificationArgs,
+    harness_solver: &'a Option<CbmcSolver>,
+) -> &'a CbmcSolver {
+    if let Some(solver) = &args.solver {
+        solver
+    } else if let Some(solver) = harness_solver {
+        solver
+    } else {
+        &DEFAULT_SOLVER
+    }
+}
+
+/// Solve the timeout (in seconds) of a harness. The `#[kani::timeout]` attribute takes precedence
+/// over `--default-harness-timeout`.
+pub fn resolve_timeout_value(
+    args: &VerificationArgs,
+    harness_metadata: &HarnessMetadata,
+) -> Option<u64> {
+    harness_metadata.attributes.timeout.or(args.default_harness_timeout)
+}
+
+/// Solve the memory limit (in MiB) of a harness. The `#[kani::memory_limit]` attribute takes
+/// precedence over `--memory-limit`.
+pub fn resolve_memory_limit(
+    args: &VerificationArgs,
+    harness_metadata: &HarnessMetadata,
+) -> Option<u64> {
+    harness_metadata.attributes.memory_limit.or(args.memory_limit)
+}
+
+/// The check that stops a CBMC process once the memory used by it and by the processes it
+/// spawned, e.g., an external SAT solver, exceeds `limit_mib`. The highest usage seen so far is
+/// recorded in `peak_memory`.
+fn memory_check(limit_mib: u64, peak_memory: &AtomicU64) -> impl Fn(u32) -> bool + Sync + '_ {
+    move |pid| {
+        let Some(tree) = ProcessTree::snapshot(pid) else { return false };
+        peak_memory.fetch_max(tree.rss, Ordering::Relaxed);
+        let exceeded = tree.rss > limit_mib.saturating_mul(1024 * 1024);
+        if exceeded {
+            // Killing CBMC doesn't stop the solver it spawned, so stop it first.
+            tree.kill_descendants();
+        }
+        exceeded
+    }
+}
+
+/// One row of the process table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ProcessEntry {
+    pid: u32,
+    parent: u32,
+    /// The resident set size in KiB.
+    rss_kib: u64,
+}
+
+/// A snapshot of a process and its descendants. We read it from `ps`, which is available on both
+/// Linux and macOS.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ProcessTree {
+    /// The root process followed by its descendants.
+    pids: Vec<u32>,
+    /// The total resident set size of the processes in bytes.
+    rss: u64,
+}
+
+impl ProcessTree {
+    /// Take a snapshot of the process tree rooted at `root`. Returns `None` if the process table
+    /// could not be read, or if `root` is no longer running.
+    fn snapshot(root: u32) -> Option<ProcessTree> {
+        let output = Command::new("ps").args(["-A", "-o", "pid=,ppid=,rss="]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let entries = parse_process_table(&String::from_utf8_lossy(&output.stdout));
+        ProcessTree::from_entries(root, &entries)
+    }
+
+    fn from_entries(root: u32, entries: &[ProcessEntry]) -> Option<ProcessTree> {
+        let by_pid: HashMap<u32, &ProcessEntry> =
+            entries.iter().map(|entry| (entry.pid, entry)).collect();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for entry in entries.iter().filter(|entry| entry.pid != entry.parent) {
+            children.entry(entry.parent).or_default().push(entry.pid);
+        }
+
+        let mut tree = ProcessTree::default();
+        let mut worklist = vec![by_pid.get(&root)?.pid];
+        while let Some(pid) = worklist.pop() {
+            tree.pids.push(pid);
+            tree.rss += by_pid[&pid].rss_kib * 1024;
+            worklist.extend(children.get(&pid).into_iter().flatten());
+        }
+        Some(tree)
+    }
+
+    /// Kill the descendants of the root process.
+    fn kill_descendants(&self) {
+        for pid in self.pids.iter().skip(1) {
+            // The process may have exited already, in which case there's nothing to do.
+            let _ = Command::new("kill").arg("-9").arg(pid.to_string()).output();
+        }
+    }
+}
+
+/// Parse the output of `ps -o pid=,ppid=,rss=`, ignoring malformed lines.
+fn parse_process_table(table: &str) -> Vec<ProcessEntry> {
+    table
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace().map(str::parse::<u64>);
+            let pid = columns.next()?.ok()?.try_into().ok()?;
+            let parent = columns.next()?.ok()?.try_into().ok()?;
+            let rss_kib = columns.next()?.ok()?;
+            Some(ProcessEntry { pid, parent, rss_kib })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::args;
-    use crate::metadata::tests::mock_proof_harness;
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
     use clap::Parser;
 
     use super::*;
@@ -537,4 +1019,160 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
     }
+
+    #[test]
+    fn check_resolve_timeout_value() {
+        let args_empty = ["kani", "x.rs"];
+        let args_default = ["kani", "x.rs", "--default-harness-timeout", "60"];
+
+        let harness_none = mock_proof_harness("check_one", None, None, None);
+        let mut harness_some = mock_proof_harness("check_one", None, None, None);
+        harness_some.attributes.timeout = Some(300);
+
+        fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u64> {
+            resolve_timeout_value(
+                &args::StandaloneArgs::try_parse_from(args).unwrap().verify_opts,
+                harness,
+            )
+        }
+
+        assert_eq!(resolve(&args_empty, &harness_none), None);
+        assert_eq!(resolve(&args_default, &harness_none), Some(60));
+        assert_eq!(resolve(&args_empty, &harness_some), Some(300));
+        assert_eq!(resolve(&args_default, &harness_some), Some(300));
+        assert!(
+            args::StandaloneArgs::try_parse_from([
+                "kani",
+                "x.rs",
+                "--default-harness-timeout",
+                "0"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn check_resolve_memory_limit() {
+        let args_empty = ["kani", "x.rs"];
+        let args_global = ["kani", "x.rs", "--memory-limit", "2048"];
+
+        let harness_none = mock_proof_harness("check_one", None, None, None);
+        let mut harness_some = mock_proof_harness("check_one", None, None, None);
+        harness_some.attributes.memory_limit = Some(8192);
+
+        fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u64> {
+            resolve_memory_limit(
+                &args::StandaloneArgs::try_parse_from(args).unwrap().verify_opts,
+                harness,
+            )
+        }
+
+        assert_eq!(resolve(&args_empty, &harness_none), None);
+        assert_eq!(resolve(&args_global, &harness_none), Some(2048));
+        assert_eq!(resolve(&args_empty, &harness_some), Some(8192));
+        assert_eq!(resolve(&args_global, &harness_some), Some(8192));
+        assert!(
+            args::StandaloneArgs::try_parse_from(["kani", "x.rs", "--memory-limit", "0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn check_parse_process_table() {
+        let table = "    1     0  1024\n  200     1   512\nbogus line\n  201   200     8\n";
+        assert_eq!(
+            parse_process_table(table),
+            [
+                ProcessEntry { pid: 1, parent: 0, rss_kib: 1024 },
+                ProcessEntry { pid: 200, parent: 1, rss_kib: 512 },
+                ProcessEntry { pid: 201, parent: 200, rss_kib: 8 },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_process_tree() {
+        let entries = parse_process_table("0 0 0\n1 0 1\n10 1 100\n11 10 20\n12 10 3\n13 1 4000\n");
+        let tree = ProcessTree::from_entries(10, &entries).unwrap();
+        assert_eq!(tree.pids, [10, 12, 11]);
+        assert_eq!(tree.rss, 123 * 1024);
+        assert_eq!(ProcessTree::from_entries(0, &entries).unwrap().pids.len(), 6);
+        assert_eq!(ProcessTree::from_entries(99, &entries), None);
+    }
+
+    #[test]
+    fn check_process_tree_snapshot() {
+        let tree = ProcessTree::snapshot(std::process::id()).unwrap();
+        assert_eq!(tree.pids[0], std::process::id());
+        assert!(tree.rss > 0);
+    }
+
+    fn property(class: &str, status: CheckStatus) -> Property {
+        use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+        Property {
+            description: format!("{class} check"),
+            property_id: PropertyId { fn_name: None, class: class.into(), id: 1 },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_failure_category() {
+        fn category(
+            status: VerificationStatus,
+            results: Result<Vec<Property>, i32>,
+        ) -> Option<FailureCategory> {
+            mock_verification_result(status, results).failure_category()
+        }
+
+        let unsupported = property("unsupported_construct", CheckStatus::Failure);
+        let assertion = property("assertion", CheckStatus::Failure);
+        let cover_miss = property("cover", CheckStatus::Unsatisfiable);
+        let cover_hit = property("cover", CheckStatus::Satisfied);
+        use VerificationStatus::*;
+        assert_eq!(category(Success, Ok(vec![cover_hit.clone()])), None);
+        assert_eq!(category(Success, Ok(vec![cover_miss])), Some(FailureCategory::CoverMiss));
+        assert_eq!(category(Failure, Ok(vec![assertion.clone()])), Some(FailureCategory::Failure));
+        assert_eq!(
+            category(Failure, Ok(vec![unsupported.clone()])),
+            Some(FailureCategory::Unsupported)
+        );
+        assert_eq!(
+            category(Failure, Ok(vec![unsupported, assertion])),
+            Some(FailureCategory::Failure)
+        );
+        assert_eq!(category(Failure, Err(1)), Some(FailureCategory::Failure));
+        assert_eq!(category(Timeout, Err(137)), Some(FailureCategory::Timeout));
+        assert_eq!(category(OutOfMemory, Err(137)), Some(FailureCategory::OutOfMemory));
+    }
+
+    #[test]
+    fn check_failure_severity() {
+        let severities = BTreeMap::from([
+            ("cover".to_string(), Severity::Warning),
+            ("unsupported_construct".to_string(), Severity::Info),
+            ("timeout".to_string(), Severity::Warning),
+        ]);
+        let severity = |status, results| {
+            mock_verification_result(status, results).failure_severity(&severities)
+        };
+
+        let unsupported = property("unsupported_construct", CheckStatus::Failure);
+        let assertion = property("assertion", CheckStatus::Failure);
+        let cover_miss = property("cover", CheckStatus::Unsatisfiable);
+        use VerificationStatus::*;
+        assert_eq!(severity(Success, Ok(vec![assertion.clone()])), None);
+        assert_eq!(severity(Success, Ok(vec![cover_miss])), Some(Severity::Warning));
+        assert_eq!(severity(Failure, Ok(vec![unsupported.clone()])), Some(Severity::Info));
+        assert_eq!(severity(Failure, Ok(vec![unsupported, assertion])), Some(Severity::Error));
+        assert_eq!(severity(Timeout, Err(137)), Some(Severity::Warning));
+        assert_eq!(severity(OutOfMemory, Err(137)), Some(Severity::Error));
+    }
 }
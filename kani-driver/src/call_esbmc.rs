@@ -0,0 +1,216 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `--engine esbmc`, which verifies the goto binary of each harness with
+//! [ESBMC](https://esbmc.org) instead of CBMC.
+//!
+//! ESBMC reads goto binaries with `--binary` and stops at the first violated property, which it
+//! reports in plain text. We parse that report into the properties of a [VerificationResult], so
+//! the results are printed like the ones of CBMC.
+
+use anyhow::{bail, Result};
+use kani_metadata::HarnessMetadata;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+use crate::call_cbmc::{
+    resolve_timeout_value, resolve_unwind_value, FailedProperties, VerificationResult,
+    VerificationStatus,
+};
+use crate::cbmc_output_parser::{CheckStatus, Property, PropertyId, SourceLocation};
+use crate::session::KaniSession;
+use crate::util::alter_extension;
+
+/// The property class of the violations reported by ESBMC, which doesn't tell which Kani check
+/// failed.
+const ESBMC_PROPERTY_CLASS: &str = "esbmc";
+
+/// The verdict at the end of ESBMC's output.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Successful,
+    Failed,
+    Unknown,
+    TimedOut,
+}
+
+impl KaniSession {
+    /// Verify a goto binary with ESBMC.
+    pub fn run_esbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
+        let output_file = alter_extension(file, "esbmc.out");
+        self.record_temporary_file(&output_file);
+
+        let mut cmd = Command::new("esbmc");
+        cmd.args(self.esbmc_flags(file, harness));
+        let start_time = Instant::now();
+        let exit_status = self.run_redirect(cmd, &output_file)?;
+        let output = std::fs::read_to_string(&output_file)?;
+        let runtime = start_time.elapsed();
+
+        let Some(verdict) = parse_verdict(&output) else {
+            bail!("esbmc didn't report a verification result (exit status {exit_status})");
+        };
+        let mut result = VerificationResult { runtime, ..VerificationResult::mock_success() };
+        match verdict {
+            Verdict::Successful => {}
+            Verdict::Failed => {
+                result.status = VerificationStatus::Failure;
+                result.failed_properties = FailedProperties::Other;
+                result.results = Ok(parse_violated_properties(&output));
+            }
+            Verdict::Unknown => {
+                result.status = VerificationStatus::Failure;
+                result.failed_properties = FailedProperties::Other;
+                result.results = Ok(vec![undetermined_property()]);
+            }
+            Verdict::TimedOut => {
+                result.status = VerificationStatus::Timeout;
+                result.results = Err(exit_status.code().unwrap_or(1));
+            }
+        }
+        Ok(result)
+    }
+
+    /// The flags of ESBMC, which mirror the ones that Kani passes to CBMC when ESBMC supports
+    /// them. ESBMC checks unwinding assertions by default.
+    fn esbmc_flags(&self, file: &Path, harness: &HarnessMetadata) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![file.into(), "--binary".into()];
+        if let Some(unwind_value) = resolve_unwind_value(&self.args, harness) {
+            args.push("--unwind".into());
+            args.push(unwind_value.to_string().into());
+        }
+        if harness.attributes.check_leaks {
+            args.push("--memory-leak-check".into());
+        }
+        if let Some(timeout) = resolve_timeout_value(&self.args, harness) {
+            args.push("--timeout".into());
+            args.push(format!("{timeout}s").into());
+        }
+        args.extend(self.args.esbmc_args.iter().cloned());
+        args
+    }
+}
+
+fn parse_verdict(output: &str) -> Option<Verdict> {
+    output.lines().rev().find_map(|line| match line.trim() {
+        "VERIFICATION SUCCESSFUL" => Some(Verdict::Successful),
+        "VERIFICATION FAILED" => Some(Verdict::Failed),
+        "VERIFICATION UNKNOWN" => Some(Verdict::Unknown),
+        "Timed out" => Some(Verdict::TimedOut),
+        _ => None,
+    })
+}
+
+/// Parse the `Violated property:` blocks of ESBMC's output, e.g.:
+///
+/// ```text
+/// Violated property:
+///   file src/lib.rs line 7 column 5 function check_add
+///   attempt to add with overflow
+///   !overflow("+", x, y)
+/// ```
+fn parse_violated_properties(output: &str) -> Vec<Property> {
+    let mut properties = vec![];
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() != "Violated property:" {
+            continue;
+        }
+        let source_location = lines.next().map(parse_location).unwrap_or_else(no_location);
+        let description = lines.next().unwrap_or_default().trim().to_string();
+        properties.push(Property {
+            description,
+            property_id: PropertyId {
+                fn_name: source_location.function.clone(),
+                class: ESBMC_PROPERTY_CLASS.to_string(),
+                id: properties.len() as u32 + 1,
+            },
+            source_location,
+            status: CheckStatus::Failure,
+            reach: None,
+            trace: None,
+        });
+    }
+    properties
+}
+
+/// Parse a location of the form `file <file> line <line> column <column> function <function>`,
+/// in which every part is optional.
+fn parse_location(line: &str) -> SourceLocation {
+    let mut location = no_location();
+    let mut words = line.split_whitespace();
+    while let (Some(key), Some(value)) = (words.next(), words.next()) {
+        let value = Some(value.to_string());
+        match key {
+            "file" => location.file = value,
+            "line" => location.line = value,
+            "column" => location.column = value,
+            "function" => location.function = value,
+            _ => {}
+        }
+    }
+    location
+}
+
+fn no_location() -> SourceLocation {
+    SourceLocation { column: None, file: None, function: None, line: None }
+}
+
+/// A property for `VERIFICATION UNKNOWN`, which ESBMC reports when k-induction can neither prove
+/// nor refute the properties.
+fn undetermined_property() -> Property {
+    Property {
+        description: "ESBMC could not determine whether the properties hold".to_string(),
+        property_id: PropertyId { fn_name: None, class: ESBMC_PROPERTY_CLASS.to_string(), id: 1 },
+        source_location: no_location(),
+        status: CheckStatus::Undetermined,
+        reach: None,
+        trace: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAILED_OUTPUT: &str = "\
+ESBMC version 7.6.1 64-bit x86_64 linux
+Parsing harness.out
+Starting Bounded Model Checking
+[Counterexample]
+
+State 1 file src/lib.rs line 6 column 9 function check_add thread 0
+----------------------------------------------------
+  x = 255
+
+Violated property:
+  file src/lib.rs line 7 column 5 function check_add
+  attempt to add with overflow
+  !overflow(\"+\", x, 1)
+
+
+VERIFICATION FAILED
+";
+
+    #[test]
+    fn check_parse_failure() {
+        assert_eq!(parse_verdict(FAILED_OUTPUT), Some(Verdict::Failed));
+        let properties = parse_violated_properties(FAILED_OUTPUT);
+        assert_eq!(properties.len(), 1);
+        assert_eq!(properties[0].description, "attempt to add with overflow");
+        assert_eq!(properties[0].status, CheckStatus::Failure);
+        assert_eq!(properties[0].source_location.file.as_deref(), Some("src/lib.rs"));
+        assert_eq!(properties[0].source_location.line.as_deref(), Some("7"));
+        assert_eq!(properties[0].source_location.function.as_deref(), Some("check_add"));
+    }
+
+    #[test]
+    fn check_parse_verdicts() {
+        assert_eq!(parse_verdict("...\nVERIFICATION SUCCESSFUL\n"), Some(Verdict::Successful));
+        assert_eq!(parse_verdict("VERIFICATION UNKNOWN\n"), Some(Verdict::Unknown));
+        assert_eq!(parse_verdict("Timed out\n"), Some(Verdict::TimedOut));
+        assert_eq!(parse_verdict("Segmentation fault\n"), None);
+    }
+}
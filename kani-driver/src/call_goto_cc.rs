@@ -6,6 +6,7 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::artifact_cache::{CacheKey, CachedStep};
 use crate::session::KaniSession;
 
 impl KaniSession {
@@ -32,6 +33,15 @@ pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()>
         Ok(())
     }
 
+    /// The key of the goto binary linked from `inputs` in the artifact cache.
+    pub fn link_cache_key(&self, inputs: &[PathBuf]) -> Result<CacheKey> {
+        let mut key = CacheKey::new(CachedStep::Link);
+        for input in inputs.iter().chain(&self.args.c_lib).chain([&self.kani_lib_c]) {
+            key.add_file(input)?;
+        }
+        Ok(key)
+    }
+
     /// Produce a goto binary with its entry point set to a particular proof harness.
     pub fn specialize_to_proof_harness(
         &self,
@@ -1,13 +1,15 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeSet;
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 use std::process::Command;
 
+use crate::artifact_cache::{CacheKey, CachedStep};
 use crate::metadata::collect_and_link_function_pointer_restrictions;
 use crate::project::Project;
 use crate::session::KaniSession;
@@ -22,6 +24,47 @@ pub fn instrument_model(
         output: &Path,
         project: &Project,
         harness: &HarnessMetadata,
+    ) -> Result<()> {
+        // The C files generated by `--gen-c` are a side output that isn't cached.
+        if self.args.gen_c {
+            return self.instrument_model_uncached(input, output, project, harness);
+        }
+        self.cached_step(
+            &project.outdir,
+            CachedStep::Instrument,
+            || self.instrument_cache_key(input, project, harness),
+            output,
+            || self.instrument_model_uncached(input, output, project, harness),
+        )
+    }
+
+    /// The key of the instrumented goto binary of a harness in the artifact cache.
+    fn instrument_cache_key(
+        &self,
+        input: &Path,
+        project: &Project,
+        harness: &HarnessMetadata,
+    ) -> Result<CacheKey> {
+        let mut key = CacheKey::new(CachedStep::Instrument);
+        key.add_file(input)?
+            .add(&harness.mangled_name)
+            .add(format!("{:?}", harness.contract))
+            .add(self.args.checks.undefined_function_on())
+            .add(self.args.run_sanity_checks);
+        if let Some(restrictions) =
+            project.get_harness_artifact(harness, ArtifactType::VTableRestriction)
+        {
+            key.add_file(restrictions)?;
+        }
+        Ok(key)
+    }
+
+    fn instrument_model_uncached(
+        &self,
+        input: &Path,
+        output: &Path,
+        project: &Project,
+        harness: &HarnessMetadata,
     ) -> Result<()> {
         // We actually start by calling goto-cc to start the specialization:
         self.specialize_to_proof_harness(input, output, &harness.mangled_name)?;
@@ -152,6 +195,50 @@ fn goto_sanity_check(&self, file: &Path) -> Result<()> {
         self.call_goto_instrument(args)
     }
 
+    /// Apply the goto-instrument slicing passes enabled for the harness, with
+    /// `--constant-propagation` and `--full-slice` or with `#[kani::slicing]`, and return the
+    /// properties that they removed from the goto binary.
+    pub fn slice_model(&self, file: &Path, harness: &HarnessMetadata) -> Result<Vec<String>> {
+        let slicing = &harness.attributes.slicing;
+        let mut passes: Vec<&str> = vec![];
+        if slicing.constant_propagation.unwrap_or(self.args.constant_propagation) {
+            passes.push("--constant-propagator");
+        }
+        if slicing.full.unwrap_or(self.args.full_slice) {
+            passes.push("--full-slice");
+        }
+        if passes.is_empty() {
+            return Ok(vec![]);
+        }
+        let before = self.goto_properties(file)?;
+        for pass in passes {
+            self.call_goto_instrument([OsStr::new(pass), file.as_os_str(), file.as_os_str()])?;
+        }
+        let after = self.goto_properties(file)?;
+        Ok(before.difference(&after).cloned().collect())
+    }
+
+    /// The names of the properties of a goto binary, as listed by `--show-properties`.
+    fn goto_properties(&self, file: &Path) -> Result<BTreeSet<String>> {
+        let output = Command::new("goto-instrument")
+            .args([OsStr::new("--show-properties"), OsStr::new("--json-ui"), file.as_os_str()])
+            .output()
+            .context("Failed to run `goto-instrument`")?;
+        if !output.status.success() {
+            bail!("`goto-instrument --show-properties` failed on `{}`", file.display());
+        }
+        let messages: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        let properties = messages
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|message| message["properties"].as_array())
+            .flatten()
+            .filter_map(|property| property["name"].as_str().map(String::from))
+            .collect();
+        Ok(properties)
+    }
+
     /// Generate a .c file from a goto binary (i.e. --gen-c)
     pub fn gen_c(&self, file: &Path, output_file: &Path) -> Result<()> {
         let args: Vec<OsString> = vec![
@@ -40,12 +40,13 @@ pub fn synthesize_loop_contracts(
         // 1. solver options
         self.handle_solver_args(&harness_metadata.attributes.solver, &mut args)?;
         // 2. object-bits option
-        if let Some(object_bits) = self.args.cbmc_object_bits() {
+        if let Some(object_bits) = self.cbmc_object_bits(harness_metadata) {
             args.push("--object-bits".into());
             args.push(object_bits.to_string().into());
         }
         // 3. and array-as-uninterpreted-functions options, which should be included
         //    in the cbmc_args.
+        args.extend(harness_metadata.attributes.cbmc_args.iter().map(OsString::from));
         args.extend(self.args.cbmc_args.iter().cloned());
 
         let mut cmd = Command::new("goto-synthesizer");
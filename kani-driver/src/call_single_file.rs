@@ -1,13 +1,14 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use kani_metadata::UnstableFeature;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::session::{lib_folder, KaniSession};
+use crate::args::{Backend, FloatMath, InlineAsm, PanicStrategy, UnknownIntrinsics};
+use crate::session::{lib_folder_for, KaniSession};
 
 pub struct LibConfig {
     args: Vec<OsString>,
@@ -33,6 +34,22 @@ pub fn new(path: PathBuf) -> LibConfig {
         LibConfig { args }
     }
 
+    /// The libraries of a `no_std` crate, which doesn't link Kani's version of `std`.
+    pub fn new_no_std(path: PathBuf) -> LibConfig {
+        let sysroot = &path.parent().unwrap();
+        let args = [
+            "--sysroot",
+            sysroot.to_str().unwrap(),
+            "-L",
+            path.to_str().unwrap(),
+            "--extern",
+            "kani",
+        ]
+        .map(OsString::from)
+        .to_vec();
+        LibConfig { args }
+    }
+
     pub fn new_no_core(path: PathBuf) -> LibConfig {
         LibConfig {
             args: ["-L", path.to_str().unwrap(), "--extern", "kani_core"]
@@ -54,8 +71,12 @@ pub fn compile_single_rust_file(
         let mut kani_args = self.kani_compiler_flags();
         kani_args.push(format!("--reachability={}", self.reachability_mode()));
 
-        let lib_path = lib_folder().unwrap();
-        let mut rustc_args = self.kani_rustc_flags(LibConfig::new(lib_path));
+        let lib_path = lib_folder_for(self.target_name())?;
+        let mut rustc_args = self.kani_rustc_flags(self.lib_config(lib_path)?);
+        if let Some(target) = &self.args.target_triple {
+            rustc_args.push("--target".into());
+            rustc_args.push(target.into());
+        }
         rustc_args.push(file.into());
         rustc_args.push("--out-dir".into());
         rustc_args.push(OsString::from(outdir.as_os_str()));
@@ -111,12 +132,47 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
         if self.args.restrict_vtable() {
             flags.push("--restrict-vtable-fn-ptrs".into());
         }
+        if self.args.restrict_fn_ptrs {
+            flags.push("--restrict-fn-ptrs".into());
+        }
         if self.args.assertion_reach_checks() {
             flags.push("--assertion-reach-checks".into());
         }
         if self.args.ignore_global_asm {
             flags.push("--ignore-global-asm".into());
         }
+        if self.args.inline_asm == InlineAsm::Havoc {
+            flags.push("--inline-asm=havoc".into());
+        }
+        match self.args.unknown_intrinsics {
+            UnknownIntrinsics::Error => flags.push("--unknown-intrinsics=error".into()),
+            UnknownIntrinsics::Unreachable => {}
+            UnknownIntrinsics::Havoc => flags.push("--unknown-intrinsics=havoc".into()),
+        }
+        if self.args.backend == Backend::Why3 {
+            flags.push("--backend=why3".into());
+        }
+        if self.args.panic_strategy == PanicStrategy::Unwind {
+            flags.push("--panic-strategy=unwind".into());
+        }
+        if self.args.float_math == FloatMath::Model {
+            flags.push("--float-math=model".into());
+        }
+        if self.args.mir_opt_level > 0 {
+            flags.push(format!("--mir-opt-level={}", self.args.mir_opt_level));
+        }
+        if self.args.shared_model {
+            flags.push("--shared-model".into());
+        }
+        if self.args.reachability_report {
+            flags.push("--reachability-report".into());
+        }
+        if self.args.unsafe_report {
+            flags.push("--unsafe-report".into());
+        }
+        if self.record_public_functions {
+            flags.push("--public-functions".into());
+        }
 
         // Users activate it via the command line switch
         if self.args.write_json_symtab {
@@ -131,6 +187,10 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--coverage-checks".into());
         }
 
+        if self.args.searches_counterexamples() {
+            flags.push("--record-nondet".into());
+        }
+
         if self.args.common_args.unstable_features.contains(UnstableFeature::ValidValueChecks) {
             flags.push("--ub-check=validity".into())
         }
@@ -142,6 +202,28 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--ub-check=uninit".into());
         }
 
+        if self.args.common_args.unstable_features.contains(UnstableFeature::UnionChecks) {
+            flags.push("--ub-check=union".into());
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::ProvenanceChecks) {
+            flags.push("--ub-check=provenance".into());
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::AliasingChecks) {
+            flags.push("--ub-check=aliasing".into());
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::TaintChecks) {
+            flags.push("--ub-check=taint".into());
+        }
+
+        if self.args.check_constant_time {
+            // The constant-time checks track the taint of the values marked with `kani::secret`.
+            flags.push("-Z taint-checks".into());
+            flags.push("--ub-check=constant_time".into());
+        }
+
         flags.extend(self.args.common_args.unstable_features.as_arguments().map(str::to_string));
 
         // This argument will select the Kani flavour of the compiler. It will be removed before
@@ -152,18 +234,40 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
     }
 
     /// This function generates all rustc configurations required by our goto-c codegen.
+    /// The libraries to compile the code under verification with.
+    pub fn lib_config(&self, lib_path: PathBuf) -> Result<LibConfig> {
+        if self.args.no_std {
+            Ok(LibConfig::new_no_std(lib_path))
+        } else if !lib_path.join("libstd.rlib").exists() {
+            bail!(
+                "The Kani libraries for target `{}` don't include `std`. Use `--no-std` to verify \
+                `no_std` crates for this target.",
+                self.target_name()
+            )
+        } else {
+            Ok(LibConfig::new(lib_path))
+        }
+    }
+
     pub fn kani_rustc_flags(&self, lib_config: LibConfig) -> Vec<OsString> {
         let mut flags: Vec<_> = base_rustc_flags(lib_config);
-        // We only use panic abort strategy for verification since we cannot handle unwind logic.
         if self.args.coverage {
             flags.extend_from_slice(
                 &["-C", "instrument-coverage", "-Z", "no-profiler-runtime"].map(OsString::from),
             );
+            if self.args.branch_coverage {
+                flags.extend_from_slice(&["-Z", "coverage-options=branch"].map(OsString::from));
+            }
         }
+        // Panics abort unless `--panic-strategy=unwind` is used.
+        let panic = match self.args.panic_strategy {
+            PanicStrategy::Abort => "panic=abort",
+            PanicStrategy::Unwind => "panic=unwind",
+        };
         flags.extend_from_slice(
             &[
                 "-C",
-                "panic=abort",
+                panic,
                 "-C",
                 "symbol-mangling-version=v0",
                 "-Z",
@@ -0,0 +1,112 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `--backend smt2`, which converts the goto program of each harness into a
+//! self-contained SMT-LIB2 script, and optionally checks it with an SMT solver.
+
+use anyhow::{bail, Result};
+use kani_metadata::{CbmcSolver, HarnessMetadata};
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+use crate::args::SmtSolver;
+use crate::call_cbmc::{FailedProperties, VerificationResult, VerificationStatus};
+use crate::session::KaniSession;
+use crate::util::alter_extension;
+
+impl KaniSession {
+    /// Write the SMT-LIB2 script of a harness next to its goto binary, and check it with the
+    /// solver selected with `--smt-solver`, if any.
+    ///
+    /// CBMC generates the script in the `QF_AUFBV` logic: it asserts that one of the properties of
+    /// the harness fails, so it is unsatisfiable if and only if the harness is verified. Without a
+    /// solver, Kani reports success once the script is written.
+    pub fn run_smt2(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
+        let start = Instant::now();
+        let script = alter_extension(file, "smt2");
+        let log = alter_extension(file, "smt2.log");
+        self.record_temporary_file(&log);
+
+        // The solver flags would select a SAT solver, so use the one that doesn't add any.
+        let mut args = self.cbmc_flags_with_solver(file, harness, &CbmcSolver::Minisat)?;
+        args.push("--smt2".into());
+        args.push("--outfile".into());
+        args.push(script.clone().into());
+        self.call_cbmc(args, &log)?;
+        if !self.args.common_args.quiet {
+            println!("SMT-LIB2 script written to: {}", script.display());
+        }
+
+        let Some(solver) = self.args.smt_solver else {
+            return Ok(VerificationResult {
+                runtime: start.elapsed(),
+                ..VerificationResult::mock_success()
+            });
+        };
+        let answer = self.call_smt_solver(solver, &script)?;
+        let status = match answer.as_str() {
+            "unsat" => VerificationStatus::Success,
+            "sat" | "unknown" => VerificationStatus::Failure,
+            _ => bail!("unexpected answer from {}: `{answer}`", solver_binary(solver)),
+        };
+        if !self.args.common_args.quiet {
+            let verdict = match status {
+                VerificationStatus::Success => console::style("SUCCESSFUL").green(),
+                _ => console::style("FAILED").red(),
+            };
+            println!(
+                "{} answered `{answer}`{}\nVERIFICATION:- {verdict}",
+                solver_binary(solver),
+                if answer == "sat" { ", so at least one property can fail" } else { "" }
+            );
+        }
+        Ok(VerificationResult {
+            status,
+            failed_properties: if status == VerificationStatus::Success {
+                FailedProperties::None
+            } else {
+                FailedProperties::Other
+            },
+            // The solver only tells whether some property fails, not which one.
+            results: Ok(vec![]),
+            runtime: start.elapsed(),
+            ..VerificationResult::mock_success()
+        })
+    }
+
+    /// Run the solver on the script and return its answer to the first `(check-sat)`, i.e.,
+    /// `sat`, `unsat` or `unknown`.
+    fn call_smt_solver(&self, solver: SmtSolver, script: &Path) -> Result<String> {
+        let output = alter_extension(script, "smt2.out");
+        self.record_temporary_file(&output);
+
+        let mut cmd = Command::new(solver_binary(solver));
+        cmd.args(solver_flags(solver));
+        cmd.arg(script);
+        // CBMC's script asks for the values of the symbols after `(check-sat)`, which solvers
+        // reject if the script is unsatisfiable, so the exit status doesn't tell the answer.
+        let _status = self.run_redirect(cmd, &output)?;
+        let answer = std::fs::read_to_string(&output)?;
+        Ok(answer.lines().next().unwrap_or_default().trim().to_string())
+    }
+}
+
+fn solver_binary(solver: SmtSolver) -> &'static str {
+    match solver {
+        SmtSolver::Z3 => "z3",
+        SmtSolver::Cvc5 => "cvc5",
+        SmtSolver::Bitwuzla => "bitwuzla",
+    }
+}
+
+/// The flags to parse the script as SMT-LIB2 and to enable models, which the script asks for.
+fn solver_flags(solver: SmtSolver) -> Vec<OsString> {
+    let flags: &[&str] = match solver {
+        SmtSolver::Z3 => &["-smt2", "model=true"],
+        SmtSolver::Cvc5 => &["--lang=smt2", "--produce-models"],
+        SmtSolver::Bitwuzla => &["--lang", "smt2", "--produce-models"],
+    };
+    flags.iter().map(OsString::from).collect()
+}
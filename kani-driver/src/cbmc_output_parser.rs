@@ -34,6 +34,12 @@
 use std::os::unix::process::ExitStatusExt;
 use std::path::PathBuf;
 use std::process::{Child, ChildStdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::warn;
 
 const RESULT_ITEM_PREFIX: &str = "  {\n    \"result\":";
 
@@ -97,6 +103,7 @@ pub struct PropertyId {
 impl Property {
     const COVER_PROPERTY_CLASS: &'static str = "cover";
     const COVERAGE_PROPERTY_CLASS: &'static str = "code_coverage";
+    const ASSUMPTION_CHECK_PROPERTY_CLASS: &'static str = "assumption_check";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -112,6 +119,11 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this is a check of the satisfiability of an assumption
+    pub fn is_assumption_check_property(&self) -> bool {
+        self.property_id.class == Self::ASSUMPTION_CHECK_PROPERTY_CLASS
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
@@ -216,7 +228,7 @@ fn deserialize<D>(d: D) -> Result<Self, D::Error>
 ///
 /// Source locations may be completely empty, which is why
 /// all members are optional.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SourceLocation {
     pub column: Option<String>,
     pub file: Option<String>,
@@ -268,7 +280,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 }
 
 /// Returns a path relative to the current working directory.
-fn filepath(file: String) -> String {
+pub fn filepath(file: String) -> String {
     let file_path = PathBuf::from(file.clone());
     let cur_dir = env::current_dir().unwrap();
 
@@ -512,8 +524,33 @@ fn next(&mut self) -> Option<Self::Item> {
 pub struct VerificationOutput {
     pub process_status: i32,
     pub processed_items: Vec<ParserItem>,
+    /// Whether the process was killed because it exceeded its timeout.
+    pub timed_out: bool,
+    /// Whether the process was killed because it exceeded its memory limit.
+    pub out_of_memory: bool,
+}
+
+/// The limits enforced while a CBMC process runs. The process is killed once any of them is
+/// exceeded.
+#[derive(Default)]
+pub struct ProcessLimits<'a> {
+    /// The maximum running time of the process.
+    pub timeout: Option<Duration>,
+    /// Check of the memory used by the process, called periodically with its process id.
+    /// It returns whether the process exceeded its memory limit, and is responsible for
+    /// stopping the processes spawned by the process, if any.
+    pub memory_check: Option<&'a (dyn Fn(u32) -> bool + Sync)>,
+    /// Flag used to stop the process, e.g., to stop the other solvers of a portfolio once one of
+    /// them is done.
+    pub cancel: Option<&'a AtomicBool>,
 }
 
+/// How often the watchdog checks whether the process was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often the watchdog measures the memory used by the process.
+const MEMORY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 /// The main function to process CBMC's output.
 ///
 /// This streams CBMC's output to be processed item-by-item with `eager_filter`.
@@ -521,18 +558,72 @@ pub struct VerificationOutput {
 /// In general, a filter will pre-process an item (this may or may not transform the item),
 /// then formatted (according to the output format) and print.
 ///
+/// The process is killed once it exceeds any of the `limits`, and the output is marked as timed
+/// out or out of memory accordingly. If the process leads its own process group, the whole group
+/// is killed, so the processes it spawned are stopped as well.
+///
 /// The cbmc process status is returned, along with the (post-filter) items.
 pub fn process_cbmc_output(
     mut process: Child,
+    limits: ProcessLimits,
     eager_filter: impl FnMut(ParserItem) -> Option<ParserItem>,
 ) -> Result<VerificationOutput> {
-    let stdout = process.stdout.as_mut().unwrap();
-    let mut stdout_reader = BufReader::new(stdout);
-    let parser = Parser::new(&mut stdout_reader);
-    // This should run until stdout is closed (which should mean the process exited)
-    let processed_items: Vec<_> = parser.filter_map(eager_filter).collect();
+    let mut stdout = process.stdout.take().unwrap();
+    let pid = process.id();
+    let process = Mutex::new(process);
+    let timed_out = AtomicBool::new(false);
+    let out_of_memory = AtomicBool::new(false);
+    let processed_items: Vec<_> = thread::scope(|scope| {
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let ProcessLimits { timeout, memory_check, cancel } = limits;
+        if timeout.is_some() || memory_check.is_some() || cancel.is_some() {
+            let (process, timed_out, out_of_memory) = (&process, &timed_out, &out_of_memory);
+            let deadline = timeout.map(|timeout| Instant::now() + timeout);
+            // Watchdog that kills the process unless the output is fully read before the timeout,
+            // before the process exceeds its memory limit, or before the process is cancelled.
+            scope.spawn(move || {
+                loop {
+                    let mut wait = deadline.map_or(Duration::MAX, |deadline| {
+                        deadline.saturating_duration_since(Instant::now())
+                    });
+                    if cancel.is_some() {
+                        wait = wait.min(CANCEL_POLL_INTERVAL);
+                    }
+                    if memory_check.is_some() {
+                        wait = wait.min(MEMORY_POLL_INTERVAL);
+                    }
+                    if !matches!(done_rx.recv_timeout(wait), Err(RecvTimeoutError::Timeout)) {
+                        break;
+                    }
+                    let expired = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+                    let exceeded = !expired && memory_check.is_some_and(|check| check(pid));
+                    if expired {
+                        timed_out.store(true, Ordering::Relaxed);
+                    } else if exceeded {
+                        out_of_memory.store(true, Ordering::Relaxed);
+                    }
+                    if expired
+                        || exceeded
+                        || cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+                    {
+                        kill_process_group(pid);
+                        // The process may have exited already, in which case there's nothing to do.
+                        let _ = process.lock().unwrap().kill();
+                        break;
+                    }
+                }
+            });
+        }
+        let mut stdout_reader = BufReader::new(&mut stdout);
+        let parser = Parser::new(&mut stdout_reader);
+        // This should run until stdout is closed (which should mean the process exited)
+        let items = parser.filter_map(eager_filter).collect();
+        // Wake up the watchdog, if any.
+        drop(done_tx);
+        items
+    });
     // This will get us the process's exit code
-    let status = process.wait()?;
+    let status = process.into_inner().unwrap().wait()?;
 
     let process_status = match (status.code(), status.signal()) {
         // normal unix exit codes (cbmc uses currently 0-10)
@@ -546,7 +637,24 @@ pub fn process_cbmc_output(
         (None, None) => unreachable!("Process exited with neither status code nor signal?"),
     };
 
-    Ok(VerificationOutput { process_status, processed_items })
+    Ok(VerificationOutput {
+        process_status,
+        processed_items,
+        timed_out: timed_out.into_inner(),
+        out_of_memory: out_of_memory.into_inner(),
+    })
+}
+
+/// Kill the process group led by `pid`. There is no such group if the process doesn't lead its
+/// own group, or if the group already exited, which isn't an error.
+fn kill_process_group(pid: u32) {
+    // SAFETY: `killpg` only sends a signal, and has no memory-safety requirements.
+    if unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) } != 0 {
+        let error = std::io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::ESRCH) {
+            warn!(?error, pid, "Failed to kill the process group");
+        }
+    }
 }
 
 /// Takes (by ownership) a vector of messages, and returns that vector with the `Result`
@@ -765,4 +873,83 @@ fn check_result_deserialization_works() {
         assert!(parser_item.is_ok());
         assert!(result_struct.is_ok());
     }
+
+    fn timeout(timeout: Duration) -> ProcessLimits<'static> {
+        ProcessLimits { timeout: Some(timeout), ..Default::default() }
+    }
+
+    #[test]
+    fn check_process_timeout() {
+        let child = std::process::Command::new("sleep")
+            .arg("10")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let output = process_cbmc_output(child, timeout(Duration::from_millis(100)), Some).unwrap();
+        assert!(output.timed_out);
+        assert!(output.processed_items.is_empty());
+        // SIGKILL
+        assert_eq!(output.process_status, 137);
+
+        let child = std::process::Command::new("true")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let output = process_cbmc_output(child, timeout(Duration::from_secs(10)), Some).unwrap();
+        assert!(!output.timed_out);
+        assert_eq!(output.process_status, 0);
+    }
+
+    #[test]
+    fn check_process_group_timeout() {
+        use std::os::unix::process::CommandExt;
+        // The background `sleep` keeps stdout open, so the output is only fully read once the
+        // whole process group was killed.
+        let child = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & wait"])
+            .stdout(std::process::Stdio::piped())
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let start = Instant::now();
+        let output = process_cbmc_output(child, timeout(Duration::from_millis(100)), Some).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(output.timed_out);
+        assert_eq!(output.process_status, 137);
+    }
+
+    #[test]
+    fn check_process_cancel() {
+        let child = std::process::Command::new("sleep")
+            .arg("10")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let cancel = AtomicBool::new(true);
+        let start = Instant::now();
+        let limits = ProcessLimits { cancel: Some(&cancel), ..Default::default() };
+        let output = process_cbmc_output(child, limits, Some).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!output.timed_out);
+        assert_eq!(output.process_status, 137);
+    }
+
+    #[test]
+    fn check_process_memory_limit() {
+        let child = std::process::Command::new("sleep")
+            .arg("10")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let check = |checked| {
+            assert_eq!(checked, pid);
+            true
+        };
+        let limits = ProcessLimits { memory_check: Some(&check), ..Default::default() };
+        let output = process_cbmc_output(child, limits, Some).unwrap();
+        assert!(output.out_of_memory);
+        assert!(!output.timed_out);
+        assert_eq!(output.process_status, 137);
+    }
 }
@@ -4,12 +4,14 @@
 use crate::args::OutputFormat;
 use crate::call_cbmc::{FailedProperties, VerificationStatus};
 use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::concrete_playback::test_generator::extract_trace_values;
 use crate::coverage::cov_results::CoverageResults;
 use console::style;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rustc_demangle::demangle;
 use std::collections::HashMap;
+use std::fmt::Write;
 
 type CbmcAltDescriptions = HashMap<&'static str, Vec<(&'static str, Option<&'static str>)>>;
 
@@ -214,7 +216,7 @@ fn format_item(item: &ParserItem, output_format: &OutputFormat) -> Option<String
     match output_format {
         OutputFormat::Old => todo!(),
         OutputFormat::Regular => format_item_regular(item),
-        OutputFormat::Terse => format_item_terse(item),
+        OutputFormat::Terse | OutputFormat::Junit | OutputFormat::Github => format_item_terse(item),
     }
 }
 
@@ -442,8 +444,23 @@ pub fn format_coverage(
     let verification_output =
         format_result(&non_coverage_checks, status, should_panic, failed_properties, show_checks);
     let cov_results_intro = "Source-based code coverage results:";
-    let result = format!("{}\n{}\n\n{}", verification_output, cov_results_intro, cov_results);
-
+    let mut result = format!("{}\n{}\n\n{}", verification_output, cov_results_intro, cov_results);
+
+    let untaken_branches = cov_results.untaken_branches();
+    if !untaken_branches.is_empty() {
+        writeln!(result, "Branch directions never taken:").unwrap();
+        for check in untaken_branches {
+            let region = &check.region;
+            let direction = check.branch.unwrap();
+            writeln!(
+                result,
+                " - {}:{}:{} ({direction} branch) in {}",
+                region.file, region.start.0, region.start.1, check.function
+            )
+            .unwrap();
+        }
+        writeln!(result).unwrap();
+    }
     result
 }
 
@@ -469,8 +486,15 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
         let failure_file = failure_source.file.unwrap();
         let failure_function = failure_source.function.unwrap();
         let failure_line = failure_source.line.unwrap();
+        let values = extract_trace_values(&failure_trace);
+        let values_message = if values.is_empty() {
+            String::new()
+        } else {
+            let values: Vec<_> = values.iter().map(|val| val.interp_val.as_str()).collect();
+            format!(" Nondeterministic values: [{}]\n", values.join(", "))
+        };
         return format!(
-            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}\n"
+            "Failed Checks: {description}\n File: \"{failure_file}\", line {failure_line}, in {failure_function}\n{values_message}"
         );
     }
     backup_failure_message
@@ -562,6 +586,92 @@ fn has_unwinding_assertion_failures(properties: &Vec<Property>) -> bool {
         || has_check_failure(&properties, UNWINDING_ASSERT_REC_DESC)
 }
 
+/// Determines if none of the assertions in a set of properties is reachable, i.e., if their
+/// success is vacuous. Harnesses with cover properties are meant to check reachability instead,
+/// so they are never considered vacuous.
+pub fn has_no_reachable_assertions(properties: &[Property]) -> bool {
+    properties.iter().all(|prop| {
+        !prop.is_cover_property()
+            && !prop.is_code_coverage_property()
+            && prop.status == CheckStatus::Unreachable
+    })
+}
+
+/// Determines if the only failures in a set of properties are unwinding assertion failures.
+pub fn has_only_unwinding_assertion_failures(properties: &[Property]) -> bool {
+    let mut failures =
+        properties.iter().filter(|prop| prop.status == CheckStatus::Failure).peekable();
+    failures.peek().is_some()
+        && failures.all(|prop| {
+            prop.description.contains(UNWINDING_ASSERT_DESC)
+                || prop.description.contains(UNWINDING_ASSERT_REC_DESC)
+        })
+}
+
+/// The ids of the loops whose unwinding assertion failed, in the format used by CBMC's
+/// `--show-loops`, e.g., `foo.0`.
+pub fn failed_unwinding_loops(properties: &[Property]) -> Vec<String> {
+    properties
+        .iter()
+        .filter(|prop| {
+            prop.status == CheckStatus::Failure && prop.description.contains(UNWINDING_ASSERT_DESC)
+        })
+        .filter_map(|prop| {
+            Some(format!("{}.{}", prop.property_id.fn_name.as_ref()?, prop.property_id.id))
+        })
+        .collect()
+}
+
+/// Describe each loop whose unwinding assertion failed: its source location, its CBMC id, and the
+/// number of iterations it was unwound, which is given by `bound` for each loop id. Each loop is
+/// followed by a suggested per-loop bound, so users don't have to increase the unwinding value of
+/// every loop of the harness. Returns an empty string if no unwinding assertion failed.
+///
+/// The suggestion uses the `loops` argument of `#[kani::unwind]`, whose paths are relative to the
+/// crate of the harness, so the loops of generic functions and closures, which can't be named
+/// this way, are suggested an `--unwindset` instead.
+pub fn format_unwinding_failures(
+    properties: &[Property],
+    crate_name: &str,
+    bound: impl Fn(&str) -> Option<u32>,
+) -> String {
+    let mut output = String::new();
+    for prop in properties.iter().filter(|prop| {
+        prop.status == CheckStatus::Failure && prop.description.contains(UNWINDING_ASSERT_DESC)
+    }) {
+        let Some(fn_name) = &prop.property_id.fn_name else { continue };
+        let loop_id = format!("{fn_name}.{}", prop.property_id.id);
+        let path = format!("{:#}::{}", demangle(fn_name), prop.property_id.id);
+        let reached = bound(&loop_id);
+        let iterations =
+            reached.map(|bound| format!(" after {bound} iteration(s)")).unwrap_or_default();
+        writeln!(
+            output,
+            "[Kani] info: Loop `{path}` at {} (CBMC id `{loop_id}`) was not fully \
+            unwound{iterations}.",
+            prop.source_location
+        )
+        .unwrap();
+        let suggested = reached.map_or(2, |bound| bound.saturating_mul(2).max(2));
+        let local_path =
+            path.strip_prefix(&format!("{crate_name}::")).map(|path| format!("crate::{path}"));
+        match local_path {
+            Some(path) if !path.contains(['<', '{']) => writeln!(
+                output,
+                "[Kani] tip: Consider bounding this loop with \
+                `#[kani::unwind(loops = {{\"{path}\": {suggested}}})]`."
+            ),
+            _ => writeln!(
+                output,
+                "[Kani] tip: Consider bounding this loop with \
+                `--cbmc-args --unwindset {loop_id}:{suggested}`."
+            ),
+        }
+        .unwrap();
+    }
+    output
+}
+
 /// Replaces the description of all properties from functions with a missing
 /// definition.
 fn modify_undefined_function_checks(mut properties: Vec<Property>) -> (Vec<Property>, bool) {
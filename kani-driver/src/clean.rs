@@ -0,0 +1,299 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani clean`, which removes the artifacts generated by Kani in the `kani`
+//! folder of the target directory, without touching the rest of the target directory.
+//!
+//! Without options, the whole `kani` folder is removed. Otherwise, the artifacts are removed by
+//! age (`--older-than`) or by build (`--keep-builds`), in units that Kani can rebuild:
+//! - The build of a crate: all the files of one compilation of the crate (`<crate>-<hash>`),
+//!   i.e., its goto binaries, symbol tables and metadata, and its `rlib`/`rmeta` files, so
+//!   cargo recompiles the crate if it is needed again.
+//! - An entry of the goto binary cache (`--cache-goto`), which is only removed by age.
+//! - The results of a coverage run (`kanicov_<date>`).
+
+use crate::args::clean_args::CargoCleanArgs;
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::ArtifactType;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// The name of the cache directory of `--cache-goto`, in the output directory.
+const CACHE_DIR: &str = "kani-cache";
+
+/// A unit of artifacts that is removed as a whole.
+#[derive(Debug)]
+struct Entry {
+    /// The entries of the same group are counted together by `--keep-builds`, e.g., the builds
+    /// of the same crate. Entries without a group are only removed by age.
+    group: Option<String>,
+    /// A short description for the user.
+    description: String,
+    paths: Vec<PathBuf>,
+    /// The latest modification time of the files of the entry.
+    modified: SystemTime,
+    size: u64,
+}
+
+pub(crate) fn clean_cargo(session: KaniSession, args: CargoCleanArgs) -> Result<()> {
+    let metadata = session.cargo_metadata(session.build_target())?;
+    let target_dir = session
+        .args
+        .target_dir
+        .clone()
+        .unwrap_or_else(|| metadata.target_directory.clone().into())
+        .join("kani");
+    let quiet = session.args.common_args.quiet;
+    if !target_dir.exists() {
+        if !quiet {
+            println!("Nothing to clean in `{}`", target_dir.display());
+        }
+        return Ok(());
+    }
+
+    let entries = if args.older_than.is_none() && args.keep_builds.is_none() {
+        vec![Entry {
+            group: None,
+            description: "all Kani artifacts".into(),
+            modified: SystemTime::UNIX_EPOCH,
+            size: dir_size(&target_dir),
+            paths: vec![target_dir],
+        }]
+    } else {
+        let older_than = args.older_than.map(|age| age.0);
+        select_stale(collect_entries(&target_dir)?, older_than, args.keep_builds, SystemTime::now())
+    };
+
+    let size: u64 = entries.iter().map(|entry| entry.size).sum();
+    for entry in &entries {
+        if session.args.common_args.verbose || args.dry_run {
+            println!(
+                "{} {}",
+                if args.dry_run { "Would remove" } else { "Removing" },
+                entry.description
+            );
+        }
+        if !args.dry_run {
+            for path in &entry.paths {
+                if path.is_dir() { fs::remove_dir_all(path)? } else { fs::remove_file(path)? }
+            }
+        }
+    }
+    if !quiet {
+        let verb = if args.dry_run { "Would remove" } else { "Removed" };
+        println!("{verb} {} entries ({:.1} MiB)", entries.len(), size as f64 / (1024.0 * 1024.0));
+    }
+    Ok(())
+}
+
+/// Keep the entries that should be removed: the ones older than `older_than`, and the ones after
+/// the `keep` most recent ones of their group.
+fn select_stale(
+    mut entries: Vec<Entry>,
+    older_than: Option<Duration>,
+    keep: Option<usize>,
+    now: SystemTime,
+) -> Vec<Entry> {
+    entries.sort_by(|a, b| b.modified.cmp(&a.modified));
+    let mut kept: BTreeMap<String, usize> = BTreeMap::new();
+    entries
+        .into_iter()
+        .filter(|entry| {
+            let too_old = older_than.is_some_and(|age| {
+                now.duration_since(entry.modified).is_ok_and(|elapsed| elapsed > age)
+            });
+            let superseded = match (&entry.group, keep) {
+                (Some(group), Some(keep)) => {
+                    let count = kept.entry(group.clone()).or_default();
+                    *count += 1;
+                    *count > keep
+                }
+                _ => false,
+            };
+            too_old || superseded
+        })
+        .collect()
+}
+
+/// Collect the builds, the cache entries and the coverage runs of every target in `target_dir`,
+/// which look like `<target>/<profile>/deps/` and `<target>/kanicov_<date>/`.
+fn collect_entries(target_dir: &Path) -> Result<Vec<Entry>> {
+    let mut entries = vec![];
+    for target in subdirs(target_dir) {
+        for path in subdirs(&target) {
+            let name = path.file_name().unwrap().to_string_lossy();
+            if let Some(stamp) = name.strip_prefix("kanicov_") {
+                let (modified, size) = stats(&[path.clone()]);
+                entries.push(Entry {
+                    group: Some("coverage".into()),
+                    description: format!("coverage results {stamp}"),
+                    paths: vec![path.clone()],
+                    modified,
+                    size,
+                });
+            } else if path.join("deps").is_dir() {
+                let deps = path.join("deps");
+                entries.extend(builds(&deps)?);
+                entries.extend(cache_entries(&deps.join(CACHE_DIR)));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// The builds of the crates in a `deps` directory, found with their Kani metadata files.
+fn builds(deps: &Path) -> Result<Vec<Entry>> {
+    let files: Vec<(PathBuf, String)> = fs::read_dir(deps)?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|typ| typ.is_file()))
+        .map(|entry| (entry.path(), entry.file_name().to_string_lossy().into_owned()))
+        .collect();
+    let metadata_suffix = format!(".{}", &*ArtifactType::Metadata);
+    let mut builds = vec![];
+    for (_, name) in &files {
+        let Some(build) = name.strip_suffix(&metadata_suffix) else { continue };
+        let paths: Vec<PathBuf> = files
+            .iter()
+            .filter(|(_, name)| belongs_to_build(name, build))
+            .map(|(path, _)| path.clone())
+            .collect();
+        let (modified, size) = stats(&paths);
+        let krate = build.rsplit_once('-').map_or(build, |(krate, _)| krate);
+        builds.push(Entry {
+            group: Some(krate.to_string()),
+            description: format!("build {build}"),
+            paths,
+            modified,
+            size,
+        });
+    }
+    Ok(builds)
+}
+
+/// Whether the file `name` was generated by the compilation `build` (`<crate>-<hash>`), e.g.,
+/// `<build>.kani-metadata.json`, `<build>_<harness>.symtab.out` or `lib<build>.rmeta`.
+fn belongs_to_build(name: &str, build: &str) -> bool {
+    let name = name.strip_prefix("lib").filter(|name| name.starts_with(build)).unwrap_or(name);
+    name.strip_prefix(build).is_some_and(|rest| rest.starts_with(['.', '_']))
+}
+
+fn cache_entries(cache_dir: &Path) -> Vec<Entry> {
+    files_in(cache_dir)
+        .into_iter()
+        .map(|path| {
+            let (modified, size) = stats(&[path.clone()]);
+            Entry {
+                group: None,
+                description: format!("cache entry {}", path.display()),
+                paths: vec![path],
+                modified,
+                size,
+            }
+        })
+        .collect()
+}
+
+/// The latest modification time and the total size of the given files and directories.
+fn stats(paths: &[PathBuf]) -> (SystemTime, u64) {
+    let mut modified = SystemTime::UNIX_EPOCH;
+    let mut size = 0;
+    for file in paths
+        .iter()
+        .flat_map(|path| if path.is_dir() { files_in(path) } else { vec![path.clone()] })
+    {
+        if let Ok(metadata) = file.metadata() {
+            modified = modified.max(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+            size += metadata.len();
+        }
+    }
+    (modified, size)
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    stats(&[dir.to_path_buf()]).1
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else { return vec![] };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|typ| typ.is_dir()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// All the files under `dir`, recursively.
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(typ) if typ.is_dir() => pending.push(entry.path()),
+                Ok(_) => files.push(entry.path()),
+                Err(_) => {}
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(group: Option<&str>, days_ago: u64, now: SystemTime) -> Entry {
+        Entry {
+            group: group.map(String::from),
+            description: format!("{group:?} {days_ago}"),
+            paths: vec![],
+            modified: now - Duration::from_secs(days_ago * 86400),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn check_select_stale() {
+        let now = SystemTime::now();
+        let entries = || {
+            vec![
+                entry(Some("foo"), 1, now),
+                entry(Some("foo"), 3, now),
+                entry(Some("bar"), 10, now),
+                entry(None, 9, now),
+                entry(Some("foo"), 2, now),
+            ]
+        };
+        let names = |entries: Vec<Entry>| -> Vec<String> {
+            entries.into_iter().map(|entry| entry.description).collect()
+        };
+        let week = Duration::from_secs(7 * 86400);
+        assert_eq!(
+            names(select_stale(entries(), Some(week), None, now)),
+            ["None 9", "Some(\"bar\") 10"]
+        );
+        assert_eq!(
+            names(select_stale(entries(), None, Some(1), now)),
+            ["Some(\"foo\") 2", "Some(\"foo\") 3"]
+        );
+        assert_eq!(
+            names(select_stale(entries(), Some(week), Some(2), now)),
+            ["Some(\"foo\") 3", "None 9", "Some(\"bar\") 10"]
+        );
+    }
+
+    #[test]
+    fn check_belongs_to_build() {
+        assert!(belongs_to_build("foo-1a2b.kani-metadata.json", "foo-1a2b"));
+        assert!(belongs_to_build("foo-1a2b_check.symtab.out", "foo-1a2b"));
+        assert!(belongs_to_build("libfoo-1a2b.rmeta", "foo-1a2b"));
+        assert!(belongs_to_build("foo-1a2b.d", "foo-1a2b"));
+        assert!(!belongs_to_build("foo-1a2bc.d", "foo-1a2b"));
+        assert!(!belongs_to_build("libbar-1a2b.rmeta", "foo-1a2b"));
+        assert!(belongs_to_build("liblibfoo-1a2b.rlib", "libfoo-1a2b"));
+    }
+}
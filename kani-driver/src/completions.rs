@@ -0,0 +1,111 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani completions`, which prints shell completion scripts generated from the
+//! `clap` definitions of the arguments.
+//!
+//! Harness names can't be known statically, so the bash and fish scripts also complete the values
+//! of `--harness` by calling `cargo-kani completions --harness-names`, which reads the metadata
+//! of the latest build instead of building the package.
+
+use crate::args::completions_args::CargoCompletionsArgs;
+use crate::args::CargoKaniArgs;
+use crate::metadata::from_json;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use kani_metadata::KaniMetadata;
+use std::collections::BTreeSet;
+
+/// The name of the binary that the scripts complete.
+const BIN_NAME: &str = "cargo-kani";
+
+/// `cargo kani completions` main entry point.
+pub(crate) fn completions_cargo(session: KaniSession, args: CargoCompletionsArgs) -> Result<()> {
+    if args.harness_names {
+        for name in harness_names(&session)? {
+            println!("{name}");
+        }
+        return Ok(());
+    }
+    let shell = args.shell.context("Missing shell")?;
+    print!("{}", completion_script(shell));
+    Ok(())
+}
+
+/// The completion script for the given shell, including the completion of harness names where
+/// supported.
+fn completion_script(shell: Shell) -> String {
+    let mut script = vec![];
+    clap_complete::generate(shell, &mut CargoKaniArgs::command(), BIN_NAME, &mut script);
+    let mut script = String::from_utf8(script).unwrap();
+    match shell {
+        Shell::Bash => {
+            // Wrap the generated function, which completes file names for `--harness`.
+            let generated = script
+                .lines()
+                .find_map(|line| line.trim_start().strip_prefix("complete -F "))
+                .and_then(|line| line.split_whitespace().next())
+                .map(String::from);
+            if let Some(generated) = generated {
+                script.push_str(&format!(
+                    r#"
+_cargo_kani_harnesses() {{
+    if [[ "${{COMP_WORDS[COMP_CWORD-1]}}" == "--harness" ]]; then
+        COMPREPLY=($(compgen -W "$({BIN_NAME} completions --harness-names 2>/dev/null)" -- "${{COMP_WORDS[COMP_CWORD]}}"))
+        return 0
+    fi
+    {generated} "$@"
+}}
+complete -F _cargo_kani_harnesses -o bashdefault -o default {BIN_NAME}
+"#
+                ));
+            }
+        }
+        Shell::Fish => {
+            script.push_str(&format!(
+                "complete -c {BIN_NAME} -l harness -x -a '({BIN_NAME} completions --harness-names 2>/dev/null)'\n"
+            ));
+        }
+        _ => {}
+    }
+    script
+}
+
+/// The names of the harnesses found by the latest build of the package, which are read from the
+/// metadata files in the output directory.
+fn harness_names(session: &KaniSession) -> Result<BTreeSet<String>> {
+    let build_target = session.build_target();
+    let target_dir = match &session.args.target_dir {
+        Some(target_dir) => target_dir.clone(),
+        None => session.cargo_metadata(build_target)?.target_directory.into(),
+    };
+    let outdir = target_dir.join("kani").join(session.target_name()).join("debug/deps");
+    let pattern = outdir.join("*.kani-metadata.json");
+    let mut names = BTreeSet::new();
+    for path in glob::glob(&pattern.to_string_lossy())?.flatten() {
+        let metadata: KaniMetadata = from_json(&path)?;
+        names.extend(metadata.proof_harnesses.into_iter().map(|harness| harness.pretty_name));
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_completion_script() {
+        let bash = completion_script(Shell::Bash);
+        assert!(bash.contains("--harness"));
+        assert!(bash.contains("complete -F _cargo_kani_harnesses"));
+        let fish = completion_script(Shell::Fish);
+        assert!(fish.ends_with(
+            "complete -c cargo-kani -l harness -x -a '(cargo-kani completions --harness-names 2>/dev/null)'\n"
+        ));
+        // The generated zsh script only completes the `--harness-names` option itself.
+        let zsh = completion_script(Shell::Zsh);
+        assert!(!zsh.contains("completions --harness-names"));
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `cargo kani playback --batch`, which runs all the concrete playback tests of the
+//! workspace and reports the status of each one:
+//! - `reproduces`: the test still fails, so the counterexample is still valid.
+//! - `stale`: the test passes, so the counterexample no longer triggers the failure.
+//! - `does-not-compile`: the test has compilation errors, e.g., because the harness changed.
+//! - `not-run`: the test was found in the sources but not built, e.g., because another error
+//!   broke the build of its crate, or because it is behind a `cfg`.
+//!
+//! The tests are found in the sources of the workspace, so the ones that no longer compile are
+//! reported as well, and they are run from the test executables that could be built.
+
+use crate::args::common::Verbosity;
+use crate::args::playback_args::MessageFormat;
+use crate::concrete_playback::debug::{last_segment, list_tests, test_args, PLAYBACK_TEST_PREFIX};
+use crate::session;
+use anyhow::{bail, Result};
+use cargo_metadata::diagnostic::DiagnosticLevel;
+use cargo_metadata::Message;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The status of a concrete playback test.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, strum_macros::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum PlaybackStatus {
+    Reproduces,
+    Stale,
+    DoesNotCompile,
+    NotRun,
+}
+
+/// A concrete playback test found in the sources.
+#[derive(Debug, PartialEq)]
+struct SourceTest {
+    name: String,
+    file: PathBuf,
+    /// The first and last lines of the test function, starting at 1.
+    lines: (usize, usize),
+}
+
+/// The outcome of building the tests.
+#[derive(Debug)]
+struct BuildOutcome {
+    /// The test executables that were built.
+    executables: Vec<PathBuf>,
+    /// The locations of the compilation errors, as `(file, line)`.
+    errors: Vec<(PathBuf, usize)>,
+}
+
+/// The status of one test, as reported to the user.
+#[derive(Debug, Serialize)]
+struct TestReport<'a> {
+    test: &'a str,
+    file: String,
+    line: usize,
+    status: PlaybackStatus,
+}
+
+/// Build the tests of the workspace with the given `cargo test` command, run the concrete
+/// playback tests, and print the status of each one.
+pub fn batch_playback(
+    cmd: Command,
+    workspace_root: &Path,
+    target_dir: &Path,
+    format: MessageFormat,
+    verbosity: &impl Verbosity,
+) -> Result<()> {
+    let tests = find_source_tests(workspace_root, target_dir);
+    let BuildOutcome { executables, errors } = build_tests(cmd, workspace_root, verbosity)?;
+
+    let mut results = BTreeMap::new();
+    for exe in &executables {
+        for test in list_tests(exe)? {
+            let name = last_segment(&test);
+            if !name.starts_with(PLAYBACK_TEST_PREFIX) {
+                continue;
+            }
+            let output = Command::new(exe).args(test_args(&test).split_whitespace()).output()?;
+            let status = if output.status.success() {
+                PlaybackStatus::Stale
+            } else {
+                PlaybackStatus::Reproduces
+            };
+            results.insert(name.to_string(), status);
+        }
+    }
+
+    let mut counts: BTreeMap<PlaybackStatus, usize> = BTreeMap::new();
+    for test in &tests {
+        let status = test_status(test, &results, &errors);
+        *counts.entry(status).or_default() += 1;
+        let file = test.file.strip_prefix(workspace_root).unwrap_or(&test.file);
+        let report = TestReport {
+            test: &test.name,
+            file: file.display().to_string(),
+            line: test.lines.0,
+            status,
+        };
+        match format {
+            MessageFormat::Json => println!("{}", serde_json::to_string(&report)?),
+            MessageFormat::Human => {
+                println!("{}:{} {}: {status}", report.file, report.line, report.test)
+            }
+        }
+    }
+
+    if format == MessageFormat::Human {
+        let summary: Vec<String> = [
+            PlaybackStatus::Reproduces,
+            PlaybackStatus::Stale,
+            PlaybackStatus::DoesNotCompile,
+            PlaybackStatus::NotRun,
+        ]
+        .iter()
+        .map(|status| format!("{} {status}", counts.get(status).copied().unwrap_or_default()))
+        .collect();
+        println!("Found {} concrete playback tests: {}", tests.len(), summary.join(", "));
+    }
+    Ok(())
+}
+
+/// Run the cargo command that builds the tests, and collect the test executables and the
+/// locations of the compilation errors.
+fn build_tests(
+    cmd: Command,
+    workspace_root: &Path,
+    verbosity: &impl Verbosity,
+) -> Result<BuildOutcome> {
+    let mut executables = vec![];
+    let mut errors = vec![];
+    if let Some(mut cargo_process) = session::run_piped(verbosity, cmd)? {
+        let reader = BufReader::new(cargo_process.stdout.take().unwrap());
+        for message in Message::parse_stream(reader) {
+            match message? {
+                Message::CompilerArtifact(artifact) if artifact.profile.test => {
+                    executables.extend(artifact.executable.map(PathBuf::from))
+                }
+                Message::CompilerMessage(msg) if msg.message.level == DiagnosticLevel::Error => {
+                    if verbosity.verbose() {
+                        eprint!("{msg}");
+                    }
+                    // The paths are relative to the workspace root, where cargo runs rustc.
+                    for span in msg.message.spans.iter().filter(|span| span.is_primary) {
+                        let file = workspace_root.join(&span.file_name);
+                        errors.extend((span.line_start..=span.line_end).map(|l| (file.clone(), l)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        // The build fails if any test doesn't compile, but `--keep-going` still builds the
+        // other crates, so we only fail if nothing could be built.
+        let status = cargo_process.wait()?;
+        if !status.success() && executables.is_empty() && errors.is_empty() {
+            bail!("Failed to build the concrete playback tests ({status}).");
+        }
+    }
+    Ok(BuildOutcome { executables, errors })
+}
+
+fn test_status(
+    test: &SourceTest,
+    results: &BTreeMap<String, PlaybackStatus>,
+    errors: &[(PathBuf, usize)],
+) -> PlaybackStatus {
+    let (first, last) = test.lines;
+    if errors.iter().any(|(file, line)| *file == test.file && (first..=last).contains(line)) {
+        PlaybackStatus::DoesNotCompile
+    } else {
+        results.get(&test.name).copied().unwrap_or(PlaybackStatus::NotRun)
+    }
+}
+
+/// Find the concrete playback tests in the Rust sources under `root`, skipping hidden
+/// directories and the target directory.
+fn find_source_tests(root: &Path, target_dir: &Path) -> Vec<SourceTest> {
+    let mut tests = vec![];
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                if !hidden && path != target_dir {
+                    pending.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs")
+                && let Ok(source) = std::fs::read_to_string(&path)
+            {
+                tests.extend(tests_in_source(&path, &source));
+            }
+        }
+    }
+    tests.sort_by(|a, b| (&a.file, a.lines).cmp(&(&b.file, b.lines)));
+    tests
+}
+
+/// Find the playback test functions in the source of `file`. A test ends at the first closing
+/// brace with the same indentation as its `fn`, which is how the generated tests are formatted.
+fn tests_in_source(file: &Path, source: &str) -> Vec<SourceTest> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut tests = vec![];
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(rest) = line.trim_start().strip_prefix("fn ") else { continue };
+        if !rest.starts_with(PLAYBACK_TEST_PREFIX) {
+            continue;
+        }
+        let name = rest.split('(').next().unwrap().trim().to_string();
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let end = lines[idx + 1..]
+            .iter()
+            .position(|line| line.strip_prefix(indent) == Some("}"))
+            .map_or(lines.len(), |offset| idx + offset + 2);
+        tests.push(SourceTest { name, file: file.to_path_buf(), lines: (idx + 1, end) });
+    }
+    tests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_tests_in_source() {
+        let source = "#[kani::proof]\n\
+            fn check() {}\n\
+            mod tests {\n    \
+                #[test]\n    \
+                fn kani_concrete_playback_check_123() {\n        \
+                    let concrete_vals: Vec<Vec<u8>> = vec![];\n        \
+                    kani::concrete_playback_run(concrete_vals, check);\n    \
+                }\n\
+            }\n";
+        let file = Path::new("src/lib.rs");
+        let tests = tests_in_source(file, source);
+        let expected = SourceTest {
+            name: "kani_concrete_playback_check_123".into(),
+            file: file.into(),
+            lines: (5, 8),
+        };
+        assert_eq!(tests, [expected]);
+
+        let results = BTreeMap::from([(tests[0].name.clone(), PlaybackStatus::Stale)]);
+        assert_eq!(test_status(&tests[0], &results, &[]), PlaybackStatus::Stale);
+        let errors = [(file.to_path_buf(), 7)];
+        assert_eq!(test_status(&tests[0], &results, &errors), PlaybackStatus::DoesNotCompile);
+        assert_eq!(test_status(&tests[0], &BTreeMap::new(), &[]), PlaybackStatus::NotRun);
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--concolic`, which confirms the failures of the harnesses by executing their
+//! counterexamples concretely.
+//!
+//! A failure may be spurious if the model over-approximates the code, e.g., because of a stub or
+//! of a function whose body is havocked. Once every harness is verified, we build the concrete
+//! playback tests generated with `--concrete-playback=inplace`, and run the test of each failed
+//! harness with the values of its counterexamples, as `playback --minimize-harness` does. With
+//! `--counterexamples N`, the counterexamples found by blocking the previous ones symbolically are
+//! executed as well. A failure is confirmed once one of them panics, and needs review otherwise.
+
+use crate::call_cbmc::VerificationResult;
+use crate::concrete_playback::counterexamples::PropertyCounterexamples;
+use crate::concrete_playback::debug::{last_segment, list_tests};
+use crate::concrete_playback::minimize::run_playback;
+use crate::concrete_playback::playback::build_playback_tests;
+use crate::concrete_playback::test_generator::{
+    extract_harness_values, playback_test_name, ConcreteVal,
+};
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+
+/// The counterexamples of a failed harness, to be executed concretely.
+pub struct ConcolicHarness {
+    /// The name of the playback test generated for the harness.
+    test: String,
+    /// The nondeterministic values of each counterexample.
+    candidates: Vec<Vec<Vec<u8>>>,
+    /// Whether one of the counterexamples fails concretely, once they were executed.
+    pub confirmed: Option<bool>,
+}
+
+impl KaniSession {
+    /// Record the counterexamples of a failed harness, so they are executed by
+    /// [Self::confirm_counterexamples].
+    pub(crate) fn record_concolic_candidates(
+        &self,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+        found: &[PropertyCounterexamples],
+    ) {
+        if !self.args.concolic {
+            return;
+        }
+        let Ok(properties) = &result.results else { return };
+        let harness_values = extract_harness_values(properties);
+        // The playback test is generated for the first counterexample.
+        let Some((_, first)) = harness_values.first() else { return };
+        let test = playback_test_name(harness.get_harness_name_unqualified(), first);
+        let candidates = if found.is_empty() {
+            harness_values.iter().map(|(_, values)| to_bytes(values)).collect()
+        } else {
+            found.iter().flat_map(|entry| &entry.counterexamples).map(|v| to_bytes(v)).collect()
+        };
+        let entry = ConcolicHarness { test, candidates, confirmed: None };
+        self.concolic_harnesses.lock().unwrap().insert(harness.pretty_name.clone(), entry);
+    }
+
+    /// Build the playback tests and run the counterexamples of each failed harness, and print
+    /// whether its failure is confirmed.
+    pub(crate) fn confirm_counterexamples(&self) -> Result<()> {
+        let mut harnesses = self.concolic_harnesses.lock().unwrap();
+        if harnesses.is_empty() {
+            return Ok(());
+        }
+        let mut cargo_args = self.args.cargo.to_cargo_args();
+        cargo_args.append(&mut self.args.target.to_cargo_args());
+        let executables = build_playback_tests(cargo_args, &self.args.common_args)?;
+        let mut tests = vec![];
+        for exe in &executables {
+            tests.extend(list_tests(exe)?.into_iter().map(|test| (exe, test)));
+        }
+
+        let quiet = self.args.common_args.quiet;
+        for (name, harness) in harnesses.iter_mut() {
+            let Some((exe, test)) =
+                tests.iter().find(|(_, test)| last_segment(test) == harness.test)
+            else {
+                if !quiet {
+                    println!(
+                        "The playback test `{}` of harness `{name}` was not found, so its failure \
+                        needs review.",
+                        harness.test
+                    );
+                }
+                harness.confirmed = Some(false);
+                continue;
+            };
+            let mut reproduced = None;
+            for (idx, values) in harness.candidates.iter().enumerate() {
+                if run_playback(exe, test, Some(values))?.failure.is_some() {
+                    reproduced = Some(idx);
+                    break;
+                }
+            }
+            let total = harness.candidates.len();
+            if !quiet {
+                match reproduced {
+                    Some(idx) => println!(
+                        "Counterexample {} of {total} of harness `{name}` fails when executed \
+                        concretely: the failure is confirmed.",
+                        idx + 1
+                    ),
+                    None => println!(
+                        "None of the {total} counterexamples of harness `{name}` fails when \
+                        executed concretely: the failure needs review, e.g., it may be caused by \
+                        a stub or another over-approximation."
+                    ),
+                }
+            }
+            harness.confirmed = Some(reproduced.is_some());
+        }
+        Ok(())
+    }
+
+    /// Whether the failure of the harness wasn't reproduced by `--concolic`.
+    pub(crate) fn needs_review(&self, harness: &HarnessMetadata) -> bool {
+        let harnesses = self.concolic_harnesses.lock().unwrap();
+        harnesses.get(&harness.pretty_name).is_some_and(|harness| harness.confirmed == Some(false))
+    }
+}
+
+fn to_bytes(values: &[ConcreteVal]) -> Vec<Vec<u8>> {
+    values.iter().map(|value| value.byte_arr.clone()).collect()
+}
@@ -0,0 +1,205 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--counterexamples N`, which searches for distinct counterexamples of each failed
+//! property.
+//!
+//! With this option, the compiler reports every nondeterministic value to the
+//! `__kani_record_nondet` function. Once a property fails, we generate a C definition of this
+//! function that assumes that the sequence of nondeterministic bytes differs from the ones of the
+//! counterexamples found so far, link it with the harness, and verify it again. We stop once the
+//! property holds, which means no other counterexample exists within the verification bounds,
+//! or once we found `N` counterexamples.
+
+use crate::call_cbmc::VerificationResult;
+use crate::cbmc_output_parser::Property;
+use crate::concrete_playback::test_generator::{extract_harness_values, ConcreteVal};
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+
+/// The counterexamples found for a failed property.
+pub struct PropertyCounterexamples {
+    /// The name of the property, e.g., `check.assertion.1`.
+    pub property: String,
+    /// The description of the property.
+    pub description: String,
+    /// The nondeterministic values of each counterexample.
+    pub counterexamples: Vec<Vec<ConcreteVal>>,
+    /// Whether the search stopped because no other counterexample was found.
+    pub exhausted: bool,
+}
+
+impl KaniSession {
+    /// Search for up to `--counterexamples` distinct counterexamples for each property that
+    /// failed in `result`.
+    pub(crate) fn find_counterexamples(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+    ) -> Result<Vec<PropertyCounterexamples>> {
+        let Some(limit) = self.args.counterexamples else { return Ok(vec![]) };
+        let Ok(properties) = &result.results else { return Ok(vec![]) };
+
+        let mut found = vec![];
+        for (property, values) in extract_harness_values(properties) {
+            let mut entry = PropertyCounterexamples {
+                property: property.property_name(),
+                description: property.description.clone(),
+                counterexamples: vec![values],
+                exhausted: false,
+            };
+            // A counterexample without nondeterministic values cannot be blocked.
+            entry.exhausted = entry.counterexamples[0].is_empty();
+            while !entry.exhausted && entry.counterexamples.len() < limit as usize {
+                let blocked =
+                    binary.with_extension(format!("cex{}.out", entry.counterexamples.len()));
+                self.block_counterexamples(binary, &blocked, &entry.counterexamples)?;
+                let next = self.run_cbmc(&blocked, harness)?;
+                let values = next.results.as_ref().ok().and_then(|properties| {
+                    extract_harness_values(properties)
+                        .into_iter()
+                        .find(|(prop, _)| is_same_property(prop, property))
+                        .map(|(_, values)| values)
+                });
+                match values {
+                    Some(values) => entry.counterexamples.push(values),
+                    None => entry.exhausted = true,
+                }
+            }
+            found.push(entry);
+        }
+        Ok(found)
+    }
+
+    /// Link `binary` with a definition of `__kani_record_nondet` that blocks the given
+    /// counterexamples, and write the result to `output`.
+    fn block_counterexamples(
+        &self,
+        binary: &Path,
+        output: &Path,
+        counterexamples: &[Vec<ConcreteVal>],
+    ) -> Result<()> {
+        let c_file = output.with_extension("c");
+        std::fs::write(&c_file, blocking_c_code(counterexamples))?;
+        let mut cmd = Command::new("goto-cc");
+        cmd.arg(binary).arg(&c_file).arg("-o").arg(output);
+        self.run_suppress(cmd)?;
+        if !self.args.keep_temps {
+            let _ = std::fs::remove_file(&c_file);
+        }
+        Ok(())
+    }
+}
+
+fn is_same_property(a: &Property, b: &Property) -> bool {
+    a.property_name() == b.property_name() && a.description == b.description
+}
+
+/// Generate the definition of `__kani_record_nondet` that assumes that the nondeterministic
+/// bytes of the harness don't start with the bytes of any of the counterexamples.
+///
+/// The generated code doesn't have loops, so it doesn't depend on the unwinding bounds.
+fn blocking_c_code(counterexamples: &[Vec<ConcreteVal>]) -> String {
+    let mut code = String::from(
+        "// Generated by Kani to block previous counterexamples.\n\
+        #include <stddef.h>\n\
+        static size_t kani_nondet_offset = 0;\n",
+    );
+    for idx in 0..counterexamples.len() {
+        writeln!(code, "static _Bool kani_matches_{idx} = 1;").unwrap();
+    }
+    code.push_str(
+        "void __kani_record_nondet(const void *value, size_t size)\n{\n    \
+        const unsigned char *bytes = value;\n    \
+        size_t start = kani_nondet_offset;\n    \
+        kani_nondet_offset += size;\n",
+    );
+    for (idx, counterexample) in counterexamples.iter().enumerate() {
+        let bytes: Vec<u8> =
+            counterexample.iter().flat_map(|val| val.byte_arr.iter().copied()).collect();
+        for (offset, byte) in bytes.iter().enumerate() {
+            writeln!(
+                code,
+                "    if (start <= {offset} && {offset} < kani_nondet_offset)\n        \
+                kani_matches_{idx} = kani_matches_{idx} && bytes[{offset} - start] == {byte};"
+            )
+            .unwrap();
+        }
+        let len = bytes.len();
+        writeln!(
+            code,
+            "    if (start < {len} && {len} <= kani_nondet_offset)\n        \
+            __CPROVER_assume(!kani_matches_{idx});"
+        )
+        .unwrap();
+    }
+    code.push_str("}\n");
+    code
+}
+
+/// Format the counterexamples found for a harness.
+pub fn format_counterexamples(found: &[PropertyCounterexamples]) -> String {
+    let mut output = String::new();
+    for entry in found {
+        writeln!(
+            output,
+            "Found {} counterexample(s) for {}: {}",
+            entry.counterexamples.len(),
+            entry.property,
+            entry.description
+        )
+        .unwrap();
+        for (idx, counterexample) in entry.counterexamples.iter().enumerate() {
+            let values: Vec<_> = counterexample.iter().map(|val| val.interp_val.as_str()).collect();
+            writeln!(output, " - Counterexample {}: [{}]", idx + 1, values.join(", ")).unwrap();
+        }
+        if entry.exhausted && entry.counterexamples.len() > 1 {
+            writeln!(output, " No other counterexample was found.").unwrap();
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn val(bytes: &[u8], interp: &str) -> ConcreteVal {
+        ConcreteVal { byte_arr: bytes.to_vec(), interp_val: interp.to_string() }
+    }
+
+    #[test]
+    fn check_blocking_c_code() {
+        let code = blocking_c_code(&[vec![val(&[1], "1"), val(&[2, 0], "2")]]);
+        assert!(code.contains("static _Bool kani_matches_0 = 1;\n"));
+        assert!(code.contains("void __kani_record_nondet(const void *value, size_t size)\n"));
+        assert!(code.contains(
+            "    if (start <= 2 && 2 < kani_nondet_offset)\n        \
+            kani_matches_0 = kani_matches_0 && bytes[2 - start] == 0;\n"
+        ));
+        assert!(code.contains(
+            "    if (start < 3 && 3 <= kani_nondet_offset)\n        \
+            __CPROVER_assume(!kani_matches_0);\n"
+        ));
+        assert!(!code.contains("for"));
+    }
+
+    #[test]
+    fn check_format_counterexamples() {
+        let found = [PropertyCounterexamples {
+            property: "check.assertion.1".to_string(),
+            description: "assertion failed: x < 10".to_string(),
+            counterexamples: vec![vec![val(&[10], "10")], vec![val(&[255], "255")]],
+            exhausted: true,
+        }];
+        assert_eq!(
+            format_counterexamples(&found),
+            "Found 2 counterexample(s) for check.assertion.1: assertion failed: x < 10\n \
+            - Counterexample 1: [10]\n - Counterexample 2: [255]\n No other counterexample was found.\n"
+        );
+    }
+}
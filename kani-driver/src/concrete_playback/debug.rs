@@ -0,0 +1,161 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `playback --debug-harness`, which prepares the concrete playback test of a harness
+//! to be debugged with `rust-gdb` or `rust-lldb`.
+//!
+//! The test executable reproduces the counterexample on its own. We generate a script for each
+//! debugger that stops at the beginning of the harness and when the failing check panics, and
+//! that runs only the playback test.
+
+use crate::args::common::Verbosity;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The prefix of the name of the concrete playback tests generated by Kani.
+pub(super) const PLAYBACK_TEST_PREFIX: &str = "kani_concrete_playback_";
+
+/// Find the playback tests of the harness in the given test executables, and generate the
+/// debugger scripts for the first one.
+pub fn debug_playback(
+    executables: &[PathBuf],
+    harness: &str,
+    verbosity: &impl Verbosity,
+) -> Result<()> {
+    let tests = find_playback_tests(executables, harness)?;
+    let (exe, test) = &tests[0];
+
+    let gdb_path = exe.with_file_name(format!("{}.gdb", last_segment(test)));
+    let lldb_path = exe.with_file_name(format!("{}.lldb", last_segment(test)));
+    std::fs::write(&gdb_path, gdb_script(test, harness))?;
+    std::fs::write(&lldb_path, lldb_script(test, harness))?;
+
+    if !verbosity.quiet() {
+        if tests.len() > 1 {
+            println!(
+                "Found {} playback tests for harness `{harness}`. Using `{test}`.",
+                tests.len()
+            );
+        }
+        println!("Executable {}", exe.display());
+        println!("Reproduce the counterexample with:");
+        println!("  {} --exact {test} --nocapture", exe.display());
+        println!("Debug it with one of:");
+        println!("  rust-gdb -x {} {}", gdb_path.display(), exe.display());
+        println!("  rust-lldb -s {} {}", lldb_path.display(), exe.display());
+    }
+    Ok(())
+}
+
+/// Find the concrete playback tests of the harness in the given test executables.
+/// Returns an error if there are none.
+pub(super) fn find_playback_tests<'a>(
+    executables: &'a [PathBuf],
+    harness: &str,
+) -> Result<Vec<(&'a PathBuf, String)>> {
+    let mut tests = vec![];
+    for exe in executables {
+        for test in list_tests(exe)? {
+            if is_playback_test_of(&test, harness) {
+                tests.push((exe, test));
+            }
+        }
+    }
+    if tests.is_empty() {
+        bail!(
+            "no concrete playback test found for harness `{harness}`. Generate one with \
+            `--concrete-playback=inplace` first"
+        );
+    }
+    Ok(tests)
+}
+
+/// List the tests of a test executable.
+pub(super) fn list_tests(exe: &Path) -> Result<Vec<String>> {
+    let output = Command::new(exe)
+        .args(["--list", "--format", "terse"])
+        .output()
+        .with_context(|| format!("Failed to list the tests of `{}`", exe.display()))?;
+    if !output.status.success() {
+        bail!("Failed to list the tests of `{}`", exe.display());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_suffix(": test"))
+        .map(String::from)
+        .collect())
+}
+
+pub(super) fn last_segment(test: &str) -> &str {
+    test.rsplit("::").next().unwrap()
+}
+
+/// Whether the test was generated for the harness, i.e., it is named
+/// `kani_concrete_playback_<harness>_<hash>`.
+fn is_playback_test_of(test: &str, harness: &str) -> bool {
+    last_segment(test)
+        .strip_prefix(PLAYBACK_TEST_PREFIX)
+        .and_then(|rest| rest.strip_prefix(harness))
+        .and_then(|rest| rest.strip_prefix('_'))
+        .is_some_and(|hash| !hash.is_empty() && hash.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The arguments to run a single test.
+pub(super) fn test_args(test: &str) -> String {
+    format!("--exact {test} --nocapture --test-threads=1")
+}
+
+fn gdb_script(test: &str, harness: &str) -> String {
+    format!(
+        "# Generated by Kani to debug the concrete playback test `{test}`.\n\
+        set breakpoint pending on\n\
+        # Stop at the beginning of the harness.\n\
+        rbreak ::{harness}$\n\
+        # Stop when a check fails. Use `up` to go to the failing check.\n\
+        break rust_panic\n\
+        run {}\n",
+        test_args(test)
+    )
+}
+
+fn lldb_script(test: &str, harness: &str) -> String {
+    format!(
+        "# Generated by Kani to debug the concrete playback test `{test}`.\n\
+        # Stop at the beginning of the harness.\n\
+        breakpoint set --func-regex '::{harness}$'\n\
+        # Stop when a check fails. Use `up` to go to the failing check.\n\
+        breakpoint set --name rust_panic\n\
+        process launch -- {}\n",
+        test_args(test)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_is_playback_test_of() {
+        assert!(is_playback_test_of("kani_concrete_playback_check_1234", "check"));
+        assert!(is_playback_test_of("tests::kani_concrete_playback_check_1234", "check"));
+        assert!(!is_playback_test_of("kani_concrete_playback_check_other_1234", "check"));
+        assert!(!is_playback_test_of("kani_concrete_playback_check_", "check"));
+        assert!(!is_playback_test_of("check_1234", "check"));
+    }
+
+    #[test]
+    fn check_debugger_scripts() {
+        let test = "tests::kani_concrete_playback_check_12";
+        let gdb = gdb_script(test, "check");
+        assert!(gdb.contains("rbreak ::check$\n"));
+        assert!(gdb.contains("break rust_panic\n"));
+        assert!(gdb.ends_with(&format!("run --exact {test} --nocapture --test-threads=1\n")));
+
+        let lldb = lldb_script(test, "check");
+        assert!(lldb.contains("breakpoint set --func-regex '::check$'\n"));
+        assert!(lldb.contains("breakpoint set --name rust_panic\n"));
+        assert!(lldb.ends_with(&format!(
+            "process launch -- --exact {test} --nocapture --test-threads=1\n"
+        )));
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `playback --minimize-harness`, which shrinks the counterexample reproduced by the
+//! concrete playback test of a harness.
+//!
+//! The playback library allows overriding the concrete values of a test with the
+//! `KANI_PLAYBACK_VALUES` environment variable. We re-run the test with smaller values, and keep
+//! them as long as the test still fails at the same location. Each value is interpreted as an
+//! unsigned little-endian integer and shrunk towards zero, so the result is the smallest
+//! counterexample found by this search, not necessarily the smallest one overall.
+
+use crate::args::common::Verbosity;
+use crate::concrete_playback::debug::{find_playback_tests, test_args};
+use anyhow::{bail, Context, Result};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variable used by the playback library to override the concrete values.
+const VALUES_ENV_VAR: &str = "KANI_PLAYBACK_VALUES";
+
+/// Environment variable used to ask the playback library to print the concrete values.
+const PRINT_VALUES_ENV_VAR: &str = "KANI_PLAYBACK_PRINT_VALUES";
+
+/// Values larger than this number of bytes are not shrunk.
+const MAX_VALUE_SIZE: usize = 16;
+
+/// Find the playback test of the harness, minimize its counterexample, and print the values that
+/// should replace the ones in the test.
+pub fn minimize_playback(
+    executables: &[PathBuf],
+    harness: &str,
+    verbosity: &impl Verbosity,
+) -> Result<()> {
+    let tests = find_playback_tests(executables, harness)?;
+    let (exe, test) = &tests[0];
+    if !verbosity.quiet() && tests.len() > 1 {
+        println!("Found {} playback tests for harness `{harness}`. Using `{test}`.", tests.len());
+    }
+
+    let original = run_playback(exe, test, None)?;
+    let (Some(values), Some(failure)) = (original.values, original.failure) else {
+        bail!("the playback test `{test}` does not reproduce a failure");
+    };
+    if !verbosity.quiet() {
+        println!("Minimizing the counterexample of `{test}` that fails at {failure}");
+    }
+
+    let mut runs = 0;
+    let minimized = shrink_values(values.clone(), |candidate| {
+        runs += 1;
+        let result = run_playback(exe, test, Some(candidate))?;
+        Ok(result.failure.as_ref() == Some(&failure))
+    })?;
+
+    if !verbosity.quiet() {
+        println!("Tried {runs} candidates.");
+        if minimized == values {
+            println!("The counterexample could not be minimized.");
+        } else {
+            println!("Replace the concrete values of `{test}` with:");
+        }
+    }
+    print!("{}", format_values(&minimized));
+    Ok(())
+}
+
+/// The result of running a playback test.
+pub(super) struct PlaybackResult {
+    /// The concrete values used by the test.
+    pub values: Option<Vec<Vec<u8>>>,
+    /// The location of the panic, if the test failed.
+    pub failure: Option<String>,
+}
+
+/// Run a playback test, with the given values instead of its own ones if any.
+pub(super) fn run_playback(
+    exe: &Path,
+    test: &str,
+    values: Option<&[Vec<u8>]>,
+) -> Result<PlaybackResult> {
+    let mut cmd = Command::new(exe);
+    cmd.args(test_args(test).split_whitespace()).env(PRINT_VALUES_ENV_VAR, "1");
+    if let Some(values) = values {
+        cmd.env(VALUES_ENV_VAR, encode_values(values));
+    }
+    let output =
+        cmd.output().with_context(|| format!("Failed to run the playback test `{test}`"))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let values = stderr
+        .lines()
+        .find_map(|line| line.strip_prefix(VALUES_ENV_VAR)?.strip_prefix('='))
+        .map(decode_values)
+        .transpose()?;
+    let failure = if output.status.success() { None } else { panic_location(&stderr) };
+    Ok(PlaybackResult { values, failure })
+}
+
+/// Extract the location of the first panic in the test output, e.g., `src/lib.rs:10:5` from
+/// `thread 'test' panicked at src/lib.rs:10:5:`.
+fn panic_location(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (_, location) = line.split_once(" panicked at ")?;
+        // Older toolchains print the message before the location: `'msg', src/lib.rs:10:5`.
+        let location = location.rsplit(", ").next().unwrap();
+        Some(location.trim_end_matches(':').to_string())
+    })
+}
+
+/// Greedily shrink each value while `still_fails` holds. The candidates for a value are zero,
+/// followed by values that get closer to the current one: `v - v/2`, `v - v/4`, ..., `v - 1`.
+fn shrink_values(
+    mut values: Vec<Vec<u8>>,
+    mut still_fails: impl FnMut(&[Vec<u8>]) -> Result<bool>,
+) -> Result<Vec<Vec<u8>>> {
+    for idx in 0..values.len() {
+        if values[idx].len() > MAX_VALUE_SIZE {
+            continue;
+        }
+        let mut current = to_int(&values[idx]);
+        'shrink: while current > 0 {
+            for candidate in candidates(current) {
+                let mut trial = values.clone();
+                trial[idx] = from_int(candidate, values[idx].len());
+                if still_fails(&trial)? {
+                    values = trial;
+                    current = candidate;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+    }
+    Ok(values)
+}
+
+fn candidates(value: u128) -> impl Iterator<Item = u128> {
+    let mut delta = value;
+    std::iter::once(0).chain(std::iter::from_fn(move || {
+        delta /= 2;
+        (delta > 0).then(|| value - delta)
+    }))
+}
+
+fn to_int(bytes: &[u8]) -> u128 {
+    bytes.iter().rev().fold(0, |acc, byte| (acc << 8) | u128::from(*byte))
+}
+
+fn from_int(value: u128, size: usize) -> Vec<u8> {
+    value.to_le_bytes()[..size].to_vec()
+}
+
+fn encode_values(values: &[Vec<u8>]) -> String {
+    let mut encoded = String::new();
+    for (idx, value) in values.iter().enumerate() {
+        if idx > 0 {
+            encoded.push(';');
+        }
+        for byte in value {
+            write!(encoded, "{byte:02x}").unwrap();
+        }
+    }
+    encoded
+}
+
+fn decode_values(encoded: &str) -> Result<Vec<Vec<u8>>> {
+    if encoded.is_empty() {
+        return Ok(vec![]);
+    }
+    encoded
+        .split(';')
+        .map(|value| {
+            (0..value.len())
+                .step_by(2)
+                .map(|idx| {
+                    value
+                        .get(idx..idx + 2)
+                        .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                        .with_context(|| format!("Invalid concrete value `{value}`"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Format the values as the body of the `concrete_vals` vector of a playback test.
+fn format_values(values: &[Vec<u8>]) -> String {
+    let mut out = String::new();
+    for value in values {
+        let bytes: Vec<_> = value.iter().map(u8::to_string).collect();
+        writeln!(out, "        vec![{}],", bytes.join(", ")).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_values_encoding() {
+        let values = vec![vec![0, 255], vec![], vec![16]];
+        assert_eq!(encode_values(&values), "00ff;;10");
+        assert_eq!(decode_values("00ff;;10").unwrap(), values);
+        assert!(decode_values("").unwrap().is_empty());
+        assert!(decode_values("0").is_err());
+        assert!(decode_values("zz").is_err());
+    }
+
+    #[test]
+    fn check_panic_location() {
+        let output = "running 1 test\n\
+            thread 'tests::kani_concrete_playback_check_1' panicked at src/lib.rs:10:5:\n\
+            assertion failed: x < 100\n";
+        assert_eq!(panic_location(output).as_deref(), Some("src/lib.rs:10:5"));
+        let old = "thread 'main' panicked at 'assertion failed: x < 100', src/main.rs:3:5\n";
+        assert_eq!(panic_location(old).as_deref(), Some("src/main.rs:3:5"));
+        assert_eq!(panic_location("test result: ok"), None);
+    }
+
+    #[test]
+    fn check_candidates() {
+        assert_eq!(candidates(100).collect::<Vec<_>>(), [0, 50, 75, 88, 94, 97, 99]);
+        assert_eq!(candidates(1).collect::<Vec<_>>(), [0]);
+    }
+
+    #[test]
+    fn check_shrink_values() {
+        // Fails when the first value (a u16) is at least 300, regardless of the second (a u8).
+        let values = vec![60000u16.to_le_bytes().to_vec(), vec![200]];
+        let minimized = shrink_values(values, |values| Ok(to_int(&values[0]) >= 300)).unwrap();
+        assert_eq!(minimized, [300u16.to_le_bytes().to_vec(), vec![0]]);
+    }
+
+    #[test]
+    fn check_format_values() {
+        assert_eq!(format_values(&[vec![1, 0], vec![]]), "        vec![1, 0],\n        vec![],\n");
+    }
+}
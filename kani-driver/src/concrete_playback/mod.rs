@@ -2,5 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! Implements the logic related to concrete playback
 
+pub mod batch;
+pub mod concolic;
+pub mod counterexamples;
+pub mod debug;
+pub mod minimize;
 pub mod playback;
 pub mod test_generator;
@@ -7,10 +7,15 @@
 use crate::args::playback_args::{CargoPlaybackArgs, KaniPlaybackArgs, MessageFormat};
 use crate::call_cargo::cargo_config_args;
 use crate::call_single_file::{base_rustc_flags, LibConfig};
+use crate::concrete_playback::batch::batch_playback;
+use crate::concrete_playback::debug::debug_playback;
+use crate::concrete_playback::minimize::minimize_playback;
 use crate::session::{lib_playback_folder, setup_cargo_command, InstallType};
 use crate::{session, util};
-use anyhow::Result;
+use anyhow::{bail, Result};
+use cargo_metadata::{Message, MetadataCommand};
 use std::ffi::OsString;
+use std::io::BufReader;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -25,6 +30,13 @@ pub fn playback_standalone(args: KaniPlaybackArgs) -> Result<()> {
     let artifact = build_test(&install, &args)?;
     debug!(?artifact, "playback_standalone");
 
+    if let Some(harness) = &args.playback.debug_harness {
+        return debug_playback(&[artifact], harness, &args.playback.common_opts);
+    }
+    if let Some(harness) = &args.playback.minimize_harness {
+        return minimize_playback(&[artifact], harness, &args.playback.common_opts);
+    }
+
     if !args.playback.common_opts.quiet() {
         print_artifact(&artifact, args.playback.message_format)
     }
@@ -74,6 +86,9 @@ fn build_test(install: &InstallType, args: &KaniPlaybackArgs) -> Result<PathBuf>
     rustc_args.push("--test".into());
     rustc_args.push(OsString::from(&args.input));
     rustc_args.push(format!("--crate-name={TEST_BIN_NAME}").into());
+    if args.playback.debug_harness.is_some() {
+        rustc_args.push("-g".into());
+    }
 
     if args.playback.common_opts.verbose() {
         rustc_args.push("--verbose".into());
@@ -93,10 +108,6 @@ fn build_test(install: &InstallType, args: &KaniPlaybackArgs) -> Result<PathBuf>
 
 /// Invokes cargo test using Kani compiler and the provided arguments.
 fn cargo_test(args: CargoPlaybackArgs) -> Result<()> {
-    let install = InstallType::new()?;
-    let mut cmd = setup_cargo_command()?;
-
-    let rustc_args = base_rustc_flags(LibConfig::new(lib_playback_folder()?));
     let mut cargo_args: Vec<OsString> = vec!["test".into()];
 
     if args.playback.common_opts.verbose() {
@@ -105,21 +116,72 @@ fn cargo_test(args: CargoPlaybackArgs) -> Result<()> {
         cargo_args.push("--quiet".into())
     }
 
-    if args.playback.message_format == MessageFormat::Json {
+    // When debugging or minimizing, we need to find the test executables in the artifacts built
+    // by cargo.
+    if args.playback.message_format == MessageFormat::Json
+        || args.playback.inspect_tests()
+        || args.batch
+    {
         cargo_args.push("--message-format=json".into());
     }
 
-    if args.playback.only_codegen {
+    if args.playback.only_codegen || args.playback.inspect_tests() || args.batch {
         cargo_args.push("--no-run".into());
     }
 
+    if args.batch {
+        // Build the tests of the crates that still compile, so the others can be reported.
+        cargo_args.push("--keep-going".into());
+        if args.cargo.common.package.is_empty() && !args.cargo.common.workspace {
+            cargo_args.push("--workspace".into());
+        }
+    }
+
     cargo_args.append(&mut args.cargo.to_cargo_args());
-    cargo_args.append(&mut cargo_config_args());
+
+    let cmd = cargo_test_command(cargo_args, &args.playback.test_args)?;
+    let verbosity = &args.playback.common_opts;
+    if let Some(harness) = &args.playback.debug_harness {
+        let executables = build_test_executables(verbosity, cmd)?;
+        return debug_playback(&executables, harness, verbosity);
+    }
+    if let Some(harness) = &args.playback.minimize_harness {
+        let executables = build_test_executables(verbosity, cmd)?;
+        return minimize_playback(&executables, harness, verbosity);
+    }
+
+    if args.batch {
+        let mut metadata_cmd = MetadataCommand::new();
+        if let Some(path) = &args.cargo.common.manifest_path {
+            metadata_cmd.manifest_path(path);
+        }
+        let metadata = metadata_cmd.no_deps().exec()?;
+        return batch_playback(
+            cmd,
+            metadata.workspace_root.as_std_path(),
+            metadata.target_directory.as_std_path(),
+            args.playback.message_format,
+            &args.playback.common_opts,
+        );
+    }
+
+    session::run_terminal(&args.playback.common_opts, cmd)?;
+    Ok(())
+}
+
+/// Build the `cargo test` command with the given arguments, which uses the Kani compiler and the
+/// concrete playback library.
+fn cargo_test_command(mut cargo_args: Vec<OsString>, test_args: &[String]) -> Result<Command> {
+    let install = InstallType::new()?;
+    let mut cmd = setup_cargo_command()?;
+    let rustc_args = base_rustc_flags(LibConfig::new(lib_playback_folder()?));
+    // Tests are replayed natively, so they are built for the host.
+    cargo_args.append(&mut cargo_config_args(env!("TARGET")));
 
     // These have to be the last arguments to cargo test.
-    if !args.playback.test_args.is_empty() {
+    if !test_args.is_empty() {
         cargo_args.push("--".into());
-        cargo_args.extend(args.playback.test_args.iter().map(|arg| arg.into()));
+        cargo_args.extend(test_args.iter().map(|arg| arg.into()));
     }
 
     // Arguments that will only be passed to the target package.
@@ -129,7 +191,42 @@ fn cargo_test(args: CargoPlaybackArgs) -> Result<()> {
         // https://doc.rust-lang.org/cargo/reference/environment-variables.html
         .env("CARGO_ENCODED_RUSTFLAGS", rustc_args.join(&OsString::from("\x1f")))
         .env("CARGO_TERM_PROGRESS_WHEN", "never");
+    Ok(cmd)
+}
 
-    session::run_terminal(&args.playback.common_opts, cmd)?;
-    Ok(())
+/// Build the tests of the crates selected by the given cargo arguments, and return the test
+/// executables.
+pub(crate) fn build_playback_tests(
+    mut cargo_args: Vec<OsString>,
+    verbosity: &impl Verbosity,
+) -> Result<Vec<PathBuf>> {
+    let mut args: Vec<OsString> =
+        vec!["test".into(), "--no-run".into(), "--message-format=json".into()];
+    if verbosity.quiet() {
+        args.push("--quiet".into());
+    }
+    args.append(&mut cargo_args);
+    build_test_executables(verbosity, cargo_test_command(args, &[])?)
+}
+
+/// Run the cargo command that builds the tests, and collect the test executables.
+fn build_test_executables(verbosity: &impl Verbosity, cmd: Command) -> Result<Vec<PathBuf>> {
+    let mut executables = vec![];
+    if let Some(mut cargo_process) = session::run_piped(verbosity, cmd)? {
+        let reader = BufReader::new(cargo_process.stdout.take().unwrap());
+        for message in Message::parse_stream(reader) {
+            match message? {
+                Message::CompilerArtifact(artifact) if artifact.profile.test => {
+                    executables.extend(artifact.executable.map(PathBuf::from))
+                }
+                Message::CompilerMessage(msg) => eprint!("{msg}"),
+                _ => {}
+            }
+        }
+        let status = cargo_process.wait()?;
+        if !status.success() {
+            bail!("Failed to build the concrete playback tests ({status}).");
+        }
+    }
+    Ok(executables)
 }
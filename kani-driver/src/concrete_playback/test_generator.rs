@@ -4,19 +4,22 @@
 //! Module for parsing concrete values from CBMC output traces,
 //! generating concrete playback unit tests, and adding them to the user's source code.
 
-use crate::args::ConcretePlaybackMode;
+use crate::args::{ConcretePlaybackFormat, ConcretePlaybackMode};
 use crate::call_cbmc::VerificationResult;
 use crate::cbmc_output_parser::Property;
 use crate::session::KaniSession;
 use anyhow::{Context, Result};
-use concrete_vals_extractor::{extract_harness_values, ConcreteVal};
+pub(crate) use concrete_vals_extractor::{
+    extract_harness_values, extract_trace_values, ConcreteVal,
+};
 use kani_metadata::{HarnessKind, HarnessMetadata};
 use std::collections::hash_map::DefaultHasher;
 use std::ffi::OsString;
+use std::fmt::Write as _;
 use std::fs::{read_to_string, File};
 use std::hash::{Hash, Hasher};
 use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::NamedTempFile;
 
@@ -50,6 +53,18 @@ pub fn gen_and_add_concrete_playback(
                     })
                     .collect();
                 unit_tests.dedup_by(|a, b| a.name == b.name);
+                let mut regressions: Vec<String> =
+                    if self.args.concrete_playback_format == ConcretePlaybackFormat::Proptest {
+                        harness_values
+                            .iter()
+                            .map(|(_, concrete_vals)| {
+                                format_proptest_regression(&harness.pretty_name, concrete_vals)
+                            })
+                            .collect()
+                    } else {
+                        vec![]
+                    };
+                regressions.dedup();
                 match playback_mode {
                     ConcretePlaybackMode::Print => {
                         for generated_unit_test in unit_tests.iter() {
@@ -60,6 +75,13 @@ pub fn gen_and_add_concrete_playback(
                             );
                         }
 
+                        for regression in regressions.iter() {
+                            println!(
+                                "Proptest regression entry for `{}`:\n```\n{regression}\n```",
+                                &harness.pretty_name,
+                            );
+                        }
+
                         if !unit_tests.is_empty() {
                             println!(
                                 "INFO: To automatically add the concrete playback unit test(s) to the \
@@ -90,6 +112,9 @@ pub fn gen_and_add_concrete_playback(
                             "Failed to modify source code for the file `{}`",
                             &harness.original_file
                         ));
+                        if !regressions.is_empty() {
+                            self.add_proptest_regressions(&harness.original_file, regressions)?;
+                        }
                     }
                 }
                 verification_result.generated_concrete_test = true;
@@ -189,6 +214,43 @@ fn add_tests_inplace(
         Ok(!unit_tests.is_empty())
     }
 
+    /// Append the regression entries to the proptest regressions file of the given source file,
+    /// skipping the entries that are already there.
+    fn add_proptest_regressions(&self, src_path: &str, regressions: Vec<String>) -> Result<()> {
+        let Some(path) = proptest_regressions_path(Path::new(src_path)) else {
+            println!(
+                "WARNING: Kani could not find the package of `{src_path}`, so the proptest \
+                regression entries were not saved."
+            );
+            return Ok(());
+        };
+        let existing = read_to_string(&path).unwrap_or_default();
+        let mut contents = if existing.is_empty() {
+            PROPTEST_REGRESSIONS_HEADER.to_string()
+        } else {
+            existing.clone()
+        };
+        let mut added = 0;
+        for regression in regressions {
+            if !existing.lines().any(|line| line == regression) {
+                contents.push_str(&regression);
+                contents.push('\n');
+                added += 1;
+            }
+        }
+        if added > 0 {
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(&path, contents)?;
+            if !self.args.common_args.quiet {
+                println!(
+                    "INFO: Added {added} proptest regression entries to `{}`.",
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+
     /// Run rustfmt on the given src file, and optionally on only the specific lines.
     fn run_rustfmt(
         &self,
@@ -276,17 +338,22 @@ fn gen_test_doc(harness: &HarnessMetadata, property: &Property) -> String {
 }
 
 /// Generate a formatted unit test from a list of concrete values.
-fn format_unit_test(
-    harness_name: &str,
-    concrete_vals: &[ConcreteVal],
-    doc_str: String,
-) -> UnitTest {
+/// The name of the playback test generated for the given harness and concrete values.
+pub(crate) fn playback_test_name(harness_name: &str, concrete_vals: &[ConcreteVal]) -> String {
     // Hash the concrete values along with the proof harness name.
     let mut hasher = DefaultHasher::new();
     harness_name.hash(&mut hasher);
     concrete_vals.hash(&mut hasher);
     let hash = hasher.finish();
-    let func_name = format!("kani_concrete_playback_{harness_name}_{hash}");
+    format!("kani_concrete_playback_{harness_name}_{hash}")
+}
+
+fn format_unit_test(
+    harness_name: &str,
+    concrete_vals: &[ConcreteVal],
+    doc_str: String,
+) -> UnitTest {
+    let func_name = playback_test_name(harness_name, concrete_vals);
 
     let func_before_concrete_vals = [
         doc_str,
@@ -328,6 +395,40 @@ fn format_concrete_vals(concrete_vals: &[ConcreteVal]) -> impl Iterator<Item = S
     })
 }
 
+/// The header of the regression files generated by proptest.
+const PROPTEST_REGRESSIONS_HEADER: &str = "\
+# Seeds for failure cases proptest has generated in the past. It is
+# automatically read and these particular cases re-run before any
+# novel cases are generated.
+#
+# It is recommended to check this file in to source control so that
+# everyone who runs the test benefits from these saved cases.
+";
+
+/// Generate a proptest regression entry for a counterexample.
+///
+/// Proptest cannot derive a seed from the values that should be generated, so the entry uses a
+/// seed for its pass-through RNG (`pt`), which returns the raw bytes of the counterexample in
+/// order. The structured values are recorded in the comment of the entry, like proptest does.
+fn format_proptest_regression(harness_name: &str, concrete_vals: &[ConcreteVal]) -> String {
+    let mut seed = String::new();
+    for byte in concrete_vals.iter().flat_map(|concrete_val| &concrete_val.byte_arr) {
+        write!(seed, "{byte:02x}").unwrap();
+    }
+    let values: Vec<_> = concrete_vals.iter().map(|val| val.interp_val.as_str()).collect();
+    format!("pt {seed} # kani counterexample for `{harness_name}`: [{}]", values.join(", "))
+}
+
+/// Compute the proptest regressions file for a source file, following proptest's default
+/// persistence: the file `<package>/src/foo/bar.rs` maps to
+/// `<package>/proptest-regressions/foo/bar.txt`.
+fn proptest_regressions_path(src_path: &Path) -> Option<PathBuf> {
+    let package_dir = src_path.ancestors().skip(1).find(|dir| dir.join("Cargo.toml").is_file())?;
+    let relative = src_path.strip_prefix(package_dir).ok()?;
+    let relative = relative.strip_prefix("src").unwrap_or(relative);
+    Some(package_dir.join("proptest-regressions").join(relative).with_extension("txt"))
+}
+
 /// Suppose `src_path` was `/path/to/file.txt`. This function extracts this into `/path/to` and `file.txt`.
 fn extract_parent_dir_and_src_file(src_path: &Path) -> Result<(String, String)> {
     let parent_dir_as_path = src_path.parent().unwrap();
@@ -363,6 +464,7 @@ struct UnitTest {
 /// ```
 mod concrete_vals_extractor {
     use crate::cbmc_output_parser::{CheckStatus, Property, TraceItem};
+    use crate::trace_values::{nondet_type, render_value};
 
     #[derive(Hash)]
     pub struct ConcreteVal {
@@ -386,14 +488,16 @@ pub fn extract_harness_values(result_items: &[Property]) -> Vec<(&Property, Vec<
                     .trace
                     .as_ref()
                     .expect(&format!("Missing trace for {}", property.property_name()));
-                let concrete_vals: Vec<ConcreteVal> =
-                    trace.iter().filter_map(&extract_from_trace_item).collect();
-
-                (property, concrete_vals)
+                (property, extract_trace_values(trace))
             })
             .collect()
     }
 
+    /// Extract the values returned by kani::any() calls in a trace.
+    pub fn extract_trace_values(trace: &[TraceItem]) -> Vec<ConcreteVal> {
+        trace.iter().filter_map(&extract_from_trace_item).collect()
+    }
+
     /// Extracts individual bytes returned by kani::any() calls.
     fn extract_from_trace_item(trace_item: &TraceItem) -> Option<ConcreteVal> {
         if let (Some(lhs), Some(source_location), Some(value)) =
@@ -437,7 +541,11 @@ fn extract_from_trace_item(trace_item: &TraceItem) -> Option<ConcreteVal> {
                     // For example, for the value `101` it will produce `'e'` instead of `101`.
                     // To correct this, we check if the value starts and ends with `'`, and
                     // convert the character into its ASCII value in that case.
-                    let interp_val = {
+                    // Prefer rendering the value using its Rust type, e.g., `'a'` for a char.
+                    let rendered = nondet_type(func).and_then(|typ| render_value(typ, &next_num));
+                    let interp_val = if let Some(rendered) = rendered {
+                        rendered
+                    } else {
                         let interp_val_str = interp_concrete_val.to_string();
                         if interp_val_str.starts_with('\'') && interp_val_str.ends_with('\'') {
                             let interp_num = interp_val_str.chars().nth(1).unwrap() as u8;
@@ -615,6 +723,37 @@ fn check_rustfmt_args_some_line_ranges() {
         assert_eq!(args, expected);
     }
 
+    #[test]
+    fn check_format_proptest_regression() {
+        let concrete_vals = [
+            ConcreteVal { byte_arr: vec![1, 3], interp_val: "769".to_string() },
+            ConcreteVal { byte_arr: vec![255], interp_val: "-1".to_string() },
+        ];
+        assert_eq!(
+            format_proptest_regression("check", &concrete_vals),
+            "pt 0103ff # kani counterexample for `check`: [769, -1]"
+        );
+        assert_eq!(
+            format_proptest_regression("check", &[]),
+            "pt  # kani counterexample for `check`: []"
+        );
+    }
+
+    #[test]
+    fn check_proptest_regressions_path() {
+        let package = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(package.path().join("src/foo")).unwrap();
+        std::fs::write(package.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(
+            proptest_regressions_path(&package.path().join("src/foo/bar.rs")).unwrap(),
+            package.path().join("proptest-regressions/foo/bar.txt")
+        );
+        assert_eq!(
+            proptest_regressions_path(&package.path().join("tests/check.rs")).unwrap(),
+            package.path().join("proptest-regressions/tests/check.txt")
+        );
+    }
+
     #[test]
     fn check_extract_parent_dir_and_src_file() {
         let src_path = "/path/to/file.txt";
@@ -664,4 +803,31 @@ fn check_concrete_vals_extractor() {
         assert_eq!(concrete_val.byte_arr, vec![1, 3]);
         assert_eq!(concrete_val.interp_val, "385");
     }
+
+    #[test]
+    fn check_typed_concrete_vals() {
+        let item = |function: &str, binary: &str, data: &str| TraceItem {
+            step_type: "assignment".to_string(),
+            lhs: Some("goto_symex$$return_value".to_string()),
+            source_location: Some(SourceLocation {
+                column: None,
+                file: None,
+                function: Some(function.to_string()),
+                line: None,
+            }),
+            value: Some(TraceValue {
+                binary: Some(binary.to_string()),
+                data: Some(TraceData::NonBool(data.to_string())),
+                width: Some(binary.len() as u32),
+            }),
+        };
+        let trace = [
+            item("kani::any_raw_internal::<char>", &format!("{:032b}", 'k' as u32), "107"),
+            item("kani::any_raw_internal::<f32>", &format!("{:032b}", 1.5f32.to_bits()), "1.5f"),
+            item("kani::any_raw_array::<i8, 2>", "11111111", "-1"),
+        ];
+        let values: Vec<_> =
+            extract_trace_values(&trace).into_iter().map(|val| val.interp_val).collect();
+        assert_eq!(values, ["'k'", "1.5", "-1"]);
+    }
 }
@@ -0,0 +1,334 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Summaries of the function contracts verified in a package, which let the crates that depend
+//! on the package assume those contracts instead of proving them again.
+//!
+//! With `--export-contracts`, `cargo kani` writes `kani-contracts.json` next to the manifest of
+//! each verified library package. The summary lists the functions whose `proof_for_contract`
+//! harnesses were all verified successfully, together with the Kani version and a fingerprint of
+//! the library, which covers its sources, its build script and its enabled features. The summary
+//! is signed with an HMAC-SHA-256 of its contents, keyed with the file given to `--contracts-key`.
+//!
+//! With `--assume-contracts`, the driver reads the summary of each dependency of the crates being
+//! verified and asks the compiler to replace the functions listed in the valid summaries by their
+//! contract, as `#[kani::stub_verified]` does. A summary is ignored, with a warning, if its
+//! signature doesn't match the key, if it was written by another Kani version, or if the library
+//! changed since. Only the holders of the key can write a summary that is accepted, so the key
+//! must be kept as private as the verification results it vouches for.
+//!
+//! Note that the dependencies are still compiled. Only their contract harnesses aren't run again.
+
+use crate::call_cargo::packages_to_verify;
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::util;
+use crate::version::KANI_VERSION;
+use anyhow::{bail, Context, Result};
+use cargo_metadata::{Metadata, Package, Target};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// The name of the summary file, in the root directory of the package.
+const SUMMARY_FILE: &str = "kani-contracts.json";
+
+/// The contracts verified in a library crate.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ContractSummary {
+    crate_name: String,
+    kani_version: String,
+    /// The fingerprint of the library when its contracts were verified.
+    fingerprint: String,
+    /// The crate-qualified path of each function whose contract was verified.
+    contracts: BTreeSet<String>,
+    /// The HMAC-SHA-256 of all the other fields.
+    signature: String,
+}
+
+impl ContractSummary {
+    fn new(
+        crate_name: &str,
+        fingerprint: String,
+        contracts: BTreeSet<String>,
+        key: &[u8],
+    ) -> ContractSummary {
+        let mut summary = ContractSummary {
+            crate_name: crate_name.to_string(),
+            kani_version: KANI_VERSION.to_string(),
+            fingerprint,
+            contracts,
+            signature: String::new(),
+        };
+        summary.signature = summary.sign(key);
+        summary
+    }
+
+    /// The HMAC of all the fields but the signature.
+    fn mac(&self, key: &[u8]) -> Hmac<Sha256> {
+        let fields = (&self.crate_name, &self.kani_version, &self.fingerprint, &self.contracts);
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+        mac.update(&serde_json::to_vec(&fields).unwrap());
+        mac
+    }
+
+    fn sign(&self, key: &[u8]) -> String {
+        hex::encode(self.mac(key).finalize().into_bytes())
+    }
+
+    /// Check that the summary may be assumed for the library with the given fingerprint.
+    fn validate(&self, fingerprint: &str, key: &[u8]) -> Result<(), String> {
+        // The signature is compared in constant time, so it can't be guessed byte by byte.
+        let signature = hex::decode(&self.signature).unwrap_or_default();
+        if self.mac(key).verify_slice(&signature).is_err() {
+            Err("its signature doesn't match its contents and the contracts key".to_string())
+        } else if self.kani_version != KANI_VERSION {
+            Err(format!("it was written by Kani {}", self.kani_version))
+        } else if self.fingerprint != fingerprint {
+            Err("the crate changed since its contracts were verified".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The fingerprint of a library, i.e., a hash of the Rust files under the directory of its root
+/// module, of its build script, and of the features enabled for it.
+fn fingerprint(src_dir: &Path, build_script: Option<&Path>, features: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for file in util::rust_files(src_dir) {
+        let relative = file.strip_prefix(src_dir).unwrap_or(&file);
+        let contents = std::fs::read(&file).unwrap_or_default();
+        update_field(&mut hasher, relative.to_string_lossy().as_bytes());
+        update_field(&mut hasher, contents);
+    }
+    if let Some(build_script) = build_script {
+        update_field(&mut hasher, "build.rs");
+        update_field(&mut hasher, std::fs::read(build_script).unwrap_or_default());
+    }
+    let mut features = features.to_vec();
+    features.sort();
+    update_field(&mut hasher, features.join(","));
+    hex::encode(hasher.finalize())
+}
+
+/// Hash a byte string along with its length, so that consecutive fields can't be confused.
+fn update_field(hasher: &mut Sha256, data: impl AsRef<[u8]>) {
+    let data = data.as_ref();
+    hasher.update((data.len() as u64).to_be_bytes());
+    hasher.update(data);
+}
+
+fn package_fingerprint(metadata: &Metadata, package: &Package, lib: &Target) -> String {
+    let build_script = package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| kind == "custom-build"))
+        .map(|target| target.src_path.as_std_path());
+    let features = metadata
+        .resolve
+        .as_ref()
+        .and_then(|resolve| resolve.nodes.iter().find(|node| node.id == package.id))
+        .map(|node| node.features.as_slice())
+        .unwrap_or_default();
+    fingerprint(&src_dir(lib), build_script, features)
+}
+
+/// The library target of a package, if any.
+fn lib_target(package: &Package) -> Option<&Target> {
+    package
+        .targets
+        .iter()
+        .find(|target| target.kind.iter().any(|kind| kind == "lib" || kind == "rlib"))
+}
+
+fn src_dir(target: &Target) -> PathBuf {
+    target.src_path.parent().map(|dir| dir.as_std_path().to_path_buf()).unwrap_or_default()
+}
+
+fn summary_path(package: &Package) -> PathBuf {
+    package.manifest_path.parent().unwrap().join(SUMMARY_FILE).into()
+}
+
+/// Write the summary of the contracts verified in each library package of the project.
+///
+/// A contract counts as verified if all the harnesses that check it were verified, and they all
+/// succeeded.
+pub(crate) fn export_contracts(
+    session: &KaniSession,
+    project: &Project,
+    results: &[HarnessResult],
+) -> Result<()> {
+    let Some(metadata) = &project.cargo_metadata else { return Ok(()) };
+    let key = session.contracts_key()?;
+    let status: HashMap<(&str, &str), VerificationStatus> = results
+        .iter()
+        .map(|result| {
+            let harness = result.harness;
+            ((harness.crate_name.as_str(), harness.pretty_name.as_str()), result.result.status)
+        })
+        .collect();
+    for package in packages_to_verify(&session.args, metadata)? {
+        let Some(target) = lib_target(package) else { continue };
+        let crate_name = target.name.replace('-', "_");
+        let Some(crate_metadata) = project.metadata.iter().find(|m| m.crate_name == crate_name)
+        else {
+            continue;
+        };
+        let mut verified: BTreeMap<&str, bool> = BTreeMap::new();
+        for harness in &crate_metadata.proof_harnesses {
+            let Some(path) = &harness.attributes.contract_path else { continue };
+            let success = status.get(&(harness.crate_name.as_str(), harness.pretty_name.as_str()))
+                == Some(&VerificationStatus::Success);
+            *verified.entry(path).or_insert(true) &= success;
+        }
+        if verified.is_empty() {
+            continue;
+        }
+        let contracts = verified
+            .into_iter()
+            .filter(|(_, success)| *success)
+            .map(|(path, _)| path.to_string())
+            .collect();
+        let fingerprint = package_fingerprint(metadata, package, target);
+        let summary = ContractSummary::new(&crate_name, fingerprint, contracts, &key);
+        let path = summary_path(package);
+        std::fs::write(&path, serde_json::to_string_pretty(&summary)?)
+            .with_context(|| format!("Failed to write `{}`", path.display()))?;
+        if !session.args.common_args.quiet {
+            println!(
+                "[info] {} verified contract(s) of `{}` written to {}",
+                summary.contracts.len(),
+                package.name,
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+impl KaniSession {
+    /// The contracts that the crates being verified may assume, from the summaries of their
+    /// dependencies. Summaries are only read with `--assume-contracts`.
+    pub(crate) fn assumed_contracts(&self, metadata: &Metadata) -> Result<Vec<String>> {
+        if !self.args.assume_contracts {
+            return Ok(vec![]);
+        }
+        let key = self.contracts_key()?;
+        let verified: Vec<_> =
+            packages_to_verify(&self.args, metadata)?.iter().map(|package| &package.id).collect();
+        let libs: Vec<_> = metadata
+            .packages
+            .iter()
+            .filter(|package| !verified.contains(&&package.id))
+            .filter_map(|package| Some((package, lib_target(package)?)))
+            .collect();
+        let mut contracts = vec![];
+        for (package, target) in &libs {
+            let path = summary_path(package);
+            if !path.exists() {
+                continue;
+            }
+            let crate_name = target.name.replace('-', "_");
+            // The paths in the summaries don't identify the version of the crate.
+            let versions =
+                libs.iter().filter(|(_, other)| other.name.replace('-', "_") == crate_name).count();
+            if versions > 1 {
+                util::warning(&format!(
+                    "Ignoring the contracts verified in `{}` because several versions of the \
+                    crate are used.",
+                    package.name
+                ));
+                continue;
+            }
+            let fingerprint = package_fingerprint(metadata, package, target);
+            match read_summary(&path, &crate_name, &fingerprint, &key) {
+                Ok(summary) => {
+                    debug!(package=%package.name, contracts=?summary.contracts, "assumed_contracts");
+                    if !self.args.common_args.quiet {
+                        println!(
+                            "[info] Assuming {} verified contract(s) of `{}`",
+                            summary.contracts.len(),
+                            package.name
+                        );
+                    }
+                    contracts.extend(summary.contracts);
+                }
+                Err(reason) => util::warning(&format!(
+                    "Ignoring the contracts verified in `{}` because {reason}.",
+                    package.name
+                )),
+            }
+        }
+        Ok(contracts)
+    }
+
+    /// The key used to sign and check the summaries, read from the `--contracts-key` file.
+    fn contracts_key(&self) -> Result<Vec<u8>> {
+        let path = self.args.contracts_key.as_ref().context("Missing `--contracts-key`")?;
+        let key = std::fs::read(path)
+            .with_context(|| format!("Failed to read the contracts key `{}`", path.display()))?;
+        if key.is_empty() {
+            bail!("The contracts key `{}` is empty", path.display());
+        }
+        Ok(key)
+    }
+}
+
+fn read_summary(
+    path: &Path,
+    crate_name: &str,
+    fingerprint: &str,
+    key: &[u8],
+) -> Result<ContractSummary, String> {
+    let summary: ContractSummary = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .ok_or_else(|| format!("`{}` isn't a valid summary", path.display()))?;
+    if summary.crate_name != crate_name {
+        return Err(format!("its summary is for crate `{}`", summary.crate_name));
+    }
+    summary.validate(fingerprint, key)?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_summary_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "pub fn div(a: u32, b: u32) -> u32 { a / b }").unwrap();
+        let key = b"secret";
+        let lib_fingerprint = || fingerprint(&src, None, &[]);
+
+        let contracts = BTreeSet::from(["my_crate::div".to_string()]);
+        let summary = ContractSummary::new("my_crate", lib_fingerprint(), contracts, key);
+        let path = dir.path().join(SUMMARY_FILE);
+        std::fs::write(&path, serde_json::to_string(&summary).unwrap()).unwrap();
+        assert_eq!(read_summary(&path, "my_crate", &lib_fingerprint(), key), Ok(summary));
+        assert!(read_summary(&path, "other_crate", &lib_fingerprint(), key).is_err());
+        assert!(read_summary(&path, "my_crate", &lib_fingerprint(), b"other").is_err());
+
+        // Edits to the summary or to the library invalidate it.
+        let mut edited = read_summary(&path, "my_crate", &lib_fingerprint(), key).unwrap();
+        edited.contracts.insert("my_crate::mul".to_string());
+        assert!(edited.validate(&lib_fingerprint(), key).is_err());
+        let features = ["std".to_string()];
+        assert!(read_summary(&path, "my_crate", &fingerprint(&src, None, &features), key).is_err());
+        let build_script = dir.path().join("build.rs");
+        std::fs::write(&build_script, "fn main() {}").unwrap();
+        let with_build = fingerprint(&src, Some(&build_script), &[]);
+        assert!(read_summary(&path, "my_crate", &with_build, key).is_err());
+        std::fs::write(src.join("lib.rs"), "pub fn div(a: u32, b: u32) -> u32 { a }").unwrap();
+        assert!(read_summary(&path, "my_crate", &lib_fingerprint(), key).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Save a snapshot of the merged coverage of a project, and compare later runs against it.
+//!
+//! A snapshot records the lines covered by any harness. When comparing against one, the lines
+//! that were covered in the snapshot but aren't covered anymore are reported as regressions.
+//! Note that lines are identified by their number, so editing a file may shift its lines and
+//! cause spurious regressions. Snapshots should be updated whenever the sources change.
+
+use crate::coverage::merge::{line_ranges, MergedCoverage};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// The lines covered by any harness, as stored in a snapshot file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CoverageSnapshot {
+    /// The version of Kani that generated the snapshot.
+    pub kani_version: String,
+    /// Map from source file to its covered lines.
+    pub files: BTreeMap<String, BTreeSet<u32>>,
+}
+
+impl CoverageSnapshot {
+    pub fn new(merged: &MergedCoverage) -> CoverageSnapshot {
+        let files = merged
+            .files
+            .iter()
+            .map(|(file, coverage)| {
+                let covered = coverage
+                    .lines
+                    .iter()
+                    .filter(|(_, hits)| **hits > 0)
+                    .map(|(line, _)| *line)
+                    .collect();
+                (file.clone(), covered)
+            })
+            .collect();
+        CoverageSnapshot { kani_version: env!("CARGO_PKG_VERSION").to_string(), files }
+    }
+
+    pub fn read(path: &Path) -> Result<CoverageSnapshot> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open coverage snapshot `{}`", path.display()))?;
+        let reader = BufReader::new(file);
+        serde_json::from_reader(reader)
+            .with_context(|| format!("Failed to parse coverage snapshot `{}`", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// The differences between the current coverage and a snapshot.
+#[derive(Debug, Default)]
+pub struct CoverageDiff {
+    /// Map from source file to the lines that are no longer covered.
+    pub lost: BTreeMap<String, Vec<u32>>,
+    /// The number of lines that are covered now, but weren't covered in the snapshot.
+    pub gained: usize,
+}
+
+impl CoverageDiff {
+    pub fn new(baseline: &CoverageSnapshot, current: &CoverageSnapshot) -> CoverageDiff {
+        let empty = BTreeSet::new();
+        let mut diff = CoverageDiff::default();
+        for (file, covered) in &baseline.files {
+            let now = current.files.get(file).unwrap_or(&empty);
+            let lost: Vec<_> = covered.difference(now).copied().collect();
+            if !lost.is_empty() {
+                diff.lost.insert(file.clone(), lost);
+            }
+        }
+        for (file, covered) in &current.files {
+            let before = baseline.files.get(file).unwrap_or(&empty);
+            diff.gained += covered.difference(before).count();
+        }
+        diff
+    }
+
+    /// The number of lines that lost proof coverage.
+    pub fn lost_lines(&self) -> usize {
+        self.lost.values().map(Vec::len).sum()
+    }
+
+    pub fn print(&self) {
+        println!("Coverage comparison:");
+        for (file, lines) in &self.lost {
+            println!(" - {file}: lost coverage of lines {}", line_ranges(lines));
+        }
+        println!(
+            "{} lines lost proof coverage, {} lines gained proof coverage.",
+            self.lost_lines(),
+            self.gained
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(files: &[(&str, &[u32])]) -> CoverageSnapshot {
+        CoverageSnapshot {
+            kani_version: "0.0.0".to_string(),
+            files: files
+                .iter()
+                .map(|(file, lines)| (file.to_string(), lines.iter().copied().collect()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn check_coverage_diff() {
+        let baseline = snapshot(&[("a.rs", &[1, 2, 3, 4]), ("removed.rs", &[1])]);
+        let current = snapshot(&[("a.rs", &[1, 4, 5]), ("new.rs", &[1, 2])]);
+
+        let diff = CoverageDiff::new(&baseline, &current);
+        assert_eq!(diff.lost["a.rs"], [2, 3]);
+        assert_eq!(diff.lost["removed.rs"], [1]);
+        assert_eq!(diff.lost_lines(), 3);
+        assert_eq!(diff.gained, 3);
+
+        let diff = CoverageDiff::new(&current, &current);
+        assert_eq!(diff.lost_lines(), 0);
+        assert_eq!(diff.gained, 0);
+    }
+
+    #[test]
+    fn check_snapshot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        snapshot(&[("a.rs", &[1, 2])]).write(&path).unwrap();
+        let read = CoverageSnapshot::read(&path).unwrap();
+        assert_eq!(read.files["a.rs"], BTreeSet::from([1, 2]));
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Export coverage results in the Cobertura XML format, which is the format
+//! understood by the coverage visualization of CI services such as GitLab and
+//! Jenkins.
+//!
+//! Each source file is reported as a class of the package that corresponds to
+//! its folder. The line and branch coverage are computed from the merged
+//! results of all harnesses (see [`MergedCoverage`]).
+
+use crate::coverage::cov_results::CoverageResults;
+use crate::coverage::merge::{FileCoverage, MergedCoverage};
+use crate::report::escape_markup as escape;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Write a Cobertura report with the coverage results of all harnesses into `path`.
+pub fn write_report<'a>(
+    path: &Path,
+    results: impl IntoIterator<Item = &'a CoverageResults>,
+) -> Result<()> {
+    std::fs::write(path, render(&MergedCoverage::new(results)))?;
+    Ok(())
+}
+
+/// Render merged coverage results as a Cobertura XML document.
+pub fn render(merged: &MergedCoverage) -> String {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let source = std::env::current_dir().map(|dir| dir.display().to_string()).unwrap_or_default();
+    cobertura_xml(merged, &source, timestamp)
+}
+
+/// The number of valid and covered lines and branches.
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    lines: usize,
+    lines_covered: usize,
+    branches: usize,
+    branches_covered: usize,
+}
+
+impl Counts {
+    fn from_file(coverage: &FileCoverage) -> Counts {
+        Counts {
+            lines: coverage.lines.len(),
+            lines_covered: coverage.covered_lines(),
+            branches: coverage.branches.len(),
+            branches_covered: coverage.branches.values().filter(|hits| **hits > 0).count(),
+        }
+    }
+
+    fn add(&mut self, other: Counts) {
+        self.lines += other.lines;
+        self.lines_covered += other.lines_covered;
+        self.branches += other.branches;
+        self.branches_covered += other.branches_covered;
+    }
+
+    /// The `line-rate` and `branch-rate` attributes of an element.
+    fn rates(&self) -> String {
+        format!(
+            "line-rate=\"{:.4}\" branch-rate=\"{:.4}\"",
+            rate(self.lines_covered, self.lines),
+            rate(self.branches_covered, self.branches)
+        )
+    }
+}
+
+fn rate(covered: usize, total: usize) -> f64 {
+    if total == 0 { 1.0 } else { covered as f64 / total as f64 }
+}
+
+/// Generate the Cobertura XML document for the merged coverage results.
+fn cobertura_xml(merged: &MergedCoverage, source: &str, timestamp: u64) -> String {
+    let mut packages: BTreeMap<String, Vec<(&String, &FileCoverage)>> = BTreeMap::new();
+    for (file, coverage) in &merged.files {
+        let package = Path::new(file).parent().map(|dir| dir.display().to_string());
+        packages.entry(package.unwrap_or_default()).or_default().push((file, coverage));
+    }
+
+    let mut total = Counts::default();
+    let mut body = String::new();
+    for (package, files) in packages {
+        let mut counts = Counts::default();
+        let mut classes = String::new();
+        for (file, coverage) in files {
+            let file_counts = Counts::from_file(coverage);
+            counts.add(file_counts);
+            write_class(&mut classes, file, coverage, file_counts);
+        }
+        total.add(counts);
+        writeln!(
+            body,
+            "    <package name=\"{}\" {} complexity=\"0\">",
+            escape(&package),
+            counts.rates()
+        )
+        .unwrap();
+        body.push_str("      <classes>\n");
+        body.push_str(&classes);
+        body.push_str("      </classes>\n");
+        body.push_str("    </package>\n");
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n",
+    );
+    writeln!(
+        xml,
+        "<coverage {} lines-covered=\"{}\" lines-valid=\"{}\" branches-covered=\"{}\" \
+        branches-valid=\"{}\" complexity=\"0\" version=\"kani {}\" timestamp=\"{timestamp}\">",
+        total.rates(),
+        total.lines_covered,
+        total.lines,
+        total.branches_covered,
+        total.branches,
+        env!("CARGO_PKG_VERSION"),
+    )
+    .unwrap();
+    writeln!(xml, "  <sources>\n    <source>{}</source>\n  </sources>", escape(source)).unwrap();
+    xml.push_str("  <packages>\n");
+    xml.push_str(&body);
+    xml.push_str("  </packages>\n");
+    xml.push_str("</coverage>\n");
+    xml
+}
+
+fn write_class(xml: &mut String, file: &str, coverage: &FileCoverage, counts: Counts) {
+    writeln!(
+        xml,
+        "        <class name=\"{0}\" filename=\"{0}\" {1} complexity=\"0\">",
+        escape(file),
+        counts.rates()
+    )
+    .unwrap();
+
+    xml.push_str("          <methods>\n");
+    for (function, (_, hits)) in &coverage.functions {
+        let rate = if *hits > 0 { 1 } else { 0 };
+        writeln!(
+            xml,
+            "            <method name=\"{}\" signature=\"\" line-rate=\"{rate}\" \
+            branch-rate=\"1\" complexity=\"0\"/>",
+            escape(function)
+        )
+        .unwrap();
+    }
+    xml.push_str("          </methods>\n");
+
+    // Branches are reported in the line where their region starts.
+    let mut branches: BTreeMap<u32, (usize, usize)> = BTreeMap::new();
+    for ((start, _, _), hits) in &coverage.branches {
+        let (covered, total) = branches.entry(start.0).or_default();
+        *covered += usize::from(*hits > 0);
+        *total += 1;
+    }
+
+    xml.push_str("          <lines>\n");
+    for (line, hits) in &coverage.lines {
+        if let Some((covered, total)) = branches.get(line) {
+            writeln!(
+                xml,
+                "            <line number=\"{line}\" hits=\"{hits}\" branch=\"true\" \
+                condition-coverage=\"{}% ({covered}/{total})\"/>",
+                covered * 100 / total
+            )
+            .unwrap();
+        } else {
+            writeln!(xml, "            <line number=\"{line}\" hits=\"{hits}\" branch=\"false\"/>")
+                .unwrap();
+        }
+    }
+    xml.push_str("          </lines>\n");
+    xml.push_str("        </class>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::CheckStatus;
+    use crate::coverage::cov_results::{CoverageCheck, CoverageRegion, CoverageTerm};
+
+    fn check(
+        file: &str,
+        start: (u32, u32),
+        end: (u32, u32),
+        branch: Option<bool>,
+        covered: bool,
+    ) -> CoverageCheck {
+        let region = CoverageRegion { file: file.to_string(), start, end };
+        let status = if covered { CheckStatus::Covered } else { CheckStatus::Uncovered };
+        CoverageCheck::new("foo".to_string(), CoverageTerm::Counter(0), region, branch, status)
+    }
+
+    #[test]
+    fn check_cobertura_xml() {
+        let mut data: BTreeMap<String, Vec<CoverageCheck>> = BTreeMap::new();
+        data.insert(
+            "src/lib.rs".to_string(),
+            vec![
+                check("src/lib.rs", (1, 1), (3, 2), None, true),
+                check("src/lib.rs", (2, 8), (2, 14), Some(true), true),
+                check("src/lib.rs", (2, 8), (2, 14), Some(false), false),
+            ],
+        );
+        data.insert(
+            "src/a&b.rs".to_string(),
+            vec![check("src/a&b.rs", (1, 1), (1, 9), None, false)],
+        );
+        let merged = MergedCoverage::new([&CoverageResults::new(data)]);
+
+        let xml = cobertura_xml(&merged, "/project", 42);
+        assert!(xml.contains(
+            "line-rate=\"0.7500\" branch-rate=\"0.5000\" lines-covered=\"3\" lines-valid=\"4\" \
+            branches-covered=\"1\" branches-valid=\"2\""
+        ));
+        assert!(xml.contains("timestamp=\"42\""));
+        assert!(xml.contains("<source>/project</source>"));
+        assert!(xml.contains("<package name=\"src\" line-rate=\"0.7500\" branch-rate=\"0.5000\""));
+        assert!(xml.contains("<class name=\"src/a&amp;b.rs\" filename=\"src/a&amp;b.rs\""));
+        assert!(xml.contains("<line number=\"1\" hits=\"0\" branch=\"false\"/>"));
+        assert!(xml.contains(
+            "<line number=\"2\" hits=\"1\" branch=\"true\" condition-coverage=\"50% (1/2)\"/>"
+        ));
+        assert!(xml.contains("<method name=\"foo\" signature=\"\" line-rate=\"1\""));
+        assert!(xml.ends_with("  </packages>\n</coverage>\n"));
+    }
+}
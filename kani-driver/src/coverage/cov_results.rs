@@ -15,6 +15,18 @@ impl CoverageResults {
     pub fn new(data: BTreeMap<String, Vec<CoverageCheck>>) -> Self {
         Self { data }
     }
+
+    /// The branch checks whose direction was never taken.
+    pub fn untaken_branches(&self) -> Vec<&CoverageCheck> {
+        let mut untaken: Vec<_> = self
+            .data
+            .values()
+            .flatten()
+            .filter(|check| check.branch.is_some() && !check.is_covered())
+            .collect();
+        untaken.sort_by_key(|check| (&check.region, check.branch));
+        untaken
+    }
 }
 
 impl fmt::Display for CoverageResults {
@@ -31,9 +43,13 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             for (function, checks) in checks_by_function {
                 writeln!(f, "{file} ({function})")?;
                 let mut sorted_checks: Vec<CoverageCheck> = checks.to_vec();
-                sorted_checks.sort_by(|a, b| a.region.start.cmp(&b.region.start));
+                sorted_checks.sort_by_key(|check| (check.region.start, check.branch));
                 for check in sorted_checks.iter() {
-                    writeln!(f, " * {} {}", check.region, check.status)?;
+                    if let Some(direction) = check.branch {
+                        writeln!(f, " * {} ({direction} branch) {}", check.region, check.status)?;
+                    } else {
+                        writeln!(f, " * {} {}", check.region, check.status)?;
+                    }
                 }
                 writeln!(f)?;
             }
@@ -47,6 +63,10 @@ pub struct CoverageCheck {
     pub function: String,
     term: CoverageTerm,
     pub region: CoverageRegion,
+    /// The branch direction counted by this check, if it's a branch check.
+    /// Otherwise, the check counts the executions of a code region.
+    #[serde(default)]
+    pub branch: Option<bool>,
     status: CheckStatus,
 }
 
@@ -55,9 +75,15 @@ pub fn new(
         function: String,
         term: CoverageTerm,
         region: CoverageRegion,
+        branch: Option<bool>,
         status: CheckStatus,
     ) -> Self {
-        Self { function, term, region, status }
+        Self { function, term, region, branch, status }
+    }
+
+    /// Whether the region of this check was covered.
+    pub fn is_covered(&self) -> bool {
+        self.status == CheckStatus::Covered
     }
 }
 
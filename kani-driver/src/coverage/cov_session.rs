@@ -4,7 +4,10 @@
 use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 
+use crate::args::CoverageFormat;
+use crate::coverage::{cobertura, lcov};
 use crate::harness_runner::HarnessResult;
 use crate::project::Project;
 use crate::KaniSession;
@@ -26,7 +29,7 @@ pub fn save_coverage_metadata(&self, project: &Project, stamp: &String) -> Resul
     }
 
     fn save_coverage_metadata_cargo(&self, project: &Project, stamp: &String) -> Result<()> {
-        let build_target = env!("TARGET");
+        let build_target = self.build_target();
         let metadata = self.cargo_metadata(build_target)?;
         let target_dir = self
             .args
@@ -36,7 +39,7 @@ fn save_coverage_metadata_cargo(&self, project: &Project, stamp: &String) -> Res
             .clone()
             .join("kani");
 
-        let outdir = target_dir.join(build_target).join(format!("kanicov_{stamp}"));
+        let outdir = target_dir.join(self.target_name()).join(format!("kanicov_{stamp}"));
 
         // Generally we don't expect this directory to exist, but there's no
         // reason to delete it if it does.
@@ -94,7 +97,7 @@ fn save_coverage_metadata_standalone(&self, project: &Project, stamp: &String) -
     pub fn save_coverage_results(
         &self,
         project: &Project,
-        results: &Vec<HarnessResult>,
+        results: &[HarnessResult],
         stamp: &String,
     ) -> Result<()> {
         if self.args.target_dir.is_some() {
@@ -106,10 +109,10 @@ pub fn save_coverage_results(
 
     pub fn save_coverage_results_cargo(
         &self,
-        results: &Vec<HarnessResult>,
+        results: &[HarnessResult],
         stamp: &String,
     ) -> Result<()> {
-        let build_target = env!("TARGET");
+        let build_target = self.build_target();
         let metadata = self.cargo_metadata(build_target)?;
         let target_dir = self
             .args
@@ -119,7 +122,7 @@ pub fn save_coverage_results_cargo(
             .clone()
             .join("kani");
 
-        let outdir = target_dir.join(build_target).join(format!("kanicov_{stamp}"));
+        let outdir = target_dir.join(self.target_name()).join(format!("kanicov_{stamp}"));
 
         // This directory should have been created by `save_coverage_metadata`,
         // so now we expect it to exist.
@@ -127,25 +130,13 @@ pub fn save_coverage_results_cargo(
             bail!("directory associated to coverage run does not exist")
         }
 
-        for harness_res in results {
-            let harness_name = harness_res.harness.mangled_name.clone();
-            let kaniraw_name = format!("{harness_name}_kaniraw");
-            let file_name = outdir.join(kaniraw_name).with_extension("json");
-            let mut cov_file = File::create(file_name)?;
-
-            let cov_results = &harness_res.result.coverage_results.clone().unwrap();
-            let serialized_data = serde_json::to_string(&cov_results)?;
-            cov_file.write_all(serialized_data.as_bytes())?;
-        }
-
-        println!("[info] Coverage results saved to {}", &outdir.display());
-        Ok(())
+        self.write_coverage_results(&outdir, results)
     }
 
     pub fn save_coverage_results_standalone(
         &self,
         project: &Project,
-        results: &Vec<HarnessResult>,
+        results: &[HarnessResult],
         stamp: &String,
     ) -> Result<()> {
         let input = project.input.clone().unwrap().canonicalize().unwrap();
@@ -158,19 +149,39 @@ pub fn save_coverage_results_standalone(
             bail!("directory associated to coverage run does not exist")
         }
 
-        for harness_res in results {
-            let harness_name = harness_res.harness.mangled_name.clone();
-            let kaniraw_name = format!("{harness_name}_kaniraw");
-            let file_name = outdir.join(kaniraw_name).with_extension("json");
-            let mut cov_file = File::create(file_name)?;
+        self.write_coverage_results(&outdir, results)
+    }
 
-            let cov_results = &harness_res.result.coverage_results.clone().unwrap();
-            let serialized_data = serde_json::to_string(&cov_results)?;
-            cov_file.write_all(serialized_data.as_bytes())?;
+    /// Writes the coverage results into `outdir` using the format selected by the user.
+    fn write_coverage_results(&self, outdir: &Path, results: &[HarnessResult]) -> Result<()> {
+        match self.args.coverage_format {
+            CoverageFormat::Raw => {
+                for harness_res in results {
+                    let harness_name = harness_res.harness.mangled_name.clone();
+                    let kaniraw_name = format!("{harness_name}_kaniraw");
+                    let file_name = outdir.join(kaniraw_name).with_extension("json");
+                    let mut cov_file = File::create(file_name)?;
+
+                    let cov_results = &harness_res.result.coverage_results.clone().unwrap();
+                    let serialized_data = serde_json::to_string(&cov_results)?;
+                    cov_file.write_all(serialized_data.as_bytes())?;
+                }
+            }
+            CoverageFormat::Lcov => {
+                let cov_results = results
+                    .iter()
+                    .map(|harness_res| harness_res.result.coverage_results.as_ref().unwrap());
+                lcov::write_report(&outdir.join("lcov.info"), cov_results)?;
+            }
+            CoverageFormat::Cobertura => {
+                let cov_results = results
+                    .iter()
+                    .map(|harness_res| harness_res.result.coverage_results.as_ref().unwrap());
+                cobertura::write_report(&outdir.join("cobertura.xml"), cov_results)?;
+            }
         }
 
         println!("[info] Coverage results saved to {}", &outdir.display());
-
         Ok(())
     }
 }
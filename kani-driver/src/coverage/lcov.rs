@@ -0,0 +1,149 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Export coverage results as an LCOV tracefile, so they can be merged with the
+//! coverage data from other tools (e.g., `cargo llvm-cov`).
+//!
+//! LCOV supports line, function and branch coverage, which are computed from
+//! the merged results of all harnesses (see [`MergedCoverage`]).
+
+use crate::coverage::cov_results::CoverageResults;
+use crate::coverage::merge::MergedCoverage;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Generate an LCOV tracefile with the coverage results of all harnesses.
+pub fn lcov_report<'a>(results: impl IntoIterator<Item = &'a CoverageResults>) -> String {
+    render(&MergedCoverage::new(results))
+}
+
+/// Render merged coverage results as an LCOV tracefile.
+pub fn render(merged: &MergedCoverage) -> String {
+    let mut report = String::new();
+    for (file, coverage) in &merged.files {
+        // Tools that merge tracefiles match source files by path, so we use
+        // absolute paths when possible.
+        let path = fs::canonicalize(file).map_or(file.to_string(), |p| p.display().to_string());
+        writeln!(report, "TN:").unwrap();
+        writeln!(report, "SF:{path}").unwrap();
+        for (function, (line, _)) in &coverage.functions {
+            writeln!(report, "FN:{line},{function}").unwrap();
+        }
+        for (function, (_, hits)) in &coverage.functions {
+            writeln!(report, "FNDA:{hits},{function}").unwrap();
+        }
+        let functions_hit = coverage.functions.values().filter(|(_, hits)| *hits > 0).count();
+        writeln!(report, "FNF:{}", coverage.functions.len()).unwrap();
+        writeln!(report, "FNH:{functions_hit}").unwrap();
+        // Both directions of a branch share the same region, which we use to
+        // number the blocks.
+        let mut blocks = BTreeMap::new();
+        for ((start, end, branch), hits) in &coverage.branches {
+            let next_block = blocks.len();
+            let block = *blocks.entry((start, end)).or_insert(next_block);
+            writeln!(report, "BRDA:{},{block},{branch},{hits}", start.0).unwrap();
+        }
+        let branches_hit = coverage.branches.values().filter(|hits| **hits > 0).count();
+        writeln!(report, "BRF:{}", coverage.branches.len()).unwrap();
+        writeln!(report, "BRH:{branches_hit}").unwrap();
+        for (line, hits) in &coverage.lines {
+            writeln!(report, "DA:{line},{hits}").unwrap();
+        }
+        writeln!(report, "LF:{}", coverage.lines.len()).unwrap();
+        writeln!(report, "LH:{}", coverage.covered_lines()).unwrap();
+        writeln!(report, "end_of_record").unwrap();
+    }
+    report
+}
+
+/// Write an LCOV tracefile with the coverage results of all harnesses into `path`.
+pub fn write_report<'a>(
+    path: &Path,
+    results: impl IntoIterator<Item = &'a CoverageResults>,
+) -> Result<()> {
+    fs::write(path, lcov_report(results))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::CheckStatus;
+    use crate::coverage::cov_results::{CoverageCheck, CoverageRegion, CoverageTerm};
+
+    fn check(function: &str, start: (u32, u32), end: (u32, u32), covered: bool) -> CoverageCheck {
+        branch_check(function, start, end, None, covered)
+    }
+
+    fn branch_check(
+        function: &str,
+        start: (u32, u32),
+        end: (u32, u32),
+        branch: Option<bool>,
+        covered: bool,
+    ) -> CoverageCheck {
+        // A file that doesn't exist, so the report keeps its relative path.
+        let region = CoverageRegion { file: "src/covered.rs".to_string(), start, end };
+        let status = if covered { CheckStatus::Covered } else { CheckStatus::Uncovered };
+        CoverageCheck::new(function.to_string(), CoverageTerm::Counter(0), region, branch, status)
+    }
+
+    fn results(checks: Vec<CoverageCheck>) -> CoverageResults {
+        CoverageResults::new(BTreeMap::from([("src/covered.rs".to_string(), checks)]))
+    }
+
+    #[test]
+    fn check_lcov_report() {
+        // `foo` spans lines 1 to 5, and the branch in lines 3 to 4 is only
+        // covered by the first harness. `bar` is never covered.
+        let first = results(vec![
+            check("foo", (1, 1), (5, 2), true),
+            check("foo", (3, 10), (4, 6), true),
+            check("bar", (7, 1), (8, 2), false),
+        ]);
+        let second =
+            results(vec![check("foo", (1, 1), (5, 2), true), check("foo", (3, 10), (4, 6), false)]);
+
+        let report = lcov_report([&first, &second]);
+        let expected = "TN:\n\
+            SF:src/covered.rs\n\
+            FN:7,bar\n\
+            FN:1,foo\n\
+            FNDA:0,bar\n\
+            FNDA:2,foo\n\
+            FNF:2\n\
+            FNH:1\n\
+            BRF:0\n\
+            BRH:0\n\
+            DA:1,2\n\
+            DA:2,2\n\
+            DA:3,1\n\
+            DA:4,1\n\
+            DA:5,2\n\
+            DA:7,0\n\
+            DA:8,0\n\
+            LF:7\n\
+            LH:5\n\
+            end_of_record\n";
+        assert_eq!(report, expected);
+    }
+
+    #[test]
+    fn check_lcov_branches() {
+        // The false branch of the condition in line 2 is never taken.
+        let results = results(vec![
+            check("foo", (1, 1), (4, 2), true),
+            branch_check("foo", (2, 8), (2, 14), Some(true), true),
+            branch_check("foo", (2, 8), (2, 14), Some(false), false),
+            check("foo", (2, 15), (3, 6), true),
+        ]);
+
+        let report = lcov_report([&results]);
+        assert!(report.contains("BRDA:2,0,0,1\nBRDA:2,0,1,0\nBRF:2\nBRH:1\n"), "{report}");
+        // Branch checks don't affect line coverage.
+        assert!(report.contains("DA:1,1\nDA:2,1\nDA:3,1\nDA:4,1\nLF:4\nLH:4\n"), "{report}");
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Merge the coverage results of multiple harnesses, possibly from different
+//! crates and verification runs, into a single project-level report.
+//!
+//! The coverage of a line is determined by the regions that start on it or, if
+//! there are none, by the innermost region that contains it. Since Kani only
+//! knows whether a region was covered or not, the hit count of a line (or
+//! function, or branch) is the number of harnesses that covered it.
+
+use crate::args::coverage_args::{CargoCoverageArgs, CoverageReportFormat};
+use crate::args_toml::Severity;
+use crate::coverage::baseline::{CoverageDiff, CoverageSnapshot};
+use crate::coverage::cov_results::{CoverageCheck, CoverageResults};
+use crate::coverage::{cobertura, lcov};
+use crate::session::KaniSession;
+use crate::util::warning;
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// The start and end of a branch region, and the branch number. The true
+/// branch is number 0, and the false one is number 1.
+pub type Branch = ((u32, u32), (u32, u32), u32);
+
+/// The coverage of a source file aggregated over multiple harnesses.
+#[derive(Debug, Default)]
+pub struct FileCoverage {
+    /// Map from function name to its first line and hit count.
+    pub functions: BTreeMap<String, (u32, u32)>,
+    /// Map from line number to its hit count.
+    pub lines: BTreeMap<u32, u32>,
+    /// Map from branch to its hit count.
+    pub branches: BTreeMap<Branch, u32>,
+}
+
+impl FileCoverage {
+    fn add_harness_checks(&mut self, checks: &[CoverageCheck]) {
+        let (branches, checks): (Vec<CoverageCheck>, Vec<CoverageCheck>) =
+            checks.iter().cloned().partition(|check| check.branch.is_some());
+        for check in branches {
+            let key = (check.region.start, check.region.end, u32::from(!check.branch.unwrap()));
+            *self.branches.entry(key).or_default() += u32::from(check.is_covered());
+        }
+
+        let mut functions: BTreeMap<&str, Vec<&CoverageCheck>> = BTreeMap::new();
+        for check in &checks {
+            functions.entry(check.function.as_str()).or_default().push(check);
+        }
+        for (function, checks) in functions {
+            let first_line = checks.iter().map(|check| check.region.start.0).min().unwrap();
+            let covered = checks.iter().any(|check| check.is_covered());
+            let entry = self.functions.entry(function.to_string()).or_insert((first_line, 0));
+            entry.0 = entry.0.min(first_line);
+            entry.1 += u32::from(covered);
+        }
+
+        let first = checks.iter().map(|check| check.region.start.0).min().unwrap_or(1);
+        let last = checks.iter().map(|check| check.region.end.0).max().unwrap_or(0);
+        for line in first..=last {
+            if let Some(covered) = line_status(&checks, line) {
+                *self.lines.entry(line).or_default() += u32::from(covered);
+            }
+        }
+    }
+
+    /// The lines that weren't covered by any harness.
+    pub fn uncovered_lines(&self) -> Vec<u32> {
+        self.lines.iter().filter(|(_, hits)| **hits == 0).map(|(line, _)| *line).collect()
+    }
+
+    pub fn covered_lines(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+}
+
+/// Whether a line was covered, or `None` if no region contains it.
+fn line_status(checks: &[CoverageCheck], line: u32) -> Option<bool> {
+    let starting: Vec<_> = checks.iter().filter(|check| check.region.start.0 == line).collect();
+    if !starting.is_empty() {
+        return Some(starting.iter().any(|check| check.is_covered()));
+    }
+    checks
+        .iter()
+        .filter(|check| check.region.start.0 < line && line <= check.region.end.0)
+        .max_by_key(|check| check.region.start)
+        .map(|check| check.is_covered())
+}
+
+/// The coverage of all source files aggregated over multiple harnesses.
+#[derive(Debug, Default)]
+pub struct MergedCoverage {
+    /// The number of harnesses whose results were merged.
+    pub harnesses: usize,
+    /// Map from source file to its coverage.
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+impl MergedCoverage {
+    pub fn new<'a>(results: impl IntoIterator<Item = &'a CoverageResults>) -> Self {
+        let mut merged = MergedCoverage::default();
+        for harness_results in results {
+            merged.harnesses += 1;
+            for (file, checks) in &harness_results.data {
+                merged.files.entry(file.clone()).or_default().add_harness_checks(checks);
+            }
+        }
+        merged
+    }
+
+    /// Render a summary of the line coverage of each file.
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        writeln!(summary, "Merged coverage of {} harnesses:", self.harnesses).unwrap();
+        let (mut total, mut covered) = (0, 0);
+        for (file, coverage) in &self.files {
+            let lines = coverage.lines.len();
+            let covered_lines = coverage.covered_lines();
+            total += lines;
+            covered += covered_lines;
+            writeln!(
+                summary,
+                "{file}: {covered_lines}/{lines} lines covered ({:.2}%)",
+                percentage(covered_lines, lines)
+            )
+            .unwrap();
+            let uncovered = coverage.uncovered_lines();
+            if !uncovered.is_empty() {
+                writeln!(summary, " - Uncovered lines: {}", line_ranges(&uncovered)).unwrap();
+            }
+        }
+        writeln!(
+            summary,
+            "Total: {covered}/{total} lines covered ({:.2}%)",
+            percentage(covered, total)
+        )
+        .unwrap();
+        summary
+    }
+}
+
+fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 { 100.0 } else { part as f64 * 100.0 / total as f64 }
+}
+
+/// Format a sorted list of lines as comma separated ranges, e.g. `1, 3-5`.
+pub fn line_ranges(lines: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+    for &line in lines {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| if start == end { start.to_string() } else { format!("{start}-{end}") })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Read the raw coverage results in the given paths. Folders are searched
+/// (non-recursively) for raw coverage results.
+fn read_raw_results(paths: &[PathBuf]) -> Result<Vec<CoverageResults>> {
+    let mut files = vec![];
+    for path in paths {
+        if path.is_dir() {
+            let mut entries: Vec<_> = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<_, _>>()?;
+            entries.retain(|entry| is_raw_results_file(entry));
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    files
+        .iter()
+        .map(|file| {
+            let reader = BufReader::new(File::open(file)?);
+            serde_json::from_reader(reader).with_context(|| {
+                format!("Failed to parse coverage results file `{}`", file.display())
+            })
+        })
+        .collect()
+}
+
+fn is_raw_results_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name.to_string_lossy().ends_with("_kaniraw.json"))
+}
+
+/// Find the folder with the coverage results of the latest verification run.
+///
+/// The folders are named `kanicov_<date>`, so the latest run is the last one
+/// in lexicographic order.
+fn latest_coverage_dir(session: &KaniSession) -> Result<PathBuf> {
+    let build_target = session.build_target();
+    let target_dir = match &session.args.target_dir {
+        Some(target_dir) => target_dir.clone(),
+        None => session.cargo_metadata(build_target)?.target_directory.into(),
+    };
+    let outdir = target_dir.join("kani").join(session.target_name());
+    let latest = fs::read_dir(&outdir)
+        .ok()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("kanicov_"))
+        })
+        .max();
+    match latest {
+        Some(dir) => Ok(dir),
+        None => bail!(
+            "no coverage results found in `{}`. Run `cargo kani --coverage -Z source-coverage` first",
+            outdir.display()
+        ),
+    }
+}
+
+/// Implements `cargo kani coverage --merge`.
+pub fn merge_coverage(session: KaniSession, args: CargoCoverageArgs) -> Result<()> {
+    let paths =
+        if args.paths.is_empty() { vec![latest_coverage_dir(&session)?] } else { args.paths };
+    let results = read_raw_results(&paths)?;
+    if results.is_empty() {
+        bail!("no coverage results found. Make sure they were saved in the `raw` format");
+    }
+
+    let merged = MergedCoverage::new(&results);
+    let report = match args.format {
+        CoverageReportFormat::Summary => merged.summary(),
+        CoverageReportFormat::Lcov => lcov::render(&merged),
+        CoverageReportFormat::Cobertura => cobertura::render(&merged),
+    };
+    if let Some(output) = &args.output {
+        fs::write(output, report)?;
+        if !session.args.common_args.quiet {
+            println!("[info] Merged coverage report saved to {}", output.display());
+        }
+    } else {
+        print!("{report}");
+    }
+
+    let snapshot = CoverageSnapshot::new(&merged);
+    if let Some(path) = &args.save_snapshot {
+        snapshot.write(path)?;
+        if !session.args.common_args.quiet {
+            println!("[info] Coverage snapshot saved to {}", path.display());
+        }
+    }
+    if let Some(path) = &args.baseline {
+        let diff = CoverageDiff::new(&CoverageSnapshot::read(path)?, &snapshot);
+        diff.print();
+        let severity = session.severities.get("coverage_regression");
+        if args.fail_on_regression && diff.lost_lines() > 0 {
+            if severity.is_some_and(|severity| *severity != Severity::Error) {
+                warning("the coverage regressed, which is not an error in the configuration");
+                return Ok(());
+            }
+            // Failure exit code without additional error message
+            drop(session);
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::CheckStatus;
+    use crate::coverage::cov_results::{CoverageRegion, CoverageTerm};
+
+    fn check(file: &str, start: u32, end: u32, covered: bool) -> CoverageCheck {
+        let region = CoverageRegion { file: file.to_string(), start: (start, 1), end: (end, 2) };
+        let status = if covered { CheckStatus::Covered } else { CheckStatus::Uncovered };
+        CoverageCheck::new("foo".to_string(), CoverageTerm::Counter(0), region, None, status)
+    }
+
+    fn results(checks: Vec<CoverageCheck>) -> CoverageResults {
+        let mut data: BTreeMap<String, Vec<CoverageCheck>> = BTreeMap::new();
+        for check in checks {
+            data.entry(check.region.file.clone()).or_default().push(check);
+        }
+        CoverageResults::new(data)
+    }
+
+    #[test]
+    fn check_merged_summary() {
+        // Each harness covers a different branch, and `b.rs` is only
+        // reachable from the second harness.
+        let first = results(vec![
+            check("a.rs", 1, 10, true),
+            check("a.rs", 2, 3, true),
+            check("a.rs", 5, 6, false),
+        ]);
+        let second = results(vec![
+            check("a.rs", 1, 10, true),
+            check("a.rs", 2, 3, false),
+            check("a.rs", 5, 6, true),
+            check("b.rs", 1, 2, true),
+            check("b.rs", 4, 4, false),
+        ]);
+
+        let merged = MergedCoverage::new([&first, &second]);
+        assert_eq!(merged.harnesses, 2);
+        assert!(merged.files["a.rs"].uncovered_lines().is_empty());
+        assert_eq!(merged.files["b.rs"].uncovered_lines(), [4]);
+        let expected = "Merged coverage of 2 harnesses:\n\
+            a.rs: 10/10 lines covered (100.00%)\n\
+            b.rs: 2/3 lines covered (66.67%)\n \
+            - Uncovered lines: 4\n\
+            Total: 12/13 lines covered (92.31%)\n";
+        assert_eq!(merged.summary(), expected);
+    }
+
+    #[test]
+    fn check_line_ranges() {
+        assert_eq!(line_ranges(&[]), "");
+        assert_eq!(line_ranges(&[1, 3, 4, 5, 7, 8]), "1, 3-5, 7-8");
+    }
+
+    #[test]
+    fn check_read_raw_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let raw = serde_json::to_string(&results(vec![check("a.rs", 1, 2, true)])).unwrap();
+        fs::write(dir.path().join("first_kaniraw.json"), &raw).unwrap();
+        fs::write(dir.path().join("second_kaniraw.json"), &raw).unwrap();
+        fs::write(dir.path().join("kanicov_kanimap.json"), "[]").unwrap();
+
+        let results = read_raw_results(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(results.len(), 2);
+        let single = read_raw_results(&[dir.path().join("first_kaniraw.json")]).unwrap();
+        assert_eq!(single.len(), 1);
+    }
+}
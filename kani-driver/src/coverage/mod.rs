@@ -1,5 +1,9 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+pub mod baseline;
+pub mod cobertura;
 pub mod cov_results;
 pub mod cov_session;
+pub mod lcov;
+pub mod merge;
@@ -0,0 +1,354 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani --doc`, which verifies the examples in the documentation of a package.
+//!
+//! Like `rustdoc --test`, we extract the code blocks of the doc comments, but only the ones meant
+//! to be verified: the blocks tagged `kani`, and the Rust blocks that contain `kani::proof`. Each
+//! block becomes a module of a generated package that depends on the packages to verify, which we
+//! then build and verify like any other package.
+
+use crate::args::cargo::CargoCommonArgs;
+use crate::call_cargo::packages_to_verify;
+use crate::project;
+use crate::session::KaniSession;
+use crate::util;
+use anyhow::{Context, Result};
+use cargo_metadata::Package;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The directory of the generated package, relative to the target directory.
+const DOC_HARNESSES_DIR: &str = "kani/doc-harnesses";
+
+/// A code block of a doc comment to verify.
+#[derive(Debug, PartialEq, Eq)]
+struct DocBlock {
+    /// The file of the doc comment, relative to the package root.
+    file: PathBuf,
+    /// The line of the first line of code, starting from 1.
+    line: usize,
+    /// The code, including the lines hidden from the documentation.
+    code: String,
+    should_panic: bool,
+}
+
+/// Verify the documentation examples of the packages selected by the arguments.
+pub(crate) fn verify_doc_harnesses(mut session: KaniSession) -> Result<()> {
+    let metadata = session.cargo_metadata(session.build_target())?;
+    let target_dir = session
+        .args
+        .target_dir
+        .clone()
+        .unwrap_or_else(|| metadata.target_directory.clone().into_std_path_buf());
+    let package_dir = std::env::current_dir()?.join(&target_dir).join(DOC_HARNESSES_DIR);
+
+    let mut dependencies = toml::Table::new();
+    let mut lib_rs = String::from("// Generated by `cargo kani --doc`.\n#![allow(unused)]\n");
+    let mut num_blocks = 0;
+    for package in packages_to_verify(&session.args, &metadata)? {
+        let Some(lib) = package
+            .targets
+            .iter()
+            .find(|target| target.kind.iter().any(|kind| kind == "lib" || kind == "rlib"))
+        else {
+            continue;
+        };
+        let package_root = package.manifest_path.parent().unwrap().as_std_path();
+        let mut blocks = vec![];
+        for file in util::rust_files(lib.src_path.parent().unwrap().as_std_path()) {
+            let content = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read `{}`", file.display()))?;
+            let relative = file.strip_prefix(package_root).unwrap_or(&file);
+            blocks.extend(extract_doc_blocks(relative, &content));
+        }
+        if blocks.is_empty() {
+            continue;
+        }
+        num_blocks += blocks.len();
+        let lib_name = lib.name.replace('-', "_");
+        dependencies.insert(lib_name.clone(), dependency(&session, package).into());
+        writeln!(lib_rs, "\nmod doc_{lib_name} {{").unwrap();
+        for block in &blocks {
+            lib_rs.push_str(&block_module(block));
+        }
+        lib_rs.push_str("}\n");
+    }
+
+    if num_blocks == 0 {
+        if !session.args.common_args.quiet {
+            println!("No documentation harnesses found.");
+        }
+        return Ok(());
+    }
+    let lock_file = metadata.workspace_root.join("Cargo.lock");
+    write_package(&package_dir, dependencies, &lib_rs, lock_file.as_std_path())?;
+
+    // Build the generated package in the target directory of the workspace, so its dependencies
+    // are only built once.
+    session.args.target_dir = Some(target_dir);
+    let mut cargo = CargoCommonArgs::default();
+    cargo.manifest_path = Some(package_dir.join("Cargo.toml"));
+    session.args.cargo = cargo;
+    let project = project::cargo_project(&session, false)?;
+    if session.args.only_codegen { Ok(()) } else { crate::verify_project(project, session) }
+}
+
+/// The dependency of the generated package on a package to verify, with the features selected on
+/// the command line.
+fn dependency(session: &KaniSession, package: &Package) -> toml::Table {
+    let cargo = &session.args.cargo;
+    let features: Vec<String> = if cargo.all_features {
+        package.features.keys().cloned().collect()
+    } else {
+        cargo
+            .features()
+            .into_iter()
+            .filter_map(|feature| match feature.split_once('/') {
+                Some((name, feature)) => (name == package.name).then(|| feature.to_string()),
+                None => package.features.contains_key(&feature).then_some(feature),
+            })
+            .collect()
+    };
+    let mut dependency = toml::Table::new();
+    dependency.insert("package".into(), package.name.clone().into());
+    dependency.insert("path".into(), package.manifest_path.parent().unwrap().to_string().into());
+    dependency.insert("features".into(), features.into());
+    dependency.insert("default-features".into(), (!cargo.no_default_features).into());
+    dependency
+}
+
+/// Write the generated package. We start from the lock file of the workspace, if any, so the
+/// dependencies have the versions that the package was tested with.
+fn write_package(
+    dir: &Path,
+    dependencies: toml::Table,
+    lib_rs: &str,
+    lock_file: &Path,
+) -> Result<()> {
+    let mut package = toml::Table::new();
+    package.insert("name".into(), "kani-doc-harnesses".into());
+    package.insert("version".into(), "0.0.0".into());
+    package.insert("edition".into(), "2021".into());
+    package.insert("publish".into(), false.into());
+    let mut manifest = toml::Table::new();
+    manifest.insert("package".into(), package.into());
+    manifest.insert("dependencies".into(), dependencies.into());
+    // Keep the package out of the workspace that contains the target directory.
+    manifest.insert("workspace".into(), toml::Table::new().into());
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), toml::to_string(&manifest)?)?;
+    fs::write(dir.join("src/lib.rs"), lib_rs)?;
+    if lock_file.exists() {
+        fs::copy(lock_file, dir.join("Cargo.lock"))?;
+    }
+    Ok(())
+}
+
+/// Extract the code blocks to verify from the doc comments (`///` and `//!`) of a file.
+fn extract_doc_blocks(file: &Path, content: &str) -> Vec<DocBlock> {
+    let mut blocks = vec![];
+    // The fence, info string, first line and code of the block we're in, if any.
+    let mut current: Option<(String, String, usize, Vec<String>)> = None;
+    for (idx, line) in content.lines().enumerate() {
+        let Some(doc) = doc_line(line) else {
+            // A block ends with its doc comment, even if the fence isn't closed.
+            if let Some((_, info, first_line, code)) = current.take() {
+                blocks.extend(doc_block(file, &info, first_line, &code));
+            }
+            continue;
+        };
+        let trimmed = doc.trim();
+        match &mut current {
+            None => {
+                if let Some(fence) = fence(trimmed) {
+                    let info = trimmed[fence.len()..].to_string();
+                    current = Some((fence, info, idx + 2, vec![]));
+                }
+            }
+            Some((fence, _, _, code)) => {
+                if trimmed.starts_with(fence.as_str())
+                    && trimmed.chars().all(|c| fence.starts_with(c))
+                {
+                    let (_, info, first_line, code) = current.take().unwrap();
+                    blocks.extend(doc_block(file, &info, first_line, &code));
+                } else {
+                    code.push(doc.to_string());
+                }
+            }
+        }
+    }
+    if let Some((_, info, first_line, code)) = current {
+        blocks.extend(doc_block(file, &info, first_line, &code));
+    }
+    blocks
+}
+
+/// The content of a doc comment line, without the comment marker and the space that follows it.
+fn doc_line(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let doc = if let Some(doc) = line.strip_prefix("///") {
+        // `////` is a regular comment.
+        if doc.starts_with('/') {
+            return None;
+        }
+        doc
+    } else {
+        line.strip_prefix("//!")?
+    };
+    Some(doc.strip_prefix(' ').unwrap_or(doc))
+}
+
+/// The fence that opens a code block, e.g., ```` ``` ```` or `~~~`.
+fn fence(line: &str) -> Option<String> {
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence: String = line.chars().take_while(|c| *c == marker).collect();
+    (fence.len() >= 3).then_some(fence)
+}
+
+/// Create the block to verify from a code block, if it is meant to be verified.
+fn doc_block(file: &Path, info: &str, line: usize, code: &[String]) -> Option<DocBlock> {
+    let tags: Vec<&str> =
+        info.split(|c: char| c == ',' || c.is_whitespace()).filter(|tag| !tag.is_empty()).collect();
+    if tags.iter().any(|tag| *tag == "ignore" || *tag == "compile_fail") {
+        return None;
+    }
+    let is_rust = tags.iter().all(|tag| {
+        matches!(*tag, "rust" | "should_panic" | "no_run" | "kani")
+            || tag.starts_with("edition")
+            || tag.starts_with("ignore-")
+    });
+    let code = code.iter().fold(String::new(), |mut code, line| {
+        writeln!(code, "{}", unhide(line)).unwrap();
+        code
+    });
+    let selected = tags.contains(&"kani")
+        || (is_rust && !tags.contains(&"test_harness") && code.contains("kani::proof"));
+    selected.then(|| DocBlock {
+        file: file.to_path_buf(),
+        line,
+        code,
+        should_panic: tags.contains(&"should_panic"),
+    })
+}
+
+/// Show a line hidden from the documentation, e.g., `# use std::ops::Add;`, like rustdoc does.
+fn unhide(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    if trimmed == "#" {
+        ""
+    } else if let Some(rest) = trimmed.strip_prefix("# ") {
+        rest
+    } else if trimmed.starts_with("##") {
+        &trimmed[1..]
+    } else {
+        line
+    }
+}
+
+/// The module of a block, named after its location so the harness names tell where they are
+/// documented, e.g., `doc_parser::src_lib_rs_42::harness`.
+///
+/// Blocks that define their own harnesses are used as is. Other blocks are the body of a harness,
+/// or define a `main` function that the harness calls. Like in rustdoc, a block that ends with
+/// `Ok::<...>(...)` may use the `?` operator, and its error fails the harness.
+fn block_module(block: &DocBlock) -> String {
+    let name: String = format!("{}_{}", block.file.display(), block.line)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let mut module = format!("/// {}:{}\nmod {name} {{\n", block.file.display(), block.line);
+    if block.code.contains("#[kani::proof") {
+        module.push_str(&block.code);
+    } else {
+        let body = if block.code.contains("fn main(") {
+            module.push_str(&block.code);
+            "main();\n".to_string()
+        } else if block.code.lines().last().is_some_and(|line| line.trim().starts_with("Ok::<")) {
+            format!("(|| {{\n{}}})().unwrap();\n", block.code)
+        } else {
+            block.code.clone()
+        };
+        module.push_str("#[kani::proof]\n");
+        if block.should_panic {
+            module.push_str("#[kani::should_panic]\n");
+        }
+        write!(module, "fn harness() {{\n{body}}}\n").unwrap();
+    }
+    module.push_str("}\n");
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+//! ```kani
+//! let x: u8 = kani::any();
+//! assert!(x.checked_add(1).is_some() || x == u8::MAX);
+//! ```
+
+/// Not verified, since it's a plain doc test.
+/// ```
+/// assert_eq!(parser::parse("1"), Some(1));
+/// ```
+///
+/// ```text
+/// kani::proof
+/// ```
+///
+/// ```
+/// # use parser::parse;
+/// #[kani::proof]
+/// fn check_parse() {
+///     assert!(parse("").is_none());
+/// }
+/// ```
+///
+/// ```kani,ignore
+/// unreachable!();
+/// ```
+pub fn parse(input: &str) -> Option<u32> {
+    todo!()
+}
+"#;
+
+    #[test]
+    fn check_extract_doc_blocks() {
+        let blocks = extract_doc_blocks(Path::new("src/lib.rs"), SOURCE);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].line, 3);
+        assert_eq!(
+            blocks[0].code,
+            "let x: u8 = kani::any();\nassert!(x.checked_add(1).is_some() || x == u8::MAX);\n"
+        );
+        assert_eq!(blocks[1].line, 17);
+        assert!(blocks[1].code.starts_with("use parser::parse;\n#[kani::proof]\n"));
+    }
+
+    #[test]
+    fn check_block_module() {
+        let block = DocBlock {
+            file: PathBuf::from("src/lib.rs"),
+            line: 3,
+            code: "let x: u8 = kani::any();\n".to_string(),
+            should_panic: true,
+        };
+        assert_eq!(
+            block_module(&block),
+            "/// src/lib.rs:3\nmod src_lib_rs_3 {\n#[kani::proof]\n#[kani::should_panic]\n\
+            fn harness() {\nlet x: u8 = kani::any();\n}\n}\n"
+        );
+    }
+
+    #[test]
+    fn check_unhide() {
+        assert_eq!(unhide("# use std::ops::Add;"), "use std::ops::Add;");
+        assert_eq!(unhide("#"), "");
+        assert_eq!(unhide("## not hidden"), "# not hidden");
+        assert_eq!(unhide("#[kani::proof]"), "#[kani::proof]");
+    }
+}
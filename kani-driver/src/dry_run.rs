@@ -0,0 +1,134 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--dry-run`, which prints the verification plan of a project instead of running it:
+//! the harnesses selected by the filters, the settings each of them would be verified with, and
+//! the artifacts that would be produced.
+//!
+//! The crates must still be compiled, since the harnesses are found by `kani-compiler`, but the
+//! goto binaries are neither linked nor verified.
+
+use crate::args::VerificationArgs;
+use crate::call_cbmc::{
+    resolve_memory_limit, resolve_solver_value, resolve_timeout_value, resolve_unwind_value,
+    solver_name,
+};
+use crate::project::Project;
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::artifact::convert_type;
+use kani_metadata::{ArtifactType, HarnessKind, HarnessMetadata};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Print the verification plan for the harnesses of `project` selected by the session arguments.
+pub(crate) fn print_plan(session: &KaniSession, project: &Project) -> Result<()> {
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    print!("{}", format_plan(&session.args, &project.outdir, &harnesses));
+    Ok(())
+}
+
+fn format_plan(args: &VerificationArgs, outdir: &Path, harnesses: &[&HarnessMetadata]) -> String {
+    let mut plan = String::new();
+    for harness in harnesses {
+        writeln!(
+            plan,
+            "Harness {} ({}:{})",
+            harness.pretty_name, harness.original_file, harness.original_start_line
+        )
+        .unwrap();
+        let attributes = &harness.attributes;
+        if let HarnessKind::ProofForContract { target_fn } = &attributes.kind {
+            writeln!(plan, "  - checks the contract of: {target_fn}").unwrap();
+        }
+        for stub in &attributes.stubs {
+            writeln!(plan, "  - stub: {} -> {}", stub.original, stub.replacement).unwrap();
+        }
+        for function in &attributes.verified_stubs {
+            writeln!(plan, "  - contract used as stub: {function}").unwrap();
+        }
+        let unwind = resolve_unwind_value(args, harness);
+        writeln!(plan, "  - unwind: {}", unwind.map_or("none".to_string(), |u| u.to_string()))
+            .unwrap();
+        for (loop_id, bound) in &attributes.loop_unwinds {
+            writeln!(plan, "  - unwind loop {loop_id}: {bound}").unwrap();
+        }
+        let solver = resolve_solver_value(args, &attributes.solver);
+        writeln!(plan, "  - solver: {}", solver_name(solver)).unwrap();
+        if let Some(timeout) = resolve_timeout_value(args, harness) {
+            writeln!(plan, "  - timeout: {timeout}s").unwrap();
+        }
+        if let Some(memory_limit) = resolve_memory_limit(args, harness) {
+            writeln!(plan, "  - memory limit: {memory_limit} MiB").unwrap();
+        }
+        let checks = args.checks.for_harness(&attributes.checks);
+        let on_off = |enabled| if enabled { "on" } else { "off" };
+        writeln!(
+            plan,
+            "  - checks: memory_safety = {}, overflow = {}, unwinding = {}",
+            on_off(checks.memory_safety),
+            on_off(checks.overflow),
+            on_off(checks.unwinding)
+        )
+        .unwrap();
+        if attributes.should_panic {
+            writeln!(plan, "  - expected to panic").unwrap();
+        }
+        if attributes.check_leaks {
+            writeln!(plan, "  - checks for memory leaks").unwrap();
+        }
+        if attributes.havoc_statics {
+            writeln!(plan, "  - starts from arbitrary mutable statics").unwrap();
+        }
+        if let Some(goto_file) = &harness.goto_file {
+            let binary = convert_type(goto_file, ArtifactType::SymTabGoto, ArtifactType::Goto);
+            writeln!(plan, "  - goto binary: {}", binary.display()).unwrap();
+        }
+        if args.visualize {
+            let report_dir =
+                outdir.join(format!("report-{}", harness.pretty_name.replace("::", "-")));
+            writeln!(plan, "  - report: {}", report_dir.display()).unwrap();
+        }
+    }
+    let count = harnesses.len();
+    writeln!(plan, "Would verify {count} harness{}.", if count == 1 { "" } else { "es" }).unwrap();
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::StandaloneArgs;
+    use crate::metadata::tests::mock_proof_harness;
+    use clap::Parser;
+    use kani_metadata::Stub;
+
+    #[test]
+    fn check_format_plan() {
+        let args = StandaloneArgs::try_parse_from(["kani", "x.rs", "--default-unwind", "2"])
+            .unwrap()
+            .verify_opts;
+        let mut contract = mock_proof_harness("check_foo", Some(3), None, None);
+        contract.attributes.kind = HarnessKind::ProofForContract { target_fn: "foo".into() };
+        contract.attributes.stubs =
+            vec![Stub { original: "bar".into(), replacement: "mock_bar".into() }];
+        contract.attributes.checks.overflow = Some(false);
+        let other = mock_proof_harness("check_other", None, None, None);
+
+        let plan = format_plan(&args, Path::new("/tmp"), &[&contract, &other]);
+        assert_eq!(
+            plan,
+            "Harness check_foo (<unknown>:0)\n  \
+            - checks the contract of: foo\n  \
+            - stub: bar -> mock_bar\n  \
+            - unwind: 3\n  \
+            - solver: cadical\n  \
+            - checks: memory_safety = on, overflow = off, unwinding = on\n\
+            Harness check_other (<unknown>:0)\n  \
+            - unwind: 2\n  \
+            - solver: cadical\n  \
+            - checks: memory_safety = on, overflow = on, unwinding = on\n\
+            Would verify 2 harnesses.\n"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--endianness`, which verifies the code as compiled for a variant of the target with
+//! another byte order.
+//!
+//! The variant of a target is a custom target spec that only differs from it by its byte order.
+//! `cargo build-dev --endianness big` writes it to `targets/<target>-be.json` (or `-le` for
+//! `little`) in the Kani installation, and builds the Kani libraries for it. The code under
+//! verification, including the standard library, is then compiled for the variant, so
+//! `cfg(target_endian)`, transmutes and pointer casts are all consistent with its byte order.
+//!
+//! `--endianness both` runs Kani again once with each byte order, and fails if either run fails.
+
+use crate::args::{Endianness, VerificationArgs};
+use crate::session::{base_folder, target_name};
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use std::ffi::OsString;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+/// The environment variable that selects the byte order of each run of `--endianness both`.
+const ENDIANNESS_VAR: &str = "KANI_ENDIANNESS";
+
+/// Whether this run should verify the harnesses with both byte orders by running Kani again.
+pub fn verifies_both(args: &VerificationArgs) -> bool {
+    args.endianness == Endianness::Both && std::env::var_os(ENDIANNESS_VAR).is_none()
+}
+
+/// Run Kani again with the same arguments for each byte order, and fail if verification fails
+/// with either.
+pub fn verify_both_endiannesses() -> Result<()> {
+    let mut args = std::env::args_os();
+    // The invocation type depends on the name Kani was invoked as, so we keep it.
+    let arg0 = args.next().unwrap_or_default();
+    let args: Vec<OsString> = args.collect();
+    let exe = std::env::current_exe()?;
+    let mut failed = vec![];
+    for endianness in [Endianness::Little, Endianness::Big] {
+        println!("Verifying with {endianness} endianness...");
+        let status = Command::new(&exe)
+            .arg0(&arg0)
+            .args(&args)
+            .env(ENDIANNESS_VAR, endianness.to_string())
+            .status()?;
+        if !status.success() {
+            failed.push(endianness.to_string());
+        }
+    }
+    if !failed.is_empty() {
+        bail!("verification failed with {} endianness", failed.join(" and "));
+    }
+    Ok(())
+}
+
+/// Apply the byte order selected by the run of `--endianness both` that this process is.
+pub fn apply_endianness_override(args: &mut VerificationArgs) -> Result<()> {
+    if let Some(endianness) = std::env::var_os(ENDIANNESS_VAR) {
+        let endianness = endianness.to_string_lossy();
+        match Endianness::from_str(&endianness, false) {
+            Ok(endianness) => args.endianness = endianness,
+            Err(_) => bail!("invalid `{ENDIANNESS_VAR}` value `{endianness}`"),
+        }
+    }
+    Ok(())
+}
+
+/// The target that the code under verification is compiled for, which is the variant of the
+/// target with the byte order of `--endianness`, if any.
+pub fn resolve_build_target(args: &VerificationArgs) -> Result<String> {
+    let target = args.target_triple.as_deref().unwrap_or(env!("TARGET")); // see build.rs
+    let suffix = match args.endianness {
+        // `both` is handled by running Kani again with each byte order.
+        Endianness::Native | Endianness::Both => return Ok(target.to_string()),
+        Endianness::Little => "le",
+        Endianness::Big => "be",
+    };
+    let spec =
+        base_folder()?.join("targets").join(format!("{}-{suffix}.json", target_name(target)));
+    if !spec.exists() {
+        let target_arg = match &args.target_triple {
+            Some(target) => format!(" --target {target}"),
+            None => String::new(),
+        };
+        bail!(
+            "The Kani libraries for the {} endian variant of target `{target}` were not found. \
+            Build them with `cargo build-dev{target_arg} --endianness {}`.",
+            args.endianness,
+            args.endianness
+        );
+    }
+    Ok(spec.to_string_lossy().into_owned())
+}
@@ -0,0 +1,175 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--message-format json`, which streams the progress of a run as JSON objects on
+//! stdout, one per line, so IDEs and other tools can show results while Kani runs. This is
+//! analogous to `cargo build --message-format json`.
+//!
+//! Each object has a `reason` field with the kind of event. The human readable output is disabled
+//! in this mode, so stdout only has events.
+
+use crate::args::MessageFormat;
+use crate::call_cbmc::VerificationResult;
+use crate::cbmc_output_parser::{CheckStatus, Property, SourceLocation, TraceItem};
+use crate::report::baseline::BaselineStatus;
+use crate::session::KaniSession;
+use kani_metadata::HarnessMetadata;
+use serde::Serialize;
+
+/// An event of a run.
+#[derive(Debug, Serialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+pub enum Event<'a> {
+    /// The compilation of the crates started.
+    BuildStarted,
+    /// The crates were compiled, and these harnesses were found.
+    BuildFinished {
+        harnesses: usize,
+    },
+    HarnessStarted {
+        harness: &'a str,
+        crate_name: &'a str,
+        file: &'a str,
+        line: usize,
+    },
+    /// The result of one property of a harness.
+    PropertyResult {
+        harness: &'a str,
+        property: String,
+        class: String,
+        description: &'a str,
+        status: CheckStatus,
+        location: &'a SourceLocation,
+    },
+    /// The trace of a property, i.e., the counterexample of a failure or the witness of a cover.
+    TraceAvailable {
+        harness: &'a str,
+        property: String,
+        steps: Vec<TraceStep<'a>>,
+    },
+    HarnessFinished {
+        harness: &'a str,
+        status: BaselineStatus,
+        failed: bool,
+        runtime_ms: u128,
+    },
+    /// All harnesses were verified.
+    RunFinished {
+        succeeded: usize,
+        failed: usize,
+        total: usize,
+        success: bool,
+    },
+}
+
+/// A step of a trace that has a source location.
+#[derive(Debug, Serialize)]
+pub struct TraceStep<'a> {
+    step_type: &'a str,
+    location: &'a SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lhs: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+}
+
+impl<'a> TraceStep<'a> {
+    fn from_item(item: &'a TraceItem) -> Option<TraceStep<'a>> {
+        Some(TraceStep {
+            step_type: &item.step_type,
+            location: item.source_location.as_ref()?,
+            lhs: item.lhs.as_deref(),
+            value: item
+                .value
+                .as_ref()
+                .and_then(|value| value.data.as_ref())
+                .map(|data| data.to_string()),
+        })
+    }
+}
+
+impl KaniSession {
+    /// Print `event` if `--message-format json` was given.
+    pub(crate) fn emit(&self, event: Event<'_>) {
+        if self.args.message_format == MessageFormat::Json {
+            println!("{}", serde_json::to_string(&event).unwrap());
+        }
+    }
+
+    /// Emit the events for the result of `harness`: the result of each property, the traces,
+    /// and the end of the harness.
+    pub(crate) fn emit_harness_result(
+        &self,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+        failed: bool,
+    ) {
+        if self.args.message_format != MessageFormat::Json {
+            return;
+        }
+        let name = harness.pretty_name.as_str();
+        for property in result.results.iter().flatten() {
+            self.emit(property_event(name, property));
+            if let Some(trace) = &property.trace {
+                let steps = trace.iter().filter_map(TraceStep::from_item).collect();
+                self.emit(Event::TraceAvailable {
+                    harness: name,
+                    property: property.property_name(),
+                    steps,
+                });
+            }
+        }
+        self.emit(Event::HarnessFinished {
+            harness: name,
+            status: BaselineStatus::from(result.status),
+            failed,
+            runtime_ms: result.runtime.as_millis(),
+        });
+    }
+}
+
+fn property_event<'a>(harness: &'a str, property: &'a Property) -> Event<'a> {
+    Event::PropertyResult {
+        harness,
+        property: property.property_name(),
+        class: property.property_class(),
+        description: &property.description,
+        status: property.status,
+        location: &property.source_location,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::PropertyId;
+
+    #[test]
+    fn check_event_format() {
+        let property = Property {
+            description: "assertion failed: x".into(),
+            property_id: PropertyId {
+                fn_name: Some("check".into()),
+                class: "assertion".into(),
+                id: 1,
+            },
+            source_location: SourceLocation {
+                column: Some("5".into()),
+                file: Some("src/lib.rs".into()),
+                function: Some("check".into()),
+                line: Some("10".into()),
+            },
+            status: CheckStatus::Failure,
+            reach: None,
+            trace: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&property_event("check", &property)).unwrap(),
+            r#"{"reason":"property-result","harness":"check","property":"check.assertion.1","class":"assertion","description":"assertion failed: x","status":"FAILURE","location":{"column":"5","file":"src/lib.rs","function":"check","line":"10"}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Event::BuildStarted).unwrap(),
+            r#"{"reason":"build-started"}"#
+        );
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--keep-artifacts`, which collects the artifacts of each verified harness in its own
+//! directory, `kani/<crate>/<harness>/` in the target directory, so the artifacts of different
+//! harnesses don't collide and survive the cleanup of temporary files.
+//!
+//! Each directory has a `manifest.json` file that describes the harness, its result, and the
+//! files that were kept: the goto binaries and symbol tables, the rendered result of CBMC, and the
+//! report of `--visualize`, if any.
+
+use crate::args::{KeepArtifacts, OutputFormat};
+use crate::call_cbmc::VerificationResult;
+use crate::project::Project;
+use crate::report::baseline::BaselineStatus;
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+use anyhow::{Context, Result};
+use kani_metadata::{ArtifactType, HarnessMetadata};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const RESULT_FILE: &str = "result.txt";
+
+/// The artifact types that are kept for each harness.
+const KEPT_TYPES: [ArtifactType; 8] = [
+    ArtifactType::Goto,
+    ArtifactType::SymTabGoto,
+    ArtifactType::SymTab,
+    ArtifactType::TypeMap,
+    ArtifactType::VTableRestriction,
+    ArtifactType::PrettyNameMap,
+    ArtifactType::Reachability,
+    ArtifactType::UnsafeOperations,
+];
+
+/// The description of the artifacts kept for one harness.
+#[derive(Debug, Serialize)]
+struct ArtifactManifest {
+    harness: String,
+    crate_name: String,
+    /// The location of the harness, as `file:line`.
+    source: String,
+    kani_version: String,
+    status: BaselineStatus,
+    failed: bool,
+    /// The files in the directory of the harness, relative to it.
+    files: Vec<String>,
+}
+
+impl KaniSession {
+    /// Copy the artifacts of `harness` to its own directory, according to `--keep-artifacts`.
+    ///
+    /// The directory of a previous run is always removed first, so it never mixes the
+    /// artifacts of different runs.
+    pub(crate) fn keep_harness_artifacts(
+        &self,
+        project: &Project,
+        harness: &HarnessMetadata,
+        report_dir: &Path,
+        result: &VerificationResult,
+        failed: bool,
+    ) -> Result<()> {
+        let Some(keep) = self.args.keep_artifacts else { return Ok(()) };
+        let harness_dir = harness_artifacts_dir(project, harness);
+        if harness_dir.exists() {
+            fs::remove_dir_all(&harness_dir)?;
+        }
+        if !should_keep(keep, failed) {
+            return Ok(());
+        }
+        fs::create_dir_all(&harness_dir)?;
+
+        let mut files = vec![];
+        for typ in KEPT_TYPES {
+            let Some(artifact) = project.get_harness_artifact(harness, typ) else { continue };
+            let file_name = artifact.file_name().unwrap();
+            fs::copy(artifact, harness_dir.join(file_name))?;
+            files.push(file_name.to_string_lossy().into_owned());
+        }
+        fs::write(
+            harness_dir.join(RESULT_FILE),
+            result.render(&OutputFormat::Regular, harness.attributes.should_panic),
+        )?;
+        files.push(RESULT_FILE.to_string());
+        if report_dir.is_dir() {
+            let mut cmd = std::process::Command::new("cp");
+            cmd.arg("-r").arg(report_dir).arg(&harness_dir);
+            self.run_suppress(cmd)?;
+            files.push(report_dir.file_name().unwrap().to_string_lossy().into_owned());
+        }
+
+        let manifest = ArtifactManifest {
+            harness: harness.pretty_name.clone(),
+            crate_name: harness.crate_name.clone(),
+            source: format!("{}:{}", harness.original_file, harness.original_start_line),
+            kani_version: KANI_VERSION.to_string(),
+            status: BaselineStatus::from(result.status),
+            failed,
+            files,
+        };
+        let path = harness_dir.join(MANIFEST_FILE);
+        fs::write(&path, serde_json::to_string_pretty(&manifest)?)
+            .with_context(|| format!("Failed to write `{}`", path.display()))
+    }
+}
+
+fn should_keep(keep: KeepArtifacts, failed: bool) -> bool {
+    match keep {
+        KeepArtifacts::None => false,
+        KeepArtifacts::Failed => failed,
+        KeepArtifacts::All => true,
+    }
+}
+
+/// The directory with the artifacts of `harness`: `kani/<crate>/<harness>/` in the target
+/// directory.
+fn harness_artifacts_dir(project: &Project, harness: &HarnessMetadata) -> PathBuf {
+    artifacts_root(&project.outdir, project.input.is_some())
+        .join(&harness.crate_name)
+        .join(harness.pretty_name.replace("::", "-"))
+}
+
+fn artifacts_root(outdir: &Path, standalone: bool) -> PathBuf {
+    if standalone {
+        // Standalone projects write their outputs next to the input file.
+        outdir.join("kani")
+    } else {
+        // The output directory of `cargo kani` is `kani/<target>/debug/deps`.
+        outdir.ancestors().nth(3).unwrap_or(outdir).to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_artifacts_root() {
+        let outdir = Path::new("/ws/target/kani/x86_64-unknown-linux-gnu/debug/deps");
+        assert_eq!(artifacts_root(outdir, false), Path::new("/ws/target/kani"));
+        assert_eq!(artifacts_root(Path::new("/ws"), true), Path::new("/ws/kani"));
+        assert!(should_keep(KeepArtifacts::Failed, true));
+        assert!(!should_keep(KeepArtifacts::Failed, false));
+        assert!(!should_keep(KeepArtifacts::None, true));
+    }
+}
@@ -4,12 +4,30 @@
 use anyhow::{bail, Result};
 use kani_metadata::{ArtifactType, HarnessMetadata};
 use rayon::prelude::*;
-use std::path::Path;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::args::OutputFormat;
-use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::args::{Backend, Engine, OutputFormat};
+use crate::args_toml::Severity;
+use crate::call_cbmc::{resolve_unwind_value, VerificationResult, VerificationStatus};
+use crate::cbmc_property_renderer::{
+    failed_unwinding_loops, format_unwinding_failures, has_only_unwinding_assertion_failures,
+};
+use crate::concrete_playback::counterexamples::format_counterexamples;
+use crate::events::Event;
+use crate::profile::{format_phase_profile, Phase};
+use crate::progress::Progress;
 use crate::project::Project;
+use crate::report::baseline::{Baseline, BaselineComparison, BaselineStatus};
+use crate::resume::{verification_fingerprint, SessionJournal};
 use crate::session::KaniSession;
+use crate::stats::format_stats_table;
+use crate::stub_suggestions::format_stub_suggestions;
+use crate::trace_mapping::map_generated_frames;
+use crate::util::warning;
 
 /// A HarnessRunner is responsible for checking all proof harnesses. The data in this structure represents
 /// "background information" that the controlling driver (e.g. cargo-kani or kani) computed.
@@ -38,7 +56,32 @@ pub(crate) fn check_all_harnesses(
     ) -> Result<Vec<HarnessResult<'pr>>> {
         self.check_stubbing(harnesses)?;
 
-        let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
+        let journal = SessionJournal::open(&self.project.outdir, self.sess.args.resume)?;
+        // Computed before any verification, since the goto models are instrumented in place.
+        let fingerprints = harnesses
+            .iter()
+            .map(|harness| {
+                let fingerprint = verification_fingerprint(self.sess, self.project, harness)?;
+                Ok((harness.mangled_name.as_str(), fingerprint))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        let fingerprint_of =
+            |harness: &HarnessMetadata| fingerprints[harness.mangled_name.as_str()];
+        let (resumed, sorted_harnesses): (Vec<_>, Vec<_>) =
+            crate::metadata::sort_harnesses_by_loc(harnesses)
+                .into_iter()
+                .partition(|harness| journal.completed(harness, fingerprint_of(harness)).is_some());
+        if !resumed.is_empty() && !self.sess.args.common_args.quiet {
+            println!(
+                "Resuming the interrupted verification: skipping {} harnesses that were already verified.",
+                resumed.len()
+            );
+        }
+        self.sess.resumed_harnesses.lock().unwrap().extend(
+            resumed
+                .iter()
+                .filter_map(|harness| journal.completed(harness, fingerprint_of(harness)).cloned()),
+        );
 
         let pool = {
             let mut builder = rayon::ThreadPoolBuilder::new();
@@ -48,28 +91,93 @@ pub(crate) fn check_all_harnesses(
             builder.build()?
         };
 
-        let results = pool.install(|| -> Result<Vec<HarnessResult<'pr>>> {
+        // Set once a harness fails with `--fail-fast`, to skip the harnesses that didn't start yet.
+        let stop = AtomicBool::new(false);
+        let progress = self.sess.args.progress.then(|| Progress::new(sorted_harnesses.len()));
+        let verify = || -> Result<Vec<Option<HarnessResult<'pr>>>> {
             sorted_harnesses
                 .par_iter()
-                .map(|harness| -> Result<HarnessResult<'pr>> {
+                .map(|harness| -> Result<Option<HarnessResult<'pr>>> {
+                    if stop.load(Ordering::Relaxed) {
+                        return Ok(None);
+                    }
+                    if let Some(progress) = &progress {
+                        progress.start(harness);
+                    }
+                    self.sess.emit(Event::HarnessStarted {
+                        harness: &harness.pretty_name,
+                        crate_name: &harness.crate_name,
+                        file: &harness.original_file,
+                        line: harness.original_start_line,
+                    });
                     let harness_filename = harness.pretty_name.replace("::", "-");
                     let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
-                    let goto_file =
+                    let model =
                         self.project.get_harness_artifact(&harness, ArtifactType::Goto).unwrap();
+                    let goto_file = self.sess.harness_binary(model, harness);
+                    if goto_file != model.to_path_buf() {
+                        self.sess.record_temporary_file(&goto_file);
+                    }
+                    let goto_file = goto_file.as_path();
 
-                    self.sess.instrument_model(goto_file, goto_file, &self.project, &harness)?;
+                    self.sess.time_phase(Phase::Instrument, Some(&harness.pretty_name), || {
+                        self.sess.instrument_model(model, goto_file, &self.project, &harness)
+                    })?;
+                    let sliced = self.sess.slice_model(goto_file, harness)?;
 
                     if self.sess.args.synthesize_loop_contracts {
                         self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
                     }
 
                     let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
-                    Ok(HarnessResult { harness, result })
+                    if !sliced.is_empty() && !self.sess.args.common_args.quiet {
+                        println!("{}", sliced_properties_note(harness, &sliced));
+                    }
+                    let failed = self.sess.is_failure(&result);
+                    let mut verified = vec![fingerprint_of(harness)];
+                    let instrumented = verification_fingerprint(self.sess, self.project, harness)?;
+                    if instrumented != verified[0] {
+                        verified.push(instrumented);
+                    }
+                    journal.record(harness, verified, &result, failed)?;
+                    self.sess.emit_harness_result(harness, &result, failed);
+                    self.sess.keep_harness_artifacts(
+                        self.project,
+                        harness,
+                        &report_dir,
+                        &result,
+                        failed,
+                    )?;
+                    if let Some(progress) = &progress {
+                        progress.finish(self.sess, harness, &result, failed);
+                    }
+                    if self.sess.args.fail_fast && failed {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    Ok(Some(HarnessResult { harness, result }))
                 })
                 .collect::<Result<Vec<_>>>()
-        })?;
+        };
+        let results = match &progress {
+            Some(progress) => std::thread::scope(|scope| {
+                scope.spawn(|| progress.tick());
+                let results = pool.install(verify);
+                progress.finish_all();
+                results
+            })?,
+            None => pool.install(verify)?,
+        };
 
-        Ok(results)
+        let skipped = results.iter().filter(|result| result.is_none()).count();
+        if skipped > 0 && !self.sess.args.common_args.quiet {
+            println!(
+                "Stopped after the first failing harness (--fail-fast): skipped {skipped} harnesses."
+            );
+        }
+        if skipped == 0 {
+            journal.finish()?;
+        }
+        Ok(results.into_iter().flatten().collect())
     }
 
     /// Return an error if the user is trying to verify a harness with stubs without enabling the
@@ -101,6 +209,18 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
 }
 
 impl KaniSession {
+    /// The goto binary that is instrumented and verified for `harness`, given its model.
+    ///
+    /// This is the model itself, except for shared models, which are specialized into a new file
+    /// since other harnesses use them.
+    pub(crate) fn harness_binary(&self, model: &Path, harness: &HarnessMetadata) -> PathBuf {
+        if self.args.shared_model {
+            model.with_extension(format!("{}.out", harness.mangled_name))
+        } else {
+            model.to_path_buf()
+        }
+    }
+
     /// Run the verification process for a single harness
     pub(crate) fn check_harness(
         &self,
@@ -112,12 +232,25 @@ pub(crate) fn check_harness(
             println!("Checking harness {}...", harness.pretty_name);
         }
 
-        if self.args.visualize {
+        if self.args.backend == Backend::Smt2 {
+            self.run_smt2(binary, harness)
+        } else if self.args.visualize {
             self.run_visualize(binary, report_dir, harness)?;
             // Strictly speaking, we're faking success here. This is more "no error"
             Ok(VerificationResult::mock_success())
         } else {
-            let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?;
+            let mut result = match self.args.engine {
+                Engine::Cbmc => self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?,
+                Engine::Esbmc => {
+                    self.with_timer(|| self.run_esbmc(binary, harness), "run_esbmc")?
+                }
+            };
+            if self.args.infer_unwind {
+                result = self.infer_unwind(binary, harness, result)?;
+            }
+            if let Ok(properties) = &mut result.results {
+                map_generated_frames(properties, harness);
+            }
 
             // When quiet, we don't want to print anything at all.
             // When output is old, we also don't have real results to print.
@@ -127,11 +260,115 @@ pub(crate) fn check_harness(
                     result.render(&self.args.output_format, harness.attributes.should_panic)
                 );
             }
+            if !self.args.common_args.quiet {
+                if !result.unsatisfiable_assumptions.is_empty() {
+                    warning(&unsatisfiable_assumptions_warning(harness, &result));
+                } else if result.is_vacuous() {
+                    warning(&vacuity_warning(harness));
+                }
+                if let Ok(properties) = &result.results {
+                    print!("{}", format_stub_suggestions(properties));
+                    print!(
+                        "{}",
+                        format_unwinding_failures(properties, &harness.crate_name, |loop_id| {
+                            harness.attributes.loop_unwinds.get(loop_id).copied().or_else(|| {
+                                // `--infer-unwind` may have increased the bound.
+                                resolve_unwind_value(&self.args, harness)
+                                    .filter(|_| !self.args.infer_unwind)
+                            })
+                        })
+                    );
+                }
+            }
+            let found = if self.args.searches_counterexamples() {
+                let found = self.find_counterexamples(binary, harness, &result)?;
+                if !self.args.common_args.quiet {
+                    print!("{}", format_counterexamples(&found));
+                }
+                found
+            } else {
+                vec![]
+            };
+            self.record_concolic_candidates(harness, &result, &found);
             self.gen_and_add_concrete_playback(harness, &mut result)?;
             Ok(result)
         }
     }
 
+    /// If verification failed only because of unwinding assertions, retry with exponentially
+    /// increasing unwind bounds until verification succeeds or the bound exceeds
+    /// `--infer-unwind-limit`.
+    ///
+    /// With `--incremental-unwind`, if a single loop fails its unwinding assertion, that loop is
+    /// instead unwound incrementally up to the limit in one CBMC run, which reuses the solver state
+    /// between bounds. CBMC doesn't report the bound that sufficed in that case, so the limit is
+    /// recorded instead.
+    ///
+    /// Returns the result of the last attempt, which records the bound that sufficed (if any).
+    fn infer_unwind(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        mut result: VerificationResult,
+    ) -> Result<VerificationResult> {
+        let mut harness = harness.clone();
+        let mut unwind = resolve_unwind_value(&self.args, &harness);
+        let mut runtime = result.runtime;
+        while let Ok(properties) = &result.results
+            && has_only_unwinding_assertion_failures(properties)
+            && let Some(next) = next_unwind(unwind, self.args.infer_unwind_limit)
+        {
+            if !self.args.common_args.quiet {
+                println!(
+                    "Unwinding assertion failed, retrying {} with unwind {next}...",
+                    harness.pretty_name
+                );
+            }
+            if self.args.incremental_unwind
+                && let [loop_id] = failed_unwinding_loops(properties).as_slice()
+            {
+                let limit = self.args.infer_unwind_limit;
+                if !self.args.common_args.quiet {
+                    println!(
+                        "Unwinding assertion of loop {loop_id} failed, unwinding it incrementally \
+                        from {next} to {limit} in {}...",
+                        harness.pretty_name
+                    );
+                }
+                let args: Vec<OsString> = vec![
+                    "--incremental-loop".into(),
+                    loop_id.into(),
+                    "--unwind-min".into(),
+                    next.to_string().into(),
+                    "--unwind-max".into(),
+                    limit.to_string().into(),
+                    "--ignore-properties-before-unwind-min".into(),
+                ];
+                result = self
+                    .with_timer(|| self.run_cbmc_with_args(binary, &harness, &args), "run_cbmc")?;
+                unwind = Some(limit);
+            } else {
+                harness.attributes.unwind_value = Some(next);
+                unwind = Some(next);
+                result = self.with_timer(|| self.run_cbmc(binary, &harness), "run_cbmc")?;
+            }
+            runtime += result.runtime;
+            if let Ok(properties) = &result.results
+                && !has_only_unwinding_assertion_failures(properties)
+            {
+                result.inferred_unwind = unwind;
+            }
+        }
+        result.runtime = runtime;
+        Ok(result)
+    }
+
+    /// Whether a harness result should make Kani exit with an error, according to `--fail-on`.
+    fn is_failure(&self, result: &VerificationResult) -> bool {
+        result.failure_category().is_some_and(|category| self.args.fail_on.contains(&category))
+            && result.failure_severity(&self.severities) == Some(Severity::Error)
+    }
+
     /// Concludes a session by printing a summary report and exiting the process with an
     /// error code (if applicable).
     ///
@@ -141,8 +378,12 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         let (successes, failures): (Vec<_>, Vec<_>) =
             results.iter().partition(|r| r.result.status == VerificationStatus::Success);
 
-        let succeeding = successes.len();
-        let failing = failures.len();
+        let resumed = self.resumed_harnesses.lock().unwrap().clone();
+        let resumed_failures: Vec<_> =
+            resumed.iter().filter(|harness| harness.status != BaselineStatus::Success).collect();
+
+        let succeeding = successes.len() + resumed.len() - resumed_failures.len();
+        let failing = failures.len() + resumed_failures.len();
         let total = succeeding + failing;
 
         if self.args.concrete_playback.is_some()
@@ -159,14 +400,80 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             if failing > 0 {
                 println!("Summary:");
             }
+            for result in results.iter() {
+                if let Some(unwind) = result.result.inferred_unwind {
+                    println!(
+                        "Harness {} needed unwind {unwind}. Consider adding `#[kani::unwind({unwind})]` to it.",
+                        result.harness.pretty_name
+                    );
+                }
+            }
+            for result in results.iter().filter(|r| !r.result.unsatisfiable_assumptions.is_empty())
+            {
+                println!("Assumptions can't be satisfied for - {}", result.harness.pretty_name);
+            }
+            for result in successes
+                .iter()
+                .filter(|r| r.result.is_vacuous() && r.result.unsatisfiable_assumptions.is_empty())
+            {
+                println!(
+                    "Verification succeeded vacuously for - {} (no assertion was reachable)",
+                    result.harness.pretty_name
+                );
+            }
+            for failure in resumed_failures.iter() {
+                println!("Verification failed for - {} (in the interrupted run)", failure.harness);
+            }
             for failure in failures.iter() {
-                println!("Verification failed for - {}", failure.harness.pretty_name);
+                let severity = match failure.result.failure_severity(&self.severities) {
+                    Some(Severity::Warning) => " (warning)",
+                    Some(Severity::Info) => " (info)",
+                    _ => "",
+                };
+                let name = &failure.harness.pretty_name;
+                let severity = if self.needs_review(failure.harness) {
+                    format!("{severity} (needs review)")
+                } else {
+                    severity.to_string()
+                };
+                match failure.result.status {
+                    VerificationStatus::Timeout => {
+                        println!("Verification timed out for - {name}{severity}")
+                    }
+                    VerificationStatus::OutOfMemory => {
+                        println!("Verification ran out of memory for - {name}{severity}")
+                    }
+                    _ => println!("Verification failed for - {name}{severity}"),
+                }
             }
 
             if total > 0 {
-                println!(
-                    "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
+                let count = |status: VerificationStatus| {
+                    failures.iter().filter(|r| r.result.status == status).count()
+                        + resumed_failures
+                            .iter()
+                            .filter(|r| r.status == BaselineStatus::from(status))
+                            .count()
+                };
+                let timeouts = count(VerificationStatus::Timeout);
+                let out_of_memory = count(VerificationStatus::OutOfMemory);
+                let mut summary = format!(
+                    "Complete - {succeeding} successfully verified harnesses, {} failures",
+                    failing - timeouts - out_of_memory
                 );
+                if timeouts > 0 {
+                    write!(summary, ", {timeouts} timeouts").unwrap();
+                }
+                if out_of_memory > 0 {
+                    write!(summary, ", {out_of_memory} out of memory").unwrap();
+                }
+                println!("{summary}, {total} total.");
+                if self.args.stats {
+                    print!("\n{}", format_stats_table(results));
+                }
+                if self.args.profile_phases {
+                    print!("\n{}", format_phase_profile(&self.phase_times, results));
+                }
             } else {
                 match self.args.harnesses.as_slice() {
                     [] =>
@@ -192,7 +499,27 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             self.show_coverage_summary()?;
         }
 
-        if failing > 0 {
+        // When comparing against a baseline, only regressions are considered failures.
+        let failed = if let Some(path) = &self.args.baseline {
+            let baseline = Baseline::read(path)?;
+            let comparison =
+                BaselineComparison::new(&baseline, results, self.args.baseline_slowdown_threshold);
+            if !self.args.common_args.quiet {
+                comparison.print();
+            }
+            comparison.has_regressions()
+        } else {
+            results.iter().any(|result| self.is_failure(&result.result))
+                || resumed.iter().any(|harness| harness.failed)
+        };
+
+        self.emit(Event::RunFinished {
+            succeeded: succeeding,
+            failed: failing,
+            total,
+            success: !failed,
+        });
+        if failed {
             // Failure exit code without additional error message
             drop(self);
             std::process::exit(1);
@@ -208,3 +535,70 @@ fn show_coverage_summary(&self) -> Result<()> {
         Ok(())
     }
 }
+
+/// The note printed after the results of a harness whose properties were removed by
+/// `--constant-propagation` or `--full-slice`, e.g., because they became trivially true.
+fn sliced_properties_note(harness: &HarnessMetadata, sliced: &[String]) -> String {
+    format!(
+        "Note: slicing removed {} properties of harness `{}`, which were not checked: {}",
+        sliced.len(),
+        harness.pretty_name,
+        sliced.join(", ")
+    )
+}
+
+/// The warning for a harness that succeeded because none of its assertions was reachable, with
+/// the assumptions that may have pruned them.
+fn vacuity_warning(harness: &HarnessMetadata) -> String {
+    let mut msg = format!(
+        "harness `{}` was verified vacuously: none of its assertions is reachable",
+        harness.pretty_name
+    );
+    if harness.assumptions.is_empty() {
+        msg.push_str(", e.g., because of dead code or a panic.");
+    } else {
+        msg.push_str(". Check whether these assumptions contradict each other:");
+        for location in &harness.assumptions {
+            write!(msg, "\n  - {location}").unwrap();
+        }
+    }
+    msg
+}
+
+/// The warning for a harness with assumptions that no execution satisfies, found with
+/// `-Z assumption-checks`.
+fn unsatisfiable_assumptions_warning(
+    harness: &HarnessMetadata,
+    result: &VerificationResult,
+) -> String {
+    let mut msg = format!(
+        "no execution of harness `{}` satisfies the assumptions up to these points, so the \
+        code after them is not verified:",
+        harness.pretty_name
+    );
+    for location in &result.unsatisfiable_assumptions {
+        write!(msg, "\n  - {location}").unwrap();
+    }
+    msg
+}
+
+/// The bound to retry with after `bound` failed, if it doesn't exceed `limit`. A bound of 0 is
+/// retried with 2, since doubling it would never make progress.
+fn next_unwind(bound: Option<u32>, limit: u32) -> Option<u32> {
+    bound.and_then(|bound| bound.max(1).checked_mul(2)).filter(|next| *next <= limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_next_unwind() {
+        assert_eq!(next_unwind(Some(0), 8), Some(2));
+        assert_eq!(next_unwind(Some(1), 8), Some(2));
+        assert_eq!(next_unwind(Some(4), 8), Some(8));
+        assert_eq!(next_unwind(Some(8), 8), None);
+        assert_eq!(next_unwind(Some(u32::MAX), u32::MAX), None);
+        assert_eq!(next_unwind(None, 8), None);
+    }
+}
@@ -0,0 +1,384 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani list`, which reports the inventory of harnesses and contracts of a
+//! package, e.g., for dashboards and proof-tracking tools.
+//!
+//! The inventory is built from the metadata emitted by `kani-compiler`, so the package is compiled
+//! but no harness is verified.
+
+use crate::args::list_args::{CargoListArgs, ListFormat};
+use crate::call_cbmc::solver_name;
+use crate::project;
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+use anyhow::{Context, Result};
+use kani_metadata::{FunctionMetadata, HarnessKind, HarnessMetadata, KaniMetadata, Stub};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// The version of the JSON inventory. Increase it whenever a change breaks existing consumers.
+const FILE_VERSION: &str = "0.1";
+
+/// `cargo kani list` main entry point.
+pub(crate) fn list_cargo(mut session: KaniSession, args: CargoListArgs) -> Result<()> {
+    session.record_public_functions = true;
+    let project = project::cargo_project(&session, false)?;
+    let inventory = Inventory::new(&project.metadata);
+    let output = match args.format {
+        ListFormat::Pretty => inventory.to_pretty(),
+        ListFormat::Json => serde_json::to_string_pretty(&inventory)? + "\n",
+    };
+    if let Some(path) = &args.output {
+        std::fs::write(path, output)
+            .with_context(|| format!("Failed to write `{}`", path.display()))?;
+        if !session.args.common_args.quiet {
+            println!("Inventory written to `{}`", path.display());
+        }
+    } else {
+        print!("{output}");
+    }
+    Ok(())
+}
+
+/// The inventory of all crates of a package.
+#[derive(Debug, Serialize)]
+struct Inventory {
+    kani_version: &'static str,
+    file_version: &'static str,
+    crates: Vec<CrateInventory>,
+    totals: Totals,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Totals {
+    standard_harnesses: usize,
+    contract_harnesses: usize,
+    functions_with_contract: usize,
+    functions_with_contract_harness: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct CrateInventory {
+    crate_name: String,
+    harnesses: Vec<HarnessEntry>,
+    /// The public functions of the crate that have a contract.
+    contracts: Vec<ContractEntry>,
+    /// The unsupported constructs found while compiling the crate, which Kani replaced by a
+    /// failing assertion.
+    unsupported_features: Vec<UnsupportedEntry>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct HarnessEntry {
+    pretty_name: String,
+    file: String,
+    start_line: usize,
+    end_line: usize,
+    /// The function whose contract is checked by this harness, if any.
+    contract_target: Option<String>,
+    stubs: Vec<Stub>,
+    /// The functions replaced by their contract in this harness.
+    verified_stubs: Vec<String>,
+    should_panic: bool,
+    unwind: Option<u32>,
+    solver: Option<String>,
+    timeout: Option<u64>,
+    memory_limit: Option<u64>,
+    cbmc_args: Vec<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ContractEntry {
+    function: String,
+    module: String,
+    /// The harnesses that check this contract with `#[kani::proof_for_contract]`.
+    harnesses: Vec<String>,
+    /// The harnesses that replace this function by its contract with `#[kani::stub_verified]`.
+    stubbed_in: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct UnsupportedEntry {
+    feature: String,
+    /// The `file:line` locations where the construct was found, sorted.
+    locations: Vec<String>,
+}
+
+impl Inventory {
+    fn new(metadata: &[KaniMetadata]) -> Inventory {
+        let mut crates: Vec<_> = metadata.iter().map(CrateInventory::new).collect();
+        crates.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+        let mut totals = Totals::default();
+        for krate in &crates {
+            for harness in &krate.harnesses {
+                if harness.contract_target.is_some() {
+                    totals.contract_harnesses += 1;
+                } else {
+                    totals.standard_harnesses += 1;
+                }
+            }
+            totals.functions_with_contract += krate.contracts.len();
+            totals.functions_with_contract_harness +=
+                krate.contracts.iter().filter(|contract| !contract.harnesses.is_empty()).count();
+        }
+        Inventory { kani_version: KANI_VERSION, file_version: FILE_VERSION, crates, totals }
+    }
+
+    fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        for krate in &self.crates {
+            writeln!(out, "Crate `{}`:", krate.crate_name).unwrap();
+            writeln!(out, "  Harnesses ({}):", krate.harnesses.len()).unwrap();
+            for harness in &krate.harnesses {
+                writeln!(out, "    - {}{}", harness.pretty_name, harness.pretty_details()).unwrap();
+            }
+            if !krate.contracts.is_empty() {
+                writeln!(out, "  Contracts ({}):", krate.contracts.len()).unwrap();
+                for contract in &krate.contracts {
+                    let harnesses = if contract.harnesses.is_empty() {
+                        "no harness".to_string()
+                    } else {
+                        contract.harnesses.join(", ")
+                    };
+                    writeln!(out, "    - {} (checked by: {harnesses})", contract.function).unwrap();
+                }
+            }
+            if !krate.unsupported_features.is_empty() {
+                writeln!(out, "  Unsupported constructs:").unwrap();
+                for unsupported in &krate.unsupported_features {
+                    writeln!(
+                        out,
+                        "    - {} ({} location(s))",
+                        unsupported.feature,
+                        unsupported.locations.len()
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        let totals = &self.totals;
+        writeln!(
+            out,
+            "Totals: {} standard harness(es), {} contract harness(es), \
+            {} of {} function contract(s) checked by a harness",
+            totals.standard_harnesses,
+            totals.contract_harnesses,
+            totals.functions_with_contract_harness,
+            totals.functions_with_contract
+        )
+        .unwrap();
+        out
+    }
+}
+
+impl CrateInventory {
+    fn new(metadata: &KaniMetadata) -> CrateInventory {
+        let mut harnesses: Vec<_> =
+            metadata.proof_harnesses.iter().map(HarnessEntry::new).collect();
+        harnesses.sort_by(|a, b| a.pretty_name.cmp(&b.pretty_name));
+        let contracts = metadata
+            .public_functions
+            .iter()
+            .filter(|function| function.has_contract)
+            .map(|function| ContractEntry::new(function, &metadata.proof_harnesses))
+            .collect();
+        let unsupported_features = metadata
+            .unsupported_features
+            .iter()
+            .map(|unsupported| UnsupportedEntry {
+                feature: unsupported.feature.clone(),
+                locations: unsupported
+                    .locations
+                    .iter()
+                    .map(|loc| format!("{}:{}", loc.filename, loc.start_line))
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            })
+            .collect();
+        CrateInventory {
+            crate_name: metadata.crate_name.clone(),
+            harnesses,
+            contracts,
+            unsupported_features,
+        }
+    }
+}
+
+impl HarnessEntry {
+    fn new(harness: &HarnessMetadata) -> HarnessEntry {
+        let attributes = &harness.attributes;
+        let contract_target = match &attributes.kind {
+            HarnessKind::ProofForContract { target_fn } => Some(target_fn.clone()),
+            HarnessKind::Proof | HarnessKind::Test => None,
+        };
+        HarnessEntry {
+            pretty_name: harness.pretty_name.clone(),
+            file: harness.original_file.clone(),
+            start_line: harness.original_start_line,
+            end_line: harness.original_end_line,
+            contract_target,
+            stubs: attributes.stubs.clone(),
+            verified_stubs: attributes.verified_stubs.clone(),
+            should_panic: attributes.should_panic,
+            unwind: attributes.unwind_value,
+            solver: attributes.solver.as_ref().map(solver_name),
+            timeout: attributes.timeout,
+            memory_limit: attributes.memory_limit,
+            cbmc_args: attributes.cbmc_args.clone(),
+            tags: attributes.tags.clone(),
+        }
+    }
+
+    /// The attributes of the harness that are shown in the pretty format, e.g.,
+    /// ` [contract: foo, unwind: 3, tags: slow]`.
+    fn pretty_details(&self) -> String {
+        let mut details = vec![];
+        if let Some(target) = &self.contract_target {
+            details.push(format!("contract: {target}"));
+        }
+        if !self.stubs.is_empty() {
+            details.push(format!("stubs: {}", self.stubs.len()));
+        }
+        if !self.verified_stubs.is_empty() {
+            details.push(format!("verified stubs: {}", self.verified_stubs.join(", ")));
+        }
+        if let Some(unwind) = self.unwind {
+            details.push(format!("unwind: {unwind}"));
+        }
+        if let Some(solver) = &self.solver {
+            details.push(format!("solver: {solver}"));
+        }
+        if !self.tags.is_empty() {
+            details.push(format!("tags: {}", self.tags.join(", ")));
+        }
+        if details.is_empty() { String::new() } else { format!(" [{}]", details.join(", ")) }
+    }
+}
+
+impl ContractEntry {
+    fn new(function: &FunctionMetadata, harnesses: &[HarnessMetadata]) -> ContractEntry {
+        let harnesses_where = |pred: &dyn Fn(&HarnessMetadata) -> bool| {
+            harnesses.iter().filter(|h| pred(h)).map(|h| h.pretty_name.clone()).collect()
+        };
+        ContractEntry {
+            function: function.name.clone(),
+            module: function.module.clone(),
+            harnesses: harnesses_where(&|harness| {
+                matches!(&harness.attributes.kind,
+                    HarnessKind::ProofForContract { target_fn }
+                        if same_function(target_fn, &function.name))
+            }),
+            stubbed_in: harnesses_where(&|harness| {
+                harness
+                    .attributes
+                    .verified_stubs
+                    .iter()
+                    .any(|stub| same_function(stub, &function.name))
+            }),
+        }
+    }
+}
+
+/// Whether the path given in a harness attribute refers to the function with the given fully
+/// qualified name. Attribute paths are relative to the harness module, so we only compare the
+/// trailing segments.
+fn same_function(attribute_path: &str, name: &str) -> bool {
+    let attribute_path = attribute_path.trim_start_matches("crate::");
+    name == attribute_path || name.ends_with(&format!("::{attribute_path}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kani_metadata::HarnessAttributes;
+
+    fn harness(name: &str, kind: HarnessKind) -> HarnessMetadata {
+        HarnessMetadata {
+            pretty_name: name.to_string(),
+            mangled_name: name.to_string(),
+            crate_name: "krate".to_string(),
+            original_file: "src/lib.rs".to_string(),
+            original_start_line: 1,
+            original_end_line: 5,
+            goto_file: None,
+            attributes: HarnessAttributes::new(kind),
+            contract: None,
+            assumptions: vec![],
+        }
+    }
+
+    fn function(name: &str, has_contract: bool) -> FunctionMetadata {
+        FunctionMetadata {
+            name: name.to_string(),
+            module: "krate::math".to_string(),
+            has_contract,
+            is_reachable: true,
+        }
+    }
+
+    #[test]
+    fn check_inventory() {
+        let mut checked = harness(
+            "krate::math::check_div",
+            HarnessKind::ProofForContract { target_fn: "div".to_string() },
+        );
+        checked.attributes.tags = vec!["slow".to_string()];
+        let mut plain = harness("krate::math::check_mod", HarnessKind::Proof);
+        plain.attributes.unwind_value = Some(3);
+        plain.attributes.verified_stubs = vec!["div".to_string()];
+        let metadata = KaniMetadata {
+            crate_name: "krate".to_string(),
+            proof_harnesses: vec![plain, checked],
+            unsupported_features: vec![],
+            test_harnesses: vec![],
+            public_functions: vec![
+                function("krate::math::div", true),
+                function("krate::math::modulo", true),
+                function("krate::math::add", false),
+            ],
+        };
+
+        let inventory = Inventory::new(&[metadata]);
+        let krate = &inventory.crates[0];
+        assert_eq!(krate.harnesses[0].pretty_name, "krate::math::check_div");
+        assert_eq!(krate.harnesses[0].tags, ["slow"]);
+        assert_eq!(krate.harnesses[1].unwind, Some(3));
+        assert_eq!(
+            krate.contracts,
+            [
+                ContractEntry {
+                    function: "krate::math::div".to_string(),
+                    module: "krate::math".to_string(),
+                    harnesses: vec!["krate::math::check_div".to_string()],
+                    stubbed_in: vec!["krate::math::check_mod".to_string()],
+                },
+                ContractEntry {
+                    function: "krate::math::modulo".to_string(),
+                    module: "krate::math".to_string(),
+                    harnesses: vec![],
+                    stubbed_in: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            inventory.totals,
+            Totals {
+                standard_harnesses: 1,
+                contract_harnesses: 1,
+                functions_with_contract: 2,
+                functions_with_contract_harness: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn check_same_function() {
+        assert!(same_function("div", "krate::math::div"));
+        assert!(same_function("crate::math::div", "krate::math::div"));
+        assert!(!same_function("iv", "krate::math::div"));
+    }
+}
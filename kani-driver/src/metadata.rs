@@ -1,7 +1,8 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
 use std::path::Path;
 use tracing::{debug, trace};
 
@@ -60,21 +61,46 @@ fn link_function_pointer_restrictions(
     }
 
     // Emit a restriction for every call site
-    let mut output = HashMap::new();
-    for crate_data in data_per_crate {
-        for call_site in crate_data.call_sites {
+    let mut output: HashMap<String, Vec<InternedString>> = HashMap::new();
+    for crate_data in &data_per_crate {
+        for call_site in &crate_data.call_sites {
             // CBMC Now supports referencing callsites by label:
             // https://github.com/diffblue/cbmc/pull/6508
             let cbmc_call_site_name = format!("{}.{}", call_site.function_name, call_site.label);
-            let trait_def = call_site.trait_method;
+            let trait_def = &call_site.trait_method;
 
             // Look up all possibilities, defaulting to the empty set
-            let possibilities =
-                combined_possible_methods.get(&trait_def).unwrap_or(&vec![]).clone();
+            let possibilities = combined_possible_methods.get(trait_def).unwrap_or(&vec![]).clone();
             output.insert(cbmc_call_site_name, possibilities);
         }
     }
 
+    // Combine the address-taken functions of each signature. Unlike vtable entries, a signature
+    // may have different functions in each crate.
+    let mut combined_address_taken: HashMap<InternedString, BTreeSet<InternedString>> =
+        HashMap::new();
+    for crate_data in &data_per_crate {
+        for entry in &crate_data.address_taken {
+            combined_address_taken
+                .entry(entry.signature)
+                .or_default()
+                .extend(entry.functions.iter().copied());
+        }
+    }
+
+    // Emit a restriction for every function pointer call site that has a possible target. The
+    // other calls are left unrestricted, since their pointer can only be obtained in a way we
+    // don't track, e.g., a transmute.
+    for crate_data in &data_per_crate {
+        for call_site in &crate_data.fn_ptr_call_sites {
+            if let Some(functions) = combined_address_taken.get(&call_site.signature) {
+                let cbmc_call_site_name =
+                    format!("{}.{}", call_site.function_name, call_site.label);
+                output.insert(cbmc_call_site_name, functions.iter().copied().collect());
+            }
+        }
+    }
+
     let f = File::create(output_filename)?;
     let f = BufWriter::new(f);
     serde_json::to_writer(f, &output)?;
@@ -96,6 +122,7 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
         proof_harnesses: vec![],
         unsupported_features: vec![],
         test_harnesses: vec![],
+        public_functions: vec![],
     };
     for md in files {
         // Note that we're taking ownership of the original vec, and so we can move the data into the new data structure.
@@ -104,6 +131,7 @@ pub fn merge_kani_metadata(files: Vec<KaniMetadata>) -> KaniMetadata {
         // https://github.com/model-checking/kani/issues/1758
         result.unsupported_features.extend(md.unsupported_features);
         result.test_harnesses.extend(md.test_harnesses);
+        result.public_functions.extend(md.public_functions);
     }
     result
 }
@@ -117,12 +145,23 @@ pub fn determine_targets<'a>(
         let harnesses = BTreeSet::from_iter(self.args.harnesses.iter());
         let total_harnesses = harnesses.len();
         let all_targets = &harnesses;
+        let all_harnesses: Vec<&HarnessMetadata> = all_harnesses
+            .iter()
+            .copied()
+            .filter(|harness| {
+                !harness.attributes.tags.iter().any(|tag| self.args.skip_tags.contains(tag))
+            })
+            .collect();
 
         if harnesses.is_empty() {
-            Ok(Vec::from(all_harnesses))
+            self.select_partition(all_harnesses)
         } else {
+            let filters = harnesses
+                .iter()
+                .map(|filter| HarnessFilter::parse(filter, self.args.exact))
+                .collect::<Result<Vec<_>>>()?;
             let harnesses_found: Vec<&HarnessMetadata> =
-                find_proof_harnesses(&harnesses, all_harnesses, self.args.exact);
+                find_proof_harnesses(&filters, &all_harnesses);
 
             // If even one harness was not found with --exact, return an error to user
             if self.args.exact && harnesses_found.len() < total_harnesses {
@@ -143,7 +182,57 @@ pub fn determine_targets<'a>(
                 );
             }
 
-            Ok(harnesses_found)
+            self.select_partition(harnesses_found)
+        }
+    }
+}
+
+/// A filter provided by the user with `--harness` to select which harnesses to run.
+#[derive(Debug)]
+pub enum HarnessFilter {
+    /// With `--exact`, the filter must be the fully qualified name of the harness.
+    Exact(String),
+    /// The filter is contained in the fully qualified name of the harness.
+    Substring(String),
+    /// A glob pattern, e.g. `parser::*`, that matches either the fully qualified or the
+    /// unqualified name of the harness. Used when the filter has any of the `*?[` characters.
+    Glob(glob::Pattern),
+    /// A regular expression delimited by `/`, e.g. `/_roundtrip$/`, that matches any part of the
+    /// fully qualified name of the harness.
+    Regex(Regex),
+}
+
+impl HarnessFilter {
+    /// Parse the filter given by the user.
+    pub fn parse(filter: &str, exact: bool) -> Result<HarnessFilter> {
+        if exact {
+            return Ok(HarnessFilter::Exact(filter.to_string()));
+        }
+        if let Some(regex) = filter.strip_prefix('/').and_then(|filter| filter.strip_suffix('/')) {
+            let regex = Regex::new(regex).with_context(|| {
+                format!("invalid regular expression in harness filter `{filter}`")
+            })?;
+            Ok(HarnessFilter::Regex(regex))
+        } else if filter.contains(['*', '?', '[']) {
+            let pattern = glob::Pattern::new(filter)
+                .with_context(|| format!("invalid glob pattern in harness filter `{filter}`"))?;
+            Ok(HarnessFilter::Glob(pattern))
+        } else {
+            Ok(HarnessFilter::Substring(filter.to_string()))
+        }
+    }
+
+    /// Whether the given harness is selected by this filter.
+    pub fn matches(&self, harness: &HarnessMetadata) -> bool {
+        match self {
+            HarnessFilter::Exact(name) => harness.pretty_name == *name,
+            // An exact match on the unqualified name is covered by the substring search.
+            HarnessFilter::Substring(substring) => harness.pretty_name.contains(substring.as_str()),
+            HarnessFilter::Glob(pattern) => {
+                pattern.matches(&harness.pretty_name)
+                    || pattern.matches(harness.get_harness_name_unqualified())
+            }
+            HarnessFilter::Regex(regex) => regex.is_match(&harness.pretty_name),
         }
     }
 }
@@ -163,35 +252,18 @@ pub fn sort_harnesses_by_loc<'a>(harnesses: &[&'a HarnessMetadata]) -> Vec<&'a H
     harnesses_clone
 }
 
-/// Search for a proof harness with a particular name.
-/// At the present time, we use `no_mangle` so collisions shouldn't happen,
-/// but this function is written to be robust against that changing in the future.
+/// Search for the proof harnesses that match any of the given filters.
 fn find_proof_harnesses<'a>(
-    targets: &BTreeSet<&String>,
+    filters: &[HarnessFilter],
     all_harnesses: &[&'a HarnessMetadata],
-    exact_filter: bool,
 ) -> Vec<&'a HarnessMetadata> {
-    debug!(?targets, "find_proof_harness");
+    debug!(?filters, "find_proof_harness");
     let mut result = vec![];
     for md in all_harnesses.iter() {
-        if exact_filter {
-            // Check for exact match only
-            if targets.contains(&md.pretty_name) {
-                // if exact match found, stop searching
-                result.push(*md);
-            } else {
-                trace!(skip = md.pretty_name, "find_proof_harnesses");
-            }
+        if filters.iter().any(|filter| filter.matches(md)) {
+            result.push(*md);
         } else {
-            // Either an exact match, or a substring match. We check the exact first since it's cheaper.
-            if targets.contains(&md.pretty_name)
-                || targets.contains(&md.get_harness_name_unqualified().to_string())
-                || targets.iter().any(|target| md.pretty_name.contains(*target))
-            {
-                result.push(*md);
-            } else {
-                trace!(skip = md.pretty_name, "find_proof_harnesses");
-            }
+            trace!(skip = md.pretty_name, "find_proof_harnesses");
         }
     }
     result
@@ -200,8 +272,14 @@ fn find_proof_harnesses<'a>(
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::call_cbmc::{
+        determine_failed_properties, FailedProperties, VerificationResult, VerificationStatus,
+    };
+    use crate::cbmc_output_parser::Property;
+    use crate::stats::HarnessStats;
     use kani_metadata::{HarnessAttributes, HarnessKind};
     use std::path::PathBuf;
+    use std::time::Duration;
 
     pub fn mock_proof_harness(
         name: &str,
@@ -221,9 +299,34 @@ pub fn mock_proof_harness(
             attributes,
             goto_file: model_file,
             contract: Default::default(),
+            assumptions: vec![],
+        }
+    }
+
+    /// A result with the given properties, or CBMC exit code, that took one second.
+    pub(crate) fn mock_verification_result(
+        status: VerificationStatus,
+        results: Result<Vec<Property>, i32>,
+    ) -> VerificationResult {
+        let failed_properties =
+            results.as_deref().map_or(FailedProperties::Other, determine_failed_properties);
+        VerificationResult {
+            status,
+            failed_properties,
+            results,
+            runtime: Duration::from_secs(1),
+            generated_concrete_test: false,
+            inferred_unwind: None,
+            coverage_results: None,
+            stats: HarnessStats::default(),
+            unsatisfiable_assumptions: vec![],
         }
     }
 
+    fn filters(filters: &[&str], exact: bool) -> Vec<HarnessFilter> {
+        filters.iter().map(|filter| HarnessFilter::parse(filter, exact).unwrap()).collect()
+    }
+
     #[test]
     fn check_find_proof_harness_without_exact() {
         let harnesses = vec![
@@ -235,34 +338,21 @@ fn check_find_proof_harness_without_exact() {
 
         // Check with harness filtering
         assert_eq!(
-            find_proof_harnesses(
-                &BTreeSet::from([&"check_three".to_string()]),
-                &ref_harnesses,
-                false,
-            )
-            .len(),
+            find_proof_harnesses(&filters(&["check_three"], false), &ref_harnesses).len(),
             1
         );
         assert!(
-            find_proof_harnesses(
-                &BTreeSet::from([&"check_two".to_string()]),
-                &ref_harnesses,
-                false,
-            )
-            .first()
-            .unwrap()
-            .mangled_name
+            find_proof_harnesses(&filters(&["check_two"], false), &ref_harnesses)
+                .first()
+                .unwrap()
+                .mangled_name
                 == "module::check_two"
         );
         assert!(
-            find_proof_harnesses(
-                &BTreeSet::from([&"check_one".to_string()]),
-                &ref_harnesses,
-                false,
-            )
-            .first()
-            .unwrap()
-            .mangled_name
+            find_proof_harnesses(&filters(&["check_one"], false), &ref_harnesses)
+                .first()
+                .unwrap()
+                .mangled_name
                 == "check_one"
         );
     }
@@ -278,35 +368,60 @@ fn check_find_proof_harness_with_exact() {
         ];
         let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
 
-        assert!(
-            find_proof_harnesses(
-                &BTreeSet::from([&"check_three".to_string()]),
-                &ref_harnesses,
-                true,
-            )
-            .is_empty()
-        );
-        assert!(
-            find_proof_harnesses(&BTreeSet::from([&"check_two".to_string()]), &ref_harnesses, true)
-                .is_empty()
-        );
+        assert!(find_proof_harnesses(&filters(&["check_three"], true), &ref_harnesses).is_empty());
+        assert!(find_proof_harnesses(&filters(&["check_two"], true), &ref_harnesses).is_empty());
         assert_eq!(
-            find_proof_harnesses(&BTreeSet::from([&"check_one".to_string()]), &ref_harnesses, true)
+            find_proof_harnesses(&filters(&["check_one"], true), &ref_harnesses)
                 .first()
                 .unwrap()
                 .mangled_name,
             "check_one"
         );
         assert_eq!(
-            find_proof_harnesses(
-                &BTreeSet::from([&"module::not_check_three".to_string()]),
-                &ref_harnesses,
-                true,
-            )
-            .first()
-            .unwrap()
-            .mangled_name,
+            find_proof_harnesses(&filters(&["module::not_check_three"], true), &ref_harnesses)
+                .first()
+                .unwrap()
+                .mangled_name,
             "module::not_check_three"
         );
     }
+
+    #[test]
+    fn check_find_proof_harness_with_patterns() {
+        let harnesses = vec![
+            mock_proof_harness("parser::check_one", None, None, None),
+            mock_proof_harness("parser::nested::check_two", None, None, None),
+            mock_proof_harness("codec::json_roundtrip", None, None, None),
+            mock_proof_harness("codec::check_three", None, None, None),
+        ];
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+        let names = |filter: &[&str]| {
+            find_proof_harnesses(&filters(filter, false), &ref_harnesses)
+                .iter()
+                .map(|h| h.pretty_name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&["parser::*"]), ["parser::check_one", "parser::nested::check_two"]);
+        assert_eq!(names(&["*_roundtrip"]), ["codec::json_roundtrip"]);
+        assert_eq!(
+            names(&["parser::*", "*_roundtrip"]),
+            ["parser::check_one", "parser::nested::check_two", "codec::json_roundtrip"]
+        );
+        // Globs also match the unqualified name.
+        assert_eq!(names(&["check_t*"]), ["parser::nested::check_two", "codec::check_three"]);
+        assert_eq!(names(&["/^codec::.*e$/"]), ["codec::check_three"]);
+        assert_eq!(
+            names(&["/check_(one|two)/"]),
+            ["parser::check_one", "parser::nested::check_two"]
+        );
+    }
+
+    #[test]
+    fn check_invalid_harness_filter() {
+        assert!(HarnessFilter::parse("/check_(/", false).is_err());
+        assert!(HarnessFilter::parse("check_[", false).is_err());
+        // Patterns are not interpreted with `--exact`.
+        assert!(matches!(HarnessFilter::parse("check_[", true), Ok(HarnessFilter::Exact(_))));
+    }
 }
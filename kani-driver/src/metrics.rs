@@ -0,0 +1,224 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--metrics`, which pushes the metrics of a run to a StatsD server or to an
+//! OpenTelemetry collector, so the health of the proofs can be tracked in existing dashboards.
+//!
+//! The metrics of each harness are tagged with the names of the harness and its crate, and with
+//! its status:
+//! - `kani.harness.duration`: the verification time.
+//! - `kani.harness.success`: 1 if the harness succeeded, 0 otherwise.
+//! - `kani.harness.vccs` and `kani.harness.peak_memory`, when they are known.
+//!
+//! The metrics of the run are `kani.run.duration`, `kani.run.harnesses` and `kani.run.failures`.
+//!
+//! StatsD metrics are sent over UDP, using the DogStatsD extension for tags. OTLP metrics are
+//! sent as JSON with `curl`. Failing to push the metrics doesn't fail the run.
+
+use crate::args::MetricsEndpoint;
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::util::warning;
+use crate::version::KANI_VERSION;
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::net::UdpSocket;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One measurement, with the attributes that identify what was measured.
+#[derive(Debug, PartialEq)]
+struct Metric {
+    name: &'static str,
+    value: f64,
+    /// The unit, in the UCUM notation used by OpenTelemetry.
+    unit: &'static str,
+    attributes: Vec<(&'static str, String)>,
+}
+
+impl KaniSession {
+    /// Push the metrics of the given results to the endpoint of `--metrics`, if any.
+    pub(crate) fn export_metrics(&self, results: &[HarnessResult<'_>], duration: Duration) {
+        let Some(endpoint) = &self.args.metrics else { return };
+        let metrics = collect_metrics(results, duration);
+        let pushed = match endpoint {
+            MetricsEndpoint::Statsd(address) => push_statsd(address, &metrics),
+            MetricsEndpoint::Otlp(url) => push_otlp(url, &metrics),
+        };
+        if let Err(error) = pushed {
+            warning(&format!("failed to push the metrics: {error:#}"));
+        }
+    }
+}
+
+fn collect_metrics(results: &[HarnessResult<'_>], duration: Duration) -> Vec<Metric> {
+    let mut metrics = vec![];
+    for HarnessResult { harness, result } in results {
+        let attributes = vec![
+            ("harness", harness.pretty_name.clone()),
+            ("crate", harness.crate_name.clone()),
+            ("status", status_name(result.status).to_string()),
+        ];
+        let mut metric = |name, value, unit| {
+            metrics.push(Metric { name, value, unit, attributes: attributes.clone() })
+        };
+        metric("kani.harness.duration", result.runtime.as_secs_f64(), "s");
+        let success = result.status == VerificationStatus::Success;
+        metric("kani.harness.success", if success { 1.0 } else { 0.0 }, "1");
+        if let Some(vccs) = result.stats.vccs {
+            metric("kani.harness.vccs", vccs as f64, "1");
+        }
+        if let Some(peak_memory) = result.stats.peak_memory {
+            metric("kani.harness.peak_memory", peak_memory as f64, "By");
+        }
+    }
+    let failures =
+        results.iter().filter(|result| result.result.status != VerificationStatus::Success).count();
+    for (name, value, unit) in [
+        ("kani.run.duration", duration.as_secs_f64(), "s"),
+        ("kani.run.harnesses", results.len() as f64, "1"),
+        ("kani.run.failures", failures as f64, "1"),
+    ] {
+        metrics.push(Metric { name, value, unit, attributes: vec![] });
+    }
+    metrics
+}
+
+fn status_name(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Success => "success",
+        VerificationStatus::Failure => "failure",
+        VerificationStatus::Timeout => "timeout",
+        VerificationStatus::OutOfMemory => "out_of_memory",
+    }
+}
+
+/// Format the metrics as StatsD lines: durations are timers in milliseconds, and the other
+/// metrics are gauges.
+fn statsd_lines(metrics: &[Metric]) -> Vec<String> {
+    metrics
+        .iter()
+        .map(|metric| {
+            let value = if metric.unit == "s" {
+                format!("{}|ms", (metric.value * 1000.0).round())
+            } else {
+                format!("{}|g", metric.value)
+            };
+            let tags: Vec<String> = metric
+                .attributes
+                .iter()
+                .map(|(key, value)| format!("{key}:{}", value.replace([',', '|', '#'], "_")))
+                .collect();
+            if tags.is_empty() {
+                format!("{}:{value}", metric.name)
+            } else {
+                format!("{}:{value}|#{}", metric.name, tags.join(","))
+            }
+        })
+        .collect()
+}
+
+fn push_statsd(address: &str, metrics: &[Metric]) -> Result<()> {
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.connect(address).with_context(|| format!("Failed to resolve `{address}`"))?;
+    for line in statsd_lines(metrics) {
+        socket.send(line.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Build an OTLP `ExportMetricsServiceRequest` in its JSON encoding, with each metric as a gauge.
+fn otlp_request(metrics: &[Metric], time_unix_nano: u128) -> Value {
+    let metrics: Vec<Value> = metrics
+        .iter()
+        .map(|metric| {
+            let attributes: Vec<Value> = metric
+                .attributes
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+                .collect();
+            json!({
+                "name": metric.name,
+                "unit": metric.unit,
+                "gauge": {
+                    "dataPoints": [{
+                        "asDouble": metric.value,
+                        "timeUnixNano": time_unix_nano.to_string(),
+                        "attributes": attributes,
+                    }]
+                }
+            })
+        })
+        .collect();
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [{ "key": "service.name", "value": { "stringValue": "kani" } }]
+            },
+            "scopeMetrics": [{
+                "scope": { "name": "kani", "version": KANI_VERSION },
+                "metrics": metrics,
+            }]
+        }]
+    })
+}
+
+fn push_otlp(url: &str, metrics: &[Metric]) -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    let body = otlp_request(metrics, now).to_string();
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "-X", "POST"])
+        .args(["-H", "Content-Type: application/json", "--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .context("Failed to run `curl`")?;
+    child.stdin.take().unwrap().write_all(body.as_bytes())?;
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("`curl` failed to post the metrics to `{url}` ({status})");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics() -> Vec<Metric> {
+        vec![
+            Metric {
+                name: "kani.harness.duration",
+                value: 1.5,
+                unit: "s",
+                attributes: vec![("harness", "check".into()), ("status", "success".into())],
+            },
+            Metric { name: "kani.run.failures", value: 0.0, unit: "1", attributes: vec![] },
+        ]
+    }
+
+    #[test]
+    fn check_statsd_lines() {
+        assert_eq!(
+            statsd_lines(&metrics()),
+            [
+                "kani.harness.duration:1500|ms|#harness:check,status:success",
+                "kani.run.failures:0|g"
+            ]
+        );
+    }
+
+    #[test]
+    fn check_otlp_request() {
+        let request = otlp_request(&metrics(), 42);
+        let metrics = &request["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics[0]["name"], "kani.harness.duration");
+        let point = &metrics[0]["gauge"]["dataPoints"][0];
+        assert_eq!(point["asDouble"], 1.5);
+        assert_eq!(point["timeUnixNano"], "42");
+        assert_eq!(point["attributes"][0]["value"]["stringValue"], "check");
+        assert_eq!(metrics[1]["gauge"]["dataPoints"][0]["attributes"], json!([]));
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani mutate`, which checks whether the harnesses of a package constrain its
+//! behavior.
+//!
+//! We apply small mutations to the sources of the package, one at a time, e.g., replacing `<`
+//! with `<=`, and verify the harnesses against each mutant. A mutant that every harness accepts
+//! is a change the proofs can't tell apart from the original code, e.g., because a harness is
+//! vacuous or its assertions are too weak.
+//!
+//! Mutations are textual: we only replace binary operators surrounded by spaces, as formatted by
+//! rustfmt, outside of comments, string literals, attributes and verification code. A mutant that
+//! doesn't compile anyway is reported as unviable.
+//!
+//! The mutants are applied to a copy of the workspace in the target directory, so the sources of
+//! the user are never modified, even if the command is interrupted.
+
+use crate::args::mutate_args::CargoMutateArgs;
+use crate::call_cargo::packages_to_verify;
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessRunner;
+use crate::project;
+use crate::session::KaniSession;
+use crate::util;
+use crate::watch::{fingerprint, Fingerprints};
+use anyhow::{bail, Context, Result};
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The operators we mutate, with their replacement. The first ones change the boundaries of
+/// comparisons, the other ones swap operators.
+const MUTATIONS: &[(&str, &str)] = &[
+    ("<", "<="),
+    ("<=", "<"),
+    (">", ">="),
+    (">=", ">"),
+    ("==", "!="),
+    ("!=", "=="),
+    ("+", "-"),
+    ("-", "+"),
+    ("*", "/"),
+    ("/", "*"),
+    ("%", "*"),
+    ("+=", "-="),
+    ("-=", "+="),
+    ("&&", "||"),
+    ("||", "&&"),
+];
+
+/// A mutation of a source file.
+#[derive(Debug, PartialEq, Eq)]
+struct Mutant {
+    file: PathBuf,
+    /// The line and column of the operator, starting from 1.
+    line: usize,
+    column: usize,
+    /// The byte offset of the operator in the file.
+    offset: usize,
+    original: &'static str,
+    replacement: &'static str,
+}
+
+impl Display for Mutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: replaced `{}` with `{}`",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.original,
+            self.replacement
+        )
+    }
+}
+
+/// What the harnesses concluded about a mutant.
+enum Outcome {
+    /// At least one harness failed, with the names of the harnesses that failed.
+    Killed(Vec<String>),
+    /// Every harness succeeded.
+    Survived,
+    /// The mutant doesn't build.
+    Unviable,
+}
+
+/// Restores the original content of a mutated file when dropped, so the copy of the workspace can
+/// be reused for the next mutant.
+struct MutatedFile {
+    path: PathBuf,
+    original: String,
+}
+
+impl MutatedFile {
+    /// Apply `mutant` to its file in the copy of the workspace rooted at `copy`.
+    fn apply(mutant: &Mutant, workspace_root: &Path, copy: &Path) -> Result<Self> {
+        let path = copy.join(mutant.file.strip_prefix(workspace_root)?);
+        let original = fs::read_to_string(&path)?;
+        let mutated = format!(
+            "{}{}{}",
+            &original[..mutant.offset],
+            mutant.replacement,
+            &original[mutant.offset + mutant.original.len()..]
+        );
+        fs::write(&path, mutated)
+            .with_context(|| format!("Failed to write `{}`", path.display()))?;
+        Ok(MutatedFile { path, original })
+    }
+}
+
+impl Drop for MutatedFile {
+    fn drop(&mut self) {
+        if fs::write(&self.path, &self.original).is_err() {
+            util::error(&format!("Failed to restore `{}`", self.path.display()));
+        }
+    }
+}
+
+/// `cargo kani mutate` main entry point.
+pub(crate) fn mutate_cargo(mut session: KaniSession, args: CargoMutateArgs) -> Result<()> {
+    let metadata = session.cargo_metadata(session.build_target())?;
+    let selected_files =
+        args.file.iter().map(|file| file.canonicalize()).collect::<Result<Vec<_>, _>>()?;
+    let mut files = vec![];
+    for package in packages_to_verify(&session.args, &metadata)? {
+        for target in &package.targets {
+            // Tests, examples and benchmarks aren't the code under proof.
+            if !target.kind.iter().any(|kind| matches!(kind.as_str(), "lib" | "rlib" | "bin")) {
+                continue;
+            }
+            let src_dir = target.src_path.parent().unwrap().as_std_path();
+            // Only the files in the workspace are copied, and so can be mutated.
+            files.extend(
+                util::rust_files(src_dir)
+                    .into_iter()
+                    .filter(|file| file.starts_with(&metadata.workspace_root)),
+            );
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let workspace_root = metadata.workspace_root.as_std_path();
+    let mut mutants = vec![];
+    for file in files {
+        if !selected_files.is_empty() && !selected_files.contains(&file.canonicalize()?) {
+            continue;
+        }
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read `{}`", file.display()))?;
+        mutants.extend(find_mutants(&file, &content));
+    }
+    let display = |mutant: &Mutant| {
+        let file = mutant.file.strip_prefix(workspace_root).unwrap_or(&mutant.file);
+        Mutant { file: file.to_path_buf(), ..*mutant }.to_string()
+    };
+    if args.list {
+        for mutant in &mutants {
+            println!("{}", display(mutant));
+        }
+        return Ok(());
+    }
+    if mutants.is_empty() {
+        println!("[mutate] No mutants found.");
+        return Ok(());
+    }
+
+    // Build and verify everything from the copy, including the original code, so the models of
+    // the mutants only differ from the original ones where the code was mutated.
+    let target_dir =
+        session.args.target_dir.clone().unwrap_or_else(|| metadata.target_directory.clone().into());
+    let copy = target_dir.join("kani-mutate");
+    fs::create_dir_all(&target_dir)?;
+    if copy.exists() {
+        fs::remove_dir_all(&copy)?;
+    }
+    copy_workspace(workspace_root, &copy, &[target_dir.canonicalize()?])?;
+    let manifest = metadata.root_package().map_or_else(
+        || metadata.workspace_root.join("Cargo.toml"),
+        |package| package.manifest_path.clone(),
+    );
+    session.args.cargo.manifest_path =
+        Some(copy.join(manifest.strip_prefix(&metadata.workspace_root)?));
+    session.args.target_dir = Some(target_dir);
+
+    // The harnesses must hold for the original code, and we only verify again the harnesses whose
+    // model is changed by a mutant.
+    println!("[mutate] Verifying the original code...");
+    let project = project::cargo_project(&session, false)?;
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let mut fingerprints = Fingerprints::default();
+    for harness in &harnesses {
+        fingerprints.record_build(&harness.pretty_name, fingerprint(&project, harness)?);
+    }
+    let runner = HarnessRunner { sess: &session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+    let failed: Vec<_> = results
+        .iter()
+        .filter(|result| result.result.status != VerificationStatus::Success)
+        .map(|result| result.harness.pretty_name.as_str())
+        .collect();
+    if !failed.is_empty() {
+        bail!("cannot test mutants, since these harnesses fail on the original code: {failed:?}");
+    }
+    for harness in &harnesses {
+        fingerprints.record_verified(&harness.pretty_name, fingerprint(&project, harness)?);
+    }
+
+    session.args.common_args.quiet = true;
+    let (mut killed, mut survived, mut unviable) = (0, vec![], 0);
+    for (idx, mutant) in mutants.iter().enumerate() {
+        let outcome = {
+            let _mutated = MutatedFile::apply(mutant, workspace_root, &copy)?;
+            verify_mutant(&session, &fingerprints)?
+        };
+        let verdict = match &outcome {
+            Outcome::Killed(harnesses) => {
+                killed += 1;
+                format!("killed by {}", harnesses.join(", "))
+            }
+            Outcome::Survived => {
+                survived.push(mutant);
+                "survived".to_string()
+            }
+            Outcome::Unviable => {
+                unviable += 1;
+                "unviable".to_string()
+            }
+        };
+        println!("[mutate] ({}/{}) {}: {verdict}", idx + 1, mutants.len(), display(mutant));
+    }
+
+    println!(
+        "[mutate] {} mutants: {killed} killed, {} survived, {unviable} unviable.",
+        mutants.len(),
+        survived.len()
+    );
+    if survived.is_empty() {
+        return Ok(());
+    }
+    println!("Mutants accepted by every harness:");
+    for mutant in &survived {
+        println!(" - {}", display(mutant));
+    }
+    bail!("{} mutants survived", survived.len())
+}
+
+/// Copy the workspace at `root` to `dest`, except for the version control data and the `skipped`
+/// directories, e.g., the target directory.
+fn copy_workspace(root: &Path, dest: &Path, skipped: &[PathBuf]) -> Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name() == ".git" || skipped.contains(&path) {
+            continue;
+        }
+        let dest = dest.join(entry.file_name());
+        if path.is_dir() {
+            copy_workspace(&path, &dest, skipped)?;
+        } else {
+            fs::copy(&path, &dest)
+                .with_context(|| format!("Failed to copy `{}`", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Build the mutated package, and verify the harnesses whose model was changed by the mutation.
+fn verify_mutant(session: &KaniSession, fingerprints: &Fingerprints) -> Result<Outcome> {
+    let Ok(project) = project::cargo_project(session, false) else {
+        return Ok(Outcome::Unviable);
+    };
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let mut changed = vec![];
+    for harness in harnesses {
+        if !fingerprints.is_unchanged(&harness.pretty_name, fingerprint(&project, harness)?) {
+            changed.push(harness);
+        }
+    }
+    let runner = HarnessRunner { sess: session, project: &project };
+    let failed: Vec<String> = runner
+        .check_all_harnesses(&changed)?
+        .iter()
+        .filter(|result| result.result.status != VerificationStatus::Success)
+        .map(|result| result.harness.pretty_name.clone())
+        .collect();
+    Ok(if failed.is_empty() { Outcome::Survived } else { Outcome::Killed(failed) })
+}
+
+/// Find the mutants of a source file.
+///
+/// We skip the items annotated with `#[kani::proof]`, `#[cfg(kani)]`, `#[cfg(test)]` or
+/// `#[test]`, since they verify the code rather than implement it.
+fn find_mutants(file: &Path, content: &str) -> Vec<Mutant> {
+    let mut mutants = vec![];
+    let mut scanner = Scanner::default();
+    // Whether the next item is verification code, and the depth of the one we're in, if any.
+    let mut skip_next_item = false;
+    let mut skipped_depth = None;
+    let mut offset = 0;
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        let code = scanner.scan(line);
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("#!") && is_verification_attribute(trimmed) {
+            // The whole file is verification code.
+            return vec![];
+        }
+        if trimmed.starts_with("#[") {
+            skip_next_item |= skipped_depth.is_none() && is_verification_attribute(trimmed);
+        } else if !skip_next_item && skipped_depth.is_none() {
+            for (op, replacement) in MUTATIONS {
+                let pattern = format!(" {op} ");
+                for (pos, _) in line.match_indices(&pattern) {
+                    let start = pos + 1;
+                    if code[start] {
+                        mutants.push(Mutant {
+                            file: file.to_path_buf(),
+                            line: idx + 1,
+                            column: line[..start].chars().count() + 1,
+                            offset: offset + start,
+                            original: op,
+                            replacement,
+                        });
+                    }
+                }
+            }
+        }
+        for (pos, c) in line.char_indices() {
+            if !code[pos] {
+                continue;
+            }
+            match c {
+                '{' => {
+                    if skip_next_item && skipped_depth.is_none() {
+                        skipped_depth = Some(scanner.depth);
+                        skip_next_item = false;
+                    }
+                    scanner.depth += 1;
+                }
+                '}' => {
+                    scanner.depth = scanner.depth.saturating_sub(1);
+                    if skipped_depth == Some(scanner.depth) {
+                        skipped_depth = None;
+                    }
+                }
+                // An item without a body, e.g., `#[cfg(kani)] use crate::proofs;`
+                ';' if skipped_depth.is_none() => skip_next_item = false,
+                _ => {}
+            }
+        }
+        offset += line.len();
+    }
+    mutants.sort_by_key(|mutant| mutant.offset);
+    mutants
+}
+
+fn is_verification_attribute(line: &str) -> bool {
+    ["kani::proof", "cfg(kani)", "cfg(test)", "#[test]"].iter().any(|attr| line.contains(attr))
+}
+
+/// Tracks which bytes of a file are code, as opposed to comments and literals, across lines.
+#[derive(Default)]
+struct Scanner {
+    in_string: bool,
+    /// The number of `#` delimiting the raw string we're in, if any.
+    raw_string: Option<usize>,
+    block_comments: usize,
+    depth: usize,
+}
+
+impl Scanner {
+    /// Return whether each byte of the line is code.
+    fn scan(&mut self, line: &str) -> Vec<bool> {
+        let bytes = line.as_bytes();
+        let mut code = vec![false; bytes.len()];
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let next = bytes.get(pos + 1).copied();
+            if self.block_comments > 0 {
+                if bytes[pos] == b'*' && next == Some(b'/') {
+                    self.block_comments -= 1;
+                    pos += 1;
+                } else if bytes[pos] == b'/' && next == Some(b'*') {
+                    self.block_comments += 1;
+                    pos += 1;
+                }
+            } else if let Some(hashes) = self.raw_string {
+                // Raw strings have no escapes, and only end with a `"` followed by their `#`.
+                let end = pos + 1 + hashes;
+                if bytes[pos] == b'"'
+                    && bytes.get(pos + 1..end).is_some_and(|rest| rest.iter().all(|&b| b == b'#'))
+                {
+                    self.raw_string = None;
+                    pos = end - 1;
+                }
+            } else if self.in_string {
+                match bytes[pos] {
+                    b'\\' => pos += 1,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+            } else {
+                match (bytes[pos], next) {
+                    (b'/', Some(b'/')) => break,
+                    (b'/', Some(b'*')) => {
+                        self.block_comments += 1;
+                        pos += 1;
+                    }
+                    (b'"', _) => self.in_string = true,
+                    (b'r', Some(b'"' | b'#')) if starts_literal(bytes, pos) => {
+                        let hashes = bytes[pos + 1..].iter().take_while(|&&b| b == b'#').count();
+                        if bytes.get(pos + 1 + hashes) == Some(&b'"') {
+                            self.raw_string = Some(hashes);
+                            pos += 1 + hashes;
+                        } else {
+                            // A raw identifier, e.g., `r#type`.
+                            code[pos] = true;
+                        }
+                    }
+                    // A `"` or `{` character literal.
+                    (b'\'', Some(b'"' | b'{' | b'}')) if bytes.get(pos + 2) == Some(&b'\'') => {
+                        pos += 2;
+                    }
+                    _ => code[pos] = true,
+                }
+            }
+            pos += 1;
+        }
+        code
+    }
+}
+
+/// Whether the `r` at `pos` starts a raw (byte) string literal rather than ends an identifier.
+fn starts_literal(bytes: &[u8], pos: usize) -> bool {
+    let start = if pos > 0 && bytes[pos - 1] == b'b' { pos - 1 } else { pos };
+    start == 0 || !(bytes[start - 1].is_ascii_alphanumeric() || bytes[start - 1] == b'_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+/// Returns `a + b` if it doesn't overflow.
+pub fn add(a: u32, b: u32) -> Option<u32> {
+    if a <= u32::MAX - b { Some(a + b) } else { None }
+}
+
+pub fn describe(x: u32) -> &'static str {
+    if x == 0 { "x == 0" } else { "x != 0" } // x > 0
+}
+
+#[cfg(kani)]
+mod proofs {
+    #[kani::proof]
+    fn check_add() {
+        let (a, b) = (kani::any(), kani::any());
+        if a < 10 && b < 10 {
+            assert_eq!(super::add(a, b), Some(a + b));
+        }
+    }
+}
+
+#[kani::requires(x > 0)]
+pub fn dec(x: u32) -> u32 {
+    x - 1
+}
+"#;
+
+    #[test]
+    fn check_find_mutants() {
+        let mutants = find_mutants(Path::new("src/lib.rs"), SOURCE);
+        let found: Vec<String> = mutants.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            [
+                "src/lib.rs:4:10: replaced `<=` with `<`",
+                "src/lib.rs:4:22: replaced `-` with `+`",
+                "src/lib.rs:4:35: replaced `+` with `-`",
+                "src/lib.rs:8:10: replaced `==` with `!=`",
+                "src/lib.rs:24:7: replaced `-` with `+`",
+            ]
+        );
+        let mutant = &mutants[0];
+        assert_eq!(&SOURCE[mutant.offset..mutant.offset + 2], "<=");
+    }
+
+    #[test]
+    fn check_skipped_files() {
+        assert!(
+            find_mutants(
+                Path::new("src/proofs.rs"),
+                "#![cfg(kani)]\nfn f(x: u8) -> bool { x > 1 }\n"
+            )
+            .is_empty()
+        );
+        let source = "#[cfg(kani)]\nuse crate::proofs;\n\nfn f(x: u8) -> bool {\n    x > 1\n}\n";
+        assert_eq!(find_mutants(Path::new("src/lib.rs"), source).len(), 1);
+    }
+
+    #[test]
+    fn check_raw_strings() {
+        let source = r##"
+fn f() -> &'static str {
+    r#"a " b < c
+    d > e"#
+}
+
+fn g(x: usize) -> bool {
+    r"\".len() > x
+}
+"##;
+        let found: Vec<String> =
+            find_mutants(Path::new("src/lib.rs"), source).iter().map(ToString::to_string).collect();
+        assert_eq!(found, ["src/lib.rs:8:16: replaced `>` with `>=`"]);
+    }
+
+    #[test]
+    fn check_copy_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        for file in ["Cargo.toml", "src/lib.rs", "target/debug/lib.rlib", ".git/HEAD"] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file).unwrap();
+        }
+        let copy = root.join("target/kani-mutate");
+        copy_workspace(&root, &copy, &[root.join("target")]).unwrap();
+        assert_eq!(fs::read_to_string(copy.join("src/lib.rs")).unwrap(), "src/lib.rs");
+        assert!(copy.join("Cargo.toml").exists());
+        assert!(!copy.join("target").exists());
+        assert!(!copy.join(".git").exists());
+    }
+}
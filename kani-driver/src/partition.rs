@@ -0,0 +1,108 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--partition K/N`, which splits the selected harnesses into `N` shards and only
+//! verifies shard `K`, so several machines can verify a large suite without coordinating.
+//!
+//! The split only depends on the names of the harnesses (and on the runtimes given with
+//! `--partition-runtimes`), so every machine computes the same shards.
+
+use crate::args::Partition;
+use crate::report::baseline::Baseline;
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use std::collections::BTreeMap;
+
+impl KaniSession {
+    /// Keep the harnesses of the shard selected with `--partition`, if any.
+    pub(crate) fn select_partition<'a>(
+        &self,
+        harnesses: Vec<&'a HarnessMetadata>,
+    ) -> Result<Vec<&'a HarnessMetadata>> {
+        let Some(partition) = self.args.partition else { return Ok(harnesses) };
+        let runtimes = match &self.args.partition_runtimes {
+            Some(path) => Baseline::read(path)?
+                .harnesses
+                .into_iter()
+                .map(|harness| ((harness.crate_name, harness.harness), harness.runtime))
+                .collect(),
+            None => BTreeMap::new(),
+        };
+        Ok(shard(harnesses, partition, &runtimes))
+    }
+}
+
+/// The harnesses of the shard `partition.shard`.
+///
+/// Without runtimes, the harnesses are sorted by name and dealt to the shards in turn. With
+/// runtimes, the longest harnesses are assigned first, each to the shard with the lowest total
+/// runtime so far. Harnesses without a recorded runtime count as the average one.
+fn shard<'a>(
+    mut harnesses: Vec<&'a HarnessMetadata>,
+    partition: Partition,
+    runtimes: &BTreeMap<(String, String), f64>,
+) -> Vec<&'a HarnessMetadata> {
+    harnesses.sort_by(|a, b| (&a.crate_name, &a.pretty_name).cmp(&(&b.crate_name, &b.pretty_name)));
+    if runtimes.is_empty() {
+        return harnesses
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| index % partition.total == partition.shard - 1)
+            .map(|(_, harness)| harness)
+            .collect();
+    }
+
+    let average = runtimes.values().sum::<f64>() / runtimes.len() as f64;
+    let runtime = |harness: &HarnessMetadata| {
+        let key = (harness.crate_name.clone(), harness.pretty_name.clone());
+        runtimes.get(&key).copied().unwrap_or(average)
+    };
+    // The sort is stable, so harnesses with the same runtime stay sorted by name.
+    harnesses.sort_by(|a, b| runtime(b).total_cmp(&runtime(a)));
+    let mut totals = vec![0.0f64; partition.total];
+    let mut selected = vec![];
+    for harness in harnesses {
+        let (lightest, _) =
+            totals.iter().enumerate().min_by(|(_, a), (_, b)| a.total_cmp(b)).unwrap();
+        totals[lightest] += runtime(harness);
+        if lightest == partition.shard - 1 {
+            selected.push(harness);
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+
+    fn names(harnesses: &[&HarnessMetadata]) -> Vec<String> {
+        harnesses.iter().map(|harness| harness.pretty_name.clone()).collect()
+    }
+
+    #[test]
+    fn check_shard() {
+        let harnesses: Vec<_> = ["e", "d", "c", "b", "a"]
+            .iter()
+            .map(|name| mock_proof_harness(name, None, Some("krate"), None))
+            .collect();
+        let all: Vec<_> = harnesses.iter().collect();
+        let partition = |shard| Partition { shard, total: 2 };
+
+        let no_runtimes = BTreeMap::new();
+        assert_eq!(names(&shard(all.clone(), partition(1), &no_runtimes)), ["a", "c", "e"]);
+        assert_eq!(names(&shard(all.clone(), partition(2), &no_runtimes)), ["b", "d"]);
+
+        let runtimes = BTreeMap::from([
+            (("krate".to_string(), "a".to_string()), 100.0),
+            (("krate".to_string(), "b".to_string()), 60.0),
+            (("krate".to_string(), "c".to_string()), 20.0),
+            (("krate".to_string(), "d".to_string()), 20.0),
+        ]);
+        // `e` counts as the average runtime, 50s.
+        assert_eq!(names(&shard(all.clone(), partition(1), &runtimes)), ["a", "c"]);
+        assert_eq!(names(&shard(all, partition(2), &runtimes)), ["b", "e", "d"]);
+    }
+}
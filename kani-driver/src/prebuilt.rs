@@ -0,0 +1,109 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for crates that another build system, e.g., Bazel or Buck2, compiled with
+//! `kani-compiler`. Given the metadata files written by the compiler, we link and verify the
+//! models of the harnesses like the ones built with cargo.
+
+use crate::metadata::from_json;
+use anyhow::{Context, Result};
+use kani_metadata::{artifact::convert_type, ArtifactType, KaniMetadata};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The artifacts that `kani-compiler` writes next to the model of a harness.
+const MODEL_ARTIFACTS: [ArtifactType; 7] = [
+    ArtifactType::SymTabGoto,
+    ArtifactType::SymTab,
+    ArtifactType::TypeMap,
+    ArtifactType::VTableRestriction,
+    ArtifactType::PrettyNameMap,
+    ArtifactType::Reachability,
+    ArtifactType::UnsafeOperations,
+];
+
+/// Load the given metadata files, and copy the models of each crate to `outdir`, in a directory
+/// per crate.
+///
+/// Build outputs are often read-only, e.g., with Bazel, so the models can't be linked in place.
+/// Also, the models may have been compiled in a sandbox, so the paths recorded in the metadata may
+/// no longer exist. In that case, we look for the models next to the metadata file.
+pub(crate) fn load_metadata(
+    metadata_files: &[PathBuf],
+    outdir: &Path,
+) -> Result<Vec<KaniMetadata>> {
+    let mut crates = vec![];
+    for metadata_file in metadata_files {
+        let mut metadata: KaniMetadata = from_json(metadata_file).with_context(|| {
+            format!("Failed to read the metadata file `{}`", metadata_file.display())
+        })?;
+        let crate_dir = outdir.join(&metadata.crate_name);
+        fs::create_dir_all(&crate_dir)?;
+        let metadata_dir = metadata_file.parent().unwrap_or(Path::new("."));
+        // With `--shared-model`, several harnesses have the same model.
+        let mut copied = HashSet::new();
+        for harness in metadata.proof_harnesses.iter_mut().chain(metadata.test_harnesses.iter_mut())
+        {
+            let Some(goto_file) = &harness.goto_file else { continue };
+            let file_name = goto_file.file_name().context("Expected a model file")?.to_owned();
+            let model =
+                if goto_file.exists() { goto_file.clone() } else { metadata_dir.join(&file_name) };
+            if copied.insert(model.clone()) {
+                copy_model(&model, &crate_dir)?;
+            }
+            harness.goto_file = Some(crate_dir.join(file_name));
+        }
+        crates.push(metadata);
+    }
+    Ok(crates)
+}
+
+/// Copy the model of a harness, and the artifacts written next to it, to the given directory.
+fn copy_model(model: &Path, dir: &Path) -> Result<()> {
+    for typ in MODEL_ARTIFACTS {
+        let artifact = convert_type(model, ArtifactType::SymTabGoto, typ);
+        if typ != ArtifactType::SymTabGoto && !artifact.exists() {
+            continue;
+        }
+        // Write a new file rather than copying, since copies keep the read-only permissions.
+        let content = fs::read(&artifact)
+            .with_context(|| format!("Failed to read `{}`", artifact.display()))?;
+        fs::write(dir.join(artifact.file_name().unwrap()), content)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+
+    #[test]
+    fn check_load_metadata() {
+        let tmp = tempfile::tempdir().unwrap();
+        let build_dir = tmp.path().join("buck-out/parser");
+        fs::create_dir_all(&build_dir).unwrap();
+        fs::write(build_dir.join("parser.check.symtab.out"), "model").unwrap();
+        // The model path recorded in the sandbox doesn't exist anymore.
+        let sandbox_model = PathBuf::from("/sandbox/parser.check.symtab.out");
+        let metadata = KaniMetadata {
+            crate_name: "parser".into(),
+            proof_harnesses: vec![mock_proof_harness("check", None, None, Some(sandbox_model))],
+            unsupported_features: vec![],
+            test_harnesses: vec![],
+            public_functions: vec![],
+        };
+        fs::write(
+            build_dir.join("parser.kani-metadata.json"),
+            serde_json::to_string(&metadata).unwrap(),
+        )
+        .unwrap();
+        let outdir = tmp.path().join("target");
+        let metadata_file = build_dir.join("parser.kani-metadata.json");
+        let crates = load_metadata(&[metadata_file], &outdir).unwrap();
+        let goto_file = crates[0].proof_harnesses[0].goto_file.as_ref().unwrap();
+        assert_eq!(*goto_file, outdir.join("parser/parser.check.symtab.out"));
+        assert_eq!(fs::read_to_string(goto_file).unwrap(), "model");
+    }
+}
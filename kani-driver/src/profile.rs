@@ -0,0 +1,167 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Break down the time spent in each phase of the verification, reported with
+//! `--profile-phases`.
+//!
+//! The build (cargo and the codegen of `kani-compiler`) is timed once for the whole project.
+//! Linking and instrumenting the goto binary are timed for each harness by the driver, while the
+//! symbolic execution and solver times are taken from the messages printed by CBMC. The rest of
+//! the CBMC runtime is reported as "Other CBMC".
+
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::stats::{format_table, optional};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The phases timed by the driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// `cargo build` or `kani-compiler`, including the codegen.
+    Build,
+    /// Linking the goto binary of a harness.
+    Link,
+    /// The `goto-instrument` passes run on the goto binary of a harness.
+    Instrument,
+}
+
+/// The time spent in each phase, either for the whole project or per harness.
+#[derive(Debug, Default)]
+pub struct PhaseTimes {
+    times: Mutex<HashMap<(Phase, Option<String>), Duration>>,
+}
+
+impl PhaseTimes {
+    /// Add `duration` to the time spent in `phase`, for the given harness if any.
+    pub fn record(&self, phase: Phase, harness: Option<&str>, duration: Duration) {
+        let mut times = self.times.lock().unwrap();
+        *times.entry((phase, harness.map(str::to_string))).or_default() += duration;
+    }
+
+    pub fn get(&self, phase: Phase, harness: Option<&str>) -> Option<Duration> {
+        self.times.lock().unwrap().get(&(phase, harness.map(str::to_string))).copied()
+    }
+}
+
+impl KaniSession {
+    /// Execute `func` and record the time it took as part of `phase`.
+    pub fn time_phase<T>(
+        &self,
+        phase: Phase,
+        harness: Option<&str>,
+        func: impl FnOnce() -> T,
+    ) -> T {
+        let start = Instant::now();
+        let ret = func();
+        self.phase_times.record(phase, harness, start.elapsed());
+        ret
+    }
+}
+
+/// Format the time spent in each phase, with one row per harness, slowest harness first.
+pub fn format_phase_profile(times: &PhaseTimes, results: &[HarnessResult<'_>]) -> String {
+    let secs =
+        |duration: Option<Duration>| optional(duration.map(|d| format!("{:.2}", d.as_secs_f64())));
+    let mut profile = String::from("Phase profile:\n");
+    writeln!(profile, "Build and codegen: {}s", secs(times.get(Phase::Build, None))).unwrap();
+
+    let mut rows: Vec<(Duration, Vec<String>)> = results
+        .iter()
+        .map(|HarnessResult { harness, result }| {
+            let name = harness.pretty_name.as_str();
+            let link = times.get(Phase::Link, Some(name));
+            let instrument = times.get(Phase::Instrument, Some(name));
+            let stats = &result.stats;
+            let symex = stats.symex_time.map(Duration::from_secs_f64);
+            let solver = stats.solver_time.map(Duration::from_secs_f64);
+            let other = result
+                .runtime
+                .saturating_sub(symex.unwrap_or_default())
+                .saturating_sub(solver.unwrap_or_default());
+            let total = link.unwrap_or_default() + instrument.unwrap_or_default() + result.runtime;
+            let row = vec![
+                name.to_string(),
+                secs(link),
+                secs(instrument),
+                secs(symex),
+                secs(solver),
+                secs(Some(other)),
+                secs(Some(total)),
+            ];
+            (total, row)
+        })
+        .collect();
+    rows.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let header = [
+        "Harness",
+        "Link (s)",
+        "Instrument (s)",
+        "Symex (s)",
+        "Solver (s)",
+        "Other CBMC (s)",
+        "Total (s)",
+    ];
+    let rows: Vec<_> = rows.into_iter().map(|(_, row)| row).collect();
+    profile.push_str(&format_table(&header, &rows));
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::{VerificationResult, VerificationStatus};
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+    use crate::stats::HarnessStats;
+
+    #[test]
+    fn check_phase_times() {
+        let times = PhaseTimes::default();
+        times.record(Phase::Link, Some("a"), Duration::from_secs(1));
+        times.record(Phase::Link, Some("a"), Duration::from_secs(2));
+        times.record(Phase::Link, Some("b"), Duration::from_secs(4));
+        assert_eq!(times.get(Phase::Link, Some("a")), Some(Duration::from_secs(3)));
+        assert_eq!(times.get(Phase::Link, Some("b")), Some(Duration::from_secs(4)));
+        assert_eq!(times.get(Phase::Link, None), None);
+        assert_eq!(times.get(Phase::Instrument, Some("a")), None);
+    }
+
+    #[test]
+    fn check_format_phase_profile() {
+        let fast = mock_proof_harness("fast", None, None, None);
+        let slow = mock_proof_harness("slow", None, None, None);
+        let result = |runtime, stats| VerificationResult {
+            runtime,
+            stats,
+            ..mock_verification_result(VerificationStatus::Success, Ok(vec![]))
+        };
+        let stats = HarnessStats {
+            solver_time: Some(6.0),
+            symex_time: Some(3.0),
+            ..HarnessStats::default()
+        };
+        let results = [
+            HarnessResult {
+                harness: &fast,
+                result: result(Duration::from_millis(500), HarnessStats::default()),
+            },
+            HarnessResult { harness: &slow, result: result(Duration::from_secs(10), stats) },
+        ];
+        let times = PhaseTimes::default();
+        times.record(Phase::Build, None, Duration::from_secs(20));
+        times.record(Phase::Link, Some("slow"), Duration::from_millis(250));
+        times.record(Phase::Instrument, Some("slow"), Duration::from_millis(750));
+        times.record(Phase::Link, Some("fast"), Duration::from_millis(100));
+        assert_eq!(
+            format_phase_profile(&times, &results),
+            "Phase profile:\n\
+             Build and codegen: 20.00s\n\
+             Harness  Link (s)  Instrument (s)  Symex (s)  Solver (s)  Other CBMC (s)  Total (s)\n\
+             slow         0.25            0.75       3.00        6.00            1.00      11.00\n\
+             fast         0.10               -          -           -            0.50       0.60\n"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--quiet --progress`, which replaces the output of each harness by a single line
+//! with the progress of the run, and only prints the results of the harnesses that failed.
+//!
+//! When stderr is a terminal, the line is redrawn in place, at least once per second. Otherwise,
+//! e.g., in CI logs, only the failures and the final line are printed.
+
+use crate::call_cbmc::VerificationResult;
+use crate::session::KaniSession;
+use console::Term;
+use kani_metadata::HarnessMetadata;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often the progress line is redrawn while no harness starts or completes.
+const TICK: Duration = Duration::from_secs(1);
+
+/// The progress of the verification of the harnesses of a run.
+pub struct Progress {
+    term: Term,
+    /// Whether the progress line can be redrawn in place.
+    live: bool,
+    total: usize,
+    done: AtomicUsize,
+    failed: AtomicUsize,
+    /// The harnesses being verified, in the order they started.
+    running: Mutex<Vec<String>>,
+    start: Instant,
+    finished: AtomicBool,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Progress {
+        let term = Term::stderr();
+        Progress {
+            live: term.is_term(),
+            term,
+            total,
+            done: AtomicUsize::new(0),
+            failed: AtomicUsize::new(0),
+            running: Mutex::new(vec![]),
+            start: Instant::now(),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    /// Record that the verification of `harness` started.
+    pub fn start(&self, harness: &HarnessMetadata) {
+        self.running.lock().unwrap().push(harness.pretty_name.clone());
+        self.draw();
+    }
+
+    /// Record the result of `harness`, and print it if it failed.
+    pub fn finish(
+        &self,
+        session: &KaniSession,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+        failed: bool,
+    ) {
+        self.running.lock().unwrap().retain(|name| *name != harness.pretty_name);
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if failed {
+            self.failed.fetch_add(1, Ordering::Relaxed);
+            let details =
+                result.render(&session.args.output_format, harness.attributes.should_panic);
+            self.print(&format!(
+                "[{done}/{}] Verification failed for - {}\n{details}",
+                self.total, harness.pretty_name
+            ));
+        }
+        self.draw();
+    }
+
+    /// Redraw the progress line every [`TICK`] until [`Self::finish_all`] is called, so the
+    /// elapsed time stays current while long harnesses run.
+    pub fn tick(&self) {
+        while !self.finished.load(Ordering::Relaxed) {
+            std::thread::sleep(TICK);
+            self.draw();
+        }
+    }
+
+    /// Replace the progress line by a final summary.
+    pub fn finish_all(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+        let line = format!(
+            "Verified {} of {} harnesses in {}: {} failed",
+            self.done.load(Ordering::Relaxed),
+            self.total,
+            format_elapsed(self.start.elapsed()),
+            self.failed.load(Ordering::Relaxed)
+        );
+        self.print(&line);
+    }
+
+    fn line(&self) -> String {
+        let done = self.done.load(Ordering::Relaxed);
+        let elapsed = format_elapsed(self.start.elapsed());
+        let running = self.running.lock().unwrap();
+        let mut line = format!("[{done}/{}] {elapsed}", self.total);
+        if let Some(current) = running.last() {
+            line.push_str(&format!(" checking {current}"));
+            if running.len() > 1 {
+                line.push_str(&format!(" (and {} more)", running.len() - 1));
+            }
+        }
+        // Keep the line from wrapping, since only the last line can be redrawn.
+        let width = self.term.size().1 as usize;
+        console::truncate_str(&line, width.saturating_sub(1), "...").into_owned()
+    }
+
+    fn draw(&self) {
+        if self.live && !self.finished.load(Ordering::Relaxed) {
+            let _ = self.term.clear_line();
+            let _ = self.term.write_str(&self.line());
+        }
+    }
+
+    /// Print a message above the progress line.
+    fn print(&self, msg: &str) {
+        if self.live {
+            let _ = self.term.clear_line();
+        }
+        let _ = self.term.write_line(msg.trim_end());
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(5)), "00:05");
+        assert_eq!(format_elapsed(Duration::from_secs(754)), "12:34");
+    }
+}
@@ -4,13 +4,19 @@
 //! The goal is to provide one project view independent on the build system (cargo / standalone
 //! rustc) and its configuration (e.g.: linker type).
 
+use crate::args::Backend;
+use crate::artifact_cache::CachedStep;
+use crate::events::Event;
 use crate::metadata::from_json;
+use crate::prebuilt;
+use crate::profile::Phase;
 use crate::session::KaniSession;
 use crate::util::crate_name;
 use anyhow::{Context, Result};
 use kani_metadata::{
     artifact::convert_type, ArtifactType, ArtifactType::*, HarnessMetadata, KaniMetadata,
 };
+use std::collections::HashSet;
 use std::env::current_dir;
 use std::fs;
 use std::ops::Deref;
@@ -86,13 +92,16 @@ fn try_new(
         session: &KaniSession,
         outdir: PathBuf,
         input: Option<PathBuf>,
-        metadata: Vec<KaniMetadata>,
+        mut metadata: Vec<KaniMetadata>,
         cargo_metadata: Option<cargo_metadata::Metadata>,
         failed_targets: Option<Vec<String>>,
     ) -> Result<Self> {
+        session.apply_harness_configs(&mut metadata);
         // For each harness (test or proof) from each metadata, read the path for the goto
         // SymTabGoto file. Use that path to find all the other artifacts.
         let mut artifacts = vec![];
+        // With `--shared-model`, several harnesses have the same model, which is linked once.
+        let mut linked = HashSet::new();
         for crate_metadata in &metadata {
             for harness_metadata in
                 crate_metadata.test_harnesses.iter().chain(crate_metadata.proof_harnesses.iter())
@@ -101,15 +110,41 @@ fn try_new(
                     harness_metadata.goto_file.as_ref().expect("Expected a model file"),
                     SymTabGoto,
                 )?;
+                if session.args.dry_run
+                    || session.args.backend == Backend::Why3
+                    || !linked.insert(symtab_out.path.clone())
+                {
+                    // Only the metadata is needed to plan the verification, and the Why3 backend
+                    // doesn't generate goto programs.
+                    continue;
+                }
                 let goto_path = convert_type(&symtab_out.path, symtab_out.typ, Goto);
 
                 // Link
-                session.link_goto_binary(&[symtab_out.to_path_buf()], &goto_path)?;
+                session.time_phase(Phase::Link, Some(&harness_metadata.pretty_name), || {
+                    let inputs = [symtab_out.to_path_buf()];
+                    session.cached_step(
+                        &outdir,
+                        CachedStep::Link,
+                        || session.link_cache_key(&inputs),
+                        &goto_path,
+                        || session.link_goto_binary(&inputs, &goto_path),
+                    )
+                })?;
                 let goto = Artifact::try_new(&goto_path, Goto)?;
 
                 // All other harness artifacts that may have been generated as part of the build.
                 artifacts.extend(
-                    [SymTab, TypeMap, VTableRestriction, PrettyNameMap].iter().filter_map(|typ| {
+                    [
+                        SymTab,
+                        TypeMap,
+                        VTableRestriction,
+                        PrettyNameMap,
+                        Reachability,
+                        UnsafeOperations,
+                    ]
+                    .iter()
+                    .filter_map(|typ| {
                         let artifact = Artifact::try_from(&symtab_out, *typ).ok()?;
                         Some(artifact)
                     }),
@@ -119,7 +154,10 @@ fn try_new(
             }
         }
 
-        Ok(Project { outdir, input, metadata, artifacts, cargo_metadata, failed_targets })
+        let project =
+            Project { outdir, input, metadata, artifacts, cargo_metadata, failed_targets };
+        session.emit(Event::BuildFinished { harnesses: project.get_all_harnesses().len() });
+        Ok(project)
     }
 }
 
@@ -174,7 +212,8 @@ pub fn try_from(artifact: &Artifact, typ: ArtifactType) -> Result<Self> {
 /// Accept a boolean to build as many targets as possible. The number of failures in that case can
 /// be collected from the project.
 pub fn cargo_project(session: &KaniSession, keep_going: bool) -> Result<Project> {
-    let outputs = session.cargo_build(keep_going)?;
+    session.emit(Event::BuildStarted);
+    let outputs = session.time_phase(Phase::Build, None, || session.cargo_build(keep_going))?;
     let outdir = outputs.outdir.canonicalize()?;
     // For the MIR Linker we know there is only one metadata per crate. Use that in our favor.
     let metadata =
@@ -242,8 +281,11 @@ fn build(self) -> Result<Project> {
         self.session.record_temporary_file(&self.metadata.path);
 
         // Build and link the artifacts.
+        self.session.emit(Event::BuildStarted);
         debug!(krate=?self.crate_name, input=?self.input, ?rlib_path, "build compile");
-        self.session.compile_single_rust_file(&self.input, &self.crate_name, &self.outdir)?;
+        self.session.time_phase(Phase::Build, None, || {
+            self.session.compile_single_rust_file(&self.input, &self.crate_name, &self.outdir)
+        })?;
 
         let metadata = from_json(&self.metadata)?;
 
@@ -311,3 +353,22 @@ pub(crate) fn std_project(std_path: &Path, session: &KaniSession) -> Result<Proj
     let metadata = outputs.iter().map(|md_file| from_json(md_file)).collect::<Result<Vec<_>>>()?;
     Project::try_new(session, outdir, None, metadata, None, None)
 }
+
+/// Generate a project from the metadata files of crates that another build system, e.g., Bazel
+/// or Buck2, compiled with `kani-compiler`. Unlike the other projects, this doesn't assume how the
+/// crates were built.
+pub(crate) fn prebuilt_project(
+    metadata_files: &[PathBuf],
+    session: &KaniSession,
+) -> Result<Project> {
+    let outdir = if let Some(target_dir) = &session.args.target_dir {
+        target_dir.clone()
+    } else {
+        current_dir()?.join("target")
+    };
+    fs::create_dir_all(&outdir)?; // This is a no-op if directory exists.
+    let outdir = outdir.canonicalize()?;
+
+    let metadata = prebuilt::load_metadata(metadata_files, &outdir)?;
+    Project::try_new(session, outdir, None, metadata, None, None)
+}
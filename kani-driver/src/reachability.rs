@@ -0,0 +1,143 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `--reachability-report`, which lists the functions included in the model of each
+//! harness, and `cargo kani why-reachable`, which prints a path through which a harness reaches
+//! a function.
+//!
+//! Both are built from the report that `kani-compiler` writes next to each model with
+//! `--reachability-report`. Since a model may be shared by several harnesses, the items of a
+//! harness are the ones reachable from it.
+
+use crate::args::why_reachable_args::CargoWhyReachableArgs;
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use anyhow::{bail, Context, Result};
+use kani_metadata::{
+    artifact::convert_type, ArtifactType, HarnessMetadata, ReachabilityReport, ReachableItem,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Read the reachability report of the model of the given harness.
+fn load_report(harness: &HarnessMetadata) -> Result<ReachabilityReport> {
+    let goto_file = harness.goto_file.as_ref().expect("Expected a model file");
+    let path = convert_type(goto_file, ArtifactType::SymTabGoto, ArtifactType::Reachability);
+    let report = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&report).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Write the items reachable from each of the given harnesses to `path`.
+pub fn write_report(harnesses: &[&HarnessMetadata], path: &Path) -> Result<()> {
+    let reports = harnesses
+        .iter()
+        .map(|harness| Ok((harness.pretty_name.as_str(), load_report(harness)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let items: BTreeMap<&str, Vec<&ReachableItem>> =
+        reports.iter().map(|(name, report)| (*name, report.reachable_from(name))).collect();
+    std::fs::write(path, serde_json::to_string_pretty(&items)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// `cargo kani why-reachable` main entry point.
+pub(crate) fn why_reachable_cargo(
+    mut session: KaniSession,
+    args: CargoWhyReachableArgs,
+) -> Result<()> {
+    session.args.reachability_report = true;
+    // Only the reports are needed, so we skip linking the models.
+    session.args.dry_run = true;
+    let project = project::cargo_project(&session, false)?;
+    let harness = find_harness(&project, &args.harness)?;
+    let report = load_report(harness)?;
+    let Some(path) =
+        report.call_path(&harness.pretty_name, |item| matches_function(&item.name, &args.function))
+    else {
+        bail!("`{}` is not reachable from harness `{}`", args.function, harness.pretty_name);
+    };
+    println!(
+        "`{}` is reachable from harness `{}` through:",
+        path.last().unwrap().name,
+        harness.pretty_name
+    );
+    for (idx, item) in path.iter().enumerate() {
+        let arrow = if idx == 0 { "   " } else { "-> " };
+        let stubbed = if item.stubbed { " (stubbed)" } else { "" };
+        println!("    {arrow}{} [{}]{stubbed}", item.name, item.krate);
+    }
+    Ok(())
+}
+
+/// Find the harness with the given name, which may omit the path of its module.
+fn find_harness<'a>(project: &'a Project, name: &str) -> Result<&'a HarnessMetadata> {
+    let suffix = format!("::{name}");
+    let harnesses: Vec<_> = project
+        .get_all_harnesses()
+        .into_iter()
+        .filter(|harness| harness.pretty_name == name || harness.pretty_name.ends_with(&suffix))
+        .collect();
+    match harnesses.as_slice() {
+        [harness] => Ok(*harness),
+        [] => bail!("no harness matches `{name}`"),
+        _ => {
+            let names: Vec<_> = harnesses.iter().map(|h| h.pretty_name.as_str()).collect();
+            bail!("`{name}` matches several harnesses: {}", names.join(", "))
+        }
+    }
+}
+
+/// Whether the item named `name` is the given function. The function may omit a prefix of the
+/// path and the generic arguments of the item.
+fn matches_function(name: &str, function: &str) -> bool {
+    let suffix = format!("::{function}");
+    let base = name.split_once("::<").map_or(name, |(base, _)| base);
+    [name, base].iter().any(|name| *name == function || name.ends_with(&suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_matches_function() {
+        assert!(matches_function("my_crate::parser::parse", "my_crate::parser::parse"));
+        assert!(matches_function("my_crate::parser::parse", "parser::parse"));
+        assert!(!matches_function("my_crate::parser::parse", "rse"));
+        assert!(matches_function("core::ptr::drop_in_place::<Foo>", "ptr::drop_in_place"));
+        assert!(matches_function("core::ptr::drop_in_place::<Foo>", "drop_in_place::<Foo>"));
+    }
+
+    #[test]
+    fn check_call_path() {
+        let item = |name: &str, uses: &[&str]| ReachableItem {
+            name: name.to_string(),
+            krate: "my_crate".to_string(),
+            stubbed: false,
+            uses: uses.iter().map(|name| name.to_string()).collect(),
+        };
+        let report = ReachabilityReport {
+            harnesses: vec!["first".into(), "second".into()],
+            items: vec![
+                item("first", &["a", "b"]),
+                item("second", &["c"]),
+                item("a", &["c"]),
+                item("b", &[]),
+                item("c", &["b"]),
+            ],
+        };
+        let names = |items: Vec<&ReachableItem>| -> Vec<String> {
+            items.iter().map(|item| item.name.clone()).collect()
+        };
+        assert_eq!(names(report.reachable_from("second")), ["second", "b", "c"]);
+        assert_eq!(
+            names(report.call_path("first", |item| item.name == "c").unwrap()),
+            ["first", "a", "c"]
+        );
+        assert_eq!(
+            names(report.call_path("second", |item| item.name == "b").unwrap()),
+            ["second", "c", "b"]
+        );
+        assert!(report.call_path("second", |item| item.name == "a").is_none());
+    }
+}
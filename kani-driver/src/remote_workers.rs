@@ -0,0 +1,213 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Distribute the verification of harnesses across remote workers.
+//!
+//! Kani still compiles the project and prepares the goto binary of each harness locally. With
+//! `--remote-worker <HOST>`, the binary is then copied to a worker with `scp`, and CBMC is run on
+//! the worker with `ssh`. Its output is streamed back and processed as if CBMC ran locally, so
+//! the results are gathered and reported by this process.
+//!
+//! Workers only need to be reachable with non-interactive `ssh` and to have CBMC (and any external
+//! solver in use) and the coreutils `timeout` in their path. Each harness is sent to the worker
+//! with the fewest harnesses in flight.
+//!
+//! Killing the local `ssh` process doesn't stop CBMC on the worker, so CBMC runs under `timeout`,
+//! which enforces the harness timeout on the worker and leads a process group with CBMC and its
+//! solver. Once the run is over, e.g., because it was cancelled, that group is stopped over `ssh`.
+//! The memory of the remote processes isn't measured, so memory limits don't apply to them.
+
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Options passed to `ssh` and `scp` so they fail instead of prompting the user.
+const SSH_OPTIONS: [&str; 2] = ["-o", "BatchMode=yes"];
+
+/// The workers given with `--remote-worker`.
+pub struct RemoteWorkers {
+    /// The host of each worker. A host may appear more than once.
+    hosts: Vec<String>,
+    /// The number of harnesses in flight per worker.
+    load: Mutex<Vec<usize>>,
+    /// The directory where binaries are copied on the workers.
+    dir: String,
+    /// Counter used to give a unique name to each binary copied to a worker.
+    uploads: AtomicUsize,
+}
+
+impl RemoteWorkers {
+    pub fn new(hosts: &[String], dir: &str) -> Option<RemoteWorkers> {
+        (!hosts.is_empty()).then(|| RemoteWorkers {
+            hosts: hosts.to_vec(),
+            load: Mutex::new(vec![0; hosts.len()]),
+            dir: dir.trim_end_matches('/').to_string(),
+            uploads: AtomicUsize::new(0),
+        })
+    }
+
+    /// Reserve the worker with the fewest harnesses in flight.
+    fn lease(&self) -> WorkerLease<'_> {
+        let mut load = self.load.lock().unwrap();
+        let idx = (0..load.len()).min_by_key(|idx| load[*idx]).unwrap();
+        load[idx] += 1;
+        WorkerLease { workers: self, idx }
+    }
+}
+
+/// A worker reserved for one CBMC run, which is released once dropped.
+struct WorkerLease<'a> {
+    workers: &'a RemoteWorkers,
+    idx: usize,
+}
+
+impl WorkerLease<'_> {
+    fn host(&self) -> &str {
+        &self.workers.hosts[self.idx]
+    }
+}
+
+impl Drop for WorkerLease<'_> {
+    fn drop(&mut self) {
+        self.workers.load.lock().unwrap()[self.idx] -= 1;
+    }
+}
+
+/// A CBMC run on a remote worker. Once dropped, the run is stopped if it's still going, and the
+/// binary copied to the worker is deleted.
+pub struct RemoteJob<'a> {
+    lease: WorkerLease<'a>,
+    remote_file: String,
+    keep_temps: bool,
+}
+
+impl Drop for RemoteJob<'_> {
+    fn drop(&mut self) {
+        // `timeout` forwards the signal to the process group of CBMC. The pattern doesn't match
+        // the shell that runs `pkill`, since its command line doesn't start with `timeout`.
+        let pattern = format!("^timeout .* {}( |$)", regex::escape(&self.remote_file));
+        let mut command = remote_command("pkill", ["-TERM".into(), "-f".into(), pattern]);
+        if !self.keep_temps {
+            write!(command, "; rm -f {}", shell_quote(&self.remote_file)).unwrap();
+        }
+        // This is best effort, the directory of the workers is meant to be temporary anyway.
+        let _ = Command::new("ssh").args(SSH_OPTIONS).arg(self.lease.host()).arg(command).output();
+    }
+}
+
+impl KaniSession {
+    /// Create the command that runs CBMC with `args` to verify `file`. With remote workers, the
+    /// file is copied to one of them and the command runs CBMC on it with `ssh`, which stops CBMC
+    /// after `timeout`, if any. The returned job must be kept alive until the command finishes.
+    pub fn cbmc_command(
+        &self,
+        file: &Path,
+        args: Vec<OsString>,
+        timeout: Option<Duration>,
+    ) -> Result<(Command, Option<RemoteJob<'_>>)> {
+        let Some(workers) = &self.remote_workers else {
+            // TODO get cbmc path from self
+            let mut cmd = Command::new("cbmc");
+            cmd.args(args);
+            return Ok((cmd, None));
+        };
+
+        let lease = workers.lease();
+        let file_name = file.file_name().unwrap().to_string_lossy();
+        let upload = workers.uploads.fetch_add(1, Ordering::Relaxed);
+        let remote_file = format!("{}/{}-{upload}-{file_name}", workers.dir, std::process::id());
+        self.upload_file(lease.host(), &workers.dir, file, &remote_file)?;
+
+        let args = args.into_iter().map(|arg| {
+            if arg == file.as_os_str() { remote_file.clone() } else { arg.to_string_lossy().into() }
+        });
+        let command = remote_command("exec timeout", timeout_args(timeout).into_iter().chain(args));
+        let mut cmd = Command::new("ssh");
+        cmd.args(SSH_OPTIONS).arg(lease.host()).arg(command);
+        Ok((cmd, Some(RemoteJob { lease, remote_file, keep_temps: self.args.keep_temps })))
+    }
+
+    fn upload_file(&self, host: &str, dir: &str, file: &Path, remote_file: &str) -> Result<()> {
+        let mut mkdir = Command::new("ssh");
+        mkdir.args(SSH_OPTIONS).arg(host).arg(remote_command("mkdir", ["-p".into(), dir.into()]));
+        self.run_suppress(mkdir)
+            .with_context(|| format!("Failed to reach the remote worker `{host}`"))?;
+
+        let mut scp = Command::new("scp");
+        scp.args(SSH_OPTIONS).arg("-q").arg(file).arg(format!("{host}:{remote_file}"));
+        self.run_suppress(scp).with_context(|| {
+            format!("Failed to copy `{}` to the remote worker `{host}`", file.display())
+        })
+    }
+}
+
+/// The arguments of `timeout` to run CBMC for at most `timeout`. A duration of 0 disables it.
+fn timeout_args(timeout: Option<Duration>) -> Vec<String> {
+    let seconds = timeout.map_or(0, |timeout| timeout.as_secs().max(1));
+    ["-s", "KILL", &seconds.to_string(), "cbmc"].map(String::from).to_vec()
+}
+
+/// Build a command line to be run by the remote shell.
+fn remote_command(program: &str, args: impl IntoIterator<Item = String>) -> String {
+    let mut command = program.to_string();
+    for arg in args {
+        write!(command, " {}", shell_quote(&arg)).unwrap();
+    }
+    command
+}
+
+/// Quote an argument for a POSIX shell, unless it only contains safe characters.
+fn shell_quote(arg: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_shell_quote() {
+        assert_eq!(shell_quote("--unwind"), "--unwind");
+        assert_eq!(shell_quote("/tmp/kani-remote/a.out"), "/tmp/kani-remote/a.out");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$(rm -rf /)"), "'$(rm -rf /)'");
+    }
+
+    #[test]
+    fn check_remote_command() {
+        let args = ["--unwind".to_string(), "2".to_string(), "my file".to_string()];
+        assert_eq!(remote_command("cbmc", args), "cbmc --unwind 2 'my file'");
+        let args = timeout_args(Some(Duration::from_secs(30)));
+        assert_eq!(remote_command("exec timeout", args), "exec timeout -s KILL 30 cbmc");
+        assert_eq!(timeout_args(None), ["-s", "KILL", "0", "cbmc"]);
+    }
+
+    #[test]
+    fn check_lease_least_loaded() {
+        let hosts = ["a".to_string(), "b".to_string(), "a".to_string()];
+        let workers = RemoteWorkers::new(&hosts, "/tmp/kani-remote/").unwrap();
+        assert_eq!(workers.dir, "/tmp/kani-remote");
+        let first = workers.lease();
+        let second = workers.lease();
+        assert_eq!((first.idx, second.idx), (0, 1));
+        drop(first);
+        assert_eq!(workers.lease().idx, 0);
+        let third = workers.lease();
+        assert_eq!(third.host(), "a");
+        assert_eq!(third.idx, 0);
+        assert!(RemoteWorkers::new(&[], "/tmp").is_none());
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Save the results of a verification run as a baseline, and compare later runs against it.
+//!
+//! A baseline records the status and runtime of each harness. When comparing against one, only
+//! regressions are considered errors: harnesses that fail now but didn't fail in the baseline
+//! (including new harnesses), and harnesses that became slower than the configured threshold.
+//! This allows adopting Kani in a project that still has failing harnesses.
+
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// Runtimes below this value are too noisy to be compared.
+const MIN_COMPARABLE_RUNTIME: f64 = 1.0;
+
+/// The result of a verification run, as stored in a baseline file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The version of Kani that generated the baseline.
+    pub kani_version: String,
+    pub harnesses: Vec<BaselineHarness>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineHarness {
+    pub crate_name: String,
+    pub harness: String,
+    pub status: BaselineStatus,
+    /// The verification time in seconds.
+    pub runtime: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaselineStatus {
+    Success,
+    Failure,
+    Timeout,
+    #[serde(rename = "out_of_memory")]
+    OutOfMemory,
+}
+
+impl From<VerificationStatus> for BaselineStatus {
+    fn from(status: VerificationStatus) -> Self {
+        match status {
+            VerificationStatus::Success => BaselineStatus::Success,
+            VerificationStatus::Failure => BaselineStatus::Failure,
+            VerificationStatus::Timeout => BaselineStatus::Timeout,
+            VerificationStatus::OutOfMemory => BaselineStatus::OutOfMemory,
+        }
+    }
+}
+
+impl Baseline {
+    pub fn new(results: &[HarnessResult<'_>]) -> Baseline {
+        let harnesses = results
+            .iter()
+            .map(|HarnessResult { harness, result }| BaselineHarness {
+                crate_name: harness.crate_name.clone(),
+                harness: harness.pretty_name.clone(),
+                status: result.status.into(),
+                runtime: result.runtime.as_secs_f64(),
+            })
+            .collect();
+        Baseline { kani_version: env!("CARGO_PKG_VERSION").to_string(), harnesses }
+    }
+
+    pub fn read(path: &Path) -> Result<Baseline> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open baseline file `{}`", path.display()))?;
+        let reader = BufReader::new(file);
+        serde_json::from_reader(reader)
+            .with_context(|| format!("Failed to parse baseline file `{}`", path.display()))
+    }
+}
+
+/// Serialize the baseline for the given results into `path`.
+pub fn write_report(path: &Path, results: &[HarnessResult<'_>]) -> Result<()> {
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(writer, &Baseline::new(results))?;
+    Ok(())
+}
+
+/// The differences between the current run and the baseline.
+#[derive(Debug, Default)]
+pub struct BaselineComparison {
+    /// Harnesses that didn't fail in the baseline (or were not part of it), but fail now.
+    pub newly_failing: Vec<String>,
+    /// Harnesses that failed in the baseline, and succeed now.
+    pub newly_passing: Vec<String>,
+    /// Harnesses that are slower than the baseline by more than the threshold, with the old and
+    /// new runtimes.
+    pub newly_slow: Vec<(String, f64, f64)>,
+}
+
+impl BaselineComparison {
+    /// Compare the current results against the baseline.
+    ///
+    /// A harness is considered newly slow if its runtime increased by more than
+    /// `slowdown_threshold` percent.
+    pub fn new(
+        baseline: &Baseline,
+        results: &[HarnessResult<'_>],
+        slowdown_threshold: u32,
+    ) -> BaselineComparison {
+        let previous: BTreeMap<_, _> = baseline
+            .harnesses
+            .iter()
+            .map(|entry| ((entry.crate_name.as_str(), entry.harness.as_str()), entry))
+            .collect();
+        let mut comparison = BaselineComparison::default();
+        for current in Baseline::new(results).harnesses {
+            let name = current.harness.clone();
+            let old = previous.get(&(current.crate_name.as_str(), current.harness.as_str()));
+            match (current.status, old) {
+                (BaselineStatus::Success, Some(old)) if old.status != BaselineStatus::Success => {
+                    comparison.newly_passing.push(name)
+                }
+                (BaselineStatus::Success, Some(old)) => {
+                    let limit = old.runtime * (1.0 + f64::from(slowdown_threshold) / 100.0);
+                    if current.runtime >= MIN_COMPARABLE_RUNTIME && current.runtime > limit {
+                        comparison.newly_slow.push((name, old.runtime, current.runtime));
+                    }
+                }
+                (BaselineStatus::Success, None) => {}
+                // The harness was already failing.
+                (_, Some(old)) if old.status != BaselineStatus::Success => {}
+                (_, _) => comparison.newly_failing.push(name),
+            }
+        }
+        comparison
+    }
+
+    /// Whether the current run has regressed compared to the baseline.
+    pub fn has_regressions(&self) -> bool {
+        !self.newly_failing.is_empty() || !self.newly_slow.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!("Baseline comparison:");
+        for harness in &self.newly_failing {
+            println!(" - Newly failing: {harness}");
+        }
+        for harness in &self.newly_passing {
+            println!(" - Newly passing: {harness}");
+        }
+        for (harness, old, new) in &self.newly_slow {
+            println!(" - Newly slow: {harness} ({old:.2}s -> {new:.2}s)");
+        }
+        println!(
+            "{} newly failing, {} newly passing, {} newly slow harnesses.",
+            self.newly_failing.len(),
+            self.newly_passing.len(),
+            self.newly_slow.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+    use std::time::Duration;
+
+    fn result(status: VerificationStatus, runtime: u64) -> VerificationResult {
+        let runtime = Duration::from_secs(runtime);
+        VerificationResult { runtime, ..mock_verification_result(status, Ok(vec![])) }
+    }
+
+    fn entry(harness: &str, status: BaselineStatus, runtime: f64) -> BaselineHarness {
+        BaselineHarness {
+            crate_name: "krate".to_string(),
+            harness: harness.to_string(),
+            status,
+            runtime,
+        }
+    }
+
+    #[test]
+    fn check_baseline_comparison() {
+        let baseline = Baseline {
+            kani_version: "0.0.0".to_string(),
+            harnesses: vec![
+                entry("still_failing", BaselineStatus::Failure, 1.0),
+                entry("regressed", BaselineStatus::Success, 1.0),
+                entry("fixed", BaselineStatus::Timeout, 10.0),
+                entry("slow", BaselineStatus::Success, 2.0),
+                entry("noisy", BaselineStatus::Success, 0.1),
+            ],
+        };
+        let names = ["still_failing", "regressed", "fixed", "slow", "noisy", "new"];
+        let harnesses: Vec<_> =
+            names.iter().map(|name| mock_proof_harness(name, None, Some("krate"), None)).collect();
+        let statuses = [
+            (VerificationStatus::Failure, 1),
+            (VerificationStatus::Failure, 1),
+            (VerificationStatus::Success, 1),
+            (VerificationStatus::Success, 4),
+            (VerificationStatus::Success, 0),
+            (VerificationStatus::Failure, 1),
+        ];
+        let results: Vec<_> = harnesses
+            .iter()
+            .zip(statuses)
+            .map(|(harness, (status, time))| HarnessResult {
+                harness,
+                result: result(status, time),
+            })
+            .collect();
+
+        let comparison = BaselineComparison::new(&baseline, &results, 50);
+        assert_eq!(comparison.newly_failing, ["regressed", "new"]);
+        assert_eq!(comparison.newly_passing, ["fixed"]);
+        assert_eq!(comparison.newly_slow, [("slow".to_string(), 2.0, 4.0)]);
+        assert!(comparison.has_regressions());
+
+        // Failures that were already in the baseline are not regressions.
+        let comparison = BaselineComparison::new(&baseline, &results[..1], 50);
+        assert!(!comparison.has_regressions());
+    }
+}
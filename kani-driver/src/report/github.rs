@@ -0,0 +1,167 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generate [GitHub Actions workflow commands] for the verification results, which GitHub shows
+//! as annotations on the lines of the pull request diff.
+//!
+//! Each failed property is reported as an `::error`, at the location where it failed, and each
+//! unreachable cover property as a `::warning`. Harnesses that failed without any property, e.g.,
+//! because of a timeout, are reported at the location of the harness.
+//!
+//! [GitHub Actions workflow commands]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+
+use crate::call_cbmc::{FailedProperties, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, SourceLocation};
+use crate::harness_runner::HarnessResult;
+use crate::report::failure_location;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Print the annotations for the given results.
+pub fn print_annotations(results: &[HarnessResult<'_>]) {
+    // Annotations must be relative to the root of the repository, which GitHub exports.
+    let root = std::env::var_os("GITHUB_WORKSPACE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+    print!("{}", annotations(&root, results));
+}
+
+fn annotations(root: &Path, results: &[HarnessResult<'_>]) -> String {
+    let mut out = String::new();
+    for HarnessResult { harness, result } in results {
+        let name = &harness.pretty_name;
+        let harness_location = Annotation {
+            file: Some(relative_path(root, &harness.original_file)),
+            line: Some(harness.original_start_line.to_string()),
+        };
+        match &result.results {
+            Ok(properties) => {
+                for property in properties {
+                    if property.status == CheckStatus::Failure {
+                        let location = Annotation::from_source(root, failure_location(property));
+                        location.write(&mut out, "error", name, &property.description);
+                    } else if property.is_cover_property()
+                        && property.status == CheckStatus::Unreachable
+                    {
+                        let location = Annotation::from_source(root, &property.source_location);
+                        let message = format!("{} is unreachable", property.description);
+                        location.write(&mut out, "warning", name, &message);
+                    }
+                }
+                if result.status == VerificationStatus::Failure
+                    && harness.attributes.should_panic
+                    && matches!(result.failed_properties, FailedProperties::None)
+                {
+                    let message = "harness was expected to panic, but no panic was encountered";
+                    harness_location.write(&mut out, "error", name, message);
+                }
+            }
+            Err(_) => {
+                let message = match result.status {
+                    VerificationStatus::Timeout => {
+                        format!("verification timed out after {}s", result.runtime.as_secs())
+                    }
+                    VerificationStatus::OutOfMemory => {
+                        "verification exceeded its memory limit".to_string()
+                    }
+                    _ => "verification failed".to_string(),
+                };
+                harness_location.write(&mut out, "error", name, &message);
+            }
+        }
+    }
+    out
+}
+
+/// The location of an annotation. Without a file, GitHub only shows it in the summary of the run.
+struct Annotation {
+    file: Option<String>,
+    line: Option<String>,
+}
+
+impl Annotation {
+    fn from_source(root: &Path, source: &SourceLocation) -> Annotation {
+        Annotation {
+            file: source.file.as_ref().map(|file| relative_path(root, file)),
+            line: source.line.clone(),
+        }
+    }
+
+    fn write(&self, out: &mut String, level: &str, harness: &str, message: &str) {
+        let mut params = vec![];
+        if let Some(file) = &self.file {
+            params.push(format!("file={}", escape_property(file)));
+            if let Some(line) = &self.line {
+                params.push(format!("line={}", escape_property(line)));
+            }
+        }
+        params.push(format!("title={}", escape_property(&format!("Kani: {harness}"))));
+        writeln!(out, "::{level} {}::{}", params.join(","), escape_data(message)).unwrap();
+    }
+}
+
+fn relative_path(root: &Path, file: &str) -> String {
+    let path = Path::new(file);
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().into_owned()
+}
+
+/// Escape the message of a workflow command.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape the value of a parameter of a workflow command.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{Property, PropertyId};
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+
+    fn property(class: &str, description: &str, status: CheckStatus) -> Property {
+        Property {
+            description: description.into(),
+            property_id: PropertyId { fn_name: Some("check".into()), class: class.into(), id: 1 },
+            source_location: SourceLocation {
+                column: Some("5".into()),
+                file: Some("/ws/src/lib.rs".into()),
+                function: Some("check".into()),
+                line: Some("10".into()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_annotations() {
+        let harness = mock_proof_harness("lib::check", None, None, None);
+        let properties = vec![
+            property("assertion", "assertion failed: x, y", CheckStatus::Failure),
+            property("assertion", "assertion failed: z", CheckStatus::Success),
+            property("cover", "cover condition: x > 1", CheckStatus::Unreachable),
+            property("cover", "cover condition: x > 2", CheckStatus::Unsatisfiable),
+        ];
+        let results = [
+            HarnessResult {
+                harness: &harness,
+                result: mock_verification_result(VerificationStatus::Failure, Ok(properties)),
+            },
+            HarnessResult {
+                harness: &harness,
+                result: mock_verification_result(VerificationStatus::Timeout, Err(137)),
+            },
+        ];
+        assert_eq!(
+            annotations(Path::new("/ws"), &results),
+            "::error file=src/lib.rs,line=10,title=Kani%3A lib%3A%3Acheck::assertion failed: x, y\n\
+            ::warning file=src/lib.rs,line=10,title=Kani%3A lib%3A%3Acheck::cover condition: x > 1 is unreachable\n\
+            ::error file=<unknown>,line=0,title=Kani%3A lib%3A%3Acheck::verification timed out after 1s\n"
+        );
+        assert_eq!(escape_data("50%\nof"), "50%25%0Aof");
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generate a self-contained HTML report for a verification run.
+//!
+//! The report has a table with one row per harness, followed by one section per harness with the
+//! list of properties, the coverage summary (if `--coverage` was used) and the counterexample
+//! trace for each failed property. The page doesn't depend on any external resource, so it can be
+//! shared as a single file.
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, Property, TraceItem};
+use crate::coverage::cov_results::CoverageResults;
+use crate::harness_runner::HarnessResult;
+use crate::report::{escape_markup as escape, failure_location};
+use anyhow::Result;
+use std::fmt::Write;
+use std::path::Path;
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }
+th { background: #f0f0f0; }
+.success { color: #1a7f37; font-weight: bold; }
+.failure { color: #cf222e; font-weight: bold; }
+.other { color: #9a6700; font-weight: bold; }
+details { margin: 0.5em 0; }
+summary { cursor: pointer; }
+pre { background: #f6f8fa; padding: 8px; overflow-x: auto; }
+"#;
+
+/// Serialize the HTML report for the given results into `path`.
+pub fn write_report(path: &Path, results: &[HarnessResult<'_>]) -> Result<()> {
+    std::fs::write(path, html_report(results))?;
+    Ok(())
+}
+
+/// Generate the HTML document for the given results.
+pub fn html_report(results: &[HarnessResult<'_>]) -> String {
+    let root = std::env::current_dir().unwrap_or_default();
+    let succeeding =
+        results.iter().filter(|r| r.result.status == VerificationStatus::Success).count();
+
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n<title>Kani verification report</title>\n");
+    writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>").unwrap();
+    writeln!(html, "<h1>Kani verification report</h1>").unwrap();
+    writeln!(
+        html,
+        "<p>Kani {} &mdash; {succeeding} successfully verified harnesses, {} failures, {} total.</p>",
+        env!("CARGO_PKG_VERSION"),
+        results.len() - succeeding,
+        results.len()
+    )
+    .unwrap();
+
+    html.push_str("<table>\n<tr><th>Harness</th><th>Crate</th><th>Status</th>");
+    html.push_str("<th>Failed checks</th><th>Time (s)</th></tr>\n");
+    for (idx, HarnessResult { harness, result }) in results.iter().enumerate() {
+        let failed = match &result.results {
+            Ok(properties) => format!(
+                "{} of {}",
+                result.failed_properties().len(),
+                properties.iter().filter(|p| !p.is_cover_property()).count()
+            ),
+            Err(_) => "-".to_string(),
+        };
+        writeln!(
+            html,
+            "<tr><td><a href=\"#harness-{idx}\">{}</a></td><td>{}</td><td>{}</td><td>{failed}</td>\
+            <td>{:.3}</td></tr>",
+            escape(&harness.pretty_name),
+            escape(&harness.crate_name),
+            status_cell(result.status),
+            result.runtime.as_secs_f64()
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n");
+
+    for (idx, HarnessResult { harness, result }) in results.iter().enumerate() {
+        writeln!(
+            html,
+            "<h2 id=\"harness-{idx}\">{} {}</h2>",
+            escape(&harness.pretty_name),
+            status_cell(result.status)
+        )
+        .unwrap();
+        writeln!(html, "<p>{}:{}</p>", escape(&harness.original_file), harness.original_start_line)
+            .unwrap();
+        match &result.results {
+            Ok(properties) => {
+                write_properties(&mut html, properties);
+                if let Some(coverage) = &result.coverage_results {
+                    write_coverage(&mut html, coverage);
+                }
+                for property in result.failed_properties() {
+                    write_trace(&mut html, &root, property);
+                }
+            }
+            Err(_) if result.status == VerificationStatus::Timeout => {
+                writeln!(
+                    html,
+                    "<p class=\"other\">Verification timed out after {}s</p>",
+                    result.runtime.as_secs()
+                )
+                .unwrap();
+            }
+            Err(_) if result.status == VerificationStatus::OutOfMemory => {
+                writeln!(html, "<p class=\"other\">Verification exceeded its memory limit</p>")
+                    .unwrap();
+            }
+            Err(exit_status) => {
+                writeln!(html, "<p class=\"failure\">CBMC failed with status {exit_status}</p>")
+                    .unwrap();
+            }
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn status_cell(status: VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Success => "<span class=\"success\">SUCCESSFUL</span>",
+        VerificationStatus::Failure => "<span class=\"failure\">FAILED</span>",
+        VerificationStatus::Timeout => "<span class=\"other\">TIMEOUT</span>",
+        VerificationStatus::OutOfMemory => "<span class=\"other\">OUT-OF-MEMORY</span>",
+    }
+}
+
+fn check_status_class(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::Success | CheckStatus::Satisfied | CheckStatus::Covered => "success",
+        CheckStatus::Failure | CheckStatus::Uncovered => "failure",
+        CheckStatus::Undetermined
+        | CheckStatus::Unknown
+        | CheckStatus::Unreachable
+        | CheckStatus::Unsatisfiable => "other",
+    }
+}
+
+/// Write the list of properties, excluding coverage checks which are summarized separately.
+fn write_properties(html: &mut String, properties: &[Property]) {
+    let properties: Vec<_> = properties.iter().filter(|p| !p.is_code_coverage_property()).collect();
+    writeln!(html, "<details>\n<summary>{} properties</summary>", properties.len()).unwrap();
+    html.push_str("<table>\n<tr><th>Check</th><th>Status</th><th>Description</th>");
+    html.push_str("<th>Location</th></tr>\n");
+    for property in properties {
+        let location = if property.source_location.is_missing() {
+            String::new()
+        } else {
+            property.source_location.to_string()
+        };
+        writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&property.property_name()),
+            check_status_class(property.status),
+            escape(&format!("{:?}", property.status).to_uppercase()),
+            escape(&property.description),
+            escape(&location)
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n</details>\n");
+}
+
+/// Write the number of covered regions per file.
+fn write_coverage(html: &mut String, coverage: &CoverageResults) {
+    html.push_str("<details>\n<summary>Coverage</summary>\n<table>\n");
+    html.push_str("<tr><th>File</th><th>Covered regions</th></tr>\n");
+    for (file, checks) in &coverage.data {
+        let covered = checks.iter().filter(|check| check.is_covered()).count();
+        writeln!(html, "<tr><td>{}</td><td>{covered} of {}</td></tr>", escape(file), checks.len())
+            .unwrap();
+    }
+    html.push_str("</table>\n</details>\n");
+}
+
+/// Write the counterexample for a failed property as the assignments in user code that lead to
+/// the failure.
+fn write_trace(html: &mut String, root: &Path, property: &Property) {
+    writeln!(
+        html,
+        "<details>\n<summary>Counterexample for <code>{}</code>: {}</summary>",
+        escape(&property.property_name()),
+        escape(&property.description)
+    )
+    .unwrap();
+    let steps: Vec<String> = property
+        .trace
+        .iter()
+        .flatten()
+        .filter(|item| is_user_step(root, item))
+        .filter_map(render_step)
+        .collect();
+    if steps.is_empty() {
+        html.push_str("<p>The trace has no assignments in user code.</p>\n");
+    } else {
+        writeln!(html, "<pre>{}</pre>", escape(&steps.join("\n"))).unwrap();
+    }
+    writeln!(html, "<p>Failure location: {}</p>", escape(&failure_location(property).to_string()))
+        .unwrap();
+    html.push_str("</details>\n");
+}
+
+/// Whether the trace step belongs to a file under the directory where Kani was invoked.
+fn is_user_step(root: &Path, item: &TraceItem) -> bool {
+    item.source_location
+        .as_ref()
+        .and_then(|location| location.file.as_ref())
+        .is_some_and(|file| Path::new(file).is_relative() || Path::new(file).starts_with(root))
+}
+
+fn render_step(item: &TraceItem) -> Option<String> {
+    if item.step_type != "assignment" {
+        return None;
+    }
+    let lhs = item.lhs.as_ref()?;
+    let data = item.value.as_ref()?.data.as_ref()?;
+    let location = item.source_location.as_ref()?;
+    Some(format!("{lhs} = {data}    // {location}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation, TraceData, TraceValue};
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+
+    #[test]
+    fn check_html_report() {
+        let location = SourceLocation {
+            column: None,
+            file: Some("src/lib.rs".into()),
+            function: Some("check".into()),
+            line: Some("7".into()),
+        };
+        let trace = vec![TraceItem {
+            step_type: "assignment".into(),
+            lhs: Some("x".into()),
+            source_location: Some(location.clone()),
+            value: Some(TraceValue {
+                binary: None,
+                data: Some(TraceData::NonBool("42".into())),
+                width: None,
+            }),
+        }];
+        let property = Property {
+            description: "assertion failed: x < 10".into(),
+            property_id: PropertyId {
+                fn_name: Some("check".into()),
+                class: "assertion".into(),
+                id: 1,
+            },
+            source_location: location,
+            status: CheckStatus::Failure,
+            reach: None,
+            trace: Some(trace),
+        };
+        let harness = mock_proof_harness("check", None, Some("krate"), None);
+        let result = mock_verification_result(VerificationStatus::Failure, Ok(vec![property]));
+        let html = html_report(&[HarnessResult { harness: &harness, result }]);
+        assert!(html.contains("0 successfully verified harnesses, 1 failures, 1 total."));
+        assert!(html.contains("<a href=\"#harness-0\">check</a>"));
+        assert!(html.contains("<td>1 of 1</td>"));
+        assert!(html.contains("assertion failed: x &lt; 10"));
+        assert!(html.contains("x = 42    // src/lib.rs:7 in function check"));
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generate verification reports in the JUnit XML format.
+//!
+//! Each crate is reported as a test suite, and each harness as a test case. Failed properties are
+//! reported as `<failure>` elements of their harness test case, while harnesses for which CBMC
+//! did not produce any result (including the ones that timed out or ran out of memory) are
+//! reported with an `<error>` element. This is the format understood by CI dashboards such as
+//! Jenkins, GitLab and Buildkite.
+
+use crate::call_cbmc::{FailedProperties, VerificationResult, VerificationStatus};
+use crate::harness_runner::HarnessResult;
+use crate::report::{escape_markup as escape, failure_location};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Serialize the JUnit report for the given results into `path`.
+pub fn write_report(path: &Path, results: &[HarnessResult<'_>]) -> Result<()> {
+    std::fs::write(path, junit_xml(results))?;
+    Ok(())
+}
+
+/// Generate the JUnit XML document for the given results.
+pub fn junit_xml(results: &[HarnessResult<'_>]) -> String {
+    let mut suites: BTreeMap<&str, Vec<&HarnessResult>> = BTreeMap::new();
+    for result in results {
+        suites.entry(result.harness.crate_name.as_str()).or_default().push(result);
+    }
+
+    let total = Counts::from_results(results.iter());
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        xml,
+        "<testsuites name=\"kani\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">",
+        total.tests, total.failures, total.errors, total.time
+    )
+    .unwrap();
+    for (crate_name, harnesses) in suites {
+        let counts = Counts::from_results(harnesses.iter().copied());
+        writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">",
+            escape(crate_name),
+            counts.tests,
+            counts.failures,
+            counts.errors,
+            counts.time
+        )
+        .unwrap();
+        for harness_result in harnesses {
+            write_test_case(&mut xml, harness_result);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn write_test_case(xml: &mut String, harness_result: &HarnessResult<'_>) {
+    let harness = harness_result.harness;
+    let result = &harness_result.result;
+    write!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\" file=\"{}\" line=\"{}\"",
+        escape(&harness.pretty_name),
+        escape(&harness.crate_name),
+        result.runtime.as_secs_f64(),
+        escape(&harness.original_file),
+        harness.original_start_line
+    )
+    .unwrap();
+    if result.status == VerificationStatus::Success {
+        xml.push_str("/>\n");
+        return;
+    }
+    xml.push_str(">\n");
+    match &result.results {
+        Ok(_) => {
+            for property in result.failed_properties() {
+                let location = failure_location(property);
+                writeln!(
+                    xml,
+                    "      <failure message=\"{}\" type=\"{}\">{}\n{}</failure>",
+                    escape(&property.description),
+                    escape(&property.property_class()),
+                    escape(&property.property_name()),
+                    escape(&location.to_string()),
+                )
+                .unwrap();
+            }
+            if harness.attributes.should_panic
+                && matches!(result.failed_properties, FailedProperties::None)
+            {
+                xml.push_str(
+                    "      <failure message=\"harness was expected to panic, but no panic was \
+                    encountered\" type=\"should_panic\"/>\n",
+                );
+            }
+        }
+        Err(_) if result.status == VerificationStatus::Timeout => {
+            writeln!(
+                xml,
+                "      <error message=\"verification timed out after {}s\" type=\"timeout\"/>",
+                result.runtime.as_secs()
+            )
+            .unwrap();
+        }
+        Err(_) if result.status == VerificationStatus::OutOfMemory => {
+            writeln!(
+                xml,
+                "      <error message=\"verification exceeded its memory limit\" type=\"out-of-memory\"/>"
+            )
+            .unwrap();
+        }
+        Err(exit_status) => {
+            writeln!(
+                xml,
+                "      <error message=\"CBMC failed with status {exit_status}\" type=\"verification_error\"/>"
+            )
+            .unwrap();
+        }
+    }
+    xml.push_str("    </testcase>\n");
+}
+
+/// The aggregated counters of a test suite.
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    time: f64,
+}
+
+impl Counts {
+    fn from_results<'a, 'pr: 'a>(results: impl Iterator<Item = &'a HarnessResult<'pr>>) -> Counts {
+        let mut counts = Counts { tests: 0, failures: 0, errors: 0, time: 0.0 };
+        for HarnessResult { result, .. } in results {
+            counts.tests += 1;
+            counts.time += result.runtime.as_secs_f64();
+            match result {
+                VerificationResult { status: VerificationStatus::Success, .. } => {}
+                VerificationResult { results: Err(_), .. } => counts.errors += 1,
+                VerificationResult { results: Ok(_), .. } => counts.failures += 1,
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{CheckStatus, Property, PropertyId, SourceLocation};
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+
+    fn failed_property(description: &str) -> Property {
+        Property {
+            description: description.to_string(),
+            property_id: PropertyId {
+                fn_name: Some("check".into()),
+                class: "assertion".into(),
+                id: 1,
+            },
+            source_location: SourceLocation {
+                column: None,
+                file: Some("src/lib.rs".into()),
+                function: None,
+                line: Some("3".into()),
+            },
+            status: CheckStatus::Failure,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn check_harnesses_are_test_cases() {
+        let ok = mock_proof_harness("ok", None, Some("krate"), None);
+        let failing = mock_proof_harness("failing", None, Some("krate"), None);
+        let crashed = mock_proof_harness("crashed", None, Some("other"), None);
+        let results = [
+            HarnessResult {
+                harness: &ok,
+                result: mock_verification_result(VerificationStatus::Success, Ok(vec![])),
+            },
+            HarnessResult {
+                harness: &failing,
+                result: mock_verification_result(
+                    VerificationStatus::Failure,
+                    Ok(vec![failed_property("assertion failed: a < b")]),
+                ),
+            },
+            HarnessResult {
+                harness: &crashed,
+                result: mock_verification_result(VerificationStatus::Failure, Err(137)),
+            },
+        ];
+        let xml = junit_xml(&results);
+        assert!(xml.contains(
+            "<testsuites name=\"kani\" tests=\"3\" failures=\"1\" errors=\"1\" time=\"3.000\">"
+        ));
+        assert!(xml.contains("<testsuite name=\"krate\" tests=\"2\" failures=\"1\" errors=\"0\""));
+        assert!(xml.contains("<testcase name=\"ok\" classname=\"krate\" time=\"1.000\""));
+        assert!(xml.contains(
+            "<failure message=\"assertion failed: a &lt; b\" type=\"assertion\">check.assertion.1"
+        ));
+        assert!(xml.contains("<error message=\"CBMC failed with status 137\""));
+    }
+}
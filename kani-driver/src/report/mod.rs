@@ -0,0 +1,175 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Module that generates machine-readable reports from the verification results.
+//!
+//! Reports are generated once all harnesses have been checked, and they are written to the
+//! locations requested by the user.
+
+use crate::args::OutputFormat;
+use crate::cbmc_output_parser::{Property, SourceLocation};
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::reachability;
+use crate::session::KaniSession;
+use crate::stats::stats_entries;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+
+pub mod baseline;
+pub mod github;
+pub mod html;
+pub mod junit;
+pub mod sarif;
+pub mod unsafe_audit;
+pub mod unsupported;
+
+/// The name of the JUnit report generated with `--output-format junit`.
+const JUNIT_REPORT: &str = "kani-junit.xml";
+/// The name of the file with the unwind bounds found by `--infer-unwind`.
+const UNWIND_SUGGESTIONS: &str = "kani-unwind-suggestions.json";
+/// The name of the file with the resource statistics of each harness, written with `--stats`.
+const STATS: &str = "kani-stats.json";
+/// The name of the file with the functions reachable from each harness, written with
+/// `--reachability-report`.
+const REACHABILITY: &str = "kani-reachability.json";
+/// The name of the file with the unsafe operations reachable from each harness, written with
+/// `--unsafe-report`.
+const UNSAFE_OPERATIONS: &str = "kani-unsafe-operations.json";
+
+impl KaniSession {
+    /// Write all the reports requested by the user for the given results.
+    pub(crate) fn write_reports(
+        &self,
+        project: &Project,
+        results: &[HarnessResult<'_>],
+    ) -> Result<()> {
+        if self.args.output_format == OutputFormat::Junit {
+            let path = project.outdir.join(JUNIT_REPORT);
+            junit::write_report(&path, results)
+                .with_context(|| format!("Failed to write JUnit report to {}", path.display()))?;
+            if !self.args.common_args.quiet {
+                println!("[info] JUnit report written to {}", path.display());
+            }
+        }
+        if self.args.output_format == OutputFormat::Github {
+            github::print_annotations(results);
+        }
+        if self.args.infer_unwind {
+            let suggestions: BTreeMap<_, _> = results
+                .iter()
+                .filter_map(|r| Some((r.harness.pretty_name.as_str(), r.result.inferred_unwind?)))
+                .collect();
+            if !suggestions.is_empty() {
+                let path = project.outdir.join(UNWIND_SUGGESTIONS);
+                std::fs::write(&path, serde_json::to_string_pretty(&suggestions)?)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                if !self.args.common_args.quiet {
+                    println!("[info] Unwind suggestions written to {}", path.display());
+                }
+            }
+        }
+        if self.args.stats {
+            let path = project.outdir.join(STATS);
+            std::fs::write(&path, serde_json::to_string_pretty(&stats_entries(results))?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            if !self.args.common_args.quiet {
+                println!("[info] Harness statistics written to {}", path.display());
+            }
+        }
+        if self.args.reachability_report {
+            let path = project.outdir.join(REACHABILITY);
+            let harnesses: Vec<_> = results.iter().map(|r| r.harness).collect();
+            reachability::write_report(&harnesses, &path)?;
+            if !self.args.common_args.quiet {
+                println!("[info] Reachability report written to {}", path.display());
+            }
+        }
+        if self.args.unsafe_report {
+            let path = project.outdir.join(UNSAFE_OPERATIONS);
+            let summary = self.write_unsafe_report(results, &path)?;
+            if !self.args.common_args.quiet {
+                println!("[info] Unsafe operations report written to {}", path.display());
+                print!("{summary}");
+            }
+        }
+        if let Some(path) = &self.args.sarif {
+            sarif::write_report(path, results)
+                .with_context(|| format!("Failed to write SARIF report to {}", path.display()))?;
+            if !self.args.common_args.quiet {
+                println!("[info] SARIF report written to {}", path.display());
+            }
+        }
+        if let Some(path) = &self.args.save_baseline {
+            baseline::write_report(path, results)
+                .with_context(|| format!("Failed to write baseline to {}", path.display()))?;
+            if !self.args.common_args.quiet {
+                println!("[info] Baseline written to {}", path.display());
+            }
+        }
+        if let Some(path) = &self.args.html_report {
+            html::write_report(path, results)
+                .with_context(|| format!("Failed to write HTML report to {}", path.display()))?;
+            if !self.args.common_args.quiet {
+                println!("[info] HTML report written to {}", path.display());
+            }
+        }
+        let paths = unsupported::write_reports(project)?;
+        if !self.args.common_args.quiet {
+            for path in paths {
+                println!("[info] Unsupported constructs report written to {}", path.display());
+            }
+            if let Some(summary) = unsupported::format_summary(project) {
+                print!("\n{summary}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return the location where the given property failed.
+///
+/// The last step of the trace (if any) is the most precise location we have, since the property
+/// location may point to a function that was inlined or to the definition of a macro.
+pub fn failure_location(property: &Property) -> &SourceLocation {
+    property
+        .trace
+        .as_ref()
+        .and_then(|trace| {
+            trace
+                .iter()
+                .rev()
+                .filter_map(|item| item.source_location.as_ref())
+                .find(|location| location.file.is_some() && location.line.is_some())
+        })
+        .unwrap_or(&property.source_location)
+}
+
+/// Escape the characters that cannot appear verbatim in XML or HTML attributes and text.
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_escape_markup() {
+        assert_eq!(
+            escape_markup("a<b && \"c\" > 'd'"),
+            "a&lt;b &amp;&amp; &quot;c&quot; &gt; &apos;d&apos;"
+        );
+    }
+}
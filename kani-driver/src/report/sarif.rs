@@ -0,0 +1,533 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generate verification reports in the [SARIF 2.1.0] format.
+//!
+//! Each property class (e.g. `assertion`, `pointer_dereference`) is reported as a rule, and each
+//! failed property is reported as a result of that rule. When CBMC produced a trace for the
+//! failure, the steps in user code are included as a code flow, which lets SARIF viewers
+//! (e.g. GitHub code scanning) step through the counterexample.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use crate::call_cbmc::{FailedProperties, VerificationStatus};
+use crate::cbmc_output_parser::{Property, SourceLocation, TraceItem};
+use crate::harness_runner::HarnessResult;
+use crate::report::failure_location;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const KANI_URI: &str = "https://github.com/model-checking/kani";
+/// The base identifier used for files inside the directory where Kani was invoked.
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Rule used when CBMC did not produce any result for a harness.
+const VERIFICATION_ERROR_RULE: &str = "verification_error";
+/// Rule used when a `#[kani::should_panic]` harness did not panic.
+const SHOULD_PANIC_RULE: &str = "should_panic";
+/// Rule used when the verification of a harness was stopped by its timeout.
+const TIMEOUT_RULE: &str = "timeout";
+/// Rule used when the verification of a harness was stopped by its memory limit.
+const OUT_OF_MEMORY_RULE: &str = "out_of_memory";
+
+/// Serialize the SARIF report for the given results into `path`.
+pub fn write_report(path: &Path, results: &[HarnessResult<'_>]) -> Result<()> {
+    let log = SarifLog::new(results);
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(writer, &log)?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    code_flows: Vec<CodeFlow>,
+    properties: ResultProperties,
+}
+
+/// Kani specific information attached to each result.
+#[derive(Debug, Serialize)]
+struct ResultProperties {
+    harness: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeFlow {
+    thread_flows: Vec<ThreadFlow>,
+}
+
+#[derive(Debug, Serialize)]
+struct ThreadFlow {
+    locations: Vec<ThreadFlowLocation>,
+}
+
+#[derive(Debug, Serialize)]
+struct ThreadFlowLocation {
+    location: Location,
+}
+
+impl SarifLog {
+    /// Build a SARIF log with one run that includes the failures of all harnesses.
+    pub fn new(results: &[HarnessResult<'_>]) -> SarifLog {
+        let root = std::env::current_dir().unwrap_or_default();
+        let mut rules = RuleSet::default();
+        let mut sarif_results = vec![];
+
+        for harness_result in results {
+            let harness = harness_result.harness;
+            let result = &harness_result.result;
+            if result.status == VerificationStatus::Success {
+                continue;
+            }
+            let harness_location = Location::from_parts(
+                &root,
+                &harness.original_file,
+                Some(harness.original_start_line as u64),
+                None,
+            );
+            match &result.results {
+                Ok(_) => {
+                    for property in result.failed_properties() {
+                        sarif_results.push(SarifResult::from_property(
+                            &root,
+                            &mut rules,
+                            &harness.pretty_name,
+                            property,
+                        ));
+                    }
+                    if harness.attributes.should_panic
+                        && matches!(result.failed_properties, FailedProperties::None)
+                    {
+                        sarif_results.push(SarifResult::for_harness(
+                            &mut rules,
+                            SHOULD_PANIC_RULE,
+                            &harness.pretty_name,
+                            "harness was expected to panic, but no panic was encountered"
+                                .to_string(),
+                            harness_location,
+                        ));
+                    }
+                }
+                Err(_) if result.status == VerificationStatus::Timeout => {
+                    sarif_results.push(SarifResult::for_harness(
+                        &mut rules,
+                        TIMEOUT_RULE,
+                        &harness.pretty_name,
+                        format!("verification timed out after {}s", result.runtime.as_secs()),
+                        harness_location,
+                    ))
+                }
+                Err(_) if result.status == VerificationStatus::OutOfMemory => {
+                    sarif_results.push(SarifResult::for_harness(
+                        &mut rules,
+                        OUT_OF_MEMORY_RULE,
+                        &harness.pretty_name,
+                        "verification exceeded its memory limit".to_string(),
+                        harness_location,
+                    ))
+                }
+                Err(exit_status) => sarif_results.push(SarifResult::for_harness(
+                    &mut rules,
+                    VERIFICATION_ERROR_RULE,
+                    &harness.pretty_name,
+                    format!("CBMC failed with status {exit_status}"),
+                    harness_location,
+                )),
+            }
+        }
+
+        SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "Kani",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: KANI_URI,
+                        rules: rules.into_rules(),
+                    },
+                },
+                results: sarif_results,
+            }],
+        }
+    }
+}
+
+impl SarifResult {
+    fn from_property(
+        root: &Path,
+        rules: &mut RuleSet,
+        harness: &str,
+        property: &Property,
+    ) -> SarifResult {
+        let class = property.property_class();
+        let location = Location::from_source(root, failure_location(property));
+        let code_flows = property
+            .trace
+            .as_ref()
+            .and_then(|trace| CodeFlow::from_trace(root, trace))
+            .into_iter()
+            .collect();
+        SarifResult {
+            rule_index: rules.index_of(&class),
+            rule_id: class,
+            level: "error",
+            message: Message { text: property.description.clone() },
+            locations: location.into_iter().collect(),
+            code_flows,
+            properties: ResultProperties { harness: harness.to_string() },
+        }
+    }
+
+    fn for_harness(
+        rules: &mut RuleSet,
+        rule: &str,
+        harness: &str,
+        message: String,
+        location: Option<Location>,
+    ) -> SarifResult {
+        SarifResult {
+            rule_index: rules.index_of(rule),
+            rule_id: rule.to_string(),
+            level: "error",
+            message: Message { text: message },
+            locations: location.into_iter().collect(),
+            code_flows: vec![],
+            properties: ResultProperties { harness: harness.to_string() },
+        }
+    }
+}
+
+impl Location {
+    fn from_source(root: &Path, source: &SourceLocation) -> Option<Location> {
+        let line = source.line.as_ref().and_then(|line| line.parse().ok());
+        let column = source.column.as_ref().and_then(|column| column.parse().ok());
+        Location::from_parts(root, source.file.as_ref()?, line, column)
+    }
+
+    fn from_parts(
+        root: &Path,
+        file: &str,
+        line: Option<u64>,
+        column: Option<u64>,
+    ) -> Option<Location> {
+        if file.is_empty() {
+            return None;
+        }
+        Some(Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation::new(root, file),
+                region: line.map(|start_line| Region { start_line, start_column: column }),
+            },
+            message: None,
+        })
+    }
+}
+
+impl ArtifactLocation {
+    /// Files inside `root` are reported relative to it, so the report can be consumed from a
+    /// different checkout. Other files (e.g. from the standard library) use absolute URIs.
+    fn new(root: &Path, file: &str) -> ArtifactLocation {
+        let path = PathBuf::from(file);
+        match path.strip_prefix(root) {
+            Ok(relative) => ArtifactLocation {
+                uri: relative.to_string_lossy().to_string(),
+                uri_base_id: Some(SRCROOT),
+            },
+            Err(_) if path.is_absolute() => {
+                ArtifactLocation { uri: format!("file://{}", path.display()), uri_base_id: None }
+            }
+            Err(_) => ArtifactLocation { uri: file.to_string(), uri_base_id: Some(SRCROOT) },
+        }
+    }
+
+    fn is_user_code(&self) -> bool {
+        self.uri_base_id.is_some()
+    }
+}
+
+impl CodeFlow {
+    /// Build a code flow with the trace steps that are part of the user code.
+    /// Traces are usually dominated by steps in the standard library, which are not useful to
+    /// navigate a counterexample in an IDE.
+    fn from_trace(root: &Path, trace: &[TraceItem]) -> Option<CodeFlow> {
+        let mut locations: Vec<ThreadFlowLocation> = vec![];
+        for item in trace {
+            let Some(mut location) = item
+                .source_location
+                .as_ref()
+                .and_then(|source| Location::from_source(root, source))
+            else {
+                continue;
+            };
+            if !location.physical_location.artifact_location.is_user_code() {
+                continue;
+            }
+            location.message = step_message(item);
+            // Collapse consecutive steps in the same line without any extra information.
+            if location.message.is_none()
+                && let Some(last) = locations.last()
+                && last.location.physical_location == location.physical_location
+            {
+                continue;
+            }
+            locations.push(ThreadFlowLocation { location });
+        }
+        (!locations.is_empty()).then(|| CodeFlow { thread_flows: vec![ThreadFlow { locations }] })
+    }
+}
+
+/// Describe assignments to user variables. Other steps do not carry a message.
+fn step_message(item: &TraceItem) -> Option<Message> {
+    if item.step_type != "assignment" {
+        return None;
+    }
+    let lhs = item.lhs.as_ref()?;
+    let data = item.value.as_ref()?.data.as_ref()?;
+    Some(Message { text: format!("{lhs} = {data}") })
+}
+
+impl PartialEq for PhysicalLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.artifact_location.uri == other.artifact_location.uri
+            && self.region.as_ref().map(|r| r.start_line)
+                == other.region.as_ref().map(|r| r.start_line)
+    }
+}
+
+/// The rules referenced by the results of a run, in the order they were first used.
+#[derive(Default)]
+struct RuleSet {
+    indices: BTreeMap<String, usize>,
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    fn index_of(&mut self, id: &str) -> usize {
+        if let Some(index) = self.indices.get(id) {
+            return *index;
+        }
+        let index = self.rules.len();
+        self.rules.push(Rule {
+            id: id.to_string(),
+            short_description: Message { text: rule_description(id) },
+        });
+        self.indices.insert(id.to_string(), index);
+        index
+    }
+
+    fn into_rules(self) -> Vec<Rule> {
+        self.rules
+    }
+}
+
+/// A short description of each property class that Kani reports.
+fn rule_description(class: &str) -> String {
+    let description = match class {
+        "assertion" => "Assertion or panic",
+        "cover" => "Cover property",
+        "overflow" => "Arithmetic overflow",
+        "division-by-zero" => "Division by zero",
+        "undefined-shift" => "Undefined shift",
+        "float_overflow" | "NaN" => "Floating-point overflow or NaN",
+        "array_bounds" => "Index out of bounds",
+        "pointer_dereference" => "Invalid pointer dereference",
+        "pointer_arithmetic" => "Invalid pointer arithmetic",
+        "pointer_primitives" => "Invalid pointer primitive",
+        "unwind" | "recursion" => "Unwinding assertion",
+        "unsupported_construct" => "Unsupported construct",
+        "missing_definition" => "Function with missing definition",
+        "safety_check" => "Undefined behavior",
+        "constant_time" => "Secret-dependent branch or memory index",
+        "memory-leak" => "Memory leak",
+        VERIFICATION_ERROR_RULE => "Verification error",
+        SHOULD_PANIC_RULE => "Expected panic",
+        TIMEOUT_RULE => "Verification timeout",
+        OUT_OF_MEMORY_RULE => "Verification out of memory",
+        _ => return format!("Kani `{class}` check"),
+    };
+    description.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::cbmc_output_parser::{CheckStatus, PropertyId};
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+
+    fn location(file: &str, line: &str) -> SourceLocation {
+        SourceLocation {
+            column: Some("5".into()),
+            file: Some(file.into()),
+            function: Some("check".into()),
+            line: Some(line.into()),
+        }
+    }
+
+    fn property(class: &str, status: CheckStatus, trace: Option<Vec<TraceItem>>) -> Property {
+        Property {
+            description: format!("{class} failed"),
+            property_id: PropertyId { fn_name: Some("check".into()), class: class.into(), id: 1 },
+            source_location: location("/root/src/lib.rs", "10"),
+            status,
+            reach: None,
+            trace,
+        }
+    }
+
+    fn failure(properties: Vec<Property>) -> VerificationResult {
+        mock_verification_result(VerificationStatus::Failure, Ok(properties))
+    }
+
+    #[test]
+    fn check_failures_are_reported_per_class() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let result = failure(vec![
+            property("assertion", CheckStatus::Failure, None),
+            property("overflow", CheckStatus::Failure, None),
+            property("assertion", CheckStatus::Success, None),
+        ]);
+        let log = SarifLog::new(&[HarnessResult { harness: &harness, result }]);
+        let json = serde_json::to_value(&log).unwrap();
+
+        assert_eq!(json["version"], "2.1.0");
+        let run = &json["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "assertion");
+        assert_eq!(rules[1]["id"], "overflow");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1]["ruleId"], "overflow");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(results[1]["properties"]["harness"], "check");
+        let region = &results[0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 10);
+        assert_eq!(region["startColumn"], 5);
+    }
+
+    #[test]
+    fn check_location_comes_from_trace() {
+        let root = std::env::current_dir().unwrap();
+        let user_file = root.join("src/main.rs").to_string_lossy().to_string();
+        let step = |line: &str| TraceItem {
+            step_type: "location-only".into(),
+            lhs: None,
+            source_location: Some(location(&user_file, line)),
+            value: None,
+        };
+        let trace = vec![step("3"), step("3"), step("4")];
+        let harness = mock_proof_harness("check", None, None, None);
+        let result = failure(vec![property("assertion", CheckStatus::Failure, Some(trace))]);
+        let log = SarifLog::new(&[HarnessResult { harness: &harness, result }]);
+        let json = serde_json::to_value(&log).unwrap();
+
+        let result = &json["runs"][0]["results"][0];
+        let physical = &result["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(physical["artifactLocation"]["uriBaseId"], SRCROOT);
+        assert_eq!(physical["region"]["startLine"], 4);
+        let flow = result["codeFlows"][0]["threadFlows"][0]["locations"].as_array().unwrap();
+        assert_eq!(flow.len(), 2);
+    }
+
+    #[test]
+    fn check_successful_harness_is_not_reported() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let mut result = failure(vec![]);
+        result.status = VerificationStatus::Success;
+        let log = SarifLog::new(&[HarnessResult { harness: &harness, result }]);
+        assert!(log.runs[0].results.is_empty());
+        assert!(log.runs[0].tool.driver.rules.is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Report of the unsafe operations reachable from each harness, written with `--unsafe-report`.
+//!
+//! The operations are listed by `kani-compiler` for each model. We add whether Kani checks each
+//! operation with the options the harness was verified with.
+
+use crate::args::EnabledChecks;
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use anyhow::{Context, Result};
+use kani_metadata::{
+    artifact::convert_type, ArtifactType, UnsafeOperation, UnsafeOperationKind, UnstableFeature,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// An unsafe operation with the check that Kani applies to it, if any.
+#[derive(Serialize)]
+struct AuditedOperation<'a> {
+    #[serde(flatten)]
+    operation: &'a UnsafeOperation,
+    check: Option<&'static str>,
+}
+
+impl KaniSession {
+    /// Write the unsafe operations reachable from each harness to `path`, and return a summary
+    /// of the operations that are not checked.
+    pub(crate) fn write_unsafe_report(
+        &self,
+        results: &[HarnessResult<'_>],
+        path: &Path,
+    ) -> Result<String> {
+        let mut reports = BTreeMap::new();
+        for result in results {
+            let harness = result.harness;
+            let goto_file = harness.goto_file.as_ref().expect("Expected a model file");
+            let report_path =
+                convert_type(goto_file, ArtifactType::SymTabGoto, ArtifactType::UnsafeOperations);
+            let report = std::fs::read_to_string(&report_path)
+                .with_context(|| format!("Failed to read {}", report_path.display()))?;
+            let mut report: BTreeMap<String, Vec<UnsafeOperation>> = serde_json::from_str(&report)
+                .with_context(|| format!("Failed to parse {}", report_path.display()))?;
+            let operations = report.remove(&harness.pretty_name).unwrap_or_default();
+            let checks = self.args.checks.for_harness(&harness.attributes.checks);
+            reports.insert(harness.pretty_name.as_str(), (operations, checks));
+        }
+
+        let audited: BTreeMap<&str, Vec<AuditedOperation>> = reports
+            .iter()
+            .map(|(harness, (operations, checks))| {
+                let operations = operations
+                    .iter()
+                    .map(|operation| AuditedOperation {
+                        operation,
+                        check: self.unsafe_operation_check(operation.kind, *checks),
+                    })
+                    .collect();
+                (*harness, operations)
+            })
+            .collect();
+        std::fs::write(path, serde_json::to_string_pretty(&audited)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        let mut summary = String::new();
+        for (harness, operations) in &audited {
+            let unchecked = operations.iter().filter(|op| op.check.is_none()).count();
+            if !operations.is_empty() {
+                writeln!(
+                    summary,
+                    "{harness}: {} unsafe operations reachable, {unchecked} without a check",
+                    operations.len()
+                )
+                .unwrap();
+            }
+        }
+        Ok(summary)
+    }
+
+    /// The check that Kani applies to an unsafe operation of the given kind, if any.
+    fn unsafe_operation_check(
+        &self,
+        kind: UnsafeOperationKind,
+        checks: EnabledChecks,
+    ) -> Option<&'static str> {
+        let unstable = &self.args.common_args.unstable_features;
+        match kind {
+            UnsafeOperationKind::RawPointerDeref => {
+                checks.memory_safety.then_some("memory safety checks")
+            }
+            UnsafeOperationKind::UnionAccess => {
+                unstable.contains(UnstableFeature::UnionChecks).then_some("-Z union-checks")
+            }
+            UnsafeOperationKind::Transmute => unstable
+                .contains(UnstableFeature::ValidValueChecks)
+                .then_some("-Z valid-value-checks"),
+            // The safety conditions of a call are only checked by the operations of the callee.
+            UnsafeOperationKind::UnsafeCall => None,
+        }
+    }
+}
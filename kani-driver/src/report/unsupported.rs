@@ -0,0 +1,128 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Report of the constructs that Kani doesn't support.
+//!
+//! Codegen replaces each unsupported construct by an assertion that fails if it is reachable, and
+//! records it in the crate metadata together with the harnesses whose model contains it. We write
+//! one JSON report per crate with those constructs, and summarize them at the end of the run.
+//! With `--unknown-intrinsics havoc`, the unsupported intrinsics are havocked instead, and they are
+//! reported as `'<name>' intrinsic (havocked)`.
+
+use crate::project::Project;
+use anyhow::{Context, Result};
+use kani_metadata::{Location, UnsupportedFeature};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// An entry of the report, which describes one kind of unsupported construct.
+#[derive(Serialize)]
+struct ReportEntry<'a> {
+    construct: &'a str,
+    locations: Vec<&'a Location>,
+    harnesses: &'a BTreeSet<String>,
+    workaround: &'static str,
+}
+
+/// The path of the report of the given crate.
+fn report_path(project: &Project, crate_name: &str) -> PathBuf {
+    project.outdir.join(format!("kani-unsupported-{crate_name}.json"))
+}
+
+/// Write the report of each crate with unsupported constructs, and return their paths.
+pub fn write_reports(project: &Project) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for metadata in project.metadata.iter().filter(|md| !md.unsupported_features.is_empty()) {
+        let entries: Vec<ReportEntry> =
+            sorted_features(&metadata.unsupported_features).into_iter().map(entry).collect();
+        let path = report_path(project, &metadata.crate_name);
+        std::fs::write(&path, serde_json::to_string_pretty(&entries)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Summarize the unsupported constructs of every crate, or return `None` if there are none.
+pub fn format_summary(project: &Project) -> Option<String> {
+    let features: Vec<&UnsupportedFeature> =
+        project.metadata.iter().flat_map(|md| md.unsupported_features.iter()).collect();
+    if features.is_empty() {
+        return None;
+    }
+    let mut summary = String::from("Unsupported constructs:\n");
+    for feature in sorted_features(features.iter().copied()) {
+        let locations = feature.locations.len();
+        let plural = if locations == 1 { "" } else { "s" };
+        write!(summary, "    - {} ({locations} location{plural})", feature.feature).unwrap();
+        if feature.harnesses.is_empty() {
+            summary.push_str(", not reachable from any harness\n");
+        } else {
+            let harnesses: Vec<&str> = feature.harnesses.iter().map(String::as_str).collect();
+            writeln!(summary, ", reachable from: {}", harnesses.join(", ")).unwrap();
+        }
+        writeln!(summary, "      {}", workaround(&feature.feature)).unwrap();
+    }
+    summary.push_str("Verification will fail if one or more of these constructs is reachable.\n");
+    if features.iter().any(|feature| is_havocked(&feature.feature)) {
+        summary.push_str(
+            "The havocked intrinsics don't fail verification: their result is nondeterministic and \
+            their other effects are ignored, which may cause false proofs.\n",
+        );
+    }
+    Some(summary)
+}
+
+fn sorted_features<'a>(
+    features: impl IntoIterator<Item = &'a UnsupportedFeature>,
+) -> Vec<&'a UnsupportedFeature> {
+    let mut features: Vec<_> = features.into_iter().collect();
+    features.sort_by(|a, b| a.feature.cmp(&b.feature));
+    features
+}
+
+fn entry(feature: &UnsupportedFeature) -> ReportEntry<'_> {
+    let mut locations: Vec<&Location> = feature.locations.iter().collect();
+    locations.sort_by(|a, b| (&a.filename, a.start_line).cmp(&(&b.filename, b.start_line)));
+    ReportEntry {
+        construct: &feature.feature,
+        locations,
+        harnesses: &feature.harnesses,
+        workaround: workaround(&feature.feature),
+    }
+}
+
+/// Suggest how to verify the harnesses that reach the given construct.
+fn workaround(construct: &str) -> &'static str {
+    if construct == "TerminatorKind::InlineAsm" {
+        "Model the `asm!` block with `#[kani::asm_model]`, or stub the function that contains it \
+        with `#[kani::stub]`."
+    } else if is_havocked(construct) {
+        "Model the intrinsic with `#[kani::builtin_model]` to verify its effects, or stub the \
+        function that calls it with `#[kani::stub]`."
+    } else if construct.ends_with(" intrinsic") {
+        "Stub the function that calls the intrinsic with `#[kani::stub]`."
+    } else {
+        "Stub the function that uses it with `#[kani::stub]`."
+    }
+}
+
+/// Whether the construct is an intrinsic handled by `--unknown-intrinsics havoc`.
+fn is_havocked(construct: &str) -> bool {
+    construct.ends_with(" intrinsic (havocked)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_workaround() {
+        assert!(workaround("TerminatorKind::InlineAsm").contains("kani::asm_model"));
+        assert!(workaround("'simd_foo' intrinsic").contains("intrinsic"));
+        assert!(workaround("CastKind::DynStar").contains("kani::stub"));
+        assert!(workaround("'foo' intrinsic (havocked)").contains("kani::builtin_model"));
+    }
+}
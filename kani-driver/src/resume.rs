@@ -0,0 +1,206 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Persist the harnesses completed by a verification run, so that `--resume` can skip them after
+//! the run was interrupted, e.g., by a crash, Ctrl-C, or the preemption of a CI machine.
+//!
+//! The journal is a JSON-lines file in the output directory, with one entry appended as soon as
+//! each harness completes. Appending one line at a time means an interrupted run loses at most
+//! the entry it was writing, which is ignored when the journal is read back.
+//! The journal is removed once every harness of a run was verified.
+//!
+//! A harness is only skipped if its goto model and the arguments CBMC verifies it with are the
+//! same as in the interrupted run, so harnesses affected by a change since then are verified again.
+
+use crate::call_cbmc::VerificationResult;
+use crate::project::Project;
+use crate::report::baseline::BaselineStatus;
+use crate::session::KaniSession;
+use crate::watch;
+use anyhow::{Context, Result};
+use kani_metadata::{ArtifactType, HarnessMetadata};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The name of the journal in the output directory of the project.
+const JOURNAL_FILE: &str = "kani-session.jsonl";
+
+/// A harness completed by a previous, interrupted run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompletedHarness {
+    pub harness: String,
+    pub crate_name: String,
+    /// The fingerprints of the verification, from [verification_fingerprint], before and after
+    /// the goto model was instrumented in place.
+    pub fingerprints: Vec<u64>,
+    pub status: BaselineStatus,
+    /// Whether the result was a failure according to the `--fail-on` categories of that run.
+    pub failed: bool,
+    /// The verification time in seconds.
+    pub runtime: f64,
+}
+
+/// The journal of the current run.
+pub struct SessionJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+    /// The harnesses completed by the interrupted run, if resuming, by crate and harness name.
+    completed: HashMap<(String, String), CompletedHarness>,
+}
+
+impl SessionJournal {
+    /// Open the journal in `outdir`. When `resume` is set, the harnesses recorded by the previous
+    /// run are kept. Otherwise, the journal starts empty.
+    pub fn open(outdir: &Path, resume: bool) -> Result<SessionJournal> {
+        let path = outdir.join(JOURNAL_FILE);
+        let completed = if resume && path.exists() { read_journal(&path)? } else { vec![] };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open `{}`", path.display()))?;
+        // Rewrite the valid entries, so new entries don't follow a partially written line.
+        file.set_len(0)?;
+        for entry in &completed {
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        }
+        // A harness verified again after an entry was written has a later entry, which prevails.
+        let completed = completed
+            .into_iter()
+            .map(|entry| ((entry.crate_name.clone(), entry.harness.clone()), entry))
+            .collect();
+        Ok(SessionJournal { path, file: Mutex::new(file), completed })
+    }
+
+    /// The result of the given harness in the interrupted run, if it was completed and the
+    /// `fingerprint` of its verification didn't change since.
+    pub fn completed(
+        &self,
+        harness: &HarnessMetadata,
+        fingerprint: u64,
+    ) -> Option<&CompletedHarness> {
+        self.completed
+            .get(&(harness.crate_name.clone(), harness.pretty_name.clone()))
+            .filter(|entry| entry.fingerprints.contains(&fingerprint))
+    }
+
+    /// Append the result of a harness to the journal.
+    pub fn record(
+        &self,
+        harness: &HarnessMetadata,
+        fingerprints: Vec<u64>,
+        result: &VerificationResult,
+        failed: bool,
+    ) -> Result<()> {
+        let entry = CompletedHarness {
+            harness: harness.pretty_name.clone(),
+            crate_name: harness.crate_name.clone(),
+            fingerprints,
+            status: result.status.into(),
+            failed,
+            runtime: result.runtime.as_secs_f64(),
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        // Write the entry at once, so entries of harnesses completing concurrently don't mix.
+        let mut file = self.file.lock().unwrap();
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Remove the journal, once all harnesses of the run were verified.
+    pub fn finish(self) -> Result<()> {
+        drop(self.file);
+        std::fs::remove_file(&self.path)
+            .with_context(|| format!("Failed to remove `{}`", self.path.display()))
+    }
+}
+
+/// The fingerprint of the verification of a harness, from its goto model and the arguments CBMC
+/// verifies it with.
+pub fn verification_fingerprint(
+    session: &KaniSession,
+    project: &Project,
+    harness: &HarnessMetadata,
+) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    watch::fingerprint(project, harness)?.hash(&mut hasher);
+    let model = project
+        .get_harness_artifact(harness, ArtifactType::Goto)
+        .with_context(|| format!("Failed to find the goto model of `{}`", harness.pretty_name))?;
+    session.cbmc_flags(&session.harness_binary(model, harness), harness)?.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+/// Read the entries of a journal. Malformed lines, e.g., the last line of a run that was killed
+/// while writing it, are ignored.
+fn read_journal(path: &Path) -> Result<Vec<CompletedHarness>> {
+    let file = File::open(path).with_context(|| format!("Failed to open `{}`", path.display()))?;
+    let mut entries = vec![];
+    for line in BufReader::new(file).lines() {
+        if let Ok(entry) = serde_json::from_str::<CompletedHarness>(&line?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+
+    fn harness(name: &str) -> HarnessMetadata {
+        mock_proof_harness(name, None, Some("krate"), None)
+    }
+
+    #[test]
+    fn check_resume() {
+        let outdir = tempfile::tempdir().unwrap();
+        let journal = SessionJournal::open(outdir.path(), false).unwrap();
+        journal
+            .record(&harness("check_a"), vec![1, 2], &VerificationResult::mock_success(), false)
+            .unwrap();
+        drop(journal);
+        // Simulate a run killed while writing an entry.
+        let mut file =
+            OpenOptions::new().append(true).open(outdir.path().join(JOURNAL_FILE)).unwrap();
+        file.write_all(b"{\"harness\":\"check_b\",\"sta").unwrap();
+
+        let journal = SessionJournal::open(outdir.path(), true).unwrap();
+        journal
+            .record(&harness("check_c"), vec![1], &VerificationResult::mock_success(), false)
+            .unwrap();
+        drop(journal);
+        let journal = SessionJournal::open(outdir.path(), true).unwrap();
+        assert!(journal.completed(&harness("check_c"), 1).is_some());
+        // The model of `check_a` is the same, either as built or as instrumented.
+        let completed = journal.completed(&harness("check_a"), 2).unwrap();
+        assert_eq!(completed.status, BaselineStatus::Success);
+        assert!(!completed.failed);
+        assert!(journal.completed(&harness("check_b"), 1).is_none());
+        // A harness whose model or arguments changed, or of another crate, is verified again.
+        assert!(journal.completed(&harness("check_c"), 2).is_none());
+        let other_crate = mock_proof_harness("check_c", None, Some("other"), None);
+        assert!(journal.completed(&other_crate, 1).is_none());
+        journal.finish().unwrap();
+        assert!(!outdir.path().join(JOURNAL_FILE).exists());
+
+        // Without `--resume`, the previous entries are discarded.
+        let journal = SessionJournal::open(outdir.path(), false).unwrap();
+        journal
+            .record(&harness("check_a"), vec![1, 2], &VerificationResult::mock_success(), false)
+            .unwrap();
+        drop(journal);
+        let journal = SessionJournal::open(outdir.path(), false).unwrap();
+        assert!(journal.completed(&harness("check_a"), 1).is_none());
+    }
+}
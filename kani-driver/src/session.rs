@@ -2,9 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::args::common::Verbosity;
-use crate::args::VerificationArgs;
+use crate::args::{MessageFormat, VerificationArgs};
+use crate::args_toml::{read_project_config, HarnessConfig, ProjectConfig, Severity};
+use crate::concrete_playback::concolic::ConcolicHarness;
+use crate::endianness::{apply_endianness_override, resolve_build_target};
+use crate::profile::PhaseTimes;
+use crate::remote_workers::RemoteWorkers;
+use crate::resume::CompletedHarness;
+use crate::tool_versions::use_pinned_tools;
 use crate::util::render_command;
 use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
 use std::io::IsTerminal;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -31,6 +39,10 @@ pub struct KaniSession {
     /// proof attributes.
     pub codegen_tests: bool,
 
+    /// Record the public functions of each crate, and whether a harness reaches them, in the
+    /// metadata of the crate.
+    pub record_public_functions: bool,
+
     /// The location we found the 'kani_rustc' command
     pub kani_compiler: PathBuf,
     /// The location we found 'kani_lib.c'
@@ -38,6 +50,27 @@ pub struct KaniSession {
 
     /// The temporary files we littered that need to be cleaned up at the end of execution
     pub temporaries: Mutex<Vec<PathBuf>>,
+
+    /// The workers used to run CBMC remotely, if any.
+    pub remote_workers: Option<RemoteWorkers>,
+
+    /// The time spent in each phase, reported with `--profile-phases`.
+    pub phase_times: PhaseTimes,
+
+    /// The harnesses skipped with `--resume`, since an interrupted run already verified them.
+    pub resumed_harnesses: Mutex<Vec<CompletedHarness>>,
+
+    /// The counterexamples of the failed harnesses, executed concretely with `--concolic`.
+    pub concolic_harnesses: Mutex<BTreeMap<String, ConcolicHarness>>,
+
+    /// The per-harness configuration read from the `--config` file.
+    pub harness_configs: BTreeMap<String, HarnessConfig>,
+
+    /// The severity of each kind of failure read from the `--config` file.
+    pub severities: BTreeMap<String, Severity>,
+
+    /// The target the code under verification is compiled for, see [Self::build_target].
+    pub target: String,
 }
 
 /// Represents where we detected Kani, with helper methods for using that information to find critical paths
@@ -51,16 +84,35 @@ pub enum InstallType {
 }
 
 impl KaniSession {
-    pub fn new(args: VerificationArgs) -> Result<Self> {
+    pub fn new(mut args: VerificationArgs) -> Result<Self> {
+        // The events are the only output on stdout with `--message-format json`.
+        if args.message_format == MessageFormat::Json {
+            args.common_args.quiet = true;
+        }
+        apply_endianness_override(&mut args)?;
+        let target = resolve_build_target(&args)?;
         init_logger(&args);
         let install = InstallType::new()?;
+        let config = match &args.config {
+            Some(path) => read_project_config(path)?,
+            None => ProjectConfig::default(),
+        };
+        use_pinned_tools(&config.tools)?;
 
         Ok(KaniSession {
+            remote_workers: RemoteWorkers::new(&args.remote_workers, &args.remote_dir),
             args,
             codegen_tests: false,
+            record_public_functions: false,
             kani_compiler: install.kani_compiler()?,
             kani_lib_c: install.kani_lib_c()?,
             temporaries: Mutex::new(vec![]),
+            phase_times: PhaseTimes::default(),
+            resumed_harnesses: Mutex::new(vec![]),
+            concolic_harnesses: Mutex::new(BTreeMap::new()),
+            harness_configs: config.harness,
+            severities: config.severity,
+            target,
         })
     }
 
@@ -84,6 +136,26 @@ pub fn record_temporary_files<T: AsRef<Path>>(&self, temps: &[T]) {
     pub fn reachability_mode(&self) -> ReachabilityMode {
         if self.codegen_tests { ReachabilityMode::Tests } else { ReachabilityMode::ProofHarnesses }
     }
+
+    /// The target triple the code under verification is compiled for, or the path of its custom
+    /// target spec, e.g., the variant of the target selected with `--endianness`.
+    pub fn build_target(&self) -> &str {
+        &self.target
+    }
+
+    /// The name of the build target, see [target_name].
+    pub fn target_name(&self) -> &str {
+        target_name(self.build_target())
+    }
+}
+
+/// The name of a target, which is the file stem of a custom target spec, e.g., `my-board` for
+/// `boards/my-board.json`. Cargo and rustc name the target folders after it.
+pub fn target_name(target: &str) -> &str {
+    match target.strip_suffix(".json") {
+        Some(_) => Path::new(target).file_stem().and_then(|stem| stem.to_str()).unwrap_or(target),
+        None => target,
+    }
 }
 
 #[derive(Debug, Copy, Clone, Display)]
@@ -255,8 +327,16 @@ fn with_timer<T, F>(verbosity: &impl Verbosity, func: F, description: &str) -> T
     ret
 }
 
-/// Return the path for the folder where the current executable is located.
+/// The environment variable that overrides the folder of Kani's binaries. Tools that embed the
+/// driver aren't installed with Kani, so they must set it.
+const BIN_DIR_VAR: &str = "KANI_BIN_DIR";
+
+/// Return the path for the folder where the current executable is located, unless it is set with
+/// `KANI_BIN_DIR`.
 fn bin_folder() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(BIN_DIR_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
     let exe = std::env::current_exe().context("Cannot determine current executable location")?;
     let dir = exe.parent().context("Executable isn't in a directory")?.to_owned();
     Ok(dir)
@@ -267,6 +347,24 @@ pub fn lib_folder() -> Result<PathBuf> {
     Ok(base_folder()?.join("lib"))
 }
 
+/// Return the path for the folder where the pre-compiled rust libraries for `target` are located.
+/// Libraries for targets other than the host are built with `build-kani build-dev --target`, and
+/// the ones of a custom target spec are found under its name, see [target_name].
+pub fn lib_folder_for(target: &str) -> Result<PathBuf> {
+    if target == env!("TARGET") {
+        return lib_folder();
+    }
+    let path = base_folder()?.join("targets").join(target).join("lib");
+    if !path.exists() {
+        bail!(
+            "Kani libraries for target `{target}` were not found in `{}`. Build them with \
+            `cargo build-dev --target {target}`.",
+            path.display()
+        );
+    }
+    Ok(path)
+}
+
 /// Return the path for the folder where the pre-compiled rust libraries are located.
 pub fn lib_playback_folder() -> Result<PathBuf> {
     Ok(base_folder()?.join("playback/lib"))
@@ -374,7 +472,8 @@ fn init_logger(args: &VerificationArgs) {
             .with_ansi(use_colors)
             .with_target(true),
     );
-    tracing::subscriber::set_global_default(subscriber).unwrap();
+    // Tools that embed the driver may create several sessions, or install their own subscriber.
+    let _ = tracing::subscriber::set_global_default(subscriber);
 }
 
 // Setup the default version of cargo being run, based on the type/mode of installation for kani
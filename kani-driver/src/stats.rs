@@ -0,0 +1,224 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Resource statistics of the verification of each harness, reported with `--stats`.
+//!
+//! Most statistics are extracted from the messages printed by CBMC, e.g.,
+//! `Generated 12 VCC(s), 3 remaining after simplification` or `4425 variables, 8431 clauses`.
+//! The peak memory is sampled while CBMC runs, so short spikes may be missed.
+
+use crate::harness_runner::HarnessResult;
+use crate::report::baseline::BaselineStatus;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// The statistics of the verification of one harness. Values that CBMC didn't report are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct HarnessStats {
+    /// The peak resident memory in bytes of CBMC and the solver it runs.
+    pub peak_memory: Option<u64>,
+    /// The number of verification conditions left after simplification.
+    pub vccs: Option<u64>,
+    /// The number of variables of the largest SAT problem.
+    pub sat_variables: Option<u64>,
+    /// The number of clauses of the largest SAT problem.
+    pub sat_clauses: Option<u64>,
+    /// The time spent in the SAT solver, in seconds.
+    pub solver_time: Option<f64>,
+    /// The time spent in symbolic execution, in seconds.
+    pub symex_time: Option<f64>,
+}
+
+impl HarnessStats {
+    /// Extract the statistics from the messages printed by CBMC.
+    pub fn from_messages<'a>(messages: impl IntoIterator<Item = &'a str>) -> HarnessStats {
+        let mut stats = HarnessStats::default();
+        for message in messages {
+            if let Some(rest) = message.strip_prefix("Generated ") {
+                // `Generated 12 VCC(s), 3 remaining after simplification`
+                if let Some(remaining) = rest
+                    .split_once(", ")
+                    .and_then(|(_, rest)| rest.strip_suffix(" remaining after simplification"))
+                    .and_then(|remaining| remaining.parse::<u64>().ok())
+                {
+                    *stats.vccs.get_or_insert(0) += remaining;
+                }
+            } else if let Some(time) = message.strip_prefix("Runtime Solver: ") {
+                add_time(&mut stats.solver_time, time);
+            } else if let Some(time) = message.strip_prefix("Runtime Symex: ") {
+                add_time(&mut stats.symex_time, time);
+            } else if let Some((variables, clauses)) = message.split_once(" variables, ") {
+                let (Ok(variables), Some(Ok(clauses))) = (
+                    variables.parse::<u64>(),
+                    clauses.strip_suffix(" clauses").map(str::parse::<u64>),
+                ) else {
+                    continue;
+                };
+                stats.sat_variables = stats.sat_variables.max(Some(variables));
+                stats.sat_clauses = stats.sat_clauses.max(Some(clauses));
+            }
+        }
+        stats
+    }
+}
+
+/// Add a time printed by CBMC, e.g., `0.25s`, to `total`.
+fn add_time(total: &mut Option<f64>, time: &str) {
+    if let Ok(time) = time.trim_end_matches('s').parse::<f64>() {
+        *total.get_or_insert(0.0) += time;
+    }
+}
+
+/// The entry of a harness in the JSON file written with `--stats`.
+#[derive(Serialize)]
+pub struct StatsEntry<'a> {
+    pub harness: &'a str,
+    pub status: BaselineStatus,
+    /// The wall-clock time of the verification, in seconds.
+    pub runtime: f64,
+    #[serde(flatten)]
+    pub stats: &'a HarnessStats,
+}
+
+pub fn stats_entries<'a>(results: &'a [HarnessResult<'_>]) -> Vec<StatsEntry<'a>> {
+    results
+        .iter()
+        .map(|HarnessResult { harness, result }| StatsEntry {
+            harness: &harness.pretty_name,
+            status: result.status.into(),
+            runtime: result.runtime.as_secs_f64(),
+            stats: &result.stats,
+        })
+        .collect()
+}
+
+/// Format the statistics of each harness as a table, slowest harness first.
+pub fn format_stats_table(results: &[HarnessResult<'_>]) -> String {
+    let mut entries = stats_entries(results);
+    entries.sort_by(|a, b| b.runtime.total_cmp(&a.runtime));
+
+    let header =
+        ["Harness", "Time (s)", "Memory (MiB)", "VCCs", "Variables", "Clauses", "Solver (s)"];
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            let stats = entry.stats;
+            vec![
+                entry.harness.to_string(),
+                format!("{:.2}", entry.runtime),
+                optional(stats.peak_memory.map(|bytes| format!("{}", bytes / (1024 * 1024)))),
+                optional(stats.vccs.map(|vccs| vccs.to_string())),
+                optional(stats.sat_variables.map(|variables| variables.to_string())),
+                optional(stats.sat_clauses.map(|clauses| clauses.to_string())),
+                optional(stats.solver_time.map(|time| format!("{time:.2}"))),
+            ]
+        })
+        .collect();
+    format_table(&header, &rows)
+}
+
+/// Render a missing value as `-`.
+pub fn optional(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".to_string())
+}
+
+/// Format a table with one row per harness. The first column is aligned to the left, and the
+/// other ones to the right.
+pub fn format_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut table = String::new();
+    let mut write_row = |cells: &mut dyn Iterator<Item = &str>| {
+        for (idx, (cell, width)) in cells.zip(&widths).enumerate() {
+            if idx == 0 {
+                write!(table, "{cell:<width$}").unwrap();
+            } else {
+                write!(table, "  {cell:>width$}").unwrap();
+            }
+        }
+        table.push('\n');
+    };
+    write_row(&mut header.iter().copied());
+    for row in rows {
+        write_row(&mut row.iter().map(String::as_str));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::{VerificationResult, VerificationStatus};
+    use crate::metadata::tests::{mock_proof_harness, mock_verification_result};
+    use std::time::Duration;
+
+    #[test]
+    fn check_stats_from_messages() {
+        let messages = [
+            "Generated 12 VCC(s), 3 remaining after simplification",
+            "Solving with CaDiCaL sc2021",
+            "4425 variables, 8431 clauses",
+            "Runtime Symex: 1.5s",
+            "Runtime Solver: 0.25s",
+            "Generated 2 VCC(s), 1 remaining after simplification",
+            "100 variables, 200 clauses",
+            "Runtime Solver: 0.5s",
+            "Runtime decision procedure: 1.2s",
+            "Not a statistic: 3 variables, many clauses",
+        ];
+        let stats = HarnessStats::from_messages(messages);
+        assert_eq!(
+            stats,
+            HarnessStats {
+                peak_memory: None,
+                vccs: Some(4),
+                sat_variables: Some(4425),
+                sat_clauses: Some(8431),
+                solver_time: Some(0.75),
+                symex_time: Some(1.5),
+            }
+        );
+        assert_eq!(
+            HarnessStats::from_messages(["Starting Bounded Model Checking"]),
+            HarnessStats::default()
+        );
+    }
+
+    #[test]
+    fn check_format_stats_table() {
+        let fast = mock_proof_harness("fast", None, None, None);
+        let slow = mock_proof_harness("slow_harness", None, None, None);
+        let result = |secs, stats| VerificationResult {
+            runtime: Duration::from_secs(secs),
+            stats,
+            ..mock_verification_result(VerificationStatus::Success, Ok(vec![]))
+        };
+        let stats = HarnessStats {
+            peak_memory: Some(300 * 1024 * 1024),
+            vccs: Some(3),
+            sat_variables: Some(4425),
+            sat_clauses: Some(8431),
+            solver_time: Some(0.25),
+            symex_time: Some(4.0),
+        };
+        let results = [
+            HarnessResult { harness: &fast, result: result(1, HarnessStats::default()) },
+            HarnessResult { harness: &slow, result: result(10, stats) },
+        ];
+        assert_eq!(
+            format_stats_table(&results),
+            "Harness       Time (s)  Memory (MiB)  VCCs  Variables  Clauses  Solver (s)\n\
+             slow_harness     10.00           300     3       4425     8431        0.25\n\
+             fast              1.00             -     -          -        -           -\n"
+        );
+        let json = serde_json::to_value(stats_entries(&results)).unwrap();
+        assert_eq!(json[1]["harness"], "slow_harness");
+        assert_eq!(json[1]["status"], "success");
+        assert_eq!(json[1]["vccs"], 3);
+        assert_eq!(json[0]["peak_memory"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Suggest a stub for each function that Kani can't verify, i.e., a foreign function or a
+//! function with a missing definition that is reached by a harness.
+//!
+//! The suggestion is a stub skeleton that can be pasted next to the harness, with the signature
+//! of the function when its declaration can be read from the sources, and the `#[kani::stub]`
+//! attribute that replaces the function with the stub.
+
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// The description of the check that fails when a foreign function is called.
+static FOREIGN_CALL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"call to foreign "(\w+)" function `([^`]+)`"#).unwrap());
+
+/// Format the stub suggestions for the failed properties caused by functions that Kani can't
+/// verify. Returns an empty string if there are none.
+pub fn format_stub_suggestions(properties: &[Property]) -> String {
+    let mut seen = BTreeSet::new();
+    let mut output = String::new();
+    for property in properties.iter().filter(|prop| prop.status == CheckStatus::Failure) {
+        let (name, call_conv, declaration) =
+            if let Some(captures) = FOREIGN_CALL.captures(&property.description) {
+                let name = captures[2].to_string();
+                let location = &property.source_location;
+                let declaration =
+                    location.file.as_ref().zip(location.line.as_ref()).and_then(|(file, line)| {
+                        let source = std::fs::read_to_string(file).ok()?;
+                        declaration_in(&source, line.parse().ok()?, &name)
+                    });
+                (name, Some(captures[1].to_string()), declaration)
+            } else if property.property_class() == "missing_definition" {
+                let Some(name) = property.property_id.fn_name.clone() else { continue };
+                (name, None, None)
+            } else {
+                continue;
+            };
+        if seen.insert(name.clone()) {
+            output.push_str(&stub_suggestion(&name, call_conv.as_deref(), declaration.as_deref()));
+        }
+    }
+    output
+}
+
+/// Extract the declaration `fn <name>(<params>) -> <ret>` that starts at the given line (starting
+/// at 1) of `source`, with its whitespace normalized.
+fn declaration_in(source: &str, line: usize, name: &str) -> Option<String> {
+    let text: Vec<&str> = source.lines().skip(line.checked_sub(1)?).take(20).collect();
+    let text = text.join(" ");
+    let short_name = name.rsplit("::").next().unwrap();
+    let start = text.find(&format!("fn {short_name}"))?;
+    let end = start + text[start..].find([';', '{'])?;
+    Some(text[start..end].split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Split a declaration `fn <name>(<params>) -> <ret>` into `(<params>)` and `<ret>`, if any.
+fn split_declaration(declaration: &str) -> Option<(&str, Option<&str>)> {
+    let open = declaration.find('(')?;
+    let mut depth = 0;
+    for (idx, c) in declaration[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            let close = open + idx + 1;
+            let ret = declaration[close..].trim().strip_prefix("->").map(str::trim);
+            return Some((&declaration[open..close], ret));
+        }
+    }
+    None
+}
+
+fn stub_suggestion(name: &str, call_conv: Option<&str>, declaration: Option<&str>) -> String {
+    let stub_name = format!("stub_{}", name.rsplit("::").next().unwrap());
+    let qualifiers = if call_conv == Some("C") { "unsafe extern \"C\" " } else { "" };
+    let (params, ret) = match declaration.and_then(split_declaration) {
+        Some((params, ret)) => (params.to_string(), ret),
+        None => (format!("(/* TODO: the parameters of `{name}` */)"), Some("/* TODO */")),
+    };
+    let body = match ret {
+        None => format!("// TODO: model the side effects of `{name}`.\n"),
+        Some(ret) if ret.starts_with("*const") => {
+            "// TODO: return a valid pointer if the callers dereference it.\n    \
+            std::ptr::null()\n"
+                .to_string()
+        }
+        Some(ret) if ret.starts_with("*mut") => {
+            "// TODO: return a valid pointer if the callers dereference it.\n    \
+            std::ptr::null_mut()\n"
+                .to_string()
+        }
+        Some(_) => "// TODO: constrain the result with `kani::assume`, or write a contract.\n    \
+            kani::any()\n"
+            .to_string(),
+    };
+    let ret = ret.map(|ret| format!(" -> {ret}")).unwrap_or_default();
+
+    let mut output = String::new();
+    writeln!(output, "Kani can't verify the calls to `{name}`. Replace it with a stub, e.g.:\n")
+        .unwrap();
+    writeln!(output, "#[cfg(kani)]\n#[allow(unused_variables)]").unwrap();
+    writeln!(output, "{qualifiers}fn {stub_name}{params}{ret} {{\n    {body}}}\n").unwrap();
+    writeln!(
+        output,
+        "Then attach the stub to the harness, with the path of `{name}` from the module of the \
+        harness, and run Kani with `-Z stubbing`:\n"
+    )
+    .unwrap();
+    writeln!(output, "#[kani::stub({name}, {stub_name})]\n").unwrap();
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_declaration_in() {
+        let source =
+            "extern \"C\" {\n    pub fn strlen(\n        cs: *const c_char,\n    ) -> size_t;\n}\n";
+        assert_eq!(
+            declaration_in(source, 2, "strlen").as_deref(),
+            Some("fn strlen( cs: *const c_char, ) -> size_t")
+        );
+        assert_eq!(declaration_in(source, 2, "memcpy"), None);
+        assert_eq!(
+            split_declaration("fn apply(f: fn(u8) -> u8, x: u8) -> u8"),
+            Some(("(f: fn(u8) -> u8, x: u8)", Some("u8")))
+        );
+        assert_eq!(split_declaration("fn reset()"), Some(("()", None)));
+    }
+
+    #[test]
+    fn check_stub_suggestion() {
+        let suggestion =
+            stub_suggestion("strlen", Some("C"), Some("fn strlen(cs: *const c_char) -> size_t"));
+        assert!(suggestion.contains(
+            "#[cfg(kani)]\n#[allow(unused_variables)]\n\
+            unsafe extern \"C\" fn stub_strlen(cs: *const c_char) -> size_t {\n    \
+            // TODO: constrain the result with `kani::assume`, or write a contract.\n    \
+            kani::any()\n}\n"
+        ));
+        assert!(suggestion.contains("#[kani::stub(strlen, stub_strlen)]"));
+
+        let suggestion =
+            stub_suggestion("ffi::get_buffer", Some("C"), Some("fn get_buffer() -> *mut u8"));
+        assert!(suggestion.contains("fn stub_get_buffer() -> *mut u8 {"));
+        assert!(suggestion.contains("std::ptr::null_mut()"));
+
+        let suggestion = stub_suggestion("krate::missing", None, None);
+        assert!(suggestion.contains(
+            "fn stub_missing(/* TODO: the parameters of `krate::missing` */) -> /* TODO */ {"
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the versions of CBMC and Kissat pinned in the `[tools]` table of a `kani.toml` are
+//! the ones in use, since different versions can give very different results.
+//!
+//! `cargo kani setup --cbmc-version <VERSION> --cbmc-commit <COMMIT>` installs the pinned versions
+//! into `tools/<tool>-<version>/bin` in the Kani home directory. These directories take precedence
+//! over the `PATH`, so a project uses its pinned tools even when others are installed.
+
+use crate::args_toml::ToolVersions;
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Put the pinned versions of the tools first in the `PATH`, and check that they are the ones
+/// that will run.
+pub fn use_pinned_tools(tools: &ToolVersions) -> Result<()> {
+    let pins = [("cbmc", &tools.cbmc), ("kissat", &tools.kissat)];
+    let pins: Vec<(&str, &String)> =
+        pins.into_iter().filter_map(|(tool, version)| Some((tool, version.as_ref()?))).collect();
+    if pins.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(tools_dir) = tools_dir() {
+        let pinned_dirs = pins
+            .iter()
+            .map(|(tool, version)| tools_dir.join(format!("{tool}-{version}")).join("bin"))
+            .filter(|dir| dir.is_dir());
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let path = std::env::join_paths(pinned_dirs.chain(std::env::split_paths(&path)))?;
+        std::env::set_var("PATH", path);
+    }
+
+    for (tool, version) in pins {
+        let pin =
+            if tool == "cbmc" { "--cbmc-commit <COMMIT>" } else { "--kissat-sha256 <SHA256>" };
+        let install =
+            format!("Install it with `cargo kani setup --{tool}-version {version} {pin}`.");
+        let output = Command::new(tool).arg("--version").output().with_context(|| {
+            format!("Failed to run `{tool}`, which is pinned to version {version}. {install}")
+        })?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let found = tool_version(&stdout);
+        if found != version {
+            bail!(
+                "`{tool}` has version {found}, but the project pins version {version} in the \
+                `[tools]` table of its configuration. {install}"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The directory of the pinned tools in the Kani home directory, which is `${KANI_HOME}`, or
+/// `${HOME}/.kani` by default, as in `cargo kani setup`.
+fn tools_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("KANI_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".kani")))?;
+    Some(home.join("tools"))
+}
+
+/// The version in the output of `--version`, e.g., `6.1.1` in `6.1.1 (cbmc-6.1.1)`.
+fn tool_version(output: &str) -> &str {
+    output.split_whitespace().next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_tool_version() {
+        assert_eq!(tool_version("6.1.1 (cbmc-6.1.1)\n"), "6.1.1");
+        assert_eq!(tool_version("3.1.1\n"), "3.1.1");
+        assert_eq!(tool_version(""), "");
+    }
+}
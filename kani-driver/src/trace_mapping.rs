@@ -0,0 +1,266 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Attribute the frames of counterexample traces that belong to code generated by Kani to the
+//! user functions they were generated for.
+//!
+//! Function contracts are instrumented as closures inside the function with the contract, so a
+//! failure in a contract is reported in functions such as `div::{closure#2}`. Similarly, a
+//! failure inside a stub is reported in the stub, although the user reasons about the function
+//! that was stubbed. We rewrite the function of these frames to the user function, and mention
+//! the contract clause or the stub involved, e.g., `div (postcondition)` or
+//! `rand::random (stubbed by mock_random)`.
+
+use crate::cbmc_output_parser::{Property, SourceLocation};
+use kani_metadata::{HarnessKind, HarnessMetadata};
+use std::collections::HashMap;
+
+/// Rewrite the functions of the source locations of the properties and their traces.
+pub fn map_generated_frames(properties: &mut [Property], harness: &HarnessMetadata) {
+    let mapper = FrameMapper::new(harness);
+    if mapper.contracts.is_empty() && mapper.stubs.is_empty() {
+        return;
+    }
+    let mut sources = SourceCache::default();
+    for property in properties {
+        mapper.map_location(&mut property.source_location, &mut sources);
+        for item in property.trace.iter_mut().flatten() {
+            if let Some(location) = &mut item.source_location {
+                mapper.map_location(location, &mut sources);
+            }
+        }
+    }
+}
+
+/// The clauses of a function contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Clause {
+    Precondition,
+    Postcondition,
+    Modifies,
+}
+
+impl Clause {
+    /// Identify the clause declared in a line of source code, if any.
+    fn from_source_line(line: &str) -> Option<Clause> {
+        let line = line.trim_start();
+        if !line.starts_with("#[") {
+            return None;
+        }
+        if line.contains("requires(") {
+            Some(Clause::Precondition)
+        } else if line.contains("ensures(") {
+            Some(Clause::Postcondition)
+        } else if line.contains("modifies(") {
+            Some(Clause::Modifies)
+        } else {
+            None
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Clause::Precondition => "precondition",
+            Clause::Postcondition => "postcondition",
+            Clause::Modifies => "modifies clause",
+        }
+    }
+}
+
+struct FrameMapper<'a> {
+    /// The functions whose contract is checked or used as a stub.
+    contracts: Vec<&'a str>,
+    /// The stubs as pairs of (original, replacement).
+    stubs: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> FrameMapper<'a> {
+    fn new(harness: &'a HarnessMetadata) -> FrameMapper<'a> {
+        let mut contracts: Vec<&str> =
+            harness.attributes.verified_stubs.iter().map(|name| user_path(name)).collect();
+        if let HarnessKind::ProofForContract { target_fn } = &harness.attributes.kind {
+            contracts.push(user_path(target_fn));
+        }
+        let stubs = harness
+            .attributes
+            .stubs
+            .iter()
+            .map(|stub| (user_path(&stub.original), user_path(&stub.replacement)))
+            .collect();
+        FrameMapper { contracts, stubs }
+    }
+
+    fn map_location(&self, location: &mut SourceLocation, sources: &mut SourceCache) {
+        let Some(function) = &location.function else { return };
+        let clause = || {
+            let line = location.line.as_ref()?.parse().ok()?;
+            sources.line(location.file.as_ref()?, line).and_then(Clause::from_source_line)
+        };
+        if let Some(mapped) = self.map_function(function, clause) {
+            location.function = Some(mapped);
+        }
+    }
+
+    /// The user function a frame should be attributed to, or `None` if the frame is not in
+    /// generated code.
+    fn map_function(
+        &self,
+        function: &str,
+        clause: impl FnOnce() -> Option<Clause>,
+    ) -> Option<String> {
+        let function = strip_generic_args(function);
+        for (original, replacement) in &self.stubs {
+            let stub = function.split("::{closure#").next().unwrap();
+            if is_path(stub, replacement) {
+                return Some(format!("{original} (stubbed by {replacement})"));
+            }
+        }
+        let generated = ["::{closure#", "::kani_register_contract"]
+            .iter()
+            .filter_map(|marker| function.find(marker))
+            .min()?;
+        let owner = &function[..generated];
+        let contract = self.contracts.iter().find(|contract| is_path(owner, contract))?;
+        Some(match clause() {
+            Some(clause) => format!("{contract} ({})", clause.description()),
+            None => contract.to_string(),
+        })
+    }
+}
+
+/// Remove the path prefixes that are not part of the pretty name of a function.
+fn user_path(path: &str) -> &str {
+    let mut path = path.trim();
+    for prefix in ["crate::", "self::", "super::"] {
+        while let Some(rest) = path.strip_prefix(prefix) {
+            path = rest;
+        }
+    }
+    path
+}
+
+/// Whether the function name refers to the given path, which may be partially qualified.
+fn is_path(function: &str, path: &str) -> bool {
+    function == path || function.strip_suffix(path).is_some_and(|prefix| prefix.ends_with("::"))
+}
+
+/// Remove the generic arguments of a function name, e.g., `foo::<u8>` becomes `foo`.
+fn strip_generic_args(function: &str) -> String {
+    let mut result = String::with_capacity(function.len());
+    let mut depth = 0;
+    let mut rest = function;
+    while !rest.is_empty() {
+        if depth == 0 && rest.starts_with("::<") {
+            depth = 1;
+            rest = &rest[3..];
+            continue;
+        }
+        let c = rest.chars().next().unwrap();
+        if depth > 0 {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => {}
+            }
+        } else {
+            result.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+/// Source files read to identify contract clauses.
+#[derive(Default)]
+struct SourceCache {
+    files: HashMap<String, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    fn line(&mut self, file: &str, line: usize) -> Option<&str> {
+        let lines = self.files.entry(file.to_string()).or_insert_with(|| {
+            std::fs::read_to_string(file)
+                .ok()
+                .map(|content| content.lines().map(String::from).collect())
+        });
+        lines.as_ref()?.get(line.checked_sub(1)?).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+    use kani_metadata::Stub;
+
+    fn mapper<'a>(contracts: &[&'a str], stubs: &[(&'a str, &'a str)]) -> FrameMapper<'a> {
+        FrameMapper { contracts: contracts.to_vec(), stubs: stubs.to_vec() }
+    }
+
+    #[test]
+    fn check_map_contract_frames() {
+        let mapper = mapper(&["div"], &[]);
+        assert_eq!(mapper.map_function("div::{closure#2}", || None).as_deref(), Some("div"));
+        assert_eq!(
+            mapper
+                .map_function("my_crate::div::{closure#2}::{closure#0}", || {
+                    Some(Clause::Postcondition)
+                })
+                .as_deref(),
+            Some("div (postcondition)")
+        );
+        assert_eq!(
+            mapper.map_function("div::kani_register_contract::<u32, {closure}>", || None),
+            Some("div".to_string())
+        );
+        assert_eq!(mapper.map_function("div", || None), None);
+        assert_eq!(mapper.map_function("other::{closure#0}", || None), None);
+        assert_eq!(mapper.map_function("my_div::{closure#0}", || None), None);
+    }
+
+    #[test]
+    fn check_map_stub_frames() {
+        let mapper = mapper(&[], &[("rand::random", "mock_random")]);
+        assert_eq!(
+            mapper.map_function("tests::mock_random::<u32>", || None).as_deref(),
+            Some("rand::random (stubbed by mock_random)")
+        );
+        assert_eq!(
+            mapper.map_function("mock_random::{closure#0}", || None).as_deref(),
+            Some("rand::random (stubbed by mock_random)")
+        );
+        assert_eq!(mapper.map_function("rand::random", || None), None);
+    }
+
+    #[test]
+    fn check_clause_from_source_line() {
+        assert_eq!(
+            Clause::from_source_line("    #[kani::requires(divisor != 0)]"),
+            Some(Clause::Precondition)
+        );
+        assert_eq!(
+            Clause::from_source_line("#[ensures(|result| *result > 0)]"),
+            Some(Clause::Postcondition)
+        );
+        assert_eq!(Clause::from_source_line("#[kani::modifies(ptr)]"), Some(Clause::Modifies));
+        assert_eq!(Clause::from_source_line("requires(x)"), None);
+    }
+
+    #[test]
+    fn check_mapper_from_harness() {
+        let mut harness = mock_proof_harness("check_div", None, None, None);
+        harness.attributes.kind = HarnessKind::ProofForContract { target_fn: "crate::div".into() };
+        harness.attributes.verified_stubs = vec!["self::helper".to_string()];
+        harness.attributes.stubs =
+            vec![Stub { original: "rand::random".into(), replacement: "mock".into() }];
+        let mapper = FrameMapper::new(&harness);
+        assert_eq!(mapper.contracts, ["helper", "div"]);
+        assert_eq!(mapper.stubs, [("rand::random", "mock")]);
+    }
+
+    #[test]
+    fn check_strip_generic_args() {
+        assert_eq!(strip_generic_args("foo::<Vec<u8>>::{closure#0}"), "foo::{closure#0}");
+        assert_eq!(strip_generic_args("<u8 as Foo>::bar"), "<u8 as Foo>::bar");
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Render the nondeterministic values of a counterexample trace as Rust values.
+//!
+//! CBMC reports the values returned by `kani::any_raw_internal::<T>()` and
+//! `kani::any_raw_array::<T, N>()` as bit vectors, and interprets them as C values. Since the
+//! type `T` is part of the name of the function, we use it to render the bytes the way they
+//! would be written in Rust, e.g., `'a'` for a `char` or `1.5` for an `f32`.
+
+/// The type of the values returned by a `kani::any_raw_*` function, e.g., `char` for
+/// `kani::any_raw_internal::<char>`. For arrays, CBMC assigns each element separately, so we
+/// return the type of the elements.
+pub fn nondet_type(function: &str) -> Option<&str> {
+    let args = function
+        .strip_prefix("kani::any_raw_internal::<")
+        .or_else(|| function.strip_prefix("kani::any_raw_array::<"))?
+        .strip_suffix('>')?;
+    Some(first_generic_arg(args).trim())
+}
+
+/// Return the first argument of a comma separated list of generic arguments.
+fn first_generic_arg(args: &str) -> &str {
+    let mut depth = 0;
+    for (idx, c) in args.char_indices() {
+        match c {
+            '<' | '[' | '(' => depth += 1,
+            '>' | ']' | ')' => depth -= 1,
+            ',' if depth == 0 => return &args[..idx],
+            _ => {}
+        }
+    }
+    args
+}
+
+/// Render the little-endian bytes of a value of type `typ` as a Rust literal.
+/// Returns `None` if the type isn't supported or the bytes aren't a valid value of the type.
+pub fn render_value(typ: &str, bytes: &[u8]) -> Option<String> {
+    macro_rules! render_int {
+        ($ty:ty) => {{
+            let bytes = bytes.try_into().ok()?;
+            Some(<$ty>::from_le_bytes(bytes).to_string())
+        }};
+    }
+    macro_rules! render_float {
+        ($ty:ty) => {{
+            let bytes = bytes.try_into().ok()?;
+            Some(format!("{:?}", <$ty>::from_le_bytes(bytes)))
+        }};
+    }
+    match typ {
+        "bool" => match bytes {
+            [0] => Some("false".to_string()),
+            [1] => Some("true".to_string()),
+            _ => None,
+        },
+        "char" => {
+            let code = u32::from_le_bytes(bytes.try_into().ok()?);
+            char::from_u32(code).map(|c| format!("{c:?}"))
+        }
+        "f32" => render_float!(f32),
+        "f64" => render_float!(f64),
+        "u8" => render_int!(u8),
+        "u16" => render_int!(u16),
+        "u32" => render_int!(u32),
+        "u64" => render_int!(u64),
+        "u128" => render_int!(u128),
+        "i8" => render_int!(i8),
+        "i16" => render_int!(i16),
+        "i32" => render_int!(i32),
+        "i64" => render_int!(i64),
+        "i128" => render_int!(i128),
+        "usize" => render_pointer_sized(bytes, false),
+        "isize" => render_pointer_sized(bytes, true),
+        _ => None,
+    }
+}
+
+/// Render a `usize` or an `isize`, whose size depends on the target.
+fn render_pointer_sized(bytes: &[u8], signed: bool) -> Option<String> {
+    match (bytes.len(), signed) {
+        (8, false) => render_value("u64", bytes),
+        (8, true) => render_value("i64", bytes),
+        (4, false) => render_value("u32", bytes),
+        (4, true) => render_value("i32", bytes),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_nondet_type() {
+        assert_eq!(nondet_type("kani::any_raw_internal::<char>"), Some("char"));
+        assert_eq!(nondet_type("kani::any_raw_array::<u8, 4>"), Some("u8"));
+        assert_eq!(nondet_type("kani::any_raw_array::<[u8; 2], 4>"), Some("[u8; 2]"));
+        assert_eq!(nondet_type("kani::any_raw_internal::<(u8, bool)>"), Some("(u8, bool)"));
+        assert_eq!(nondet_type("kani::any::<char>"), None);
+    }
+
+    #[test]
+    fn check_render_value() {
+        assert_eq!(render_value("bool", &[1]).as_deref(), Some("true"));
+        assert_eq!(render_value("bool", &[2]), None);
+        assert_eq!(render_value("char", &[97, 0, 0, 0]).as_deref(), Some("'a'"));
+        assert_eq!(render_value("char", &[10, 0, 0, 0]).as_deref(), Some("'\\n'"));
+        assert_eq!(render_value("char", &[0, 0xd8, 0, 0]), None);
+        assert_eq!(render_value("f32", &1.5f32.to_le_bytes()).as_deref(), Some("1.5"));
+        assert_eq!(render_value("f64", &f64::NAN.to_le_bytes()).as_deref(), Some("NaN"));
+        assert_eq!(render_value("f64", &f64::NEG_INFINITY.to_le_bytes()).as_deref(), Some("-inf"));
+        assert_eq!(render_value("i16", &(-2i16).to_le_bytes()).as_deref(), Some("-2"));
+        assert_eq!(render_value("u16", &(-2i16).to_le_bytes()).as_deref(), Some("65534"));
+        assert_eq!(render_value("isize", &(-1i64).to_le_bytes()).as_deref(), Some("-1"));
+        assert_eq!(render_value("u32", &[1, 0]), None);
+        assert_eq!(render_value("MyStruct", &[1]), None);
+    }
+}
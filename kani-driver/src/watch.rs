@@ -0,0 +1,212 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implements `cargo kani --watch`, which keeps re-verifying a package while it is edited.
+//!
+//! Kani polls the sources of the workspace, and rebuilds the package whenever one of them changes.
+//! Since the goto model of a harness only includes the code reachable from it, we only verify the
+//! harnesses whose model changed since they were last verified.
+
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use crate::util;
+use anyhow::{Context, Result};
+use kani_metadata::{ArtifactType, HarnessMetadata};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often we check the workspace for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Verify the cargo package, and verify it again every time its sources change.
+///
+/// Errors while building or verifying the package are reported, and Kani keeps watching the
+/// sources. This function only returns if it cannot determine which sources to watch.
+pub(crate) fn watch_cargo_project(session: KaniSession) -> Result<()> {
+    let metadata = session.cargo_metadata(session.build_target())?;
+    let workspace_root = metadata.workspace_root.into_std_path_buf();
+    let mut ignored = vec![metadata.target_directory.into_std_path_buf()];
+    if let Some(target_dir) = &session.args.target_dir {
+        ignored.push(std::env::current_dir()?.join(target_dir));
+    }
+
+    let mut fingerprints = Fingerprints::default();
+    let mut sources = source_snapshot(&workspace_root, &ignored);
+    loop {
+        let result = project::cargo_project(&session, false)
+            .and_then(|project| verify_changed(&session, &project, &mut fingerprints));
+        if let Err(error) = result {
+            util::error(&format!("{error:#}"));
+        }
+
+        println!("[watch] Waiting for changes in `{}`...", workspace_root.display());
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = source_snapshot(&workspace_root, &ignored);
+            if current != sources {
+                sources = current;
+                break;
+            }
+        }
+    }
+}
+
+/// Verify the harnesses of the project whose model changed since they were last verified.
+fn verify_changed(
+    session: &KaniSession,
+    project: &Project,
+    fingerprints: &mut Fingerprints,
+) -> Result<()> {
+    let harnesses = session.determine_targets(&project.get_all_harnesses())?;
+    let mut changed = vec![];
+    for harness in &harnesses {
+        let fingerprint = fingerprint(project, harness)?;
+        if !fingerprints.is_unchanged(&harness.pretty_name, fingerprint) {
+            fingerprints.record_build(&harness.pretty_name, fingerprint);
+            changed.push(*harness);
+        }
+    }
+
+    let unchanged = harnesses.len() - changed.len();
+    if changed.is_empty() {
+        println!("[watch] No harness was affected by the changes.");
+        return Ok(());
+    }
+
+    let runner = HarnessRunner { sess: session, project };
+    let results = runner.check_all_harnesses(&changed)?;
+
+    // The goto model is instrumented in place. If the package isn't rebuilt, the next build
+    // reuses the instrumented model, which should also be considered unchanged.
+    for harness in &changed {
+        fingerprints.record_verified(&harness.pretty_name, fingerprint(project, harness)?);
+    }
+    print_summary(&results, unchanged);
+    Ok(())
+}
+
+fn print_summary(results: &[HarnessResult<'_>], unchanged: usize) {
+    let failures: Vec<_> =
+        results.iter().filter(|r| r.result.status != VerificationStatus::Success).collect();
+    for failure in &failures {
+        println!("Verification failed for - {}", failure.harness.pretty_name);
+    }
+    println!(
+        "[watch] {} successfully verified harnesses, {} failures, {unchanged} unchanged.",
+        results.len() - failures.len(),
+        failures.len(),
+    );
+}
+
+/// Compute the fingerprint of the goto model of a harness and of its metadata, which holds the
+/// attributes that affect its verification, e.g., its unwinding bound or its solver.
+pub(crate) fn fingerprint(project: &Project, harness: &HarnessMetadata) -> Result<u64> {
+    let goto_file = project
+        .get_harness_artifact(harness, ArtifactType::Goto)
+        .with_context(|| format!("Failed to find the goto model of `{}`", harness.pretty_name))?;
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&std::fs::read(goto_file)?);
+    hasher.write(&serde_json::to_vec(harness)?);
+    Ok(hasher.finish())
+}
+
+/// The fingerprints of the model of each harness the last time it was verified.
+#[derive(Default)]
+pub(crate) struct Fingerprints {
+    /// Map from harness name to the fingerprints of the model built by the compiler and the model
+    /// after it was instrumented for verification.
+    harnesses: HashMap<String, (u64, Option<u64>)>,
+}
+
+impl Fingerprints {
+    pub(crate) fn is_unchanged(&self, harness: &str, fingerprint: u64) -> bool {
+        self.harnesses.get(harness).is_some_and(|(built, verified)| {
+            *built == fingerprint || *verified == Some(fingerprint)
+        })
+    }
+
+    pub(crate) fn record_build(&mut self, harness: &str, fingerprint: u64) {
+        self.harnesses.insert(harness.to_string(), (fingerprint, None));
+    }
+
+    pub(crate) fn record_verified(&mut self, harness: &str, fingerprint: u64) {
+        if let Some((_, verified)) = self.harnesses.get_mut(harness) {
+            *verified = Some(fingerprint);
+        }
+    }
+}
+
+/// Collect the modification time of the files that may affect the build.
+///
+/// We only look at Rust sources and Cargo manifests, and skip hidden directories
+/// and the target directories.
+fn source_snapshot(root: &Path, ignored: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut snapshot = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                if !hidden && !ignored.contains(&path) {
+                    pending.push(path);
+                }
+            } else if is_source(&path)
+                && let Ok(modified) = entry.metadata().and_then(|md| md.modified())
+            {
+                snapshot.insert(path, modified);
+            }
+        }
+    }
+    snapshot
+}
+
+fn is_source(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "rs")
+        || path.file_name().is_some_and(|name| name == "Cargo.toml" || name == "Cargo.lock")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_fingerprints() {
+        let mut fingerprints = Fingerprints::default();
+        assert!(!fingerprints.is_unchanged("check", 1));
+
+        fingerprints.record_build("check", 1);
+        fingerprints.record_verified("check", 2);
+        assert!(fingerprints.is_unchanged("check", 1));
+        assert!(fingerprints.is_unchanged("check", 2));
+        assert!(!fingerprints.is_unchanged("check", 3));
+        assert!(!fingerprints.is_unchanged("other", 1));
+
+        fingerprints.record_build("check", 3);
+        assert!(!fingerprints.is_unchanged("check", 2));
+    }
+
+    #[test]
+    fn check_source_snapshot() {
+        let root = tempfile::tempdir().unwrap();
+        let target = root.path().join("target");
+        std::fs::create_dir_all(root.path().join("src")).unwrap();
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::create_dir_all(root.path().join(".git")).unwrap();
+        for file in ["Cargo.toml", "src/lib.rs", "src/notes.md", "target/out.rs", ".git/x.rs"] {
+            std::fs::write(root.path().join(file), "").unwrap();
+        }
+
+        let snapshot = source_snapshot(root.path(), &[target]);
+        let files: Vec<_> =
+            snapshot.keys().map(|path| path.strip_prefix(root.path()).unwrap()).collect();
+        assert_eq!(files, [Path::new("Cargo.toml"), Path::new("src/lib.rs")]);
+    }
+}
@@ -25,6 +25,12 @@ pub enum ArtifactType {
     /// A `json` file that stores the name to prettyName mapping for symbols
     /// (used to demangle names from the C dump).
     PrettyNameMap,
+    /// A `json` file that lists the functions included in the model, written with
+    /// `--reachability-report`.
+    Reachability,
+    /// A `json` file that lists the unsafe operations reachable from each harness of the model,
+    /// written with `--unsafe-report`.
+    UnsafeOperations,
 }
 
 impl ArtifactType {
@@ -37,6 +43,8 @@ const fn extension(&self) -> &'static str {
             ArtifactType::TypeMap => "type_map.json",
             ArtifactType::VTableRestriction => "restrictions.json",
             ArtifactType::PrettyNameMap => "pretty_name_map.json",
+            ArtifactType::Reachability => "reachability.json",
+            ArtifactType::UnsafeOperations => "unsafe_operations.json",
         }
     }
 }
@@ -64,7 +72,9 @@ pub fn convert_type(path: &Path, from: ArtifactType, to: ArtifactType) -> PathBu
         | ArtifactType::SymTabGoto
         | ArtifactType::TypeMap
         | ArtifactType::VTableRestriction
-        | ArtifactType::PrettyNameMap => {
+        | ArtifactType::PrettyNameMap
+        | ArtifactType::Reachability
+        | ArtifactType::UnsafeOperations => {
             result.set_extension("");
             result.set_extension(to);
         }
@@ -23,4 +23,9 @@ pub enum CbmcSolver {
     /// `--external-sat-solver`. The specified binary must exist in path.
     #[strum(disabled, serialize = "bin=<SAT_SOLVER_BINARY>")]
     Binary(String),
+
+    /// A portfolio of solvers that are run in parallel on the same formula. Kani uses the
+    /// result of the first solver to finish.
+    #[strum(disabled, serialize = "portfolio:<SOLVER>,<SOLVER>,...")]
+    Portfolio(Vec<CbmcSolver>),
 }
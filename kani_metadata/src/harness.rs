@@ -3,6 +3,7 @@
 
 use crate::CbmcSolver;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// A CBMC-level `assigns` contract that needs to be enforced on a function.
@@ -36,6 +37,8 @@ pub struct HarnessMetadata {
     pub attributes: HarnessAttributes,
     /// A CBMC-level assigns contract that should be enforced when running this harness.
     pub contract: Option<AssignsContract>,
+    /// The locations of the `kani::assume` calls reachable from this harness.
+    pub assumptions: Vec<String>,
 }
 
 /// The attributes added by the user to control how a harness is executed.
@@ -45,14 +48,57 @@ pub struct HarnessAttributes {
     pub kind: HarnessKind,
     /// Whether the harness is expected to panic or not.
     pub should_panic: bool,
+    /// Whether the harness checks that all its heap allocations are freed.
+    pub check_leaks: bool,
+    /// Whether the harness starts from an arbitrary state of the mutable statics.
+    pub havoc_statics: bool,
     /// Optional data to store solver.
     pub solver: Option<CbmcSolver>,
     /// Optional data to store unwind value.
     pub unwind_value: Option<u32>,
+    /// The unwind bounds of individual loops, keyed by their CBMC id, e.g., `foo.0`.
+    pub loop_unwinds: BTreeMap<String, u32>,
+    /// Optional timeout in seconds for the verification of this harness.
+    pub timeout: Option<u64>,
+    /// Optional memory limit in MiB for the verification of this harness.
+    pub memory_limit: Option<u64>,
+    /// Additional arguments passed to CBMC when verifying this harness.
+    pub cbmc_args: Vec<String>,
+    /// The tags attached to this harness with `#[kani::tag]`.
+    pub tags: Vec<String>,
+    /// The automatic checks turned on or off for this harness with `#[kani::checks]`.
+    pub checks: HarnessChecks,
+    /// The slicing passes turned on or off for this harness with `#[kani::slicing]`.
+    pub slicing: HarnessSlicing,
     /// The stubs used in this harness.
     pub stubs: Vec<Stub>,
     /// The name of the functions being stubbed by their contract.
     pub verified_stubs: Vec<String>,
+    /// The crate-qualified definition path of the function whose contract this harness checks,
+    /// e.g., `my_crate::ops::div`. Unlike the path in [HarnessKind::ProofForContract], it doesn't
+    /// depend on the module of the harness.
+    pub contract_path: Option<String>,
+}
+
+/// The classes of automatic checks that a harness turns on or off with `#[kani::checks]`.
+/// `None` means the class follows the command line options, e.g., `--no-overflow-checks`.
+#[derive(Clone, Default, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HarnessChecks {
+    pub memory_safety: Option<bool>,
+    pub overflow: Option<bool>,
+    pub unwinding: Option<bool>,
+}
+
+/// The slicing passes that a harness turns on or off with `#[kani::slicing]`.
+/// `None` means the pass follows the command line options, e.g., `--full-slice`.
+#[derive(Clone, Default, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HarnessSlicing {
+    /// CBMC's `--slice-formula`, which is on by default.
+    pub formula: Option<bool>,
+    /// goto-instrument's `--constant-propagator`.
+    pub constant_propagation: Option<bool>,
+    /// goto-instrument's `--full-slice`.
+    pub full: Option<bool>,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -71,10 +117,20 @@ pub fn new(kind: HarnessKind) -> HarnessAttributes {
         HarnessAttributes {
             kind,
             should_panic: false,
+            check_leaks: false,
+            havoc_statics: false,
             solver: None,
             unwind_value: None,
+            loop_unwinds: BTreeMap::new(),
+            timeout: None,
+            memory_limit: None,
+            cbmc_args: vec![],
+            tags: vec![],
+            checks: HarnessChecks::default(),
+            slicing: HarnessSlicing::default(),
             stubs: vec![],
             verified_stubs: vec![],
+            contract_path: None,
         }
     }
 
@@ -3,18 +3,25 @@
 
 extern crate clap;
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::PathBuf,
+};
 
 use serde::{Deserialize, Serialize};
 
 pub use artifact::ArtifactType;
 pub use cbmc_solver::CbmcSolver;
 pub use harness::*;
+pub use reachability::*;
+pub use unsafe_ops::*;
 pub use vtable::*;
 
 pub mod artifact;
 mod cbmc_solver;
 mod harness;
+mod reachability;
+mod unsafe_ops;
 pub mod unstable;
 mod vtable;
 
@@ -32,6 +39,24 @@ pub struct KaniMetadata {
     pub unsupported_features: Vec<UnsupportedFeature>,
     /// If crates are built in test-mode, then test harnesses will be recorded here.
     pub test_harnesses: Vec<HarnessMetadata>,
+    /// The public functions of this crate, and whether they are covered by a harness. Only
+    /// recorded by the commands that report them, e.g., `assess`.
+    #[serde(default)]
+    pub public_functions: Vec<FunctionMetadata>,
+}
+
+/// Information about a public function, used to assess how much of a crate has been specified
+/// and verified.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FunctionMetadata {
+    /// The fully qualified name of the function.
+    pub name: String,
+    /// The path of the module where the function is declared.
+    pub module: String,
+    /// Whether the function has a contract.
+    pub has_contract: bool,
+    /// Whether the function is reachable from at least one harness of the crate.
+    pub is_reachable: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +66,9 @@ pub struct UnsupportedFeature {
     pub feature: String,
     /// A list of locations (file, line) where this unsupported feature can be found.
     pub locations: HashSet<Location>,
+    /// The harnesses whose model contains this unsupported feature.
+    #[serde(default)]
+    pub harnesses: BTreeSet<String>,
 }
 
 /// The location in a file
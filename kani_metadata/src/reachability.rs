@@ -0,0 +1,82 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Data structures to represent the functions included in a goto model.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The functions and statics included in a goto model, and the items that each of them uses.
+///
+/// A model may be shared by several harnesses, so the items of one harness are the ones
+/// reachable from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReachabilityReport {
+    /// The harnesses that the model was generated for.
+    pub harnesses: Vec<String>,
+    /// The items of the model, sorted by name.
+    pub items: Vec<ReachableItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachableItem {
+    /// The name of the function or static.
+    pub name: String,
+    /// The crate where the item is defined.
+    #[serde(rename = "crate")]
+    pub krate: String,
+    /// Whether the body of the function was replaced by a stub.
+    pub stubbed: bool,
+    /// The items that this item uses, sorted by name.
+    pub uses: Vec<String>,
+}
+
+impl ReachabilityReport {
+    /// The items reachable from the given harness, in the order of the report.
+    pub fn reachable_from(&self, harness: &str) -> Vec<&ReachableItem> {
+        let reachable: HashSet<&str> =
+            self.parents(harness).iter().map(|(item, _)| item.name.as_str()).collect();
+        self.items.iter().filter(|item| reachable.contains(item.name.as_str())).collect()
+    }
+
+    /// One of the shortest paths from the harness to an item that matches `is_target`, starting
+    /// with the harness, or `None` if no such item is reachable from it.
+    pub fn call_path(
+        &self,
+        harness: &str,
+        is_target: impl Fn(&ReachableItem) -> bool,
+    ) -> Option<Vec<&ReachableItem>> {
+        let parents = self.parents(harness);
+        // The parents are listed in breadth-first order, so the first target is the closest.
+        let mut target = parents.iter().find(|entry| is_target(entry.0))?.0;
+        let mut path = vec![target];
+        while let Some(parent) = parents.iter().find(|entry| entry.0.name == target.name)?.1 {
+            target = parent;
+            path.push(target);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// List each item reachable from the harness, in breadth-first order, with the item it was
+    /// first reached from. The harness has no parent.
+    fn parents(&self, harness: &str) -> Vec<(&ReachableItem, Option<&ReachableItem>)> {
+        let by_name: HashMap<&str, &ReachableItem> =
+            self.items.iter().map(|item| (item.name.as_str(), item)).collect();
+        let Some(root) = by_name.get(harness) else { return vec![] };
+        let mut parents = vec![(*root, None)];
+        let mut visited = HashSet::from([harness]);
+        let mut next = 0;
+        while let Some((item, _)) = parents.get(next).copied() {
+            next += 1;
+            for used in &item.uses {
+                if let Some(used) = by_name.get(used.as_str()) {
+                    if visited.insert(used.name.as_str()) {
+                        parents.push((*used, Some(item)));
+                    }
+                }
+            }
+        }
+        parents
+    }
+}
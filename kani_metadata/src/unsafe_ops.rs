@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Data structures to represent the unsafe operations reachable from a harness.
+
+use serde::{Deserialize, Serialize};
+
+/// An unsafe operation in the body of a function.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UnsafeOperation {
+    /// The file of the operation.
+    pub file: String,
+    /// The line of the operation.
+    pub line: usize,
+    pub kind: UnsafeOperationKind,
+    /// The function that contains the operation.
+    pub function: String,
+    /// What the operation applies to, e.g., the type of the pointer or the called function.
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsafeOperationKind {
+    /// The dereference of a raw pointer.
+    RawPointerDeref,
+    /// A read or write of a union field.
+    UnionAccess,
+    /// A transmute from one type to another.
+    Transmute,
+    /// A call to an unsafe function.
+    UnsafeCall,
+}
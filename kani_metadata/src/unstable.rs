@@ -92,6 +92,22 @@ pub enum UnstableFeature {
     GhostState,
     /// Automatically check that uninitialized memory is not used.
     UninitChecks,
+    /// Automatically check that union fields aren't read with an invalid value because another
+    /// field was written last.
+    UnionChecks,
+    /// Automatically check that integers aren't cast into pointers and that pointer arithmetic
+    /// stays within its allocation, following the strict provenance rules.
+    ProvenanceChecks,
+    /// Automatically check that values marked with `kani::secret` don't flow into a
+    /// `kani::public_sink`.
+    TaintChecks,
+    /// Automatically check that references aren't used after they were invalidated by an aliasing
+    /// access, following a simplified version of Stacked Borrows.
+    AliasingChecks,
+    /// Remove the assignments that cannot affect any check before codegen.
+    MirSlicing,
+    /// Automatically check that the assumptions of each harness can be satisfied.
+    AssumptionChecks,
     /// Enable an unstable option or subcommand.
     UnstableOptions,
 }
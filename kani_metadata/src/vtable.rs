@@ -1,7 +1,8 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-//! Data structures to represent vtable trait function pointer restrictions
+//! Data structures to represent vtable trait function pointer restrictions, and the restrictions
+//! of plain function pointer calls.
 
 // TODO: We currently use `InternedString`, but possibly should only use `String`
 pub use cbmc::InternedString;
@@ -47,6 +48,30 @@ pub struct PossibleMethodEntry {
     pub possibilities: Vec<InternedString>,
 }
 
+/// A call through a plain function pointer, e.g., `let f: fn(u8) -> u8 = ...; f(x)`.
+/// This is identified like a [CallSite], and the pointer may only point to the functions whose
+/// address is taken with the same signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FnPtrCallSite {
+    /// The canonical name of the function pointer type being called (see function
+    /// `fn_ptr_signature` in the Kani compiler).
+    pub signature: InternedString,
+    /// The (mangled symbol name of the) function this code is within
+    pub function_name: InternedString,
+    /// The unique label we applied to this function invocation.
+    pub label: InternedString,
+}
+
+/// The functions whose address is taken as a function pointer of a given signature, e.g., by a
+/// cast `foo as fn(u8) -> u8` or by a constant that contains a function pointer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressTakenEntry {
+    /// The canonical name of the function pointer type.
+    pub signature: InternedString,
+    /// The (mangled symbol name of the) functions whose address is taken with this signature.
+    pub functions: Vec<InternedString>,
+}
+
 /// Represents the full set of vtable restrictions visible in this crate.
 /// Currently corresponds to a `*.restrictions.json` file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,4 +82,12 @@ pub struct VtableCtxResults {
     /// When all of these are aggregated together from all linked crates, these collectively represent
     /// the only function pointers that might exist in this vtable entry.
     pub possible_methods: Vec<PossibleMethodEntry>,
+    /// Each call through a plain function pointer that is visible in this crate. These are only
+    /// collected with `--restrict-fn-ptrs`.
+    #[serde(default)]
+    pub fn_ptr_call_sites: Vec<FnPtrCallSite>,
+    /// The functions whose address is taken in this crate, per signature. When aggregated from
+    /// all linked crates, these are the only targets of a function pointer call.
+    #[serde(default)]
+    pub address_taken: Vec<AddressTakenEntry>,
 }
@@ -5,8 +5,9 @@
 //! primitive types and other std containers.
 
 use crate::Arbitrary;
+use alloc::boxed::Box;
 
-impl<T> Arbitrary for std::boxed::Box<T>
+impl<T> Arbitrary for Box<T>
 where
     T: Arbitrary,
 {
@@ -12,8 +12,23 @@
     static CONCRETE_VALS: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
 }
 
+/// Environment variable used to override the concrete values of a playback test. This is used by
+/// Kani to minimize counterexamples. The values are encoded as hexadecimal strings separated
+/// by `;`.
+const VALUES_ENV_VAR: &str = "KANI_PLAYBACK_VALUES";
+
+/// When this environment variable is set, the concrete values of the playback test are printed
+/// to the standard error, using the same encoding as `KANI_PLAYBACK_VALUES`.
+const PRINT_VALUES_ENV_VAR: &str = "KANI_PLAYBACK_PRINT_VALUES";
+
 /// This function sets concrete values and plays back the user's proof harness.
 pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, proof_harness: F) {
+    if let Ok(values) = std::env::var(VALUES_ENV_VAR) {
+        local_concrete_vals = decode_values(&values);
+    }
+    if std::env::var_os(PRINT_VALUES_ENV_VAR).is_some() {
+        eprintln!("{VALUES_ENV_VAR}={}", encode_values(&local_concrete_vals));
+    }
     // Det vals in the user test case should be in the same order as the order of kani::any() calls.
     // Here, we need to reverse this order because det vals are popped off of the outer Vec,
     // so the chronological first det val should come last.
@@ -40,6 +55,34 @@ pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, pro
     });
 }
 
+/// Encode the concrete values as hexadecimal strings separated by `;`.
+fn encode_values(values: &[Vec<u8>]) -> String {
+    let encoded: Vec<String> = values
+        .iter()
+        .map(|value| value.iter().map(|byte| format!("{byte:02x}")).collect())
+        .collect();
+    encoded.join(";")
+}
+
+/// Decode the concrete values from the format used by `KANI_PLAYBACK_VALUES`.
+fn decode_values(encoded: &str) -> Vec<Vec<u8>> {
+    if encoded.is_empty() {
+        return vec![];
+    }
+    encoded
+        .split(';')
+        .map(|value| {
+            (0..value.len())
+                .step_by(2)
+                .map(|idx| {
+                    u8::from_str_radix(&value[idx..idx + 2], 16)
+                        .expect("Invalid concrete value in `KANI_PLAYBACK_VALUES`")
+                })
+                .collect()
+        })
+        .collect()
+}
+
 /// Iterate over `any_raw_internal` since CBMC produces assignment per element.
 pub(crate) unsafe fn any_raw_array<T: Copy, const N: usize>() -> [T; N] {
     [(); N].map(|_| crate::any_raw_internal::<T>())
@@ -3,6 +3,7 @@
 
 //! This module contains functions to work with futures (and async/.await) in Kani.
 
+use alloc::{boxed::Box, vec::Vec};
 use std::{
     future::Future,
     pin::Pin,
@@ -19,17 +19,26 @@
 #![feature(ptr_metadata)]
 #![feature(f16)]
 #![feature(f128)]
+// Targets without an operating system, e.g. `thumbv7em-none-eabihf`, only have `core` and `alloc`.
+#![cfg_attr(feature = "no_std", no_std)]
 
 // Allow us to use `kani::` to access crate features.
 extern crate self as kani;
 
+extern crate alloc;
+// The Kani API refers to `std` paths, which are all available in `core` too.
+#[cfg(feature = "no_std")]
+extern crate core as std;
+
 pub mod arbitrary;
 #[cfg(feature = "concrete_playback")]
 mod concrete_playback;
 pub mod futures;
 pub mod invariant;
+pub mod mmio;
 pub mod shadow;
 pub mod slice;
+pub mod taint;
 pub mod vec;
 
 mod models;
@@ -37,10 +46,11 @@
 #[cfg(feature = "concrete_playback")]
 pub use concrete_playback::concrete_playback_run;
 pub use invariant::Invariant;
+pub use taint::{public_sink, secret};
 
 #[cfg(not(feature = "concrete_playback"))]
 /// NOP `concrete_playback` for type checking during verification mode.
-pub fn concrete_playback_run<F: Fn()>(_: Vec<Vec<u8>>, _: F) {
+pub fn concrete_playback_run<F: Fn()>(_: alloc::vec::Vec<alloc::vec::Vec<u8>>, _: F) {
     unreachable!("Concrete playback does not work during verification")
 }
 
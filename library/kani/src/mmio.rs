@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains functions to model memory-mapped I/O (MMIO) in embedded code.
+//!
+//! Device registers live at fixed addresses that don't point to any object Kani knows about, so
+//! dereferencing them, e.g., with `core::ptr::read_volatile`, fails the pointer checks. Besides,
+//! the hardware may change the value of a register at any time. Code that accesses registers can
+//! use these functions instead when compiled with `cfg(kani)`, so each read returns any value.
+//!
+//! # Example:
+//!
+//! ```rust
+//! const STATUS: *const u32 = 0x4000_0000 as *const u32;
+//!
+//! fn is_ready() -> bool {
+//!     #[cfg(kani)]
+//!     let status = kani::mmio::any_read(STATUS);
+//!     #[cfg(not(kani))]
+//!     let status = unsafe { core::ptr::read_volatile(STATUS) };
+//!     status & 1 == 1
+//! }
+//! ```
+use crate::{any, Arbitrary};
+
+/// Model a volatile read of the register at `addr`, which may return any value of type `T`.
+///
+/// The address itself is not dereferenced, but it must be properly aligned for `T`.
+pub fn any_read<T: Arbitrary>(addr: *const T) -> T {
+    assert!(addr.is_aligned(), "MMIO read from a misaligned address");
+    any()
+}
+
+/// Model a volatile write of `value` to the register at `addr`. The value is discarded.
+///
+/// The address itself is not dereferenced, but it must be properly aligned for `T`.
+pub fn write<T>(addr: *mut T, value: T) {
+    assert!(addr.is_aligned(), "MMIO write to a misaligned address");
+    drop(value);
+}
@@ -0,0 +1,122 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains the markers of the taint checks enabled with `-Z taint-checks`.
+//!
+//! A value returned by [`secret`] is tainted, and so is every value computed from it, every
+//! pointer to it, and every object it is written to. Kani reports a failure if a tainted value
+//! reaches [`public_sink`] on any path.
+//!
+//! # Example
+//!
+//! ```
+//! fn log_attempt(log: &mut Vec<u8>, user: u8, password: u8) {
+//!     log.push(user);
+//!     log.push(password); // Oops.
+//! }
+//!
+//! #[kani::proof]
+//! fn check_log() {
+//!     let mut log = vec![];
+//!     log_attempt(&mut log, kani::any(), kani::secret(kani::any()));
+//!     kani::public_sink(log.as_slice());
+//! }
+//! ```
+//!
+//! Only explicit flows are tracked: a value that is chosen by a branch on a secret isn't tainted.
+//!
+//! The remaining definitions of this module are used by the instrumentation and are not meant to
+//! be visible to the end user.
+
+// The definitions used by the instrumentation are only called by the code that Kani generates.
+#![allow(dead_code)]
+
+const MAX_NUM_OBJECTS: usize = 1024;
+const MAX_NUM_ARGUMENTS: usize = 8;
+
+const MAX_NUM_OBJECTS_ASSERT_MSG: &str =
+    "The number of objects exceeds the maximum number supported by Kani's taint checks (1024)";
+
+/// Whether each object was written a tainted value.
+static mut TAINTED_OBJECTS: [bool; MAX_NUM_OBJECTS] = [false; MAX_NUM_OBJECTS];
+
+/// Whether each argument of the function being called is tainted. The arguments past the last
+/// entry share it.
+static mut TAINTED_ARGUMENTS: [bool; MAX_NUM_ARGUMENTS] = [false; MAX_NUM_ARGUMENTS];
+
+/// Whether the value returned by the last function call is tainted.
+static mut TAINTED_RETURN: bool = false;
+
+/// Mark `value` as secret. The value returned, and everything computed from it, must not reach a
+/// [`public_sink`].
+#[crate::unstable(feature = "taint-checks", issue = 3171, reason = "experimental taint checks")]
+#[rustc_diagnostic_item = "KaniSecret"]
+#[inline(never)]
+pub fn secret<T>(value: T) -> T {
+    value
+}
+
+/// Check that neither `value` nor the memory it points to is derived from a [`secret`].
+///
+/// To check the contents of a buffer, pass the buffer as a slice, e.g., `log.as_slice()`.
+#[crate::unstable(feature = "taint-checks", issue = 3171, reason = "experimental taint checks")]
+#[rustc_diagnostic_item = "KaniPublicSink"]
+#[inline(never)]
+pub fn public_sink<T: ?Sized>(_value: &T) {}
+
+/// Record that a value with the given taint was written to the object at `ptr`.
+#[kanitool::disable_checks(pointer)]
+#[rustc_diagnostic_item = "KaniTaintObject"]
+fn taint_object<T: ?Sized>(ptr: *const T, tainted: bool) {
+    if tainted {
+        let obj = crate::mem::pointer_object(ptr);
+        crate::assert(obj < MAX_NUM_OBJECTS, MAX_NUM_OBJECTS_ASSERT_MSG);
+        unsafe { TAINTED_OBJECTS[obj] = true };
+    }
+}
+
+/// Return whether a tainted value was written to the object at `ptr`.
+#[kanitool::disable_checks(pointer)]
+#[rustc_diagnostic_item = "KaniIsObjectTainted"]
+fn is_object_tainted<T: ?Sized>(ptr: *const T) -> bool {
+    let obj = crate::mem::pointer_object(ptr);
+    crate::assert(obj < MAX_NUM_OBJECTS, MAX_NUM_OBJECTS_ASSERT_MSG);
+    unsafe { TAINTED_OBJECTS[obj] }
+}
+
+/// Set the taint of the argument `idx` of the function about to be called.
+#[rustc_diagnostic_item = "KaniSetArgumentTaint"]
+fn set_argument_taint(idx: usize, tainted: bool) {
+    unsafe {
+        if idx < MAX_NUM_ARGUMENTS {
+            TAINTED_ARGUMENTS[idx] = tainted;
+        } else {
+            TAINTED_ARGUMENTS[MAX_NUM_ARGUMENTS - 1] |= tainted;
+        }
+    }
+}
+
+/// Return the taint of the argument `idx` of the current function.
+#[rustc_diagnostic_item = "KaniArgumentTaint"]
+fn argument_taint(idx: usize) -> bool {
+    unsafe { TAINTED_ARGUMENTS[idx.min(MAX_NUM_ARGUMENTS - 1)] }
+}
+
+/// Set the taint of the value returned by the current function.
+#[rustc_diagnostic_item = "KaniSetReturnTaint"]
+fn set_return_taint(tainted: bool) {
+    unsafe { TAINTED_RETURN = tainted };
+}
+
+/// Return the taint of the value returned by the last function call.
+#[rustc_diagnostic_item = "KaniReturnTaint"]
+fn return_taint() -> bool {
+    unsafe { TAINTED_RETURN }
+}
+
+/// Check that `cond` holds, which is reported as a `constant_time` property.
+#[rustc_diagnostic_item = "KaniConstantTimeCheck"]
+#[inline(never)]
+fn constant_time_check(cond: bool, msg: &'static str) {
+    let _ = (cond, msg);
+}
@@ -0,0 +1,202 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides instrumentation for checking that references aren't used after an
+//! aliasing access invalidated them, following a simplified Stacked Borrows model.
+//!
+//! The compiler gives a tag to every reference and raw pointer local, and inserts calls to
+//! `aliasing_retag` where they are created and to `aliasing_access` where memory is accessed
+//! through them. Tags cross function boundaries through the argument and return registers.
+//!
+//! Note that for each harness, the tracked byte is chosen non-deterministically, so calls to
+//! `aliasing_access` should be only used in assertion contexts.
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#![allow(dead_code)]
+
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! kani_aliasing {
+    ($core:path) => {
+        /// The tag of the pointers whose origin isn't known, e.g., because they were loaded from
+        /// memory. The accesses through them aren't checked.
+        const UNKNOWN_TAG: u64 = 0;
+        /// The tag of the accesses to a local through its name.
+        const OWNER_TAG: u64 = 1;
+        /// The tag shared by the raw pointers, which may be used interchangeably.
+        const RAW_TAG: u64 = 2;
+        /// The first tag given to a mutable reference.
+        const FIRST_UNIQUE_TAG: u64 = 3;
+
+        const MAX_BORROW_STACK_SIZE: usize = 8;
+        const MAX_NUM_TAGGED_ARGUMENTS: usize = 8;
+
+        /// Global object for tracking the borrow stack of a non-deterministically chosen byte.
+        static mut BORROW_STACK: BorrowStack = BorrowStack::new();
+
+        /// The tag given to the next mutable reference.
+        static mut NEXT_UNIQUE_TAG: u64 = FIRST_UNIQUE_TAG;
+
+        /// The tags of the pointer arguments of the function being called.
+        static mut ARGUMENT_TAGS: [u64; MAX_NUM_TAGGED_ARGUMENTS] =
+            [UNKNOWN_TAG; MAX_NUM_TAGGED_ARGUMENTS];
+
+        /// The tag of the pointer returned by the last function call.
+        static mut RETURN_TAG: u64 = UNKNOWN_TAG;
+
+        /// A simplified Stacked Borrows stack of a non-deterministically chosen byte, which is
+        /// identified by its object and offset. The stack holds the tags of the pointers that may
+        /// access the byte, with the owner at the bottom.
+        ///
+        /// An access through a tag removes the mutable references above it, and a write also
+        /// removes the raw pointers above it. An access through a tag that isn't in the stack is
+        /// undefined behavior.
+        struct BorrowStack {
+            pub tracked_object_id: usize,
+            pub tracked_offset: usize,
+            /// Whether the stack is tracked, which stops when it overflows.
+            pub tracked: bool,
+            pub items: [u64; MAX_BORROW_STACK_SIZE],
+            pub len: usize,
+        }
+
+        impl BorrowStack {
+            /// This is a dummy initialization function -- the values will be eventually overwritten by a
+            /// call to `initialize_borrow_stack`.
+            pub const fn new() -> Self {
+                Self {
+                    tracked_object_id: 0,
+                    tracked_offset: 0,
+                    tracked: false,
+                    items: [UNKNOWN_TAG; MAX_BORROW_STACK_SIZE],
+                    len: 0,
+                }
+            }
+
+            /// Return whether the `size` bytes at `ptr` include the tracked byte.
+            #[kanitool::disable_checks(pointer)]
+            pub fn covers(&self, ptr: *const u8, size: usize) -> bool {
+                let offset = super::mem::pointer_offset(ptr);
+                self.tracked
+                    && self.tracked_object_id == super::mem::pointer_object(ptr)
+                    && self.tracked_offset >= offset
+                    && self.tracked_offset - offset < size
+            }
+
+            /// Perform an access through `tag` and return whether the tag is in the stack.
+            pub fn access(&mut self, tag: u64, write: bool) -> bool {
+                let mut granting = None;
+                for idx in 0..self.len {
+                    if self.items[idx] == tag {
+                        granting = Some(idx);
+                    }
+                }
+                let Some(granting) = granting else { return false };
+                if write {
+                    self.len = granting + 1;
+                } else {
+                    // A read keeps the raw pointers above the tag.
+                    let mut len = granting + 1;
+                    for idx in granting + 1..self.len {
+                        if self.items[idx] == RAW_TAG {
+                            self.items[len] = RAW_TAG;
+                            len += 1;
+                        }
+                    }
+                    self.len = len;
+                }
+                true
+            }
+
+            pub fn push(&mut self, tag: u64) {
+                if self.len < MAX_BORROW_STACK_SIZE {
+                    self.items[self.len] = tag;
+                    self.len += 1;
+                } else {
+                    self.tracked = false;
+                }
+            }
+        }
+
+        /// Set the tracked byte to a non-deterministic one, owned by its object.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniInitializeBorrowStack"]
+        fn initialize_borrow_stack() {
+            unsafe {
+                BORROW_STACK.tracked_object_id = super::any();
+                BORROW_STACK.tracked_offset = super::any();
+                BORROW_STACK.tracked = true;
+                BORROW_STACK.items[0] = OWNER_TAG;
+                BORROW_STACK.len = 1;
+            }
+        }
+
+        /// Perform an access of `size` bytes at `ptr` through `tag`, and return whether it is
+        /// allowed.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniAliasingAccess"]
+        fn aliasing_access<T>(ptr: *const T, size: usize, tag: u64, write: bool) -> bool {
+            unsafe {
+                tag == UNKNOWN_TAG
+                    || !BORROW_STACK.covers(ptr as *const u8, size)
+                    || BORROW_STACK.access(tag, write)
+            }
+        }
+
+        /// Return the tag of a new pointer to the `size` bytes at `ptr` derived from a pointer
+        /// with the tag `parent`. The new pointer is a mutable reference if `unique` is set, and
+        /// a raw pointer otherwise.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniAliasingRetag"]
+        fn aliasing_retag<T>(ptr: *const T, size: usize, parent: u64, unique: bool) -> u64 {
+            if parent == UNKNOWN_TAG {
+                return UNKNOWN_TAG;
+            }
+            unsafe {
+                let tag = if unique {
+                    NEXT_UNIQUE_TAG += 1;
+                    NEXT_UNIQUE_TAG - 1
+                } else {
+                    RAW_TAG
+                };
+                if BORROW_STACK.covers(ptr as *const u8, size) {
+                    BORROW_STACK.push(tag);
+                }
+                tag
+            }
+        }
+
+        /// Set the tag of the pointer argument `idx` of the function about to be called.
+        #[rustc_diagnostic_item = "KaniSetArgumentTag"]
+        fn set_argument_tag(idx: usize, tag: u64) {
+            if idx < MAX_NUM_TAGGED_ARGUMENTS {
+                unsafe { ARGUMENT_TAGS[idx] = tag };
+            }
+        }
+
+        /// Return the tag of the pointer argument `idx` of the current function.
+        #[rustc_diagnostic_item = "KaniArgumentTag"]
+        fn argument_tag(idx: usize) -> u64 {
+            if idx < MAX_NUM_TAGGED_ARGUMENTS { unsafe { ARGUMENT_TAGS[idx] } } else { UNKNOWN_TAG }
+        }
+
+        /// Forget the tags of the arguments, so they are not read by a function that is called
+        /// without setting them, e.g., by the standard library.
+        #[rustc_diagnostic_item = "KaniResetArgumentTags"]
+        fn reset_argument_tags() {
+            unsafe { ARGUMENT_TAGS = [UNKNOWN_TAG; MAX_NUM_TAGGED_ARGUMENTS] };
+        }
+
+        /// Set the tag of the pointer returned by the current function.
+        #[rustc_diagnostic_item = "KaniSetReturnTag"]
+        fn set_return_tag(tag: u64) {
+            unsafe { RETURN_TAG = tag };
+        }
+
+        /// Return the tag of the pointer returned by the last function call.
+        #[rustc_diagnostic_item = "KaniReturnTag"]
+        fn return_tag() -> u64 {
+            unsafe { RETURN_TAG }
+        }
+    };
+}
@@ -20,6 +20,7 @@
 #![feature(f16)]
 #![feature(f128)]
 
+mod aliasing;
 mod arbitrary;
 mod mem;
 mod mem_init;
@@ -50,6 +51,10 @@ pub mod mem {
             mod mem_init {
                 kani_core::kani_mem_init!(core);
             }
+
+            mod aliasing {
+                kani_core::kani_aliasing!(core);
+            }
         }
     };
 
@@ -65,6 +70,10 @@ pub mod mem {
         mod mem_init {
             kani_core::kani_mem_init!(std);
         }
+
+        mod aliasing {
+            kani_core::kani_aliasing!(std);
+        }
     };
 }
 
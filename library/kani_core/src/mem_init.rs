@@ -368,7 +368,8 @@ fn copy_init_state_single<const LAYOUT_SIZE: usize, T>(from: *const T, to: *cons
 
         /// Information about currently tracked argument, used for passing union initialization
         /// state across function boundaries. This struct is written to by the caller and read from
-        /// by the callee.
+        /// by the callee, except for returned unions, which use the argument number 0 and are
+        /// written to by the callee and read from by the caller.
         #[derive(Clone, Copy)]
         struct ArgumentBuffer {
             selected_argument: usize,
@@ -416,5 +417,83 @@ fn load_argument<const LAYOUT_SIZE: usize, T>(to: *const T, selected_argument: u
                 MEM_INIT_STATE.bless::<LAYOUT_SIZE>(to_ptr as *const u8, 1);
             }
         }
+
+        /// Global object for tracking the active field of unions, i.e., the field last written.
+        static mut UNION_FIELD_STATE: UnionFieldState = UnionFieldState::new();
+
+        /// The active field of a non-deterministically chosen union, which is identified by the
+        /// object and the offset of its first byte. `None` means that the active field is unknown,
+        /// e.g., because the union was written through a raw pointer to its bytes.
+        struct UnionFieldState {
+            pub tracked_object_id: usize,
+            pub tracked_offset: usize,
+            pub field: Option<usize>,
+        }
+
+        impl UnionFieldState {
+            /// This is a dummy initialization function -- the values will be eventually overwritten by a
+            /// call to `initialize_union_field_state`.
+            pub const fn new() -> Self {
+                Self { tracked_object_id: 0, tracked_offset: 0, field: None }
+            }
+
+            /// Return whether `ptr` points to the currently tracked union.
+            #[kanitool::disable_checks(pointer)]
+            pub fn is_tracked(&self, ptr: *const u8) -> bool {
+                self.tracked_object_id == super::mem::pointer_object(ptr)
+                    && self.tracked_offset == super::mem::pointer_offset(ptr)
+            }
+        }
+
+        /// Set the tracked union to a non-deterministic one.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniInitializeUnionFieldState"]
+        fn initialize_union_field_state() {
+            unsafe {
+                UNION_FIELD_STATE.tracked_object_id = super::any();
+                UNION_FIELD_STATE.tracked_offset = super::any();
+                UNION_FIELD_STATE.field = None;
+            }
+        }
+
+        /// Record that `field` is the active field of the union at `ptr`.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniSetUnionField"]
+        fn set_union_field<T>(ptr: *const T, field: usize) {
+            unsafe {
+                if UNION_FIELD_STATE.is_tracked(ptr as *const u8) {
+                    UNION_FIELD_STATE.field = Some(field);
+                }
+            }
+        }
+
+        /// Return whether `field` may be the active field of the union at `ptr`, i.e., whether the
+        /// union is not tracked, or its active field is unknown or is `field`.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniIsUnionFieldActive"]
+        fn is_union_field_active<T>(ptr: *const T, field: usize) -> bool {
+            unsafe {
+                !UNION_FIELD_STATE.is_tracked(ptr as *const u8)
+                    || UNION_FIELD_STATE.field.map_or(true, |active| active == field)
+            }
+        }
+
+        /// Copy the active field of the union at `from` to the union at `to` by non-deterministically
+        /// switching the tracked union.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniCopyUnionField"]
+        fn copy_union_field<T>(from: *const T, to: *const T) {
+            let (from, to) = (from as *const u8, to as *const u8);
+            unsafe {
+                if UNION_FIELD_STATE.is_tracked(from) {
+                    if super::any() {
+                        UNION_FIELD_STATE.tracked_object_id = super::mem::pointer_object(to);
+                        UNION_FIELD_STATE.tracked_offset = super::mem::pointer_offset(to);
+                    }
+                } else if UNION_FIELD_STATE.is_tracked(to) {
+                    UNION_FIELD_STATE.field = None;
+                }
+            }
+        }
     };
 }
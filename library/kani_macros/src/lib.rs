@@ -51,6 +51,29 @@ pub fn should_panic(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::should_panic(attr, item)
 }
 
+/// Specifies that a proof harness must free all the heap allocations it makes.**
+///
+/// The attribute `#[kani::check_leaks]` can only be used alongside `#[kani::proof]`. Kani reports
+/// a failure if some memory allocated by the harness is never freed, e.g., because of a
+/// `Box::leak` or a reference cycle of `Rc`s.
+#[proc_macro_attribute]
+pub fn check_leaks(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::check_leaks(attr, item)
+}
+
+/// Specifies that a proof harness starts from an arbitrary state of the mutable statics.**
+///
+/// The attribute `#[kani::havoc_statics]` can only be used alongside `#[kani::proof]`. By default,
+/// a harness starts with the statics of a fresh process. With this attribute, the `static mut`
+/// items and the statics with interior mutability (e.g., an `AtomicUsize` counter) of the crates
+/// under verification hold any value instead, which models a harness that runs after arbitrary
+/// code. Statics that are lazily initialized, e.g., a `OnceLock` or a `lazy_static!`, start
+/// uninitialized, so their initializer runs as in a fresh process.
+#[proc_macro_attribute]
+pub fn havoc_statics(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::havoc_statics(attr, item)
+}
+
 /// Specifies that a function contains recursion for contract instrumentation.**
 ///
 /// This attribute is only used for function-contract instrumentation. Kani uses
@@ -64,12 +87,97 @@ pub fn recursion(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Set Loop unwind limit for proof harnesses
 /// The attribute `#[kani::unwind(arg)]` can only be called alongside `#[kani::proof]`.
 /// arg - Takes in a integer value (u32) that represents the unwind value for the harness.
+/// Alternatively, `loops = {"path::to::fn::0": 16, "*": 2}` sets the unwind value of individual
+/// loops, identified by the path of their function and their index in it. `"*"` applies to the
+/// other loops.
 #[allow(clippy::too_long_first_doc_paragraph)]
 #[proc_macro_attribute]
 pub fn unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::unwind(attr, item)
 }
 
+/// Turn the arithmetic overflow checks of a function on or off.
+///
+/// The attribute `#[kani::overflow_checks(off)]` removes the overflow checks of the addition,
+/// subtraction, multiplication and negation operations of the annotated function, e.g., of a hash
+/// function that relies on wrapping arithmetic. The operations wrap around instead, and the other
+/// functions of the harness keep their checks.
+#[proc_macro_attribute]
+pub fn overflow_checks(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::overflow_checks(attr, item)
+}
+
+/// Bound the number of nested calls of a recursive function.
+///
+/// The attribute `#[kani::recursion_bound(N)]` makes Kani stop exploring the recursion of the
+/// annotated function after `N` nested calls, and report a failed check
+/// `recursion bound exceeded` if more nested calls are possible. Unlike `#[kani::unwind]`, the
+/// bound only applies to this function, and can be used on any function, not only on harnesses.
+///
+/// Note that the calls of all the instantiations of a generic function are counted together.
+#[proc_macro_attribute]
+pub fn recursion_bound(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::recursion_bound(attr, item)
+}
+
+/// Set a verification time limit for proof harnesses
+/// The attribute `#[kani::timeout(arg)]` can only be called alongside `#[kani::proof]`.
+/// arg - Takes in a integer value (u64) that represents the timeout in seconds for the harness.
+#[allow(clippy::too_long_first_doc_paragraph)]
+#[proc_macro_attribute]
+pub fn timeout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::timeout(attr, item)
+}
+
+/// Set a memory limit for proof harnesses
+/// The attribute `#[kani::memory_limit(arg)]` can only be called alongside `#[kani::proof]`.
+/// arg - Takes in a integer value (u64) that represents the memory limit in MiB for the harness.
+#[allow(clippy::too_long_first_doc_paragraph)]
+#[proc_macro_attribute]
+pub fn memory_limit(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::memory_limit(attr, item)
+}
+
+/// Pass additional arguments to CBMC when verifying a proof harness.
+///
+/// The attribute `#[kani::cbmc_args("--arg", ...)]` can only be used alongside `#[kani::proof]`.
+/// Each argument must be a string literal, e.g., `#[kani::cbmc_args("--refine-arrays")]`.
+#[proc_macro_attribute]
+pub fn cbmc_args(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::cbmc_args(attr, item)
+}
+
+/// Turn classes of automatic checks on or off for a proof harness, overriding the command line
+/// options such as `--no-overflow-checks`.
+///
+/// The attribute `#[kani::checks(<check> = on|off, ...)]` can only be used alongside
+/// `#[kani::proof]`. The supported checks are `memory_safety`, `overflow` and `unwinding`, e.g.,
+/// `#[kani::checks(overflow = off, unwinding = on)]`.
+#[proc_macro_attribute]
+pub fn checks(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::checks(attr, item)
+}
+
+/// Turn slicing passes on or off for a proof harness, overriding the command line options such
+/// as `--full-slice`.
+///
+/// The attribute `#[kani::slicing(<pass> = on|off, ...)]` can only be used alongside
+/// `#[kani::proof]`. The supported passes are `formula`, `constant_propagation` and `full`, e.g.,
+/// `#[kani::slicing(full = on)]`.
+#[proc_macro_attribute]
+pub fn slicing(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::slicing(attr, item)
+}
+
+/// Attach tags to a proof harness, e.g., to group harnesses in external tools.
+///
+/// The attribute `#[kani::tag("tag", ...)]` can only be used alongside `#[kani::proof]`.
+/// Each tag must be a non-empty string literal, e.g., `#[kani::tag("slow")]`.
+#[proc_macro_attribute]
+pub fn tag(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::tag(attr, item)
+}
+
 /// Specify a function/method stub pair to use for proof harness
 ///
 /// The attribute `#[kani::stub(original, replacement)]` can only be used alongside `#[kani::proof]`.
@@ -82,6 +190,65 @@ pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::stub(attr, item)
 }
 
+/// Register a function as the model of the `asm!` blocks with the given template.
+///
+/// The attribute `#[kani::asm_model("template")]` replaces every reachable `asm!` block whose
+/// template matches the given one, modulo whitespace, by a call to the annotated function.
+/// The model must take the inputs of the block, in order, followed by a mutable reference to each
+/// of its outputs, e.g.:
+///
+/// ```ignore
+/// #[kani::asm_model("lzcnt {0}, {1}")]
+/// fn lzcnt_model(input: u64, output: &mut u64) {
+///     *output = input.leading_zeros() as u64;
+/// }
+/// ```
+///
+/// A single block can be modeled instead with `#[kani::asm_model(location = "file:line")]`, where
+/// `file` is a suffix of the path of the file that contains the block, and `line` is the line
+/// where the block starts. This takes precedence over the models of templates.
+#[proc_macro_attribute]
+pub fn asm_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::asm_model(attr, item)
+}
+
+/// Register a function as the model of a builtin that Kani can't verify.
+///
+/// The attribute `#[kani::builtin_model("symbol")]` replaces every reachable call to the foreign
+/// function with the given symbol, such as a compiler builtin, or to the Rust intrinsic with the
+/// given name, by a call to the annotated function, which must have the same signature, e.g.:
+///
+/// ```ignore
+/// #[kani::builtin_model("__udivti3")]
+/// fn udivti3_model(a: u128, b: u128) -> u128 {
+///     a / b
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn builtin_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::builtin_model(attr, item)
+}
+
+/// Use a static value as the model of the global allocator during verification.
+///
+/// The attribute `#[kani::allocator_model]` must annotate a `static` whose type implements
+/// [`GlobalAlloc`](core::alloc::GlobalAlloc). Every allocation reachable from a harness, including
+/// the ones of the `#[global_allocator]` of the crate, then calls the methods of this value, e.g.:
+///
+/// ```ignore
+/// #[cfg(kani)]
+/// #[kani::allocator_model]
+/// static MODEL: CountingAllocator = CountingAllocator::new();
+/// ```
+///
+/// Without a model, Kani verifies the allocations with its own `malloc` model, and never
+/// executes the implementation of the `#[global_allocator]`.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn allocator_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::allocator_model(attr, item)
+}
+
 /// Select the SAT solver to use with CBMC for this harness
 ///
 /// The attribute `#[kani::solver(arg)]` can only be used alongside `#[kani::proof]`.
@@ -536,10 +703,133 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
 
+    /// Count the nested calls of the function in a static, and check that they don't exceed the
+    /// bound. The assumption that follows the check stops the unwinding of the recursion.
+    pub fn recursion_bound(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let bound = parse_macro_input!(attr as syn::LitInt);
+        let ItemFn { attrs, vis, sig, block } = parse_macro_input!(item as ItemFn);
+        if sig.constness.is_some() || sig.asyncness.is_some() {
+            abort!(
+                sig,
+                "`#[kani::recursion_bound]` cannot be applied to `const` or `async` functions"
+            );
+        }
+        // The return type of the body can't be written if it's opaque, so we let it be inferred.
+        let output = match &sig.output {
+            syn::ReturnType::Type(_, ty) if !matches!(**ty, syn::Type::ImplTrait(_)) => {
+                quote!(-> #ty)
+            }
+            _ => quote!(),
+        };
+        let msg = format!(
+            "recursion bound exceeded: `{}` has more than {} nested calls",
+            sig.ident,
+            bound.base10_digits()
+        );
+        quote!(
+            #(#attrs)*
+            #vis #sig {
+                static mut DEPTH: usize = 0;
+                unsafe { DEPTH += 1 };
+                kani::assert(unsafe { DEPTH } <= #bound, #msg);
+                kani::assume(unsafe { DEPTH } <= #bound);
+                let result = (move || #output #block)();
+                unsafe { DEPTH -= 1 };
+                result
+            }
+        )
+        .into()
+    }
+
+    /// Models are only called from the code that Kani generates, so they look unused.
+    pub fn asm_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let args = proc_macro2::TokenStream::from(attr);
+        let fn_item = parse_macro_input!(item as ItemFn);
+        quote!(
+            #[allow(dead_code)]
+            #[kanitool::asm_model(#args)]
+            #fn_item
+        )
+        .into()
+    }
+
+    /// Models are only called from the code that Kani generates, so they look unused.
+    pub fn builtin_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let args = proc_macro2::TokenStream::from(attr);
+        let fn_item = parse_macro_input!(item as ItemFn);
+        quote!(
+            #[allow(dead_code)]
+            #[kanitool::builtin_model(#args)]
+            #fn_item
+        )
+        .into()
+    }
+
+    /// Register the methods of the annotated static as the models of the allocation functions
+    /// that the global allocator defines, i.e., the models of `__rust_alloc`, `__rust_dealloc`,
+    /// `__rust_realloc` and `__rust_alloc_zeroed`.
+    pub fn allocator_model(attr: TokenStream, item: TokenStream) -> TokenStream {
+        if !attr.is_empty() {
+            abort_call_site!("`#[kani::allocator_model]` doesn't take any arguments");
+        }
+        let static_item = parse_macro_input!(item as syn::ItemStatic);
+        let name = &static_item.ident;
+        quote!(
+            #[allow(dead_code)]
+            #static_item
+
+            const _: () = {
+                use ::core::alloc::{GlobalAlloc, Layout};
+
+                #[allow(dead_code)]
+                #[kanitool::builtin_model("__rust_alloc")]
+                unsafe fn alloc_model(size: usize, align: usize) -> *mut u8 {
+                    GlobalAlloc::alloc(&#name, Layout::from_size_align_unchecked(size, align))
+                }
+
+                #[allow(dead_code)]
+                #[kanitool::builtin_model("__rust_alloc_zeroed")]
+                unsafe fn alloc_zeroed_model(size: usize, align: usize) -> *mut u8 {
+                    let layout = Layout::from_size_align_unchecked(size, align);
+                    GlobalAlloc::alloc_zeroed(&#name, layout)
+                }
+
+                #[allow(dead_code)]
+                #[kanitool::builtin_model("__rust_dealloc")]
+                unsafe fn dealloc_model(ptr: *mut u8, size: usize, align: usize) {
+                    let layout = Layout::from_size_align_unchecked(size, align);
+                    GlobalAlloc::dealloc(&#name, ptr, layout)
+                }
+
+                #[allow(dead_code)]
+                #[kanitool::builtin_model("__rust_realloc")]
+                unsafe fn realloc_model(
+                    ptr: *mut u8,
+                    size: usize,
+                    align: usize,
+                    new_size: usize,
+                ) -> *mut u8 {
+                    let layout = Layout::from_size_align_unchecked(size, align);
+                    GlobalAlloc::realloc(&#name, ptr, layout, new_size)
+                }
+            };
+        )
+        .into()
+    }
+
     kani_attribute!(should_panic, no_args);
+    kani_attribute!(check_leaks, no_args);
+    kani_attribute!(havoc_statics, no_args);
+    kani_attribute!(cbmc_args);
+    kani_attribute!(checks);
+    kani_attribute!(slicing);
+    kani_attribute!(memory_limit);
+    kani_attribute!(overflow_checks);
     kani_attribute!(recursion, no_args);
     kani_attribute!(solver);
     kani_attribute!(stub);
+    kani_attribute!(tag);
+    kani_attribute!(timeout);
     kani_attribute!(unstable);
     kani_attribute!(unwind);
 }
@@ -569,10 +859,44 @@ pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
         result
     }
 
+    /// Add #[allow(dead_code)] to an `asm!` model, since only Kani calls it.
+    pub fn asm_model(_attr: TokenStream, item: TokenStream) -> TokenStream {
+        let mut result = TokenStream::new();
+        result.extend("#[allow(dead_code)]".parse::<TokenStream>().unwrap());
+        result.extend(item);
+        result
+    }
+
+    /// Add #[allow(dead_code)] to a builtin model, since only Kani calls it.
+    pub fn builtin_model(_attr: TokenStream, item: TokenStream) -> TokenStream {
+        let mut result = TokenStream::new();
+        result.extend("#[allow(dead_code)]".parse::<TokenStream>().unwrap());
+        result.extend(item);
+        result
+    }
+
+    /// Add #[allow(dead_code)] to an allocator model, since only Kani uses it.
+    pub fn allocator_model(_attr: TokenStream, item: TokenStream) -> TokenStream {
+        let mut result = TokenStream::new();
+        result.extend("#[allow(dead_code)]".parse::<TokenStream>().unwrap());
+        result.extend(item);
+        result
+    }
+
     no_op!(should_panic);
+    no_op!(check_leaks);
+    no_op!(havoc_statics);
+    no_op!(cbmc_args);
+    no_op!(checks);
+    no_op!(slicing);
+    no_op!(memory_limit);
+    no_op!(overflow_checks);
     no_op!(recursion);
+    no_op!(recursion_bound);
     no_op!(solver);
     no_op!(stub);
+    no_op!(tag);
+    no_op!(timeout);
     no_op!(unstable);
     no_op!(unwind);
     no_op!(requires);
@@ -31,6 +31,7 @@ pub fn proxy(bin: &str) -> Result<()> {
         ArgsResult::ExplicitSetup { use_local_bundle, use_local_toolchain } => {
             setup::setup(use_local_bundle, use_local_toolchain)
         }
+        ArgsResult::SetupTools(versions) => setup::setup_tools(versions),
         ArgsResult::Default => {
             fail_if_in_dev_environment()?;
             if !setup::appears_setup() {
@@ -53,7 +54,13 @@ pub fn proxy(bin: &str) -> Result<()> {
 /// Minimalist argument parsing result type
 #[derive(PartialEq, Eq, Debug)]
 enum ArgsResult {
-    ExplicitSetup { use_local_bundle: Option<OsString>, use_local_toolchain: Option<OsString> },
+    ExplicitSetup {
+        use_local_bundle: Option<OsString>,
+        use_local_toolchain: Option<OsString>,
+    },
+    /// `setup --cbmc-version <VERSION> --cbmc-commit <COMMIT>` and/or
+    /// `setup --kissat-version <VERSION> --kissat-sha256 <SHA256>`.
+    SetupTools(setup::ToolVersions),
     Default,
 }
 
@@ -106,10 +113,37 @@ fn parse_args(args: Vec<OsString>) -> ArgsResult {
         &[_, Some("setup")] | &[_, Some("kani"), Some("setup")] => {
             ArgsResult::ExplicitSetup { use_local_bundle: None, use_local_toolchain: None }
         }
+        [_, Some("setup"), tools @ ..] | [_, Some("kani"), Some("setup"), tools @ ..] => {
+            parse_tool_versions(tools).map_or(ArgsResult::Default, ArgsResult::SetupTools)
+        }
         _ => ArgsResult::Default,
     }
 }
 
+/// Parse the `--cbmc-version`, `--cbmc-commit`, `--kissat-version` and `--kissat-sha256` options
+/// that follow `setup`, in any order. Whether they are consistent is checked by the setup.
+fn parse_tool_versions(args: &[Option<&str>]) -> Option<setup::ToolVersions> {
+    let mut versions = setup::ToolVersions::default();
+    for pair in args.chunks(2) {
+        match pair {
+            [Some("--cbmc-version"), Some(version)] if versions.cbmc.is_none() => {
+                versions.cbmc = Some(version.to_string())
+            }
+            [Some("--cbmc-commit"), Some(commit)] if versions.cbmc_commit.is_none() => {
+                versions.cbmc_commit = Some(commit.to_string())
+            }
+            [Some("--kissat-version"), Some(version)] if versions.kissat.is_none() => {
+                versions.kissat = Some(version.to_string())
+            }
+            [Some("--kissat-sha256"), Some(sha256)] if versions.kissat_sha256.is_none() => {
+                versions.kissat_sha256 = Some(sha256.to_string())
+            }
+            _ => return None,
+        }
+    }
+    (versions != setup::ToolVersions::default()).then_some(versions)
+}
+
 /// In dev environments, this proxy shouldn't be used.
 /// But accidentally using it (with the test suite) can fire off
 /// hundreds of HTTP requests trying to download a non-existent release bundle.
@@ -324,5 +358,73 @@ fn trial(args: &[&str]) -> ArgsResult {
                 ])
             );
         }
+        {
+            let e = ArgsResult::SetupTools(setup::ToolVersions {
+                cbmc: Some("6.1.1".into()),
+                cbmc_commit: Some("COMMIT".into()),
+                kissat: Some("3.1.1".into()),
+                kissat_sha256: Some("SHA256".into()),
+            });
+            assert_eq!(
+                e,
+                trial(&[
+                    "cargo-kani",
+                    "kani",
+                    "setup",
+                    "--cbmc-version",
+                    "6.1.1",
+                    "--cbmc-commit",
+                    "COMMIT",
+                    "--kissat-version",
+                    "3.1.1",
+                    "--kissat-sha256",
+                    "SHA256"
+                ])
+            );
+            assert_eq!(
+                e,
+                trial(&[
+                    "cargo-kani",
+                    "setup",
+                    "--kissat-sha256",
+                    "SHA256",
+                    "--kissat-version",
+                    "3.1.1",
+                    "--cbmc-version",
+                    "6.1.1",
+                    "--cbmc-commit",
+                    "COMMIT"
+                ])
+            );
+            let e = ArgsResult::SetupTools(setup::ToolVersions {
+                cbmc: Some("6.1.1".into()),
+                cbmc_commit: Some("COMMIT".into()),
+                ..Default::default()
+            });
+            assert_eq!(
+                e,
+                trial(&[
+                    "cargo",
+                    "kani",
+                    "setup",
+                    "--cbmc-version",
+                    "6.1.1",
+                    "--cbmc-commit",
+                    "COMMIT"
+                ])
+            );
+            assert_eq!(ArgsResult::Default, trial(&["cargo-kani", "setup", "--cbmc-version"]));
+            assert_eq!(
+                ArgsResult::Default,
+                trial(&[
+                    "cargo-kani",
+                    "setup",
+                    "--cbmc-version",
+                    "6.1.1",
+                    "--cbmc-version",
+                    "6.0.0"
+                ])
+            );
+        }
     }
 }
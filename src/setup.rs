@@ -69,6 +69,168 @@ pub fn appears_incomplete() -> Option<PathBuf> {
     None
 }
 
+/// The versions of the verification tools given to `cargo kani setup`, along with the pins of
+/// their sources: the commit of the CBMC release tag, and the SHA-256 of the Kissat release
+/// archive.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ToolVersions {
+    pub cbmc: Option<String>,
+    pub cbmc_commit: Option<String>,
+    pub kissat: Option<String>,
+    pub kissat_sha256: Option<String>,
+}
+
+/// The directory where specific versions of CBMC and Kissat are installed, shared by all the
+/// versions of Kani: `tools/<tool>-<version>/bin` in the Kani home directory.
+fn tools_dir() -> Result<PathBuf> {
+    let kani_dir = kani_dir()?;
+    Ok(kani_dir.parent().expect("No base directory?").join("tools"))
+}
+
+/// Installs the given versions of CBMC and Kissat, and pins them in the `kani.toml` of the
+/// current package, so `cargo kani` checks that they are the ones in use.
+pub fn setup_tools(versions: ToolVersions) -> Result<()> {
+    let tools_dir = tools_dir()?;
+    match (&versions.cbmc, &versions.cbmc_commit) {
+        (Some(version), Some(commit)) => {
+            install_cbmc(&tools_dir.join(format!("cbmc-{version}")), version, commit)?
+        }
+        (None, None) => {}
+        _ => bail!("`--cbmc-version` and `--cbmc-commit` must be given together"),
+    }
+    match (&versions.kissat, &versions.kissat_sha256) {
+        (Some(version), Some(sha256)) => {
+            install_kissat(&tools_dir.join(format!("kissat-{version}")), version, sha256)?
+        }
+        (None, None) => {}
+        _ => bail!("`--kissat-version` and `--kissat-sha256` must be given together"),
+    }
+
+    let Ok(manifest) = locate_project() else {
+        println!("Not inside a package, so the versions were not pinned in a `kani.toml`.");
+        return Ok(());
+    };
+    let config_path = manifest.with_file_name("kani.toml");
+    let config = match std::fs::read_to_string(&config_path) {
+        Ok(config) => config,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error.into()),
+    };
+    std::fs::write(&config_path, pin_tool_versions(&config, &versions))?;
+    println!("Pinned the versions in {}", config_path.display());
+    Ok(())
+}
+
+/// Builds CBMC `version` from its sources, and installs it into `install_dir`.
+///
+/// The release tag is checked out only if it points to `commit`, since a tag can be moved.
+/// The submodules are pinned by this commit.
+fn install_cbmc(install_dir: &Path, version: &str, commit: &str) -> Result<()> {
+    if commit.len() != 40 || !commit.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        bail!("`{commit}` is not the full hash of a commit");
+    }
+    let cbmc = install_dir.join("bin").join("cbmc");
+    if verify_tool_version(&cbmc, version).is_ok() {
+        println!("CBMC {version} is already installed in {}", install_dir.display());
+        return Ok(());
+    }
+    println!("Building CBMC {version}, this may take a while...");
+    let work_dir = env::temp_dir().join(format!("kani-cbmc-{version}"));
+    if work_dir.exists() {
+        std::fs::remove_dir_all(&work_dir)?;
+    }
+    Command::new("git")
+        .args(["clone", "--depth", "1", "--branch", &format!("cbmc-{version}")])
+        .arg("https://github.com/diffblue/cbmc")
+        .arg(&work_dir)
+        .run()
+        .with_context(|| format!("Failed to download CBMC {version}"))?;
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).current_dir(&work_dir).output()?;
+    let head = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !head.trim().eq_ignore_ascii_case(commit) {
+        std::fs::remove_dir_all(&work_dir)?;
+        bail!("The tag `cbmc-{version}` points to commit `{}` instead of `{commit}`", head.trim());
+    }
+    Command::new("git").args(["submodule", "update", "--init"]).current_dir(&work_dir).run()?;
+    Command::new("cmake")
+        .args(["-S", ".", "-B", "build", "-DWITH_JBMC=OFF", "-Dsat_impl=minisat2;cadical"])
+        .arg(format!("-DCMAKE_INSTALL_PREFIX={}", install_dir.display()))
+        .current_dir(&work_dir)
+        .run()?;
+    Command::new("cmake").args(["--build", "build", "--parallel"]).current_dir(&work_dir).run()?;
+    Command::new("cmake").args(["--install", "build"]).current_dir(&work_dir).run()?;
+    std::fs::remove_dir_all(&work_dir)?;
+    verify_tool_version(&cbmc, version)
+}
+
+/// Builds Kissat `version` from its sources, and installs it into `install_dir`.
+///
+/// The sources are only extracted if the SHA-256 of the release archive is `sha256`.
+fn install_kissat(install_dir: &Path, version: &str, sha256: &str) -> Result<()> {
+    let kissat = install_dir.join("bin").join("kissat");
+    if verify_tool_version(&kissat, version).is_ok() {
+        println!("Kissat {version} is already installed in {}", install_dir.display());
+        return Ok(());
+    }
+    println!("Building Kissat {version}...");
+    let work_dir = env::temp_dir().join(format!("kani-kissat-{version}"));
+    if work_dir.exists() {
+        std::fs::remove_dir_all(&work_dir)?;
+    }
+    std::fs::create_dir_all(&work_dir)?;
+    let archive = work_dir.join("kissat.tar.gz");
+    Command::new("curl")
+        .args(["-sSLf", "-o"])
+        .arg(&archive)
+        .arg(format!("https://github.com/arminbiere/kissat/archive/refs/tags/rel-{version}.tar.gz"))
+        .run()
+        .with_context(|| format!("Failed to download Kissat {version}"))?;
+    let digest = sha256_digest(&archive)?;
+    if !digest.eq_ignore_ascii_case(sha256) {
+        std::fs::remove_dir_all(&work_dir)?;
+        bail!("The SHA-256 of the Kissat {version} archive is `{digest}` instead of `{sha256}`");
+    }
+    Command::new("tar")
+        .args(["--strip-components=1", "-zxf"])
+        .arg(&archive)
+        .current_dir(&work_dir)
+        .run()?;
+    Command::new("./configure").current_dir(&work_dir).run()?;
+    Command::new("make").arg("kissat").current_dir(&work_dir).run()?;
+    std::fs::create_dir_all(install_dir.join("bin"))?;
+    std::fs::copy(work_dir.join("build").join("kissat"), &kissat)?;
+    std::fs::remove_dir_all(&work_dir)?;
+    verify_tool_version(&kissat, version)
+}
+
+/// The SHA-256 of a file, computed with `sha256sum`, or `shasum` on macOS.
+fn sha256_digest(file: &Path) -> Result<String> {
+    let output = Command::new("sha256sum")
+        .arg(file)
+        .output()
+        .or_else(|_| Command::new("shasum").args(["-a", "256"]).arg(file).output())
+        .context("Failed to run `sha256sum` or `shasum`")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.split_whitespace().next() {
+        Some(digest) if output.status.success() => Ok(digest.to_string()),
+        _ => bail!("Failed to compute the SHA-256 of `{}`", file.display()),
+    }
+}
+
+/// Checks that `tool --version` reports `version`, e.g., `6.1.1 (cbmc-6.1.1)` for CBMC.
+fn verify_tool_version(tool: &Path, version: &str) -> Result<()> {
+    let output = Command::new(tool)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("Failed to run `{}`", tool.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let found = stdout.split_whitespace().next().unwrap_or_default();
+    if !output.status.success() || found != version {
+        bail!("`{}` reports version `{found}` instead of `{version}`", tool.display());
+    }
+    Ok(())
+}
+
 /// Sets up Kani by unpacking/installing to `~/.kani/kani-VERSION`
 pub fn setup(
     use_local_bundle: Option<OsString>,
@@ -204,6 +366,52 @@ fn setup_python_deps(kani_dir: &Path) -> Result<()> {
 //
 // Just putting a bit of space between that and the helper functions below.
 
+/// Finds the `Cargo.toml` of the current package.
+fn locate_project() -> Result<PathBuf> {
+    let output =
+        Command::new("cargo").args(["locate-project", "--message-format", "plain"]).output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().into())
+}
+
+/// Sets the `cbmc` and `kissat` entries of the `[tools]` table of a `kani.toml`, keeping the rest
+/// of the file, including its comments, as is.
+fn pin_tool_versions(config: &str, versions: &ToolVersions) -> String {
+    let pins: Vec<(&str, &String)> = [("cbmc", &versions.cbmc), ("kissat", &versions.kissat)]
+        .into_iter()
+        .filter_map(|(tool, version)| version.as_ref().map(|version| (tool, version)))
+        .collect();
+    let pin_line = |tool: &str, version: &str| format!("{tool} = \"{version}\"");
+
+    let mut lines: Vec<String> = config.lines().map(String::from).collect();
+    let Some(header) = lines.iter().position(|line| line.trim() == "[tools]") else {
+        if !lines.is_empty() && !lines.last().unwrap().is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("[tools]".into());
+        lines.extend(pins.iter().map(|(tool, version)| pin_line(tool, version)));
+        return lines.join("\n") + "\n";
+    };
+    let end = lines[header + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| header + 1 + offset);
+    let mut missing = vec![];
+    for (tool, version) in pins {
+        let existing = lines[header + 1..end]
+            .iter()
+            .position(|line| line.split_once('=').is_some_and(|(key, _)| key.trim() == tool));
+        match existing {
+            Some(offset) => lines[header + 1 + offset] = pin_line(tool, version),
+            None => missing.push(pin_line(tool, version)),
+        }
+    }
+    lines.splice(header + 1..header + 1, missing);
+    lines.join("\n") + "\n"
+}
+
 /// The filename of the release bundle
 fn download_filename() -> String {
     format!("kani-{VERSION}-{TARGET}.tar.gz")
@@ -260,3 +468,29 @@ fn symlink_rust_toolchain(toolchain: &Path, kani_dir: &Path) -> Result<()> {
     std::os::unix::fs::symlink(toolchain, path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_pin_tool_versions() {
+        let versions = ToolVersions { cbmc: Some("6.1.1".into()), ..Default::default() };
+        assert_eq!(pin_tool_versions("", &versions), "[tools]\ncbmc = \"6.1.1\"\n");
+        assert_eq!(
+            pin_tool_versions("[flags]\ntests = true\n", &versions),
+            "[flags]\ntests = true\n\n[tools]\ncbmc = \"6.1.1\"\n"
+        );
+        let config =
+            "[tools]\n# Pinned\ncbmc = \"6.0.0\"\nkissat = \"3.1.1\"\n\n[flags]\ncbmc = true\n";
+        assert_eq!(
+            pin_tool_versions(config, &versions),
+            "[tools]\n# Pinned\ncbmc = \"6.1.1\"\nkissat = \"3.1.1\"\n\n[flags]\ncbmc = true\n"
+        );
+        let versions = ToolVersions { kissat: Some("4.0.0".into()), ..Default::default() };
+        assert_eq!(
+            pin_tool_versions("[tools]\ncbmc = \"6.1.1\"\n", &versions),
+            "[tools]\nkissat = \"4.0.0\"\ncbmc = \"6.1.1\"\n"
+        );
+    }
+}
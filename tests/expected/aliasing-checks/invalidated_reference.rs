@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z aliasing-checks
+//! Check that using a mutable reference after an aliasing access invalidated it is reported,
+//! including when the reference is passed to a function.
+
+fn write(target: &mut u32, value: u32) {
+    *target = value;
+}
+
+#[kani::proof]
+fn check_two_mutable_references_fails() {
+    let mut value: u32 = kani::any();
+    let ptr = &mut value as *mut u32;
+    let first = unsafe { &mut *ptr };
+    let second = unsafe { &mut *ptr };
+    *second = 1;
+    *first = 2;
+}
+
+#[kani::proof]
+fn check_write_through_owner_fails() {
+    let mut value: u32 = kani::any();
+    let ptr = &mut value as *mut u32;
+    let reference = unsafe { &mut *ptr };
+    value = 1;
+    write(reference, value);
+}
+
+#[kani::proof]
+fn check_nested_references() {
+    let mut value: u32 = kani::any();
+    let ptr = &mut value as *mut u32;
+    let outer = unsafe { &mut *ptr };
+    let inner = &mut *outer;
+    write(inner, 1);
+    *outer += 1;
+    unsafe { *ptr += 1 };
+    assert_eq!(value, 3);
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::check_leaks]` reports heap allocations that are never freed.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Node {
+    next: Option<Rc<RefCell<Node>>>,
+}
+
+#[kani::proof]
+#[kani::check_leaks]
+fn check_freed() {
+    let boxed = Box::new(kani::any::<u32>());
+    let v = vec![*boxed; 4];
+    assert_eq!(v.len(), 4);
+}
+
+#[kani::proof]
+#[kani::check_leaks]
+fn check_box_leak() {
+    let leaked: &'static mut u32 = Box::leak(Box::new(kani::any()));
+    *leaked = 0;
+}
+
+#[kani::proof]
+#[kani::check_leaks]
+fn check_rc_cycle() {
+    let first = Rc::new(RefCell::new(Node { next: None }));
+    let second = Rc::new(RefCell::new(Node { next: Some(first.clone()) }));
+    first.borrow_mut().next = Some(second);
+}
+
+/// Without the attribute, leaks aren't reported.
+#[kani::proof]
+fn check_box_leak_unchecked() {
+    let leaked: &'static mut u32 = Box::leak(Box::new(kani::any()));
+    *leaked = 0;
+}
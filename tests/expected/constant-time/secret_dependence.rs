@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --check-constant-time
+//! Check that branches and memory indices that depend on a secret are reported with the
+//! `constant_time` property class, and that constant-time code is accepted.
+
+const TABLE: [u8; 4] = [3, 1, 4, 1];
+
+fn leaky_eq(a: &[u8; 4], b: &[u8; 4]) -> bool {
+    for i in 0..4 {
+        if a[i] != b[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn constant_time_eq(a: &[u8; 4], b: &[u8; 4]) -> bool {
+    let mut diff = 0;
+    for i in 0..4 {
+        diff |= a[i] ^ b[i];
+    }
+    diff == 0
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_early_exit_fails() {
+    let key: [u8; 4] = kani::secret(kani::any());
+    let guess: [u8; 4] = kani::any();
+    let _ = leaky_eq(&key, &guess);
+}
+
+#[kani::proof]
+fn check_table_lookup_fails() {
+    let key: u8 = kani::secret(kani::any());
+    let _ = TABLE[(key & 3) as usize];
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_constant_time_eq() {
+    let key: [u8; 4] = kani::secret(kani::any());
+    let guess: [u8; 4] = kani::any();
+    let _ = constant_time_eq(&key, &guess);
+}
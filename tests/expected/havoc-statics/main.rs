@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::havoc_statics]` makes a harness start from an arbitrary value of the
+//! mutable statics, which exposes a bug that a fresh process doesn't trigger.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static COUNTER: AtomicU8 = AtomicU8::new(0);
+
+fn next() -> u8 {
+    COUNTER.fetch_add(1, Ordering::Relaxed);
+    let value = COUNTER.load(Ordering::Relaxed);
+    assert!(value != 0, "counter wrapped around");
+    value
+}
+
+#[kani::proof]
+fn check_fresh() {
+    assert_eq!(next(), 1);
+}
+
+#[kani::proof]
+#[kani::havoc_statics]
+fn check_havoc() {
+    next();
+}
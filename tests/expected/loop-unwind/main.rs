@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::unwind(loops = {...})]` sets the bound of individual loops, and that `"*"`
+//! sets the bound of the other ones.
+
+fn sum(data: &[u8; 16]) -> u32 {
+    let mut total = 0;
+    for byte in data {
+        total += *byte as u32;
+    }
+    total
+}
+
+fn count_set(flags: &[bool; 2]) -> usize {
+    let mut count = 0;
+    for flag in flags {
+        if *flag {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[kani::proof]
+#[kani::unwind(loops = {"crate::sum::0": 17, "*": 3})]
+fn check_loop_bounds() {
+    let data: [u8; 16] = kani::any();
+    let flags: [bool; 2] = kani::any();
+    assert!(sum(&data) <= 16 * 255);
+    assert!(count_set(&flags) <= 2);
+}
+
+#[kani::proof]
+#[kani::unwind(loops = {"crate::sum::0": 5, "*": 3})]
+fn check_loop_bound_too_small() {
+    let data: [u8; 16] = kani::any();
+    assert!(sum(&data) <= 16 * 255);
+}
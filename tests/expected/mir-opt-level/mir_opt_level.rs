@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --mir-opt-level 2
+//! Check that `--mir-opt-level 2` keeps the behavior and the checks of the inlined functions and
+//! of the branches decided by constants.
+
+struct Meters(u32);
+
+impl Meters {
+    fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+fn is_short(distance: &Meters) -> bool {
+    distance.value() < 100
+}
+
+fn read(ptr: *const u32) -> u32 {
+    unsafe { *ptr }
+}
+
+#[kani::proof]
+fn check_inlined_getter() {
+    let distance = Meters(kani::any());
+    kani::assume(distance.value() < 50);
+    assert!(is_short(&distance));
+}
+
+#[kani::proof]
+fn check_constant_branch() {
+    let mode = 2;
+    let value: u32 = kani::any();
+    let result = match mode {
+        1 => value,
+        _ => value / 2,
+    };
+    assert!(result <= value);
+    if mode == 1 {
+        kani::cover!(true, "unreachable mode");
+    }
+}
+
+#[kani::proof]
+fn check_inlined_deref_fails() {
+    let ptr = {
+        let value = 10;
+        &value as *const u32
+    };
+    assert_eq!(read(ptr), 10);
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z mir-slicing
+//! Check that `-Z mir-slicing` keeps the checks of the harness and the values they depend on.
+
+#[derive(Clone, Copy)]
+struct State {
+    counter: u32,
+    history: [u32; 4],
+}
+
+fn step(state: State, input: u32) -> State {
+    // The history is never read by any check.
+    let history = [state.history[1], state.history[2], state.history[3], input];
+    State { counter: state.counter + input, history }
+}
+
+#[kani::proof]
+fn check_dead_state() {
+    let input: u32 = kani::any();
+    kani::assume(input < 10);
+    let state = step(State { counter: 0, history: [0; 4] }, input);
+    let _unused = state.history[3] * 2;
+    assert!(state.counter < 10);
+}
+
+#[kani::proof]
+fn check_overflow_kept() {
+    let input: u32 = kani::any();
+    // The result is unused, but the overflow check must be kept.
+    let _unused = input + 1;
+}
+
+#[kani::proof]
+fn check_float_overflow_kept() {
+    let x: f64 = kani::any();
+    kani::assume(x.is_finite());
+    // The result is unused, but the float overflow check must be kept.
+    let _unused = x * 2.0;
+}
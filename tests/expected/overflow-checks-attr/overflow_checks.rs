@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::overflow_checks(off)]` only removes the overflow checks of the annotated
+//! function.
+
+/// FNV-1a, which relies on wrapping multiplication.
+#[kani::overflow_checks(off)]
+fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash = 2166136261;
+    for byte in bytes {
+        hash = (hash ^ *byte as u32) * 16777619;
+    }
+    hash
+}
+
+#[kani::overflow_checks(off)]
+fn negate(x: i8) -> i8 {
+    -x
+}
+
+fn add(x: u8, y: u8) -> u8 {
+    x + y
+}
+
+#[kani::proof]
+fn check_hash() {
+    let bytes: [u8; 2] = kani::any();
+    let _ = fnv1a(&bytes);
+    assert_eq!(negate(i8::MIN), i8::MIN);
+}
+
+/// The checks of the other functions are kept.
+#[kani::proof]
+fn check_add() {
+    let _ = add(kani::any(), kani::any());
+}
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z provenance-checks
+//! Check that integer-to-pointer casts and pointer arithmetic that leaves its allocation are
+//! reported, while the strict provenance APIs are not.
+
+#[kani::proof]
+fn check_int_to_ptr_cast_fails() {
+    let x = 10u32;
+    let addr = &x as *const u32 as usize;
+    let ptr = addr as *const u32;
+    assert_eq!(unsafe { *ptr }, 10);
+}
+
+#[kani::proof]
+fn check_offset_out_of_bounds_fails() {
+    let arr = [1u8, 2, 3, 4];
+    let ptr = arr.as_ptr();
+    let _past = unsafe { ptr.add(8) };
+}
+
+#[kani::proof]
+fn check_strict_provenance_apis() {
+    let arr = [1u8, 2, 3, 4];
+    let ptr = arr.as_ptr();
+    // Computing the pointer one past the end is allowed.
+    let end = unsafe { ptr.add(arr.len()) };
+    assert_eq!(unsafe { end.offset_from(ptr) }, 4);
+    // The address can be changed while keeping the provenance.
+    let second = ptr.with_addr(ptr.addr() + 1);
+    assert_eq!(unsafe { *second }, 2);
+    let null = 0 as *const u8;
+    assert!(null.is_null());
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::recursion_bound]` stops the recursion of a function, and reports when more
+//! nested calls are possible.
+
+/// Count the nesting depth of a sequence of `(` and `)`.
+#[kani::recursion_bound(4)]
+fn depth(input: &[u8]) -> Option<usize> {
+    match input.split_first() {
+        Some((b'(', rest)) => {
+            let inner = depth(rest)?;
+            Some(inner + 1)
+        }
+        _ => Some(0),
+    }
+}
+
+#[kani::proof]
+fn check_within_bound() {
+    let input: [u8; 3] = kani::any();
+    assert!(depth(&input).unwrap() <= 3);
+}
+
+#[kani::proof]
+fn check_exceeds_bound() {
+    let input: [u8; 8] = kani::any();
+    let _ = depth(&input);
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --shared-model
+//! Check that harnesses that share a model are verified from their own entry point.
+
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+#[kani::proof]
+fn check_double() {
+    let x: u8 = kani::any();
+    assert!(double(x) <= 510);
+}
+
+#[kani::proof]
+fn check_double_fails() {
+    let x: u8 = kani::any();
+    assert!(double(x) < 510, "double overflowed a byte");
+}
+
+#[kani::proof]
+fn check_other() {
+    let x: u8 = kani::any();
+    kani::assume(x < 10);
+    assert!(x < 10);
+}
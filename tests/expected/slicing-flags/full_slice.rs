@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --enable-unstable --full-slice
+//
+// Check that `--full-slice` and `#[kani::slicing]` don't change the verification results.
+
+fn checksum(data: &[u8; 4]) -> u8 {
+    data.iter().fold(0, |acc, byte| acc ^ byte)
+}
+
+#[kani::proof]
+fn check_checksum() {
+    let data: [u8; 4] = kani::any();
+    let unused = data[0].wrapping_add(1);
+    assert_eq!(checksum(&data), data[0] ^ data[1] ^ data[2] ^ data[3]);
+    let _ = unused;
+}
+
+#[kani::proof]
+#[kani::slicing(full = off, constant_propagation = on)]
+fn check_constant() {
+    let data = [1, 2, 3, 4];
+    assert_eq!(checksum(&data), 4);
+}
@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z taint-checks
+//! Check that secret values are reported when they flow into a public sink, either directly,
+//! through a function call, or through memory.
+
+fn mix(key: u8, nonce: u8) -> u8 {
+    key ^ nonce
+}
+
+fn log_attempt(log: &mut Vec<u8>, user: u8, password: u8) {
+    log.push(user);
+    log.push(password);
+}
+
+#[kani::proof]
+fn check_direct_flow_fails() {
+    let key: u8 = kani::secret(kani::any());
+    let derived = key.wrapping_add(1);
+    kani::public_sink(&derived);
+}
+
+#[kani::proof]
+fn check_call_flow_fails() {
+    let key: u8 = kani::secret(kani::any());
+    let output = mix(key, kani::any());
+    kani::public_sink(&output);
+}
+
+#[kani::proof]
+fn check_memory_flow_fails() {
+    let mut log = Vec::with_capacity(2);
+    log_attempt(&mut log, kani::any(), kani::secret(kani::any()));
+    kani::public_sink(log.as_slice());
+}
+
+#[kani::proof]
+fn check_public_values() {
+    let key: u8 = kani::secret(kani::any());
+    let nonce: u8 = kani::any();
+    let _ciphertext = mix(key, nonce);
+    let counter = nonce.wrapping_add(1);
+    kani::public_sink(&counter);
+    kani::public_sink(&mix(nonce, counter));
+}
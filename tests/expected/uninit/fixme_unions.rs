@@ -14,24 +14,6 @@ union U {
     b: u32,
 }
 
-/// Reading non-padding data but a union is behind a pointer.
-#[kani::proof]
-unsafe fn pointer_union_should_pass() {
-    let u = U { a: 0 }; // `u` is initialized for 2 bytes.
-    let u_ptr = addr_of!(u);
-    let u1 = *u_ptr;
-    let padding = u1.a; // Read 2 bytes from `u`.
-}
-
-/// Reading padding data but a union is behind a pointer.
-#[kani::proof]
-unsafe fn pointer_union_should_fail() {
-    let u = U { a: 0 }; // `u` is initialized for 2 bytes.
-    let u_ptr = addr_of!(u);
-    let u1 = *u_ptr;
-    let padding = u1.b; // Read 4 bytes from `u`.
-}
-
 #[repr(C)]
 struct S {
     u: U,
@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks
+
+//! Checks that Kani catches reads of uninitialized memory through raw pointers into `MaybeUninit`
+//! buffers and partially initialized arrays.
+
+use std::alloc::{alloc, Layout};
+use std::mem::MaybeUninit;
+
+#[kani::proof]
+fn check_read_uninit() {
+    let buf = MaybeUninit::<u32>::uninit();
+    let uninit = unsafe { *buf.as_ptr() }; // ~ERROR: `buf` was never written.
+}
+
+#[kani::proof]
+fn check_read_after_write() {
+    let mut buf = MaybeUninit::<u32>::uninit();
+    buf.write(42);
+    assert!(unsafe { *buf.as_ptr() } == 42);
+    assert!(unsafe { buf.assume_init() } == 42);
+}
+
+#[kani::proof]
+fn check_read_partially_init_array() {
+    let mut buf = MaybeUninit::<[u8; 4]>::uninit();
+    let ptr = buf.as_mut_ptr() as *mut u8;
+    unsafe {
+        *ptr = 1;
+        *ptr.add(1) = 2;
+        assert!(*ptr.add(1) == 2);
+        let uninit = *ptr.add(2); // ~ERROR: only the first two bytes were written.
+    }
+}
+
+#[kani::proof]
+fn check_read_partially_init_alloc() {
+    unsafe {
+        let ptr = alloc(Layout::new::<[u16; 4]>()) as *mut u16;
+        *ptr = 1;
+        assert!(*ptr == 1);
+        let uninit = *ptr.add(3); // ~ERROR: only the first element was written.
+    }
+}
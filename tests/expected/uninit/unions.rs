@@ -175,3 +175,21 @@ unsafe fn helper(u1: U, u2: U) {
     let u2 = U { b: 0 }; // `u2` is initialized for 4 bytes.
     helper(u1, u2);
 }
+
+/// Reading non-padding data but a union is behind a pointer.
+#[kani::proof]
+unsafe fn pointer_union_should_pass() {
+    let u = U { a: 0 }; // `u` is initialized for 2 bytes.
+    let u_ptr = addr_of!(u);
+    let u1 = *u_ptr;
+    let padding = u1.a; // Read 2 bytes from `u`.
+}
+
+/// Reading padding data but a union is behind a pointer.
+#[kani::proof]
+unsafe fn pointer_union_should_fail() {
+    let u = U { a: 0 }; // `u` is initialized for 2 bytes.
+    let u_ptr = addr_of!(u);
+    let u1 = *u_ptr;
+    let padding = u1.b; // Read 4 bytes from `u`.
+}
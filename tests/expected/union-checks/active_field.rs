@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z union-checks
+//! Check that reading a union field other than the one last written is only reported when the
+//! value is invalid for the type of the field.
+
+union ByteOrBool {
+    byte: u8,
+    flag: bool,
+}
+
+#[kani::proof]
+fn check_read_invalid_bool_fails() {
+    let byte: u8 = kani::any();
+    let u = ByteOrBool { byte };
+    let _flag = unsafe { u.flag };
+}
+
+#[kani::proof]
+fn check_read_valid_bool() {
+    let byte: u8 = kani::any_where(|byte| *byte <= 1);
+    let mut u = ByteOrBool { flag: true };
+    u.byte = byte;
+    let copy = u;
+    assert_eq!(unsafe { copy.flag }, byte == 1);
+}
+
+#[kani::proof]
+fn check_read_active_field() {
+    let flag: bool = kani::any();
+    let u = ByteOrBool { flag };
+    assert_eq!(unsafe { u.flag }, flag);
+    assert!(unsafe { u.byte } <= 1);
+}
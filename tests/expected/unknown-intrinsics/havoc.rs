@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --unknown-intrinsics havoc
+
+//! Check that an unsupported intrinsic is havocked and reported with `--unknown-intrinsics havoc`.
+
+#[kani::proof]
+fn check_catch_unwind() {
+    let result = std::panic::catch_unwind(|| 1);
+    assert!(result.is_ok() || result.is_err());
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani reports which loop failed its unwinding assertion, and suggests a bound for
+//! that loop only.
+
+fn sum(bound: u8) -> u32 {
+    let mut total = 0;
+    for i in 0..bound {
+        total += i as u32;
+    }
+    total
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_sum() {
+    let bound: u8 = kani::any();
+    kani::assume(bound < 5);
+    assert!(sum(bound) <= 10);
+}
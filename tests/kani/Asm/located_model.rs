@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that an `asm!` block can be modeled by its location.
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use std::arch::asm;
+
+    #[kani::asm_model(location = "Asm/located_model.rs:17")]
+    fn double_model(input: u64, output: &mut u64) {
+        *output = input.wrapping_mul(2);
+    }
+
+    fn double(input: u64) -> u64 {
+        let output: u64;
+        unsafe { asm!("lea {0}, [{1} + {1}]", out(reg) output, in(reg) input) };
+        output
+    }
+
+    #[kani::proof]
+    fn check_double() {
+        let input: u64 = kani::any();
+        assert_eq!(double(input), input.wrapping_mul(2));
+    }
+}
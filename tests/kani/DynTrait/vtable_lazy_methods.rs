@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the vtable methods are collected when their trait method is invoked via dynamic
+//! dispatch, even if the vtable is created before the invocation is reached.
+
+trait Shape {
+    fn area(&self) -> u32;
+
+    fn double_area(&self) -> u32 {
+        2 * self.area()
+    }
+
+    fn unused(&self) -> u32 {
+        unreachable!()
+    }
+}
+
+trait Named: Shape {
+    fn name(&self) -> char;
+}
+
+struct Square(u8);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 as u32 * self.0 as u32
+    }
+}
+
+impl Named for Square {
+    fn name(&self) -> char {
+        's'
+    }
+}
+
+fn make(side: u8) -> Box<dyn Named> {
+    Box::new(Square(side))
+}
+
+/// The invocations are in a function that is visited after `make`.
+fn describe(shape: &dyn Named) -> (char, u32) {
+    (shape.name(), shape.double_area())
+}
+
+#[kani::proof]
+fn check_lazy_vtable_methods() {
+    let side: u8 = kani::any();
+    kani::assume(side < 10);
+    let shape = make(side);
+    let (name, area) = describe(shape.as_ref());
+    assert_eq!(name, 's');
+    assert_eq!(area, 2 * side as u32 * side as u32);
+}
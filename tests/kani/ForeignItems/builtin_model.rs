@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that a call to a foreign function without a definition is replaced by its model.
+
+extern "C" {
+    fn checksum(data: *const u8, len: usize) -> u32;
+}
+
+#[kani::builtin_model("checksum")]
+fn checksum_model(data: *const u8, len: usize) -> u32 {
+    let bytes = unsafe { std::slice::from_raw_parts(data, len) };
+    bytes.iter().map(|byte| *byte as u32).sum()
+}
+
+#[kani::proof]
+fn check_checksum() {
+    let data: [u8; 2] = kani::any();
+    let sum = unsafe { checksum(data.as_ptr(), data.len()) };
+    assert_eq!(sum, data[0] as u32 + data[1] as u32);
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the allocations of a crate with a `#[global_allocator]` use Kani's allocation model
+//! when the crate doesn't provide a model of its allocator.
+
+use std::alloc::{GlobalAlloc, Layout};
+
+/// An allocator that can't be verified.
+struct Unverifiable;
+
+unsafe impl GlobalAlloc for Unverifiable {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        unreachable!("the global allocator shouldn't be executed")
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        unreachable!("the global allocator shouldn't be executed")
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Unverifiable = Unverifiable;
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_vec() {
+    let mut v = vec![kani::any::<u8>()];
+    v.push(10);
+    assert_eq!(v.len(), 2);
+    let b = Box::new(v[1]);
+    assert_eq!(*b, 10);
+}
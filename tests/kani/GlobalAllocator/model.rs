@@ -0,0 +1,50 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the allocations call the model of the allocator declared with
+//! `#[kani::allocator_model]` instead of the `#[global_allocator]`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A bump allocator that can't be verified efficiently.
+struct Bump;
+
+unsafe impl GlobalAlloc for Bump {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        unreachable!("the global allocator shouldn't be executed")
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: Bump = Bump;
+
+/// A model that counts the live allocations.
+struct Counting {
+    live: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.live.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.live.fetch_sub(1, Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[kani::allocator_model]
+static MODEL: Counting = Counting { live: AtomicUsize::new(0) };
+
+#[kani::proof]
+fn check_box() {
+    let b = Box::new(kani::any::<u32>());
+    assert_eq!(MODEL.live.load(Ordering::Relaxed), 1);
+    drop(b);
+    assert_eq!(MODEL.live.load(Ordering::Relaxed), 0);
+}
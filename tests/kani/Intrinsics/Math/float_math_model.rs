@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: -Z unstable-options --float-math model
+
+//! Check the properties that `--float-math model` guarantees for transcendental functions.
+
+#[kani::proof]
+fn check_sin_cos() {
+    let x: f32 = kani::any();
+    kani::assume(x.is_finite());
+    let (sin, cos) = (x.sin(), x.cos());
+    assert!(-1.0 <= sin && sin <= 1.0);
+    assert!(-1.0 <= cos && cos <= 1.0);
+    assert!(sin.abs() <= x.abs());
+    assert_eq!(0f64.cos(), 1.0);
+}
+
+#[kani::proof]
+fn check_exp_log() {
+    let x: f64 = kani::any();
+    kani::assume(x.is_finite());
+    assert!(x.exp() >= 0.0);
+    if x >= 0.0 {
+        assert!(x.exp() >= 1.0);
+        assert!(x.exp2() >= 1.0);
+    }
+    if x > 1.0 {
+        assert!(x.ln() >= 0.0);
+        assert!(x.log10() >= 0.0);
+    }
+    if x < 0.0 {
+        assert!(x.ln().is_nan());
+    }
+    assert_eq!(1f64.ln(), 0.0);
+}
+
+#[kani::proof]
+fn check_pow() {
+    let x: f32 = kani::any();
+    let y: f32 = kani::any();
+    assert_eq!(x.powf(0.0), 1.0);
+    assert_eq!(x.powi(0), 1.0);
+    if x > 0.0 && !y.is_nan() {
+        assert!(x.powf(y) >= 0.0);
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `simd_select`, `simd_masked_load`, `simd_masked_store`,
+//! `simd_gather` and `simd_scatter` are supported, and that only the lanes
+//! selected by the mask are accessed.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::*;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct ptrx4<T>(*const T, *const T, *const T, *const T);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct mut_ptrx4<T>(*mut T, *mut T, *mut T, *mut T);
+
+const MASK: i32x4 = i32x4(-1, 0, -1, 0);
+
+#[kani::proof]
+fn check_select() {
+    let a = i32x4(1, 2, 3, 4);
+    let b = i32x4(5, 6, 7, 8);
+    let res: i32x4 = unsafe { simd_select(MASK, a, b) };
+    assert_eq!(res, i32x4(1, 6, 3, 8));
+}
+
+#[kani::proof]
+fn check_masked_load_store() {
+    // Only the first three lanes are in bounds, and the mask doesn't select the last one.
+    let mut data = [10, 20, 30];
+    let res: i32x4 = unsafe { simd_masked_load(MASK, data.as_ptr(), i32x4(0, 0, 0, 0)) };
+    assert_eq!(res, i32x4(10, 0, 30, 0));
+    unsafe { simd_masked_store(MASK, data.as_mut_ptr(), i32x4(1, 2, 3, 4)) };
+    assert_eq!(data, [1, 20, 3]);
+}
+
+#[kani::proof]
+fn check_gather_scatter() {
+    let mut data = [10, 20];
+    let base = data.as_mut_ptr();
+    let null = std::ptr::null_mut();
+    let pointers = unsafe { mut_ptrx4(base.add(1), null, base, null) };
+    let const_pointers = ptrx4(pointers.0 as *const i32, null, base as *const i32, null);
+    let res: i32x4 = unsafe { simd_gather(i32x4(0, 0, 0, 0), const_pointers, MASK) };
+    assert_eq!(res, i32x4(20, 0, 10, 0));
+    unsafe { simd_scatter(i32x4(1, 2, 3, 4), pointers, MASK) };
+    assert_eq!(data, [3, 1]);
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the SIMD intrinsics `simd_saturating_add` and
+//! `simd_saturating_sub` are supported and match the scalar operations.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::{simd_saturating_add, simd_saturating_sub};
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i8x2(i8, i8);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct u8x2(u8, u8);
+
+#[kani::proof]
+fn check_saturating_signed() {
+    let a: i8 = kani::any();
+    let b: i8 = kani::any();
+    let sum: i8x2 = unsafe { simd_saturating_add(i8x2(a, b), i8x2(b, a)) };
+    assert_eq!(sum.0, a.saturating_add(b));
+    assert_eq!(sum.1, b.saturating_add(a));
+    let diff: i8x2 = unsafe { simd_saturating_sub(i8x2(a, b), i8x2(b, a)) };
+    assert_eq!(diff.0, a.saturating_sub(b));
+    assert_eq!(diff.1, b.saturating_sub(a));
+}
+
+#[kani::proof]
+fn check_saturating_unsigned() {
+    let a: u8 = kani::any();
+    let b: u8 = kani::any();
+    let sum: u8x2 = unsafe { simd_saturating_add(u8x2(a, b), u8x2(b, a)) };
+    assert_eq!(sum.0, a.saturating_add(b));
+    let diff: u8x2 = unsafe { simd_saturating_sub(u8x2(a, b), u8x2(b, a)) };
+    assert_eq!(diff.0, a.saturating_sub(b));
+    assert_eq!(diff.1, b.saturating_sub(a));
+}
@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that the horizontal reductions `simd_reduce_*` are supported and
+//! return the expected results.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::*;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct f32x2(f32, f32);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i32x2(i32, i32);
+
+#[kani::proof]
+fn check_integer_reductions() {
+    let a: u32 = kani::any();
+    let b: u32 = kani::any();
+    let v = u32x4(a, b, 1, 2);
+    unsafe {
+        assert_eq!(simd_reduce_add_ordered(v, 3), a.wrapping_add(b).wrapping_add(6));
+        assert_eq!(simd_reduce_add_unordered(v), a.wrapping_add(b).wrapping_add(3));
+        assert_eq!(simd_reduce_mul_unordered(v), a.wrapping_mul(b).wrapping_mul(2));
+        assert_eq!(simd_reduce_and(v), a & b & 1 & 2);
+        assert_eq!(simd_reduce_or(v), a | b | 3);
+        assert_eq!(simd_reduce_xor(v), a ^ b ^ 3);
+        assert_eq!(simd_reduce_max(v), a.max(b).max(2));
+        assert_eq!(simd_reduce_min(v), a.min(b).min(1));
+    }
+}
+
+#[kani::proof]
+fn check_float_reductions() {
+    let v = f32x2(1.5, -2.0);
+    unsafe {
+        assert_eq!(simd_reduce_add_ordered(v, 1.0), 0.5);
+        assert_eq!(simd_reduce_mul_unordered(v), -3.0);
+        assert_eq!(simd_reduce_max(v), 1.5);
+        assert_eq!(simd_reduce_min(f32x2(f32::NAN, -2.0)), -2.0);
+    }
+}
+
+#[kani::proof]
+fn check_mask_reductions() {
+    let a: bool = kani::any();
+    let b: bool = kani::any();
+    let mask = i32x2(if a { -1 } else { 0 }, if b { -1 } else { 0 });
+    unsafe {
+        assert_eq!(simd_reduce_all(mask), a && b);
+        assert_eq!(simd_reduce_any(mask), a || b);
+    }
+}
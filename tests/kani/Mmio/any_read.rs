@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// Test the Kani library's API to model accesses to memory-mapped device registers.
+#![no_std]
+
+const STATUS: *const u32 = 0x4000_0000 as *const u32;
+const CONTROL: *mut u32 = 0x4000_0004 as *mut u32;
+
+fn is_ready() -> bool {
+    kani::mmio::any_read(STATUS) & 1 == 1
+}
+
+#[kani::proof]
+fn check_register_may_change() {
+    let first = is_ready();
+    let second = is_ready();
+    kani::cover!(first && !second);
+    kani::cover!(!first && second);
+}
+
+#[kani::proof]
+fn check_write() {
+    let value: u32 = kani::any();
+    kani::mmio::write(CONTROL, value | 1);
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Test the restrictions of plain function pointer calls to the functions whose address is taken
+// with the same signature.
+
+// kani-flags: --enable-unstable --restrict-fn-ptrs
+
+fn double(x: u8) -> u8 {
+    x.wrapping_mul(2)
+}
+
+fn half(x: u8) -> u8 {
+    x / 2
+}
+
+fn is_zero(x: u8) -> bool {
+    x == 0
+}
+
+static OPS: [fn(u8) -> u8; 2] = [double, half];
+
+#[kani::proof]
+fn check_static_table() {
+    let idx: usize = kani::any();
+    kani::assume(idx < OPS.len());
+    let x: u8 = kani::any();
+    let res = OPS[idx](x);
+    assert!(res == x.wrapping_mul(2) || res == x / 2);
+}
+
+#[kani::proof]
+fn check_reify_and_closure() {
+    let pred: fn(u8) -> bool = if kani::any() { is_zero } else { |x| x > 10 };
+    let op: unsafe fn(u8) -> u8 = half;
+    let x: u8 = kani::any();
+    let res = unsafe { op(x) };
+    assert!(res <= 127);
+    assert!(pred(x) == (x == 0) || pred(x) == (x > 10));
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the state of the statics at the beginning of a harness annotated with
+//! `#[kani::havoc_statics]`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+static mut FLAG: bool = false;
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static CONFIG: OnceLock<u32> = OnceLock::new();
+static LIMIT: u32 = 10;
+
+#[kani::proof]
+#[kani::havoc_statics]
+fn check_mutable_statics_are_havocked() {
+    kani::cover!(unsafe { FLAG }, "static mut can be true");
+    kani::cover!(COUNT.load(Ordering::Relaxed) > 0, "atomic can be non-zero");
+    assert_eq!(LIMIT, 10, "immutable statics keep their value");
+}
+
+#[kani::proof]
+#[kani::havoc_statics]
+fn check_lazy_statics_are_uninitialized() {
+    assert!(CONFIG.get().is_none());
+    assert_eq!(*CONFIG.get_or_init(|| 5), 5);
+}
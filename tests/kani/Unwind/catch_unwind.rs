@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --panic-strategy unwind
+
+//! Check that `catch_unwind` stops a panic, and that the destructors of the frames that the panic
+//! unwinds are executed.
+
+use std::panic::catch_unwind;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+fn may_panic(fail: bool) -> u8 {
+    let _guard = Guard;
+    if fail {
+        panic!("failure");
+    }
+    10
+}
+
+#[kani::proof]
+fn check_catch_panic() {
+    let fail: bool = kani::any();
+    let result = catch_unwind(|| may_panic(fail));
+    assert_eq!(result.is_err(), fail);
+    if !fail {
+        assert_eq!(result.unwrap(), 10);
+    }
+    assert!(DROPPED.load(Ordering::SeqCst));
+}
+
+#[kani::proof]
+fn check_no_panic() {
+    let result = catch_unwind(|| 5u32);
+    assert_eq!(result.ok(), Some(5));
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --panic-strategy unwind
+// kani-verify-fail
+
+//! Check that a panic that isn't caught by `catch_unwind` still fails verification.
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+#[kani::proof]
+fn check_uncaught_panic() {
+    let _guard = Guard;
+    let fail: bool = kani::any();
+    if fail {
+        panic!("uncaught");
+    }
+}
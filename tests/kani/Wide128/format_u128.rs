@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --default-unwind 48
+
+//! Check that formatting a `u128` wider than 64 bits is verified successfully, since the
+//! software implementation of the 128-bit helpers used by `Display` is encoded natively.
+
+#[kani::proof]
+fn check_format_u128_max() {
+    let s = u128::MAX.to_string();
+    assert_eq!(s, "340282366920938463463374607431768211455");
+}
+
+#[kani::proof]
+fn check_format_i128_min() {
+    let s = i128::MIN.to_string();
+    assert_eq!(s.len(), 40);
+    assert!(s.starts_with('-'));
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A crate that relies on the contract verified in `contract_lib`.
+
+#[cfg(kani)]
+mod verify {
+    /// Only fails if `div` is replaced by its contract, whose precondition rejects a zero
+    /// divisor.
+    #[kani::proof]
+    fn check_zero_divisor() {
+        contract_lib::div(1, 0);
+    }
+}
@@ -0,0 +1,49 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that the contracts verified in a library are exported with `--export-contracts`, and
+# assumed by the crates that depend on it with `--assume-contracts`, as long as the summary is
+# signed with the same key and the library didn't change.
+
+set +e
+
+TMP_DIR="tmp_dir"
+
+rm -rf ${TMP_DIR}
+mkdir ${TMP_DIR}
+cp -r lib app ${TMP_DIR}
+pushd ${TMP_DIR} > /dev/null
+echo "first secret" > key
+echo "second secret" > other_key
+
+# Expects two arguments: "key file" "log file"
+function verify_app {
+    cargo kani --manifest-path app/Cargo.toml -Z function-contracts --assume-contracts \
+        --contracts-key "$1" > "$2" 2>&1
+    grep -E "Assuming|Ignoring|VERIFICATION:-" "$2"
+}
+
+echo "[TEST] Export the contracts of the library..."
+cargo kani --manifest-path lib/Cargo.toml -Z function-contracts --export-contracts \
+    --contracts-key key > export.log 2>&1
+grep -E "VERIFICATION:-|verified contract" export.log | sed "s#$(pwd)/##"
+grep -q "contract_lib::div" lib/kani-contracts.json && echo "Summary lists contract_lib::div"
+
+echo "[TEST] Assume the contracts of the library..."
+verify_app key assume.log
+
+echo "[TEST] Check the summary with another key..."
+verify_app other_key other_key.log
+
+echo "[TEST] Check the summary after the library changed..."
+echo "// A new comment" >> lib/src/lib.rs
+verify_app key changed.log
+
+echo "[TEST] Don't assume any contract without --assume-contracts..."
+cargo kani --manifest-path app/Cargo.toml -Z function-contracts > no_assume.log 2>&1
+grep -E "Assuming|Ignoring|VERIFICATION:-" no_assume.log
+
+# Cleanup
+popd > /dev/null
+rm -rf ${TMP_DIR}
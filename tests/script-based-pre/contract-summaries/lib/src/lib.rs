@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A library whose contract is verified and exported to the crates that depend on it.
+
+/// Unlike its contract, the implementation accepts a zero divisor, so the crates that call it
+/// with a zero divisor only fail verification if the contract is assumed.
+#[cfg_attr(kani, kani::requires(b != 0))]
+#[cfg_attr(kani, kani::ensures(|result: &u32| *result <= a))]
+pub fn div(a: u32, b: u32) -> u32 {
+    a.checked_div(b).unwrap_or(0)
+}
+
+#[cfg(kani)]
+mod verify {
+    #[kani::proof_for_contract(super::div)]
+    fn check_div() {
+        super::div(kani::any(), kani::any());
+    }
+}
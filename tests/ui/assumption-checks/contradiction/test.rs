@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z assumption-checks
+//
+// Check that `-Z assumption-checks` reports the assumptions that no execution satisfies, without
+// failing verification.
+
+#[kani::proof]
+fn check_contradiction() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    if x == 20 {
+        kani::assume(x < 5);
+        assert!(x == 7);
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that `#[kani::checks]` turns the floating-point overflow checks off for one harness only.
+
+fn add(a: f32, b: f32) -> f32 {
+    a + b
+}
+
+#[kani::proof]
+#[kani::checks(overflow = off)]
+fn check_unchecked() {
+    add(kani::any(), kani::any());
+}
+
+#[kani::proof]
+fn check_default() {
+    add(kani::any(), kani::any());
+}
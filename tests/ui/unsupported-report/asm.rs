@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the unsupported constructs are summarized at the end of the run, together with the
+//! harnesses that reach them.
+
+fn nop() {
+    unsafe {
+        std::arch::asm!("NOP");
+    }
+}
+
+#[kani::proof]
+fn reaches_asm() {
+    nop();
+}
+
+#[kani::proof]
+fn safe() {
+    assert!(1 + 1 == 2);
+}
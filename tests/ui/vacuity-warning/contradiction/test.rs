@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that Kani warns about harnesses whose assertions are all unreachable, and lists the
+// assumptions that may have pruned them.
+
+#[kani::proof]
+fn check_contradiction() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    kani::assume(x < 5);
+    assert!(x == 7);
+}
+
+#[kani::proof]
+fn check_reachable() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    assert!(x != 7);
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+//! Harnesses whose arithmetic the Why3 backend checks for overflows.
+
+#[kani::proof]
+fn check_add() {
+    let x: u8 = kani::any();
+    let y = x + 1;
+    kani::assert(y > x, "y is greater than x");
+}
+
+#[kani::proof]
+fn check_unchecked_add() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    let y = unsafe { std::intrinsics::unchecked_add(x, 100) };
+    kani::assert(y >= 100, "y is at least 100");
+}
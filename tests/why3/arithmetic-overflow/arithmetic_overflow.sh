@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check the WhyML program that the Why3 backend generates for checked and unchecked arithmetic.
+# This requires Kani to be built with the `why3` feature of the compiler.
+
+set -eu
+
+cd $(dirname $0)
+rm -rf build
+
+kani --backend why3 --only-codegen -Z unstable-options --target-dir build arithmetic.rs
+cat build/*.mlw
+
+rm -rf build
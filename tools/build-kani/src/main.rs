@@ -10,7 +10,10 @@
 mod parser;
 mod sysroot;
 
-use crate::sysroot::{build_bin, build_lib, kani_no_core_lib, kani_playback_lib, kani_sysroot_lib};
+use crate::sysroot::{
+    build_bin, build_lib, build_target_lib, endian_target_spec, kani_no_core_lib,
+    kani_playback_lib, kani_sysroot_lib,
+};
 use anyhow::{bail, Result};
 use clap::Parser;
 use std::{ffi::OsString, path::Path, process::Command};
@@ -23,6 +26,16 @@ fn main() -> Result<()> {
             let bin_folder = &build_bin(&build_parser.args)?;
             if !build_parser.skip_libs {
                 build_lib(&bin_folder)?;
+                for target in &build_parser.target {
+                    build_target_lib(&bin_folder, target)?;
+                }
+                for endianness in &build_parser.endianness {
+                    let host = env!("TARGET").to_string();
+                    for target in std::iter::once(&host).chain(&build_parser.target) {
+                        let spec = endian_target_spec(target, *endianness)?;
+                        build_target_lib(&bin_folder, &spec.to_string_lossy())?;
+                    }
+                }
             }
             Ok(())
         }
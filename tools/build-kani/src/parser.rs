@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! This file contains a small parser for our build script.
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[clap(name = "build-kani")]
@@ -21,6 +21,21 @@ pub struct BuildDevParser {
     /// libraries or the underlying Rust compiler.
     #[clap(long)]
     pub skip_libs: bool,
+    /// Also build the Kani libraries for the given target triple or custom target spec, so it can
+    /// be verified with `--target`. This option can be repeated.
+    #[clap(long, value_name = "TRIPLE|SPEC")]
+    pub target: Vec<String>,
+    /// Also build the Kani libraries for the variant of the host and of each `--target` with the
+    /// given endianness, so it can be verified with `--endianness`. This option can be repeated.
+    #[clap(long, value_enum)]
+    pub endianness: Vec<Endianness>,
+}
+
+/// The byte order of a target variant built with `--endianness`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Endianness {
+    Little,
+    Big,
 }
 
 #[derive(Args, Debug, Eq, PartialEq)]
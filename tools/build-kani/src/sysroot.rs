@@ -9,8 +9,10 @@
 //! Rustc expects the sysroot to have a specific folder layout:
 //! `{SYSROOT}/rustlib/<target-triplet>/lib/<libraries>`
 //!
-//! Note: We don't cross-compile. Target is the same as the host.
+//! The verification libraries can also be built for other targets, which are stored in
+//! `targets/<target-triplet>/lib/` with the same layout.
 
+use crate::parser::Endianness;
 use crate::{cp, AutoRun};
 use anyhow::{bail, format_err, Result};
 use cargo_metadata::{Artifact, Message};
@@ -60,6 +62,12 @@ pub fn kani_no_core_lib() -> PathBuf {
     path_buf!(kani_sysroot(), "no_core/lib")
 }
 
+/// Returns the path to where Kani and std pre-compiled libraries for a target other than the host
+/// are stored.
+pub fn kani_target_lib(target: &str) -> PathBuf {
+    path_buf!(kani_sysroot(), "targets", target, "lib")
+}
+
 /// Returns the path to where Kani's pre-compiled binaries are stored.
 fn kani_sysroot_bin() -> PathBuf {
     path_buf!(kani_sysroot(), "bin")
@@ -91,6 +99,84 @@ fn build_verification_lib(compiler_path: &Path) -> Result<()> {
     copy_artifacts(&artifacts, &kani_sysroot_lib(), true)
 }
 
+/// Build the `targets/<target>/lib/` folder used to verify the given target with `--target`.
+/// It has the same contents as the `lib/` folder, but compiled for the given target.
+/// For targets without an operating system, which have no `std`, it only contains `core`, `alloc`
+/// and Kani's libraries built with the `no_std` feature.
+///
+/// The target can also be the path of a custom target spec, e.g. `boards/my-board.json`, whose
+/// libraries are stored under its file stem, i.e., `targets/my-board/lib/`.
+pub fn build_target_lib(bin_folder: &Path, target: &str) -> Result<()> {
+    let compiler_path = bin_folder.join("kani-compiler");
+    let compiler_args = ["--kani-compiler", "-Cllvm-args=--ignore-global-asm --build-std"];
+    let (extra_args, packages) = if is_no_std_target(target)? {
+        (vec!["-Z", "build-std=core,alloc", "--features=kani/no_std"], vec!["kani", "kani_macros"])
+    } else {
+        (
+            vec!["-Z", "build-std=panic_abort,std,test", "--config", "profile.dev.panic=\"abort\""],
+            vec!["std", "kani", "kani_macros"],
+        )
+    };
+    let artifacts =
+        build_kani_lib_for(&compiler_path, target, &packages, &extra_args, &compiler_args)?;
+    let name = target_name(target);
+    copy_artifacts_for(&artifacts, &kani_target_lib(name), name, true)
+}
+
+/// Write the spec of the variant of `target` with the given endianness to
+/// `targets/<target>-<le|be>.json`, where `--endianness` looks for it, and return its path.
+/// The variant only differs from `target` by its byte order.
+pub fn endian_target_spec(target: &str, endianness: Endianness) -> Result<PathBuf> {
+    let output = Command::new("rustc")
+        .args(["-Z", "unstable-options", "--print", "target-spec-json", "--target", target])
+        .output()?;
+    if !output.status.success() {
+        bail!("Failed to print the target spec of `{target}`");
+    }
+    let mut spec: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let (endian, layout, suffix) = match endianness {
+        Endianness::Little => ("little", "e", "le"),
+        Endianness::Big => ("big", "E", "be"),
+    };
+    // The byte order of the data layout is its `e` or `E` part, which may be omitted for big
+    // endian.
+    let mut data_layout = vec![layout];
+    data_layout.extend(
+        spec["data-layout"]
+            .as_str()
+            .unwrap_or_default()
+            .split('-')
+            .filter(|part| !part.is_empty() && *part != "e" && *part != "E"),
+    );
+    spec["data-layout"] = data_layout.join("-").into();
+    spec["target-endian"] = endian.into();
+    // Only the targets that are built into rustc may set `is-builtin`.
+    spec.as_object_mut().unwrap().remove("is-builtin");
+    let path =
+        path_buf!(kani_sysroot(), "targets", format!("{}-{suffix}.json", target_name(target)));
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(&spec)?)?;
+    Ok(path)
+}
+
+/// Whether the target has no operating system, and thus no `std`, e.g. `thumbv7em-none-eabihf`.
+/// For a custom target spec, that's the case if its `os` is missing or `none`.
+fn is_no_std_target(target: &str) -> Result<bool> {
+    if target.ends_with(".json") {
+        let spec: serde_json::Value = serde_json::from_str(&fs::read_to_string(target)?)?;
+        return Ok(spec["os"].as_str().map_or(true, |os| os == "none"));
+    }
+    Ok(target.split('-').any(|component| component == "none"))
+}
+
+/// The name of a target, which is the file stem of a custom target spec.
+fn target_name(target: &str) -> &str {
+    match target.strip_suffix(".json") {
+        Some(_) => Path::new(target).file_stem().and_then(|stem| stem.to_str()).unwrap_or(target),
+        None => target,
+    }
+}
+
 /// Build the `lib-playback/` folder that will be used during counter example playback.
 /// This will include Kani's libraries compiled with `concrete-playback` feature enabled.
 fn build_playback_lib(compiler_path: &Path) -> Result<()> {
@@ -114,9 +200,18 @@ fn build_kani_lib(
     packages: &[&str],
     extra_cargo_args: &[&str],
     extra_rustc_args: &[&str],
+) -> Result<Vec<Artifact>> {
+    build_kani_lib_for(compiler_path, build_target(), packages, extra_cargo_args, extra_rustc_args)
+}
+
+fn build_kani_lib_for(
+    compiler_path: &Path,
+    target: &str,
+    packages: &[&str],
+    extra_cargo_args: &[&str],
+    extra_rustc_args: &[&str],
 ) -> Result<Vec<Artifact>> {
     // Run cargo build with -Z build-std
-    let target = build_target();
     let target_dir = env!("KANI_BUILD_LIBS");
     let args = [
         "build",
@@ -175,6 +270,16 @@ fn build_kani_lib(
 
 /// Copy all the artifacts to their correct place to generate a valid sysroot.
 fn copy_artifacts(artifacts: &[Artifact], sysroot_lib: &Path, copy_std: bool) -> Result<()> {
+    copy_artifacts_for(artifacts, sysroot_lib, build_target(), copy_std)
+}
+
+/// Copy all the artifacts to their correct place to generate a valid sysroot for `target`.
+fn copy_artifacts_for(
+    artifacts: &[Artifact],
+    sysroot_lib: &Path,
+    target: &str,
+    copy_std: bool,
+) -> Result<()> {
     // Create sysroot folder.
     sysroot_lib.exists().then(|| fs::remove_dir_all(sysroot_lib));
     fs::create_dir_all(sysroot_lib)?;
@@ -184,7 +289,7 @@ fn copy_artifacts(artifacts: &[Artifact], sysroot_lib: &Path, copy_std: bool) ->
 
     //  Copy standard libraries into rustlib/<target>/lib/ folder.
     if copy_std {
-        let std_path = path_buf!(&sysroot_lib, "rustlib", build_target(), "lib");
+        let std_path = path_buf!(&sysroot_lib, "rustlib", target, "lib");
         fs::create_dir_all(&std_path).expect(&format!("Failed to create {std_path:?}"));
         copy_libs(&artifacts, &std_path, &is_std_lib);
     }
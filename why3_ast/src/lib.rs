@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! This crate contains the representation of WhyML programs, the language of the
+//! [Why3](https://www.why3.org/) platform, and a writer that prints them in the concrete syntax
+//! expected by `why3 prove`.
+//!
+//! The representation only covers the subset of WhyML that Kani's Why3 backend generates: a
+//! [why3_program::Program] is a list of modules, each of which imports some theories of the Why3
+//! standard library and defines functions whose bodies are [why3_program::Expr] trees.
+
+pub mod why3_program;
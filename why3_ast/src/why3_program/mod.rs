@@ -0,0 +1,267 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A WhyML program.
+
+mod writer;
+
+use num_bigint::BigInt;
+
+/// A literal of a base type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Literal {
+    Bool(bool),
+    Int(BigInt),
+    Unit,
+}
+
+impl Literal {
+    pub fn int<T: Into<BigInt>>(value: T) -> Self {
+        Literal::Int(value.into())
+    }
+}
+
+/// The types of WhyML that the backend uses. Rust integers of every width are mathematical
+/// integers, whose range is checked explicitly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    Int,
+    Unit,
+}
+
+/// Unary operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// Logical negation, i.e., `not`.
+    Not,
+    /// Arithmetic negation, i.e., `-`.
+    Neg,
+}
+
+/// Binary operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// Logical and, i.e., `&&`.
+    And,
+    /// Logical or, i.e., `||`.
+    Or,
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Add,
+    Sub,
+    Mul,
+    /// Division rounded towards zero, as in Rust, from `int.ComputerDivision`.
+    Div,
+    /// Remainder of the division rounded towards zero, from `int.ComputerDivision`.
+    Mod,
+}
+
+/// A WhyML expression. WhyML doesn't distinguish statements from expressions: e.g., an assignment
+/// is an expression of type `unit`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Literal(Literal),
+    /// A variable, which is either a parameter or a mutable variable declared with
+    /// [Expr::LetRef]. Mutable variables are dereferenced implicitly.
+    Variable(String),
+    UnaryOp {
+        op: UnaryOp,
+        operand: Box<Expr>,
+    },
+    BinaryOp {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    If {
+        condition: Box<Expr>,
+        then_branch: Box<Expr>,
+        else_branch: Box<Expr>,
+    },
+    /// A sequence `e1; e2; ...; en`, whose value is the one of its last expression.
+    Sequence(Vec<Expr>),
+    /// `let ref name : typ = value in body`, which declares a mutable variable.
+    LetRef {
+        name: String,
+        typ: Type,
+        value: Box<Expr>,
+        body: Box<Expr>,
+    },
+    /// `name <- value`.
+    Assign {
+        name: String,
+        value: Box<Expr>,
+    },
+    /// `any typ ensures { post }`, an arbitrary value that satisfies `post`, in which the value is
+    /// named `result`.
+    Any {
+        typ: Type,
+        post: Option<Box<Expr>>,
+    },
+    /// `assert { [@expl:message] condition }`, which must be proved.
+    Assert {
+        condition: Box<Expr>,
+        message: Option<String>,
+    },
+    /// `assume { condition }`.
+    Assume {
+        condition: Box<Expr>,
+    },
+    /// `absurd`, i.e., code that must be unreachable.
+    Absurd,
+}
+
+impl Expr {
+    pub fn bool(value: bool) -> Expr {
+        Expr::Literal(Literal::Bool(value))
+    }
+
+    pub fn int<T: Into<BigInt>>(value: T) -> Expr {
+        Expr::Literal(Literal::int(value))
+    }
+
+    pub fn unit() -> Expr {
+        Expr::Literal(Literal::Unit)
+    }
+
+    pub fn variable(name: impl Into<String>) -> Expr {
+        Expr::Variable(name.into())
+    }
+
+    pub fn unary(op: UnaryOp, operand: Expr) -> Expr {
+        Expr::UnaryOp { op, operand: Box::new(operand) }
+    }
+
+    pub fn binary(op: BinaryOp, left: Expr, right: Expr) -> Expr {
+        Expr::BinaryOp { op, left: Box::new(left), right: Box::new(right) }
+    }
+
+    pub fn and(self, other: Expr) -> Expr {
+        Expr::binary(BinaryOp::And, self, other)
+    }
+
+    pub fn if_then_else(condition: Expr, then_branch: Expr, else_branch: Expr) -> Expr {
+        Expr::If {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch: Box::new(else_branch),
+        }
+    }
+
+    /// Build a sequence, flattening nested sequences.
+    pub fn sequence(exprs: Vec<Expr>) -> Expr {
+        let mut flat = Vec::with_capacity(exprs.len());
+        for expr in exprs {
+            match expr {
+                Expr::Sequence(inner) => flat.extend(inner),
+                expr => flat.push(expr),
+            }
+        }
+        if flat.len() == 1 { flat.pop().unwrap() } else { Expr::Sequence(flat) }
+    }
+
+    pub fn let_ref(name: impl Into<String>, typ: Type, value: Expr, body: Expr) -> Expr {
+        Expr::LetRef { name: name.into(), typ, value: Box::new(value), body: Box::new(body) }
+    }
+
+    pub fn assign(name: impl Into<String>, value: Expr) -> Expr {
+        Expr::Assign { name: name.into(), value: Box::new(value) }
+    }
+
+    pub fn any(typ: Type, post: Option<Expr>) -> Expr {
+        Expr::Any { typ, post: post.map(Box::new) }
+    }
+
+    pub fn assert(condition: Expr, message: Option<String>) -> Expr {
+        Expr::Assert { condition: Box::new(condition), message }
+    }
+
+    pub fn assume(condition: Expr) -> Expr {
+        Expr::Assume { condition: Box::new(condition) }
+    }
+}
+
+impl std::ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        Expr::unary(UnaryOp::Not, self)
+    }
+}
+
+/// A function parameter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: String,
+    pub typ: Type,
+}
+
+impl Parameter {
+    pub fn new(name: impl Into<String>, typ: Type) -> Self {
+        Parameter { name: name.into(), typ }
+    }
+}
+
+/// A function definition, i.e., `let name (params) : return_type = body`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Type,
+    pub body: Expr,
+}
+
+impl Function {
+    pub fn new(
+        name: impl Into<String>,
+        parameters: Vec<Parameter>,
+        return_type: Type,
+        body: Expr,
+    ) -> Self {
+        Function { name: name.into(), parameters, return_type, body }
+    }
+}
+
+/// A module, which imports modules of the Why3 standard library, e.g., `int.Int`, and defines
+/// functions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    pub imports: Vec<String>,
+    pub functions: Vec<Function>,
+}
+
+impl Module {
+    pub fn new(name: impl Into<String>) -> Self {
+        Module { name: name.into(), imports: vec![], functions: vec![] }
+    }
+
+    pub fn add_import(&mut self, import: impl Into<String>) {
+        self.imports.push(import.into());
+    }
+
+    pub fn add_function(&mut self, function: Function) {
+        self.functions.push(function);
+    }
+}
+
+/// A WhyML file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program {
+    pub modules: Vec<Module>,
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Program::default()
+    }
+
+    pub fn add_module(&mut self, module: Module) {
+        self.modules.push(module);
+    }
+}
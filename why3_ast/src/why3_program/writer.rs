@@ -0,0 +1,325 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A writer for WhyML programs.
+//!
+//! Compound expressions that appear as operands are always parenthesized, so the output doesn't
+//! depend on the precedence of WhyML operators. The branches of `if` are always delimited by
+//! `begin ... end`, and so is a `let` that is followed by another expression, since its body would
+//! extend as far as possible otherwise.
+//!
+//! For example, the following program:
+//!
+//! ```text
+//! module Example
+//!   use int.Int
+//!
+//!   let check_example () : unit =
+//!     let ref x : int = (any int ensures { ((0 <= result) && (result <= 255)) }) in
+//!     if (x < 10) then begin
+//!       x <- (x + 1)
+//!     end;
+//!     assert { [@expl:x is small] (x <= 10) };
+//!     ()
+//! end
+//! ```
+
+use crate::why3_program::{BinaryOp, Expr, Function, Literal, Module, Program, Type, UnaryOp};
+use std::io::Write;
+
+const INDENT: &str = "  ";
+
+impl Program {
+    /// Write the program in WhyML syntax.
+    pub fn write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        for (idx, module) in self.modules.iter().enumerate() {
+            if idx > 0 {
+                writeln!(writer)?;
+            }
+            module.write_to(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Module {
+    fn write_to<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
+        writeln!(writer, "module {}", self.name)?;
+        for import in &self.imports {
+            writeln!(writer, "{INDENT}use {import}")?;
+        }
+        for function in &self.functions {
+            writeln!(writer)?;
+            for line in function.lines() {
+                writeln!(writer, "{INDENT}{line}")?;
+            }
+        }
+        writeln!(writer, "end")
+    }
+}
+
+impl Function {
+    fn lines(&self) -> Vec<String> {
+        let parameters = if self.parameters.is_empty() {
+            "()".to_string()
+        } else {
+            self.parameters
+                .iter()
+                .map(|param| format!("({} : {})", param.name, param.typ))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut lines = vec![format!("let {} {parameters} : {} =", self.name, self.return_type)];
+        lines.extend(indent(self.body.lines()));
+        lines
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Bool => write!(f, "bool"),
+            Type::Int => write!(f, "int"),
+            Type::Unit => write!(f, "unit"),
+        }
+    }
+}
+
+impl std::fmt::Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::Bool(value) => write!(f, "{value}"),
+            // Negative literals must be parenthesized when they are an operand.
+            Literal::Int(value) if value.sign() == num_bigint::Sign::Minus => {
+                write!(f, "({value})")
+            }
+            Literal::Int(value) => write!(f, "{value}"),
+            Literal::Unit => write!(f, "()"),
+        }
+    }
+}
+
+impl UnaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOp::Not => "not ",
+            UnaryOp::Neg => "- ",
+        }
+    }
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Eq => "=",
+            BinaryOp::Neq => "<>",
+            BinaryOp::Lt => "<",
+            BinaryOp::Lte => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Gte => ">=",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "div",
+            BinaryOp::Mod => "mod",
+        }
+    }
+}
+
+impl Expr {
+    /// The expression as an operand, i.e., on a single line and parenthesized if needed, or `None`
+    /// if the expression must be written as a block.
+    fn operand(&self) -> Option<String> {
+        Some(match self {
+            Expr::Literal(literal) => literal.to_string(),
+            Expr::Variable(name) => name.clone(),
+            Expr::UnaryOp { op, operand } => format!("({}{})", op.symbol(), operand.as_operand()),
+            Expr::BinaryOp { op: op @ (BinaryOp::Div | BinaryOp::Mod), left, right } => {
+                format!("({} {} {})", op.symbol(), left.as_operand(), right.as_operand())
+            }
+            Expr::BinaryOp { op, left, right } => {
+                format!("({} {} {})", left.as_operand(), op.symbol(), right.as_operand())
+            }
+            Expr::Any { typ, post: None } => format!("(any {typ})"),
+            Expr::Any { typ, post: Some(post) } => {
+                format!("(any {typ} ensures {{ {} }})", post.as_operand())
+            }
+            _ => return None,
+        })
+    }
+
+    /// The expression as an operand, wrapping blocks in `begin ... end` on a single line.
+    fn as_operand(&self) -> String {
+        self.operand().unwrap_or_else(|| format!("begin {} end", self.lines().join(" ")))
+    }
+
+    /// Whether WhyML parses the expression greedily, so it needs to be wrapped in
+    /// `begin ... end` if another expression follows it.
+    fn is_greedy(&self) -> bool {
+        matches!(self, Expr::LetRef { .. } | Expr::Sequence(_))
+    }
+
+    /// The lines of the expression written as a block.
+    fn lines(&self) -> Vec<String> {
+        if let Some(operand) = self.operand() {
+            return vec![operand];
+        }
+        match self {
+            Expr::If { condition, then_branch, else_branch } => {
+                let mut lines = vec![format!("if {} then begin", condition.as_operand())];
+                lines.extend(indent(then_branch.lines()));
+                if **else_branch != Expr::unit() {
+                    lines.push("end else begin".to_string());
+                    lines.extend(indent(else_branch.lines()));
+                }
+                lines.push("end".to_string());
+                lines
+            }
+            Expr::Sequence(exprs) => {
+                let mut lines = vec![];
+                for (idx, expr) in exprs.iter().enumerate() {
+                    let mut expr_lines = if idx + 1 < exprs.len() && expr.is_greedy() {
+                        wrap(expr.lines())
+                    } else {
+                        expr.lines()
+                    };
+                    if idx + 1 < exprs.len() {
+                        expr_lines.last_mut().unwrap().push(';');
+                    }
+                    lines.extend(expr_lines);
+                }
+                lines
+            }
+            Expr::LetRef { name, typ, value, body } => {
+                let mut lines = block_value(format!("let ref {name} : {typ} ="), value, " in");
+                lines.extend(body.lines());
+                lines
+            }
+            Expr::Assign { name, value } => block_value(format!("{name} <-"), value, ""),
+            Expr::Assert { condition, message } => {
+                let label = message
+                    .as_ref()
+                    .map(|msg| format!("[@expl:{}] ", msg.replace(['[', ']'], "")))
+                    .unwrap_or_default();
+                vec![format!("assert {{ {label}{} }}", condition.as_operand())]
+            }
+            Expr::Assume { condition } => vec![format!("assume {{ {} }}", condition.as_operand())],
+            Expr::Absurd => vec!["absurd".to_string()],
+            Expr::Literal(_)
+            | Expr::Variable(_)
+            | Expr::UnaryOp { .. }
+            | Expr::BinaryOp { .. }
+            | Expr::Any { .. } => unreachable!("operands are handled above"),
+        }
+    }
+}
+
+/// Write `prefix value suffix`, wrapping the value in `begin ... end` if it is a block.
+fn block_value(prefix: String, value: &Expr, suffix: &str) -> Vec<String> {
+    match value.operand() {
+        Some(operand) => vec![format!("{prefix} {operand}{suffix}")],
+        None => {
+            let mut lines = vec![format!("{prefix} begin")];
+            lines.extend(indent(value.lines()));
+            lines.push(format!("end{suffix}"));
+            lines
+        }
+    }
+}
+
+fn wrap(lines: Vec<String>) -> Vec<String> {
+    let mut wrapped = vec!["begin".to_string()];
+    wrapped.extend(indent(lines));
+    wrapped.push("end".to_string());
+    wrapped
+}
+
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines.into_iter().map(|line| format!("{INDENT}{line}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::why3_program::*;
+
+    #[test]
+    fn sample_program() {
+        let x = || Expr::variable("x");
+        let result = || Expr::variable("result");
+        let range = Expr::binary(BinaryOp::Lte, Expr::int(0), result()).and(Expr::binary(
+            BinaryOp::Lte,
+            result(),
+            Expr::int(255),
+        ));
+        let body = Expr::let_ref(
+            "x",
+            Type::Int,
+            Expr::any(Type::Int, Some(range)),
+            Expr::sequence(vec![
+                Expr::if_then_else(
+                    Expr::binary(BinaryOp::Lt, x(), Expr::int(10)),
+                    Expr::assign("x", Expr::binary(BinaryOp::Add, x(), Expr::int(1))),
+                    Expr::unit(),
+                ),
+                Expr::assert(
+                    Expr::binary(BinaryOp::Lte, x(), Expr::int(10)),
+                    Some("x is small".to_string()),
+                ),
+                Expr::unit(),
+            ]),
+        );
+        let mut module = Module::new("Example");
+        module.add_import("int.Int");
+        module.add_function(Function::new("check_example", vec![], Type::Unit, body));
+        let mut program = Program::new();
+        program.add_module(module);
+
+        let mut output = Vec::new();
+        program.write_to(&mut output).unwrap();
+        let expected = String::from(
+            "\
+module Example
+  use int.Int
+
+  let check_example () : unit =
+    let ref x : int = (any int ensures { ((0 <= result) && (result <= 255)) }) in
+    if (x < 10) then begin
+      x <- (x + 1)
+    end;
+    assert { [@expl:x is small] (x <= 10) };
+    ()
+end
+",
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[test]
+    fn nested_let_in_sequence() {
+        let body = Expr::sequence(vec![
+            Expr::let_ref("y", Type::Bool, Expr::bool(true), Expr::assume(Expr::variable("y"))),
+            Expr::if_then_else(
+                Expr::bool(false),
+                Expr::Absurd,
+                Expr::binary(BinaryOp::Div, Expr::int(-7), Expr::int(2)),
+            ),
+        ]);
+        let lines = body.lines();
+        assert_eq!(
+            lines,
+            vec![
+                "begin",
+                "  let ref y : bool = true in",
+                "  assume { y }",
+                "end;",
+                "if false then begin",
+                "  absurd",
+                "end else begin",
+                "  (div (-7) 2)",
+                "end",
+            ]
+        );
+    }
+}